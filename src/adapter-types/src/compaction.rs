@@ -95,6 +95,12 @@ where
     pub base_policy: ReadPolicy<T>,
     /// Holds expressed by transactions, that should prevent compaction.
     pub holds: MutableAntichain<T>,
+    /// The read frontier of the collection when its read capability was
+    /// installed, if known.
+    ///
+    /// Times beyond this frontier were once readable, so a collection whose
+    /// read frontier has since advanced past them has had them compacted away.
+    pub initial_since: Option<Antichain<T>>,
 }
 
 impl<T: timely::progress::Timestamp> From<ReadPolicy<T>> for ReadCapability<T> {
@@ -102,6 +108,7 @@ impl<T: timely::progress::Timestamp> From<ReadPolicy<T>> for ReadCapability<T> {
         Self {
             base_policy,
             holds: MutableAntichain::new(),
+            initial_since: None,
         }
     }
 }
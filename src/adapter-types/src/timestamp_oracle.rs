@@ -26,3 +26,13 @@ pub const DEFAULT_PG_TIMESTAMP_ORACLE_CONNECT_TIMEOUT: Duration = Duration::from
 
 /// Default value for `DynamicConfig::pg_connection_pool_tcp_user_timeout`.
 pub const DEFAULT_PG_TIMESTAMP_ORACLE_TCP_USER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default value for `DynamicConfig::circuit_breaker_failure_threshold`.
+pub const DEFAULT_PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_FAILURE_THRESHOLD: usize = 5;
+
+/// Default value for `DynamicConfig::circuit_breaker_latency_threshold`.
+pub const DEFAULT_PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_LATENCY_THRESHOLD: Duration =
+    Duration::from_secs(5);
+
+/// Default value for `DynamicConfig::circuit_breaker_recovery_threshold`.
+pub const DEFAULT_PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_RECOVERY_THRESHOLD: usize = 10;
@@ -9,9 +9,11 @@
 
 //! Implementations around supporting the SUBSCRIBE protocol with the dataflow layer
 
-use std::cmp::Ordering;
+use std::cmp::{self, Ordering};
 use std::collections::BTreeSet;
 use std::iter;
+use std::sync::atomic::{self, AtomicU64};
+use std::sync::Arc;
 
 use anyhow::anyhow;
 use itertools::Itertools;
@@ -28,6 +30,7 @@ use timely::progress::Antichain;
 use tokio::sync::mpsc;
 
 use crate::coord::peek::PeekResponseUnary;
+use crate::resume_token::ResumeToken;
 use crate::{AdapterError, ExecuteContext, ExecuteResponse};
 
 #[derive(Debug)]
@@ -77,6 +80,18 @@ impl ActiveComputeSink {
                     ComputeSinkRemovalReason::DependencyDropped(d) => PeekResponseUnary::Error(
                         format!("subscribe has been terminated because underlying {d} was dropped"),
                     ),
+                    ComputeSinkRemovalReason::BufferLimitExceeded {
+                        buffered,
+                        limit,
+                        delivered_through,
+                    } => PeekResponseUnary::Error(
+                        AdapterError::SubscribeBufferLimitExceeded {
+                            buffered,
+                            limit,
+                            delivered_through,
+                        }
+                        .to_string(),
+                    ),
                 };
                 subscribe.send(message);
             }
@@ -89,6 +104,15 @@ impl ActiveComputeSink {
                             "copy has been terminated because underlying {d} was dropped"
                         )))
                     }
+                    ComputeSinkRemovalReason::BufferLimitExceeded {
+                        buffered,
+                        limit,
+                        delivered_through,
+                    } => Err(AdapterError::SubscribeBufferLimitExceeded {
+                        buffered,
+                        limit,
+                        delivered_through,
+                    }),
                 };
                 copy_to.process_response(message);
             }
@@ -110,6 +134,10 @@ pub struct ActiveSubscribe {
     pub channel: mpsc::UnboundedSender<PeekResponseUnary>,
     /// Whether progress information should be emitted.
     pub emit_progress: bool,
+    /// Whether progress information includes a token to resume from.
+    pub emit_resume_tokens: bool,
+    /// The frontier of the token the subscribe resumes from, if any.
+    pub resume_from: Option<Timestamp>,
     /// As of of subscribe
     pub as_of: Timestamp,
     /// Number of columns in the output.
@@ -122,23 +150,47 @@ pub struct ActiveSubscribe {
     pub start_time: EpochMillis,
     /// How to modify output
     pub output: SubscribeOutput,
+    /// The number of bytes of rows sent on `channel` that the client has not
+    /// yet received, shared with the receiving
+    /// [`RowBatchStream`](crate::session::RowBatchStream).
+    pub buffered_bytes: Arc<AtomicU64>,
+    /// The value of `max_subscribe_buffer_size` when the subscribe was
+    /// created.
+    pub max_buffered_bytes: u64,
+    /// The upper of the last batch sent on `channel`, i.e. all updates at
+    /// times before it have been sent.
+    pub sent_upper: Antichain<Timestamp>,
+    /// Whether the progress message at the `as_of` has been sent, which
+    /// happens with the first batch received from compute.
+    pub as_of_reported: bool,
 }
 
 impl ActiveSubscribe {
-    pub(crate) fn initialize(&self) {
-        // Always emit progress message indicating snapshot timestamp.
-        self.send_progress_message(&Antichain::from_elem(self.as_of));
-    }
-
     fn send_progress_message(&self, upper: &Antichain<Timestamp>) {
         if !self.emit_progress {
             return;
         }
         if let Some(upper) = upper.as_option() {
+            // The progress message at the `as_of` precedes the snapshot, so resuming from it
+            // would skip the snapshot. A resumed subscribe has no snapshot, and all updates before
+            // the frontier it resumes from were delivered even if its `as_of` is before it.
+            let frontier = match self.resume_from {
+                Some(resume_from) => Some(cmp::max(*upper, resume_from)),
+                None => (*upper > self.as_of).then_some(*upper),
+            };
+            let token = self
+                .emit_resume_tokens
+                .then(|| frontier.map(|frontier| ResumeToken { frontier }.encode()));
             let mut row_buf = Row::default();
             let mut packer = row_buf.packer();
             packer.push(Datum::from(numeric::Numeric::from(*upper)));
             packer.push(Datum::True);
+            if let Some(token) = &token {
+                packer.push(match token {
+                    Some(token) => Datum::String(token),
+                    None => Datum::Null,
+                });
+            }
 
             // Fill in the mz_diff or mz_state column
             packer.push(Datum::Null);
@@ -158,10 +210,40 @@ impl ActiveSubscribe {
         }
     }
 
+    /// Returns the reason to terminate the subscribe if its client has fallen
+    /// so far behind that the rows it has not yet received exceed
+    /// `max_buffered_bytes`.
+    pub(crate) fn buffer_limit_exceeded(&self) -> Option<ComputeSinkRemovalReason> {
+        let buffered = self.buffered_bytes.load(atomic::Ordering::Relaxed);
+        if buffered <= self.max_buffered_bytes {
+            return None;
+        }
+        // All updates before the upper of the last sent batch have been sent,
+        // so a new subscribe can continue after the time just before it. No
+        // batch beyond the `as_of` means the snapshot hasn't been sent yet.
+        let delivered_through = self
+            .sent_upper
+            .as_option()
+            .filter(|upper| **upper > self.as_of)
+            .map(|upper| upper.step_back().expect("beyond as_of"));
+        Some(ComputeSinkRemovalReason::BufferLimitExceeded {
+            buffered,
+            limit: self.max_buffered_bytes,
+            delivered_through,
+        })
+    }
+
     /// Process a subscribe response.
     ///
     /// Returns `true` if the subscribe is finished.
     pub(crate) fn process_response(&mut self, batch: SubscribeBatch) -> bool {
+        // Always emit a progress message indicating the snapshot timestamp before the first batch.
+        // Compute reports a batch with an upper of `as_of` while the inputs have not yet reached
+        // it, so this also tells clients that a subscribe waiting on its inputs is running.
+        if !self.as_of_reported {
+            self.send_progress_message(&Antichain::from_elem(self.as_of));
+            self.as_of_reported = true;
+        }
         let mut row_buf = Row::default();
         match batch.updates {
             Ok(mut rows) => {
@@ -337,6 +419,10 @@ impl ActiveSubscribe {
                             // complete. For regular "data" updates this
                             // is always `false`.
                             packer.push(Datum::False);
+                            if self.emit_resume_tokens {
+                                // Only progress messages carry a resume token.
+                                packer.push(Datum::Null);
+                            }
                         }
 
                         match &self.output {
@@ -359,21 +445,28 @@ impl ActiveSubscribe {
                 self.send(PeekResponseUnary::Error(text));
             }
         }
-        // Emit progress message if requested. Don't emit progress for the first batch if the upper
-        // is exactly `as_of` (we're guaranteed it is not less than `as_of`, but it might be exactly
-        // `as_of`) as we've already emitted that progress message in `initialize`.
+        // Emit progress message if requested. Don't emit progress if the upper is exactly `as_of`
+        // (we're guaranteed it is not less than `as_of`, but it might be exactly `as_of`) as we've
+        // already emitted that progress message above.
         if !batch.upper.less_equal(&self.as_of) {
             self.send_progress_message(&batch.upper);
         }
-        batch.upper.is_empty()
+        self.sent_upper = batch.upper;
+        self.sent_upper.is_empty()
     }
 
     /// Sends a message to the client if the subscribe has not already completed
     /// and if the client has not already gone away.
+    ///
+    /// There is no backpressure on the channel, but the bytes of rows that
+    /// the client has yet to receive are accounted in `buffered_bytes` and
+    /// bounded by [`ActiveSubscribe::buffer_limit_exceeded`].
     pub fn send(&self, response: PeekResponseUnary) {
-        // TODO(benesch): the lack of backpressure here can result in
-        // unbounded memory usage.
-        let _ = self.channel.send(response);
+        let bytes = response.byte_len();
+        if self.channel.send(response).is_ok() {
+            self.buffered_bytes
+                .fetch_add(bytes, atomic::Ordering::Relaxed);
+        }
     }
 }
 
@@ -387,6 +480,13 @@ pub enum ComputeSinkRemovalReason {
     /// The compute sink was forcibly terminated because an object it depended on
     /// was dropped.
     DependencyDropped(String),
+    /// The subscribe was forcibly terminated because its client fell too far
+    /// behind. See [`AdapterError::SubscribeBufferLimitExceeded`].
+    BufferLimitExceeded {
+        buffered: u64,
+        limit: u64,
+        delivered_through: Option<Timestamp>,
+    },
 }
 
 /// A description of an active copy to from coord's perspective.
@@ -15,6 +15,7 @@ use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet};
 use std::convert;
 use std::sync::Arc;
+use std::time::Instant;
 
 use futures::Future;
 use itertools::Itertools;
@@ -34,7 +35,8 @@ use mz_catalog::builtin::{
 };
 use mz_catalog::config::{ClusterReplicaSizeMap, Config, StateConfig};
 use mz_catalog::durable::{
-    test_bootstrap_args, DurableCatalogState, OpenableDurableCatalogState, StashConfig, Transaction,
+    test_bootstrap_args, CommitError, DroppedItem, DurableCatalogError, DurableCatalogState,
+    Item as DurableItem, OpenableDurableCatalogState, ReplicaIncarnation, StashConfig, Transaction,
 };
 use mz_catalog::memory::error::{AmbiguousRename, Error, ErrorKind};
 use mz_catalog::memory::objects::{
@@ -1006,6 +1008,17 @@ impl Catalog {
         self.state.try_get_role_by_name(role_name)
     }
 
+    /// Returns the comment on the item identified by `id`, as opposed to one on any of its
+    /// columns, if any.
+    pub fn get_item_comment(&self, id: &GlobalId) -> Option<&str> {
+        let comment_id = self.state.get_comment_id(ObjectId::Item(*id));
+        self.state
+            .comments
+            .get_object_comments(comment_id)
+            .and_then(|comments| comments.get(&None))
+            .map(|comment| comment.as_str())
+    }
+
     /// Creates a new schema in the `Catalog` for temporary items
     /// indicated by the TEMPORARY or TEMP keywords.
     pub fn create_temporary_schema(
@@ -1236,10 +1249,17 @@ impl Catalog {
         // The user closure was successful, apply the updates. Terminate the
         // process if this fails, because we have to restart envd due to
         // indeterminate catalog state, which we only reconcile during catalog
-        // init.
-        tx.commit()
-            .await
-            .unwrap_or_terminate("catalog storage transaction commit must succeed");
+        // init. The exception is a commit that times out, which the next commit
+        // reconciles, so that a degraded catalog backend doesn't stall the
+        // coordinator.
+        let deadline = Instant::now() + self.system_config().catalog_commit_timeout();
+        match tx.commit_with_deadline(deadline).await {
+            Ok(()) => {}
+            Err(mz_catalog::durable::CatalogError::Durable(DurableCatalogError::Commit(
+                e @ CommitError::Timeout { .. },
+            ))) => return Err(Error::new(ErrorKind::Durable(e.into())).into()),
+            Err(e) => Err(e).unwrap_or_terminate("catalog storage transaction commit must succeed"),
+        }
 
         // Dropping here keeps the mutable borrow on self, preventing us accidentally
         // mutating anything until after f is executed.
@@ -1276,6 +1296,15 @@ impl Catalog {
         tx: &mut Transaction<'_>,
         state: &mut CatalogState,
     ) -> Result<(), AdapterError> {
+        // Materialized views cannot outlive their cluster, so those of dropped clusters are
+        // dropped for good rather than soft-dropped.
+        let dropped_clusters: BTreeSet<_> = ops
+            .iter()
+            .filter_map(|op| match op {
+                Op::DropObject(ObjectId::Cluster(id)) => Some(*id),
+                _ => None,
+            })
+            .collect();
         for op in ops {
             match op {
                 Op::AlterRole {
@@ -1680,7 +1709,14 @@ impl Catalog {
                         )?;
                     }
                     let num_processes = config.location.num_processes();
-                    state.insert_cluster_replica(cluster_id, name.clone(), id, config, owner_id);
+                    state.insert_cluster_replica(
+                        cluster_id,
+                        name.clone(),
+                        id,
+                        config,
+                        owner_id,
+                        ReplicaIncarnation::default(),
+                    );
                     builtin_table_updates
                         .extend(state.pack_cluster_replica_update(cluster_id, &name, 1));
                     for process_id in 0..num_processes {
@@ -2091,7 +2127,21 @@ impl Catalog {
                                 )));
                             }
                             if !entry.item().is_temporary() {
-                                tx.remove_item(id)?;
+                                // Soft-dropped materialized views stay restorable with `UNDROP`
+                                // until their restore window lapses.
+                                if state.system_config().enable_soft_drop()
+                                    && matches!(
+                                        entry.item(),
+                                        CatalogItem::MaterializedView(mv)
+                                            if !dropped_clusters.contains(&mv.cluster_id)
+                                    )
+                                {
+                                    let dropped_at = oracle_write_ts.into();
+                                    let expires_at = state.soft_drop_expires_at(dropped_at);
+                                    tx.soft_remove_item(id, dropped_at, expires_at)?;
+                                } else {
+                                    tx.remove_item(id)?;
+                                }
                             }
 
                             builtin_table_updates.extend(state.pack_item_update(id, -1));
@@ -2117,6 +2167,75 @@ impl Catalog {
                         }
                     }
                 }
+                Op::UndropItem { id, oid } => {
+                    let Some(dropped) = tx.remove_dropped_item(id)? else {
+                        return Err(SqlCatalogError::UnknownItem(id.to_string()).into());
+                    };
+                    let DurableItem {
+                        id,
+                        schema_id,
+                        name,
+                        create_sql,
+                        owner_id,
+                        privileges,
+                    } = dropped.item;
+                    let Some(schema) = state
+                        .database_by_id
+                        .values()
+                        .find_map(|database| database.schemas_by_id.get(&schema_id))
+                    else {
+                        return Err(SqlCatalogError::UnknownSchema(schema_id.to_string()).into());
+                    };
+                    let name = QualifiedItemName {
+                        qualifiers: ItemQualifiers {
+                            database_spec: schema.database().clone(),
+                            schema_spec: schema.id().clone(),
+                        },
+                        item: name,
+                    };
+                    // Fails if the item's cluster or any of its dependencies were dropped while
+                    // it was soft-dropped.
+                    let item = state.deserialize_item(id, create_sql.clone())?;
+                    tx.insert_item(
+                        id,
+                        schema_id,
+                        &name.item,
+                        create_sql,
+                        owner_id,
+                        privileges.clone(),
+                    )?;
+
+                    if Self::should_audit_log_item(&item) {
+                        state.add_to_audit_log(
+                            oracle_write_ts,
+                            session,
+                            tx,
+                            builtin_table_updates,
+                            audit_events,
+                            EventType::Create,
+                            catalog_type_to_audit_object_type(item.typ()),
+                            EventDetails::IdFullNameV1(IdFullNameV1 {
+                                id: id.to_string(),
+                                name: Self::full_name_detail(&state.resolve_full_name(
+                                    &name,
+                                    session.map(|session| session.conn_id()),
+                                )),
+                            }),
+                        )?;
+                    }
+                    state.insert_item(
+                        id,
+                        oid,
+                        name,
+                        item,
+                        owner_id,
+                        PrivilegeMap::from_mz_acl_items(privileges),
+                    );
+                    builtin_table_updates.extend(state.pack_item_update(id, 1));
+                }
+                Op::PurgeDroppedItems(ids) => {
+                    tx.remove_dropped_items(&ids);
+                }
                 Op::GrantRole {
                     role_id,
                     member_id,
@@ -3238,6 +3357,13 @@ impl Catalog {
         *privileges = PrivilegeMap::from_mz_acl_items(flat_privileges);
     }
 
+    /// Returns the soft-dropped items that have not yet been purged from the durable catalog.
+    pub async fn dropped_items(&self) -> Result<Vec<DroppedItem>, Error> {
+        let mut storage = self.storage().await;
+        let tx = storage.transaction().await?;
+        Ok(tx.get_dropped_items().collect())
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn confirm_leadership(&self) -> Result<(), AdapterError> {
         Ok(self.storage().await.confirm_leadership().await?)
@@ -3594,6 +3720,13 @@ pub enum Op {
         comment: Option<String>,
     },
     DropObject(ObjectId),
+    /// Restores a soft-dropped item under its original id and name.
+    UndropItem {
+        id: GlobalId,
+        oid: u32,
+    },
+    /// Permanently forgets soft-dropped items whose restore window has lapsed.
+    PurgeDroppedItems(BTreeSet<GlobalId>),
     GrantRole {
         role_id: RoleId,
         member_id: RoleId,
@@ -4253,6 +4386,7 @@ mod tests {
     use std::time::{Duration, Instant};
     use std::{env, iter};
 
+    use chrono::{TimeZone, Utc};
     use itertools::Itertools;
     use tokio_postgres::types::Type;
     use tokio_postgres::NoTls;
@@ -4264,8 +4398,10 @@ mod tests {
     };
     use mz_catalog::durable::initialize::CATALOG_KIND_KEY;
     use mz_catalog::durable::objects::serialization::proto;
+    use mz_controller::clusters::{ClusterEvent, ClusterStatus};
     use mz_controller_types::{ClusterId, ReplicaId};
     use mz_expr::MirScalarExpr;
+    use mz_orchestrator::{NotReadyReason, ServiceProcessMetrics};
     use mz_ore::now::{to_datetime, NOW_ZERO, SYSTEM_TIME};
     use mz_ore::task;
     use mz_persist_client::PersistClient;
@@ -4776,6 +4912,115 @@ mod tests {
         .await;
     }
 
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+    async fn test_replica_metric_updates() {
+        Catalog::with_debug(SYSTEM_TIME.clone(), |catalog| async move {
+            let metrics = [
+                ServiceProcessMetrics {
+                    cpu_nano_cores: Some(1),
+                    memory_bytes: Some(2),
+                    disk_usage_bytes: Some(95),
+                    disk_capacity_bytes: Some(100),
+                },
+                ServiceProcessMetrics::default(),
+            ];
+            let updates =
+                catalog
+                    .state()
+                    .pack_replica_metric_updates(ReplicaId::User(1), &metrics, 1);
+            let rows: Vec<_> = updates
+                .iter()
+                .map(|update| {
+                    assert_eq!(update.diff, 1);
+                    update.row.unpack()
+                })
+                .collect();
+            assert_eq!(
+                rows,
+                vec![
+                    vec![
+                        Datum::String("u1"),
+                        Datum::UInt64(0),
+                        Datum::UInt64(1),
+                        Datum::UInt64(2),
+                        Datum::UInt64(95),
+                        Datum::UInt64(100),
+                    ],
+                    vec![
+                        Datum::String("u1"),
+                        Datum::UInt64(1),
+                        Datum::Null,
+                        Datum::Null,
+                        Datum::Null,
+                        Datum::Null,
+                    ],
+                ]
+            );
+            catalog.expire().await;
+        })
+        .await;
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+    async fn test_duplicate_replica_status_events() {
+        Catalog::with_debug(SYSTEM_TIME.clone(), |mut catalog| async move {
+            let replica = catalog
+                .clusters()
+                .flat_map(|cluster| cluster.replicas())
+                .next()
+                .expect("builtin replica");
+            let (cluster_id, replica_id) = (replica.cluster_id, replica.replica_id);
+            let event = |secs, status| ClusterEvent {
+                cluster_id,
+                replica_id,
+                process_id: 0,
+                status,
+                time: Utc.timestamp_opt(secs, 0).unwrap(),
+            };
+            let not_ready = |secs| {
+                event(
+                    secs,
+                    ClusterStatus::NotReady(Some(NotReadyReason::OomKilled)),
+                )
+            };
+
+            // Of a run of identical statuses, only the first changes the status of the process,
+            // so only it is observed by the crash-loop detection.
+            let mut changes = Vec::new();
+            for event in [
+                not_ready(0),
+                not_ready(1),
+                not_ready(2),
+                event(3, ClusterStatus::Ready),
+                event(4, ClusterStatus::Ready),
+                not_ready(5),
+            ] {
+                let replica = catalog
+                    .get_cluster(cluster_id)
+                    .replica(replica_id)
+                    .expect("replica exists");
+                if !replica.is_status_change(&event) {
+                    continue;
+                }
+                changes.push(event.time.timestamp());
+                catalog
+                    .transact(
+                        mz_repr::Timestamp::MIN,
+                        None,
+                        vec![Op::UpdateClusterReplicaStatus { event }],
+                        |_catalog| Ok(()),
+                    )
+                    .await
+                    .expect("failed to transact");
+            }
+            assert_eq!(changes, vec![0, 3, 5]);
+            catalog.expire().await;
+        })
+        .await;
+    }
+
     #[mz_ore::test(tokio::test)]
     #[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
     async fn test_get_privileges() {
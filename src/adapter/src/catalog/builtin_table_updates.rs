@@ -21,10 +21,10 @@ use mz_catalog::builtin::{
     MZ_COMMENTS, MZ_CONNECTIONS, MZ_DATABASES, MZ_DEFAULT_PRIVILEGES, MZ_EGRESS_IPS, MZ_FUNCTIONS,
     MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_INTERNAL_CLUSTER_REPLICAS, MZ_KAFKA_CONNECTIONS,
     MZ_KAFKA_SINKS, MZ_KAFKA_SOURCES, MZ_LIST_TYPES, MZ_MAP_TYPES, MZ_MATERIALIZED_VIEWS,
-    MZ_OBJECT_DEPENDENCIES, MZ_OPERATORS, MZ_POSTGRES_SOURCES, MZ_PSEUDO_TYPES, MZ_ROLES,
-    MZ_ROLE_MEMBERS, MZ_SCHEMAS, MZ_SECRETS, MZ_SESSIONS, MZ_SINKS, MZ_SOURCES,
-    MZ_SSH_TUNNEL_CONNECTIONS, MZ_STORAGE_USAGE_BY_SHARD, MZ_SUBSCRIPTIONS, MZ_SYSTEM_PRIVILEGES,
-    MZ_TABLES, MZ_TYPES, MZ_TYPE_PG_METADATA, MZ_VIEWS, MZ_WEBHOOKS_SOURCES,
+    MZ_OBJECT_DEPENDENCIES, MZ_OPERATORS, MZ_PERSIST_TXN_TABLES, MZ_PORTALS, MZ_POSTGRES_SOURCES,
+    MZ_PSEUDO_TYPES, MZ_ROLES, MZ_ROLE_MEMBERS, MZ_SCHEMAS, MZ_SECRETS, MZ_SESSIONS, MZ_SINKS,
+    MZ_SOURCES, MZ_SSH_TUNNEL_CONNECTIONS, MZ_STORAGE_USAGE_BY_SHARD, MZ_SUBSCRIPTIONS,
+    MZ_SYSTEM_PRIVILEGES, MZ_TABLES, MZ_TYPES, MZ_TYPE_PG_METADATA, MZ_VIEWS, MZ_WEBHOOKS_SOURCES,
 };
 use mz_catalog::config::AwsPrincipalContext;
 use mz_catalog::memory::error::{Error, ErrorKind};
@@ -42,6 +42,7 @@ use mz_expr::MirScalarExpr;
 use mz_orchestrator::{CpuLimit, DiskLimit, MemoryLimit, NotReadyReason, ServiceProcessMetrics};
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
+use mz_ore::now::EpochMillis;
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::jsonb::Jsonb;
 use mz_repr::adt::mz_acl_item::{AclMode, MzAclItem, PrivilegeMap};
@@ -58,6 +59,7 @@ use mz_sql_parser::ast::display::AstDisplay;
 use mz_storage_types::connections::aws::{AwsAuth, AwsConnection};
 use mz_storage_types::connections::inline::ReferencedConnection;
 use mz_storage_types::connections::{KafkaConnection, StringOrSecret};
+use mz_storage_types::controller::PersistTxnTablesImpl;
 use mz_storage_types::sinks::{KafkaSinkConnection, StorageSinkConnection};
 use mz_storage_types::sources::{
     GenericSourceConnection, KafkaSourceConnection, PostgresSourceConnection,
@@ -305,6 +307,12 @@ impl CatalogState {
             ClusterStatus::Ready => None,
             ClusterStatus::NotReady(None) => None,
             ClusterStatus::NotReady(Some(NotReadyReason::OomKilled)) => Some("oom-killed"),
+            ClusterStatus::NotReady(Some(NotReadyReason::CrashLooping { oom_killed: false })) => {
+                Some("crash-looping, backing off")
+            }
+            ClusterStatus::NotReady(Some(NotReadyReason::CrashLooping { oom_killed: true })) => {
+                Some("crash-looping, backing off: oom-killed")
+            }
         };
 
         BuiltinTableUpdate {
@@ -1380,6 +1388,7 @@ impl CatalogState {
                     cpu_nano_cores,
                     memory_bytes,
                     disk_usage_bytes,
+                    disk_capacity_bytes,
                 },
             )| {
                 Row::pack_slice(&[
@@ -1388,6 +1397,7 @@ impl CatalogState {
                     (*cpu_nano_cores).into(),
                     (*memory_bytes).into(),
                     (*disk_usage_bytes).into(),
+                    (*disk_capacity_bytes).into(),
                 ])
             },
         );
@@ -1485,6 +1495,43 @@ impl CatalogState {
         }
     }
 
+    pub fn pack_portal_update(
+        &self,
+        conn: &ConnMeta,
+        name: &str,
+        created_at: EpochMillis,
+        buffered_bytes: u64,
+        diff: Diff,
+    ) -> BuiltinTableUpdate {
+        let created_dt = mz_ore::now::to_datetime(created_at);
+        BuiltinTableUpdate {
+            id: self.resolve_builtin_table(&MZ_PORTALS),
+            row: Row::pack_slice(&[
+                Datum::UInt32(conn.conn_id().unhandled()),
+                Datum::String(name),
+                Datum::TimestampTz(created_dt.try_into().expect("must fit")),
+                Datum::UInt64(buffered_bytes),
+            ]),
+            diff,
+        }
+    }
+
+    pub fn pack_persist_txn_tables_update(
+        &self,
+        mode: PersistTxnTablesImpl,
+        previous_mode: Option<PersistTxnTablesImpl>,
+    ) -> BuiltinTableUpdate {
+        let previous_mode = previous_mode.map(|mode| mode.to_string());
+        BuiltinTableUpdate {
+            id: self.resolve_builtin_table(&MZ_PERSIST_TXN_TABLES),
+            row: Row::pack_slice(&[
+                Datum::String(&mode.to_string()),
+                Datum::from(previous_mode.as_deref()),
+            ]),
+            diff: 1,
+        }
+    }
+
     pub fn pack_default_privileges_update(
         &self,
         default_privilege_object: &DefaultPrivilegeObject,
@@ -585,6 +585,7 @@ impl Catalog {
                                         .is_retained_metrics_object
                                         .then(|| state.system_config().metrics_retention().try_into().expect("invalid metrics retention")),
                                     is_retained_metrics_object: coll.is_retained_metrics_object,
+                                    parameter_overrides: Default::default(),
                                 }),
                                 MZ_SYSTEM_ROLE_ID,
                                 PrivilegeMap::from_mz_acl_items(acl_items),
@@ -653,6 +654,7 @@ impl Catalog {
                 name,
                 config,
                 owner_id,
+                incarnation,
             } in replicas
             {
                 let logging = ReplicaLogging {
@@ -677,9 +679,17 @@ impl Catalog {
                     name: name.clone(),
                     config: config.clone().into(),
                     owner_id: owner_id.clone(),
+                    incarnation,
                 });
 
-                state.insert_cluster_replica(cluster_id, name, replica_id, config, owner_id);
+                state.insert_cluster_replica(
+                    cluster_id,
+                    name,
+                    replica_id,
+                    config,
+                    owner_id,
+                    incarnation,
+                );
             }
             txn.set_replicas(allocated_replicas)?;
 
@@ -1678,6 +1688,7 @@ fn add_new_builtin_clusters_migration(
                 mz_catalog::durable::ClusterConfig {
                     // TODO: Should builtin clusters be managed or unmanaged?
                     variant: mz_catalog::durable::ClusterVariant::Unmanaged,
+                    transaction_isolation: None,
                 },
             )?;
         }
@@ -23,7 +23,7 @@ use once_cell::sync::Lazy;
 use serde::Serialize;
 use timely::progress::Antichain;
 use tokio::sync::mpsc;
-use tracing::{info, warn};
+use tracing::{error, info, warn};
 
 use mz_audit_log::{EventDetails, EventType, ObjectType, VersionedEvent, VersionedStorageUsage};
 use mz_build_info::DUMMY_BUILD_INFO;
@@ -32,6 +32,7 @@ use mz_catalog::builtin::{
     GRANTABLE_BUILTIN_ROLE_IDS,
 };
 use mz_catalog::config::{AwsPrincipalContext, ClusterReplicaSizeMap};
+use mz_catalog::durable::ReplicaIncarnation;
 use mz_catalog::memory::error::{Error, ErrorKind};
 use mz_catalog::memory::objects::{
     CatalogEntry, CatalogItem, Cluster, ClusterConfig, ClusterReplica, ClusterReplicaProcessStatus,
@@ -85,6 +86,7 @@ use mz_storage_types::connections::ConnectionContext;
 
 // DO NOT add any more imports from `crate` outside of `crate::catalog`.
 use crate::catalog::{BuiltinTableUpdate, ConnCatalog};
+use crate::coord::timeline::TimelineLinks;
 use crate::coord::ConnMeta;
 use crate::optimize::{self, Optimize};
 use crate::session::Session;
@@ -948,6 +950,7 @@ impl CatalogState {
                     .compaction_window
                     .or(custom_logical_compaction_window),
                 is_retained_metrics_object,
+                parameter_overrides: source.parameter_overrides,
             }),
             Plan::CreateView(CreateViewPlan { view, .. }) => {
                 // Collect optimizer parameters.
@@ -1367,6 +1370,7 @@ impl CatalogState {
         replica_id: ReplicaId,
         config: ReplicaConfig,
         owner_id: RoleId,
+        incarnation: ReplicaIncarnation,
     ) {
         let replica = ClusterReplica {
             name: replica_name.clone(),
@@ -1383,6 +1387,7 @@ impl CatalogState {
                 .collect(),
             config,
             owner_id,
+            incarnation,
         };
         let cluster = self
             .clusters_by_id
@@ -1990,6 +1995,26 @@ impl CatalogState {
         &self.system_configuration
     }
 
+    /// Returns the links between timelines, as configured by `linked_timelines`.
+    pub fn timeline_links(&self) -> TimelineLinks {
+        let linked_timelines = self.system_configuration.linked_timelines();
+        linked_timelines.parse().unwrap_or_else(|e| {
+            error!("invalid `linked_timelines` config: {linked_timelines}: {e}");
+            TimelineLinks::default()
+        })
+    }
+
+    /// Returns the time at which the restore window of an item soft-dropped at `dropped_at` ends,
+    /// as configured by `soft_drop_retention`.
+    pub fn soft_drop_expires_at(&self, dropped_at: EpochMillis) -> EpochMillis {
+        let retention = self.system_configuration.soft_drop_retention();
+        let retention = u64::try_from(retention.as_millis()).unwrap_or_else(|_e| {
+            error!("absurd soft drop retention duration: {retention:?}");
+            u64::MAX
+        });
+        dropped_at.saturating_add(retention)
+    }
+
     /// Serializes the catalog's in-memory state.
     ///
     /// There are no guarantees about the format of the serialized state, except
@@ -11,7 +11,7 @@ use std::collections::BTreeMap;
 use std::fmt::{Debug, Display, Formatter};
 use std::future::Future;
 use std::pin::{self};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use anyhow::bail;
@@ -50,7 +50,9 @@ use crate::coord::{Coordinator, ExecuteContextExtra};
 use crate::error::AdapterError;
 use crate::metrics::Metrics;
 use crate::optimize::{self, Optimize};
-use crate::session::{EndTransactionAction, PreparedStatement, Session, TransactionId};
+use crate::session::{
+    CancelSignal, EndTransactionAction, PreparedStatement, Session, TransactionId,
+};
 use crate::statement_logging::StatementEndedExecutionReason;
 use crate::telemetry::{self, SegmentClientExt, StatementFailureType};
 use crate::webhook::AppendWebhookResponse;
@@ -99,6 +101,11 @@ pub struct Client {
     metrics: Metrics,
     environment_id: EnvironmentId,
     segment_client: Option<mz_segment::Client>,
+    /// The cancel signals of the active sessions, along with their secret keys.
+    ///
+    /// Cancel requests signal the canceled session directly, as the coordinator may be blocked
+    /// awaiting work on behalf of the statement they cancel.
+    cancel_signals: Arc<Mutex<BTreeMap<ConnectionIdType, (u32, CancelSignal)>>>,
 }
 
 impl Client {
@@ -126,6 +133,7 @@ impl Client {
             metrics,
             environment_id,
             segment_client,
+            cancel_signals: Arc::new(Mutex::new(BTreeMap::new())),
         }
     }
 
@@ -160,6 +168,7 @@ impl Client {
         let uuid = session.uuid();
         let application_name = session.application_name().into();
         let notice_tx = session.retain_notice_transmitter();
+        let portal_usage = session.portal_usage().clone();
 
         let (tx, rx) = oneshot::channel();
         self.send(Command::Startup {
@@ -170,12 +179,18 @@ impl Client {
             uuid,
             application_name,
             notice_tx,
+            portal_usage,
         });
 
         // When startup fails, no need to call terminate (handle_startup does this). Delay creating
         // the client until after startup to sidestep the panic in its `Drop` implementation.
         let response = rx.await.expect("sender dropped")?;
 
+        self.cancel_signals.lock().expect("lock poisoned").insert(
+            session.conn_id().unhandled(),
+            (session.secret_key(), session.cancel_signal().clone()),
+        );
+
         // Create the client as soon as startup succeeds (before any await points) so its `Drop` can
         // handle termination.
         let mut client = SessionClient {
@@ -256,6 +271,18 @@ Issue a SQL query to get started. Need help?
 
     /// Cancels the query currently running on the specified connection.
     pub fn cancel_request(&mut self, conn_id: ConnectionIdType, secret_key: u32) {
+        // Signal the session directly, in case the coordinator is blocked on the statement. Requests
+        // with a mismatched secret key are rogue, and ignored here just like by the coordinator.
+        if let Some((key, cancel_signal)) = self
+            .cancel_signals
+            .lock()
+            .expect("lock poisoned")
+            .get(&conn_id)
+        {
+            if *key == secret_key {
+                cancel_signal.cancel();
+            }
+        }
         self.send(Command::CancelRequest {
             conn_id,
             secret_key,
@@ -336,6 +363,14 @@ Issue a SQL query to get started. Need help?
             .send((OpenTelemetryContext::obtain(), cmd))
             .expect("coordinator unexpectedly gone");
     }
+
+    /// Stops cancel requests from signaling the session of the connection `conn_id`.
+    fn remove_cancel_signal(&self, conn_id: &ConnectionId) {
+        self.cancel_signals
+            .lock()
+            .expect("lock poisoned")
+            .remove(&conn_id.unhandled());
+    }
 }
 
 /// A coordinator client that is bound to a connection.
@@ -550,6 +585,15 @@ impl SessionClient {
         .await
     }
 
+    /// Resets the session, as `DISCARD ALL` does, without executing a statement.
+    ///
+    /// Fails if the session is in a transaction block.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn reset_session(&mut self) -> Result<(), AdapterError> {
+        self.send(|tx, session| Command::ResetSession { session, tx })
+            .await
+    }
+
     /// Fails a transaction.
     pub fn fail_transaction(&mut self) {
         let session = self.session.take().expect("session invariant violated");
@@ -669,6 +713,7 @@ impl SessionClient {
     /// Terminates the client session.
     pub async fn terminate(&mut self) {
         let conn_id = self.session().conn_id().clone();
+        self.inner().remove_cancel_signal(&conn_id);
         let res = self
             .send_without_session(|tx| Command::Terminate {
                 conn_id,
@@ -725,6 +770,7 @@ impl SessionClient {
         let name_hint = ApplicationNameHint::from_str(application_name);
         let (tx, mut rx) = oneshot::channel();
         let conn_id = session.conn_id().clone();
+        let cancel_signal = session.cancel_signal().clone();
         self.inner().send({
             let cmd = f(tx, session);
             // Measure the success and error rate of certain commands:
@@ -736,6 +782,7 @@ impl SessionClient {
                 Command::Startup { .. }
                 | Command::CatalogSnapshot { .. }
                 | Command::Commit { .. }
+                | Command::ResetSession { .. }
                 | Command::CancelRequest { .. }
                 | Command::PrivilegedCancelRequest { .. }
                 | Command::GetSystemVars { .. }
@@ -770,6 +817,7 @@ impl SessionClient {
                 },
                 _err = &mut cancel_future, if !cancelled => {
                     cancelled = true;
+                    cancel_signal.cancel();
                     self.inner().send(Command::PrivilegedCancelRequest {
                         conn_id: conn_id.clone(),
                     });
@@ -820,6 +868,7 @@ impl Drop for SessionClient {
             // We may not have a connection to the Coordinator if the session was
             // prematurely terminated, for example due to a timeout.
             if let Some(inner) = &self.inner {
+                inner.remove_cancel_signal(session.conn_id());
                 inner.send(Command::Terminate {
                     conn_id: session.conn_id().clone(),
                     tx: None,
@@ -36,7 +36,7 @@ use crate::coord::consistency::CoordinatorInconsistencies;
 use crate::coord::peek::PeekResponseUnary;
 use crate::coord::ExecuteContextExtra;
 use crate::error::AdapterError;
-use crate::session::{EndTransactionAction, RowBatchStream, Session};
+use crate::session::{EndTransactionAction, PortalUsage, RowBatchStream, Session};
 use crate::statement_logging::StatementEndedExecutionReason;
 use crate::util::Transmittable;
 use crate::webhook::AppendWebhookResponse;
@@ -61,6 +61,7 @@ pub enum Command {
         uuid: Uuid,
         application_name: String,
         notice_tx: mpsc::UnboundedSender<AdapterNotice>,
+        portal_usage: PortalUsage,
     },
 
     Execute {
@@ -76,6 +77,14 @@ pub enum Command {
         tx: oneshot::Sender<Response<ExecuteResponse>>,
     },
 
+    /// Resets the session, and the coordinator state of its connection, as if the connection
+    /// had just been established. Used by `DISCARD ALL`, e.g. between the clients of a
+    /// connection pooler.
+    ResetSession {
+        session: Session,
+        tx: oneshot::Sender<Response<()>>,
+    },
+
     CancelRequest {
         conn_id: ConnectionIdType,
         secret_key: u32,
@@ -127,7 +136,9 @@ pub enum Command {
 impl Command {
     pub fn session(&self) -> Option<&Session> {
         match self {
-            Command::Execute { session, .. } | Command::Commit { session, .. } => Some(session),
+            Command::Execute { session, .. }
+            | Command::Commit { session, .. }
+            | Command::ResetSession { session, .. } => Some(session),
             Command::CancelRequest { .. }
             | Command::Startup { .. }
             | Command::CatalogSnapshot { .. }
@@ -143,7 +154,9 @@ impl Command {
 
     pub fn session_mut(&mut self) -> Option<&mut Session> {
         match self {
-            Command::Execute { session, .. } | Command::Commit { session, .. } => Some(session),
+            Command::Execute { session, .. }
+            | Command::Commit { session, .. }
+            | Command::ResetSession { session, .. } => Some(session),
             Command::CancelRequest { .. }
             | Command::Startup { .. }
             | Command::CatalogSnapshot { .. }
@@ -380,6 +393,8 @@ pub enum ExecuteResponse {
         /// Session parameters that changed because the transaction ended.
         params: BTreeMap<&'static str, String>,
     },
+    /// A soft-dropped object was restored.
+    Undropped,
     /// The specified number of rows were updated in the requested table.
     Updated(usize),
     /// A connection was validated.
@@ -491,6 +506,7 @@ impl TryInto<ExecuteResponse> for ExecuteResponseKind {
             ExecuteResponseKind::Subscribing => Err(()),
             ExecuteResponseKind::TransactionCommitted => Err(()),
             ExecuteResponseKind::TransactionRolledBack => Err(()),
+            ExecuteResponseKind::Undropped => Ok(ExecuteResponse::Undropped),
             ExecuteResponseKind::Updated => Err(()),
             ExecuteResponseKind::ValidatedConnection => Ok(ExecuteResponse::ValidatedConnection),
             ExecuteResponseKind::SendingRowsImmediate => Err(()),
@@ -560,6 +576,7 @@ impl ExecuteResponse {
             Subscribing { .. } => None,
             TransactionCommitted { .. } => Some("COMMIT".into()),
             TransactionRolledBack { .. } => Some("ROLLBACK".into()),
+            Undropped => Some("UNDROP".into()),
             Updated(n) => Some(format!("UPDATE {}", n)),
             ValidatedConnection => Some("VALIDATE CONNECTION".into()),
         }
@@ -586,6 +603,7 @@ impl ExecuteResponse {
             | AlterSchemaSwap
             | AlterSecret
             | AlterConnection
+            | AlterSink
             | AlterSource
             | PurifiedAlterSource => {
                 vec![AlteredObject]
@@ -626,7 +644,8 @@ impl ExecuteResponse {
             DropOwned => vec![DroppedOwned],
             PlanKind::EmptyQuery => vec![ExecuteResponseKind::EmptyQuery],
             ExplainPlan | ExplainTimestamp | Select | ShowAllVariables | ShowCreate
-            | ShowColumns | ShowVariable | InspectShard | ExplainSinkSchema => {
+            | ShowColumns | ShowVariable | ShowTimestamp | InspectShard | InspectCollection
+            | ExplainSinkSchema => {
                 vec![
                     ExecuteResponseKind::CopyTo,
                     SendingRows,
@@ -656,6 +675,7 @@ impl ExecuteResponse {
             StartTransaction => vec![StartedTransaction],
             SideEffectingFunc => vec![SendingRows, SendingRowsImmediate],
             ValidateConnection => vec![ExecuteResponseKind::ValidatedConnection],
+            Undrop => vec![Undropped],
         }
     }
 }
@@ -73,7 +73,7 @@ use std::net::Ipv4Addr;
 use std::num::NonZeroI64;
 use std::ops::Neg;
 use std::str::FromStr;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -95,7 +95,8 @@ use mz_compute_types::dataflows::DataflowDescription;
 use mz_compute_types::plan::Plan;
 use mz_compute_types::ComputeInstanceId;
 use mz_controller::clusters::{ClusterConfig, ClusterEvent, CreateReplicaConfig};
-use mz_controller::ControllerConfig;
+use mz_controller::drops::DropBatchId;
+use mz_controller::{ControllerConfig, WatchSetId};
 use mz_controller_types::{ClusterId, ReplicaId};
 use mz_expr::{OptimizedMirRelationExpr, RowSetFinishing};
 use mz_orchestrator::ServiceProcessMetrics;
@@ -109,7 +110,7 @@ use mz_persist_client::usage::{ShardsUsageReferenced, StorageUsageClient};
 use mz_pgcopy::CopyFormatParams;
 use mz_repr::explain::{ExplainConfig, ExplainFormat};
 use mz_repr::role_id::RoleId;
-use mz_repr::{GlobalId, RelationDesc, Timestamp};
+use mz_repr::{Diff, GlobalId, RelationDesc, Row, Timestamp};
 use mz_secrets::cache::CachingSecretsReader;
 use mz_secrets::{SecretsController, SecretsReader};
 use mz_sql::ast::{CreateSubsourceStatement, Raw, Statement};
@@ -130,6 +131,7 @@ use mz_storage_types::sources::Timeline;
 use mz_timestamp_oracle::WriteTimestamp;
 use mz_transform::dataflow::DataflowMetainfo;
 use opentelemetry::trace::TraceContextExt;
+use prometheus::Histogram;
 use timely::progress::Antichain;
 use timely::PartialOrder;
 use tokio::runtime::Handle as TokioHandle;
@@ -157,7 +159,8 @@ use crate::optimize::dataflows::{
     dataflow_import_id_bundle, ComputeInstanceSnapshot, DataflowBuilder,
 };
 use crate::optimize::{self, Optimize, OptimizerConfig};
-use crate::session::{EndTransactionAction, Session};
+use crate::resume_token::ResumeToken;
+use crate::session::{CancelToken, EndTransactionAction, PortalUsage, PortalUsageEntry, Session};
 use crate::statement_logging::StatementEndedExecutionReason;
 use crate::util::{ClientTransmitter, CompletedClientTransmitter, ResultExt};
 use crate::webhook::{WebhookAppenderInvalidator, WebhookConcurrencyLimiter};
@@ -190,6 +193,20 @@ mod privatelink_status;
 mod read_policy;
 mod sequencer;
 mod sql;
+mod storage_statistics;
+mod timestamp_cache;
+mod timestamp_oracle_status;
+
+/// The interval at which `mz_internal.mz_portals` is refreshed.
+const PORTAL_USAGE_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The interval at which soft-dropped items whose restore window lapsed are purged.
+const DROPPED_ITEMS_PURGE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The interval at which the storage statistics collections are reconciled with the statistics
+/// the storage controller accumulated. Statistics are written as they are reported, so this is
+/// only a safety net.
+const STORAGE_STATISTICS_RECONCILE_INTERVAL: Duration = Duration::from_secs(10 * 60);
 
 #[derive(Debug)]
 pub enum Message<T = mz_repr::Timestamp> {
@@ -220,6 +237,12 @@ pub enum Message<T = mz_repr::Timestamp> {
     LinearizeReads,
     StorageUsageFetch,
     StorageUsageUpdate(ShardsUsageReferenced),
+    /// Refreshes `mz_internal.mz_portals` from the sessions' portal usage.
+    PortalUsageRefresh,
+    /// Purges soft-dropped items whose restore window lapsed.
+    PurgeDroppedItems,
+    /// Reconciles the storage statistics collections with the storage controller.
+    StorageStatisticsReconcile,
     RealTimeRecencyTimestamp {
         conn_id: ConnectionId,
         real_time_recency_ts: Timestamp,
@@ -266,6 +289,15 @@ pub enum Message<T = mz_repr::Timestamp> {
     },
     DrainStatementLog,
     PrivateLinkVpcEndpointEvents(Vec<VpcEndpointEvent>),
+    /// Updates to `mz_internal.mz_timestamp_oracle_status`.
+    TimestampOracleStatus(Vec<(Row, Diff)>),
+    /// Releases read holds that were dropped without being explicitly released.
+    ReleaseReadHolds(read_policy::ReadHolds<T>),
+    /// A wait returned by [`Coordinator::determine_timestamp_and_wait`] has resolved.
+    DeterminationWaitFinished {
+        conn_id: ConnectionId,
+        watch_set: WatchSetId,
+    },
 }
 
 impl Message {
@@ -277,6 +309,7 @@ impl Message {
                 Command::Startup { .. } => "command-startup",
                 Command::Execute { .. } => "command-execute",
                 Command::Commit { .. } => "command-commit",
+                Command::ResetSession { .. } => "command-reset_session",
                 Command::CancelRequest { .. } => "command-cancel_request",
                 Command::PrivilegedCancelRequest { .. } => "command-privileged_cancel_request",
                 Command::GetWebhook { .. } => "command-get_webhook",
@@ -298,6 +331,9 @@ impl Message {
             Message::LinearizeReads => "linearize_reads",
             Message::StorageUsageFetch => "storage_usage_fetch",
             Message::StorageUsageUpdate(_) => "storage_usage_update",
+            Message::PortalUsageRefresh => "portal_usage_refresh",
+            Message::PurgeDroppedItems => "purge_dropped_items",
+            Message::StorageStatisticsReconcile => "storage_statistics_reconcile",
             Message::RealTimeRecencyTimestamp { .. } => "real_time_recency_timestamp",
             Message::RetireExecute { .. } => "retire_execute",
             Message::ExecuteSingleStatementTransaction { .. } => {
@@ -313,6 +349,9 @@ impl Message {
             Message::DrainStatementLog => "drain_statement_log",
             Message::AlterConnectionValidationReady(..) => "alter_connection_validation_ready",
             Message::PrivateLinkVpcEndpointEvents(_) => "private_link_vpc_endpoint_events",
+            Message::TimestampOracleStatus(_) => "timestamp_oracle_status",
+            Message::ReleaseReadHolds(_) => "release_read_holds",
+            Message::DeterminationWaitFinished { .. } => "determination_wait_finished",
         }
     }
 }
@@ -470,6 +509,9 @@ pub struct PeekStageTimestampReadHold {
     timeline_context: TimelineContext,
     oracle_read_ts: Option<Timestamp>,
     real_time_recency_ts: Option<mz_repr::Timestamp>,
+    /// The time until which to wait for the target replica to report introspection data, once
+    /// the peek has started waiting.
+    introspection_wait_deadline: Option<Instant>,
     optimizer: Either<optimize::peek::Optimizer, optimize::copy_to::Optimizer>,
     /// An optional context set iff the state machine is initiated from
     /// sequencing an EXPALIN for this statement.
@@ -659,6 +701,8 @@ pub struct SubscribeOptimizeMir {
     validity: PlanValidity,
     plan: plan::SubscribePlan,
     timeline: TimelineContext,
+    /// The token the subscribe resumes from, if any.
+    resume_from: Option<ResumeToken>,
 }
 
 #[derive(Debug)]
@@ -666,6 +710,7 @@ pub struct SubscribeTimestampOptimizeLir {
     validity: PlanValidity,
     plan: plan::SubscribePlan,
     timeline: TimelineContext,
+    resume_from: Option<ResumeToken>,
     optimizer: optimize::subscribe::Optimizer,
     global_mir_plan: optimize::subscribe::GlobalMirPlan<optimize::subscribe::Unresolved>,
 }
@@ -675,6 +720,7 @@ pub struct SubscribeFinish {
     validity: PlanValidity,
     cluster_id: ComputeInstanceId,
     plan: plan::SubscribePlan,
+    resume_from: Option<ResumeToken>,
     global_lir_plan: optimize::subscribe::GlobalLirPlan,
 }
 
@@ -858,9 +904,20 @@ pub struct ConnMeta {
     /// any, is cleared.
     drop_sinks: BTreeSet<GlobalId>,
 
+    /// Watch sets installed to wait for a timestamp determination to become readable, until
+    /// they resolve. They are cancelled when the connection is cleared, so that waits of
+    /// disconnected clients do not linger in the controller.
+    determination_waits: BTreeSet<WatchSetId>,
+
     /// Channel on which to send notices to a session.
     notice_tx: mpsc::UnboundedSender<AdapterNotice>,
 
+    /// Accounting of the results buffered by the session's open portals.
+    portal_usage: PortalUsage,
+    /// The portals of this connection most recently reported in
+    /// `mz_internal.mz_portals`, keyed by portal name.
+    reported_portals: BTreeMap<String, PortalUsageEntry>,
+
     /// The role that initiated the database context. Fixed for the duration of the connection.
     /// WARNING: This role reference is not updated when the role is dropped.
     /// Consumers should not assume that this role exist.
@@ -1107,6 +1164,9 @@ pub struct ExecuteContext {
     internal_cmd_tx: mpsc::UnboundedSender<Message>,
     session: Session,
     extra: ExecuteContextExtra,
+    /// Observes the cancellation of the statement, for awaits that would
+    /// otherwise delay its cancellation.
+    cancel_token: CancelToken,
 }
 
 impl ExecuteContext {
@@ -1118,6 +1178,10 @@ impl ExecuteContext {
         &mut self.session
     }
 
+    pub fn cancel_token(&self) -> &CancelToken {
+        &self.cancel_token
+    }
+
     pub fn tx(&self) -> &ClientTransmitter<ExecuteResponse> {
         &self.tx
     }
@@ -1132,11 +1196,13 @@ impl ExecuteContext {
         session: Session,
         extra: ExecuteContextExtra,
     ) -> Self {
+        let cancel_token = session.cancel_signal().token();
         Self {
             tx,
             session,
             extra,
             internal_cmd_tx,
+            cancel_token,
         }
     }
 
@@ -1161,6 +1227,7 @@ impl ExecuteContext {
             internal_cmd_tx,
             session,
             extra,
+            cancel_token: _,
         } = self;
         (tx, internal_cmd_tx, session, extra)
     }
@@ -1173,6 +1240,7 @@ impl ExecuteContext {
             internal_cmd_tx,
             session,
             extra,
+            cancel_token: _,
         } = self;
         let reason = if extra.is_trivial() {
             None
@@ -1191,6 +1259,16 @@ impl ExecuteContext {
         }
     }
 
+    /// Retires the execution with [`AdapterError::Canceled`], and records
+    /// the time since the statement was canceled, if it was canceled through
+    /// its [`CancelToken`].
+    pub(crate) fn retire_canceled(self, cancel_to_retire_seconds: &Histogram) {
+        if let Some(canceled_at) = self.cancel_token.canceled_at() {
+            cancel_to_retire_seconds.observe(canceled_at.elapsed().as_secs_f64());
+        }
+        self.retire(Err(AdapterError::Canceled));
+    }
+
     pub fn extra(&self) -> &ExecuteContextExtra {
         &self.extra
     }
@@ -1259,7 +1337,10 @@ pub struct Coordinator {
     /// in `self.read_capability[id]`, using the `release_read_holds` method.
     ///
     /// We use a Vec because `ReadHolds` doesn't have a way of tracking multiplicity.
-    txn_read_holds: BTreeMap<ConnectionId, Vec<read_policy::ReadHolds<Timestamp>>>,
+    txn_read_holds: BTreeMap<ConnectionId, Vec<read_policy::AcquiredReadHolds>>,
+
+    /// Timestamp determinations made since the write frontiers of collections last moved.
+    timestamp_determinations: timestamp_cache::TimestampDeterminationCache,
 
     /// Access to the peek fields should be restricted to methods in the [`peek`] API.
     /// A map from pending peek ids to the queue into which responses are sent, and
@@ -1296,6 +1377,25 @@ pub struct Coordinator {
     /// it manually.
     advance_timelines_interval: tokio::time::Interval,
 
+    /// The interval at which `mz_internal.mz_portals` is refreshed.
+    portal_usage_interval: tokio::time::Interval,
+
+    /// The interval at which soft-dropped items whose restore window lapsed are purged.
+    dropped_items_purge_interval: tokio::time::Interval,
+
+    /// The interval at which the storage statistics collections are reconciled.
+    storage_statistics_reconcile_interval: tokio::time::Interval,
+    /// The statistics written to the storage statistics collections.
+    storage_statistics: storage_statistics::StorageStatistics,
+
+    /// The names of catalog items, by which the controller identifies collections in logs and
+    /// errors.
+    collection_names: Arc<RwLock<BTreeMap<GlobalId, String>>>,
+
+    /// Batches of dropped collections that are not gone from the controllers yet, and when they
+    /// were dropped.
+    pending_collection_drops: BTreeMap<DropBatchId, Instant>,
+
     /// Handle to secret manager that can create and delete secrets from
     /// an arbitrary secret storage engine.
     secrets_controller: Arc<dyn SecretsController>,
@@ -1360,7 +1460,7 @@ impl Coordinator {
         // Inform the controllers about their initial configuration.
         let system_config = self.catalog().system_config();
         let compute_config = flags::compute_config(system_config);
-        let storage_config = flags::storage_config(system_config);
+        let storage_config = self.storage_config();
         let scheduling_config = flags::orchestrator_scheduling_config(system_config);
         let merge_effort = system_config.default_idle_arrangement_merge_effort();
         let exert_prop = system_config.default_arrangement_exert_proportionality();
@@ -1375,6 +1475,13 @@ impl Coordinator {
             .set_default_arrangement_exert_proportionality(exert_prop);
         self.controller
             .set_enable_compute_aggressive_readhold_downgrades(aggressive_downgrades);
+        self.update_replica_disk_high_water_mark();
+        self.update_frontier_divergence_threshold();
+        self.register_collection_names();
+        self.update_epoch_millis_collections();
+        let item_ids: Vec<_> = self.catalog().entries().map(|entry| entry.id()).collect();
+        self.update_collection_names(item_ids.iter().copied());
+        self.update_status_attributions(item_ids);
 
         let mut policies_to_set: BTreeMap<CompactionWindow, CollectionIdBundle> =
             Default::default();
@@ -1755,6 +1862,12 @@ impl Coordinator {
         // Expose mapping from T-shirt sizes to actual sizes
         builtin_table_updates.extend(self.catalog().state().pack_all_replica_size_updates());
 
+        // Expose the persist-txn tables mode, so operators can verify migrations.
+        builtin_table_updates.push(self.catalog().state().pack_persist_txn_tables_update(
+            self.controller.persist_txn_tables(),
+            self.controller.previous_persist_txn_tables(),
+        ));
+
         // Advance all tables to the current timestamp
         debug!("coordinator init: advancing all tables to current timestamp");
         let WriteTimestamp {
@@ -1886,6 +1999,12 @@ impl Coordinator {
             .unwrap_or_terminate("init_txns");
         self.apply_local_write(init_ts).await;
 
+        let dropped_items = self
+            .catalog()
+            .dropped_items()
+            .await
+            .unwrap_or_terminate("cannot fail to read dropped items");
+
         let catalog = self.catalog();
         let source_status_collection_id = catalog
             .resolve_builtin_storage_collection(&mz_catalog::builtin::MZ_SOURCE_STATUS_HISTORY);
@@ -1922,7 +2041,7 @@ impl Coordinator {
             }
         };
 
-        let collections: Vec<_> = catalog
+        let mut collections: Vec<_> = catalog
             .entries()
             .filter_map(|entry| {
                 let id = entry.id();
@@ -1949,6 +2068,37 @@ impl Coordinator {
             })
             .collect();
 
+        // The collections of soft-dropped materialized views outlive restarts until their restore
+        // window lapses, so that they can still be undropped.
+        let mut retained_drops = Vec::new();
+        for dropped in dropped_items {
+            let id = dropped.item.id;
+            let mv = match catalog
+                .state()
+                .deserialize_item(id, dropped.item.create_sql)
+            {
+                Ok(CatalogItem::MaterializedView(mv)) => mv,
+                Ok(item) => {
+                    warn!("unexpected soft-dropped {} {id}", item.typ());
+                    continue;
+                }
+                // The item's cluster or one of its dependencies was dropped in the meantime, so
+                // the item can no longer be restored.
+                Err(e) => {
+                    warn!("cannot retain soft-dropped item {id}: {e}");
+                    continue;
+                }
+            };
+            let collection_desc = CollectionDescription {
+                desc: mv.desc,
+                data_source: DataSource::Other(DataSourceOther::Compute),
+                since: None,
+                status_collection_id: None,
+            };
+            collections.push((id, collection_desc));
+            retained_drops.push((mv.cluster_id, id, dropped.expires_at));
+        }
+
         self.controller
             .storage
             .migrate_collections(collections.clone())
@@ -1964,6 +2114,13 @@ impl Coordinator {
             .unwrap_or_terminate("cannot fail to create collections");
 
         self.apply_local_write(register_ts).await;
+
+        // Retained collections whose restore window already lapsed are dropped by the next
+        // purge of dropped items.
+        for (cluster_id, id, expires_at) in retained_drops {
+            self.controller
+                .retain_dropped_collection(cluster_id, id, expires_at);
+        }
     }
 
     /// Invokes the optimizer on all indexes and materialized views in the catalog and inserts the
@@ -2403,6 +2560,7 @@ impl Coordinator {
 
             self.schedule_storage_usage_collection().await;
             self.spawn_privatelink_vpc_endpoints_watch_task();
+            self.spawn_timestamp_oracle_status_task();
             self.spawn_statement_logging_task();
             flags::tracing_config(self.catalog.system_config()).apply(&self.tracing_handle);
 
@@ -2491,6 +2649,17 @@ impl Coordinator {
                         span.follows_from(Span::current());
                         Message::GroupCommitInitiate(span, None)
                     },
+                    // `tick()` on `Interval` is cancel-safe:
+                    // https://docs.rs/tokio/1.19.2/tokio/time/struct.Interval.html#cancel-safety
+                    _ = self.portal_usage_interval.tick() => Message::PortalUsageRefresh,
+                    // `tick()` on `Interval` is cancel-safe:
+                    // https://docs.rs/tokio/1.19.2/tokio/time/struct.Interval.html#cancel-safety
+                    _ = self.dropped_items_purge_interval.tick() => Message::PurgeDroppedItems,
+                    // `tick()` on `Interval` is cancel-safe:
+                    // https://docs.rs/tokio/1.19.2/tokio/time/struct.Interval.html#cancel-safety
+                    _ = self.storage_statistics_reconcile_interval.tick() => {
+                        Message::StorageStatisticsReconcile
+                    }
 
                     // Process the idle metric at the lowest priority to sample queue non-idle time.
                     // `recv()` on `Receiver` is cancellation safe:
@@ -2881,6 +3050,15 @@ pub fn serve(
         let segment_client_clone = segment_client.clone();
         let coord_now = now.clone();
         let advance_timelines_interval = tokio::time::interval(catalog.config().timestamp_interval);
+        let mut portal_usage_interval = tokio::time::interval(PORTAL_USAGE_REFRESH_INTERVAL);
+        portal_usage_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut dropped_items_purge_interval = tokio::time::interval(DROPPED_ITEMS_PURGE_INTERVAL);
+        dropped_items_purge_interval
+            .set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut storage_statistics_reconcile_interval =
+            tokio::time::interval(STORAGE_STATISTICS_RECONCILE_INTERVAL);
+        storage_statistics_reconcile_interval
+            .set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
         // We get the timestamp oracle impl once on startup, to ensure that it
         // doesn't change in between when the system var changes: all oracles must
@@ -2930,6 +3108,16 @@ pub fn serve(
                     )
                     .boxed(),
                 );
+                let controller = match controller {
+                    Ok(controller) => controller,
+                    Err(e) => {
+                        drop(span);
+                        bootstrap_tx
+                            .send(Err(AdapterError::Storage(e)))
+                            .expect("bootstrap_rx is not dropped until it receives this message");
+                        return;
+                    }
+                };
 
                 let caching_secrets_reader = CachingSecretsReader::new(secrets_controller.reader());
                 let mut coord = Coordinator {
@@ -2944,6 +3132,9 @@ pub fn serve(
                     storage_read_capabilities: Default::default(),
                     compute_read_capabilities: Default::default(),
                     txn_read_holds: Default::default(),
+                    timestamp_determinations: timestamp_cache::TimestampDeterminationCache::new(
+                        timestamp_cache::DEFAULT_TIMESTAMP_CACHE_CAPACITY,
+                    ),
                     pending_peeks: BTreeMap::new(),
                     client_pending_peeks: BTreeMap::new(),
                     pending_real_time_recency_timestamp: BTreeMap::new(),
@@ -2954,6 +3145,12 @@ pub fn serve(
                     write_lock_wait_group: VecDeque::new(),
                     pending_writes: Vec::new(),
                     advance_timelines_interval,
+                    portal_usage_interval,
+                    dropped_items_purge_interval,
+                    storage_statistics_reconcile_interval,
+                    storage_statistics: Default::default(),
+                    collection_names: Default::default(),
+                    pending_collection_drops: BTreeMap::new(),
                     secrets_controller,
                     caching_secrets_reader,
                     cloud_resource_controller,
@@ -15,9 +15,10 @@ use std::time::Duration;
 
 use derivative::Derivative;
 use futures::future::{BoxFuture, FutureExt};
+use mz_ore::cast::CastLossy;
 use mz_ore::metrics::MetricsFutureExt;
-use mz_ore::task;
 use mz_ore::vec::VecExt;
+use mz_ore::{soft_assert_or_log, task};
 use mz_repr::{Diff, GlobalId, Row, Timestamp};
 use mz_sql::plan::Plan;
 use mz_storage_client::client::TimestamplessUpdate;
@@ -158,7 +159,11 @@ impl Coordinator {
     /// writes.
     #[instrument(level = "debug", skip(self))]
     pub(crate) async fn try_group_commit(&mut self, permit: Option<GroupCommitPermit>) {
-        let timestamp = self.peek_local_write_ts().await;
+        // Don't block the coordinator on an unavailable timestamp oracle. Pending writes stay
+        // queued, and the next periodic group commit retries them.
+        let Ok(timestamp) = self.try_peek_local_write_ts().await else {
+            return;
+        };
         let now = Timestamp::from((self.catalog().config().now)());
 
         // HACK: This is a special case to allow writes to the mz_sessions table to proceed even
@@ -268,7 +273,15 @@ impl Coordinator {
         let WriteTimestamp {
             timestamp,
             advance_to,
-        } = self.get_local_write_ts().await;
+        } = match self.try_get_local_write_ts().await {
+            Ok(write_ts) => write_ts,
+            Err(_) => {
+                // The timestamp oracle became unavailable. Requeue the writes, and let the next
+                // periodic group commit retry them.
+                self.pending_writes.extend(pending_writes);
+                return;
+            }
+        };
 
         // While we're flipping on the feature flags for persist-txn tables and
         // the separated Postgres timestamp oracle, we also need to confirm
@@ -301,7 +314,18 @@ impl Coordinator {
                             action,
                         },
                 } => {
-                    for WriteOp { id, rows } in writes {
+                    for WriteOp { id, rows, read_ts } in writes {
+                        if let Some(read_ts) = read_ts {
+                            soft_assert_or_log!(
+                                timestamp > read_ts,
+                                "write timestamp {timestamp} not beyond read timestamp {read_ts}"
+                            );
+                            self.metrics
+                                .read_then_write_timestamp_gap_ms
+                                .observe(f64::cast_lossy(u64::from(
+                                    timestamp.saturating_sub(read_ts),
+                                )));
+                        }
                         // If the table that some write was targeting has been deleted while the
                         // write was waiting, then the write will be ignored and we respond to the
                         // client that the write was successful. This is only possible if the write
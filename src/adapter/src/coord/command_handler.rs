@@ -47,7 +47,7 @@ use mz_sql_parser::ast::{
 use mz_storage_types::sources::Timeline;
 use opentelemetry::trace::TraceContextExt;
 use tokio::sync::{mpsc, oneshot};
-use tracing::{debug_span, instrument, warn, Instrument};
+use tracing::{debug, debug_span, instrument, warn, Instrument};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use crate::command::{
@@ -57,7 +57,7 @@ use crate::coord::appends::{Deferred, PendingWriteTxn};
 use crate::coord::{ConnMeta, Coordinator, Message, PendingTxn, PurifiedStatementReady};
 use crate::error::AdapterError;
 use crate::notice::AdapterNotice;
-use crate::session::{Session, TransactionOps, TransactionStatus};
+use crate::session::{PortalUsage, Session, TransactionOps, TransactionStatus};
 use crate::util::{ClientTransmitter, ResultExt};
 use crate::webhook::{
     AppendWebhookResponse, AppendWebhookValidator, WebhookAppender, WebhookAppenderInvalidator,
@@ -84,6 +84,7 @@ impl Coordinator {
                     uuid,
                     application_name,
                     notice_tx,
+                    portal_usage,
                 } => {
                     // Note: We purposefully do not use a ClientTransmitter here because startup
                     // handles errors and cleanup of sessions itself.
@@ -95,6 +96,7 @@ impl Coordinator {
                         uuid,
                         application_name,
                         notice_tx,
+                        portal_usage,
                     )
                     .await;
                 }
@@ -208,6 +210,12 @@ impl Coordinator {
                         .await;
                 }
 
+                Command::ResetSession { mut session, tx } => {
+                    let tx = ClientTransmitter::new(tx, self.internal_cmd_tx.clone());
+                    let result = self.reset_session(&mut session).await;
+                    tx.send(result, session);
+                }
+
                 Command::CatalogSnapshot { tx } => {
                     let _ = tx.send(CatalogSnapshot {
                         catalog: self.owned_catalog(),
@@ -223,7 +231,7 @@ impl Coordinator {
         .boxed_local()
     }
 
-    #[tracing::instrument(level = "debug", skip(self, tx, secret_key, notice_tx))]
+    #[tracing::instrument(level = "debug", skip(self, tx, secret_key, notice_tx, portal_usage))]
     async fn handle_startup(
         &mut self,
         tx: oneshot::Sender<Result<StartupResponse, AdapterError>>,
@@ -233,6 +241,7 @@ impl Coordinator {
         uuid: uuid::Uuid,
         application_name: String,
         notice_tx: mpsc::UnboundedSender<AdapterNotice>,
+        portal_usage: PortalUsage,
     ) {
         // Early return if successful, otherwise cleanup any possible state.
         match self.handle_startup_inner(&user, &conn_id).await {
@@ -272,7 +281,10 @@ impl Coordinator {
                 let conn = ConnMeta {
                     secret_key,
                     notice_tx,
+                    portal_usage,
+                    reported_portals: BTreeMap::new(),
                     drop_sinks: BTreeSet::new(),
+                    determination_waits: BTreeSet::new(),
                     connected_at: self.now(),
                     user,
                     application_name,
@@ -579,7 +591,8 @@ impl Coordinator {
                     | Statement::RevokeRole(_)
                     | Statement::Update(_)
                     | Statement::ValidateConnection(_)
-                    | Statement::Comment(_) => {
+                    | Statement::Comment(_)
+                    | Statement::Undrop(_) => {
                         let txn_status = ctx.session_mut().transaction_mut();
 
                         // If we're not in an implicit transaction and we could generate exactly one
@@ -925,7 +938,14 @@ impl Coordinator {
         }
 
         if let Some(ctx) = maybe_ctx {
-            ctx.retire(Err(AdapterError::Canceled));
+            ctx.retire_canceled(&self.metrics.cancel_to_retire_seconds);
+        }
+
+        // Cancel waits for timestamp determinations, which resolves them early.
+        if let Some(conn) = self.active_conns.get_mut(&conn_id) {
+            for watch_set in std::mem::take(&mut conn.determination_waits) {
+                self.controller.cancel_watch_set(watch_set);
+            }
         }
 
         self.cancel_pending_peeks(&conn_id);
@@ -935,14 +955,15 @@ impl Coordinator {
     /// Handle termination of a client session.
     ///
     /// This cleans up any state in the coordinator associated with the session.
+    ///
+    /// Several paths can request termination of the same connection, e.g. a client disconnecting
+    /// while the coordinator terminates its session. Commands are handled one at a time, so only
+    /// the first request tears the connection down, and later requests find it gone and return.
     #[tracing::instrument(level = "debug", skip(self))]
     async fn handle_terminate(&mut self, conn_id: ConnectionId) {
-        if self.active_conns.get(&conn_id).is_none() {
-            // If the session doesn't exist in `active_conns`, then this method will panic later on.
-            // Instead we explicitly panic here while dumping the entire Coord to the logs to help
-            // debug. This panic is very infrequent so we want as much information as possible.
-            // See https://github.com/MaterializeInc/materialize/issues/18996.
-            panic!("unknown connection: {conn_id:?}\n\n{self:?}")
+        if !self.active_conns.contains_key(&conn_id) {
+            debug!(%conn_id, "ignoring termination of unknown connection");
+            return;
         }
 
         // We do not need to call clear_transaction here because there are no side effects to run
@@ -965,8 +986,17 @@ impl Coordinator {
         // Queue the builtin table update, but do not wait for it to complete. We explicitly do
         // this to prevent blocking the Coordinator in the case that a lot of connections are
         // closed at once, which occurs regularly in some workflows.
-        let update = self.catalog().state().pack_session_update(&conn, -1);
-        let _builtin_update_notify = self.builtin_table_update().defer(vec![update]);
+        let mut updates = vec![self.catalog().state().pack_session_update(&conn, -1)];
+        updates.extend(conn.reported_portals.iter().map(|(name, usage)| {
+            self.catalog().state().pack_portal_update(
+                &conn,
+                name,
+                usage.created_at,
+                usage.buffered_bytes,
+                -1,
+            )
+        }));
+        let _builtin_update_notify = self.builtin_table_update().defer(updates);
     }
 
     /// Returns the necessary metadata for appending to a webhook source, and a channel to send
@@ -61,18 +61,24 @@ impl Coordinator {
 
     /// # Invariants:
     ///
-    /// * Read capabilities should reference known objects.
+    /// * Read capabilities should reference known objects, or soft-dropped objects whose
+    ///   collections are still retained.
     ///
     fn check_read_capabilities(&self) -> Result<(), Vec<ReadCapabilitiesInconsistency>> {
         let mut read_capabilities_inconsistencies = Vec::new();
         for (gid, _) in &self.storage_read_capabilities {
-            if self.catalog().try_get_entry(gid).is_none() {
+            if self.catalog().try_get_entry(gid).is_none()
+                && !self.controller.is_retaining_dropped_collection(*gid)
+            {
                 read_capabilities_inconsistencies
                     .push(ReadCapabilitiesInconsistency::Storage(gid.clone()));
             }
         }
         for (gid, _) in &self.compute_read_capabilities {
-            if !gid.is_transient() && self.catalog().try_get_entry(gid).is_none() {
+            if !gid.is_transient()
+                && self.catalog().try_get_entry(gid).is_none()
+                && !self.controller.is_retaining_dropped_collection(*gid)
+            {
                 read_capabilities_inconsistencies
                     .push(ReadCapabilitiesInconsistency::Compute(gid.clone()));
             }
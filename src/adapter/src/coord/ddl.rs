@@ -12,7 +12,7 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use fail::fail_point;
 use futures::Future;
@@ -28,25 +28,28 @@ use mz_compute_client::protocol::response::PeekResponse;
 use mz_controller::clusters::ReplicaLocation;
 use mz_controller_types::{ClusterId, ReplicaId};
 use mz_ore::error::ErrorExt;
+use mz_ore::now::EpochMillis;
 use mz_ore::retry::Retry;
 use mz_ore::str::StrExt;
 use mz_ore::task;
 use mz_repr::adt::numeric::Numeric;
 use mz_repr::{GlobalId, Timestamp};
 use mz_sql::catalog::{CatalogCluster, CatalogSchema};
-use mz_sql::names::{ObjectId, ResolvedDatabaseSpecifier};
+use mz_sql::names::{CommentObjectId, ObjectId, ResolvedDatabaseSpecifier};
 use mz_sql::session::vars::{
     self, SystemVars, Var, MAX_AWS_PRIVATELINK_CONNECTIONS, MAX_CLUSTERS,
     MAX_CREDIT_CONSUMPTION_RATE, MAX_DATABASES, MAX_KAFKA_CONNECTIONS, MAX_MATERIALIZED_VIEWS,
     MAX_OBJECTS_PER_SCHEMA, MAX_POSTGRES_CONNECTIONS, MAX_REPLICAS_PER_CLUSTER, MAX_ROLES,
     MAX_SCHEMAS_PER_DATABASE, MAX_SECRETS, MAX_SINKS, MAX_SOURCES, MAX_TABLES,
 };
+use mz_storage_client::client::StatusAttribution;
 use mz_storage_client::controller::ExportDescription;
 use mz_storage_types::connections::inline::IntoInlineConnection;
 use mz_storage_types::controller::StorageError;
 use mz_storage_types::instances::StorageInstanceId;
+use mz_storage_types::parameters::StorageParameters;
 use mz_storage_types::read_policy::ReadPolicy;
-use mz_storage_types::sources::GenericSourceConnection;
+use mz_storage_types::sources::{GenericSourceConnection, Timeline};
 use serde_json::json;
 use tracing::{event, info_span, instrument, warn, Instrument, Level};
 
@@ -205,7 +208,7 @@ impl Coordinator {
     pub(crate) async fn catalog_transact_with<'a, F, R>(
         &mut self,
         conn_id: Option<&ConnectionId>,
-        ops: Vec<catalog::Op>,
+        mut ops: Vec<catalog::Op>,
         f: F,
     ) -> Result<(R, BuiltinTableAppendNotify), AdapterError>
     where
@@ -237,9 +240,53 @@ impl Coordinator {
         let mut update_jemalloc_profiling_config = false;
         let mut update_default_arrangement_merge_options = false;
         let mut update_http_config = false;
+        let mut update_replica_disk_high_water_mark = false;
+        let mut update_frontier_divergence_threshold = false;
+        let mut items_changed = false;
+        let mut collection_names_to_update = BTreeSet::new();
+        let mut status_attributions_to_update = BTreeSet::new();
         let mut log_indexes_to_drop = Vec::new();
 
         for op in &ops {
+            items_changed |= matches!(
+                op,
+                catalog::Op::CreateItem { .. }
+                    | catalog::Op::UndropItem { .. }
+                    | catalog::Op::RenameItem { .. }
+                    | catalog::Op::RenameSchema { .. }
+            );
+            match op {
+                catalog::Op::CreateItem { id, .. }
+                | catalog::Op::UndropItem { id, .. }
+                | catalog::Op::RenameItem { id, .. }
+                | catalog::Op::DropObject(ObjectId::Item(id)) => {
+                    collection_names_to_update.insert(*id);
+                }
+                catalog::Op::RenameSchema {
+                    database_spec,
+                    schema_spec,
+                    ..
+                } => {
+                    let schema =
+                        self.catalog()
+                            .get_schema(database_spec, schema_spec, &SYSTEM_CONN_ID);
+                    collection_names_to_update.extend(schema.items.values().copied());
+                }
+                _ => {}
+            }
+            if let catalog::Op::CreateItem { id, .. }
+            | catalog::Op::UndropItem { id, .. }
+            | catalog::Op::UpdateOwner {
+                id: ObjectId::Item(id),
+                ..
+            }
+            | catalog::Op::Comment {
+                object_id: CommentObjectId::Source(id) | CommentObjectId::Sink(id),
+                ..
+            } = op
+            {
+                status_attributions_to_update.insert(*id);
+            }
             match op {
                 catalog::Op::DropObject(ObjectId::Item(id)) => {
                     match self.catalog().get_entry(id).item() {
@@ -248,6 +295,7 @@ impl Coordinator {
                         }
                         CatalogItem::Source(source) => {
                             sources_to_drop.push(*id);
+                            update_storage_config |= !source.parameter_overrides.is_empty();
                             if let DataSourceDesc::Ingestion(ingestion) = &source.data_source {
                                 match &ingestion.desc.connection {
                                     GenericSourceConnection::Postgres(conn) => {
@@ -347,6 +395,11 @@ impl Coordinator {
                     update_default_arrangement_merge_options |=
                         name == vars::DEFAULT_ARRANGEMENT_EXERT_PROPORTIONALITY.name();
                     update_http_config |= vars::is_http_config_var(name);
+                    update_replica_disk_high_water_mark |=
+                        name == vars::CLUSTER_REPLICA_DISK_HIGH_WATER_MARK.name();
+                    update_frontier_divergence_threshold |= name
+                        == vars::FRONTIER_DIVERGENCE_THRESHOLD.name()
+                        || name == vars::FRONTIER_DIVERGENCE_TICKS.name();
                 }
                 catalog::Op::ResetAllSystemConfiguration => {
                     // Assume they all need to be updated.
@@ -362,6 +415,23 @@ impl Coordinator {
                     update_jemalloc_profiling_config = true;
                     update_default_arrangement_merge_options = true;
                     update_http_config = true;
+                    update_replica_disk_high_water_mark = true;
+                    update_frontier_divergence_threshold = true;
+                }
+                catalog::Op::CreateItem {
+                    item: CatalogItem::Source(source),
+                    ..
+                } => {
+                    update_storage_config |= !source.parameter_overrides.is_empty();
+                }
+                catalog::Op::UpdateItem {
+                    id,
+                    to_item: CatalogItem::Source(source),
+                    ..
+                } => {
+                    let cur_source = self.catalog().get_entry(id).source();
+                    update_storage_config |= cur_source.map(|s| &s.parameter_overrides)
+                        != Some(&source.parameter_overrides);
                 }
                 catalog::Op::RenameItem { id, .. } => {
                     let item = self.catalog().get_entry(id);
@@ -497,6 +567,36 @@ impl Coordinator {
 
         self.validate_resource_limits(&ops, conn_id.unwrap_or(&SYSTEM_CONN_ID))?;
 
+        // Soft-dropped materialized views disappear from the catalog like any other drop, but
+        // their collections are retained so that they can be restored with `UNDROP`. Collections
+        // cannot outlive their cluster, so the materialized views of dropped clusters are dropped
+        // for good.
+        let soft_dropped_materialized_views = if self.catalog().system_config().enable_soft_drop() {
+            let (soft_dropped, dropped) = std::mem::take(&mut materialized_views_to_drop)
+                .into_iter()
+                .partition(|(cluster_id, _)| !clusters_to_drop.contains(cluster_id));
+            materialized_views_to_drop = dropped;
+            soft_dropped
+        } else {
+            Vec::new()
+        };
+
+        // The same goes for materialized views soft-dropped earlier on clusters dropped now.
+        let retained_drops_to_drop: Vec<_> = clusters_to_drop
+            .iter()
+            .flat_map(|cluster_id| {
+                self.controller
+                    .retained_drops_on(*cluster_id)
+                    .into_iter()
+                    .map(|id| (*cluster_id, id))
+            })
+            .collect();
+        if !retained_drops_to_drop.is_empty() {
+            ops.push(catalog::Op::PurgeDroppedItems(
+                retained_drops_to_drop.iter().map(|(_, id)| *id).collect(),
+            ));
+        }
+
         // This will produce timestamps that are guaranteed to increase on each
         // call, and also never be behind the system clock. If the system clock
         // hasn't advanced (or has gone backward), it will increment by 1. For
@@ -505,7 +605,7 @@ impl Coordinator {
         // always going up, and believe we will always be close to the system
         // clock because it is well configured (chrony) and so may only rarely
         // regress or pause for 10s.
-        let oracle_write_ts = self.get_local_write_ts().await.timestamp;
+        let oracle_write_ts = self.try_get_local_write_ts().await?.timestamp;
 
         let Coordinator {
             catalog,
@@ -545,18 +645,11 @@ impl Coordinator {
                     assert_eq!(should_be_empty, became_empty, "emptiness did not match!");
                 }
             }
-            if !sources_to_drop.is_empty() {
-                self.drop_sources(sources_to_drop);
-            }
-            if !tables_to_drop.is_empty() {
-                self.drop_sources(tables_to_drop);
-            }
+            self.drop_storage_statistics(&sources_to_drop, &storage_sinks_to_drop)
+                .await;
             if !webhook_sources_to_restart.is_empty() {
                 self.restart_webhook_sources(webhook_sources_to_restart);
             }
-            if !storage_sinks_to_drop.is_empty() {
-                self.drop_storage_sinks(storage_sinks_to_drop);
-            }
             if !compute_sinks_to_drop.is_empty() {
                 self.drop_compute_sinks_with_reason(compute_sinks_to_drop)
                     .await;
@@ -579,11 +672,20 @@ impl Coordinator {
                     }
                 }
             }
-            if !indexes_to_drop.is_empty() {
-                self.drop_indexes(indexes_to_drop);
-            }
-            if !materialized_views_to_drop.is_empty() {
-                self.drop_materialized_views(materialized_views_to_drop);
+            // The collections are dropped once the compute sinks and peeks that read from them
+            // are gone.
+            sources_to_drop.extend(tables_to_drop);
+            self.drop_collections_batched(
+                sources_to_drop,
+                storage_sinks_to_drop,
+                indexes_to_drop,
+                materialized_views_to_drop,
+            );
+            if !soft_dropped_materialized_views.is_empty() {
+                self.retain_soft_dropped_materialized_views(
+                    soft_dropped_materialized_views,
+                    oracle_write_ts.into(),
+                );
             }
             if !secrets_to_drop.is_empty() {
                 self.drop_secrets(secrets_to_drop).await;
@@ -602,6 +704,12 @@ impl Coordinator {
                     self.drop_compute_read_policy(&id);
                 }
             }
+            if !retained_drops_to_drop.is_empty() {
+                for cluster_id in &clusters_to_drop {
+                    self.controller.release_retained_drops_on(*cluster_id);
+                }
+                self.drop_retained_collections(retained_drops_to_drop);
+            }
             if !clusters_to_drop.is_empty() {
                 for cluster_id in clusters_to_drop {
                     self.controller.drop_cluster(cluster_id);
@@ -664,6 +772,21 @@ impl Coordinator {
             if update_http_config {
                 self.update_http_config();
             }
+            if update_replica_disk_high_water_mark {
+                self.update_replica_disk_high_water_mark();
+            }
+            if update_frontier_divergence_threshold {
+                self.update_frontier_divergence_threshold();
+            }
+            if !collection_names_to_update.is_empty() {
+                self.update_collection_names(collection_names_to_update);
+            }
+            if items_changed {
+                self.update_epoch_millis_collections();
+            }
+            if !status_attributions_to_update.is_empty() {
+                self.update_status_attributions(status_attributions_to_update);
+            }
         }
         .instrument(info_span!("coord::catalog_transact_with::finalize"))
         .await;
@@ -725,8 +848,7 @@ impl Coordinator {
 
     fn drop_sources(&mut self, sources: Vec<GlobalId>) {
         for id in &sources {
-            self.active_webhooks.remove(id);
-            self.drop_storage_read_policy(id);
+            self.drop_source_state(id);
         }
         self.controller
             .storage
@@ -734,6 +856,65 @@ impl Coordinator {
             .unwrap_or_terminate("cannot fail to drop sources");
     }
 
+    /// Forgets the state the coordinator maintains for the source or table `id`.
+    fn drop_source_state(&mut self, id: &GlobalId) {
+        self.active_webhooks.remove(id);
+        self.drop_storage_read_policy(id);
+    }
+
+    /// Drops the collections of the given sources and tables, storage sinks, indexes, and
+    /// materialized views with a single batched drop, rather than separate drops per kind of
+    /// object and cluster. The collections are dropped in that order.
+    fn drop_collections_batched(
+        &mut self,
+        sources: Vec<GlobalId>,
+        sinks: Vec<GlobalId>,
+        indexes: Vec<(ClusterId, GlobalId)>,
+        mviews: Vec<(ClusterId, GlobalId)>,
+    ) {
+        let mut ids = Vec::new();
+        for id in sources {
+            self.drop_source_state(&id);
+            ids.push(id);
+        }
+        for id in sinks {
+            self.drop_storage_read_policy(&id);
+            ids.push(id);
+        }
+        for (_cluster_id, id) in indexes {
+            if self.drop_compute_read_policy(&id) {
+                ids.push(id);
+            } else {
+                tracing::error!("Instructed to drop a non-index index");
+            }
+        }
+        for (_cluster_id, id) in mviews {
+            if !self
+                .controller
+                .compute
+                .enable_aggressive_readhold_downgrades()
+            {
+                // If aggressive downgrades are disabled, MV dataflows have read policies that we
+                // must drop.
+                if !self.drop_compute_read_policy(&id) {
+                    tracing::error!("Instructed to drop a materialized view that isn't one");
+                    continue;
+                }
+            }
+            self.drop_storage_read_policy(&id);
+            ids.push(id);
+        }
+        if ids.is_empty() {
+            return;
+        }
+
+        let batch = self
+            .controller
+            .drop_collections_batched(ids)
+            .unwrap_or_terminate("cannot fail to drop collections");
+        self.pending_collection_drops.insert(batch, Instant::now());
+    }
+
     fn restart_webhook_sources(&mut self, sources: impl IntoIterator<Item = GlobalId>) {
         for id in sources {
             self.active_webhooks.remove(&id);
@@ -797,16 +978,6 @@ impl Coordinator {
         self.drop_compute_sinks(sink_cluster_id_map.into_iter());
     }
 
-    pub(crate) fn drop_storage_sinks(&mut self, sinks: Vec<GlobalId>) {
-        for id in &sinks {
-            self.drop_storage_read_policy(id);
-        }
-        self.controller
-            .storage
-            .drop_sinks(sinks)
-            .unwrap_or_terminate("cannot fail to drop sinks");
-    }
-
     pub(crate) fn drop_indexes(&mut self, indexes: Vec<(ClusterId, GlobalId)>) {
         let mut by_cluster: BTreeMap<_, Vec<_>> = BTreeMap::new();
         for (cluster_id, id) in indexes {
@@ -863,6 +1034,52 @@ impl Coordinator {
         self.drop_sources(source_ids)
     }
 
+    /// Hands the collections of materialized views soft-dropped at `dropped_at` to the
+    /// controller to retain until their restore window lapses.
+    ///
+    /// The restore window ends at the same time as recorded in the durable catalog, so that it
+    /// is unaffected by restarts.
+    fn retain_soft_dropped_materialized_views(
+        &mut self,
+        mviews: Vec<(ClusterId, GlobalId)>,
+        dropped_at: EpochMillis,
+    ) {
+        let expires_at = self.catalog().state().soft_drop_expires_at(dropped_at);
+        for (cluster_id, id) in mviews {
+            self.controller
+                .retain_dropped_collection(cluster_id, id, expires_at);
+        }
+    }
+
+    /// Drops the materialized views whose collections the controller no longer retains, and
+    /// forgets their soft-dropped catalog entries.
+    ///
+    /// Materialized views soft-dropped before a restart only have a storage collection, as their
+    /// dataflows are not re-created at bootstrap. Dropping it finalizes the view's shard.
+    pub(crate) async fn purge_dropped_items(&mut self) {
+        let expired = self.controller.expire_retained_drops((self.now)());
+        if expired.is_empty() {
+            return;
+        }
+        let ids = expired.iter().map(|(_, id)| *id).collect();
+        self.drop_retained_collections(expired);
+        let ops = vec![catalog::Op::PurgeDroppedItems(ids)];
+        if let Err(err) = self.catalog_transact(None::<&Session>, ops).await {
+            tracing::warn!("Failed to purge dropped items: {:?}", err);
+        }
+    }
+
+    /// Drops the collections of soft-dropped materialized views that the controller no longer
+    /// retains.
+    fn drop_retained_collections(&mut self, retained: Vec<(ClusterId, GlobalId)>) {
+        let (mviews, storage_only): (Vec<_>, Vec<_>) =
+            retained.into_iter().partition(|(cluster_id, id)| {
+                self.controller.compute.collection(*cluster_id, *id).is_ok()
+            });
+        self.drop_materialized_views(mviews);
+        self.drop_sources(storage_only.into_iter().map(|(_, id)| id).collect());
+    }
+
     async fn drop_secrets(&mut self, secrets: Vec<GlobalId>) {
         fail_point!("drop_secrets");
         for secret in secrets {
@@ -922,10 +1139,25 @@ impl Coordinator {
     }
 
     fn update_storage_config(&mut self) {
-        let config_params = flags::storage_config(self.catalog().system_config());
+        let config_params = self.storage_config();
         self.controller.storage.update_parameters(config_params);
     }
 
+    /// Returns the storage parameters of the system configuration, together with the tuning
+    /// parameters that sources override in their definitions.
+    pub(crate) fn storage_config(&self) -> StorageParameters {
+        let mut config_params = flags::storage_config(self.catalog().system_config());
+        config_params.source_overrides = self
+            .catalog()
+            .entries()
+            .filter_map(|entry| {
+                let overrides = &entry.source()?.parameter_overrides;
+                (!overrides.is_empty()).then(|| (entry.id(), overrides.clone()))
+            })
+            .collect();
+        config_params
+    }
+
     fn update_pg_timestamp_oracle_config(&mut self) {
         let config_params = flags::pg_timstamp_oracle_config(self.catalog().system_config());
         if let Some(config) = self.pg_timestamp_oracle_config.as_ref() {
@@ -995,6 +1227,87 @@ impl Coordinator {
             .set_default_arrangement_exert_proportionality(prop);
     }
 
+    pub(crate) fn update_replica_disk_high_water_mark(&mut self) {
+        let mark = self
+            .catalog()
+            .system_config()
+            .cluster_replica_disk_high_water_mark()
+            .try_into()
+            .expect("value constrained to be convertible to f64");
+        self.controller.set_replica_disk_high_water_mark(mark);
+    }
+
+    pub(crate) fn update_frontier_divergence_threshold(&mut self) {
+        let config = self.catalog().system_config();
+        let threshold = config.frontier_divergence_threshold();
+        let ticks = config.frontier_divergence_ticks();
+        self.controller
+            .set_frontier_divergence_threshold(threshold, ticks);
+    }
+
+    /// Registers the names of catalog items with the controller, which uses them to identify
+    /// collections in logs and errors.
+    ///
+    /// The controller consults the names only when formatting, so it resolves them against a map
+    /// that the coordinator maintains with [`Coordinator::update_collection_names`] rather than
+    /// against the catalog, which lives on the coordinator thread.
+    pub(crate) fn register_collection_names(&self) {
+        let names = Arc::clone(&self.collection_names);
+        self.controller.register_name_resolver(Arc::new(move |id| {
+            names.read().expect("lock poisoned").get(&id).cloned()
+        }));
+    }
+
+    /// Refreshes the names of the catalog items among `ids` that the controller resolves,
+    /// forgetting those of items that no longer exist.
+    pub(crate) fn update_collection_names(&self, ids: impl IntoIterator<Item = GlobalId>) {
+        let catalog = self.catalog();
+        let mut names = self.collection_names.write().expect("lock poisoned");
+        for id in ids {
+            match catalog.try_get_entry(&id) {
+                Some(entry) => {
+                    let name = catalog.resolve_full_name(entry.name(), entry.conn_id());
+                    names.insert(id, name.to_string());
+                }
+                None => {
+                    names.remove(&id);
+                }
+            }
+        }
+    }
+
+    /// Informs the storage controller about the owners and comments of the sources and sinks
+    /// among `ids`, which it records along with their status updates.
+    ///
+    /// The controller retains the attribution of dropped objects until it has recorded their
+    /// final status, so there is no need to refresh after drops.
+    pub(crate) fn update_status_attributions(&mut self, ids: impl IntoIterator<Item = GlobalId>) {
+        let catalog = self.catalog();
+        let attributions = ids
+            .into_iter()
+            .filter_map(|id| catalog.try_get_entry(&id))
+            .filter(|entry| entry.is_source() || entry.is_sink())
+            .map(|entry| {
+                let attribution = StatusAttribution {
+                    owner: Some(catalog.get_role(entry.owner_id()).name.clone()),
+                    comment: catalog.get_item_comment(&entry.id()).map(String::from),
+                };
+                (entry.id(), attribution)
+            })
+            .collect();
+        self.controller
+            .storage
+            .update_status_attributions(attributions);
+    }
+
+    /// Informs the controller about the collections in the epoch-milliseconds timeline, whose
+    /// lag behind the wall clock it records.
+    pub(crate) fn update_epoch_millis_collections(&mut self) {
+        let id_bundle = self.ids_in_timeline(&Timeline::EpochMilliseconds);
+        self.controller
+            .set_epoch_millis_collections(id_bundle.iter().collect());
+    }
+
     fn update_http_config(&mut self) {
         let webhook_request_limit = self
             .catalog()
@@ -1271,7 +1584,10 @@ impl Coordinator {
                 | Op::ResetSystemConfiguration { .. }
                 | Op::ResetAllSystemConfiguration { .. }
                 | Op::UpdateRotatedKeys { .. }
-                | Op::Comment { .. } => {}
+                | Op::Comment { .. }
+                | Op::PurgeDroppedItems(_) => {}
+                // Only materialized views are ever soft-dropped.
+                Op::UndropItem { .. } => new_materialized_views += 1,
             }
         }
 
@@ -17,7 +17,7 @@ use crate::coord::Coordinator;
 use crate::session::Session;
 
 /// A bundle of storage and compute collection identifiers.
-#[derive(Deserialize, Debug, Default, Clone)]
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct CollectionIdBundle {
     /// The identifiers for sources in the storage layer.
     pub storage_ids: BTreeSet<GlobalId>,
@@ -18,16 +18,22 @@
 //!
 //! [`mz_introspection`]: https://materialize.com/docs/sql/show-clusters/#mz_introspection-system-cluster
 
+use std::collections::BTreeSet;
+
+use mz_compute_types::ComputeInstanceId;
+use mz_controller_types::ReplicaId;
 use mz_expr::CollectionPlan;
-use mz_repr::GlobalId;
+use mz_repr::{GlobalId, Timestamp};
 use mz_sql::catalog::SessionCatalog;
 use mz_sql::plan::{
     ExplainPlanPlan, ExplainTimestampPlan, Explainee, ExplaineeStatement, Plan, SubscribeFrom,
 };
 use smallvec::SmallVec;
+use timely::progress::Timestamp as TimelyTimestamp;
 
 use crate::catalog::ConnCatalog;
-use crate::coord::TargetCluster;
+use crate::coord::id_bundle::CollectionIdBundle;
+use crate::coord::{Coordinator, TargetCluster};
 use crate::notice::AdapterNotice;
 use crate::session::Session;
 use crate::AdapterError;
@@ -91,7 +97,9 @@ pub fn auto_run_on_introspection<'a, 's, 'p>(
         | Plan::ShowAllVariables
         | Plan::ShowCreate(_)
         | Plan::ShowVariable(_)
+        | Plan::ShowTimestamp
         | Plan::InspectShard(_)
+        | Plan::InspectCollection(_)
         | Plan::SetVariable(_)
         | Plan::ResetVariable(_)
         | Plan::SetTransaction(_)
@@ -111,6 +119,7 @@ pub fn auto_run_on_introspection<'a, 's, 'p>(
         | Plan::AlterIndexSetOptions(_)
         | Plan::AlterIndexResetOptions(_)
         | Plan::AlterConnection(_)
+        | Plan::AlterSink(_)
         | Plan::AlterSource(_)
         | Plan::PurifiedAlterSource { .. }
         | Plan::AlterSetCluster(_)
@@ -139,6 +148,7 @@ pub fn auto_run_on_introspection<'a, 's, 'p>(
         | Plan::AlterDefaultPrivileges(_)
         | Plan::ReassignOwned(_)
         | Plan::ValidateConnection(_)
+        | Plan::Undrop(_)
         | Plan::SideEffectingFunc(_) => return TargetCluster::Active,
     };
 
@@ -239,3 +249,42 @@ pub fn check_cluster_restrictions(
         Ok(())
     }
 }
+
+impl Coordinator {
+    /// Reports whether the replica `replica_id` of the cluster `cluster_id` has yet to report
+    /// introspection data for the collections in `id_bundle`.
+    ///
+    /// A replica that is still starting has not advanced the write frontiers of its introspection
+    /// collections past the minimum timestamp, so queries that only read from these collections
+    /// would return empty results.
+    pub(crate) fn introspection_data_unreported(
+        &self,
+        cluster_id: ComputeInstanceId,
+        replica_id: ReplicaId,
+        id_bundle: &CollectionIdBundle,
+    ) -> bool {
+        // Queries that also read other collections are not affected.
+        if !id_bundle.storage_ids.is_empty()
+            || id_bundle.compute_ids.keys().any(|id| *id != cluster_id)
+        {
+            return false;
+        }
+        let Some(cluster) = self.catalog().try_get_cluster(cluster_id) else {
+            return false;
+        };
+        let introspection_ids: BTreeSet<_> = cluster.log_indexes.values().collect();
+        let ids: Vec<_> = id_bundle.compute_ids.values().flatten().collect();
+        if ids.is_empty() || !ids.iter().all(|id| introspection_ids.contains(id)) {
+            return false;
+        }
+
+        ids.into_iter().all(
+            |id| match self.controller.compute.collection(cluster_id, *id) {
+                Ok(collection) => collection
+                    .replica_write_frontier(replica_id)
+                    .map_or(true, |frontier| frontier.less_equal(&Timestamp::minimum())),
+                Err(_) => false,
+            },
+        )
+    }
+}
@@ -16,12 +16,14 @@ use std::time::{Duration, Instant};
 use futures::future::LocalBoxFuture;
 use futures::FutureExt;
 use mz_adapter_types::connection::ConnectionId;
-use mz_controller::clusters::ClusterEvent;
+use mz_controller::clusters::{ClusterEvent, ClusterStatus};
 use mz_controller::ControllerResponse;
+use mz_orchestrator::NotReadyReason;
 use mz_ore::cast::CastFrom;
 use mz_ore::now::EpochMillis;
 use mz_ore::task;
 use mz_persist_client::usage::ShardsUsageReferenced;
+use mz_repr::{Datum, Row};
 use mz_sql::ast::Statement;
 use mz_sql::names::ResolvedIds;
 use mz_sql::plan::{CreateSourcePlans, Plan};
@@ -117,6 +119,15 @@ impl Coordinator {
                 Message::StorageUsageUpdate(sizes) => {
                     self.storage_usage_update(sizes).await;
                 }
+                Message::PortalUsageRefresh => {
+                    self.portal_usage_refresh();
+                }
+                Message::PurgeDroppedItems => {
+                    self.purge_dropped_items().await;
+                }
+                Message::StorageStatisticsReconcile => {
+                    self.reconcile_storage_statistics().await;
+                }
                 Message::RealTimeRecencyTimestamp {
                     conn_id,
                     real_time_recency_ts,
@@ -186,6 +197,14 @@ impl Coordinator {
                 Message::DrainStatementLog => {
                     self.drain_statement_log().await;
                 }
+                Message::ReleaseReadHolds(read_holds) => {
+                    self.release_read_holds_inner(vec![read_holds]);
+                }
+                Message::DeterminationWaitFinished { conn_id, watch_set } => {
+                    if let Some(conn) = self.active_conns.get_mut(&conn_id) {
+                        conn.determination_waits.remove(&watch_set);
+                    }
+                }
                 Message::PrivateLinkVpcEndpointEvents(events) => {
                     self.controller
                         .storage
@@ -198,6 +217,15 @@ impl Coordinator {
                         )
                         .await;
                 }
+                Message::TimestampOracleStatus(updates) => {
+                    self.controller
+                        .storage
+                        .record_introspection_updates(
+                            mz_storage_client::controller::IntrospectionType::TimestampOracleStatus,
+                            updates,
+                        )
+                        .await;
+                }
             }
         }
         .instrument(span)
@@ -264,7 +292,14 @@ impl Coordinator {
         // increase. This is intentionally the timestamp of when collection
         // finished, not when it started, so that we don't write data with a
         // timestamp in the past.
-        let collection_timestamp: EpochMillis = self.get_local_write_ts().await.timestamp.into();
+        let collection_timestamp: EpochMillis = match self.try_get_local_write_ts().await {
+            Ok(write_ts) => write_ts.timestamp.into(),
+            Err(err) => {
+                tracing::warn!("Failed to update storage metrics: {:?}", err);
+                self.schedule_storage_usage_collection().await;
+                return;
+            }
+        };
 
         let mut ops = vec![];
         for (shard_id, shard_usage) in shards_usage.by_shard {
@@ -281,6 +316,50 @@ impl Coordinator {
         self.schedule_storage_usage_collection().await;
     }
 
+    /// Brings `mz_internal.mz_portals` up to date with the portals currently
+    /// open in each session.
+    ///
+    /// Portals are advanced by the sessions themselves, outside of the
+    /// coordinator, so their usage is sampled here rather than reported
+    /// eagerly.
+    #[tracing::instrument(level = "debug", skip_all)]
+    fn portal_usage_refresh(&mut self) {
+        let state = self.catalog.state();
+        let mut updates = Vec::new();
+        for conn in self.active_conns.values_mut() {
+            let current = conn.portal_usage.snapshot();
+            if current == conn.reported_portals {
+                continue;
+            }
+            for (name, usage) in &conn.reported_portals {
+                if current.get(name) != Some(usage) {
+                    updates.push(state.pack_portal_update(
+                        conn,
+                        name,
+                        usage.created_at,
+                        usage.buffered_bytes,
+                        -1,
+                    ));
+                }
+            }
+            for (name, usage) in &current {
+                if conn.reported_portals.get(name) != Some(usage) {
+                    updates.push(state.pack_portal_update(
+                        conn,
+                        name,
+                        usage.created_at,
+                        usage.buffered_bytes,
+                        1,
+                    ));
+                }
+            }
+            conn.reported_portals = current;
+        }
+        if !updates.is_empty() {
+            self.builtin_table_update().background(updates);
+        }
+    }
+
     pub async fn schedule_storage_usage_collection(&self) {
         // Instead of using an `tokio::timer::Interval`, we calculate the time until the next
         // usage collection and wait for that amount of time. This is so we can keep the intervals
@@ -309,7 +388,12 @@ impl Coordinator {
                 .expect("storage usage collection interval must fit into u64");
         let offset =
             rngs::SmallRng::from_seed(seed).gen_range(0..storage_usage_collection_interval_ms);
-        let now_ts: EpochMillis = self.peek_local_write_ts().await.into();
+        // The wall clock is close enough to schedule by while the timestamp oracle is
+        // unavailable.
+        let now_ts: EpochMillis = match self.try_peek_local_write_ts().await {
+            Ok(ts) => ts.into(),
+            Err(_) => self.now(),
+        };
 
         // 2) Determine the amount of ms between now and the next collection time.
         let previous_collection_ts =
@@ -348,12 +432,14 @@ impl Coordinator {
                 match self.active_compute_sinks.get_mut(&sink_id) {
                     Some(ActiveComputeSink::Subscribe(active_subscribe)) => {
                         let finished = active_subscribe.process_response(response);
-                        if finished {
-                            self.drop_compute_sinks_with_reason([(
-                                sink_id,
-                                ComputeSinkRemovalReason::Finished,
-                            )])
-                            .await;
+                        let reason = if finished {
+                            Some(ComputeSinkRemovalReason::Finished)
+                        } else {
+                            active_subscribe.buffer_limit_exceeded()
+                        };
+                        if let Some(reason) = reason {
+                            self.drop_compute_sinks_with_reason([(sink_id, reason)])
+                                .await;
                         }
                     }
                     _ => {
@@ -409,6 +495,104 @@ impl Coordinator {
                     self.builtin_table_update().background(updates);
                 }
             }
+            ControllerResponse::ReplicaDiskHighWaterMark(crossed) => {
+                for mark in crossed {
+                    // The replica may have been dropped since its metrics were collected.
+                    let Some((cluster, replica)) = self.catalog().clusters().find_map(|cluster| {
+                        cluster
+                            .replica(mark.replica_id)
+                            .map(|replica| (cluster, replica))
+                    }) else {
+                        continue;
+                    };
+                    warn!(
+                        cluster = %cluster.name,
+                        replica = %replica.name,
+                        process_id = mark.process_id,
+                        disk_usage_bytes = mark.disk_usage_bytes,
+                        disk_capacity_bytes = mark.disk_capacity_bytes,
+                        "cluster replica crossed the disk high-water mark"
+                    );
+                    let notice = AdapterNotice::ClusterReplicaDiskHighWaterMark {
+                        cluster: cluster.name.clone(),
+                        replica: replica.name.clone(),
+                        process_id: mark.process_id,
+                        disk_usage_bytes: mark.disk_usage_bytes,
+                        disk_capacity_bytes: mark.disk_capacity_bytes,
+                    };
+                    self.broadcast_notice(notice);
+                }
+            }
+            ControllerResponse::ReplicaCrashLooping(crash_loops) => {
+                for crash_loop in crash_loops {
+                    // The replica may have been dropped since it was found crash-looping.
+                    let Some(replica) = self
+                        .catalog()
+                        .try_get_cluster(crash_loop.cluster_id)
+                        .and_then(|cluster| cluster.replica(crash_loop.replica_id))
+                    else {
+                        continue;
+                    };
+                    warn!(
+                        cluster_id = %crash_loop.cluster_id,
+                        replica = %replica.name,
+                        restarts = crash_loop.restarts,
+                        backoff = ?crash_loop.backoff,
+                        last_failure = ?crash_loop.last_failure,
+                        "cluster replica is crash-looping, backing off"
+                    );
+                    let status = ClusterStatus::NotReady(Some(NotReadyReason::CrashLooping {
+                        oom_killed: crash_loop.last_failure == Some(NotReadyReason::OomKilled),
+                    }));
+                    let process_ids: Vec<_> = replica.process_status.keys().copied().collect();
+                    for process_id in process_ids {
+                        self.update_cluster_replica_status(ClusterEvent {
+                            cluster_id: crash_loop.cluster_id,
+                            replica_id: crash_loop.replica_id,
+                            process_id,
+                            status,
+                            time: crash_loop.time,
+                        })
+                        .await;
+                    }
+                }
+            }
+            ControllerResponse::FrontierDivergences(divergences) => {
+                // The controller has already logged and counted the divergences.
+                let occurred_at = self.now_datetime().try_into().expect("must fit");
+                let updates = divergences
+                    .into_iter()
+                    .map(|divergence| {
+                        let row = Row::pack_slice(&[
+                            Datum::String(&divergence.id.to_string()),
+                            Datum::MzTimestamp(divergence.compute_write_frontier),
+                            Datum::MzTimestamp(divergence.storage_write_frontier),
+                            Datum::TimestampTz(occurred_at),
+                        ]);
+                        (row, 1)
+                    })
+                    .collect();
+                self.controller
+                    .storage
+                    .record_introspection_updates(
+                        mz_storage_client::controller::IntrospectionType::FrontierDivergenceHistory,
+                        updates,
+                    )
+                    .await;
+            }
+            ControllerResponse::CollectionsDropped(batches) => {
+                for batch in batches {
+                    if let Some(dropped_at) = self.pending_collection_drops.remove(&batch) {
+                        self.metrics
+                            .collection_drop_seconds
+                            .observe(dropped_at.elapsed().as_secs_f64());
+                    }
+                }
+            }
+            ControllerResponse::StorageStatistics(source_stats, sink_stats) => {
+                self.handle_storage_statistics(source_stats, sink_stats)
+                    .await;
+            }
             ControllerResponse::WatchSetFinished(sets) => {
                 for set in sets {
                     let (id, ev) = set
@@ -594,7 +778,7 @@ impl Coordinator {
     async fn message_alter_connection_validation_ready(
         &mut self,
         AlterConnectionValidationReady {
-            mut ctx,
+            ctx,
             result,
             connection_gid,
             mut plan_validity,
@@ -619,10 +803,8 @@ impl Coordinator {
             }
         };
 
-        let result = self
-            .sequence_alter_connection_stage_finish(ctx.session_mut(), connection_gid, conn)
+        self.sequence_alter_connection_stage_finish(ctx, connection_gid, conn)
             .await;
-        ctx.retire(result);
     }
 
     #[tracing::instrument(level = "debug", skip_all)]
@@ -660,6 +842,33 @@ impl Coordinator {
     async fn message_cluster_event(&mut self, event: ClusterEvent) {
         event!(Level::TRACE, event = format!("{:?}", event));
 
+        // It is possible that we receive a status update for a replica that has
+        // already been dropped from the catalog. Just ignore these events.
+        let Some(replica) = self
+            .catalog()
+            .try_get_cluster(event.cluster_id)
+            .and_then(|cluster| cluster.replica(event.replica_id))
+        else {
+            return;
+        };
+        // The orchestrator may report the status of a process repeatedly, which must not count
+        // as repeated restarts.
+        if !replica.is_status_change(&event) {
+            return;
+        }
+
+        self.controller.observe_cluster_event(&event);
+        // The processes of a replica that is held back from restarting go away, which must not
+        // hide that it is crash-looping.
+        if self.controller.replica_backing_off(event.replica_id) {
+            return;
+        }
+        self.update_cluster_replica_status(event).await;
+    }
+
+    /// Records the status of a replica process in the catalog, notifying sessions if the status
+    /// of the replica changed.
+    async fn update_cluster_replica_status(&mut self, event: ClusterEvent) {
         // It is possible that we receive a status update for a replica that has
         // already been dropped from the catalog. Just ignore these events.
         let Some(cluster) = self.catalog().try_get_cluster(event.cluster_id) else {
@@ -669,7 +878,7 @@ impl Coordinator {
             return;
         };
 
-        if event.status != replica.process_status[&event.process_id].status {
+        if replica.is_status_change(&event) {
             let old_status = replica.status();
 
             self.catalog_transact(
@@ -715,21 +924,12 @@ impl Coordinator {
             if let TimestampContext::TimelineTimestamp {
                 timeline,
                 chosen_ts,
-                oracle_ts,
+                ..
             } = read_txn.timestamp_context()
             {
-                let oracle_ts = match oracle_ts {
-                    Some(oracle_ts) => oracle_ts,
-                    None => {
-                        // There was no oracle timestamp, so no need to delay.
-                        ready_txns.push(read_txn);
-                        continue;
-                    }
-                };
-
-                if chosen_ts <= oracle_ts {
-                    // Chosen ts was already <= the oracle ts, so we're good
-                    // to go!
+                if !read_txn.timestamp_context().requires_linearization_wait() {
+                    // There was no oracle timestamp, or the chosen ts was
+                    // already <= the oracle ts, so we're good to go!
                     ready_txns.push(read_txn);
                     continue;
                 }
@@ -877,6 +1077,7 @@ impl Coordinator {
                         oracle_read_ts,
                         source_ids,
                         real_time_recency_ts: Some(real_time_recency_ts),
+                        introspection_wait_deadline: None,
                         optimizer,
                         explain_ctx,
                     }),
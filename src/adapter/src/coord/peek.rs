@@ -74,6 +74,18 @@ pub enum PeekResponseUnary {
     Canceled,
 }
 
+impl PeekResponseUnary {
+    /// Returns the number of bytes of rows held by the response.
+    pub fn byte_len(&self) -> u64 {
+        match self {
+            PeekResponseUnary::Rows(rows) => {
+                rows.iter().map(|row| u64::cast_from(row.byte_len())).sum()
+            }
+            PeekResponseUnary::Error(_) | PeekResponseUnary::Canceled => 0,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PeekDataflowPlan<T = mz_repr::Timestamp> {
     pub(crate) desc: DataflowDescription<mz_compute_types::plan::Plan<T>, (), T>,
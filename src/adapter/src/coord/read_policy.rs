@@ -30,16 +30,18 @@ use mz_compute_types::ComputeInstanceId;
 use mz_repr::{GlobalId, Timestamp};
 use mz_storage_types::read_policy::ReadPolicy;
 use timely::progress::Antichain;
-use tracing::instrument;
+use tokio::sync::mpsc;
+use tracing::{debug, instrument};
 
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::timeline::{TimelineContext, TimelineState};
+use crate::coord::Message;
 use crate::session::Session;
 use crate::util::ResultExt;
 
 /// Relevant information for acquiring or releasing a bundle of read holds.
 #[derive(Clone, Debug)]
-pub(crate) struct ReadHolds<T> {
+pub struct ReadHolds<T> {
     holds: HashMap<Antichain<T>, CollectionIdBundle>,
 }
 
@@ -146,6 +148,49 @@ impl<T: Eq + Hash + Ord> ReadHolds<T> {
     }
 }
 
+/// Read holds acquired on behalf of a single query or transaction.
+///
+/// The holds are released when this struct is passed to
+/// [`Coordinator::release_read_holds`](crate::coord::Coordinator::release_read_holds).
+/// If it is instead dropped, e.g. because an error path returned early, the holds
+/// are sent back to the coordinator to be released asynchronously, so that they
+/// never hold back compaction indefinitely.
+#[derive(Debug)]
+pub(crate) struct AcquiredReadHolds {
+    holds: Option<ReadHolds<Timestamp>>,
+    internal_cmd_tx: mpsc::UnboundedSender<Message>,
+}
+
+impl AcquiredReadHolds {
+    fn new(holds: ReadHolds<Timestamp>, internal_cmd_tx: mpsc::UnboundedSender<Message>) -> Self {
+        AcquiredReadHolds {
+            holds: Some(holds),
+            internal_cmd_tx,
+        }
+    }
+
+    /// Returns the read holds owned by this struct.
+    pub fn holds(&self) -> &ReadHolds<Timestamp> {
+        self.holds.as_ref().expect("only taken on release")
+    }
+
+    /// Takes the read holds out of this struct, disarming its `Drop` impl.
+    fn take(mut self) -> ReadHolds<Timestamp> {
+        self.holds.take().expect("only taken on release")
+    }
+}
+
+impl Drop for AcquiredReadHolds {
+    fn drop(&mut self) {
+        let Some(holds) = self.holds.take() else {
+            return;
+        };
+        debug!("read holds dropped without explicit release: {holds:?}");
+        // If the coordinator is gone there is nothing left to release the holds on.
+        let _ = self.internal_cmd_tx.send(Message::ReleaseReadHolds(holds));
+    }
+}
+
 impl crate::coord::Coordinator {
     /// Initialize the storage read policies.
     ///
@@ -238,6 +283,14 @@ impl crate::coord::Coordinator {
                             .holds
                             .update_iter(time.iter().map(|t| (*t, 1)));
                     }
+                    read_capability.initial_since = Some(
+                        self.controller
+                            .compute
+                            .collection(compute_instance, id)
+                            .expect("id does not exist")
+                            .read_capability()
+                            .clone(),
+                    );
                     self.compute_read_capabilities.insert(id, read_capability);
                     compute_policy_updates
                         .entry(compute_instance)
@@ -253,6 +306,14 @@ impl crate::coord::Coordinator {
                         .holds
                         .update_iter(time.iter().map(|t| (*t, 1)));
                 }
+                read_capability.initial_since = Some(
+                    self.controller
+                        .storage
+                        .collection(id)
+                        .expect("id does not exist")
+                        .implied_capability
+                        .clone(),
+                );
                 self.storage_read_capabilities.insert(id, read_capability);
                 storage_policy_updates.push((id, self.storage_read_capabilities[&id].policy()));
             }
@@ -392,12 +453,15 @@ impl crate::coord::Coordinator {
     /// depending on the `precise` argument, we either fall back to acquiring a read hold at
     /// the lowest possible time for that id, or return an error. The returned error contains
     /// those collection sinces that were later than the specified time.
+    ///
+    /// The read holds are released when the returned [`AcquiredReadHolds`] is passed to
+    /// [`Self::release_read_holds`] or dropped.
     pub(crate) fn acquire_read_holds(
         &mut self,
         time: Timestamp,
         id_bundle: &CollectionIdBundle,
         precise: bool,
-    ) -> Result<ReadHolds<Timestamp>, Vec<(Antichain<Timestamp>, CollectionIdBundle)>> {
+    ) -> Result<AcquiredReadHolds, Vec<(Antichain<Timestamp>, CollectionIdBundle)>> {
         let read_holds = self.initialize_read_holds(time, id_bundle);
         if precise {
             // If we are not able to acquire read holds precisely at the specified time (only later), then error out.
@@ -444,7 +508,10 @@ impl crate::coord::Coordinator {
                 .unwrap_or_terminate("cannot fail to set read policy");
         }
 
-        Ok(read_holds)
+        Ok(AcquiredReadHolds::new(
+            read_holds,
+            self.internal_cmd_tx.clone(),
+        ))
     }
 
     /// Attempt to acquire read holds on the indicated collections at the indicated `time`.
@@ -463,11 +530,20 @@ impl crate::coord::Coordinator {
         precise: bool,
     ) -> Result<(), Vec<(Antichain<Timestamp>, CollectionIdBundle)>> {
         let read_holds = self.acquire_read_holds(time, id_bundle, precise)?;
+        self.store_txn_read_holds(session, read_holds);
+        Ok(())
+    }
+
+    /// Arranges for the given read holds to be released at the end of the transaction.
+    pub(crate) fn store_txn_read_holds(
+        &mut self,
+        session: &Session,
+        read_holds: AcquiredReadHolds,
+    ) {
         self.txn_read_holds
             .entry(session.conn_id().clone())
             .or_insert_with(Vec::new)
             .push(read_holds);
-        Ok(())
     }
 
     /// Attempt to update the timestamp of the read holds on the indicated collections from the
@@ -578,13 +654,22 @@ impl crate::coord::Coordinator {
         new_read_holds
     }
 
+    /// Release the given read holds.
+    pub(crate) fn release_read_holds(&mut self, read_holdses: Vec<AcquiredReadHolds>) {
+        let read_holdses = read_holdses
+            .into_iter()
+            .map(AcquiredReadHolds::take)
+            .collect();
+        self.release_read_holds_inner(read_holdses);
+    }
+
     /// Release the given read holds.
     ///
     /// This method relies on a previous call to
     /// `initialize_read_holds`, `acquire_read_holds`, or `update_read_hold` that returned
     /// `ReadHolds`, and its behavior will be erratic if called on anything else,
     /// or if called more than once on the same bundle of read holds.
-    pub(super) fn release_read_holds(&mut self, read_holdses: Vec<ReadHolds<Timestamp>>) {
+    pub(super) fn release_read_holds_inner(&mut self, read_holdses: Vec<ReadHolds<Timestamp>>) {
         // Update STORAGE read policies.
         let mut storage_policy_changes = Vec::new();
         for read_holds in read_holdses.iter() {
@@ -625,3 +710,57 @@ impl crate::coord::Coordinator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_holds(id: GlobalId, time: u64) -> ReadHolds<Timestamp> {
+        let id_bundle = CollectionIdBundle {
+            storage_ids: BTreeSet::from([id]),
+            compute_ids: BTreeMap::new(),
+        };
+        ReadHolds {
+            holds: HashMap::from([(Antichain::from_elem(Timestamp::from(time)), id_bundle)]),
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_acquired_read_holds_released_on_drop() {
+        let (internal_cmd_tx, mut internal_cmd_rx) = mpsc::unbounded_channel();
+
+        // Holds that are dropped, e.g. on an early error return, are sent to the coordinator to
+        // be released.
+        let acquired =
+            AcquiredReadHolds::new(read_holds(GlobalId::User(1), 5), internal_cmd_tx.clone());
+        drop(acquired);
+        let Ok(Message::ReleaseReadHolds(released)) = internal_cmd_rx.try_recv() else {
+            panic!("dropped read holds were not sent for release");
+        };
+        assert_eq!(
+            released.times().collect::<Vec<_>>(),
+            vec![&Antichain::from_elem(Timestamp::from(5u64))]
+        );
+        assert_eq!(
+            released.id_bundle().storage_ids,
+            BTreeSet::from([GlobalId::User(1)])
+        );
+
+        // Holds that are released explicitly are not released again when dropped.
+        let acquired =
+            AcquiredReadHolds::new(read_holds(GlobalId::User(2), 7), internal_cmd_tx.clone());
+        let taken = acquired.take();
+        assert_eq!(
+            taken.id_bundle().storage_ids,
+            BTreeSet::from([GlobalId::User(2)])
+        );
+        assert!(internal_cmd_rx.try_recv().is_err());
+
+        // Holds that outlive the coordinator are dropped quietly.
+        drop(internal_cmd_rx);
+        drop(AcquiredReadHolds::new(
+            read_holds(GlobalId::User(3), 9),
+            internal_cmd_tx,
+        ));
+    }
+}
@@ -39,7 +39,9 @@ use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::{introspection, Coordinator, Message, TargetCluster};
 use crate::error::AdapterError;
 use crate::notice::AdapterNotice;
-use crate::session::{EndTransactionAction, Session, TransactionOps, TransactionStatus, WriteOp};
+use crate::session::{
+    EndTransactionAction, PortalState, Session, TransactionOps, TransactionStatus, WriteOp,
+};
 use crate::util::ClientTransmitter;
 use crate::ExecuteContext;
 
@@ -217,6 +219,10 @@ impl Coordinator {
                     let result = self.sequence_comment_on(ctx.session(), plan).await;
                     ctx.retire(result);
                 }
+                Plan::Undrop(plan) => {
+                    let result = self.sequence_undrop(ctx.session(), plan).await;
+                    ctx.retire(result);
+                }
                 Plan::CopyTo(plan) => {
                     self.sequence_copy_to(ctx, plan, target_cluster).await;
                 }
@@ -239,11 +245,21 @@ impl Coordinator {
                     let result = self.sequence_show_variable(ctx.session(), plan);
                     ctx.retire(result);
                 }
+                Plan::ShowTimestamp => {
+                    let result = self
+                        .sequence_show_timestamp(ctx.session(), ctx.cancel_token())
+                        .await;
+                    ctx.retire(result);
+                }
                 Plan::InspectShard(plan) => {
                     // TODO: Ideally, this await would happen off the main thread.
                     let result = self.sequence_inspect_shard(ctx.session(), plan).await;
                     ctx.retire(result);
                 }
+                Plan::InspectCollection(plan) => {
+                    let result = self.sequence_inspect_collection(ctx.session(), plan);
+                    ctx.retire(result);
+                }
                 Plan::SetVariable(plan) => {
                     let result = self.sequence_set_variable(ctx.session_mut(), plan);
                     ctx.retire(result);
@@ -410,6 +426,10 @@ impl Coordinator {
                     let result = self.sequence_alter_secret(ctx.session(), plan).await;
                     ctx.retire(result);
                 }
+                Plan::AlterSink(plan) => {
+                    let result = self.sequence_alter_sink(plan).await;
+                    ctx.retire(result);
+                }
                 Plan::PurifiedAlterSource {
                     alter_source,
                     subsources,
@@ -441,19 +461,14 @@ impl Coordinator {
                     ctx.retire(Ok(ExecuteResponse::DiscardedTemp));
                 }
                 Plan::DiscardAll => {
-                    let ret = if let TransactionStatus::Started(_) = ctx.session().transaction() {
-                        self.clear_transaction(ctx.session_mut()).await;
-                        self.drop_temp_items(ctx.session().conn_id()).await;
-                        ctx.session_mut().reset();
-                        Ok(ExecuteResponse::DiscardedAll)
-                    } else {
-                        Err(AdapterError::OperationProhibitsTransaction(
-                            "DISCARD ALL".into(),
-                        ))
-                    };
+                    let ret = self
+                        .reset_session(ctx.session_mut())
+                        .await
+                        .map(|()| ExecuteResponse::DiscardedAll);
                     ctx.retire(ret);
                 }
                 Plan::Declare(plan) => {
+                    return_if_err!(ctx.session().check_portal_memory(), ctx);
                     self.declare(ctx, plan.name, plan.stmt, plan.sql, plan.params);
                 }
                 Plan::Fetch(FetchPlan {
@@ -461,6 +476,18 @@ impl Coordinator {
                     count,
                     timeout,
                 }) => {
+                    // Fetching from a cursor that has not started yet would
+                    // buffer a new result set, so it is subject to the limit.
+                    // Cursors that are already running may still be drained.
+                    let not_started = ctx
+                        .session()
+                        .get_portal_unverified(&name)
+                        .map_or(false, |portal| {
+                            matches!(portal.state, PortalState::NotStarted)
+                        });
+                    if not_started {
+                        return_if_err!(ctx.session().check_portal_memory(), ctx);
+                    }
                     let ctx_extra = std::mem::take(ctx.extra_mut());
                     ctx.retire(Ok(ExecuteResponse::Fetch {
                         name,
@@ -684,8 +711,10 @@ impl Coordinator {
         when: QueryWhen,
         real_time_recency_ts: Option<Timestamp>,
     ) -> Result<ExecuteResponse, AdapterError> {
+        let cancel_token = ctx.cancel_token().clone();
         self.sequence_explain_timestamp_finish_inner(
             ctx.session_mut(),
+            &cancel_token,
             format,
             cluster_id,
             optimized_plan,
@@ -748,7 +777,7 @@ impl Coordinator {
                     returning: Vec::new(),
                     max_result_size: catalog.system_config().max_result_size(),
                 };
-                Self::send_diffs(session, diffs_plan)
+                Self::send_diffs(session, diffs_plan, None)
             }
             None => panic!(
                 "tried using sequence_insert_constant on non-constant MirRelationExpr {:?}",
@@ -761,6 +790,7 @@ impl Coordinator {
     pub(crate) fn send_diffs(
         session: &mut Session,
         mut plan: plan::SendDiffsPlan,
+        read_ts: Option<Timestamp>,
     ) -> Result<ExecuteResponse, AdapterError> {
         let affected_rows = {
             let mut affected_rows = Diff::from(0);
@@ -806,6 +836,7 @@ impl Coordinator {
         session.add_transaction_ops(TransactionOps::Writes(vec![WriteOp {
             id: plan.id,
             rows: plan.updates,
+            read_ts,
         }]))?;
         if !plan.returning.is_empty() {
             let finishing = RowSetFinishing {
@@ -29,7 +29,7 @@ use mz_sql::plan::{
     CreateClusterPlan, CreateClusterReplicaPlan, CreateClusterUnmanagedPlan, CreateClusterVariant,
     PlanClusterOption,
 };
-use mz_sql::session::vars::{SystemVars, Var, MAX_REPLICAS_PER_CLUSTER};
+use mz_sql::session::vars::{IsolationLevel, SystemVars, Var, MAX_REPLICAS_PER_CLUSTER};
 
 use crate::catalog::Op;
 use crate::coord::Coordinator;
@@ -41,7 +41,11 @@ impl Coordinator {
     pub(super) async fn sequence_create_cluster(
         &mut self,
         session: &Session,
-        CreateClusterPlan { name, variant }: CreateClusterPlan,
+        CreateClusterPlan {
+            name,
+            variant,
+            transaction_isolation,
+        }: CreateClusterPlan,
     ) -> Result<ExecuteResponse, AdapterError> {
         tracing::debug!("sequence_create_cluster");
 
@@ -74,6 +78,7 @@ impl Coordinator {
         };
         let config = ClusterConfig {
             variant: cluster_variant,
+            transaction_isolation,
         };
         let ops = vec![catalog::Op::CreateCluster {
             id,
@@ -653,26 +658,56 @@ impl Coordinator {
             }
         }
 
+        match &options.transaction_isolation {
+            AlterOptionParameter::Set(level) => new_config.transaction_isolation = Some(*level),
+            AlterOptionParameter::Reset => new_config.transaction_isolation = None,
+            AlterOptionParameter::Unchanged => {}
+        }
+
         if new_config == config {
             return Ok(ExecuteResponse::AlteredObject(ObjectType::Cluster));
         }
 
+        if new_config.variant == config.variant {
+            // Only the default transaction isolation changed, which doesn't affect replicas.
+            let op = catalog::Op::UpdateClusterConfig {
+                id: cluster_id,
+                name: self.catalog.get_cluster(cluster_id).name().to_string(),
+                config: new_config,
+            };
+            self.catalog_transact(Some(session), vec![op]).await?;
+            return Ok(ExecuteResponse::AlteredObject(ObjectType::Cluster));
+        }
+
+        let transaction_isolation = new_config.transaction_isolation;
         match (&config.variant, new_config.variant) {
             (Managed(config), Managed(new_config)) => {
                 self.sequence_alter_cluster_managed_to_managed(
-                    session, cluster_id, config, new_config,
+                    session,
+                    cluster_id,
+                    config,
+                    new_config,
+                    transaction_isolation,
                 )
                 .await?;
             }
             (Unmanaged, Managed(new_config)) => {
                 self.sequence_alter_cluster_unmanaged_to_managed(
-                    session, cluster_id, new_config, options,
+                    session,
+                    cluster_id,
+                    new_config,
+                    transaction_isolation,
+                    options,
                 )
                 .await?;
             }
             (Managed(_), Unmanaged) => {
-                self.sequence_alter_cluster_managed_to_unmanaged(session, cluster_id)
-                    .await?;
+                self.sequence_alter_cluster_managed_to_unmanaged(
+                    session,
+                    cluster_id,
+                    transaction_isolation,
+                )
+                .await?;
             }
             (Unmanaged, Unmanaged) => {
                 self.sequence_alter_cluster_unmanaged_to_unmanaged(
@@ -692,6 +727,7 @@ impl Coordinator {
         cluster_id: ClusterId,
         config: &ClusterVariantManaged,
         new_config: ClusterVariantManaged,
+        transaction_isolation: Option<IsolationLevel>,
     ) -> Result<(), AdapterError> {
         let cluster = self.catalog.get_cluster(cluster_id);
         let name = cluster.name().to_string();
@@ -823,7 +859,10 @@ impl Coordinator {
         ops.push(catalog::Op::UpdateClusterConfig {
             id: cluster_id,
             name,
-            config: ClusterConfig { variant },
+            config: ClusterConfig {
+                variant,
+                transaction_isolation,
+            },
         });
 
         self.catalog_transact(Some(session), ops).await?;
@@ -836,6 +875,7 @@ impl Coordinator {
         session: &Session,
         cluster_id: ClusterId,
         mut new_config: ClusterVariantManaged,
+        transaction_isolation: Option<IsolationLevel>,
         options: PlanClusterOption,
     ) -> Result<(), AdapterError> {
         let cluster = self.catalog.get_cluster(cluster_id);
@@ -967,7 +1007,10 @@ impl Coordinator {
         ops.push(catalog::Op::UpdateClusterConfig {
             id: cluster_id,
             name: cluster_name,
-            config: ClusterConfig { variant },
+            config: ClusterConfig {
+                variant,
+                transaction_isolation,
+            },
         });
 
         self.catalog_transact(Some(session), ops).await?;
@@ -978,6 +1021,7 @@ impl Coordinator {
         &mut self,
         session: &Session,
         cluster_id: ClusterId,
+        transaction_isolation: Option<IsolationLevel>,
     ) -> Result<(), AdapterError> {
         let cluster = self.catalog().get_cluster(cluster_id);
         let mut ops = vec![];
@@ -986,7 +1030,10 @@ impl Coordinator {
         ops.push(catalog::Op::UpdateClusterConfig {
             id: cluster_id,
             name: cluster.name().to_string(),
-            config: ClusterConfig { variant },
+            config: ClusterConfig {
+                variant,
+                transaction_isolation,
+            },
         });
 
         self.catalog_transact(Some(session), ops).await?;
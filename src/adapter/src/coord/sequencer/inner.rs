@@ -23,10 +23,12 @@ use mz_cloud_resources::VpcEndpointConfig;
 use mz_controller_types::{ClusterId, ReplicaId};
 use mz_expr::{CollectionPlan, MirScalarExpr, OptimizedMirRelationExpr, RowSetFinishing};
 use mz_ore::collections::{CollectionExt, HashSet};
+use mz_ore::str::StrExt;
 use mz_ore::task::spawn;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_ore::vec::VecExt;
 use mz_ore::{soft_assert_or_log, task};
+use mz_repr::adt::interval::Interval;
 use mz_repr::adt::jsonb::Jsonb;
 use mz_repr::adt::mz_acl_item::{MzAclItem, PrivilegeMap};
 use mz_repr::explain::json::json_string;
@@ -50,25 +52,27 @@ use mz_catalog::memory::objects::{
 };
 use mz_sql::plan::{
     AlterConnectionAction, AlterConnectionPlan, ExplainSinkSchemaPlan, IndexOption, MutationKind,
-    Params, Plan, PlannedAlterRoleOption, PlannedRoleVariable, QueryWhen, SideEffectingFunc,
-    UpdatePrivilege, VariableValue,
+    Params, Plan, PlanError, PlannedAlterRoleOption, PlannedRoleVariable, QueryWhen,
+    SideEffectingFunc, UpdatePrivilege, VariableValue,
 };
 use mz_sql::session::user::UserKind;
 use mz_sql::session::vars::{
     IsolationLevel, OwnedVarInput, SessionVars, VarInput, CLUSTER_VAR_NAME, DATABASE_VAR_NAME,
     SCHEMA_ALIAS, TRANSACTION_ISOLATION_VAR_NAME,
 };
-use mz_sql::{plan, rbac};
+use mz_sql::{normalize, plan, rbac};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{
     AlterSourceAddSubsourceOptionName, ConnectionOption, ConnectionOptionName,
-    CreateSourceConnection, CreateSourceSubsource, DeferredItemName, PgConfigOption,
-    PgConfigOptionName, ReferencedSubsources, Statement, TransactionMode, WithOptionValue,
+    CreateSourceConnection, CreateSourceSubsource, DeferredItemName, Expr, PgConfigOption,
+    PgConfigOptionName, ReferencedSubsources, Statement, TransactionMode, UnresolvedItemName,
+    WithOptionValue,
 };
 use mz_ssh_util::keys::SshKeyPairSet;
 use mz_storage_client::controller::{CollectionDescription, DataSource, DataSourceOther};
 use mz_storage_types::connections::inline::IntoInlineConnection;
 use mz_storage_types::controller::StorageError;
+use mz_storage_types::sources::Timeline;
 use mz_transform::notice::{OptimizerNoticeApi, OptimizerNoticeKind, RawOptimizerNotice};
 use mz_transform::EmptyStatisticsOracle;
 use timely::progress::Antichain;
@@ -79,7 +83,10 @@ use crate::catalog::{self, Catalog, ConnCatalog, UpdatePrivilegeVariant};
 use crate::command::{ExecuteResponse, Response};
 use crate::coord::appends::{Deferred, DeferredPlan, PendingWriteTxn};
 use crate::coord::id_bundle::CollectionIdBundle;
-use crate::coord::timestamp_selection::{TimestampDetermination, TimestampSource};
+use crate::coord::timeline::TimelineContext;
+use crate::coord::timestamp_selection::{
+    TimestampContext, TimestampDetermination, TimestampProvider, TimestampSource,
+};
 use crate::coord::{
     AlterConnectionValidationReady, Coordinator, CreateConnectionValidationReady, ExecuteContext,
     Message, PendingRead, PendingReadTxn, PendingTxn, PendingTxnResponse, PlanValidity,
@@ -91,7 +98,8 @@ use crate::notice::{AdapterNotice, DroppedInUseIndex};
 use crate::optimize::dataflows::{prep_scalar_expr, EvalTime, ExprPrepStyle};
 use crate::optimize::{self, Optimize};
 use crate::session::{
-    EndTransactionAction, RequireLinearization, Session, TransactionOps, TransactionStatus, WriteOp,
+    CancelToken, EndTransactionAction, RequireLinearization, Session, TransactionOps,
+    TransactionStatus, WriteOp,
 };
 use crate::util::{viewable_variables, ClientTransmitter, ResultExt};
 use crate::{guard_write_critical_section, PeekResponseUnary, TimestampExplanation};
@@ -266,6 +274,19 @@ impl Coordinator {
             if_not_exists_ids,
         } = self.create_source_inner(session, plans).await?;
 
+        // Creating the collections of an ingestion that is too large to run can't fail cleanly,
+        // so fail the statement beforehand.
+        for (source_id, source) in &sources {
+            if let DataSourceDesc::Ingestion(ingestion) = &source.data_source {
+                let ingestion = ingestion
+                    .clone()
+                    .into_inline_connection(self.catalog().state());
+                self.controller
+                    .storage
+                    .check_ingestion_size(*source_id, &ingestion)?;
+            }
+        }
+
         let transact_result = self
             .catalog_transact_with_side_effects(Some(session), ops, |coord| async {
                 for (source_id, source) in sources {
@@ -616,10 +637,12 @@ impl Coordinator {
             owner_id: *ctx.session().current_role_id(),
         }];
 
+        // Determine the initial validity for the table. Do so before the table is created, which
+        // can't be undone if the timestamp oracle is unavailable.
+        let register_ts = self.try_get_local_write_ts().await?.timestamp;
+
         let catalog_result = self
             .catalog_transact_with_side_effects(Some(ctx.session()), ops, |coord| async {
-                // Determine the initial validity for the table.
-                let register_ts = coord.get_local_write_ts().await.timestamp;
                 if let Some(id) = ctx.extra().contents() {
                     coord.set_statement_execution_timestamp(id, register_ts);
                 }
@@ -901,6 +924,138 @@ impl Coordinator {
         Ok(ExecuteResponse::Comment)
     }
 
+    #[instrument(skip_all)]
+    pub(super) async fn sequence_undrop(
+        &mut self,
+        session: &Session,
+        plan::UndropPlan { id }: plan::UndropPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        // Once the restore window lapses the collections are dropped, and the object is gone
+        // for good even if its catalog entry has not been purged yet.
+        if !self.controller.is_retaining_dropped_collection(id) {
+            return Err(CatalogError::UnknownItem(id.to_string()).into());
+        }
+
+        // The dataflows of materialized views soft-dropped before a restart are not re-created at
+        // bootstrap, only their storage collections are. Plan the dataflow up front, as nothing may
+        // fail once the item is restored in the catalog.
+        let Some(dropped) = self
+            .catalog()
+            .dropped_items()
+            .await?
+            .into_iter()
+            .find(|dropped| dropped.item.id == id)
+        else {
+            return Err(CatalogError::UnknownItem(id.to_string()).into());
+        };
+        let CatalogItem::MaterializedView(mv) = self
+            .catalog()
+            .state()
+            .deserialize_item(id, dropped.item.create_sql)?
+        else {
+            return Err(CatalogError::UnknownItem(id.to_string()).into());
+        };
+        let dataflow = if self
+            .controller
+            .compute
+            .collection(mv.cluster_id, id)
+            .is_err()
+        {
+            let compute_instance = self
+                .instance_snapshot(mv.cluster_id)
+                .map_err(|_| CatalogError::UnknownCluster(mv.cluster_id.to_string()))?;
+            let internal_view_id = self.allocate_transient_id()?;
+            let optimizer_config = optimize::OptimizerConfig::from(self.catalog().system_config());
+            let mut optimizer = optimize::materialized_view::Optimizer::new(
+                self.owned_catalog(),
+                compute_instance,
+                id,
+                internal_view_id,
+                mv.desc.iter_names().cloned().collect(),
+                mv.non_null_assertions.clone(),
+                mv.refresh_schedule.clone(),
+                dropped.item.name,
+                optimizer_config,
+            );
+
+            // MIR ⇒ MIR optimization (global)
+            let global_mir_plan = optimizer.optimize(mv.optimized_expr.clone())?;
+            let optimized_plan = global_mir_plan.df_desc().clone();
+
+            // MIR ⇒ LIR lowering and LIR ⇒ LIR optimization (global)
+            let global_lir_plan = optimizer.optimize(global_mir_plan)?;
+            let (physical_plan, metainfo) = global_lir_plan.unapply();
+            let notice_ids = iter::repeat_with(|| self.allocate_transient_id())
+                .take(metainfo.optimizer_notices.len())
+                .collect::<Result<Vec<_>, _>>()?;
+            let metainfo = self
+                .catalog()
+                .render_notices(metainfo, notice_ids, Some(id));
+            Some((optimized_plan, physical_plan, metainfo))
+        } else {
+            None
+        };
+
+        let oid = self.catalog_mut().allocate_oid()?;
+        let op = catalog::Op::UndropItem { id, oid };
+        self.catalog_transact(Some(session), vec![op]).await?;
+
+        let cluster_id = self
+            .controller
+            .restore_dropped_collection(id)
+            .expect("retained collections checked above");
+        if let Some((optimized_plan, physical_plan, metainfo)) = dataflow {
+            let catalog = self.catalog_mut();
+            catalog.set_optimized_plan(id, optimized_plan);
+            catalog.set_physical_plan(id, physical_plan.clone());
+            catalog.set_dataflow_metainfo(id, metainfo);
+
+            // Resume the view where its dataflow left off, as at bootstrap.
+            let mut df_desc = physical_plan;
+            let as_of =
+                self.bootstrap_materialized_view_as_of(&df_desc, cluster_id, &mv.refresh_schedule);
+            df_desc.set_as_of(as_of);
+            let until = mv
+                .refresh_schedule
+                .as_ref()
+                .and_then(|s| s.last_refresh())
+                .and_then(|r| r.try_step_forward());
+            if let Some(until) = until {
+                df_desc.until.meet_assign(&Antichain::from_elem(until));
+            }
+            self.controller
+                .active_compute()
+                .create_dataflow(cluster_id, df_desc)
+                .unwrap_or_terminate("dataflow creation cannot fail");
+        }
+
+        let compaction_window = self
+            .catalog()
+            .get_entry(&id)
+            .item()
+            .initial_logical_compaction_window()
+            .unwrap_or(CompactionWindow::Default);
+        let mut id_bundle = CollectionIdBundle {
+            storage_ids: BTreeSet::from([id]),
+            compute_ids: BTreeMap::new(),
+        };
+        // Without aggressive read hold downgrades, materialized view dataflows also have a
+        // compute read policy.
+        if !self
+            .controller
+            .compute
+            .enable_aggressive_readhold_downgrades()
+        {
+            id_bundle
+                .compute_ids
+                .insert(cluster_id, BTreeSet::from([id]));
+        }
+        self.initialize_read_policies(&id_bundle, compaction_window)
+            .await;
+
+        Ok(ExecuteResponse::Undropped)
+    }
+
     #[instrument(skip_all)]
     pub(super) async fn sequence_drop_objects(
         &mut self,
@@ -1482,6 +1637,47 @@ impl Coordinator {
         Ok(Self::send_immediate_rows(vec![row]))
     }
 
+    #[instrument(skip_all)]
+    pub(super) async fn sequence_show_timestamp(
+        &self,
+        session: &Session,
+        cancel_token: &CancelToken,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        // Report on the timeline of the session's transaction, or that of user tables if the
+        // transaction has not been assigned a timeline yet.
+        let timeline = session
+            .transaction()
+            .timeline()
+            .unwrap_or(Timeline::EpochMilliseconds);
+        // Consult the oracle regardless of the session's isolation level.
+        let oracle_read_ts = self
+            .oracle_read_ts(
+                session,
+                cancel_token,
+                session.vars().transaction_isolation(),
+                &TimelineContext::TimelineDependent(timeline),
+                &QueryWhen::FreshestTableWrite,
+            )
+            .await?;
+
+        // The collections the session's transaction holds reads on, if any.
+        let upper = self.txn_read_holds.get(session.conn_id()).map(|txn_reads| {
+            let mut id_bundle = CollectionIdBundle::default();
+            for read_holds in txn_reads {
+                id_bundle.extend(&read_holds.holds().id_bundle());
+            }
+            Self::largest_not_in_advance_of_upper(&self.least_valid_write(&id_bundle))
+        });
+
+        let session_wall_time = session.pcx().wall_time.try_into().expect("must fit");
+        let row = Row::pack_slice(&[
+            Datum::from(oracle_read_ts),
+            Datum::from(upper),
+            Datum::TimestampTz(session_wall_time),
+        ]);
+        Ok(Self::send_immediate_rows(vec![row]))
+    }
+
     #[instrument(skip_all)]
     pub(super) async fn sequence_inspect_shard(
         &self,
@@ -1506,6 +1702,30 @@ impl Coordinator {
         Ok(Self::send_immediate_rows(vec![jsonb.into_row()]))
     }
 
+    /// Asks the storage cluster of a collection to report its state for checking against the
+    /// controller's view, returning the IDs of the collections whose state was requested. The
+    /// outcome of the check is logged and recorded in metrics.
+    #[instrument(skip_all)]
+    pub(super) fn sequence_inspect_collection(
+        &mut self,
+        session: &Session,
+        plan: plan::InspectCollectionPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        if !session.user().is_internal() {
+            return Err(AdapterError::Unauthorized(
+                rbac::UnauthorizedError::MzSystem {
+                    action: "inspect".into(),
+                },
+            ));
+        }
+        let ids = self.controller.storage.report_collection_state(plan.id)?;
+        let rows = ids
+            .into_iter()
+            .map(|id| Row::pack_slice(&[Datum::String(&id.to_string())]))
+            .collect();
+        Ok(Self::send_immediate_rows(rows))
+    }
+
     #[instrument(skip_all)]
     pub(super) fn sequence_set_variable(
         &self,
@@ -1591,7 +1811,7 @@ impl Coordinator {
     }
 
     pub(super) fn sequence_set_transaction(
-        &self,
+        &mut self,
         session: &mut Session,
         plan: plan::SetTransactionPlan,
     ) -> Result<ExecuteResponse, AdapterError> {
@@ -1603,6 +1823,11 @@ impl Coordinator {
                 }
                 TransactionMode::IsolationLevel(isolation_level) => {
                     self.validate_set_isolation_level(session)?;
+                    // Only release a pinned timestamp once the transaction may be reconfigured,
+                    // so that a rejected statement leaves the transaction's reads consistent.
+                    if session.get_transaction_timestamp_determination().is_some() {
+                        self.unpin_transaction_timestamp(session);
+                    }
 
                     session.vars_mut().set(
                         Some(self.catalog().system_config()),
@@ -1680,12 +1905,12 @@ impl Coordinator {
             Ok((
                 Some(TransactionOps::Peeks {
                     determination,
+                    cluster_id,
                     requires_linearization: RequireLinearization::Required,
-                    ..
                 }),
                 _,
-            )) if ctx.session().vars().transaction_isolation()
-                == &IsolationLevel::StrictSerializable =>
+            )) if self.isolation_level(ctx.session(), cluster_id)
+                == IsolationLevel::StrictSerializable =>
             {
                 let conn_id = ctx.session().conn_id().clone();
                 let pending_read_txn = PendingReadTxn {
@@ -1709,12 +1934,12 @@ impl Coordinator {
             Ok((
                 Some(TransactionOps::Peeks {
                     determination,
+                    cluster_id,
                     requires_linearization: RequireLinearization::Required,
-                    ..
                 }),
                 _,
-            )) if ctx.session().vars().transaction_isolation()
-                == &IsolationLevel::StrongSessionSerializable =>
+            )) if self.isolation_level(ctx.session(), cluster_id)
+                == IsolationLevel::StrongSessionSerializable =>
             {
                 if let Some((timeline, ts)) = determination.timestamp_context.timeline_timestamp() {
                     ctx.session_mut()
@@ -1778,6 +2003,16 @@ impl Coordinator {
 
                         // `rows` can be empty if, say, a DELETE's WHERE clause had 0 results.
                         writes.retain(|WriteOp { rows, .. }| !rows.is_empty());
+
+                        // Writes are assigned their timestamp by the timestamp oracle, so fail
+                        // them instead of queueing them while the oracle is unavailable.
+                        if !writes.is_empty()
+                            && self
+                                .timestamp_oracle_circuit_breaker()
+                                .is_some_and(|breaker| breaker.is_open())
+                        {
+                            return Err(AdapterError::TimestampOracleUnavailableForWrites);
+                        }
                     }
                     TransactionOps::DDL {
                         ops,
@@ -1827,7 +2062,128 @@ impl Coordinator {
                 };
                 ctx.retire(Ok(Self::send_immediate_rows(vec![Row::pack_slice(&[res])])));
             }
+            SideEffectingFunc::MzQuiesceCluster { cluster_name } => {
+                let cluster_id =
+                    return_if_err!(self.catalog().resolve_cluster(&cluster_name), ctx).id;
+                let handle = self
+                    .controller
+                    .quiesce_cluster(cluster_id)
+                    .expect("compute instance does not exist");
+                // The controller resolves the handle once the peeks, subscribes, and copy-tos
+                // outstanding on the cluster have completed, which can take arbitrarily long, so
+                // wait for it off the main coordinator loop. The handle is canceled if the
+                // cluster is unquiesced in the meantime.
+                task::spawn(|| "coord::quiesce_cluster", async move {
+                    let quiesced = Datum::from(handle.await.is_ok());
+                    ctx.retire(Ok(Self::send_immediate_rows(vec![Row::pack_slice(&[
+                        quiesced,
+                    ])])));
+                });
+            }
+            SideEffectingFunc::MzUnquiesceCluster { cluster_name } => {
+                let cluster_id =
+                    return_if_err!(self.catalog().resolve_cluster(&cluster_name), ctx).id;
+                let was_quiescing = self
+                    .controller
+                    .cluster_is_quiescing(cluster_id)
+                    .expect("compute instance does not exist");
+                self.controller
+                    .unquiesce_cluster(cluster_id)
+                    .expect("compute instance does not exist");
+                ctx.retire(Ok(Self::send_immediate_rows(vec![Row::pack_slice(&[
+                    Datum::from(was_quiescing),
+                ])])));
+            }
+            SideEffectingFunc::MzEstimateCompaction {
+                name,
+                retain_history,
+            } => {
+                let result = self
+                    .sequence_estimate_compaction(ctx.session(), name, retain_history)
+                    .await;
+                ctx.retire(result);
+            }
+        }
+    }
+
+    /// Estimates how much data compaction could reclaim in the storage collection named `name` if
+    /// its compaction window was `retain_history`. The compaction window is left unchanged.
+    ///
+    /// The estimate is returned as a JSON object, or NULL if either argument is NULL.
+    async fn sequence_estimate_compaction(
+        &self,
+        session: &Session,
+        name: Option<String>,
+        retain_history: Option<Interval>,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let (Some(name), Some(retain_history)) = (name, retain_history) else {
+            return Ok(Self::send_immediate_rows(vec![Row::pack_slice(&[
+                Datum::Null,
+            ])]));
+        };
+        let name = match mz_sql_parser::parser::parse_expr(&name).map_err(PlanError::from)? {
+            Expr::Identifier(idents) => {
+                normalize::unresolved_item_name(UnresolvedItemName(idents))?
+            }
+            _ => {
+                return Err(PlanError::Unstructured(format!(
+                    "invalid object name: {}",
+                    name.quoted()
+                ))
+                .into())
+            }
+        };
+        let id = self
+            .catalog()
+            .for_session(session)
+            .resolve_item(&name)?
+            .id();
+        if self.controller.storage.collection(id).is_err() {
+            return Err(AdapterError::Unsupported(
+                "compaction estimates for objects without a storage collection",
+            ));
+        }
+        // Compaction windows are durations of wall clock time, which LSNs don't measure.
+        if let TimelineContext::TimelineDependent(Timeline::PostgresLsn(_)) =
+            self.get_timeline_context(id)
+        {
+            return Err(AdapterError::Unsupported(
+                "compaction estimates for sources in TIMELINE 'mz_pg_lsn'",
+            ));
         }
+        let compaction_window = retain_history
+            .duration()
+            .map_err(|e| e.to_string())
+            .and_then(|duration| CompactionWindow::try_from(duration).map_err(|e| e.to_string()))
+            .map_err(|e| PlanError::Unstructured(format!("invalid retention: {e}")))?;
+
+        let estimate = self
+            .controller
+            .estimate_compaction(vec![(id, compaction_window.into())])
+            .await?
+            .remove(&id)
+            .expect("estimate of the requested collection");
+        let jsonb = Jsonb::from_serde_json(serde_json::json!({
+            "estimated_reclaimable_bytes": estimate.estimated_reclaimable_bytes,
+            "estimated_collapsed_timestamps": estimate.estimated_collapsed_timestamps,
+        }))?;
+        Ok(Self::send_immediate_rows(vec![jsonb.into_row()]))
+    }
+
+    /// Returns an error if the identified cluster is quiescing and must not accept new peeks,
+    /// subscribes, or copy-tos.
+    pub(super) fn ensure_cluster_not_quiescing(
+        &self,
+        cluster: &Cluster,
+    ) -> Result<(), AdapterError> {
+        let quiescing = self
+            .controller
+            .cluster_is_quiescing(cluster.id())
+            .expect("compute instance does not exist");
+        if quiescing {
+            return Err(AdapterError::ClusterQuiescing(cluster.name.clone()));
+        }
+        Ok(())
     }
 
     /// Checks to see if the session needs a real time recency timestamp and if so returns
@@ -1835,6 +2191,7 @@ impl Coordinator {
     pub(super) fn recent_timestamp(
         &self,
         session: &Session,
+        isolation_level: &IsolationLevel,
         source_ids: impl Iterator<Item = GlobalId>,
     ) -> Option<BoxFuture<'static, Timestamp>> {
         // Ideally this logic belongs inside of
@@ -1842,7 +2199,7 @@ impl Coordinator {
         // logic in there would make it extremely difficult and inconvenient to pull the waiting off
         // of the main coord thread.
         if session.vars().real_time_recency()
-            && session.vars().transaction_isolation() == &IsolationLevel::StrictSerializable
+            && isolation_level == &IsolationLevel::StrictSerializable
             && !session.contains_read_timestamp()
         {
             Some(self.controller.recent_timestamp(source_ids))
@@ -2021,7 +2378,8 @@ impl Coordinator {
             self.sequence_explain_timestamp_begin_inner(ctx.session(), plan, target_cluster),
             ctx
         );
-        match self.recent_timestamp(ctx.session(), source_ids.iter().cloned()) {
+        let isolation_level = self.isolation_level(ctx.session(), cluster_id);
+        match self.recent_timestamp(ctx.session(), &isolation_level, source_ids.iter().cloned()) {
             Some(fut) => {
                 let validity = PlanValidity {
                     transient_revision: self.catalog().transient_revision(),
@@ -2032,6 +2390,7 @@ impl Coordinator {
                 };
                 let internal_cmd_tx = self.internal_cmd_tx.clone();
                 let conn_id = ctx.session().conn_id().clone();
+                let cancel_token = ctx.cancel_token().clone();
                 self.pending_real_time_recency_timestamp.insert(
                     conn_id.clone(),
                     RealTimeRecencyContext::ExplainTimestamp {
@@ -2044,7 +2403,12 @@ impl Coordinator {
                     },
                 );
                 task::spawn(|| "real_time_recency_explain_timestamp", async move {
-                    let real_time_recency_ts = fut.await;
+                    // Stop fetching the frontiers once the statement is canceled. Its context
+                    // is retired by the coordinator when it handles the cancel request.
+                    let Ok(real_time_recency_ts) = cancel_token.run_until_canceled(fut).await
+                    else {
+                        return;
+                    };
                     // It is not an error for these results to be ready after `internal_cmd_rx` has been dropped.
                     let result = internal_cmd_tx.send(Message::RealTimeRecencyTimestamp {
                         conn_id,
@@ -2057,9 +2421,11 @@ impl Coordinator {
                 });
             }
             None => {
+                let cancel_token = ctx.cancel_token().clone();
                 let result = self
                     .sequence_explain_timestamp_finish_inner(
                         ctx.session_mut(),
+                        &cancel_token,
                         format,
                         cluster_id,
                         optimized_plan,
@@ -2172,11 +2538,13 @@ impl Coordinator {
             }
         }
         let respond_immediately = determination.respond_immediately();
+        let oracle_catch_up_ms = determination.timestamp_context.oracle_catch_up_ms();
         TimestampExplanation {
             determination,
             sources,
             session_wall_time: session.pcx().wall_time,
             respond_immediately,
+            oracle_catch_up_ms,
         }
     }
 
@@ -2184,6 +2552,7 @@ impl Coordinator {
     pub(super) async fn sequence_explain_timestamp_finish_inner(
         &mut self,
         session: &mut Session,
+        cancel_token: &CancelToken,
         format: ExplainFormat,
         cluster_id: ClusterId,
         source: OptimizedMirRelationExpr,
@@ -2201,7 +2570,16 @@ impl Coordinator {
         let source_ids = source.depends_on();
         let timeline_context = self.validate_timeline_context(source_ids.clone())?;
 
-        let oracle_read_ts = self.oracle_read_ts(session, &timeline_context, &when).await;
+        let isolation_level = self.isolation_level(session, cluster_id);
+        let oracle_read_ts = self
+            .oracle_read_ts(
+                session,
+                cancel_token,
+                &isolation_level,
+                &timeline_context,
+                &when,
+            )
+            .await?;
 
         let determination = self
             .sequence_peek_timestamp(
@@ -2602,9 +2980,13 @@ impl Coordinator {
                 diffs
             };
 
-            // We need to clear out the timestamp context so the write doesn't fail due to a
-            // read only transaction.
-            let timestamp_context = ctx.session_mut().take_transaction_timestamp_context();
+            // We need to clear out the read's timestamp determination so the write doesn't fail
+            // due to a read only transaction. We keep it around to carry the read timestamp over
+            // to the write.
+            let determination = ctx.session_mut().take_transaction_timestamp_determination();
+            let read_ts = determination
+                .as_ref()
+                .and_then(|determination| determination.timestamp_context.timestamp().copied());
             // No matter what isolation level the client is using, we must linearize this
             // read. The write will be performed right after this, as part of a single
             // transaction, so the write must have a timestamp greater than or equal to the
@@ -2613,6 +2995,25 @@ impl Coordinator {
             // Note: It's only OK for the write to have a greater timestamp than the read
             // because the write lock prevents any other writes from happening in between
             // the read and write.
+            //
+            // If the determination already shows the read timestamp to be no later than the
+            // oracle's read timestamp, the read is linearized and we can skip the round trip
+            // through the coordinator. Group commit then only needs to take a fresh write
+            // timestamp from the oracle, which is strictly beyond the read timestamp, and
+            // confirms leadership itself.
+            let already_linearized = determination.as_ref().map_or(false, |determination| {
+                match &determination.timestamp_context {
+                    TimestampContext::TimelineTimestamp {
+                        chosen_ts,
+                        oracle_ts: Some(oracle_ts),
+                        ..
+                    } => chosen_ts <= oracle_ts,
+                    _ => false,
+                }
+            });
+            let timestamp_context = determination
+                .filter(|_| !already_linearized)
+                .map(|determination| determination.timestamp_context);
             if let Some(timestamp_context) = timestamp_context {
                 let (tx, rx) = tokio::sync::oneshot::channel();
                 let conn_id = ctx.session().conn_id().clone();
@@ -2662,6 +3063,7 @@ impl Coordinator {
                             returning: returning_rows,
                             max_result_size,
                         },
+                        read_ts,
                     );
                     ctx.retire(result);
                 }
@@ -2899,6 +3301,20 @@ impl Coordinator {
         Ok(ExecuteResponse::AlteredObject(ObjectType::Secret))
     }
 
+    #[instrument(skip_all)]
+    pub(super) async fn sequence_alter_sink(
+        &mut self,
+        plan::AlterSinkPlan { id, action }: plan::AlterSinkPlan,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        match action {
+            plan::AlterSinkAction::ResetProgress => {
+                self.controller.storage.reset_export_progress(id).await?;
+            }
+        }
+
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Sink))
+    }
+
     #[instrument(skip_all)]
     pub(super) async fn sequence_alter_connection(
         &mut self,
@@ -2949,7 +3365,7 @@ impl Coordinator {
     #[instrument(skip_all)]
     async fn sequence_alter_connection_options(
         &mut self,
-        mut ctx: ExecuteContext,
+        ctx: ExecuteContext,
         id: GlobalId,
         set_options: BTreeMap<ConnectionOptionName, Option<WithOptionValue<mz_sql::names::Aug>>>,
         drop_options: BTreeSet<ConnectionOptionName>,
@@ -3081,20 +3497,58 @@ impl Coordinator {
                 },
             );
         } else {
-            let result = self
-                .sequence_alter_connection_stage_finish(ctx.session_mut(), id, conn)
+            self.sequence_alter_connection_stage_finish(ctx, id, conn)
                 .await;
-            ctx.retire(result);
         }
     }
 
+    /// Applies the altered `connection` and retires `ctx`, optionally only once all sources
+    /// using the connection have restarted.
     #[instrument(skip_all)]
     pub(crate) async fn sequence_alter_connection_stage_finish(
+        &mut self,
+        mut ctx: ExecuteContext,
+        id: GlobalId,
+        connection: Connection,
+    ) {
+        let restarts = match self
+            .sequence_alter_connection_stage_finish_inner(ctx.session_mut(), id, connection)
+            .await
+        {
+            Ok(restarts) => restarts,
+            Err(e) => return ctx.retire(Err(e)),
+        };
+        let response = ExecuteResponse::AlteredObject(ObjectType::Connection);
+
+        match restarts {
+            Some(restarts)
+                if self
+                    .catalog()
+                    .system_config()
+                    .alter_connection_wait_for_restarts() =>
+            {
+                task::spawn(
+                    || format!("alter_connection_wait_for_restarts:{id}"),
+                    async move {
+                        // The sender is only dropped if the controller goes away, in which case
+                        // there is nothing left to wait for.
+                        let _ = restarts.await;
+                        ctx.retire(Ok(response));
+                    },
+                );
+            }
+            _ => ctx.retire(Ok(response)),
+        }
+    }
+
+    /// Applies the altered `connection`, returning a receiver that resolves once the sources
+    /// using it have restarted, if there are any.
+    async fn sequence_alter_connection_stage_finish_inner(
         &mut self,
         session: &mut Session,
         id: GlobalId,
         mut connection: Connection,
-    ) -> Result<ExecuteResponse, AdapterError> {
+    ) -> Result<Option<oneshot::Receiver<()>>, AdapterError> {
         match &mut connection.connection {
             mz_storage_types::connections::Connection::Ssh(ref mut ssh) => {
                 // Retain the connection's current SSH keys
@@ -3176,13 +3630,23 @@ impl Coordinator {
             }
         }
 
-        if !sources.is_empty() {
-            self.controller
+        // Restarting all sources at once could overwhelm the upstream system, so the
+        // controller staggers the restarts.
+        let restarts = if !sources.is_empty() {
+            let max_concurrency = self
+                .catalog()
+                .system_config()
+                .alter_connection_restart_concurrency();
+            let restarts = self
+                .controller
                 .storage
-                .alter_collection(sources)
+                .alter_collection_connections(sources, max_concurrency)
                 .await
                 .expect("altering collection after txn must succeed");
-        }
+            Some(restarts)
+        } else {
+            None
+        };
 
         if !sinks.is_empty() {
             self.controller
@@ -3192,7 +3656,7 @@ impl Coordinator {
                 .expect("altering exports after txn must succeed")
         }
 
-        Ok(ExecuteResponse::AlteredObject(ObjectType::Connection))
+        Ok(restarts)
     }
 
     #[instrument(skip_all)]
@@ -3696,6 +4160,59 @@ impl Coordinator {
                 )
                 .await;
             }
+            plan::AlterSourceAction::SetParameterOverrides { set, reset } => {
+                const ALTER_SOURCE: &str = "ALTER SOURCE...SET";
+
+                // Overrides apply to the dataflow of an ingestion, which subsources and
+                // webhook sources don't have.
+                if !matches!(cur_source.data_source, DataSourceDesc::Ingestion(_)) {
+                    return Err(AdapterError::Unsupported(
+                        "parameter overrides for sources without an ingestion",
+                    ));
+                }
+
+                // The overrides are options of the source's definition, so that they are
+                // durable and visible in `SHOW CREATE SOURCE`.
+                let (mut create_source_stmt, resolved_ids) =
+                    create_sql_to_stmt_deps(self, ALTER_SOURCE, cur_entry.create_sql())?;
+                create_source_stmt.with_options.retain(|option| {
+                    !reset.contains(&option.name) && !set.iter().any(|o| o.name == option.name)
+                });
+                create_source_stmt.with_options.extend(set);
+
+                let mut catalog = self.catalog().for_system_session();
+                catalog.mark_id_unresolvable_for_replanning(cur_entry.id());
+
+                // Re-define our source in terms of the amended statement, which also validates
+                // the new options.
+                let plan = match mz_sql::plan::plan(
+                    None,
+                    &catalog,
+                    Statement::CreateSource(create_source_stmt),
+                    &Params::empty(),
+                    &resolved_ids,
+                )? {
+                    Plan::CreateSource(plan) => plan,
+                    _ => unreachable!("create source plan is only valid response"),
+                };
+                let source = Source::new(
+                    id,
+                    plan,
+                    resolved_ids,
+                    cur_source.custom_logical_compaction_window,
+                    cur_source.is_retained_metrics_object,
+                );
+
+                // Updating the source sends the new overrides to the storage controller as part
+                // of the storage configuration. They take effect the next time the source's
+                // dataflow is rendered.
+                let ops = vec![catalog::Op::UpdateItem {
+                    id,
+                    name: cur_entry.name().clone(),
+                    to_item: CatalogItem::Source(source),
+                }];
+                self.catalog_transact(Some(session), ops).await?;
+            }
         }
 
         Ok(ExecuteResponse::AlteredObject(ObjectType::Source))
@@ -256,10 +256,12 @@ impl Coordinator {
                 let ids = self
                     .index_oracle(*cluster_id)
                     .sufficient_collections(resolved_ids.0.iter());
+                // Holds acquired before a failing REFRESH AT time are released when dropped on
+                // the early return.
+                let mut read_holds = Vec::with_capacity(refresh_schedule.ats.len());
                 for refresh_at_ts in &refresh_schedule.ats {
-                    match self.acquire_read_holds_auto_cleanup(session, *refresh_at_ts, &ids, true)
-                    {
-                        Ok(()) => {}
+                    match self.acquire_read_holds(*refresh_at_ts, &ids, true) {
+                        Ok(holds) => read_holds.push(holds),
                         Err(earliest_possible) => {
                             return Err(AdapterError::InputNotReadableAtRefreshAtTime(
                                 *refresh_at_ts,
@@ -268,6 +270,9 @@ impl Coordinator {
                         }
                     };
                 }
+                for holds in read_holds {
+                    self.store_txn_read_holds(session, holds);
+                }
             }
         }
 
@@ -10,6 +10,7 @@
 use std::collections::BTreeSet;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use http::Uri;
 use itertools::Either;
@@ -34,7 +35,7 @@ use crate::command::ExecuteResponse;
 use crate::coord::id_bundle::CollectionIdBundle;
 use crate::coord::peek::{self, PeekDataflowPlan, PlannedPeek};
 use crate::coord::sequencer::inner::{check_log_reads, return_if_err};
-use crate::coord::timeline::TimelineContext;
+use crate::coord::timeline::{LinearizedRead, TimelineContext};
 use crate::coord::timestamp_selection::{
     TimestampContext, TimestampDetermination, TimestampProvider,
 };
@@ -52,6 +53,10 @@ use crate::optimize::{self, Optimize, OverrideFrom};
 use crate::session::{RequireLinearization, Session, TransactionOps, TransactionStatus};
 use crate::statement_logging::StatementLifecycleEvent;
 
+/// The interval at which peeks that wait for a replica to report introspection data check whether
+/// it has.
+const INTROSPECTION_DATA_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 impl Coordinator {
     /// Sequence a peek, determining a timestamp and the most efficient dataflow interaction.
     ///
@@ -229,6 +234,11 @@ impl Coordinator {
                     }
                 }
                 TimestampReadHold(stage) => {
+                    let Some((ctx, stage)) =
+                        self.peek_stage_await_introspection_data(ctx, root_otel_ctx.clone(), stage)
+                    else {
+                        return;
+                    };
                     let next = return_if_err!(
                         self.peek_stage_timestamp_read_hold(ctx.session_mut(), stage)
                             .await,
@@ -332,17 +342,23 @@ impl Coordinator {
                 cluster.name.clone(),
             ));
         }
+        self.ensure_cluster_not_quiescing(cluster)?;
 
         let source_ids = plan.source.depends_on();
         let mut timeline_context = self.validate_timeline_context(source_ids.clone())?;
-        if matches!(timeline_context, TimelineContext::TimestampIndependent)
-            && plan.source.contains_temporal()?
-        {
+        let contains_temporal = plan.source.contains_temporal()?;
+        if matches!(timeline_context, TimelineContext::TimestampIndependent) && contains_temporal {
             // If the source IDs are timestamp independent but the query contains temporal functions,
             // then the timeline context needs to be upgraded to timestamp dependent. This is
             // required because `source_ids` doesn't contain functions.
             timeline_context = TimelineContext::TimestampDependent;
         }
+        self.add_default_timeline_notice(
+            session,
+            &timeline_context,
+            &source_ids,
+            contains_temporal,
+        );
 
         let notices = check_log_reads(
             &catalog,
@@ -388,7 +404,11 @@ impl Coordinator {
             explain_ctx,
         }: PeekStageLinearizeTimestamp,
     ) {
-        let isolation_level = ctx.session.vars().transaction_isolation().clone();
+        let cluster_id = match optimizer.as_ref() {
+            Either::Left(optimizer) => optimizer.cluster_id(),
+            Either::Right(optimizer) => optimizer.cluster_id(),
+        };
+        let isolation_level = self.isolation_level(&ctx.session, cluster_id);
         let linearized_timeline =
             Coordinator::get_linearized_timeline(&isolation_level, &plan.when, &timeline_context);
 
@@ -405,9 +425,52 @@ impl Coordinator {
             explain_ctx,
         };
 
+        // A read-only transaction reads at the timestamp that it pinned on its first read, which
+        // was linearized then, so its later reads don't read the oracle again.
+        if linearized_timeline.is_some() {
+            if let Some(pinned) = ctx
+                .session()
+                .get_pinned_read_only_timestamp_determination(&plan.when)
+            {
+                let stage = PeekStage::RealTimeRecency(build_stage(pinned.oracle_read_ts));
+                // Ignore errors if the coordinator has shut down.
+                let _ = internal_cmd_tx.send(Message::PeekStageReady {
+                    ctx,
+                    otel_ctx: root_otel_ctx,
+                    stage,
+                });
+                return;
+            }
+        }
+
         match linearized_timeline {
             Some(timeline) => {
+                let queued_read = match self.linearized_read() {
+                    Ok(LinearizedRead::Oracle) => None,
+                    Ok(LinearizedRead::Queued(queued_read)) => Some(queued_read),
+                    Ok(LinearizedRead::Serializable) => {
+                        // Don't wait for the unavailable timestamp oracle, and serve the read
+                        // with serializable isolation instead.
+                        ctx.session()
+                            .add_notice(AdapterNotice::TimestampOracleDegraded);
+                        let stage = PeekStage::RealTimeRecency(build_stage(None));
+                        // Ignore errors if the coordinator has shut down.
+                        let _ = internal_cmd_tx.send(Message::PeekStageReady {
+                            ctx,
+                            otel_ctx: root_otel_ctx,
+                            stage,
+                        });
+                        return;
+                    }
+                    Err(e) => return ctx.retire(Err(e)),
+                };
+                let read_timeout = self
+                    .catalog()
+                    .system_config()
+                    .timestamp_oracle_degraded_read_timeout();
                 let shared_oracle = self.get_shared_timestamp_oracle(&timeline);
+                self.metrics.peek_oracle_reads.inc();
+                let cancel_to_retire_seconds = self.metrics.cancel_to_retire_seconds.clone();
 
                 if let Some(shared_oracle) = shared_oracle {
                     // We can do it in an async task, because we can ship off
@@ -415,7 +478,26 @@ impl Coordinator {
 
                     let span = tracing::debug_span!("linearized timestamp task");
                     mz_ore::task::spawn(|| "linearized timestamp task", async move {
-                        let oracle_read_ts = shared_oracle.read_ts().instrument(span).await;
+                        let read_ts = shared_oracle.read_ts().instrument(span);
+                        let read_ts = async {
+                            match queued_read {
+                                None => Ok(read_ts.await),
+                                // The oracle is unavailable, only wait for it for a bounded time.
+                                Some(_queued_read) => tokio::time::timeout(read_timeout, read_ts)
+                                    .await
+                                    .map_err(|_| AdapterError::TimestampOracleReadTimeout {
+                                        timeout: read_timeout,
+                                    }),
+                            }
+                        };
+                        let read_ts = ctx.cancel_token().run_until_canceled(read_ts).await;
+                        let oracle_read_ts = match read_ts {
+                            Ok(Ok(oracle_read_ts)) => oracle_read_ts,
+                            Ok(Err(e)) => return ctx.retire(Err(e)),
+                            Err(_canceled_at) => {
+                                return ctx.retire_canceled(&cancel_to_retire_seconds);
+                            }
+                        };
                         let stage = build_stage(Some(oracle_read_ts));
 
                         let stage = PeekStage::RealTimeRecency(stage);
@@ -430,7 +512,16 @@ impl Coordinator {
                     // Timestamp oracle can't be shipped to an async task, we
                     // have to do it here.
                     let oracle = self.get_timestamp_oracle(&timeline);
-                    let oracle_read_ts = oracle.read_ts().await;
+                    let read_ts = ctx
+                        .cancel_token()
+                        .run_until_canceled(oracle.read_ts())
+                        .await;
+                    let oracle_read_ts = match read_ts {
+                        Ok(oracle_read_ts) => oracle_read_ts,
+                        Err(_canceled_at) => {
+                            return ctx.retire_canceled(&cancel_to_retire_seconds);
+                        }
+                    };
                     let stage = build_stage(Some(oracle_read_ts));
 
                     let stage = PeekStage::RealTimeRecency(stage);
@@ -455,6 +546,62 @@ impl Coordinator {
         }
     }
 
+    /// Waits for the target replica to report introspection data, if the peek only reads its
+    /// introspection collections and the replica has yet to report any.
+    ///
+    /// Peeks only wait for up to `introspection_wait_timeout`. Peeks that don't wait, or stop
+    /// waiting, proceed with a notice that their results may be empty.
+    fn peek_stage_await_introspection_data(
+        &mut self,
+        ctx: ExecuteContext,
+        root_otel_ctx: OpenTelemetryContext,
+        mut stage: PeekStageTimestampReadHold,
+    ) -> Option<(ExecuteContext, PeekStageTimestampReadHold)> {
+        let Some(replica_id) = stage.target_replica else {
+            return Some((ctx, stage));
+        };
+        let cluster_id = match stage.optimizer.as_ref() {
+            Either::Left(optimizer) => optimizer.cluster_id(),
+            Either::Right(optimizer) => optimizer.cluster_id(),
+        };
+        let id_bundle = self
+            .dataflow_builder(cluster_id)
+            .sufficient_collections(&stage.source_ids);
+        if !self.introspection_data_unreported(cluster_id, replica_id, &id_bundle) {
+            return Some((ctx, stage));
+        }
+
+        let now = Instant::now();
+        if let Some(timeout) = ctx.session().vars().introspection_wait_timeout() {
+            let deadline = *stage
+                .introspection_wait_deadline
+                .get_or_insert_with(|| now + timeout);
+            if now < deadline {
+                let poll_interval = INTROSPECTION_DATA_POLL_INTERVAL.min(deadline - now);
+                let internal_cmd_tx = self.internal_cmd_tx.clone();
+                task::spawn(|| "peek_await_introspection_data", async move {
+                    tokio::time::sleep(poll_interval).await;
+                    // Ignore errors if the coordinator has shut down.
+                    let _ = internal_cmd_tx.send(Message::PeekStageReady {
+                        ctx,
+                        otel_ctx: root_otel_ctx,
+                        stage: PeekStage::TimestampReadHold(stage),
+                    });
+                });
+                return None;
+            }
+        }
+
+        let replica = self
+            .catalog()
+            .get_cluster_replica(cluster_id, replica_id)
+            .name
+            .clone();
+        ctx.session()
+            .add_notice(AdapterNotice::IntrospectionDataNotReported { replica });
+        Some((ctx, stage))
+    }
+
     /// Determine a read timestamp and create appropriate read holds.
     #[tracing::instrument(level = "debug", skip_all)]
     async fn peek_stage_timestamp_read_hold(
@@ -468,6 +615,7 @@ impl Coordinator {
             timeline_context,
             oracle_read_ts,
             real_time_recency_ts,
+            introspection_wait_deadline: _,
             optimizer,
             explain_ctx,
         }: PeekStageTimestampReadHold,
@@ -497,6 +645,11 @@ impl Coordinator {
                 (&explain_ctx).into(),
             )
             .await?;
+        // Only enforce `max_staleness` for queries that actually read. `EXPLAIN` reports whether
+        // the bound was satisfied without failing.
+        if let ExplainContext::None = explain_ctx {
+            determination.check_max_staleness(self.catalog().state(), session)?;
+        }
 
         Ok(PeekStageOptimize {
             validity,
@@ -668,10 +821,30 @@ impl Coordinator {
             explain_ctx,
         }: PeekStageRealTimeRecency,
     ) -> Option<(ExecuteContext, PeekStageTimestampReadHold)> {
-        match self.recent_timestamp(ctx.session(), source_ids.iter().cloned()) {
+        let cluster_id = match optimizer.as_ref() {
+            Either::Left(optimizer) => optimizer.cluster_id(),
+            Either::Right(optimizer) => optimizer.cluster_id(),
+        };
+        let isolation_level = self.isolation_level(ctx.session(), cluster_id);
+        // A linearized read without an oracle timestamp was degraded to serializable isolation
+        // because the timestamp oracle is unavailable, and so doesn't use real-time recency.
+        let degraded = oracle_read_ts.is_none()
+            && Coordinator::get_linearized_timeline(
+                &isolation_level,
+                &plan.when,
+                &timeline_context,
+            )
+            .is_some();
+        let recent_timestamp = if degraded {
+            None
+        } else {
+            self.recent_timestamp(ctx.session(), &isolation_level, source_ids.iter().cloned())
+        };
+        match recent_timestamp {
             Some(fut) => {
                 let internal_cmd_tx = self.internal_cmd_tx.clone();
                 let conn_id = ctx.session().conn_id().clone();
+                let cancel_token = ctx.cancel_token().clone();
                 self.pending_real_time_recency_timestamp.insert(
                     conn_id.clone(),
                     RealTimeRecencyContext::Peek {
@@ -687,7 +860,12 @@ impl Coordinator {
                     },
                 );
                 task::spawn(|| "real_time_recency_peek", async move {
-                    let real_time_recency_ts = fut.await;
+                    // Stop fetching the frontiers once the statement is canceled. Its context
+                    // is retired by the coordinator when it handles the cancel request.
+                    let Ok(real_time_recency_ts) = cancel_token.run_until_canceled(fut).await
+                    else {
+                        return;
+                    };
                     // It is not an error for these results to be ready after `internal_cmd_rx` has been dropped.
                     let result = internal_cmd_tx.send(Message::RealTimeRecencyTimestamp {
                         conn_id: conn_id.clone(),
@@ -712,6 +890,7 @@ impl Coordinator {
                     explain_ctx,
                     oracle_read_ts,
                     real_time_recency_ts: None,
+                    introspection_wait_deadline: None,
                 },
             )),
         }
@@ -757,6 +936,10 @@ impl Coordinator {
             }
         }
 
+        if let Some(statement_logging_id) = ctx.extra.contents() {
+            self.set_statement_dependencies(statement_logging_id, &id_bundle);
+        }
+
         if let Some(uuid) = ctx.extra().contents() {
             let ts = determination.timestamp_context.timestamp_or_default();
             let mut transitive_storage_deps = BTreeSet::new();
@@ -956,7 +1139,7 @@ impl Coordinator {
                         source_bundle
                     };
                     let determination = self
-                        .determine_timestamp(
+                        .determine_timestamp_and_sample_wait(
                             session,
                             determine_bundle,
                             when,
@@ -988,7 +1171,7 @@ impl Coordinator {
         if let Some(txn_reads) = self.txn_read_holds.get(session.conn_id()) {
             // Transactions involving peeks will acquire read holds at most once.
             assert_eq!(txn_reads.len(), 1);
-            let txn_reads = &txn_reads[0];
+            let txn_reads = txn_reads[0].holds();
             // Find referenced ids not in the read hold. A reference could be caused by a
             // user specifying an object in a different schema than the first query. An
             // index could be caused by a CREATE INDEX after the transaction started.
@@ -1004,6 +1187,17 @@ impl Coordinator {
                     names: valid_names,
                 });
             }
+            // The transaction's timestamp was chosen for the collections of its first statement,
+            // so validate it against the frontiers of this one.
+            if let Some(timestamp) = determination.timestamp_context.timestamp() {
+                if !self.least_valid_read(source_bundle).less_equal(timestamp) {
+                    coord_bail!(self.generate_timestamp_not_valid_error_msg(
+                        source_bundle,
+                        cluster_id,
+                        *timestamp
+                    ));
+                }
+            }
         } else if let Some((timestamp, bundle)) = potential_read_holds {
             self.acquire_read_holds_auto_cleanup(session, timestamp, bundle, true)
                 .expect("able to acquire read holds at the time that we just got from `determine_timestamp`");
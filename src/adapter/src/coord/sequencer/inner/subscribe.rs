@@ -7,6 +7,11 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+
+use mz_expr::MirScalarExpr;
+use mz_repr::{Datum, ScalarType};
 use mz_sql::plan::{self, QueryWhen};
 use timely::progress::Antichain;
 use tokio::sync::mpsc;
@@ -15,13 +20,15 @@ use tracing::{instrument, Span};
 use crate::active_compute_sink::{ActiveComputeSink, ActiveSubscribe};
 use crate::command::ExecuteResponse;
 use crate::coord::sequencer::inner::{check_log_reads, return_if_err};
+use crate::coord::timestamp_selection::TimestampProvider;
 use crate::coord::{
     Coordinator, Message, PlanValidity, StageResult, Staged, SubscribeFinish, SubscribeOptimizeMir,
     SubscribeStage, SubscribeTimestampOptimizeLir, TargetCluster,
 };
 use crate::error::AdapterError;
 use crate::optimize::Optimize;
-use crate::session::{Session, TransactionOps};
+use crate::resume_token::ResumeToken;
+use crate::session::{RowBatchStream, Session, TransactionOps};
 use crate::util::ResultExt;
 use crate::{optimize, AdapterNotice, ExecuteContext, TimelineContext};
 
@@ -79,9 +86,23 @@ impl Coordinator {
     fn subscribe_validate(
         &mut self,
         session: &mut Session,
-        plan: plan::SubscribePlan,
+        mut plan: plan::SubscribePlan,
         target_cluster: TargetCluster,
     ) -> Result<SubscribeStage, AdapterError> {
+        // A subscribe that resumes from a token starts at the `AS OF` it translates to.
+        let resume_from = plan
+            .resume_from
+            .as_deref()
+            .map(ResumeToken::decode)
+            .transpose()?;
+        if let Some(token) = &resume_from {
+            let (as_of, with_snapshot) = token.as_of();
+            plan.when = QueryWhen::AtTimestamp(MirScalarExpr::literal_ok(
+                Datum::MzTimestamp(as_of),
+                ScalarType::MzTimestamp,
+            ));
+            plan.with_snapshot = with_snapshot;
+        }
         let plan::SubscribePlan { from, when, .. } = &plan;
 
         let cluster = self
@@ -102,6 +123,8 @@ impl Coordinator {
             })
             .transpose()?;
 
+        self.ensure_cluster_not_quiescing(cluster)?;
+
         // SUBSCRIBE AS OF, similar to peeks, doesn't need to worry about transaction
         // timestamp semantics.
         if when == &QueryWhen::Immediately {
@@ -124,11 +147,13 @@ impl Coordinator {
 
         // Determine timeline.
         let mut timeline = self.validate_timeline_context(depends_on.clone())?;
-        if matches!(timeline, TimelineContext::TimestampIndependent) && from.contains_temporal() {
+        let contains_temporal = from.contains_temporal();
+        if matches!(timeline, TimelineContext::TimestampIndependent) && contains_temporal {
             // If the from IDs are timestamp independent but the query contains temporal functions
             // then the timeline context needs to be upgraded to timestamp dependent.
             timeline = TimelineContext::TimestampDependent;
         }
+        self.add_default_timeline_notice(session, &timeline, &depends_on, contains_temporal);
 
         let validity = PlanValidity {
             transient_revision: self.catalog().transient_revision(),
@@ -142,6 +167,7 @@ impl Coordinator {
             validity,
             plan,
             timeline,
+            resume_from,
         }))
     }
 
@@ -153,6 +179,7 @@ impl Coordinator {
             validity,
             plan,
             timeline,
+            resume_from,
         }: SubscribeOptimizeMir,
     ) -> Result<StageResult<Box<SubscribeStage>>, AdapterError> {
         let plan::SubscribePlan {
@@ -169,7 +196,15 @@ impl Coordinator {
         let conn_id = session.conn_id().clone();
         let up_to = up_to
             .as_ref()
-            .map(|expr| Coordinator::evaluate_when(self.catalog().state(), expr.clone(), session))
+            .map(|expr| {
+                Coordinator::evaluate_when(
+                    self.catalog().state(),
+                    expr.clone(),
+                    session,
+                    session.pcx().wall_time,
+                    timeline.timeline(),
+                )
+            })
             .transpose()?;
         let optimizer_config = optimize::OptimizerConfig::from(self.catalog().system_config());
 
@@ -197,6 +232,7 @@ impl Coordinator {
                             validity,
                             plan,
                             timeline,
+                            resume_from,
                             optimizer,
                             global_mir_plan,
                         });
@@ -212,31 +248,85 @@ impl Coordinator {
         ctx: &ExecuteContext,
         SubscribeTimestampOptimizeLir {
             validity,
-            plan,
+            mut plan,
             timeline,
+            resume_from,
             mut optimizer,
             global_mir_plan,
         }: SubscribeTimestampOptimizeLir,
     ) -> Result<StageResult<Box<SubscribeStage>>, AdapterError> {
+        // Evaluate an explicit `AS OF` bound only once, and determine the timestamp from its
+        // value, so that notices about the bound are not emitted a second time.
+        let as_of = plan
+            .when
+            .advance_to_timestamp()
+            .map(|as_of| {
+                Coordinator::evaluate_when(
+                    self.catalog().state(),
+                    as_of,
+                    ctx.session(),
+                    ctx.session().pcx().wall_time,
+                    timeline.timeline(),
+                )
+            })
+            .transpose()?;
+        if let Some(as_of) = as_of {
+            let as_of =
+                MirScalarExpr::literal_ok(Datum::MzTimestamp(as_of), ScalarType::MzTimestamp);
+            plan.when = match plan.when {
+                QueryWhen::AtLeastTimestamp(_) => QueryWhen::AtLeastTimestamp(as_of),
+                _ => QueryWhen::AtTimestamp(as_of),
+            };
+        }
         let plan::SubscribePlan { when, .. } = &plan;
 
+        // Validate explicit `AS OF` and `UP TO` bounds together, before determining a timestamp
+        // for a subscription that could never produce any data.
+        if let (Some(as_of), Some(up_to)) = (as_of, optimizer.up_to()) {
+            if up_to <= as_of {
+                return Err(AdapterError::InvalidAsOfUpTo { as_of, up_to });
+            }
+        }
+
+        // Check that the updates after a resume token are still retained, before the timestamp
+        // selection fails more obscurely.
+        let id_bundle = global_mir_plan.id_bundle(optimizer.cluster_id());
+        if let Some(token) = &resume_from {
+            let since = self.least_valid_read(&id_bundle);
+            if !since.less_equal(&token.as_of().0) {
+                return Err(AdapterError::ResumeTokenBeforeRetention {
+                    resumes_from: token.frontier,
+                    earliest_token: ResumeToken::earliest(&since).map(|token| token.encode()),
+                });
+            }
+        }
+
         // Timestamp selection
-        let oracle_read_ts = self.oracle_read_ts(ctx.session(), &timeline, when).await;
-        let as_of = self
-            .determine_timestamp(
+        let isolation_level = self.isolation_level(ctx.session(), optimizer.cluster_id());
+        let oracle_read_ts = self
+            .oracle_read_ts(
+                ctx.session(),
+                ctx.cancel_token(),
+                &isolation_level,
+                &timeline,
+                when,
+            )
+            .await?;
+        let determination = self
+            .determine_timestamp_and_sample_wait(
                 ctx.session(),
-                &global_mir_plan.id_bundle(optimizer.cluster_id()),
+                &id_bundle,
                 when,
                 optimizer.cluster_id(),
                 &timeline,
                 oracle_read_ts,
                 None,
             )
-            .await?
-            .timestamp_context
-            .timestamp_or_default();
+            .await?;
+        let as_of = determination.timestamp_context.timestamp_or_default();
         if let Some(id) = ctx.extra().contents() {
             self.set_statement_execution_timestamp(id, as_of);
+            self.set_statement_dependencies(id, &id_bundle);
         }
         if let Some(up_to) = optimizer.up_to() {
             if as_of == up_to {
@@ -262,6 +352,7 @@ impl Coordinator {
                         validity,
                         cluster_id: optimizer.cluster_id(),
                         plan,
+                        resume_from,
                         global_lir_plan,
                     });
                     Ok(Box::new(stage))
@@ -281,30 +372,39 @@ impl Coordinator {
                 plan::SubscribePlan {
                     copy_to,
                     emit_progress,
+                    emit_resume_tokens,
                     output,
                     ..
                 },
+            resume_from,
             global_lir_plan,
         }: SubscribeFinish,
     ) -> Result<StageResult<Box<SubscribeStage>>, AdapterError> {
         let sink_id = global_lir_plan.sink_id();
 
         let (tx, rx) = mpsc::unbounded_channel();
+        let buffered_bytes = Arc::new(AtomicU64::new(0));
+        let as_of = global_lir_plan
+            .as_of()
+            .expect("set to Some in an earlier stage");
         let active_subscribe = ActiveSubscribe {
             user: ctx.session().user().clone(),
             conn_id: ctx.session().conn_id().clone(),
             channel: tx,
             emit_progress,
-            as_of: global_lir_plan
-                .as_of()
-                .expect("set to Some in an earlier stage"),
+            emit_resume_tokens,
+            resume_from: resume_from.map(|token| token.frontier),
+            as_of,
             arity: global_lir_plan.sink_desc().from_desc.arity(),
             cluster_id,
             depends_on: validity.dependency_ids,
             start_time: self.now(),
             output,
+            buffered_bytes: Arc::clone(&buffered_bytes),
+            max_buffered_bytes: ctx.session().vars().max_subscribe_buffer_size(),
+            sent_upper: Antichain::from_elem(as_of),
+            as_of_reported: false,
         };
-        active_subscribe.initialize();
 
         let (df_desc, df_meta) = global_lir_plan.unapply();
         // Emit notices.
@@ -329,7 +429,7 @@ impl Coordinator {
         }
 
         let resp = ExecuteResponse::Subscribing {
-            rx,
+            rx: RowBatchStream::new(rx, buffered_bytes),
             ctx_extra: std::mem::take(ctx.extra_mut()),
         };
         let resp = match copy_to {
@@ -12,7 +12,7 @@
 
 use mz_adapter_types::connection::ConnectionId;
 use mz_ore::now::EpochMillis;
-use mz_repr::{GlobalId, ScalarType};
+use mz_repr::{GlobalId, ScalarType, Timestamp};
 use mz_sql::names::{Aug, ResolvedIds};
 use mz_sql::plan::{Params, StatementDesc};
 use mz_sql_parser::ast::display::AstDisplay;
@@ -21,6 +21,7 @@ use mz_sql_parser::ast::{Raw, Statement, StatementKind};
 use crate::active_compute_sink::{ActiveComputeSink, ComputeSinkRemovalReason};
 use crate::catalog::Catalog;
 use crate::coord::appends::BuiltinTableAppendNotify;
+use crate::coord::timestamp_selection::TimestampContext;
 use crate::coord::Coordinator;
 use crate::session::{Session, TransactionStatus};
 use crate::util::describe;
@@ -198,7 +199,64 @@ impl Coordinator {
         session.clear_transaction()
     }
 
+    /// Resets a session, and the coordinator state of its connection, as if the connection had
+    /// just been established, e.g. for `DISCARD ALL` between the clients of a connection pooler.
+    ///
+    /// Unlike [`Coordinator::clear_connection`], this only visits the state that the connection
+    /// can have outside of a transaction block, so its cost is proportional to the state being
+    /// cleared. Fails if the session is in a transaction block, whose read holds must not be
+    /// released underneath it.
+    pub(crate) async fn reset_session(
+        &mut self,
+        session: &mut Session,
+    ) -> Result<(), AdapterError> {
+        match session.transaction() {
+            // `DISCARD ALL` runs in its own, implicit transaction.
+            TransactionStatus::Default | TransactionStatus::Started(_) => {}
+            TransactionStatus::InTransaction(_)
+            | TransactionStatus::InTransactionImplicit(_)
+            | TransactionStatus::Failed(_) => {
+                return Err(AdapterError::OperationProhibitsTransaction(
+                    "DISCARD ALL".into(),
+                ));
+            }
+        }
+
+        let conn_id = session.conn_id().clone();
+        if let Some(txn_reads) = self.txn_read_holds.remove(&conn_id) {
+            self.release_read_holds(txn_reads);
+        }
+        if let Some(conn) = self.active_conns.get_mut(&conn_id) {
+            for watch_set in std::mem::take(&mut conn.determination_waits) {
+                self.controller.cancel_watch_set(watch_set);
+            }
+        }
+        self.drop_temp_items(&conn_id).await;
+        session.reset();
+        Ok(())
+    }
+
+    /// Releases the transaction's pinned read timestamp, and the read holds acquired for it,
+    /// so that the next statement in the transaction determines a fresh timestamp. Returns
+    /// the released timestamp context, if the transaction had one.
+    ///
+    /// Must only be used after verifying that no transaction anomalies will occur if the
+    /// timestamp is released.
+    pub(crate) fn unpin_transaction_timestamp(
+        &mut self,
+        session: &mut Session,
+    ) -> Option<TimestampContext<Timestamp>> {
+        let timestamp_context = session.take_transaction_timestamp_context();
+        if let Some(txn_reads) = self.txn_read_holds.remove(session.conn_id()) {
+            self.release_read_holds(txn_reads);
+        }
+        timestamp_context
+    }
+
     /// Clears coordinator state for a connection.
+    ///
+    /// This is idempotent: state that has already been cleared, including the connection's entry
+    /// in `active_conns`, is skipped.
     pub(crate) async fn clear_connection(&mut self, conn_id: &ConnectionId) {
         self.remove_active_compute_sinks(conn_id, ComputeSinkRemovalReason::Finished)
             .await;
@@ -207,6 +265,13 @@ impl Coordinator {
         if let Some(txn_reads) = self.txn_read_holds.remove(conn_id) {
             self.release_read_holds(txn_reads);
         }
+
+        // Cancel waits for timestamp determinations that nobody will observe anymore.
+        if let Some(conn) = self.active_conns.get_mut(conn_id) {
+            for watch_set in std::mem::take(&mut conn.determination_waits) {
+                self.controller.cancel_watch_set(watch_set);
+            }
+        }
     }
 
     pub(crate) async fn add_active_compute_sink(
@@ -263,10 +328,11 @@ impl Coordinator {
         conn_id: &ConnectionId,
         reason: ComputeSinkRemovalReason,
     ) {
-        let drop_sinks = self
-            .active_conns
-            .get_mut(conn_id)
-            .expect("must exist for active session")
+        // The connection may already have been torn down by another termination path.
+        let Some(conn) = self.active_conns.get(conn_id) else {
+            return;
+        };
+        let drop_sinks = conn
             .drop_sinks
             .iter()
             .map(|sink_id| (*sink_id, reason.clone()))
@@ -280,11 +346,9 @@ impl Coordinator {
         if let Some(sink) = self.active_compute_sinks.remove(&id) {
             let session_type = metrics::session_type_label_value(sink.user());
 
-            self.active_conns
-                .get_mut(sink.connection_id())
-                .expect("must exist for active compute sink")
-                .drop_sinks
-                .remove(&id);
+            if let Some(conn) = self.active_conns.get_mut(sink.connection_id()) {
+                conn.drop_sinks.remove(&id);
+            }
 
             match &sink {
                 ActiveComputeSink::Subscribe(active_subscribe) => {
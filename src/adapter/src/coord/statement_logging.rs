@@ -33,6 +33,7 @@ use crate::statement_logging::{
     SessionHistoryEvent, StatementBeganExecutionRecord, StatementEndedExecutionReason,
     StatementEndedExecutionRecord, StatementLifecycleEvent, StatementPreparedRecord,
 };
+use crate::CollectionIdBundle;
 
 use super::Message;
 
@@ -355,10 +356,12 @@ impl Coordinator {
             execution_timestamp,
             transaction_id,
             transient_index_id,
+            dependencies,
         } = record;
 
         let cluster = cluster_id.map(|id| id.to_string());
         let transient_index_id = transient_index_id.map(|id| id.to_string());
+        let dependency_ids = dependencies.as_ref().map(Self::pack_dependency_ids);
         packer.extend([
             Datum::Uuid(*id),
             Datum::Uuid(*prepared_statement_id),
@@ -377,6 +380,18 @@ impl Coordinator {
                 Some(transient_index_id) => Datum::String(transient_index_id),
             },
         ]);
+        match &dependency_ids {
+            None => packer.push(Datum::Null),
+            Some(dependency_ids) => packer
+                .push_array(
+                    &[ArrayDimension {
+                        lower_bound: 1,
+                        length: dependency_ids.len(),
+                    }],
+                    dependency_ids.iter().map(|id| Datum::String(id)),
+                )
+                .expect("correct array dimensions"),
+        }
         packer
             .push_array(
                 &[ArrayDimension {
@@ -393,6 +408,17 @@ impl Coordinator {
         ));
     }
 
+    /// Renders the collections in `id_bundle` as they are stored in the `dependency_ids` column:
+    /// storage collections by their id, and compute collections as `<cluster id>:<id>`.
+    fn pack_dependency_ids(id_bundle: &CollectionIdBundle) -> Vec<String> {
+        let storage_ids = id_bundle.storage_ids.iter().map(|id| id.to_string());
+        let compute_ids = id_bundle
+            .compute_ids
+            .iter()
+            .flat_map(|(instance, ids)| ids.iter().map(move |id| format!("{instance}:{id}")));
+        storage_ids.chain(compute_ids).collect()
+    }
+
     fn pack_statement_began_execution_update(record: &StatementBeganExecutionRecord) -> Row {
         let mut row = Row::default();
         let mut packer = row.packer();
@@ -563,6 +589,16 @@ impl Coordinator {
         });
     }
 
+    /// Set the collections a statement reads from, once its timestamp has been determined.
+    pub fn set_statement_dependencies(
+        &mut self,
+        id: StatementLoggingId,
+        id_bundle: &CollectionIdBundle,
+    ) {
+        let dependencies = id_bundle.clone();
+        self.mutate_record(id, |record| record.dependencies = Some(dependencies));
+    }
+
     /// Possibly record the beginning of statement execution, depending on a randomly-chosen value.
     /// If the execution beginning was indeed logged, returns a `StatementLoggingId` that must be
     /// passed to `end_statement_execution` to record when it ends.
@@ -646,6 +682,7 @@ impl Coordinator {
             cluster_name: None,
             execution_timestamp: None,
             transient_index_id: None,
+            dependencies: None,
         };
         let mseh_update = Self::pack_statement_began_execution_update(&record);
         self.statement_logging
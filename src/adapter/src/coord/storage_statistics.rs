@@ -0,0 +1,223 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Maintenance of `mz_internal.mz_source_statistics_per_worker` and
+//! `mz_internal.mz_sink_statistics_per_worker`.
+//!
+//! The storage controller forwards the statistics that storage instances report as they arrive,
+//! and the coordinator writes those that changed. Statistics are also periodically reconciled
+//! with those that the storage controller accumulated, in case any were missed.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use mz_repr::{Diff, GlobalId, Row};
+use mz_storage_client::controller::IntrospectionType;
+use mz_storage_client::statistics::{PackableStats, SinkStatisticsUpdate, SourceStatisticsUpdate};
+
+use crate::coord::Coordinator;
+
+/// The statistics the coordinator wrote to the statistics collections.
+#[derive(Debug, Default)]
+pub(crate) struct StorageStatistics {
+    sources: WrittenStatistics,
+    sinks: WrittenStatistics,
+}
+
+/// The rows written to a statistics collection, by object and worker.
+#[derive(Debug, Default)]
+struct WrittenStatistics(BTreeMap<(GlobalId, usize), Row>);
+
+impl WrittenStatistics {
+    /// Replaces the rows of the objects and workers in `rows`, returning the updates that apply
+    /// the change to the collection. Rows that did not change are not written again.
+    fn update(
+        &mut self,
+        rows: impl IntoIterator<Item = ((GlobalId, usize), Row)>,
+    ) -> Vec<(Row, Diff)> {
+        let mut updates = Vec::new();
+        for (key, row) in rows {
+            if self.0.get(&key) == Some(&row) {
+                continue;
+            }
+            updates.push((row.clone(), 1));
+            if let Some(prev) = self.0.insert(key, row) {
+                updates.push((prev, -1));
+            }
+        }
+        updates
+    }
+
+    /// Retracts the rows of `ids`.
+    fn retract(&mut self, ids: &BTreeSet<GlobalId>) -> Vec<(Row, Diff)> {
+        let (retracted, retained) = std::mem::take(&mut self.0)
+            .into_iter()
+            .partition(|((id, _), _)| ids.contains(id));
+        self.0 = retained;
+        retracted.into_values().map(|row| (row, -1)).collect()
+    }
+
+    /// Replaces all rows with `rows`, returning the updates that apply the change to the
+    /// collection.
+    fn reconcile(
+        &mut self,
+        rows: impl IntoIterator<Item = ((GlobalId, usize), Row)>,
+    ) -> Vec<(Row, Diff)> {
+        let rows: BTreeMap<_, _> = rows.into_iter().collect();
+        let mut updates = Vec::new();
+        for (key, prev) in &self.0 {
+            if rows.get(key) != Some(prev) {
+                updates.push((prev.clone(), -1));
+            }
+        }
+        for (key, row) in &rows {
+            if self.0.get(key) != Some(row) {
+                updates.push((row.clone(), 1));
+            }
+        }
+        self.0 = rows;
+        updates
+    }
+}
+
+/// Packs `stats` into rows of its statistics collection, keyed by object and worker.
+fn pack_stats<S: PackableStats>(
+    stats: impl IntoIterator<Item = S>,
+    key: impl Fn(&S) -> (GlobalId, usize),
+) -> Vec<((GlobalId, usize), Row)> {
+    stats
+        .into_iter()
+        .map(|stat| {
+            let mut row = Row::default();
+            stat.pack(row.packer());
+            (key(&stat), row)
+        })
+        .collect()
+}
+
+impl Coordinator {
+    /// Writes the statistics that the storage controller forwarded, skipping those of objects
+    /// that were dropped in the meantime.
+    pub(crate) async fn handle_storage_statistics(
+        &mut self,
+        source_stats: Vec<SourceStatisticsUpdate>,
+        sink_stats: Vec<SinkStatisticsUpdate>,
+    ) {
+        let (source_stats, sink_stats) = self.live_storage_statistics(source_stats, sink_stats);
+        let source_updates = self.storage_statistics.sources.update(source_stats);
+        let sink_updates = self.storage_statistics.sinks.update(sink_stats);
+        self.write_storage_statistics(source_updates, sink_updates)
+            .await;
+    }
+
+    /// Reconciles the statistics collections with the statistics that the storage controller
+    /// accumulated.
+    pub(crate) async fn reconcile_storage_statistics(&mut self) {
+        let (source_stats, sink_stats) = self.controller.storage.statistics_snapshot();
+        let (source_stats, sink_stats) = self.live_storage_statistics(source_stats, sink_stats);
+        let source_updates = self.storage_statistics.sources.reconcile(source_stats);
+        let sink_updates = self.storage_statistics.sinks.reconcile(sink_stats);
+        self.write_storage_statistics(source_updates, sink_updates)
+            .await;
+    }
+
+    /// Retracts the statistics of the dropped `sources` and `sinks`.
+    pub(crate) async fn drop_storage_statistics(
+        &mut self,
+        sources: &[GlobalId],
+        sinks: &[GlobalId],
+    ) {
+        let source_updates = self
+            .storage_statistics
+            .sources
+            .retract(&sources.iter().copied().collect());
+        let sink_updates = self
+            .storage_statistics
+            .sinks
+            .retract(&sinks.iter().copied().collect());
+        self.write_storage_statistics(source_updates, sink_updates)
+            .await;
+    }
+
+    /// Packs the statistics of the objects that are still in the catalog.
+    fn live_storage_statistics(
+        &self,
+        source_stats: Vec<SourceStatisticsUpdate>,
+        sink_stats: Vec<SinkStatisticsUpdate>,
+    ) -> (Vec<((GlobalId, usize), Row)>, Vec<((GlobalId, usize), Row)>) {
+        let catalog = self.catalog();
+        let source_stats = source_stats
+            .into_iter()
+            .filter(|stat| catalog.try_get_entry(&stat.id).is_some());
+        let sink_stats = sink_stats
+            .into_iter()
+            .filter(|stat| catalog.try_get_entry(&stat.id).is_some());
+        (
+            pack_stats(source_stats, |stat| (stat.id, stat.worker_id)),
+            pack_stats(sink_stats, |stat| (stat.id, stat.worker_id)),
+        )
+    }
+
+    async fn write_storage_statistics(
+        &mut self,
+        source_updates: Vec<(Row, Diff)>,
+        sink_updates: Vec<(Row, Diff)>,
+    ) {
+        for (type_, updates) in [
+            (IntrospectionType::StorageSourceStatistics, source_updates),
+            (IntrospectionType::StorageSinkStatistics, sink_updates),
+        ] {
+            if !updates.is_empty() {
+                self.controller
+                    .storage
+                    .record_introspection_updates(type_, updates)
+                    .await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::Datum;
+
+    use super::*;
+
+    fn row(n: i64) -> Row {
+        Row::pack_slice(&[Datum::Int64(n)])
+    }
+
+    #[mz_ore::test]
+    fn test_written_statistics() {
+        let (a, b) = (GlobalId::User(1), GlobalId::User(2));
+        let mut written = WrittenStatistics::default();
+
+        assert_eq!(
+            written.update([((a, 0), row(1)), ((a, 1), row(1))]),
+            vec![(row(1), 1), (row(1), 1)]
+        );
+        // Unchanged statistics are not written again.
+        assert_eq!(written.update([((a, 0), row(1))]), vec![]);
+        // Changed statistics replace the previous ones.
+        assert_eq!(
+            written.update([((a, 0), row(2)), ((b, 0), row(3))]),
+            vec![(row(2), 1), (row(1), -1), (row(3), 1)]
+        );
+
+        // Reconciliation drops the statistics that are gone, and fixes up the others.
+        let mut updates = written.reconcile([((a, 0), row(2)), ((a, 1), row(4))]);
+        updates.sort();
+        assert_eq!(updates, vec![(row(1), -1), (row(3), -1), (row(4), 1)]);
+
+        assert_eq!(
+            written.retract(&BTreeSet::from([a])),
+            vec![(row(2), -1), (row(4), -1)]
+        );
+        assert!(written.0.is_empty());
+    }
+}
@@ -12,6 +12,7 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 use std::future::Future;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
@@ -28,6 +29,7 @@ use mz_sql::names::{ResolvedDatabaseSpecifier, SchemaSpecifier};
 use mz_sql::session::vars::TimestampOracleImpl;
 use mz_storage_types::sources::Timeline;
 use mz_timestamp_oracle::batching_oracle::BatchingTimestampOracle;
+use mz_timestamp_oracle::circuit_breaker::{CircuitBreaker, QueuedRead};
 use mz_timestamp_oracle::postgres_oracle::{
     PostgresTimestampOracle, PostgresTimestampOracleConfig,
 };
@@ -72,6 +74,127 @@ impl TimelineContext {
             Self::TimestampIndependent | Self::TimestampDependent => None,
         }
     }
+
+    /// Merges the contexts of a group of objects into the context of the group, or returns an
+    /// error if the objects belong to incompatible timelines. Objects in different timelines are
+    /// only compatible if `links` translates their timelines into a common timeline, which then
+    /// becomes the timeline of the group.
+    pub fn merge<I>(contexts: I, links: &TimelineLinks) -> Result<TimelineContext, AdapterError>
+    where
+        I: IntoIterator<Item = TimelineContext>,
+    {
+        let mut timeline_contexts: Vec<_> = contexts.into_iter().collect();
+        // If there's more than one timeline, we will not produce meaningful
+        // data to a user. Take, for example, some realtime source and a debezium
+        // consistency topic source. The realtime source uses something close to now
+        // for its timestamps. The debezium source starts at 1 and increments per
+        // transaction. We don't want to choose some timestamp that is valid for both
+        // of these because the debezium source will never get to the same value as the
+        // realtime source's "milliseconds since Unix epoch" value. And even if it did,
+        // it's not meaningful to join just because those two numbers happen to be the
+        // same now.
+        //
+        // Another example: assume two separate debezium consistency topics. Both
+        // start counting at 1 and thus have similarish numbers that probably overlap
+        // a lot. However it's still not meaningful to join those two at a specific
+        // transaction counter number because those counters are unrelated to the
+        // other.
+        let mut timelines: BTreeSet<_> = timeline_contexts
+            .drain_filter_swapping(|timeline_context| timeline_context.contains_timeline())
+            .filter_map(|timeline_context| timeline_context.timeline().cloned())
+            .collect();
+
+        // Unless the timelines are linked, in which case their timestamps are comparable once
+        // translated into their common timeline. A single timeline is left as is, so that
+        // objects that are only ever queried on their own keep using their own timeline.
+        if timelines.len() > 1 {
+            timelines = timelines
+                .iter()
+                .map(|timeline| links.common_timeline(timeline).clone())
+                .collect();
+        }
+
+        // A single or group of objects may contain multiple compatible timeline
+        // contexts. For example `SELECT *, 1, mz_now() FROM t` will contain all
+        // types of contexts. We choose the strongest context level to return back.
+        if timelines.len() > 1 {
+            Err(AdapterError::Unsupported(
+                "multiple timelines within one dataflow",
+            ))
+        } else if timelines.len() == 1 {
+            Ok(TimelineContext::TimelineDependent(timelines.into_element()))
+        } else if timeline_contexts
+            .iter()
+            .contains(&TimelineContext::TimestampDependent)
+        {
+            Ok(TimelineContext::TimestampDependent)
+        } else {
+            Ok(TimelineContext::TimestampIndependent)
+        }
+    }
+}
+
+/// Links between timelines whose timestamps are comparable, for example because they are derived
+/// from the same external clock.
+///
+/// Each linked timeline translates into a common timeline. Objects whose timelines translate into
+/// the same common timeline can be queried together, at a timestamp of the common timeline.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimelineLinks {
+    /// Maps each linked timeline to its common timeline. Common timelines are never linked
+    /// themselves.
+    links: BTreeMap<Timeline, Timeline>,
+}
+
+impl TimelineLinks {
+    /// Links `timeline` to `common`, and with it all timelines that are already linked to
+    /// `timeline`.
+    pub fn link(&mut self, timeline: Timeline, common: Timeline) -> Result<(), String> {
+        let common = self.common_timeline(&common).clone();
+        if common == timeline {
+            return Err(format!(
+                "cannot link timeline {} to itself",
+                timeline.to_string()
+            ));
+        }
+        if let Some(prev) = self.links.get(&timeline) {
+            if *prev != common {
+                return Err(format!(
+                    "timeline {} is already linked to {}",
+                    timeline.to_string(),
+                    prev.to_string()
+                ));
+            }
+        }
+        for linked in self.links.values_mut() {
+            if *linked == timeline {
+                *linked = common.clone();
+            }
+        }
+        self.links.insert(timeline, common);
+        Ok(())
+    }
+
+    /// Returns the common timeline that `timeline` translates into.
+    pub fn common_timeline<'a>(&'a self, timeline: &'a Timeline) -> &'a Timeline {
+        self.links.get(timeline).unwrap_or(timeline)
+    }
+}
+
+impl FromStr for TimelineLinks {
+    type Err = String;
+
+    /// Parses a comma-separated list of `<timeline>=<common timeline>` links.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut links = TimelineLinks::default();
+        for link in s.split(',').map(str::trim).filter(|link| !link.is_empty()) {
+            let Some((timeline, common)) = link.split_once('=') else {
+                return Err(format!("invalid timeline link: {link}"));
+            };
+            links.link(timeline.trim().parse()?, common.trim().parse()?)?;
+        }
+        Ok(links)
+    }
 }
 
 /// Global state for a single timeline.
@@ -92,6 +215,63 @@ impl<T: fmt::Debug> fmt::Debug for TimelineState<T> {
     }
 }
 
+/// How a linearized read obtains its timestamp from the timestamp oracle.
+#[derive(Debug)]
+pub(crate) enum LinearizedRead {
+    /// The timestamp oracle is healthy, and the read waits for it.
+    Oracle,
+    /// The timestamp oracle is unavailable, and the read waits for it for at most
+    /// `timestamp_oracle_degraded_read_timeout`.
+    Queued(QueuedRead),
+    /// The timestamp oracle is unavailable, and the read is served with serializable isolation
+    /// instead.
+    Serializable,
+}
+
+impl LinearizedRead {
+    /// Determines how a linearized read obtains its timestamp while `breaker` tracks the health
+    /// of the timestamp oracle.
+    ///
+    /// While the breaker is open, the read is served with serializable isolation if
+    /// `serializable_reads` is set, and otherwise waits for the oracle unless
+    /// `max_queued_reads` reads are waiting already.
+    fn determine(
+        breaker: Option<&Arc<CircuitBreaker>>,
+        serializable_reads: bool,
+        max_queued_reads: usize,
+    ) -> Result<Self, AdapterError> {
+        let Some(breaker) = breaker.filter(|breaker| breaker.is_open()) else {
+            return Ok(LinearizedRead::Oracle);
+        };
+        if serializable_reads {
+            return Ok(LinearizedRead::Serializable);
+        }
+        breaker
+            .try_queue_read(max_queued_reads)
+            .map(LinearizedRead::Queued)
+            .ok_or(AdapterError::TimestampOracleUnavailableForReads)
+    }
+}
+
+/// Awaits `oracle_call`, a call to a timestamp oracle whose backend is tracked by `breaker`,
+/// unless the breaker is or becomes open. In that case, the call is abandoned so that it does not
+/// block the coordinator, and [`AdapterError::TimestampOracleUnavailableForWrites`] is returned.
+pub(crate) async fn guard_timestamp_oracle<F: Future>(
+    breaker: Option<Arc<CircuitBreaker>>,
+    oracle_call: F,
+) -> Result<F::Output, AdapterError> {
+    let Some(breaker) = breaker else {
+        return Ok(oracle_call.await);
+    };
+    if breaker.is_open() {
+        return Err(AdapterError::TimestampOracleUnavailableForWrites);
+    }
+    tokio::select! {
+        output = oracle_call => Ok(output),
+        () = breaker.opened() => Err(AdapterError::TimestampOracleUnavailableForWrites),
+    }
+}
+
 impl Coordinator {
     pub(crate) fn now(&self) -> EpochMillis {
         (self.catalog().config().now)()
@@ -123,6 +303,29 @@ impl Coordinator {
             .get_shared()
     }
 
+    /// Returns the circuit breaker that tracks the health of the backend of the timestamp
+    /// oracles, if they have one.
+    pub(crate) fn timestamp_oracle_circuit_breaker(&self) -> Option<&Arc<CircuitBreaker>> {
+        match self.timestamp_oracle_impl {
+            TimestampOracleImpl::Postgres => self
+                .pg_timestamp_oracle_config
+                .as_ref()
+                .map(|config| &config.circuit_breaker),
+            TimestampOracleImpl::Catalog => None,
+        }
+    }
+
+    /// Determines how a linearized read obtains its timestamp, degrading gracefully while the
+    /// circuit breaker of the timestamp oracle is open.
+    pub(crate) fn linearized_read(&self) -> Result<LinearizedRead, AdapterError> {
+        let system_vars = self.catalog().system_config();
+        LinearizedRead::determine(
+            self.timestamp_oracle_circuit_breaker(),
+            system_vars.timestamp_oracle_degraded_serializable_reads(),
+            system_vars.timestamp_oracle_degraded_read_queue_size(),
+        )
+    }
+
     /// Returns a reference to the timestamp oracle used for reads and writes
     /// from/to a local input.
     fn get_local_timestamp_oracle(&self) -> &dyn TimestampOracle<Timestamp> {
@@ -156,12 +359,26 @@ impl Coordinator {
             .await
     }
 
+    /// Like [`Coordinator::get_local_write_ts`], but fails rather than blocks while the timestamp
+    /// oracle is unavailable. See [`guard_timestamp_oracle`].
+    pub(crate) async fn try_get_local_write_ts(&mut self) -> Result<WriteTimestamp, AdapterError> {
+        let breaker = self.timestamp_oracle_circuit_breaker().cloned();
+        guard_timestamp_oracle(breaker, self.get_local_write_ts()).await
+    }
+
     /// Peek the current timestamp used for operations on local inputs. Used to determine how much
     /// to block group commits by.
     pub(crate) async fn peek_local_write_ts(&self) -> Timestamp {
         self.get_local_timestamp_oracle().peek_write_ts().await
     }
 
+    /// Like [`Coordinator::peek_local_write_ts`], but fails rather than blocks while the timestamp
+    /// oracle is unavailable. See [`guard_timestamp_oracle`].
+    pub(crate) async fn try_peek_local_write_ts(&self) -> Result<Timestamp, AdapterError> {
+        let breaker = self.timestamp_oracle_circuit_breaker().cloned();
+        guard_timestamp_oracle(breaker, self.peek_local_write_ts()).await
+    }
+
     /// Peek the current timestamp used for operations on local inputs. Used to determine how much
     /// to block group commits by.
     #[instrument(name = "coord::apply_local_write", skip(self))]
@@ -441,43 +658,10 @@ impl Coordinator {
     where
         I: IntoIterator<Item = GlobalId>,
     {
-        let mut timeline_contexts: Vec<_> = self.get_timeline_contexts(ids).into_iter().collect();
-        // If there's more than one timeline, we will not produce meaningful
-        // data to a user. Take, for example, some realtime source and a debezium
-        // consistency topic source. The realtime source uses something close to now
-        // for its timestamps. The debezium source starts at 1 and increments per
-        // transaction. We don't want to choose some timestamp that is valid for both
-        // of these because the debezium source will never get to the same value as the
-        // realtime source's "milliseconds since Unix epoch" value. And even if it did,
-        // it's not meaningful to join just because those two numbers happen to be the
-        // same now.
-        //
-        // Another example: assume two separate debezium consistency topics. Both
-        // start counting at 1 and thus have similarish numbers that probably overlap
-        // a lot. However it's still not meaningful to join those two at a specific
-        // transaction counter number because those counters are unrelated to the
-        // other.
-        let timelines: Vec<_> = timeline_contexts
-            .drain_filter_swapping(|timeline_context| timeline_context.contains_timeline())
-            .collect();
-
-        // A single or group of objects may contain multiple compatible timeline
-        // contexts. For example `SELECT *, 1, mz_now() FROM t` will contain all
-        // types of contexts. We choose the strongest context level to return back.
-        if timelines.len() > 1 {
-            Err(AdapterError::Unsupported(
-                "multiple timelines within one dataflow",
-            ))
-        } else if timelines.len() == 1 {
-            Ok(timelines.into_element())
-        } else if timeline_contexts
-            .iter()
-            .contains(&TimelineContext::TimestampDependent)
-        {
-            Ok(TimelineContext::TimestampDependent)
-        } else {
-            Ok(TimelineContext::TimestampIndependent)
-        }
+        TimelineContext::merge(
+            self.get_timeline_contexts(ids),
+            &self.catalog().state().timeline_links(),
+        )
     }
 
     /// Return the [`TimelineContext`] belonging to a GlobalId, if one exists.
@@ -488,11 +672,35 @@ impl Coordinator {
 
     /// Return the [`TimelineContext`]s belonging to a list of GlobalIds, if any exist.
     fn get_timeline_contexts<I>(&self, ids: I) -> BTreeSet<TimelineContext>
+    where
+        I: IntoIterator<Item = GlobalId>,
+    {
+        self.get_timeline_contexts_by_id(ids)
+            .into_values()
+            .collect()
+    }
+
+    /// Returns the views and materialized views among a list of GlobalIds and their
+    /// dependencies that make a query over them [`TimelineContext::TimestampDependent`].
+    pub(crate) fn timestamp_dependent_ids<I>(&self, ids: I) -> BTreeSet<GlobalId>
+    where
+        I: IntoIterator<Item = GlobalId>,
+    {
+        self.get_timeline_contexts_by_id(ids)
+            .into_iter()
+            .filter(|(_, timeline)| timeline == &TimelineContext::TimestampDependent)
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// Return the [`TimelineContext`] of each of a list of GlobalIds and their dependencies,
+    /// for the ids that belong to one.
+    fn get_timeline_contexts_by_id<I>(&self, ids: I) -> BTreeMap<GlobalId, TimelineContext>
     where
         I: IntoIterator<Item = GlobalId>,
     {
         let mut seen: BTreeSet<GlobalId> = BTreeSet::new();
-        let mut timelines: BTreeSet<TimelineContext> = BTreeSet::new();
+        let mut timelines: BTreeMap<GlobalId, TimelineContext> = BTreeMap::new();
 
         // Recurse through IDs to find all sources and tables, adding new ones to
         // the set until we reach the bottom.
@@ -506,8 +714,22 @@ impl Coordinator {
             if let Some(entry) = self.catalog().try_get_entry(&id) {
                 match entry.item() {
                     CatalogItem::Source(source) => {
-                        timelines
-                            .insert(TimelineContext::TimelineDependent(source.timeline.clone()));
+                        // The subsources and progress collection of a source in the timeline of
+                        // its upstream LSNs are written at LSNs as well.
+                        let timeline = entry
+                            .used_by()
+                            .iter()
+                            .filter_map(|used_by| self.catalog().try_get_entry(used_by))
+                            .filter(|primary| primary.subsources().contains(&id))
+                            .find_map(|primary| match primary.item() {
+                                CatalogItem::Source(primary) => match &primary.timeline {
+                                    timeline @ Timeline::PostgresLsn(_) => Some(timeline),
+                                    _ => None,
+                                },
+                                _ => None,
+                            })
+                            .unwrap_or(&source.timeline);
+                        timelines.insert(id, TimelineContext::TimelineDependent(timeline.clone()));
                     }
                     CatalogItem::Index(index) => {
                         ids.push(index.on);
@@ -516,9 +738,9 @@ impl Coordinator {
                         // If the definition contains a temporal function, the timeline must
                         // be timestamp dependent.
                         if optimized_expr.contains_temporal() {
-                            timelines.insert(TimelineContext::TimestampDependent);
+                            timelines.insert(id, TimelineContext::TimestampDependent);
                         } else {
-                            timelines.insert(TimelineContext::TimestampIndependent);
+                            timelines.insert(id, TimelineContext::TimestampIndependent);
                         }
                         ids.extend(optimized_expr.depends_on());
                     }
@@ -529,16 +751,17 @@ impl Coordinator {
                         // for constant queries. If we choose a timestamp larger than the upper,
                         // which represents the current progress of the view, then the query will
                         // need to block and wait for the materialized view to advance.
-                        timelines.insert(TimelineContext::TimestampDependent);
+                        timelines.insert(id, TimelineContext::TimestampDependent);
                         ids.extend(optimized_expr.depends_on());
                     }
                     CatalogItem::Table(table) => {
-                        timelines.insert(TimelineContext::TimelineDependent(table.timeline()));
+                        timelines.insert(id, TimelineContext::TimelineDependent(table.timeline()));
                     }
                     CatalogItem::Log(_) => {
-                        timelines.insert(TimelineContext::TimelineDependent(
-                            Timeline::EpochMilliseconds,
-                        ));
+                        timelines.insert(
+                            id,
+                            TimelineContext::TimelineDependent(Timeline::EpochMilliseconds),
+                        );
                     }
                     CatalogItem::Sink(_)
                     | CatalogItem::Type(_)
@@ -730,3 +953,113 @@ impl Coordinator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use mz_ore::metrics::MetricsRegistry;
+
+    use crate::notice::AdapterNotice;
+
+    use super::*;
+
+    /// Returns a circuit breaker that was opened by a failing timestamp oracle.
+    fn open_circuit_breaker() -> Arc<CircuitBreaker> {
+        let config =
+            PostgresTimestampOracleConfig::new("postgres://unused", &MetricsRegistry::new());
+        let breaker = Arc::clone(&config.circuit_breaker);
+        while !breaker.is_open() {
+            breaker.record_attempt(Duration::ZERO, false);
+        }
+        breaker
+    }
+
+    #[mz_ore::test]
+    fn test_linearized_read() {
+        // Without a circuit breaker, or while it is closed, reads wait for the oracle.
+        assert!(matches!(
+            LinearizedRead::determine(None, false, 1),
+            Ok(LinearizedRead::Oracle)
+        ));
+        let config =
+            PostgresTimestampOracleConfig::new("postgres://unused", &MetricsRegistry::new());
+        assert!(matches!(
+            LinearizedRead::determine(Some(&config.circuit_breaker), true, 1),
+            Ok(LinearizedRead::Oracle)
+        ));
+
+        // While it is open, reads queue up to the bound.
+        let breaker = open_circuit_breaker();
+        let queued = LinearizedRead::determine(Some(&breaker), false, 1);
+        assert!(matches!(queued, Ok(LinearizedRead::Queued(_))));
+        assert!(matches!(
+            LinearizedRead::determine(Some(&breaker), false, 1),
+            Err(AdapterError::TimestampOracleUnavailableForReads)
+        ));
+        drop(queued);
+        assert!(matches!(
+            LinearizedRead::determine(Some(&breaker), false, 1),
+            Ok(LinearizedRead::Queued(_))
+        ));
+
+        // Or are served with serializable isolation, if allowed.
+        assert!(matches!(
+            LinearizedRead::determine(Some(&breaker), true, 0),
+            Ok(LinearizedRead::Serializable)
+        ));
+    }
+
+    #[mz_ore::test(tokio::test)]
+    async fn test_guard_timestamp_oracle() {
+        // Without a circuit breaker, or while it is closed, oracle calls are awaited.
+        assert!(matches!(
+            guard_timestamp_oracle(None, async { 1 }).await,
+            Ok(1)
+        ));
+        let config =
+            PostgresTimestampOracleConfig::new("postgres://unused", &MetricsRegistry::new());
+        let breaker = Arc::clone(&config.circuit_breaker);
+        assert!(matches!(
+            guard_timestamp_oracle(Some(Arc::clone(&breaker)), async { 1 }).await,
+            Ok(1)
+        ));
+
+        // A stalled oracle call is abandoned once the breaker trips.
+        let stalled = guard_timestamp_oracle(
+            Some(Arc::clone(&breaker)),
+            std::future::pending::<Timestamp>(),
+        );
+        let trip = async {
+            while !breaker.is_open() {
+                breaker.record_attempt(Duration::ZERO, false);
+                tokio::task::yield_now().await;
+            }
+        };
+        let (stalled, ()) = futures::future::join(stalled, trip).await;
+        assert!(matches!(
+            stalled,
+            Err(AdapterError::TimestampOracleUnavailableForWrites)
+        ));
+
+        // While it is open, oracle calls fail fast.
+        assert!(matches!(
+            guard_timestamp_oracle(Some(breaker), async { 1 }).await,
+            Err(AdapterError::TimestampOracleUnavailableForWrites)
+        ));
+    }
+
+    #[mz_ore::test]
+    fn test_timestamp_oracle_degraded_notice() {
+        let notice = AdapterNotice::TimestampOracleDegraded;
+        assert_eq!(
+            notice.to_string(),
+            "the timestamp oracle is unavailable, query was served with serializable isolation \
+             instead of strict serializable"
+        );
+        assert_eq!(
+            AdapterError::TimestampOracleUnavailableForWrites.to_string(),
+            "cannot commit writes while the timestamp oracle is unavailable"
+        );
+    }
+}
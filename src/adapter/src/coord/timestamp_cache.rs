@@ -0,0 +1,203 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Caches timestamp determinations until the write frontiers of collections move.
+//!
+//! Under heavy point-query load against the same objects, consecutive determinations have the
+//! same inputs and thus the same results until a write frontier moves. The controller advances
+//! its frontier epoch whenever it handles write frontier updates, and determinations are cached
+//! for the epoch they were made in only.
+//!
+//! Only determinations that depend on nothing but their [`DeterminationKey`] and the frontiers
+//! of the involved collections are cached. That excludes linearized reads, whose oracle
+//! timestamp differs between reads, reads with an `AS OF`, and reads that depend on the state of
+//! their session.
+
+use std::collections::BTreeMap;
+
+use mz_compute_types::ComputeInstanceId;
+use mz_repr::Timestamp;
+use mz_sql::plan::QueryWhen;
+use mz_sql::session::vars::IsolationLevel;
+
+use crate::coord::id_bundle::CollectionIdBundle;
+use crate::coord::timeline::TimelineContext;
+use crate::coord::timestamp_selection::TimestampDetermination;
+use crate::session::Session;
+
+/// The default number of determinations that are cached within an epoch.
+pub(crate) const DEFAULT_TIMESTAMP_CACHE_CAPACITY: usize = 1024;
+
+/// The inputs of a cacheable timestamp determination, besides the frontiers of the involved
+/// collections.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct DeterminationKey {
+    pub(crate) id_bundle: CollectionIdBundle,
+    pub(crate) when: QueryWhen,
+    pub(crate) isolation_level: IsolationLevel,
+    pub(crate) compute_instance: ComputeInstanceId,
+    pub(crate) timeline_context: TimelineContext,
+}
+
+impl DeterminationKey {
+    /// Returns the key of the determination with the given inputs, if its result depends on
+    /// nothing else than the key and the frontiers of the involved collections.
+    pub(crate) fn cacheable(
+        session: &Session,
+        id_bundle: &CollectionIdBundle,
+        when: &QueryWhen,
+        isolation_level: &IsolationLevel,
+        compute_instance: ComputeInstanceId,
+        timeline_context: &TimelineContext,
+        oracle_read_ts: Option<Timestamp>,
+        real_time_recency_ts: Option<Timestamp>,
+    ) -> Option<Self> {
+        let cacheable = when.advance_to_timestamp().is_none()
+            && oracle_read_ts.is_none()
+            && real_time_recency_ts.is_none()
+            && isolation_level != &IsolationLevel::StrongSessionSerializable
+            && session.vars().max_staleness().is_none()
+            && !(session.vars().read_your_writes() && session.last_write_ts().is_some());
+        cacheable.then(|| DeterminationKey {
+            id_bundle: id_bundle.clone(),
+            when: when.clone(),
+            isolation_level: *isolation_level,
+            compute_instance,
+            timeline_context: timeline_context.clone(),
+        })
+    }
+}
+
+/// Timestamp determinations of the current frontier epoch.
+#[derive(Debug)]
+pub(crate) struct TimestampDeterminationCache {
+    /// The number of determinations that are cached within an epoch.
+    capacity: usize,
+    /// The epoch the cached determinations were made in.
+    epoch: u64,
+    determinations: BTreeMap<DeterminationKey, TimestampDetermination<Timestamp>>,
+}
+
+impl TimestampDeterminationCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        TimestampDeterminationCache {
+            capacity,
+            epoch: 0,
+            determinations: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the determination for `key` that was cached in the frontier `epoch`, if any.
+    pub(crate) fn get(
+        &mut self,
+        key: &DeterminationKey,
+        epoch: u64,
+    ) -> Option<&TimestampDetermination<Timestamp>> {
+        self.advance(epoch);
+        self.determinations.get(key)
+    }
+
+    /// Caches the determination `det` for `key`, which was made in the frontier `epoch`.
+    ///
+    /// Once the cache is full, further determinations are not cached until the epoch advances.
+    pub(crate) fn insert(
+        &mut self,
+        key: DeterminationKey,
+        epoch: u64,
+        det: TimestampDetermination<Timestamp>,
+    ) {
+        self.advance(epoch);
+        if self.determinations.len() < self.capacity || self.determinations.contains_key(&key) {
+            self.determinations.insert(key, det);
+        }
+    }
+
+    /// Forgets the determinations of earlier epochs than `epoch`.
+    fn advance(&mut self, epoch: u64) {
+        if epoch != self.epoch {
+            self.determinations.clear();
+            self.epoch = epoch;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use mz_repr::GlobalId;
+    use mz_storage_types::sources::Timeline;
+    use timely::progress::Antichain;
+
+    use super::*;
+    use crate::coord::timestamp_selection::TimestampContext;
+
+    fn key(id: u64) -> DeterminationKey {
+        DeterminationKey {
+            id_bundle: CollectionIdBundle {
+                storage_ids: BTreeSet::new(),
+                compute_ids: BTreeMap::from([(
+                    ComputeInstanceId::User(1),
+                    BTreeSet::from([GlobalId::User(id)]),
+                )]),
+            },
+            when: QueryWhen::Immediately,
+            isolation_level: IsolationLevel::Serializable,
+            compute_instance: ComputeInstanceId::User(1),
+            timeline_context: TimelineContext::TimestampDependent,
+        }
+    }
+
+    fn determination(ts: u64) -> TimestampDetermination<Timestamp> {
+        let ts = Timestamp::from(ts);
+        TimestampDetermination {
+            timestamp_context: TimestampContext::TimelineTimestamp {
+                timeline: Timeline::EpochMilliseconds,
+                chosen_ts: ts,
+                oracle_ts: None,
+            },
+            since: Antichain::from_elem(Timestamp::from(0)),
+            upper: Antichain::from_elem(ts.step_forward()),
+            largest_not_in_advance_of_upper: ts,
+            oracle_read_ts: None,
+            session_oracle_read_ts: None,
+            max_staleness: None,
+        }
+    }
+
+    fn chosen(det: Option<&TimestampDetermination<Timestamp>>) -> Option<u64> {
+        det.map(|det| det.largest_not_in_advance_of_upper.into())
+    }
+
+    #[mz_ore::test]
+    fn test_timestamp_determination_cache() {
+        let mut cache = TimestampDeterminationCache::new(2);
+        assert_eq!(chosen(cache.get(&key(1), 1)), None);
+        cache.insert(key(1), 1, determination(10));
+
+        // Repeated determinations within the epoch are hits, for the same key only.
+        assert_eq!(chosen(cache.get(&key(1), 1)), Some(10));
+        assert_eq!(chosen(cache.get(&key(1), 1)), Some(10));
+        assert_eq!(chosen(cache.get(&key(2), 1)), None);
+        let mut strict = key(1);
+        strict.isolation_level = IsolationLevel::StrictSerializable;
+        assert_eq!(chosen(cache.get(&strict, 1)), None);
+
+        // A frontier advance starts a new epoch, in which earlier determinations are misses.
+        assert_eq!(chosen(cache.get(&key(1), 2)), None);
+        cache.insert(key(1), 2, determination(20));
+        assert_eq!(chosen(cache.get(&key(1), 2)), Some(20));
+
+        // Determinations beyond the capacity are not cached.
+        cache.insert(key(2), 2, determination(20));
+        cache.insert(key(3), 2, determination(20));
+        assert_eq!(chosen(cache.get(&key(2), 2)), Some(20));
+        assert_eq!(chosen(cache.get(&key(3), 2)), None);
+    }
+}
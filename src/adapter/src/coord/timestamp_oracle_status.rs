@@ -0,0 +1,95 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::time::Duration;
+
+use mz_ore::now::to_datetime;
+use mz_ore::task::spawn;
+use mz_repr::{Datum, Diff, Row};
+use mz_storage_types::sources::Timeline;
+use mz_timestamp_oracle::circuit_breaker::CircuitBreakerState;
+
+use crate::coord::Coordinator;
+
+use super::Message;
+
+/// The interval at which the timestamp oracle is probed while its circuit breaker is open.
+const PROBE_INTERVAL: Duration = Duration::from_secs(1);
+
+impl Coordinator {
+    /// Spawns a task that reports the state of the circuit breaker of the timestamp oracle to
+    /// `mz_internal.mz_timestamp_oracle_status`.
+    ///
+    /// While the circuit breaker is open, the task also probes the oracle, so that the breaker
+    /// observes its recovery even when no queries are waiting for it.
+    pub(crate) fn spawn_timestamp_oracle_status_task(&self) {
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        let now = self.catalog().config().now.clone();
+        let pack_status = move |state: CircuitBreakerState| {
+            let changed_at = to_datetime(now()).try_into().expect("must fit");
+            Row::pack_slice(&[
+                Datum::String(state.as_str()),
+                Datum::TimestampTz(changed_at),
+            ])
+        };
+
+        let (Some(breaker), Some(oracle)) = (
+            self.timestamp_oracle_circuit_breaker().cloned(),
+            self.get_shared_timestamp_oracle(&Timeline::EpochMilliseconds),
+        ) else {
+            // Without a circuit breaker, the oracle is always considered available.
+            let status = pack_status(CircuitBreakerState::Closed);
+            let _ = internal_cmd_tx.send(Message::TimestampOracleStatus(vec![(status, 1)]));
+            return;
+        };
+
+        spawn(|| "timestamp_oracle_status", async move {
+            let mut state_rx = breaker.subscribe();
+            let mut prev_status: Option<Row> = None;
+            loop {
+                let state = *state_rx.borrow_and_update();
+                let status = pack_status(state);
+                let updates: Vec<(Row, Diff)> = prev_status
+                    .replace(status.clone())
+                    .map(|prev_status| (prev_status, -1))
+                    .into_iter()
+                    .chain([(status, 1)])
+                    .collect();
+                if internal_cmd_tx
+                    .send(Message::TimestampOracleStatus(updates))
+                    .is_err()
+                {
+                    // The coordinator has shut down.
+                    return;
+                }
+
+                match state {
+                    CircuitBreakerState::Closed => {
+                        if state_rx.changed().await.is_err() {
+                            return;
+                        }
+                    }
+                    CircuitBreakerState::Open => loop {
+                        let probe = async {
+                            oracle.read_ts().await;
+                            tokio::time::sleep(PROBE_INTERVAL).await;
+                        };
+                        tokio::select! {
+                            changed = state_rx.changed() => match changed {
+                                Ok(()) => break,
+                                Err(_) => return,
+                            },
+                            () = probe => {}
+                        }
+                    },
+                }
+            }
+        });
+    }
+}
@@ -9,33 +9,50 @@
 
 //! Logic for selecting timestamps for various operations on collections.
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use chrono::{DateTime, NaiveDateTime, Utc};
 use differential_dataflow::lattice::Lattice;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use mz_adapter_types::connection::ConnectionId;
+use mz_catalog::memory::objects::{CatalogItem, DataSourceDesc};
 use mz_compute_types::ComputeInstanceId;
 use mz_expr::MirScalarExpr;
 use mz_ore::cast::CastLossy;
+use mz_ore::now::NowFn;
+use mz_ore::str::StrExt;
 use mz_repr::explain::ExprHumanizer;
 use mz_repr::{GlobalId, RowArena, ScalarType, Timestamp, TimestampManipulation};
 use mz_sql::plan::QueryWhen;
-use mz_sql::session::vars::IsolationLevel;
+use mz_sql::session::vars::{IsolationLevel, TRANSACTION_ISOLATION_VAR_NAME};
+use mz_storage_types::sources::postgres::{format_lsn, parse_lsn};
 use mz_storage_types::sources::Timeline;
+use rand::distributions::{Bernoulli, Distribution};
+use rand::{thread_rng, Rng};
 use serde::{Deserialize, Serialize};
 use timely::progress::frontier::AntichainRef;
 use timely::progress::{Antichain, Timestamp as TimelyTimestamp};
+use tokio::sync::mpsc::UnboundedSender;
 use tracing::{event, Level};
 
 use crate::catalog::CatalogState;
+use crate::coord::catalog_oracle::InMemoryTimestampOracle;
 use crate::coord::id_bundle::CollectionIdBundle;
-use crate::coord::timeline::TimelineContext;
-use crate::coord::Coordinator;
+use crate::coord::timeline::{LinearizedRead, TimelineContext};
+use crate::coord::timestamp_cache::DeterminationKey;
+use crate::coord::{Coordinator, Message};
+use crate::notice::AdapterNotice;
 use crate::optimize::dataflows::{prep_scalar_expr, ExprPrepStyle};
-use crate::session::Session;
+use crate::session::{CancelToken, Session};
 use crate::AdapterError;
 
 /// The timeline and timestamp context of a read.
+///
+/// Serialized as part of a [`TimestampDetermination`], whose version covers its shape.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TimestampContext<T> {
     /// Read is executed in a specific timeline with a specific timestamp.
@@ -51,6 +68,7 @@ pub enum TimestampContext<T> {
         /// The timestamp that would have been chosen for the read by the
         /// (linearized) timestamp oracle). In most cases this will be picked as
         /// the `chosen_ts`.
+        #[serde(default)]
         oracle_ts: Option<T>,
     },
     /// Read is execute without a timeline or timestamp.
@@ -78,6 +96,8 @@ impl<T: TimestampManipulation> TimestampContext<T> {
             }
             TimelineContext::TimestampDependent => {
                 // We default to the `Timeline::EpochMilliseconds` timeline if one doesn't exist.
+                // Sessions may ask to be notified of this, see
+                // `Coordinator::add_default_timeline_notice`.
                 Self::TimelineTimestamp {
                     timeline: transaction_timeline.unwrap_or(Timeline::EpochMilliseconds),
                     chosen_ts,
@@ -130,6 +150,116 @@ impl<T: TimestampManipulation> TimestampContext<T> {
     pub fn antichain(&self) -> Antichain<T> {
         Antichain::from_elem(self.timestamp_or_default())
     }
+
+    /// Whether results at the chosen timestamp must be held back until the timestamp oracle has
+    /// caught up to it, to linearize the read.
+    pub fn requires_linearization_wait(&self) -> bool {
+        match self {
+            Self::TimelineTimestamp {
+                chosen_ts,
+                oracle_ts: Some(oracle_ts),
+                ..
+            } => chosen_ts > oracle_ts,
+            _ => false,
+        }
+    }
+}
+
+impl TimestampContext<Timestamp> {
+    /// The number of milliseconds that the timestamp oracle must advance by before results at
+    /// the chosen timestamp can be returned, if the read requires a linearization wait.
+    pub fn oracle_catch_up_ms(&self) -> Option<u64> {
+        match self {
+            Self::TimelineTimestamp {
+                chosen_ts,
+                oracle_ts: Some(oracle_ts),
+                ..
+            } if self.requires_linearization_wait() => {
+                Some(chosen_ts.saturating_sub(*oracle_ts).into())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// How fresh the data that a query reads from a collection must be.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CollectionFreshness {
+    /// Reads of the collection are linearized with the timestamp oracle, if the isolation level
+    /// requires it.
+    #[default]
+    Linearized,
+    /// The collection only ever receives appends, so it can also be read as of the latest time
+    /// it is complete for, ahead of the timestamp oracle.
+    AppendOnly,
+}
+
+/// The inputs of a timestamp determination that describe the session issuing the query.
+///
+/// Usually built from a live [`Session`], but determinations that don't belong to one, like
+/// those explained for a hypothetical session, can provide the inputs directly.
+#[derive(Debug, Clone)]
+pub struct TimestampDeterminationContext<'a> {
+    /// The connection issuing the query, if any. Only used for logging.
+    pub conn_id: Option<ConnectionId>,
+    /// The wall time of the transaction the query belongs to, which `now()` evaluates to. Only
+    /// `None` outside of a transaction, where it must not be needed.
+    pub wall_time: Option<DateTime<Utc>>,
+    /// Whether real time recency is enabled.
+    pub real_time_recency: bool,
+    /// How far ahead of the inputs' uppers a real time recency timestamp may be, if bounded.
+    pub real_time_recency_timeout: Option<Duration>,
+    /// The session's timestamp oracles by timeline, which bound reads under Strong Session
+    /// Serializable, if any.
+    pub session_oracles:
+        Option<&'a BTreeMap<Timeline, InMemoryTimestampOracle<Timestamp, NowFn<Timestamp>>>>,
+    /// Whether the query must observe the session's own writes.
+    pub read_your_writes: bool,
+    /// The largest timestamp at which a write of the session was applied, if any.
+    pub last_write_ts: Option<Timestamp>,
+    /// The session's `max_staleness`, if set.
+    pub max_staleness: Option<Duration>,
+    /// Where to send notices about the determination. Notices are dropped if this is `None`.
+    pub notices: Option<UnboundedSender<AdapterNotice>>,
+}
+
+impl<'a> From<&'a Session> for TimestampDeterminationContext<'a> {
+    fn from(session: &'a Session) -> Self {
+        TimestampDeterminationContext {
+            conn_id: Some(session.conn_id().clone()),
+            wall_time: session.transaction().inner().map(|txn| txn.pcx.wall_time),
+            real_time_recency: session.vars().real_time_recency(),
+            real_time_recency_timeout: session.vars().real_time_recency_timeout(),
+            session_oracles: Some(session.timestamp_oracles()),
+            read_your_writes: session.vars().read_your_writes(),
+            last_write_ts: session.last_write_ts().copied(),
+            max_staleness: session.vars().max_staleness(),
+            notices: Some(session.retain_notice_transmitter()),
+        }
+    }
+}
+
+impl TimestampDeterminationContext<'_> {
+    /// Returns the wall time of the transaction the query belongs to.
+    ///
+    /// Panics if the query does not belong to a transaction.
+    fn wall_time(&self) -> DateTime<Utc> {
+        self.wall_time.expect("no active transaction")
+    }
+
+    /// Returns the read timestamp of the session's oracle for `timeline`, if it has one.
+    fn session_oracle_read_ts(&self, timeline: &Timeline) -> Option<Timestamp> {
+        self.session_oracles?
+            .get(timeline)
+            .map(|oracle| oracle.read_ts())
+    }
+
+    /// Sends `notice` to the session, if there is one.
+    fn add_notice(&self, notice: AdapterNotice) {
+        if let Some(notices) = &self.notices {
+            let _ = notices.send(notice);
+        }
+    }
 }
 
 #[async_trait(?Send)]
@@ -202,6 +332,52 @@ impl TimestampProvider for Coordinator {
             .expect("id does not exist")
             .write_frontier
     }
+
+    /// The read capability a compute collection had when its read policy was initialized.
+    fn compute_initial_read_capability<'a>(
+        &'a self,
+        _instance: ComputeInstanceId,
+        id: GlobalId,
+    ) -> Option<&'a Antichain<Timestamp>> {
+        self.compute_read_capabilities
+            .get(&id)
+            .and_then(|capability| capability.initial_since.as_ref())
+    }
+
+    /// The implied capability a storage collection had when its read policy was initialized.
+    fn storage_initial_implied_capability<'a>(
+        &'a self,
+        id: GlobalId,
+    ) -> Option<&'a Antichain<Timestamp>> {
+        self.storage_read_capabilities
+            .get(&id)
+            .and_then(|capability| capability.initial_since.as_ref())
+    }
+
+    /// Sources that never produce retractions are append-only, if enabled.
+    fn collection_freshness(&self, id: GlobalId) -> CollectionFreshness {
+        if !self
+            .catalog()
+            .system_config()
+            .enable_append_only_read_at_upper()
+        {
+            return CollectionFreshness::Linearized;
+        }
+        let Some(CatalogItem::Source(source)) = self.catalog().try_get_entry(&id).map(|e| e.item())
+        else {
+            return CollectionFreshness::Linearized;
+        };
+        match &source.data_source {
+            DataSourceDesc::Ingestion(ingestion) if ingestion.desc.monotonic() => {
+                CollectionFreshness::AppendOnly
+            }
+            DataSourceDesc::Webhook { .. } => CollectionFreshness::AppendOnly,
+            DataSourceDesc::Ingestion(_)
+            | DataSourceDesc::Introspection(_)
+            | DataSourceDesc::Progress
+            | DataSourceDesc::Source => CollectionFreshness::Linearized,
+        }
+    }
 }
 
 #[async_trait(?Send)]
@@ -226,6 +402,47 @@ pub trait TimestampProvider {
     fn storage_implied_capability<'a>(&'a self, id: GlobalId) -> &'a Antichain<Timestamp>;
     fn storage_write_frontier<'a>(&'a self, id: GlobalId) -> &'a Antichain<Timestamp>;
 
+    /// Returns the read capability of the compute collection when it was first made readable, if
+    /// known. Used to tell times that were compacted away from times before the collection existed.
+    fn compute_initial_read_capability<'a>(
+        &'a self,
+        instance: ComputeInstanceId,
+        id: GlobalId,
+    ) -> Option<&'a Antichain<Timestamp>>;
+    /// Returns the implied capability of the storage collection when it was first made readable,
+    /// if known. Used to tell times that were compacted away from times before the collection
+    /// existed.
+    fn storage_initial_implied_capability<'a>(
+        &'a self,
+        id: GlobalId,
+    ) -> Option<&'a Antichain<Timestamp>>;
+
+    /// Returns how fresh the data read from the collection `id` must be.
+    fn collection_freshness(&self, _id: GlobalId) -> CollectionFreshness {
+        CollectionFreshness::Linearized
+    }
+
+    /// Returns the collections in `id_bundle` that are [`CollectionFreshness::AppendOnly`].
+    fn append_only_collections(&self, id_bundle: &CollectionIdBundle) -> CollectionIdBundle {
+        let append_only =
+            |id: &GlobalId| self.collection_freshness(*id) == CollectionFreshness::AppendOnly;
+        CollectionIdBundle {
+            storage_ids: id_bundle
+                .storage_ids
+                .iter()
+                .copied()
+                .filter(append_only)
+                .collect(),
+            compute_ids: id_bundle
+                .compute_ids
+                .iter()
+                .map(|(instance, ids)| {
+                    (*instance, ids.iter().copied().filter(append_only).collect())
+                })
+                .collect(),
+        }
+    }
+
     fn get_timeline(timeline_context: &TimelineContext) -> Option<Timeline> {
         let timeline = match timeline_context {
             TimelineContext::TimelineDependent(timeline) => Some(timeline.clone()),
@@ -279,7 +496,7 @@ pub trait TimestampProvider {
     async fn determine_timestamp_for(
         &self,
         catalog: &CatalogState,
-        session: &Session,
+        ctx: &TimestampDeterminationContext<'_>,
         id_bundle: &CollectionIdBundle,
         when: &QueryWhen,
         compute_instance: ComputeInstanceId,
@@ -328,7 +545,13 @@ pub trait TimestampProvider {
         let mut candidate = Timestamp::minimum();
 
         if let Some(timestamp) = when.advance_to_timestamp() {
-            let ts = Coordinator::evaluate_when(catalog, timestamp, session)?;
+            let ts = Coordinator::evaluate_when_with(
+                timestamp,
+                &catalog.for_system_session(),
+                &|notice| ctx.add_notice(notice),
+                ctx.wall_time(),
+                timeline.as_ref(),
+            )?;
             candidate.join_assign(&ts);
         }
 
@@ -360,21 +583,62 @@ pub trait TimestampProvider {
             candidate.join_assign(&largest_not_in_advance_of_upper);
         }
 
+        // Append-only inputs can be read as of their upper without reordering the query with
+        // respect to earlier reads of them, so under linearized isolation levels we advance
+        // towards their upper, while the oracle timestamp still bounds the candidate for the
+        // other inputs. A candidate beyond the oracle timestamp is linearized by waiting for the
+        // oracle to catch up to it, so this never weakens the isolation level. To not make the
+        // query wait on its other inputs for longer than it would anyway, the candidate is only
+        // advanced up to their upper. Serializable queries already read all their inputs as of
+        // their upper, and gain nothing from this.
+        if linearized_timeline.is_some() && when.can_advance_to_upper() {
+            let append_only = self.append_only_collections(id_bundle);
+            if !append_only.is_empty() {
+                let append_only_upper = Coordinator::largest_not_in_advance_of_upper(
+                    &self.least_valid_write(&append_only),
+                );
+                let linearized = id_bundle.difference(&append_only);
+                let advance_to = if linearized.is_empty() {
+                    append_only_upper
+                } else {
+                    let linearized_upper = Coordinator::largest_not_in_advance_of_upper(
+                        &self.least_valid_write(&linearized),
+                    );
+                    std::cmp::min(
+                        append_only_upper,
+                        std::cmp::max(candidate, linearized_upper),
+                    )
+                };
+                candidate.join_assign(&advance_to);
+            }
+        }
+
         if let Some(real_time_recency_ts) = real_time_recency_ts {
             assert!(
-                session.vars().real_time_recency()
-                    && isolation_level == &IsolationLevel::StrictSerializable,
+                ctx.real_time_recency && isolation_level == &IsolationLevel::StrictSerializable,
                 "real time recency timestamp should only be supplied when real time recency \
                             is enabled and the isolation level is strict serializable"
             );
+            // The query blocks until its inputs catch up with the real time recency timestamp,
+            // which may be arbitrarily far ahead of them. Refuse to wait longer than the session
+            // allows.
+            if let Some(timeout) = ctx.real_time_recency_timeout {
+                let bound = Timestamp::try_from(timeout).unwrap_or_else(|_| Timestamp::maximum());
+                if real_time_recency_ts.saturating_sub(largest_not_in_advance_of_upper) > bound {
+                    return Err(AdapterError::RealTimeRecencyTimeout {
+                        timestamp: real_time_recency_ts,
+                        upper: largest_not_in_advance_of_upper,
+                        timeout,
+                    });
+                }
+            }
             candidate.join_assign(&real_time_recency_ts);
         }
 
         let mut session_oracle_read_ts = None;
         if isolation_level == &IsolationLevel::StrongSessionSerializable {
             if let Some(timeline) = &timeline {
-                if let Some(oracle) = session.get_timestamp_oracle(timeline) {
-                    let session_ts = oracle.read_ts();
+                if let Some(session_ts) = ctx.session_oracle_read_ts(timeline) {
                     candidate.join_assign(&session_ts);
                     session_oracle_read_ts = Some(session_ts);
                 }
@@ -398,6 +662,21 @@ pub trait TimestampProvider {
             }
         }
 
+        // Under isolation levels weaker than Strict Serializable, nothing so far guarantees that
+        // the candidate is at least as large as the session's own writes. Strict Serializable needs
+        // no adjustment, as the oracle read timestamp is never behind an applied write. Writes to
+        // tables land in the `EpochMilliseconds` timeline, so only queries in that timeline are
+        // affected.
+        if ctx.read_your_writes
+            && isolation_level != &IsolationLevel::StrictSerializable
+            && when.can_advance_to_timeline_ts()
+            && timeline == Some(Timeline::EpochMilliseconds)
+        {
+            if let Some(last_write_ts) = &ctx.last_write_ts {
+                candidate.join_assign(last_write_ts);
+            }
+        }
+
         // If the timestamp is greater or equal to some element in `since` we are
         // assured that the answer will be correct.
         //
@@ -408,13 +687,24 @@ pub trait TimestampProvider {
         let timestamp = if since.less_equal(&candidate) {
             event!(
                 Level::DEBUG,
-                conn_id = format!("{}", session.conn_id()),
+                conn_id = ctx.conn_id.as_ref().map(|conn_id| conn_id.to_string()),
                 since = format!("{since:?}"),
                 largest_not_in_advance_of_upper = format!("{largest_not_in_advance_of_upper}"),
                 timestamp = format!("{candidate}")
             );
             candidate
         } else {
+            // An explicit `AS OF` cannot be advanced to the since. Call out when that is because
+            // the requested time has aged out of the inputs' retention windows.
+            if let (QueryWhen::AtTimestamp(_), Some(earliest_available)) = (when, since.as_option())
+            {
+                if self.compacted_past(id_bundle, compute_instance, candidate) {
+                    return Err(AdapterError::AsOfBeforeRetention {
+                        requested: candidate,
+                        earliest_available: *earliest_available,
+                    });
+                }
+            }
             coord_bail!(self.generate_timestamp_not_valid_error_msg(
                 id_bundle,
                 compute_instance,
@@ -422,6 +712,18 @@ pub trait TimestampProvider {
             ));
         };
 
+        // A `max_staleness` bound is only meaningful in the wall-clock timeline, and queries that
+        // name their own timestamp with `AS OF` have already accepted whatever staleness that
+        // implies.
+        let max_staleness = match (ctx.max_staleness, &timeline) {
+            (Some(max_staleness), Some(Timeline::EpochMilliseconds))
+                if when.advance_to_timestamp().is_none() =>
+            {
+                Some(self.check_staleness(ctx, id_bundle, timestamp, oracle_read_ts, max_staleness))
+            }
+            _ => None,
+        };
+
         let timestamp_context = TimestampContext::from_timeline_context(
             timestamp,
             oracle_read_ts,
@@ -436,9 +738,53 @@ pub trait TimestampProvider {
             largest_not_in_advance_of_upper,
             oracle_read_ts,
             session_oracle_read_ts,
+            max_staleness,
         })
     }
 
+    /// Measures how far `timestamp` lags behind the oracle read timestamp or, if there is none,
+    /// the session's wall time, and reports the collections whose uppers hold it back further
+    /// than `max_staleness`.
+    fn check_staleness(
+        &self,
+        ctx: &TimestampDeterminationContext<'_>,
+        id_bundle: &CollectionIdBundle,
+        timestamp: Timestamp,
+        oracle_read_ts: Option<Timestamp>,
+        max_staleness: Duration,
+    ) -> StalenessCheck<Timestamp> {
+        let reference = oracle_read_ts.unwrap_or_else(|| {
+            Timestamp::try_from(ctx.wall_time().timestamp_millis())
+                .unwrap_or_else(|_| Timestamp::minimum())
+        });
+        let bound = Timestamp::try_from(max_staleness).unwrap_or_else(|_| Timestamp::maximum());
+        let threshold = reference.saturating_sub(bound);
+
+        let is_lagging = |upper: &Antichain<Timestamp>| {
+            Coordinator::largest_not_in_advance_of_upper(upper) < threshold
+        };
+        let mut lagging: Vec<_> = id_bundle
+            .storage_ids
+            .iter()
+            .filter(|id| is_lagging(self.storage_write_frontier(**id)))
+            .copied()
+            .collect();
+        for (instance, compute_ids) in &id_bundle.compute_ids {
+            lagging.extend(
+                compute_ids.iter().filter(|id| {
+                    is_lagging(&self.compute_write_frontier(*instance, **id).to_owned())
+                }),
+            );
+        }
+
+        StalenessCheck {
+            max_staleness,
+            reference,
+            satisfied: timestamp >= threshold,
+            lagging,
+        }
+    }
+
     /// The smallest common valid read frontier among the specified collections.
     fn least_valid_read(&self, id_bundle: &CollectionIdBundle) -> Antichain<mz_repr::Timestamp> {
         let mut since = Antichain::from_elem(Timestamp::minimum());
@@ -478,6 +824,44 @@ pub trait TimestampProvider {
         since
     }
 
+    /// Reports whether `candidate` is not readable for some collection in `id_bundle` only
+    /// because those collections were compacted past it.
+    ///
+    /// This is the case when every collection whose read frontier is beyond `candidate` was
+    /// readable at `candidate` when it was created. Collections created after `candidate`, or
+    /// whose creation frontier is unknown, never held data at that time, so this returns `false`
+    /// for them.
+    fn compacted_past(
+        &self,
+        id_bundle: &CollectionIdBundle,
+        compute_instance: ComputeInstanceId,
+        candidate: mz_repr::Timestamp,
+    ) -> bool {
+        let readable_at_creation =
+            |since: &Antichain<Timestamp>, initial: Option<&Antichain<Timestamp>>| {
+                since.less_equal(&candidate)
+                    || initial.map_or(false, |initial| initial.less_equal(&candidate))
+            };
+        let compute_compacted = id_bundle
+            .compute_ids
+            .get(&compute_instance)
+            .into_iter()
+            .flatten()
+            .all(|id| {
+                readable_at_creation(
+                    self.compute_read_capability(compute_instance, *id),
+                    self.compute_initial_read_capability(compute_instance, *id),
+                )
+            });
+        let storage_compacted = id_bundle.storage_ids.iter().all(|id| {
+            readable_at_creation(
+                self.storage_implied_capability(*id),
+                self.storage_initial_implied_capability(*id),
+            )
+        });
+        compute_compacted && storage_compacted
+    }
+
     fn generate_timestamp_not_valid_error_msg(
         &self,
         id_bundle: &CollectionIdBundle,
@@ -520,30 +904,142 @@ pub trait TimestampProvider {
 }
 
 impl Coordinator {
+    /// Returns the isolation level of queries that `session` runs on `compute_instance`.
+    ///
+    /// This is the session's `transaction_isolation`, unless neither the session nor its role
+    /// has set it and the cluster has a default transaction isolation of its own.
+    pub(crate) fn isolation_level(
+        &self,
+        session: &Session,
+        compute_instance: ComputeInstanceId,
+    ) -> IsolationLevel {
+        let vars = session.vars();
+        let role_default_is_set = self
+            .catalog()
+            .try_get_role(session.session_role_id())
+            .map_or(false, |role| {
+                role.vars().any(|(name, _)| {
+                    name.eq_ignore_ascii_case(TRANSACTION_ISOLATION_VAR_NAME.as_str())
+                })
+            });
+        if !vars.transaction_isolation_is_set() && !role_default_is_set {
+            let cluster_default = self
+                .catalog()
+                .try_get_cluster(compute_instance)
+                .and_then(|cluster| cluster.config.transaction_isolation);
+            if let Some(isolation_level) = cluster_default {
+                return isolation_level;
+            }
+        }
+        *vars.transaction_isolation()
+    }
+
+    /// Notifies `session` that the timestamp of a query over `source_ids` defaults to the
+    /// [`Timeline::EpochMilliseconds`] timeline, if it does and the session asked for it with
+    /// `notice_on_default_timeline`.
+    ///
+    /// The default applies to [`TimelineContext::TimestampDependent`] queries, see
+    /// [`TimestampContext::from_timeline_context`]. The notice names what made the query timestamp
+    /// dependent: temporal functions in the query itself, if `contains_temporal`, and the views
+    /// and materialized views among `source_ids`.
+    pub(crate) fn add_default_timeline_notice(
+        &self,
+        session: &Session,
+        timeline_context: &TimelineContext,
+        source_ids: &BTreeSet<GlobalId>,
+        contains_temporal: bool,
+    ) {
+        if !session.vars().notice_on_default_timeline()
+            || timeline_context != &TimelineContext::TimestampDependent
+        {
+            return;
+        }
+        let mut triggers = Vec::new();
+        if contains_temporal {
+            triggers.push("mz_now()".to_string());
+        }
+        for id in self.timestamp_dependent_ids(source_ids.iter().copied()) {
+            let entry = self.catalog().get_entry(&id);
+            let name = self
+                .catalog()
+                .resolve_full_name(entry.name(), Some(session.conn_id()));
+            triggers.push(format!(
+                "{} {}",
+                entry.item().typ(),
+                name.to_string().quoted()
+            ));
+        }
+        session.add_notice(AdapterNotice::DefaultTimeline { triggers });
+    }
+
+    /// Returns a linearized read timestamp from the timestamp oracle, if the query requires one.
+    ///
+    /// This waits for the oracle on the coordinator's main loop, so while the oracle is
+    /// unavailable the read fails immediately, unless it may be served with serializable
+    /// isolation instead, in which case this returns `None`. The wait is abandoned if the
+    /// statement is canceled through `cancel_token`.
     pub(crate) async fn oracle_read_ts(
         &self,
         session: &Session,
+        cancel_token: &CancelToken,
+        isolation_level: &IsolationLevel,
         timeline_ctx: &TimelineContext,
         when: &QueryWhen,
-    ) -> Option<Timestamp> {
-        let isolation_level = session.vars().transaction_isolation().clone();
+    ) -> Result<Option<Timestamp>, AdapterError> {
         let linearized_timeline =
-            Coordinator::get_linearized_timeline(&isolation_level, when, timeline_ctx);
+            Coordinator::get_linearized_timeline(isolation_level, when, timeline_ctx);
         let oracle_read_ts = match linearized_timeline {
-            Some(timeline) => {
-                let timestamp_oracle = self.get_timestamp_oracle(&timeline);
-                Some(timestamp_oracle.read_ts().await)
-            }
+            Some(timeline) => match self.linearized_read()? {
+                LinearizedRead::Oracle => {
+                    // Note: This failpoint is used to simulate a slow timestamp oracle.
+                    let mut oracle_delay = None;
+                    (|| {
+                        fail::fail_point!("slow_oracle_read_ts", |val| {
+                            oracle_delay = val
+                                .and_then(|val| val.parse().ok())
+                                .map(Duration::from_millis)
+                        });
+                    })();
+
+                    let timestamp_oracle = self.get_timestamp_oracle(&timeline);
+                    let read_ts = async {
+                        if let Some(oracle_delay) = oracle_delay {
+                            tokio::time::sleep(oracle_delay).await;
+                        }
+                        timestamp_oracle.read_ts().await
+                    };
+                    match cancel_token.run_until_canceled(read_ts).await {
+                        Ok(read_ts) => Some(read_ts),
+                        Err(canceled_at) => {
+                            self.metrics
+                                .cancel_to_retire_seconds
+                                .observe(canceled_at.elapsed().as_secs_f64());
+                            return Err(AdapterError::Canceled);
+                        }
+                    }
+                }
+                LinearizedRead::Queued(_) => {
+                    return Err(AdapterError::TimestampOracleUnavailableForReads)
+                }
+                LinearizedRead::Serializable => {
+                    session.add_notice(AdapterNotice::TimestampOracleDegraded);
+                    None
+                }
+            },
             None => None,
         };
 
-        oracle_read_ts
+        Ok(oracle_read_ts)
     }
 
     /// Determines the timestamp for a query.
+    ///
+    /// Determinations that depend only on their inputs and the frontiers of the involved
+    /// collections are cached until the next write frontier update, see
+    /// [`crate::coord::timestamp_cache`].
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) async fn determine_timestamp(
-        &self,
+        &mut self,
         session: &Session,
         id_bundle: &CollectionIdBundle,
         when: &QueryWhen,
@@ -552,20 +1048,59 @@ impl Coordinator {
         oracle_read_ts: Option<Timestamp>,
         real_time_recency_ts: Option<mz_repr::Timestamp>,
     ) -> Result<TimestampDetermination<mz_repr::Timestamp>, AdapterError> {
-        let isolation_level = session.vars().transaction_isolation();
-        let det = self
-            .determine_timestamp_for(
-                self.catalog().state(),
-                session,
-                id_bundle,
-                when,
-                compute_instance,
-                timeline_context,
-                oracle_read_ts,
-                real_time_recency_ts,
-                isolation_level,
-            )
-            .await?;
+        let mut isolation_level = &self.isolation_level(session, compute_instance);
+        // A linearized read without an oracle timestamp was degraded because the timestamp
+        // oracle is unavailable, and is served with serializable isolation instead.
+        if oracle_read_ts.is_none()
+            && Self::get_linearized_timeline(isolation_level, when, timeline_context).is_some()
+        {
+            isolation_level = &IsolationLevel::Serializable;
+        }
+        let ctx = TimestampDeterminationContext::from(session);
+        let epoch = self.controller.frontier_epoch();
+        let key = DeterminationKey::cacheable(
+            session,
+            id_bundle,
+            when,
+            isolation_level,
+            compute_instance,
+            timeline_context,
+            oracle_read_ts,
+            real_time_recency_ts,
+        );
+        let cached = key
+            .as_ref()
+            .and_then(|key| self.timestamp_determinations.get(key, epoch))
+            .cloned()
+            // Read holds can be released without a write frontier update, so the cached
+            // timestamp must still be readable.
+            .filter(|det| match det.timestamp_context.timestamp() {
+                Some(ts) => self.least_valid_read(id_bundle).less_equal(ts),
+                None => true,
+            });
+        let det = match cached {
+            Some(det) => det,
+            None => {
+                let det = self
+                    .determine_timestamp_for(
+                        self.catalog().state(),
+                        &ctx,
+                        id_bundle,
+                        when,
+                        compute_instance,
+                        timeline_context,
+                        oracle_read_ts,
+                        real_time_recency_ts,
+                        isolation_level,
+                    )
+                    .await?;
+                if let Some(key) = key {
+                    self.timestamp_determinations
+                        .insert(key, epoch, det.clone());
+                }
+                det
+            }
+        };
         self.metrics
             .determine_timestamp
             .with_label_values(&[
@@ -577,15 +1112,24 @@ impl Coordinator {
                 &compute_instance.to_string(),
             ])
             .inc();
-        if !det.respond_immediately()
-            && isolation_level == &IsolationLevel::StrictSerializable
-            && real_time_recency_ts.is_none()
-        {
+        let sample_rate = self
+            .catalog()
+            .system_config()
+            .strict_serializable_timestamp_difference_sample_rate()
+            .try_into()
+            .expect("value constrained to be convertible to f64");
+        if Self::sample_timestamp_difference(
+            &det,
+            isolation_level,
+            real_time_recency_ts,
+            sample_rate,
+            &mut thread_rng(),
+        ) {
             if let Some(strict) = det.timestamp_context.timestamp() {
                 let serializable_det = self
                     .determine_timestamp_for(
                         self.catalog().state(),
-                        session,
+                        &ctx,
                         id_bundle,
                         when,
                         compute_instance,
@@ -608,6 +1152,162 @@ impl Coordinator {
         Ok(det)
     }
 
+    /// Returns whether to also determine the timestamp that a serializable query would have
+    /// chosen, to report its difference to the strict serializable determination `det`.
+    ///
+    /// Only a `sample_rate` fraction of the qualifying determinations are sampled, as this
+    /// doubles their cost.
+    fn sample_timestamp_difference<R: Rng>(
+        det: &TimestampDetermination<mz_repr::Timestamp>,
+        isolation_level: &IsolationLevel,
+        real_time_recency_ts: Option<mz_repr::Timestamp>,
+        sample_rate: f64,
+        rng: &mut R,
+    ) -> bool {
+        !det.respond_immediately()
+            && isolation_level == &IsolationLevel::StrictSerializable
+            && real_time_recency_ts.is_none()
+            && Bernoulli::new(sample_rate)
+                .expect("rate must be in range [0, 1]")
+                .sample(rng)
+    }
+
+    /// Like [`Coordinator::determine_timestamp`], but also returns a future that resolves once
+    /// the chosen timestamp is readable from all inputs in `id_bundle`, unless that is already
+    /// the case.
+    ///
+    /// The future is driven by the controller's watch sets, so it only makes progress while the
+    /// coordinator keeps processing the controller. If the session's connection is cleared first,
+    /// the wait is cancelled and the future resolves early.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) async fn determine_timestamp_and_wait(
+        &mut self,
+        session: &Session,
+        id_bundle: &CollectionIdBundle,
+        when: &QueryWhen,
+        compute_instance: ComputeInstanceId,
+        timeline_context: &TimelineContext,
+        oracle_read_ts: Option<Timestamp>,
+        real_time_recency_ts: Option<mz_repr::Timestamp>,
+    ) -> Result<
+        (
+            TimestampDetermination<mz_repr::Timestamp>,
+            Option<BoxFuture<'static, ()>>,
+        ),
+        AdapterError,
+    > {
+        let det = self
+            .determine_timestamp(
+                session,
+                id_bundle,
+                when,
+                compute_instance,
+                timeline_context,
+                oracle_read_ts,
+                real_time_recency_ts,
+            )
+            .await?;
+        let wait = match det.timestamp_context.timestamp() {
+            Some(ts) if !det.respond_immediately() => {
+                let (watch_set, wait) = self
+                    .controller
+                    .install_watch_set_waiter(id_bundle.iter().collect(), *ts);
+                match self.active_conns.get_mut(session.conn_id()) {
+                    Some(conn) => {
+                        conn.determination_waits.insert(watch_set);
+                        // Forget the wait once it resolves, so that long-lived connections don't
+                        // accumulate finished waits.
+                        let internal_cmd_tx = self.internal_cmd_tx.clone();
+                        let conn_id = session.conn_id().clone();
+                        let wait = async move {
+                            wait.await;
+                            let _ = internal_cmd_tx
+                                .send(Message::DeterminationWaitFinished { conn_id, watch_set });
+                        };
+                        Some(wait.boxed())
+                    }
+                    None => Some(wait),
+                }
+            }
+            _ => None,
+        };
+        Ok((det, wait))
+    }
+
+    /// Like [`Coordinator::determine_timestamp`], but also reports how long the query waits for
+    /// the chosen timestamp to become readable.
+    ///
+    /// Waits are timed with watch sets, so only a `timestamp_wait_sample_rate` fraction of the
+    /// queries is timed, to keep their cost off most queries.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) async fn determine_timestamp_and_sample_wait(
+        &mut self,
+        session: &Session,
+        id_bundle: &CollectionIdBundle,
+        when: &QueryWhen,
+        compute_instance: ComputeInstanceId,
+        timeline_context: &TimelineContext,
+        oracle_read_ts: Option<Timestamp>,
+        real_time_recency_ts: Option<mz_repr::Timestamp>,
+    ) -> Result<TimestampDetermination<mz_repr::Timestamp>, AdapterError> {
+        let sample_rate = self
+            .catalog()
+            .system_config()
+            .timestamp_wait_sample_rate()
+            .try_into()
+            .expect("value constrained to be convertible to f64");
+        let sampled = Bernoulli::new(sample_rate)
+            .expect("rate must be in range [0, 1]")
+            .sample(&mut thread_rng());
+        if !sampled {
+            return self
+                .determine_timestamp(
+                    session,
+                    id_bundle,
+                    when,
+                    compute_instance,
+                    timeline_context,
+                    oracle_read_ts,
+                    real_time_recency_ts,
+                )
+                .await;
+        }
+
+        let (det, wait) = self
+            .determine_timestamp_and_wait(
+                session,
+                id_bundle,
+                when,
+                compute_instance,
+                timeline_context,
+                oracle_read_ts,
+                real_time_recency_ts,
+            )
+            .await?;
+        if let Some(wait) = wait {
+            self.observe_timestamp_wait(compute_instance, wait);
+        }
+        Ok(det)
+    }
+
+    /// Reports how long a query on `compute_instance` waits for its timestamp to become readable,
+    /// once the `wait` returned by [`Coordinator::determine_timestamp_and_wait`] resolves.
+    fn observe_timestamp_wait(
+        &self,
+        compute_instance: ComputeInstanceId,
+        wait: BoxFuture<'static, ()>,
+    ) {
+        let histogram = self
+            .metrics
+            .timestamp_wait_seconds
+            .with_label_values(&[&compute_instance.to_string()]);
+        let start = Instant::now();
+        mz_ore::task::spawn(|| "timestamp_wait", async move {
+            wait.await;
+            histogram.observe(start.elapsed().as_secs_f64());
+        });
+    }
+
     /// The largest element not in advance of any object in the collection.
     ///
     /// Times that are not greater to this frontier are complete for all collections
@@ -630,18 +1330,61 @@ impl Coordinator {
         }
     }
 
+    /// Evaluates an AS OF or UP TO expression to a timestamp.
+    ///
+    /// `wall_time` is the wall clock of the transaction the expression belongs to, which is the
+    /// time its first statement was executed. Calls to `now()` evaluate to it, so that every
+    /// statement in a transaction observes the same "now", matching Postgres semantics.
+    ///
+    /// Timestamps and timestamps with time zone are rounded up to the next millisecond if they have
+    /// sub-millisecond precision, so that the result is never before the point in time the
+    /// expression refers to. A notice is issued to `session` when that happens.
+    ///
+    /// In the `timeline` of the upstream LSNs of a PostgreSQL source, strings are LSNs in the
+    /// textual format of `pg_lsn`.
     pub(crate) fn evaluate_when(
         catalog: &CatalogState,
-        mut timestamp: MirScalarExpr,
+        timestamp: MirScalarExpr,
         session: &Session,
+        wall_time: DateTime<Utc>,
+        timeline: Option<&Timeline>,
+    ) -> Result<mz_repr::Timestamp, AdapterError> {
+        Self::evaluate_when_with(
+            timestamp,
+            &catalog.for_session(session),
+            &|notice| session.add_notice(notice),
+            wall_time,
+            timeline,
+        )
+    }
+
+    /// Like [`Coordinator::evaluate_when`], but without a session: types are named in errors
+    /// with `humanizer`, and notices are passed to `add_notice`.
+    fn evaluate_when_with(
+        mut timestamp: MirScalarExpr,
+        humanizer: &dyn ExprHumanizer,
+        add_notice: &dyn Fn(AdapterNotice),
+        wall_time: DateTime<Utc>,
+        timeline: Option<&Timeline>,
     ) -> Result<mz_repr::Timestamp, AdapterError> {
         let temp_storage = RowArena::new();
-        prep_scalar_expr(&mut timestamp, ExprPrepStyle::AsOfUpTo)?;
+        prep_scalar_expr(&mut timestamp, ExprPrepStyle::AsOfUpTo { wall_time })?;
         let evaled = timestamp.eval(&[], &temp_storage)?;
         if evaled.is_null() {
             coord_bail!("can't use {} as a mz_timestamp for AS OF or UP TO", evaled);
         }
         let ty = timestamp.typ(&[]);
+        let round_up = |millis: i64, subsec_nanos: u32, requested: String| {
+            let rounded = subsec_nanos % 1_000_000 != 0;
+            let ts: mz_repr::Timestamp = (millis + i64::from(rounded)).try_into()?;
+            if rounded {
+                add_notice(AdapterNotice::TimestampRoundedUp {
+                    requested,
+                    used: ts,
+                });
+            }
+            Ok::<_, AdapterError>(ts)
+        };
         Ok(match ty.scalar_type {
             ScalarType::MzTimestamp => evaled.unwrap_mz_timestamp(),
             ScalarType::Numeric { .. } => {
@@ -655,21 +1398,84 @@ impl Coordinator {
             ScalarType::UInt32 => u64::from(evaled.unwrap_uint32()).into(),
             ScalarType::UInt64 => evaled.unwrap_uint64().into(),
             ScalarType::TimestampTz { .. } => {
-                evaled.unwrap_timestamptz().timestamp_millis().try_into()?
+                let ts = evaled.unwrap_timestamptz();
+                round_up(
+                    ts.timestamp_millis(),
+                    ts.timestamp_subsec_nanos(),
+                    ts.to_string(),
+                )?
             }
             ScalarType::Timestamp { .. } => {
-                evaled.unwrap_timestamp().timestamp_millis().try_into()?
+                let ts = evaled.unwrap_timestamp();
+                round_up(
+                    ts.timestamp_millis(),
+                    ts.timestamp_subsec_nanos(),
+                    ts.to_string(),
+                )?
+            }
+            ScalarType::String if matches!(timeline, Some(Timeline::PostgresLsn(_))) => {
+                let lsn = evaled.unwrap_str();
+                match parse_lsn(lsn) {
+                    Some(lsn) => lsn.into(),
+                    None => coord_bail!("invalid LSN for AS OF or UP TO: {}", lsn.quoted()),
+                }
             }
             _ => coord_bail!(
                 "can't use {} as a mz_timestamp for AS OF or UP TO",
-                catalog.for_session(session).humanize_column_type(&ty)
+                humanizer.humanize_column_type(&ty)
             ),
         })
     }
 }
 
+/// The version of the serialized shape of [`TimestampDetermination`] and
+/// [`TimestampExplanation`], which is consumed by the statement log and by `EXPLAIN TIMESTAMP AS
+/// JSON`.
+///
+/// Within a version, the shape is stable: fields are not removed or renamed and don't change
+/// their meaning. This includes the nested [`TimestampContext`], [`StalenessCheck`], and
+/// [`TimestampSource`]. Any change to the shape must bump the version, add an entry below, and
+/// add a blob of the new shape to the compatibility corpus in
+/// `tests/testdata/timestamp_serialization`. Blobs of all prior versions must keep deserializing,
+/// so fields added by later versions default when they are missing. Blobs of versions newer than
+/// this one are rejected.
+///
+/// Versions:
+///
+/// 1. Serialized without a `version` field, and without the `oracle_ts` of a timeline timestamp
+///    and the `session_oracle_read_ts` and `max_staleness` of a determination.
+/// 2. Adds the `version` field to determinations and explanations, and the fields missing from
+///    version 1.
+/// 3. Adds the `oracle_catch_up_ms` of an explanation.
+pub const TIMESTAMP_SERIALIZATION_VERSION: u64 = 3;
+
+/// The version of blobs that were serialized without a `version` field.
+fn unversioned() -> u64 {
+    1
+}
+
+fn check_serialization_version(version: u64) -> Result<(), String> {
+    if version > TIMESTAMP_SERIALIZATION_VERSION {
+        return Err(format!(
+            "unsupported timestamp serialization version {version}, \
+             the latest supported version is {TIMESTAMP_SERIALIZATION_VERSION}"
+        ));
+    }
+    Ok(())
+}
+
 /// Information used when determining the timestamp for a query.
+///
+/// Serialized in a stable, versioned shape, see [`TIMESTAMP_SERIALIZATION_VERSION`].
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(
+    into = "SerializedTimestampDetermination<T>",
+    try_from = "SerializedTimestampDetermination<T>",
+    bound(
+        serialize = "T: Serialize + Clone",
+        deserialize = "T: Deserialize<'de>"
+    )
+)]
 pub struct TimestampDetermination<T> {
     /// The chosen timestamp context from `determine_timestamp`.
     pub timestamp_context: TimestampContext<T>,
@@ -683,6 +1489,88 @@ pub struct TimestampDetermination<T> {
     pub oracle_read_ts: Option<T>,
     /// The value of the session local timestamp's oracle timestamp, if used.
     pub session_oracle_read_ts: Option<T>,
+    /// The outcome of checking the session's `max_staleness`, if one applied.
+    pub max_staleness: Option<StalenessCheck<T>>,
+}
+
+/// The serialized shape of a [`TimestampDetermination`].
+#[derive(Serialize, Deserialize)]
+struct SerializedTimestampDetermination<T> {
+    #[serde(default = "unversioned")]
+    version: u64,
+    timestamp_context: TimestampContext<T>,
+    since: Antichain<T>,
+    upper: Antichain<T>,
+    largest_not_in_advance_of_upper: T,
+    oracle_read_ts: Option<T>,
+    #[serde(default)]
+    session_oracle_read_ts: Option<T>,
+    #[serde(default)]
+    max_staleness: Option<StalenessCheck<T>>,
+}
+
+impl<T> From<TimestampDetermination<T>> for SerializedTimestampDetermination<T> {
+    fn from(determination: TimestampDetermination<T>) -> Self {
+        let TimestampDetermination {
+            timestamp_context,
+            since,
+            upper,
+            largest_not_in_advance_of_upper,
+            oracle_read_ts,
+            session_oracle_read_ts,
+            max_staleness,
+        } = determination;
+        Self {
+            version: TIMESTAMP_SERIALIZATION_VERSION,
+            timestamp_context,
+            since,
+            upper,
+            largest_not_in_advance_of_upper,
+            oracle_read_ts,
+            session_oracle_read_ts,
+            max_staleness,
+        }
+    }
+}
+
+impl<T> TryFrom<SerializedTimestampDetermination<T>> for TimestampDetermination<T> {
+    type Error = String;
+
+    fn try_from(serialized: SerializedTimestampDetermination<T>) -> Result<Self, Self::Error> {
+        let SerializedTimestampDetermination {
+            version,
+            timestamp_context,
+            since,
+            upper,
+            largest_not_in_advance_of_upper,
+            oracle_read_ts,
+            session_oracle_read_ts,
+            max_staleness,
+        } = serialized;
+        check_serialization_version(version)?;
+        Ok(Self {
+            timestamp_context,
+            since,
+            upper,
+            largest_not_in_advance_of_upper,
+            oracle_read_ts,
+            session_oracle_read_ts,
+            max_staleness,
+        })
+    }
+}
+
+/// The outcome of checking a chosen timestamp against the session's `max_staleness`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StalenessCheck<T> {
+    /// The configured staleness bound.
+    pub max_staleness: Duration,
+    /// The time the staleness was measured against.
+    pub reference: T,
+    /// Whether the chosen timestamp was within `max_staleness` of `reference`.
+    pub satisfied: bool,
+    /// The input collections whose uppers are more than `max_staleness` behind `reference`.
+    pub lagging: Vec<GlobalId>,
 }
 
 impl<T: TimestampManipulation> TimestampDetermination<T> {
@@ -696,8 +1584,50 @@ impl<T: TimestampManipulation> TimestampDetermination<T> {
     }
 }
 
+impl TimestampDetermination<Timestamp> {
+    /// Returns an error if the chosen timestamp violated the session's `max_staleness`.
+    pub fn check_max_staleness(
+        &self,
+        catalog: &CatalogState,
+        session: &Session,
+    ) -> Result<(), AdapterError> {
+        let Some(check) = &self.max_staleness else {
+            return Ok(());
+        };
+        if check.satisfied {
+            return Ok(());
+        }
+        let lagging = check
+            .lagging
+            .iter()
+            .map(|id| match catalog.try_get_entry(id) {
+                Some(entry) => catalog
+                    .resolve_full_name(entry.name(), Some(session.conn_id()))
+                    .to_string(),
+                None => id.to_string(),
+            })
+            .collect();
+        Err(AdapterError::MaxStalenessExceeded {
+            timestamp: self.timestamp_context.timestamp_or_default(),
+            reference: check.reference,
+            max_staleness: check.max_staleness,
+            lagging,
+        })
+    }
+}
+
 /// Information used when determining the timestamp for a query.
+///
+/// Serialized in a stable, versioned shape, see [`TIMESTAMP_SERIALIZATION_VERSION`].
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(
+    into = "SerializedTimestampExplanation<T>",
+    try_from = "SerializedTimestampExplanation<T>",
+    bound(
+        serialize = "T: Serialize + Clone",
+        deserialize = "T: Deserialize<'de>"
+    )
+)]
 pub struct TimestampExplanation<T> {
     /// The chosen timestamp from `determine_timestamp`.
     pub determination: TimestampDetermination<T>,
@@ -707,6 +1637,68 @@ pub struct TimestampExplanation<T> {
     pub session_wall_time: DateTime<Utc>,
     /// Cached value of determination.respond_immediately()
     pub respond_immediately: bool,
+    /// Cached value of determination.timestamp_context.oracle_catch_up_ms()
+    pub oracle_catch_up_ms: Option<u64>,
+}
+
+/// The serialized shape of a [`TimestampExplanation`].
+#[derive(Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "T: Serialize + Clone",
+    deserialize = "T: Deserialize<'de>"
+))]
+struct SerializedTimestampExplanation<T> {
+    #[serde(default = "unversioned")]
+    version: u64,
+    determination: TimestampDetermination<T>,
+    sources: Vec<TimestampSource<T>>,
+    session_wall_time: DateTime<Utc>,
+    respond_immediately: bool,
+    #[serde(default)]
+    oracle_catch_up_ms: Option<u64>,
+}
+
+impl<T> From<TimestampExplanation<T>> for SerializedTimestampExplanation<T> {
+    fn from(explanation: TimestampExplanation<T>) -> Self {
+        let TimestampExplanation {
+            determination,
+            sources,
+            session_wall_time,
+            respond_immediately,
+            oracle_catch_up_ms,
+        } = explanation;
+        Self {
+            version: TIMESTAMP_SERIALIZATION_VERSION,
+            determination,
+            sources,
+            session_wall_time,
+            respond_immediately,
+            oracle_catch_up_ms,
+        }
+    }
+}
+
+impl<T> TryFrom<SerializedTimestampExplanation<T>> for TimestampExplanation<T> {
+    type Error = String;
+
+    fn try_from(serialized: SerializedTimestampExplanation<T>) -> Result<Self, Self::Error> {
+        let SerializedTimestampExplanation {
+            version,
+            determination,
+            sources,
+            session_wall_time,
+            respond_immediately,
+            oracle_catch_up_ms,
+        } = serialized;
+        check_serialization_version(version)?;
+        Ok(Self {
+            determination,
+            sources,
+            session_wall_time,
+            respond_immediately,
+            oracle_catch_up_ms,
+        })
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -725,13 +1717,19 @@ pub trait DisplayableInTimeline {
 
 impl DisplayableInTimeline for mz_repr::Timestamp {
     fn fmt(&self, timeline: Option<&Timeline>, f: &mut fmt::Formatter) -> fmt::Result {
-        if let Some(Timeline::EpochMilliseconds) = timeline {
-            let ts_ms: u64 = self.into();
-            if let Ok(ts_ms) = i64::try_from(ts_ms) {
-                if let Some(ndt) = NaiveDateTime::from_timestamp_millis(ts_ms) {
-                    return write!(f, "{:13} ({})", self, ndt.format("%Y-%m-%d %H:%M:%S%.3f"));
+        match timeline {
+            Some(Timeline::EpochMilliseconds) => {
+                let ts_ms: u64 = self.into();
+                if let Ok(ts_ms) = i64::try_from(ts_ms) {
+                    if let Some(ndt) = NaiveDateTime::from_timestamp_millis(ts_ms) {
+                        return write!(f, "{:13} ({})", self, ndt.format("%Y-%m-%d %H:%M:%S%.3f"));
+                    }
                 }
             }
+            Some(Timeline::PostgresLsn(_)) => {
+                return write!(f, "{:13} ({})", self, format_lsn(self.into()));
+            }
+            _ => {}
         }
         write!(f, "{:13}", self)
     }
@@ -779,6 +1777,13 @@ impl<T: fmt::Display + fmt::Debug + DisplayableInTimeline + TimestampManipulatio
                 oracle_read_ts.display(timeline)
             )?;
         }
+        if let Some(oracle_catch_up_ms) = self.oracle_catch_up_ms {
+            writeln!(
+                f,
+                "       oracle catch-up required: {} ms",
+                oracle_catch_up_ms
+            )?;
+        }
         if let Some(session_oracle_read_ts) = &self.determination.session_oracle_read_ts {
             writeln!(
                 f,
@@ -817,6 +1822,18 @@ impl<T: fmt::Display + fmt::Debug + DisplayableInTimeline + TimestampManipulatio
             self.respond_immediately
         )?;
         writeln!(f, "                       timeline: {:?}", &timeline)?;
+        if let Some(check) = &self.determination.max_staleness {
+            writeln!(
+                f,
+                "                  max staleness: {:?} ({})",
+                check.max_staleness,
+                if check.satisfied {
+                    "satisfied"
+                } else {
+                    "not satisfied"
+                },
+            )?;
+        }
         writeln!(
             f,
             "              session wall time: {:13} ({})",
@@ -849,3 +1866,225 @@ impl<T: fmt::Display + fmt::Debug + DisplayableInTimeline + TimestampManipulatio
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_expr::UnmaterializableFunc;
+    use mz_ore::now::to_datetime;
+    use mz_repr::adt::timestamp::CheckedTimestamp;
+    use mz_repr::Datum;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `decNumberFromInt32` on OS `linux`
+    fn test_evaluate_when_now_uses_transaction_wall_time() {
+        let catalog = CatalogState::empty();
+        let mut session = Session::dummy();
+        session
+            .start_transaction(to_datetime(1_000), None, None)
+            .expect("valid transaction");
+        let now = MirScalarExpr::CallUnmaterializable(UnmaterializableFunc::CurrentTimestamp);
+
+        let wall_time = session.pcx().wall_time;
+        let first = Coordinator::evaluate_when(&catalog, now.clone(), &session, wall_time, None)
+            .expect("valid AS OF");
+
+        // A later statement in the same transaction does not start a new transaction, and so
+        // must observe the same wall clock.
+        session.start_transaction_single_stmt(to_datetime(5_000));
+        let wall_time = session.pcx().wall_time;
+        let second = Coordinator::evaluate_when(&catalog, now, &session, wall_time, None)
+            .expect("valid AS OF");
+
+        assert_eq!(first, Timestamp::from(1_000u64));
+        assert_eq!(first, second);
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `decNumberFromInt32` on OS `linux`
+    fn test_evaluate_when_rounds_up_sub_millisecond_timestamps() {
+        let catalog = CatalogState::empty();
+        let mut session = Session::dummy();
+        let evaluate = |session: &mut Session, micros: i64| {
+            let dt = NaiveDateTime::from_timestamp_micros(micros).expect("valid timestamp");
+            let timestamptz = MirScalarExpr::literal_ok(
+                Datum::TimestampTz(
+                    CheckedTimestamp::from_timestamplike(DateTime::from_utc(dt, Utc)).unwrap(),
+                ),
+                ScalarType::TimestampTz { precision: None },
+            );
+            let timestamp = MirScalarExpr::literal_ok(
+                Datum::Timestamp(CheckedTimestamp::from_timestamplike(dt).unwrap()),
+                ScalarType::Timestamp { precision: None },
+            );
+            let wall_time = to_datetime(0);
+            let tz = Coordinator::evaluate_when(&catalog, timestamptz, session, wall_time, None)
+                .expect("valid AS OF");
+            let ts = Coordinator::evaluate_when(&catalog, timestamp, session, wall_time, None)
+                .expect("valid AS OF");
+            assert_eq!(tz, ts);
+            let notices = session.drain_notices();
+            (tz, notices.len())
+        };
+
+        // Exact milliseconds are used as they are.
+        assert_eq!(evaluate(&mut session, 5_000), (Timestamp::from(5u64), 0));
+        // Anything below or above a millisecond is rounded up, with a notice for each
+        // expression.
+        assert_eq!(evaluate(&mut session, 5_001), (Timestamp::from(6u64), 2));
+        assert_eq!(evaluate(&mut session, 5_999), (Timestamp::from(6u64), 2));
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `decNumberFromInt32` on OS `linux`
+    fn test_evaluate_when_lsn() {
+        let catalog = CatalogState::empty();
+        let session = Session::dummy();
+        let wall_time = to_datetime(0);
+        let lsn_timeline = Timeline::PostgresLsn("u1".into());
+        let evaluate = |s: &str, timeline: &Timeline| {
+            let lsn = MirScalarExpr::literal_ok(Datum::String(s), ScalarType::String);
+            Coordinator::evaluate_when(&catalog, lsn, &session, wall_time, Some(timeline))
+        };
+
+        // LSNs are accepted in the timeline of the upstream LSNs of a source ...
+        assert_eq!(
+            evaluate("16/B374D848", &lsn_timeline).expect("valid AS OF"),
+            Timestamp::from(0x16_B374D848u64)
+        );
+        assert!(evaluate("B374D848", &lsn_timeline).is_err());
+        // ... and nowhere else.
+        assert!(evaluate("16/B374D848", &Timeline::EpochMilliseconds).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_sample_timestamp_difference() {
+        // A determination that has to wait for its inputs.
+        let det = TimestampDetermination {
+            timestamp_context: TimestampContext::TimelineTimestamp {
+                timeline: Timeline::EpochMilliseconds,
+                chosen_ts: Timestamp::from(10u64),
+                oracle_ts: Some(Timestamp::from(10u64)),
+            },
+            since: Antichain::from_elem(Timestamp::minimum()),
+            upper: Antichain::from_elem(Timestamp::from(5u64)),
+            largest_not_in_advance_of_upper: Timestamp::from(4u64),
+            oracle_read_ts: Some(Timestamp::from(10u64)),
+            session_oracle_read_ts: None,
+            max_staleness: None,
+        };
+        assert!(!det.respond_immediately());
+
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(42);
+        let mut sample = |isolation_level, sample_rate| {
+            Coordinator::sample_timestamp_difference(
+                &det,
+                &isolation_level,
+                None,
+                sample_rate,
+                &mut rng,
+            )
+        };
+        for _ in 0..100 {
+            assert!(sample(IsolationLevel::StrictSerializable, 1.0));
+            assert!(!sample(IsolationLevel::StrictSerializable, 0.0));
+            assert!(!sample(IsolationLevel::Serializable, 1.0));
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_explain_oracle_catch_up() {
+        let explain = |chosen_ts: u64, oracle_ts: Option<u64>| {
+            let timestamp_context = TimestampContext::TimelineTimestamp {
+                timeline: Timeline::EpochMilliseconds,
+                chosen_ts: Timestamp::from(chosen_ts),
+                oracle_ts: oracle_ts.map(Timestamp::from),
+            };
+            let oracle_catch_up_ms = timestamp_context.oracle_catch_up_ms();
+            TimestampExplanation {
+                determination: TimestampDetermination {
+                    timestamp_context,
+                    since: Antichain::from_elem(Timestamp::minimum()),
+                    upper: Antichain::from_elem(Timestamp::from(chosen_ts + 1)),
+                    largest_not_in_advance_of_upper: Timestamp::from(chosen_ts),
+                    oracle_read_ts: oracle_ts.map(Timestamp::from),
+                    session_oracle_read_ts: None,
+                    max_staleness: None,
+                },
+                sources: Vec::new(),
+                session_wall_time: Utc::now(),
+                respond_immediately: true,
+                oracle_catch_up_ms,
+            }
+            .to_string()
+        };
+        let catch_up = "oracle catch-up required:";
+
+        // The chosen timestamp is ahead of the oracle, so results wait for it to catch up.
+        let explanation = explain(1_250, Some(1_000));
+        assert!(
+            explanation.contains("       oracle catch-up required: 250 ms\n"),
+            "{explanation}"
+        );
+
+        // The read is already linearized, or isn't linearized at all.
+        assert!(!explain(1_000, Some(1_000)).contains(catch_up));
+        assert!(!explain(1_000, Some(1_250)).contains(catch_up));
+        assert!(!explain(1_000, None).contains(catch_up));
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `open`
+    fn test_timestamp_serialization_compatibility() {
+        let corpus = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/testdata/timestamp_serialization");
+        let read = |version: u64| -> serde_json::Value {
+            let path = corpus.join(format!("explanation-v{version}.json"));
+            let blob = std::fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("missing corpus entry {}: {e}", path.display()));
+            serde_json::from_str(&blob).unwrap()
+        };
+        let deserialize = |blob: serde_json::Value| {
+            serde_json::from_value::<TimestampExplanation<Timestamp>>(blob)
+        };
+
+        // Blobs of every version keep deserializing.
+        for version in 1..=TIMESTAMP_SERIALIZATION_VERSION {
+            deserialize(read(version))
+                .unwrap_or_else(|e| panic!("failed to deserialize version {version}: {e}"));
+        }
+
+        // Fields added after version 1 default.
+        let v1 = deserialize(read(1)).unwrap();
+        assert_eq!(
+            v1.determination.timestamp_context,
+            TimestampContext::TimelineTimestamp {
+                timeline: Timeline::EpochMilliseconds,
+                chosen_ts: Timestamp::from(1673618185000u64),
+                oracle_ts: None,
+            }
+        );
+        assert_eq!(v1.determination.session_oracle_read_ts, None);
+        assert!(v1.determination.max_staleness.is_none());
+        assert_eq!(v1.oracle_catch_up_ms, None);
+
+        // The current shape is exactly that of the latest version in the corpus. If this fails,
+        // the shape changed: bump `TIMESTAMP_SERIALIZATION_VERSION` and add the new shape to the
+        // corpus.
+        let latest = read(TIMESTAMP_SERIALIZATION_VERSION);
+        let reserialized = serde_json::to_value(deserialize(latest.clone()).unwrap()).unwrap();
+        assert_eq!(reserialized, latest);
+
+        // Blobs of unknown, newer versions are rejected.
+        let mut future = latest;
+        future["version"] = (TIMESTAMP_SERIALIZATION_VERSION + 1).into();
+        let err = deserialize(future).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unsupported timestamp serialization version"),
+            "{err}"
+        );
+    }
+}
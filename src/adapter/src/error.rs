@@ -11,8 +11,10 @@ use std::collections::BTreeMap;
 use std::error::Error;
 use std::fmt;
 use std::num::TryFromIntError;
+use std::time::Duration;
 
 use crate::CollectionIdBundle;
+use bytesize::ByteSize;
 use dec::TryFromDecimalError;
 use itertools::Itertools;
 use mz_catalog::builtin::MZ_INTROSPECTION_CLUSTER;
@@ -99,6 +101,8 @@ pub enum AdapterError {
     ConcurrentClusterDrop,
     /// Target cluster has no replicas to service query.
     NoClusterReplicasAvailable(String),
+    /// Target cluster is quiescing for maintenance and refuses new queries.
+    ClusterQuiescing(String),
     /// The named operation cannot be run in a transaction.
     OperationProhibitsTransaction(String),
     /// The named operation requires an active transaction.
@@ -131,6 +135,24 @@ pub enum AdapterError {
     },
     /// Result size of a query is too large.
     ResultSize(String),
+    /// The open portals of a session buffer more result bytes than permitted
+    /// by `max_portal_memory`.
+    PortalMemoryExceeded {
+        /// The number of bytes currently buffered by the session's portals.
+        used: u64,
+        /// The value of `max_portal_memory`.
+        limit: u64,
+    },
+    /// The client of a subscribe fell behind, leaving more undelivered bytes
+    /// than permitted by `max_subscribe_buffer_size`.
+    SubscribeBufferLimitExceeded {
+        /// The number of bytes of updates buffered for the client.
+        buffered: u64,
+        /// The value of `max_subscribe_buffer_size`.
+        limit: u64,
+        /// The timestamp through which all updates were delivered, if any.
+        delivered_through: Option<Timestamp>,
+    },
     /// The specified feature is not permitted in safe mode.
     SafeModeViolation(String),
     /// Waiting on a query timed out.
@@ -214,6 +236,65 @@ pub enum AdapterError {
     /// A CREATE MATERIALIZED VIEW statement tried to acquire a read hold at a REFRESH AT time,
     /// but was unable to get a precise read hold.
     InputNotReadableAtRefreshAtTime(Timestamp, Vec<(Antichain<Timestamp>, CollectionIdBundle)>),
+    /// The timestamp selected for a query lags behind the reference time by more than the
+    /// session's `max_staleness`.
+    MaxStalenessExceeded {
+        /// The timestamp that was selected for the query.
+        timestamp: Timestamp,
+        /// The time the staleness is measured against.
+        reference: Timestamp,
+        /// The configured staleness bound.
+        max_staleness: Duration,
+        /// The names of the input collections holding the timestamp back.
+        lagging: Vec<String>,
+    },
+    /// The `AS OF` timestamp of a query precedes the times still retained by its inputs, which
+    /// have been compacted past it.
+    AsOfBeforeRetention {
+        /// The timestamp requested by the query.
+        requested: Timestamp,
+        /// The earliest timestamp at which all inputs are readable.
+        earliest_available: Timestamp,
+    },
+    /// Waiting for the inputs of a query to catch up with its real-time recency timestamp would
+    /// take longer than the session's `real_time_recency_timeout`.
+    RealTimeRecencyTimeout {
+        /// The real-time recency timestamp of the query.
+        timestamp: Timestamp,
+        /// The largest timestamp at which all inputs are currently complete.
+        upper: Timestamp,
+        /// The configured wait bound.
+        timeout: Duration,
+    },
+    /// Writes can't be committed while the circuit breaker of the timestamp oracle is open.
+    TimestampOracleUnavailableForWrites,
+    /// A strict serializable read waited longer than `timestamp_oracle_degraded_read_timeout`
+    /// for the unavailable timestamp oracle.
+    TimestampOracleReadTimeout {
+        /// The configured wait bound.
+        timeout: Duration,
+    },
+    /// A strict serializable read can't wait for the unavailable timestamp oracle, either because
+    /// too many reads are waiting already or because it would block the coordinator.
+    TimestampOracleUnavailableForReads,
+    /// The `UP TO` bound of a `SUBSCRIBE` is not beyond its `AS OF` bound, so the subscription
+    /// could never produce any data.
+    InvalidAsOfUpTo {
+        /// The timestamp of the `AS OF` bound.
+        as_of: Timestamp,
+        /// The timestamp of the `UP TO` bound.
+        up_to: Timestamp,
+    },
+    /// The `RESUME FROM` token of a `SUBSCRIBE` is malformed or of an unsupported version.
+    InvalidResumeToken(String),
+    /// The inputs of a `SUBSCRIBE` were compacted past the frontier of its `RESUME FROM` token,
+    /// so the updates after it are no longer retained.
+    ResumeTokenBeforeRetention {
+        /// The frontier the token resumes from.
+        resumes_from: Timestamp,
+        /// The earliest token that can be resumed from, if the inputs are readable at all.
+        earliest_token: Option<String>,
+    },
 }
 
 impl AdapterError {
@@ -330,6 +411,17 @@ impl AdapterError {
                     ).join("; "),
                 ))
             }
+            AdapterError::MaxStalenessExceeded { reference, .. } => {
+                Some(format!("The staleness is measured against {reference}."))
+            }
+            AdapterError::AsOfBeforeRetention { .. }
+            | AdapterError::ResumeTokenBeforeRetention { .. } => Some(
+                "The inputs of the query were compacted according to their retention windows."
+                    .into(),
+            ),
+            AdapterError::RealTimeRecencyTimeout { upper, .. } => {
+                Some(format!("The inputs of the query are complete up to {upper}."))
+            }
             _ => None,
         }
     }
@@ -361,6 +453,42 @@ impl AdapterError {
             AdapterError::NoClusterReplicasAvailable(_) => {
                 Some("You can create cluster replicas using CREATE CLUSTER REPLICA".into())
             }
+            AdapterError::AsOfBeforeRetention {
+                earliest_available,
+                ..
+            } => Some(format!(
+                "Use AS OF {earliest_available} or later, or AS OF AT LEAST to read at the \
+                earliest available timestamp."
+            )),
+            AdapterError::InvalidAsOfUpTo { as_of, .. } => Some(format!(
+                "Use an UP TO bound greater than {as_of}, or omit it to subscribe indefinitely."
+            )),
+            AdapterError::ResumeTokenBeforeRetention {
+                earliest_token: Some(token),
+                ..
+            } => Some(format!(
+                "Resume from '{token}' or a later token, or start a new SUBSCRIBE with a \
+                snapshot."
+            )),
+            AdapterError::ResumeTokenBeforeRetention {
+                earliest_token: None,
+                ..
+            } => Some("Start a new SUBSCRIBE with a snapshot.".into()),
+            AdapterError::ClusterQuiescing(_) => Some(
+                "Run the query on a different cluster, or retry once maintenance has finished."
+                    .into(),
+            ),
+            AdapterError::PortalMemoryExceeded { .. } => Some(
+                "Close open cursors with CLOSE, or increase the max_portal_memory session variable."
+                    .into(),
+            ),
+            AdapterError::SubscribeBufferLimitExceeded {
+                delivered_through: Some(ts),
+                ..
+            } => Some(format!(
+                "Resume with AS OF {ts} WITH (SNAPSHOT = false), or increase the \
+                max_subscribe_buffer_size session variable."
+            )),
             AdapterError::UntargetedLogRead { .. } => Some(
                 "Use `SET cluster_replica = <replica-name>` to target a specific replica in the \
                  active cluster. Note that subsequent queries will only be answered by \
@@ -377,6 +505,20 @@ impl AdapterError {
                  statement_timeout = '60s'`."
                     .into(),
             ),
+            AdapterError::RealTimeRecencyTimeout { .. } => Some(
+                "Consider increasing real_time_recency_timeout, or disabling real_time_recency \
+                 to read the data that is already available."
+                    .into(),
+            ),
+            AdapterError::TimestampOracleUnavailableForWrites => {
+                Some("Retry the transaction once the timestamp oracle recovers.".into())
+            }
+            AdapterError::TimestampOracleReadTimeout { .. }
+            | AdapterError::TimestampOracleUnavailableForReads => Some(
+                "Retry the query, or use SET TRANSACTION_ISOLATION TO 'SERIALIZABLE' to read \
+                 without the timestamp oracle."
+                    .into(),
+            ),
             AdapterError::PlanError(e) => e.hint(),
             AdapterError::UnallowedOnCluster { cluster, .. } => {
                 (cluster != MZ_INTROSPECTION_CLUSTER.name).then(||
@@ -412,6 +554,11 @@ impl AdapterError {
                     VarError::RequiresUnsafeMode { .. } => SqlState::CANT_CHANGE_RUNTIME_PARAM,
                     VarError::RequiresFeatureFlag { .. } => SqlState::CANT_CHANGE_RUNTIME_PARAM,
                 },
+                mz_catalog::memory::error::ErrorKind::Durable(
+                    mz_catalog::durable::DurableCatalogError::Commit(
+                        mz_catalog::durable::CommitError::Timeout { .. },
+                    ),
+                ) => SqlState::QUERY_CANCELED,
                 _ => SqlState::INTERNAL_ERROR,
             },
             AdapterError::ChangedPlan(_) => SqlState::FEATURE_NOT_SUPPORTED,
@@ -441,6 +588,7 @@ impl AdapterError {
             AdapterError::ConstraintViolation(NotNullViolation(_)) => SqlState::NOT_NULL_VIOLATION,
             AdapterError::ConcurrentClusterDrop => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::NoClusterReplicasAvailable(_) => SqlState::FEATURE_NOT_SUPPORTED,
+            AdapterError::ClusterQuiescing(_) => SqlState::OBJECT_NOT_IN_PREREQUISITE_STATE,
             AdapterError::OperationProhibitsTransaction(_) => SqlState::ACTIVE_SQL_TRANSACTION,
             AdapterError::OperationRequiresTransaction(_) => SqlState::NO_ACTIVE_SQL_TRANSACTION,
             AdapterError::ParseError(_) => SqlState::SYNTAX_ERROR,
@@ -459,6 +607,8 @@ impl AdapterError {
             AdapterError::RelationOutsideTimeDomain { .. } => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::ResourceExhaustion { .. } => SqlState::INSUFFICIENT_RESOURCES,
             AdapterError::ResultSize(_) => SqlState::OUT_OF_MEMORY,
+            AdapterError::PortalMemoryExceeded { .. } => SqlState::OUT_OF_MEMORY,
+            AdapterError::SubscribeBufferLimitExceeded { .. } => SqlState::OUT_OF_MEMORY,
             AdapterError::SafeModeViolation(_) => SqlState::INTERNAL_ERROR,
             AdapterError::SubscribeOnlyTransaction => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::Optimizer(e) => match e {
@@ -508,6 +658,16 @@ impl AdapterError {
             // `DATA_EXCEPTION`, similarly to `AbsurdSubscribeBounds`.
             AdapterError::MaterializedViewWouldNeverRefresh(_, _) => SqlState::DATA_EXCEPTION,
             AdapterError::InputNotReadableAtRefreshAtTime(_, _) => SqlState::DATA_EXCEPTION,
+            AdapterError::MaxStalenessExceeded { .. } => SqlState::DATA_EXCEPTION,
+            AdapterError::AsOfBeforeRetention { .. } => SqlState::SNAPSHOT_TOO_OLD,
+            AdapterError::RealTimeRecencyTimeout { .. } => SqlState::QUERY_CANCELED,
+            AdapterError::TimestampOracleUnavailableForWrites => SqlState::OPERATOR_INTERVENTION,
+            AdapterError::TimestampOracleReadTimeout { .. } => SqlState::QUERY_CANCELED,
+            AdapterError::TimestampOracleUnavailableForReads => SqlState::QUERY_CANCELED,
+            // `DATA_EXCEPTION`, similarly to `AbsurdSubscribeBounds`.
+            AdapterError::InvalidAsOfUpTo { .. } => SqlState::DATA_EXCEPTION,
+            AdapterError::InvalidResumeToken(_) => SqlState::INVALID_PARAMETER_VALUE,
+            AdapterError::ResumeTokenBeforeRetention { .. } => SqlState::SNAPSHOT_TOO_OLD,
         }
     }
 
@@ -582,6 +742,13 @@ impl fmt::Display for AdapterError {
                     cluster.quoted()
                 )
             }
+            AdapterError::ClusterQuiescing(cluster) => {
+                write!(
+                    f,
+                    "CLUSTER {} is quiescing and does not accept new queries",
+                    cluster.quoted()
+                )
+            }
             AdapterError::OperationProhibitsTransaction(op) => {
                 write!(f, "{} cannot be run inside a transaction block", op)
             }
@@ -633,6 +800,29 @@ impl fmt::Display for AdapterError {
                 )
             }
             AdapterError::ResultSize(e) => write!(f, "{e}"),
+            AdapterError::PortalMemoryExceeded { used, limit } => write!(
+                f,
+                "open portals buffer {} which exceeds max_portal_memory of {}",
+                ByteSize::b(*used),
+                ByteSize::b(*limit)
+            ),
+            AdapterError::SubscribeBufferLimitExceeded {
+                buffered,
+                limit,
+                delivered_through,
+            } => {
+                write!(
+                    f,
+                    "subscribe terminated because its client fell behind: {} of updates are \
+                    buffered, which exceeds max_subscribe_buffer_size of {}; ",
+                    ByteSize::b(*buffered),
+                    ByteSize::b(*limit)
+                )?;
+                match delivered_through {
+                    Some(ts) => write!(f, "all updates through timestamp {ts} were delivered"),
+                    None => write!(f, "no updates were delivered"),
+                }
+            }
             AdapterError::SafeModeViolation(feature) => {
                 write!(f, "cannot create {} in safe mode", feature)
             }
@@ -723,6 +913,67 @@ impl fmt::Display for AdapterError {
                     "REFRESH AT requested for a time where not all the inputs are readable"
                 )
             }
+            AdapterError::MaxStalenessExceeded {
+                timestamp,
+                max_staleness,
+                lagging,
+                ..
+            } => {
+                write!(
+                    f,
+                    "query timestamp {timestamp} exceeds max_staleness of {}ms",
+                    max_staleness.as_millis()
+                )?;
+                if !lagging.is_empty() {
+                    write!(
+                        f,
+                        " (lagging inputs: {})",
+                        lagging.iter().map(|name| name.quoted()).join(", ")
+                    )?;
+                }
+                Ok(())
+            }
+            AdapterError::AsOfBeforeRetention {
+                requested,
+                earliest_available,
+            } => write!(
+                f,
+                "AS OF {requested} is before the earliest available timestamp \
+                {earliest_available}; earlier data is no longer retained"
+            ),
+            AdapterError::RealTimeRecencyTimeout {
+                timestamp, timeout, ..
+            } => write!(
+                f,
+                "waiting for real-time recency timestamp {timestamp} would exceed \
+                real_time_recency_timeout of {}ms",
+                timeout.as_millis()
+            ),
+            AdapterError::TimestampOracleUnavailableForWrites => {
+                f.write_str("cannot commit writes while the timestamp oracle is unavailable")
+            }
+            AdapterError::TimestampOracleReadTimeout { timeout } => write!(
+                f,
+                "waiting for the unavailable timestamp oracle exceeded \
+                timestamp_oracle_degraded_read_timeout of {}ms",
+                timeout.as_millis()
+            ),
+            AdapterError::TimestampOracleUnavailableForReads => f.write_str(
+                "cannot serve strict serializable reads while the timestamp oracle is unavailable",
+            ),
+            AdapterError::InvalidAsOfUpTo { as_of, up_to } => write!(
+                f,
+                "UP TO {up_to} must be greater than AS OF {as_of}; the subscription would never \
+                produce any data"
+            ),
+            AdapterError::InvalidResumeToken(reason) => {
+                write!(f, "invalid resume token: {reason}")
+            }
+            AdapterError::ResumeTokenBeforeRetention { resumes_from, .. } => write!(
+                f,
+                "resume token for {resumes_from} is before the earliest available timestamp; \
+                updates after it are no longer retained"
+            ),
         }
     }
 }
@@ -11,7 +11,9 @@ use std::time::Duration;
 
 use mz_compute_client::protocol::command::ComputeParameters;
 use mz_compute_types::dataflows::YieldSpec;
-use mz_orchestrator::scheduling_config::{ServiceSchedulingConfig, ServiceTopologySpreadConfig};
+use mz_orchestrator::scheduling_config::{
+    ServiceCrashLoopConfig, ServiceSchedulingConfig, ServiceTopologySpreadConfig,
+};
 use mz_ore::cast::CastFrom;
 use mz_ore::error::ErrorExt;
 use mz_persist_client::cfg::PersistParameters;
@@ -87,6 +89,7 @@ pub fn storage_config(config: &SystemVars) -> StorageParameters {
             tcp_user_timeout: Some(config.pg_source_tcp_user_timeout()),
         },
         pg_source_snapshot_statement_timeout: config.pg_source_snapshot_statement_timeout(),
+        pg_source_snapshot_lock_timeout: config.pg_source_snapshot_lock_timeout(),
         keep_n_source_status_history_entries: config.keep_n_source_status_history_entries(),
         keep_n_sink_status_history_entries: config.keep_n_sink_status_history_entries(),
         keep_n_privatelink_status_history_entries: config
@@ -182,8 +185,24 @@ pub fn storage_config(config: &SystemVars) -> StorageParameters {
             collect_strict_count: config.pg_source_snapshot_collect_strict_count(),
             fallback_to_strict_count: config.pg_source_snapshot_fallback_to_strict_count(),
             wait_for_count: config.pg_source_snapshot_wait_for_count(),
+            max_in_flight_bytes: config.pg_source_snapshot_max_in_flight_bytes(),
+            copy_buffer_bytes: config.pg_source_snapshot_copy_buffer_bytes(),
+            peek_rewind_changes: config.pg_source_snapshot_peek_rewind_changes(),
+            group_window: config.pg_source_snapshot_group_window(),
+            group_timeout: config.pg_source_snapshot_group_timeout(),
+            // Fast snapshots are only suitable for some tables, so they can only be enabled for
+            // individual sources.
+            read_committed: false,
         },
         enable_dependency_read_hold_asserts: config.enable_dependency_read_hold_asserts(),
+        frontiers_history_full_resolution_window: config.frontiers_history_full_resolution_window(),
+        storage_command_max_bytes: config.storage_command_max_bytes(),
+        storage_command_chunking: config.storage_command_chunking(),
+        dropped_ids_batch_size: config.storage_dropped_ids_batch_size(),
+        // Overrides are part of the definitions of sources, not the system configuration.
+        source_overrides: Default::default(),
+        // Assigned by the storage controller.
+        generation: 0,
     }
 }
 
@@ -224,6 +243,15 @@ pub fn pg_timstamp_oracle_config(config: &SystemVars) -> PostgresTimestampOracle
         // oracle.
         pg_connection_pool_connect_timeout: Some(config.crdb_connect_timeout()),
         pg_connection_pool_tcp_user_timeout: Some(config.crdb_tcp_user_timeout()),
+        circuit_breaker_failure_threshold: Some(
+            config.pg_timestamp_oracle_circuit_breaker_failure_threshold(),
+        ),
+        circuit_breaker_latency_threshold: Some(
+            config.pg_timestamp_oracle_circuit_breaker_latency_threshold(),
+        ),
+        circuit_breaker_recovery_threshold: Some(
+            config.pg_timestamp_oracle_circuit_breaker_recovery_threshold(),
+        ),
     }
 }
 
@@ -250,5 +278,12 @@ pub fn orchestrator_scheduling_config(config: &SystemVars) -> ServiceSchedulingC
         soften_az_affinity: config.cluster_soften_az_affinity(),
         soften_az_affinity_weight: config.cluster_soften_az_affinity_weight(),
         always_use_disk: config.cluster_always_use_disk(),
+        crash_loop: ServiceCrashLoopConfig {
+            max_restarts: config.cluster_crash_loop_max_restarts(),
+            window: config.cluster_crash_loop_window(),
+            initial_backoff: config.cluster_crash_loop_initial_backoff(),
+            max_backoff: config.cluster_crash_loop_max_backoff(),
+            healthy_period: config.cluster_crash_loop_healthy_period(),
+        },
     }
 }
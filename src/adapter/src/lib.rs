@@ -45,6 +45,7 @@ mod error;
 mod explain;
 mod notice;
 mod optimize;
+mod resume_token;
 mod util;
 
 pub mod catalog;
@@ -61,9 +62,10 @@ pub use crate::client::{Client, Handle, SessionClient};
 pub use crate::command::{ExecuteResponse, ExecuteResponseKind, RowsFuture, StartupResponse};
 pub use crate::coord::id_bundle::CollectionIdBundle;
 pub use crate::coord::peek::PeekResponseUnary;
-pub use crate::coord::timeline::TimelineContext;
+pub use crate::coord::timeline::{TimelineContext, TimelineLinks};
 pub use crate::coord::timestamp_selection::{
-    TimestampContext, TimestampExplanation, TimestampProvider,
+    CollectionFreshness, TimestampContext, TimestampDeterminationContext, TimestampExplanation,
+    TimestampProvider, TIMESTAMP_SERIALIZATION_VERSION,
 };
 pub use crate::coord::ExecuteContext;
 pub use crate::coord::ExecuteContextExtra;
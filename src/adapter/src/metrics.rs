@@ -13,7 +13,7 @@ use mz_ore::stats::{histogram_milliseconds_buckets, histogram_seconds_buckets};
 use mz_sql::ast::{AstInfo, Statement, StatementKind, SubscribeOutput};
 use mz_sql::session::user::User;
 use mz_sql_parser::ast::statement_kind_label_value;
-use prometheus::{HistogramVec, IntCounter, IntCounterVec, IntGaugeVec};
+use prometheus::{Histogram, HistogramVec, IntCounter, IntCounterVec, IntGaugeVec};
 
 #[derive(Debug, Clone)]
 pub struct Metrics {
@@ -26,11 +26,15 @@ pub struct Metrics {
     pub watchdog_messages_processed: IntCounter,
     pub determine_timestamp: IntCounterVec,
     pub timestamp_difference_for_strict_serializable_ms: HistogramVec,
+    pub timestamp_wait_seconds: HistogramVec,
     pub commands: IntCounterVec,
     pub storage_usage_collection_time_seconds: HistogramVec,
     pub subscribe_outputs: IntCounterVec,
     pub canceled_peeks: IntCounterVec,
+    pub cancel_to_retire_seconds: Histogram,
+    pub peek_oracle_reads: IntCounter,
     pub linearize_message_seconds: HistogramVec,
+    pub read_then_write_timestamp_gap_ms: Histogram,
     pub time_to_first_row_seconds: HistogramVec,
     pub statement_logging_unsampled_bytes: IntCounterVec,
     pub statement_logging_actual_bytes: IntCounterVec,
@@ -39,6 +43,7 @@ pub struct Metrics {
     pub append_table_duration_seconds: HistogramVec,
     pub webhook_validation_reduce_failures: IntCounterVec,
     pub webhook_get_appender: IntCounter,
+    pub collection_drop_seconds: Histogram,
 }
 
 impl Metrics {
@@ -88,6 +93,12 @@ impl Metrics {
                 var_labels:["compute_instance"],
                 buckets: histogram_milliseconds_buckets(1., 8000.),
             )),
+            timestamp_wait_seconds: registry.register(metric!(
+                name: "mz_timestamp_wait_seconds",
+                help: "The time queries waited for the timestamp chosen for them to become readable.",
+                var_labels: ["compute_instance"],
+                buckets: histogram_seconds_buckets(0.000_128, 32.0),
+            )),
             commands: registry.register(metric!(
                 name: "mz_adapter_commands",
                 help: "The total number of adapter commands issued of the given type since process start.",
@@ -107,12 +118,26 @@ impl Metrics {
                 name: "mz_canceled_peeks_total",
                 help: "The total number of canceled peeks since process start.",
             )),
+            cancel_to_retire_seconds: registry.register(metric!(
+                name: "mz_cancel_to_retire_seconds",
+                help: "The number of seconds between a cancel request and the retirement of the statement it canceled.",
+                buckets: histogram_seconds_buckets(0.000_128, 32.0),
+            )),
+            peek_oracle_reads: registry.register(metric!(
+                name: "mz_peek_oracle_reads_total",
+                help: "The number of timestamp oracle reads for linearized peeks.",
+            )),
             linearize_message_seconds: registry.register(metric!(
                 name: "mz_linearize_message_seconds",
                 help: "The number of seconds it takes to linearize strict serializable messages",
                 var_labels: ["type", "immediately_handled"],
                 buckets: histogram_seconds_buckets(0.000_128, 8.0),
             )),
+            read_then_write_timestamp_gap_ms: registry.register(metric!(
+                name: "mz_read_then_write_timestamp_gap_ms",
+                help: "Difference in milliseconds between the timestamp a read-then-write operation read at and the timestamp its write committed at.",
+                buckets: histogram_milliseconds_buckets(1., 8000.),
+            )),
             time_to_first_row_seconds: registry.register(metric! {
                 name: "mz_time_to_first_row_seconds",
                 help: "Latency of an execute for a successful query from pgwire's perspective",
@@ -153,6 +178,11 @@ impl Metrics {
                 name: "mz_webhook_get_appender_count",
                 help: "Count of getting a webhook appender from the Coordinator.",
             )),
+            collection_drop_seconds: registry.register(metric!(
+                name: "mz_collection_drop_seconds",
+                help: "Latency from dropping a batch of collections until they are gone from the storage and compute controllers.",
+                buckets: histogram_seconds_buckets(0.128, 32.0),
+            )),
         }
     }
 }
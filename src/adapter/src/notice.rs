@@ -64,6 +64,13 @@ pub enum AdapterNotice {
         status: ClusterStatus,
         time: DateTime<Utc>,
     },
+    ClusterReplicaDiskHighWaterMark {
+        cluster: String,
+        replica: String,
+        process_id: u64,
+        disk_usage_bytes: u64,
+        disk_capacity_bytes: u64,
+    },
     CascadeDroppedObject {
         objects: Vec<String>,
     },
@@ -123,11 +130,22 @@ pub enum AdapterNotice {
     PerReplicaLogRead {
         log_names: Vec<String>,
     },
+    IntrospectionDataNotReported {
+        replica: String,
+    },
     VarDefaultUpdated {
         role: Option<String>,
         var_name: Option<String>,
     },
     Welcome(String),
+    TimestampOracleDegraded,
+    TimestampRoundedUp {
+        requested: String,
+        used: mz_repr::Timestamp,
+    },
+    DefaultTimeline {
+        triggers: Vec<String>,
+    },
 }
 
 impl AdapterNotice {
@@ -162,6 +180,7 @@ impl AdapterNotice {
                 NoticeSeverity::Warning => Severity::Warning,
             },
             AdapterNotice::ClusterReplicaStatusChanged { .. } => Severity::Notice,
+            AdapterNotice::ClusterReplicaDiskHighWaterMark { .. } => Severity::Warning,
             AdapterNotice::CascadeDroppedObject { .. } => Severity::Notice,
             AdapterNotice::DroppedActiveDatabase { .. } => Severity::Notice,
             AdapterNotice::DroppedActiveCluster { .. } => Severity::Notice,
@@ -187,8 +206,12 @@ impl AdapterNotice {
             AdapterNotice::WebhookSourceCreated { .. } => Severity::Notice,
             AdapterNotice::DroppedInUseIndex { .. } => Severity::Notice,
             AdapterNotice::PerReplicaLogRead { .. } => Severity::Notice,
+            AdapterNotice::IntrospectionDataNotReported { .. } => Severity::Notice,
             AdapterNotice::VarDefaultUpdated { .. } => Severity::Notice,
             AdapterNotice::Welcome(_) => Severity::Notice,
+            AdapterNotice::TimestampOracleDegraded => Severity::Warning,
+            AdapterNotice::TimestampRoundedUp { .. } => Severity::Notice,
+            AdapterNotice::DefaultTimeline { .. } => Severity::Notice,
         }
     }
 
@@ -197,6 +220,10 @@ impl AdapterNotice {
         match self {
             AdapterNotice::PlanNotice(notice) => notice.detail(),
             AdapterNotice::QueryTimestamp { explanation } => Some(format!("\n{explanation}")),
+            AdapterNotice::DefaultTimeline { triggers } => Some(format!(
+                "The query depends on the timestamp it runs at because of {}, but reads no object that belongs to a timeline.",
+                triggers.join(", ")
+            )),
             AdapterNotice::CascadeDroppedObject { objects } => Some(
                 objects
                     .iter()
@@ -219,9 +246,11 @@ impl AdapterNotice {
                 match status {
                     ServiceStatus::NotReady(None) => Some("The cluster replica may be restarting or going offline.".into()),
                     ServiceStatus::NotReady(Some(NotReadyReason::OomKilled)) => Some("The cluster replica may have run out of memory and been killed.".into()),
+                    ServiceStatus::NotReady(Some(NotReadyReason::CrashLooping { .. })) => Some("The cluster replica kept crashing and is held back from restarting for a while. Consider resizing the cluster or reducing its workload.".into()),
                     ServiceStatus::Ready => None,
                 }
             },
+            AdapterNotice::ClusterReplicaDiskHighWaterMark { .. } => Some("The cluster replica may crash once it runs out of disk. Consider resizing the cluster or reducing its workload.".into()),
             AdapterNotice::RbacUserDisabled => Some("To enable RBAC globally run `ALTER SYSTEM SET enable_rbac_checks TO TRUE` as a superuser. TO enable RBAC for just this session run `SET enable_session_rbac_checks TO TRUE`.".into()),
             AdapterNotice::AlterIndexOwner {name: _} => Some("Change the ownership of the index's relation, instead.".into()),
             AdapterNotice::UnknownSessionDatabase(_) => Some(
@@ -231,7 +260,14 @@ impl AdapterNotice {
                     .into(),
             ),
             AdapterNotice::OptimizerNotice { notice: _, hint } => Some(hint.clone()),
+            AdapterNotice::IntrospectionDataNotReported { .. } => Some(
+                "To wait for the replica to report introspection data, \
+                 SET introspection_wait_timeout to a duration."
+                    .into(),
+            ),
             AdapterNotice::DroppedInUseIndex(..) => Some("To free up the resources used by the index, recreate all the above-mentioned objects.".into()),
+            AdapterNotice::TimestampOracleDegraded => Some("The results may not reflect writes that completed before the query started. Strict serializable reads resume once the timestamp oracle recovers.".into()),
+            AdapterNotice::DefaultTimeline { .. } => Some("To pin the query to a timeline, also read from an object that belongs to it. Queries that rely on the default may change meaning once objects in other timelines are common.".into()),
             _ => None
         }
     }
@@ -252,6 +288,7 @@ impl AdapterNotice {
             }
             AdapterNotice::UserRequested { .. } => SqlState::WARNING,
             AdapterNotice::ClusterReplicaStatusChanged { .. } => SqlState::WARNING,
+            AdapterNotice::ClusterReplicaDiskHighWaterMark { .. } => SqlState::WARNING,
             AdapterNotice::CascadeDroppedObject { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::DroppedActiveDatabase { .. } => SqlState::WARNING,
             AdapterNotice::DroppedActiveCluster { .. } => SqlState::WARNING,
@@ -277,8 +314,12 @@ impl AdapterNotice {
             AdapterNotice::DroppedInUseIndex { .. } => SqlState::WARNING,
             AdapterNotice::WebhookSourceCreated { .. } => SqlState::WARNING,
             AdapterNotice::PerReplicaLogRead { .. } => SqlState::WARNING,
+            AdapterNotice::IntrospectionDataNotReported { .. } => SqlState::WARNING,
             AdapterNotice::VarDefaultUpdated { .. } => SqlState::SUCCESSFUL_COMPLETION,
             AdapterNotice::Welcome(_) => SqlState::SUCCESSFUL_COMPLETION,
+            AdapterNotice::TimestampOracleDegraded => SqlState::WARNING,
+            AdapterNotice::TimestampRoundedUp { .. } => SqlState::SUCCESSFUL_COMPLETION,
+            AdapterNotice::DefaultTimeline { .. } => SqlState::WARNING,
         }
     }
 }
@@ -341,6 +382,17 @@ impl fmt::Display for AdapterNotice {
                 )?;
                 Ok(())
             }
+            AdapterNotice::ClusterReplicaDiskHighWaterMark {
+                cluster,
+                replica,
+                process_id,
+                disk_usage_bytes,
+                disk_capacity_bytes,
+            } => write!(
+                f,
+                "cluster replica {}.{} process {} is using {} of {} bytes of disk",
+                cluster, replica, process_id, disk_usage_bytes, disk_capacity_bytes,
+            ),
             AdapterNotice::DroppedActiveDatabase { name } => {
                 write!(f, "active database {} has been dropped", name.quoted())
             }
@@ -429,6 +481,10 @@ impl fmt::Display for AdapterNotice {
             AdapterNotice::PerReplicaLogRead { log_names } => {
                 write!(f, "Queried introspection relations: {}. Unlike other objects in Materialize, results from querying these objects depend on the current values of the `cluster` and `cluster_replica` session variables.", log_names.join(", "))
             }
+            AdapterNotice::IntrospectionDataNotReported { replica } => write!(
+                f,
+                "replica {replica} has not yet reported introspection data; it may still be starting"
+            ),
             AdapterNotice::VarDefaultUpdated { role, var_name } => {
                 let vars = match var_name {
                     Some(name) => format!("variable {} was", name.quoted()),
@@ -444,6 +500,20 @@ impl fmt::Display for AdapterNotice {
                 )
             }
             AdapterNotice::Welcome(message) => message.fmt(f),
+            AdapterNotice::TimestampOracleDegraded => write!(
+                f,
+                "the timestamp oracle is unavailable, query was served with {} isolation instead of {}",
+                IsolationLevel::Serializable.as_str(),
+                IsolationLevel::StrictSerializable.as_str(),
+            ),
+            AdapterNotice::TimestampRoundedUp { requested, used } => write!(
+                f,
+                "{requested} has sub-millisecond precision and was rounded up to the mz_timestamp {used}"
+            ),
+            AdapterNotice::DefaultTimeline { .. } => write!(
+                f,
+                "query timestamp defaulted to the EpochMilliseconds timeline"
+            ),
         }
     }
 }
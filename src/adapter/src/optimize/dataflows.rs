@@ -119,7 +119,10 @@ pub enum ExprPrepStyle<'a> {
         catalog_state: &'a CatalogState,
     },
     /// The expression is being prepared for evaluation in an AS OF or UP TO clause.
-    AsOfUpTo,
+    AsOfUpTo {
+        /// Wall clock of the transaction the expression is evaluated in.
+        wall_time: DateTime<Utc>,
+    },
     /// The expression is being prepared for evaluation in a CHECK expression of a webhook source.
     WebhookValidation {
         /// Time at which this expression is being evaluated.
@@ -463,7 +466,7 @@ pub fn prep_relation_expr(
             })
         }
         ExprPrepStyle::OneShot { .. }
-        | ExprPrepStyle::AsOfUpTo
+        | ExprPrepStyle::AsOfUpTo { .. }
         | ExprPrepStyle::WebhookValidation { .. } => expr
             .0
             .try_visit_scalars_mut(&mut |s| prep_scalar_expr(s, style)),
@@ -477,7 +480,9 @@ pub fn prep_relation_expr(
 ///
 /// * `OneShot`: Calls to all unmaterializable functions are replaced.
 /// * `Index`: An error is produced if a call to an unmaterializable function is encountered.
-/// * `AsOfUpTo`: An error is produced if a call to an unmaterializable function is encountered.
+/// * `AsOfUpTo`: Calls to `UnmaterializableFunc::CurrentTimestamp` are replaced with the
+///   transaction's wall clock, an error is produced if a call to any other unmaterializable
+///   function is encountered.
 /// * `WebhookValidation`: Only calls to `UnmaterializableFunc::CurrentTimestamp` are replaced,
 ///   others are left untouched.
 ///
@@ -500,7 +505,22 @@ pub fn prep_scalar_expr(
         }),
 
         // Reject the query if it contains any unmaterializable function calls.
-        ExprPrepStyle::Index | ExprPrepStyle::AsOfUpTo => {
+        ExprPrepStyle::Index | ExprPrepStyle::AsOfUpTo { .. } => {
+            // `now()` in an AS OF or UP TO clause refers to the wall clock of the transaction, so
+            // that all statements in a transaction observe the same value, as in Postgres.
+            if let ExprPrepStyle::AsOfUpTo { wall_time } = style {
+                expr.try_visit_mut_post(&mut |e| {
+                    if let MirScalarExpr::CallUnmaterializable(
+                        f @ UnmaterializableFunc::CurrentTimestamp,
+                    ) = e
+                    {
+                        let now: Datum = wall_time.try_into()?;
+                        *e = MirScalarExpr::literal_ok(now, f.output_type().scalar_type);
+                    }
+                    Ok::<_, anyhow::Error>(())
+                })?;
+            }
+
             let mut last_observed_unmaterializable_func = None;
             expr.visit_mut_post(&mut |e| {
                 if let MirScalarExpr::CallUnmaterializable(f) = e {
@@ -511,7 +531,7 @@ pub fn prep_scalar_expr(
             if let Some(f) = last_observed_unmaterializable_func {
                 let err = match style {
                     ExprPrepStyle::Index => OptimizerError::UnmaterializableFunction(f),
-                    ExprPrepStyle::AsOfUpTo => OptimizerError::UncallableFunction {
+                    ExprPrepStyle::AsOfUpTo { .. } => OptimizerError::UncallableFunction {
                         func: f,
                         context: "AS OF or UP TO",
                     },
@@ -0,0 +1,167 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Tokens with which clients resume a `SUBSCRIBE` where a previous one left off.
+//!
+//! A `SUBSCRIBE ... WITH (PROGRESS, RESUME TOKENS)` includes a token in each of its progress
+//! messages after the snapshot that encodes the frontier the message reports, i.e. all updates at
+//! times before it were delivered. A `SUBSCRIBE ... WITH (RESUME FROM = '<token>')` then delivers exactly the
+//! updates at times not before that frontier, without a snapshot, so that no update is delivered
+//! twice or skipped across the resume boundary.
+//!
+//! Tokens are opaque to clients. They are the URL-safe base64 encoding of a versioned
+//! [`ProtoSubscribeResumeToken`].
+
+use mz_compute_client::protocol::response::ProtoSubscribeResumeToken;
+use mz_proto::{ProtoType, RustType};
+use mz_repr::Timestamp;
+use prost::Message;
+use timely::progress::Antichain;
+
+use crate::AdapterError;
+
+/// The version of the tokens that are handed out, and the only one that is accepted.
+const RESUME_TOKEN_VERSION: u32 = 1;
+
+/// The frontier through which a subscribe delivered its updates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ResumeToken {
+    /// All updates at times before the frontier were delivered.
+    pub(crate) frontier: Timestamp,
+}
+
+impl ResumeToken {
+    /// Returns the token of the earliest frontier a subscribe can resume from when its inputs are
+    /// compacted to `since`, if they are readable at all.
+    pub(crate) fn earliest(since: &Antichain<Timestamp>) -> Option<Self> {
+        let since = since.as_option()?;
+        let frontier = if *since == Timestamp::MIN {
+            *since
+        } else {
+            since.try_step_forward()?
+        };
+        Some(ResumeToken { frontier })
+    }
+
+    /// Returns the `AS OF` of a subscribe that resumes from the token, and whether it emits a
+    /// snapshot.
+    ///
+    /// The subscribe doesn't emit a snapshot but only the updates at times beyond its `AS OF`,
+    /// which are those not before the frontier. Nothing was delivered before the minimum
+    /// frontier, so a subscribe that resumes from it starts over with a snapshot at the minimum
+    /// time instead, which contains exactly the updates at that time.
+    pub(crate) fn as_of(&self) -> (Timestamp, bool) {
+        match self.frontier.step_back() {
+            Some(as_of) => (as_of, false),
+            None => (self.frontier, true),
+        }
+    }
+
+    pub(crate) fn encode(&self) -> String {
+        let proto = ProtoSubscribeResumeToken {
+            version: RESUME_TOKEN_VERSION,
+            frontier: Some(Antichain::from_elem(self.frontier).into_proto()),
+        };
+        base64::encode_config(proto.encode_to_vec(), base64::URL_SAFE_NO_PAD)
+    }
+
+    pub(crate) fn decode(token: &str) -> Result<Self, AdapterError> {
+        let invalid = |reason: &str| AdapterError::InvalidResumeToken(reason.into());
+        let bytes = base64::decode_config(token, base64::URL_SAFE_NO_PAD)
+            .map_err(|_| invalid("not base64"))?;
+        let proto = ProtoSubscribeResumeToken::decode(&*bytes).map_err(|_| invalid("malformed"))?;
+        if proto.version != RESUME_TOKEN_VERSION {
+            return Err(AdapterError::InvalidResumeToken(format!(
+                "unsupported version {}",
+                proto.version
+            )));
+        }
+        let frontier: Antichain<Timestamp> = proto
+            .frontier
+            .into_rust_if_some("ProtoSubscribeResumeToken::frontier")
+            .map_err(|_| invalid("malformed"))?;
+        match frontier.as_option() {
+            Some(frontier) => Ok(ResumeToken {
+                frontier: *frontier,
+            }),
+            None => Err(invalid("the subscribe it was handed out by has finished")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_resume_token_round_trip() {
+        for frontier in [0, 1, 1_700_000_000_000, u64::MAX] {
+            let token = ResumeToken {
+                frontier: frontier.into(),
+            };
+            let encoded = token.encode();
+            assert!(encoded
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+            assert_eq!(ResumeToken::decode(&encoded).unwrap(), token);
+        }
+
+        let decode_err = |token: &str| ResumeToken::decode(token).unwrap_err().to_string();
+        assert_eq!(
+            decode_err("not a token!"),
+            "invalid resume token: not base64"
+        );
+        assert_eq!(decode_err("_w"), "invalid resume token: malformed");
+        let future = ProtoSubscribeResumeToken {
+            version: RESUME_TOKEN_VERSION + 1,
+            frontier: Some(Antichain::from_elem(Timestamp::from(1)).into_proto()),
+        };
+        let future = base64::encode_config(future.encode_to_vec(), base64::URL_SAFE_NO_PAD);
+        assert_eq!(
+            decode_err(&future),
+            "invalid resume token: unsupported version 2"
+        );
+        let finished = ProtoSubscribeResumeToken {
+            version: RESUME_TOKEN_VERSION,
+            frontier: Some(Antichain::<Timestamp>::new().into_proto()),
+        };
+        let finished = base64::encode_config(finished.encode_to_vec(), base64::URL_SAFE_NO_PAD);
+        assert_eq!(
+            decode_err(&finished),
+            "invalid resume token: the subscribe it was handed out by has finished"
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_resume_token_compaction() {
+        // A subscribe resumes from the time just before the frontier, without a snapshot, unless
+        // nothing was delivered yet.
+        let token = |frontier: u64| ResumeToken {
+            frontier: frontier.into(),
+        };
+        assert_eq!(token(10).as_of(), (9.into(), false));
+        assert_eq!(token(0).as_of(), (0.into(), true));
+
+        // The earliest valid token resumes at the since, and is invalidated when the inputs are
+        // compacted further.
+        let since = Antichain::from_elem(Timestamp::from(10));
+        let earliest = ResumeToken::earliest(&since).unwrap();
+        assert_eq!(earliest, token(11));
+        assert!(since.less_equal(&earliest.as_of().0));
+        assert!(!since.less_equal(&token(10).as_of().0));
+        let since = Antichain::from_elem(Timestamp::from(11));
+        assert!(!since.less_equal(&earliest.as_of().0));
+
+        assert_eq!(
+            ResumeToken::earliest(&Antichain::from_elem(Timestamp::from(0))),
+            Some(token(0))
+        );
+        assert_eq!(ResumeToken::earliest(&Antichain::new()), None);
+    }
+}
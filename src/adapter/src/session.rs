@@ -14,15 +14,22 @@
 use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
+use std::future::Future;
 use std::mem;
-use std::sync::Arc;
+use std::pin::Pin;
+use std::sync::atomic::{self, AtomicU64};
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use std::time::Instant;
 
 use chrono::{DateTime, Utc};
 use derivative::Derivative;
+use futures::{Stream, StreamExt};
 use mz_adapter_types::connection::ConnectionId;
 use mz_build_info::{BuildInfo, DUMMY_BUILD_INFO};
 use mz_controller_types::ClusterId;
-use mz_ore::now::{EpochMillis, NowFn};
+use mz_ore::cast::CastFrom;
+use mz_ore::now::{EpochMillis, NowFn, SYSTEM_TIME};
 use mz_pgwire_common::Format;
 use mz_repr::role_id::RoleId;
 use mz_repr::user::ExternalUserMetadata;
@@ -72,6 +79,12 @@ where
     uuid: Uuid,
     prepared_statements: BTreeMap<String, PreparedStatement>,
     portals: BTreeMap<String, Portal>,
+    /// Accounting of the results buffered by `portals`, shared with the
+    /// coordinator.
+    portal_usage: PortalUsage,
+    /// Signals the cancellation of the statements of this session, shared
+    /// with the client.
+    cancel_signal: CancelSignal,
     transaction: TransactionStatus<T>,
     pcx: Option<PlanContext>,
     /// The role metadata of the current session.
@@ -107,6 +120,9 @@ where
     #[derivative(Debug = "ignore")]
     qcell_owner: QCellOwner,
     session_oracles: BTreeMap<Timeline, InMemoryTimestampOracle<T, NowFn<T>>>,
+    /// The largest timestamp at which a write performed by this session was
+    /// applied, if any.
+    last_write_ts: Option<T>,
 }
 
 impl<T: TimestampManipulation> Session<T> {
@@ -189,6 +205,8 @@ impl<T: TimestampManipulation> Session<T> {
             pcx: None,
             prepared_statements: BTreeMap::new(),
             portals: BTreeMap::new(),
+            portal_usage: PortalUsage::default(),
+            cancel_signal: CancelSignal::default(),
             role_metadata: None,
             vars,
             notices_tx,
@@ -198,6 +216,7 @@ impl<T: TimestampManipulation> Session<T> {
             external_metadata_rx: None,
             qcell_owner: QCellOwner::new(),
             session_oracles: BTreeMap::new(),
+            last_write_ts: None,
         }
     }
 
@@ -326,6 +345,7 @@ impl<T: TimestampManipulation> Session<T> {
     #[must_use]
     pub fn clear_transaction(&mut self) -> TransactionStatus<T> {
         self.portals.clear();
+        self.portal_usage.clear();
         self.pcx = None;
         mem::take(&mut self.transaction)
     }
@@ -421,7 +441,9 @@ impl<T: TimestampManipulation> Session<T> {
             return None;
         }
         // Filter out notices for other clusters.
-        if let AdapterNotice::ClusterReplicaStatusChanged { cluster, .. } = &notice {
+        if let AdapterNotice::ClusterReplicaStatusChanged { cluster, .. }
+        | AdapterNotice::ClusterReplicaDiskHighWaterMark { cluster, .. } = &notice
+        {
             if cluster != self.vars.cluster() {
                 return None;
             }
@@ -442,14 +464,20 @@ impl<T: TimestampManipulation> Session<T> {
     /// any existed. Must only be used after verifying that no transaction
     /// anomalies will occur if cleared.
     pub fn take_transaction_timestamp_context(&mut self) -> Option<TimestampContext<T>> {
+        self.take_transaction_timestamp_determination()
+            .map(|determination| determination.timestamp_context)
+    }
+
+    /// Like [`Session::take_transaction_timestamp_context`], but returns the
+    /// read's entire timestamp determination, including the frontiers and
+    /// oracle timestamps it was made against.
+    pub fn take_transaction_timestamp_determination(
+        &mut self,
+    ) -> Option<TimestampDetermination<T>> {
         if let Some(Transaction { ops, .. }) = self.transaction.inner_mut() {
             if let TransactionOps::Peeks { .. } = ops {
                 let ops = std::mem::take(ops);
-                Some(
-                    ops.timestamp_determination()
-                        .expect("checked above")
-                        .timestamp_context,
-                )
+                Some(ops.timestamp_determination().expect("checked above"))
             } else {
                 None
             }
@@ -475,6 +503,37 @@ impl<T: TimestampManipulation> Session<T> {
         }
     }
 
+    /// Returns the timestamp determination that a `READ ONLY` transaction pinned on its first
+    /// read, if a read with `when` reuses it.
+    ///
+    /// A read-only transaction reads at the timestamp that was chosen for its first read
+    /// throughout, so its later reads need no timestamp from the oracle. Reads with an `AS OF`
+    /// choose their own timestamp and don't reuse the pinned one.
+    pub fn get_pinned_read_only_timestamp_determination(
+        &self,
+        when: &QueryWhen,
+    ) -> Option<TimestampDetermination<T>> {
+        if !self.transaction.in_immediate_multi_stmt_txn(when) {
+            return None;
+        }
+        match self.transaction.inner() {
+            Some(Transaction {
+                ops:
+                    TransactionOps::Peeks {
+                        determination:
+                            determination @ TimestampDetermination {
+                                timestamp_context: TimestampContext::TimelineTimestamp { .. },
+                                ..
+                            },
+                        ..
+                    },
+                access: Some(TransactionAccessMode::ReadOnly),
+                ..
+            }) => Some(determination.clone()),
+            _ => None,
+        }
+    }
+
     /// Whether this session has a timestamp for a read transaction.
     pub fn contains_read_timestamp(&self) -> bool {
         matches!(
@@ -589,6 +648,7 @@ impl<T: TimestampManipulation> Session<T> {
         if !portal_name.is_empty() && self.portals.contains_key(&portal_name) {
             return Err(AdapterError::DuplicateCursor(portal_name));
         }
+        self.portal_usage.insert(portal_name.clone(), SYSTEM_TIME());
         self.portals.insert(
             portal_name,
             Portal {
@@ -611,9 +671,43 @@ impl<T: TimestampManipulation> Session<T> {
     ///
     /// If there is no such portal, this method does nothing. Returns whether that portal existed.
     pub fn remove_portal(&mut self, portal_name: &str) -> bool {
+        self.portal_usage.remove(portal_name);
         self.portals.remove(portal_name).is_some()
     }
 
+    /// Recomputes the number of result bytes buffered by the specified portal.
+    ///
+    /// Must be called whenever rows are stored into or released from the
+    /// portal's state, so that [`Session::portal_usage`] stays accurate.
+    pub fn update_portal_buffered_bytes(&mut self, portal_name: &str) {
+        if let Some(portal) = self.portals.get(portal_name) {
+            self.portal_usage
+                .set_buffered_bytes(portal_name, portal.buffered_bytes());
+        }
+    }
+
+    /// Returns the accounting of the results buffered by this session's
+    /// portals.
+    pub fn portal_usage(&self) -> &PortalUsage {
+        &self.portal_usage
+    }
+
+    /// Returns the signal that cancels the statements of this session.
+    pub fn cancel_signal(&self) -> &CancelSignal {
+        &self.cancel_signal
+    }
+
+    /// Returns an error if the results buffered by this session's portals
+    /// exceed `max_portal_memory`.
+    pub fn check_portal_memory(&self) -> Result<(), AdapterError> {
+        let used = self.portal_usage.total_bytes();
+        let limit = self.vars.max_portal_memory();
+        if used > limit {
+            return Err(AdapterError::PortalMemoryExceeded { used, limit });
+        }
+        Ok(())
+    }
+
     /// Retrieves a reference to the specified portal.
     ///
     /// If there is no such portal, returns `None`.
@@ -645,6 +739,7 @@ impl<T: TimestampManipulation> Session<T> {
             match self.portals.entry(name.clone()) {
                 Entry::Occupied(_) => continue,
                 Entry::Vacant(entry) => {
+                    self.portal_usage.insert(name.clone(), SYSTEM_TIME());
                     entry.insert(Portal {
                         stmt: stmt.map(Arc::new),
                         desc,
@@ -662,11 +757,14 @@ impl<T: TimestampManipulation> Session<T> {
         coord_bail!("unable to create a new portal");
     }
 
-    /// Resets the session to its initial state. Returns sinks that need to be
-    /// dropped.
+    /// Resets the session to its initial state: clears the transaction, portals, prepared
+    /// statements, and session timestamp oracles, and resets all variables to their defaults,
+    /// which include the defaults of the session's role.
     pub fn reset(&mut self) {
         let _ = self.clear_transaction();
         self.prepared_statements.clear();
+        self.session_oracles.clear();
+        self.last_write_ts = None;
         self.vars.reset_all();
     }
 
@@ -816,12 +914,30 @@ impl<T: TimestampManipulation> Session<T> {
         self.session_oracles.get(timeline)
     }
 
-    /// If the current session is using the Strong Session Serializable isolation level advance the
-    /// session local timestamp oracle to `write_ts`.
+    /// Returns the timestamp oracles of the session, by timeline.
+    pub fn timestamp_oracles(&self) -> &BTreeMap<Timeline, InMemoryTimestampOracle<T, NowFn<T>>> {
+        &self.session_oracles
+    }
+
+    /// Records that a write performed by this session was applied at `timestamp`.
+    ///
+    /// If the current session is using the Strong Session Serializable isolation level, this also
+    /// advances the session local timestamp oracle to `timestamp`.
     pub fn apply_write(&mut self, timestamp: T) {
         if self.vars().transaction_isolation() == &IsolationLevel::StrongSessionSerializable {
-            self.ensure_local_timestamp_oracle().apply_write(timestamp);
+            self.ensure_local_timestamp_oracle()
+                .apply_write(timestamp.clone());
         }
+        self.last_write_ts = Some(match self.last_write_ts.take() {
+            Some(last_write_ts) => std::cmp::max(last_write_ts, timestamp),
+            None => timestamp,
+        });
+    }
+
+    /// Returns the largest timestamp at which a write performed by this session was applied, if
+    /// any.
+    pub fn last_write_ts(&self) -> Option<&T> {
+        self.last_write_ts.as_ref()
     }
 }
 
@@ -877,6 +993,144 @@ pub struct Portal {
     pub state: PortalState,
 }
 
+impl Portal {
+    /// Returns the number of bytes of result rows currently buffered by the
+    /// portal.
+    pub fn buffered_bytes(&self) -> u64 {
+        match &self.state {
+            PortalState::InProgress(Some(InProgressRows {
+                current: Some(rows),
+                ..
+            })) => rows.iter().map(|row| u64::cast_from(row.byte_len())).sum(),
+            _ => 0,
+        }
+    }
+}
+
+/// Resource usage of a single portal, as tracked by [`PortalUsage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortalUsageEntry {
+    /// The time at which the portal was created.
+    pub created_at: EpochMillis,
+    /// The number of bytes of result rows buffered by the portal.
+    pub buffered_bytes: u64,
+}
+
+/// Per-session accounting of the result bytes buffered by open portals.
+///
+/// The handle is cheaply cloneable. The session owns the authoritative copy
+/// and updates it as portals are created, advanced and closed; the
+/// coordinator holds a clone so that it can report open portals without
+/// access to the session itself.
+#[derive(Debug, Clone, Default)]
+pub struct PortalUsage {
+    inner: Arc<Mutex<BTreeMap<String, PortalUsageEntry>>>,
+}
+
+impl PortalUsage {
+    fn insert(&self, name: String, created_at: EpochMillis) {
+        let entry = PortalUsageEntry {
+            created_at,
+            buffered_bytes: 0,
+        };
+        self.inner
+            .lock()
+            .expect("lock poisoned")
+            .insert(name, entry);
+    }
+
+    fn remove(&self, name: &str) {
+        self.inner.lock().expect("lock poisoned").remove(name);
+    }
+
+    fn clear(&self) {
+        self.inner.lock().expect("lock poisoned").clear();
+    }
+
+    fn set_buffered_bytes(&self, name: &str, buffered_bytes: u64) {
+        if let Some(entry) = self.inner.lock().expect("lock poisoned").get_mut(name) {
+            entry.buffered_bytes = buffered_bytes;
+        }
+    }
+
+    /// Returns the total number of bytes buffered across all portals.
+    pub fn total_bytes(&self) -> u64 {
+        self.inner
+            .lock()
+            .expect("lock poisoned")
+            .values()
+            .map(|entry| entry.buffered_bytes)
+            .sum()
+    }
+
+    /// Returns a copy of the current usage of every open portal, keyed by
+    /// portal name.
+    pub fn snapshot(&self) -> BTreeMap<String, PortalUsageEntry> {
+        self.inner.lock().expect("lock poisoned").clone()
+    }
+}
+
+/// Signals the cancellation of the statements executed by a session.
+///
+/// The handle is cheaply cloneable. Cancel requests are handled by the
+/// coordinator, which can't handle them while it awaits work on behalf of the
+/// statement they cancel. Clients therefore also signal cancellations through
+/// this handle, and the coordinator selects on the [`CancelToken`] of a
+/// statement around such awaits.
+#[derive(Debug, Clone)]
+pub struct CancelSignal {
+    tx: Arc<watch::Sender<Option<Instant>>>,
+}
+
+impl Default for CancelSignal {
+    fn default() -> Self {
+        let (tx, _rx) = watch::channel(None);
+        CancelSignal { tx: Arc::new(tx) }
+    }
+}
+
+impl CancelSignal {
+    /// Cancels the statement that is currently executing, if any.
+    pub fn cancel(&self) {
+        self.tx.send_replace(Some(Instant::now()));
+    }
+
+    /// Returns a token that observes the cancellations signaled from now on.
+    pub fn token(&self) -> CancelToken {
+        CancelToken {
+            rx: self.tx.subscribe(),
+        }
+    }
+}
+
+/// Observes the cancellation of a statement, see [`CancelSignal`].
+#[derive(Debug, Clone)]
+pub struct CancelToken {
+    rx: watch::Receiver<Option<Instant>>,
+}
+
+impl CancelToken {
+    /// Returns the time at which the statement was canceled, if it was.
+    pub fn canceled_at(&self) -> Option<Instant> {
+        match self.rx.has_changed() {
+            Ok(true) => *self.rx.borrow(),
+            _ => None,
+        }
+    }
+
+    /// Drives `fut` to completion, unless the statement is canceled first. In
+    /// that case, `fut` is dropped and the time at which the statement was
+    /// canceled is returned instead.
+    pub async fn run_until_canceled<F: Future>(&self, fut: F) -> Result<F::Output, Instant> {
+        let mut rx = self.rx.clone();
+        tokio::select! {
+            biased;
+            Ok(()) = rx.changed() => Err(rx.borrow().expect("cancellations carry their time")),
+            output = fut => Ok(output),
+        }
+    }
+}
+
 /// Execution states of a portal.
 pub enum PortalState {
     /// Portal not yet started.
@@ -909,7 +1163,42 @@ impl InProgressRows {
 }
 
 /// A channel of batched rows.
-pub type RowBatchStream = UnboundedReceiver<PeekResponseUnary>;
+///
+/// Keeps track of the bytes of rows that were sent on the channel but not yet
+/// received, so that the sender can bound them.
+#[derive(Debug)]
+pub struct RowBatchStream {
+    rx: UnboundedReceiver<PeekResponseUnary>,
+    /// The number of bytes of rows sent but not yet received, shared with the
+    /// sender.
+    buffered_bytes: Arc<AtomicU64>,
+}
+
+impl RowBatchStream {
+    /// Creates a stream of the batches received on `rx`, whose sender adds
+    /// the bytes of each batch it sends to `buffered_bytes`.
+    pub fn new(rx: UnboundedReceiver<PeekResponseUnary>, buffered_bytes: Arc<AtomicU64>) -> Self {
+        Self { rx, buffered_bytes }
+    }
+
+    /// Receives the next batch, or `None` if the sender has gone away.
+    pub async fn recv(&mut self) -> Option<PeekResponseUnary> {
+        self.next().await
+    }
+}
+
+impl Stream for RowBatchStream {
+    type Item = PeekResponseUnary;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let response = ready!(self.rx.poll_recv(cx));
+        if let Some(response) = &response {
+            self.buffered_bytes
+                .fetch_sub(response.byte_len(), atomic::Ordering::Relaxed);
+        }
+        Poll::Ready(response)
+    }
+}
 
 /// The transaction status of a session.
 ///
@@ -1379,6 +1668,9 @@ pub struct WriteOp {
     pub id: GlobalId,
     /// The data rows.
     pub rows: Vec<(Row, Diff)>,
+    /// For rows derived from a read, e.g. by an `UPDATE`, the timestamp they
+    /// were read at. The write must commit at a strictly later timestamp.
+    pub read_ts: Option<mz_repr::Timestamp>,
 }
 
 /// Whether a transaction requires linearization.
@@ -1398,3 +1690,162 @@ impl From<&ExplainContext> for RequireLinearization {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use mz_repr::Timestamp;
+    use mz_timestamp_oracle::{ShareableTimestampOracle, TimestampOracle, WriteTimestamp};
+    use timely::progress::Antichain;
+
+    use super::*;
+
+    fn peeks_at(ts: u64) -> TransactionOps<Timestamp> {
+        TransactionOps::Peeks {
+            determination: TimestampDetermination {
+                timestamp_context: TimestampContext::TimelineTimestamp {
+                    timeline: Timeline::EpochMilliseconds,
+                    chosen_ts: ts.into(),
+                    oracle_ts: Some(ts.into()),
+                },
+                since: Antichain::from_elem(0.into()),
+                upper: Antichain::from_elem((ts + 1).into()),
+                largest_not_in_advance_of_upper: ts.into(),
+                oracle_read_ts: Some(ts.into()),
+                session_oracle_read_ts: None,
+                max_staleness: None,
+            },
+            cluster_id: ClusterId::User(1),
+            requires_linearization: RequireLinearization::Required,
+        }
+    }
+
+    fn chosen_ts(session: &Session<Timestamp>) -> Option<Timestamp> {
+        session
+            .get_transaction_timestamp_determination()
+            .and_then(|determination| determination.timestamp_context.timestamp().copied())
+    }
+
+    #[mz_ore::test]
+    fn test_unpin_transaction_timestamp() {
+        let mut session = Session::<Timestamp>::dummy();
+        session
+            .start_transaction(Utc::now(), None, None)
+            .expect("can start transaction");
+
+        // The first statement pins the transaction's timestamp.
+        session.add_transaction_ops(peeks_at(1)).expect("can peek");
+        assert_eq!(chosen_ts(&session), Some(1.into()));
+
+        // Unpinning releases it...
+        let unpinned = session.take_transaction_timestamp_context();
+        assert_eq!(
+            unpinned.as_ref().and_then(|ctx| ctx.timestamp()),
+            Some(&1.into())
+        );
+        assert_eq!(chosen_ts(&session), None);
+        assert!(session.transaction().is_in_multi_statement_transaction());
+
+        // ...so the next statement in the transaction pins a fresh one.
+        session.add_transaction_ops(peeks_at(2)).expect("can peek");
+        assert_eq!(chosen_ts(&session), Some(2.into()));
+
+        // Clearing the transaction drops the pin too.
+        let _ = session.clear_transaction();
+        assert_eq!(chosen_ts(&session), None);
+    }
+
+    #[mz_ore::test]
+    fn test_reset() {
+        let mut session = Session::<Timestamp>::dummy();
+        session.apply_write(5.into());
+        session
+            .ensure_local_timestamp_oracle()
+            .apply_write(5.into());
+        session.set_prepared_statement(
+            "p".into(),
+            None,
+            String::new(),
+            StatementDesc::new(None),
+            0,
+            0,
+        );
+
+        // None of the state of the previous client survives a reset.
+        session.reset();
+        assert!(session
+            .get_timestamp_oracle(&Timeline::EpochMilliseconds)
+            .is_none());
+        assert_eq!(session.last_write_ts(), None);
+        assert!(session.get_prepared_statement_unverified("p").is_none());
+    }
+
+    /// A timestamp oracle whose every operation takes an hour.
+    struct SlowOracle;
+
+    impl SlowOracle {
+        async fn stall() {
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+        }
+    }
+
+    #[async_trait(?Send)]
+    impl TimestampOracle<Timestamp> for SlowOracle {
+        async fn write_ts(&mut self) -> WriteTimestamp<Timestamp> {
+            Self::stall().await;
+            WriteTimestamp {
+                timestamp: Timestamp::MIN,
+                advance_to: Timestamp::MIN.step_forward(),
+            }
+        }
+
+        async fn peek_write_ts(&self) -> Timestamp {
+            Self::stall().await;
+            Timestamp::MIN
+        }
+
+        async fn read_ts(&self) -> Timestamp {
+            Self::stall().await;
+            Timestamp::MIN
+        }
+
+        async fn apply_write(&mut self, _write_ts: Timestamp) {
+            Self::stall().await;
+        }
+
+        fn get_shared(&self) -> Option<Arc<dyn ShareableTimestampOracle<Timestamp> + Send + Sync>> {
+            None
+        }
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `epoll_wait` on OS `linux`
+    async fn test_cancel_slow_oracle_read() {
+        let session = Session::<Timestamp>::dummy();
+        let cancel_signal = session.cancel_signal().clone();
+
+        // Tokens only observe the cancellations signaled after they were created.
+        cancel_signal.cancel();
+        let token = session.cancel_signal().token();
+        assert_eq!(token.canceled_at(), None);
+
+        let start = Instant::now();
+        mz_ore::task::spawn(|| "cancel", async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            cancel_signal.cancel();
+        });
+        let canceled_at = token
+            .run_until_canceled(SlowOracle.read_ts())
+            .await
+            .expect_err("the read is canceled");
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert_eq!(token.canceled_at(), Some(canceled_at));
+
+        // Later statements aren't canceled.
+        let token = session.cancel_signal().token();
+        assert_eq!(token.canceled_at(), None);
+        assert_eq!(token.run_until_canceled(async { 1 }).await, Ok(1));
+    }
+}
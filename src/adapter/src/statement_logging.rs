@@ -15,7 +15,7 @@ use mz_sql_parser::ast::StatementKind;
 use uuid::Uuid;
 
 use crate::session::TransactionId;
-use crate::{AdapterError, ExecuteResponse};
+use crate::{AdapterError, CollectionIdBundle, ExecuteResponse};
 
 #[derive(Clone, Debug)]
 pub enum StatementLifecycleEvent {
@@ -53,6 +53,8 @@ pub struct StatementBeganExecutionRecord {
     pub execution_timestamp: Option<EpochMillis>,
     pub transaction_id: TransactionId,
     pub transient_index_id: Option<GlobalId>,
+    /// The collections the statement read from, as used to determine its timestamp.
+    pub dependencies: Option<CollectionIdBundle>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -224,6 +226,7 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             | ExecuteResponse::StartedTransaction
             | ExecuteResponse::TransactionCommitted { .. }
             | ExecuteResponse::TransactionRolledBack { .. }
+            | ExecuteResponse::Undropped
             | ExecuteResponse::Updated(_)
             | ExecuteResponse::ValidatedConnection { .. } => {
                 StatementEndedExecutionReason::Success {
@@ -13,6 +13,7 @@ use mz_compute_client::controller::error::{
     CollectionUpdateError, DataflowCreationError, InstanceMissing, PeekError, ReadPolicyError,
     SubscribeTargetError,
 };
+use mz_controller::ControllerError;
 use mz_controller_types::ClusterId;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_ore::{halt, soft_assert_no_log};
@@ -335,12 +336,23 @@ impl ShouldHalt for StorageError {
             | StorageError::Generic(_)
             | StorageError::DataflowError(_)
             | StorageError::InvalidAlter { .. }
-            | StorageError::ShuttingDown(_) => false,
+            | StorageError::ShuttingDown(_)
+            | StorageError::PersistTxnTablesRegression { .. }
+            | StorageError::CommandTooLarge { .. } => false,
             StorageError::IOError(e) => e.is_unrecoverable(),
         }
     }
 }
 
+impl<T> ShouldHalt for ControllerError<T> {
+    fn should_halt(&self) -> bool {
+        match self {
+            ControllerError::Storage(e) => e.should_halt(),
+            ControllerError::ReadFrontierBelowSince { .. } => false,
+        }
+    }
+}
+
 impl ShouldHalt for DataflowCreationError {
     fn should_halt(&self) -> bool {
         match self {
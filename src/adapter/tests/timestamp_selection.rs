@@ -12,15 +12,20 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use futures::executor::block_on;
 use mz_adapter::catalog::CatalogState;
 use mz_adapter::session::Session;
-use mz_adapter::{CollectionIdBundle, TimelineContext, TimestampProvider};
+use mz_adapter::{
+    AdapterNotice, CollectionFreshness, CollectionIdBundle, TimelineContext, TimelineLinks,
+    TimestampDeterminationContext, TimestampProvider,
+};
 use mz_compute_types::ComputeInstanceId;
 use mz_expr::MirScalarExpr;
+use mz_repr::adt::timestamp::CheckedTimestamp;
 use mz_repr::{Datum, GlobalId, ScalarType, Timestamp};
 use mz_sql::plan::QueryWhen;
-use mz_sql::session::vars::IsolationLevel;
+use mz_sql::session::vars::{IsolationLevel, SystemVars, VarInput};
 use mz_sql_parser::ast::TransactionIsolationLevel;
 use mz_storage_types::sources::Timeline;
 use serde::{Deserialize, Serialize};
@@ -36,6 +41,9 @@ struct Set {
 struct SetFrontier {
     read: Timestamp,
     write: Timestamp,
+    /// The read frontier when the collection was created, if known.
+    #[serde(default)]
+    initial: Option<Timestamp>,
 }
 
 impl Set {
@@ -63,11 +71,13 @@ struct Frontiers {
     compute: BTreeMap<(ComputeInstanceId, GlobalId), Frontier>,
     storage: BTreeMap<GlobalId, Frontier>,
     oracle: Timestamp,
+    append_only: BTreeSet<GlobalId>,
 }
 
 struct Frontier {
     read: Antichain<Timestamp>,
     write: Antichain<Timestamp>,
+    initial: Option<Antichain<Timestamp>>,
 }
 
 impl From<SetFrontier> for Frontier {
@@ -75,6 +85,7 @@ impl From<SetFrontier> for Frontier {
         Frontier {
             read: Antichain::from_elem(s.read),
             write: Antichain::from_elem(s.write),
+            initial: s.initial.map(Antichain::from_elem),
         }
     }
 }
@@ -125,6 +136,29 @@ impl TimestampProvider for Frontiers {
     ) -> &'a timely::progress::Antichain<Timestamp> {
         &self.storage.get(&id).unwrap().write
     }
+
+    fn compute_initial_read_capability<'a>(
+        &'a self,
+        instance: ComputeInstanceId,
+        id: GlobalId,
+    ) -> Option<&'a timely::progress::Antichain<Timestamp>> {
+        self.compute.get(&(instance, id)).unwrap().initial.as_ref()
+    }
+
+    fn storage_initial_implied_capability<'a>(
+        &'a self,
+        id: GlobalId,
+    ) -> Option<&'a timely::progress::Antichain<Timestamp>> {
+        self.storage.get(&id).unwrap().initial.as_ref()
+    }
+
+    fn collection_freshness(&self, id: GlobalId) -> CollectionFreshness {
+        if self.append_only.contains(&id) {
+            CollectionFreshness::AppendOnly
+        } else {
+            CollectionFreshness::Linearized
+        }
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -132,6 +166,9 @@ struct Determine {
     id_bundle: IdBundle,
     when: String,
     instance: String,
+    /// The timelines of the collections in `id_bundle`, if they belong to any.
+    #[serde(default)]
+    timelines: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
@@ -179,10 +216,18 @@ fn parse_query_when(s: &str) -> QueryWhen {
 /// This works by mocking out the compute and storage controllers and timestamp oracle. Then we can
 /// call determine_timestamp for specified sources and QueryWhens. The testdrive language supports
 /// various set directives that can be used to set the state of the fake controllers or timestamp
-/// oracle. The tuple of two timestamps for those specifies the `(read frontier, write frontier)`.
-/// Transaction isolation can also be set. The `determine` directive runs determine_timestamp and
-/// returns the chosen timestamp. Append `full` as an argument to it to see the entire
-/// TimestampDetermination.
+/// oracle. The tuple of two timestamps for those specifies the `(read frontier, write frontier)`,
+/// optionally followed by an `initial` read frontier the collection had when it was created.
+/// Transaction isolation, the session's `max_staleness` and `read_your_writes`, the timestamp of
+/// the session's last write, the transaction's wall time (in milliseconds), and a real time
+/// recency timestamp together with the session's `real_time_recency_timeout` can also be set, as
+/// can links between timelines, in the format of `linked_timelines`, and the collections that are
+/// append-only.
+/// The `determine` directive runs determine_timestamp for the collections of the given timelines,
+/// or timestamp dependent collections if none are given, and returns
+/// the chosen timestamp, or the error if no timestamp can be chosen or the chosen timestamp
+/// violates `max_staleness`. Append
+/// `full` as an argument to it to see the entire TimestampDetermination.
 // TODO(aljoscha): We allow `futures::block_on` for testing because
 // `determine_timestamp_for()` is now async. We will remove async here again
 // once we have sufficiently evolved the TimestampOracle API and are done with
@@ -196,9 +241,17 @@ fn test_timestamp_selection() {
             compute: BTreeMap::new(),
             storage: BTreeMap::new(),
             oracle: Timestamp::MIN,
+            append_only: BTreeSet::new(),
         };
         let catalog = CatalogState::empty();
         let mut isolation = TransactionIsolationLevel::StrictSerializable;
+        let mut max_staleness = String::new();
+        let mut wall_time = 0;
+        let mut read_your_writes = "off".to_string();
+        let mut session_write: Option<Timestamp> = None;
+        let mut real_time_recency_ts: Option<Timestamp> = None;
+        let mut real_time_recency_timeout = String::new();
+        let mut timeline_links = TimelineLinks::default();
         tf.run(move |tc| -> String {
             match tc.directive.as_str() {
                 "set-compute" => {
@@ -232,17 +285,100 @@ fn test_timestamp_selection() {
                         };
                     "".into()
                 }
+                "set-max-staleness" => {
+                    max_staleness = tc.input.trim().to_string();
+                    "".into()
+                }
+                "set-wall-time" => {
+                    wall_time = serde_json::from_str(&tc.input).unwrap();
+                    "".into()
+                }
+                "set-read-your-writes" => {
+                    read_your_writes = tc.input.trim().to_string();
+                    "".into()
+                }
+                "set-session-write" => {
+                    session_write = serde_json::from_str(&tc.input).unwrap();
+                    "".into()
+                }
+                "set-real-time-recency" => {
+                    real_time_recency_ts = serde_json::from_str(&tc.input).unwrap();
+                    "".into()
+                }
+                "set-real-time-recency-timeout" => {
+                    real_time_recency_timeout = tc.input.trim().to_string();
+                    "".into()
+                }
+                "set-append-only" => {
+                    let ids: Vec<String> = serde_json::from_str(&tc.input).unwrap();
+                    f.append_only = ids.iter().map(|id| id.parse().unwrap()).collect();
+                    "".into()
+                }
+                "set-timeline-links" => {
+                    timeline_links = tc.input.trim().parse().unwrap();
+                    "".into()
+                }
                 "determine" => {
                     let det: Determine = serde_json::from_str(&tc.input).unwrap();
                     let mut session = Session::dummy();
+                    session
+                        .vars_mut()
+                        .set(None, "max_staleness", VarInput::Flat(&max_staleness), false)
+                        .unwrap();
+                    session
+                        .vars_mut()
+                        .set(
+                            None,
+                            "read_your_writes",
+                            VarInput::Flat(&read_your_writes),
+                            false,
+                        )
+                        .unwrap();
+                    session
+                        .vars_mut()
+                        .set(
+                            None,
+                            "real_time_recency_timeout",
+                            VarInput::Flat(&real_time_recency_timeout),
+                            false,
+                        )
+                        .unwrap();
+                    if real_time_recency_ts.is_some() {
+                        let mut system_vars = SystemVars::default();
+                        system_vars
+                            .set("allow_real_time_recency", VarInput::Flat("on"))
+                            .unwrap();
+                        session
+                            .vars_mut()
+                            .set(
+                                Some(&system_vars),
+                                "real_time_recency",
+                                VarInput::Flat("on"),
+                                false,
+                            )
+                            .unwrap();
+                    }
+                    if let Some(ts) = session_write {
+                        session.apply_write(ts);
+                    }
                     let _ = session.start_transaction(
-                        mz_ore::now::to_datetime(0),
+                        mz_ore::now::to_datetime(wall_time),
                         None,
                         Some(isolation),
                     );
 
                     // TODO: Factor out into method, or somesuch!
-                    let timeline_ctx = TimelineContext::TimestampDependent;
+                    let timeline_ctx = if det.timelines.is_empty() {
+                        TimelineContext::TimestampDependent
+                    } else {
+                        let timeline_contexts = det.timelines.iter().map(|timeline| {
+                            TimelineContext::TimelineDependent(timeline.parse().unwrap())
+                        });
+                        match TimelineContext::merge(timeline_contexts, &timeline_links) {
+                            Ok(timeline_ctx) => timeline_ctx,
+                            Err(e) => return format!("error: {e}\n"),
+                        }
+                    };
                     let isolation_level = IsolationLevel::from(isolation);
                     let when = parse_query_when(&det.when);
                     let linearized_timeline =
@@ -262,18 +398,24 @@ fn test_timestamp_selection() {
                         None
                     };
 
-                    let ts = block_on(f.determine_timestamp_for(
+                    let ts = match block_on(f.determine_timestamp_for(
                         &catalog,
-                        &session,
+                        &TimestampDeterminationContext::from(&session),
                         &det.id_bundle.into(),
                         &parse_query_when(&det.when),
                         det.instance.parse().unwrap(),
-                        &TimelineContext::TimestampDependent,
+                        &timeline_ctx,
                         oracle_read_ts,
-                        None, /* real_time_recency_ts */
+                        real_time_recency_ts,
                         &IsolationLevel::from(isolation),
-                    ))
-                    .unwrap();
+                    )) {
+                        Ok(ts) => ts,
+                        Err(e) => return format!("error: {e}\n"),
+                    };
+
+                    if let Err(e) = ts.check_max_staleness(&catalog, &session) {
+                        return format!("error: {e}\n");
+                    }
 
                     if tc.args.contains_key("full") {
                         format!("{}\n", serde_json::to_string_pretty(&ts).unwrap())
@@ -286,3 +428,133 @@ fn test_timestamp_selection() {
         })
     })
 }
+
+/// Tests determine_timestamp with a context that is not built from a session, as is done for
+/// hypothetical sessions.
+#[allow(clippy::disallowed_methods)]
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `decNumberFromInt32` on OS `linux`
+fn test_determine_timestamp_without_session() {
+    let f = Frontiers {
+        compute: BTreeMap::new(),
+        storage: BTreeMap::from([(
+            GlobalId::User(1),
+            Frontier {
+                read: Antichain::from_elem(Timestamp::from(10u64)),
+                write: Antichain::from_elem(Timestamp::from(20u64)),
+                initial: None,
+            },
+        )]),
+        oracle: Timestamp::MIN,
+        append_only: BTreeSet::new(),
+    };
+    let catalog = CatalogState::empty();
+    let id_bundle = CollectionIdBundle {
+        storage_ids: BTreeSet::from([GlobalId::User(1)]),
+        compute_ids: BTreeMap::new(),
+    };
+    let timeline_ctx = TimelineContext::TimelineDependent(Timeline::EpochMilliseconds);
+    let determine = |ctx: &TimestampDeterminationContext<'_>,
+                     when: QueryWhen,
+                     isolation_level: IsolationLevel,
+                     oracle_read_ts: Option<u64>| {
+        block_on(f.determine_timestamp_for(
+            &catalog,
+            ctx,
+            &id_bundle,
+            &when,
+            "u1".parse().unwrap(),
+            &timeline_ctx,
+            oracle_read_ts.map(Timestamp::from),
+            None,
+            &isolation_level,
+        ))
+        .expect("valid determination")
+    };
+
+    // Only the timestamp oracles of the session are borrowed from it, and only by the
+    // determinations under Strong Session Serializable.
+    let mut session = Session::dummy();
+    session
+        .vars_mut()
+        .set(
+            None,
+            "transaction_isolation",
+            VarInput::Flat(IsolationLevel::StrongSessionSerializable.as_str()),
+            false,
+        )
+        .unwrap();
+    session.apply_write(Timestamp::from(15u64));
+    let (notices_tx, mut notices_rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut ctx = TimestampDeterminationContext {
+        conn_id: None,
+        wall_time: Some(mz_ore::now::to_datetime(1_000)),
+        real_time_recency: false,
+        real_time_recency_timeout: None,
+        session_oracles: Some(session.timestamp_oracles()),
+        read_your_writes: false,
+        last_write_ts: None,
+        max_staleness: None,
+        notices: Some(notices_tx),
+    };
+
+    // Serializable reads are served at the upper.
+    let det = determine(
+        &ctx,
+        QueryWhen::Immediately,
+        IsolationLevel::Serializable,
+        None,
+    );
+    assert_eq!(
+        det.timestamp_context.timestamp(),
+        Some(&Timestamp::from(19u64))
+    );
+
+    // Strong Session Serializable reads are bounded by the session's oracle.
+    let det = determine(
+        &ctx,
+        QueryWhen::Immediately,
+        IsolationLevel::StrongSessionSerializable,
+        Some(12),
+    );
+    assert_eq!(
+        det.timestamp_context.timestamp(),
+        Some(&Timestamp::from(15u64))
+    );
+    assert_eq!(det.session_oracle_read_ts, Some(Timestamp::from(15u64)));
+
+    // Notices about the `AS OF` are sent to the context.
+    let dt = NaiveDateTime::from_timestamp_micros(12_500).expect("valid timestamp");
+    let as_of = MirScalarExpr::literal_ok(
+        Datum::Timestamp(CheckedTimestamp::from_timestamplike(dt).unwrap()),
+        ScalarType::Timestamp { precision: None },
+    );
+    let det = determine(
+        &ctx,
+        QueryWhen::AtTimestamp(as_of),
+        IsolationLevel::Serializable,
+        None,
+    );
+    assert_eq!(
+        det.timestamp_context.timestamp(),
+        Some(&Timestamp::from(13u64))
+    );
+    assert!(matches!(
+        notices_rx.try_recv(),
+        Ok(AdapterNotice::TimestampRoundedUp { .. })
+    ));
+
+    // Reads observe the writes the context reports.
+    ctx.read_your_writes = true;
+    ctx.last_write_ts = Some(Timestamp::from(25u64));
+    let det = determine(
+        &ctx,
+        QueryWhen::Immediately,
+        IsolationLevel::Serializable,
+        None,
+    );
+    assert_eq!(
+        det.timestamp_context.timestamp(),
+        Some(&Timestamp::from(25u64))
+    );
+}
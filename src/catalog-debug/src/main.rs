@@ -28,9 +28,9 @@ use mz_catalog::durable::debug::{
     AuditLogCollection, ClusterCollection, ClusterIntrospectionSourceIndexCollection,
     ClusterReplicaCollection, Collection, CollectionTrace, CollectionType, CommentCollection,
     ConfigCollection, DatabaseCollection, DebugCatalogState, DefaultPrivilegeCollection,
-    IdAllocatorCollection, ItemCollection, RoleCollection, SchemaCollection, SettingCollection,
-    StorageUsageCollection, SystemConfigurationCollection, SystemItemMappingCollection,
-    SystemPrivilegeCollection, TimestampCollection, Trace,
+    DroppedItemCollection, IdAllocatorCollection, ItemCollection, RoleCollection, SchemaCollection,
+    SettingCollection, StorageUsageCollection, SystemConfigurationCollection,
+    SystemItemMappingCollection, SystemPrivilegeCollection, TimestampCollection, Trace,
 };
 use mz_catalog::durable::{
     persist_backed_catalog_state, stash_backed_catalog_state, BootstrapArgs,
@@ -277,6 +277,7 @@ macro_rules! for_collection {
             CollectionType::Config => $fn::<ConfigCollection>($($arg),*).await?,
             CollectionType::Database => $fn::<DatabaseCollection>($($arg),*).await?,
             CollectionType::DefaultPrivileges => $fn::<DefaultPrivilegeCollection>($($arg),*).await?,
+            CollectionType::DroppedItem => $fn::<DroppedItemCollection>($($arg),*).await?,
             CollectionType::IdAlloc => $fn::<IdAllocatorCollection>($($arg),*).await?,
             CollectionType::Item => $fn::<ItemCollection>($($arg),*).await?,
             CollectionType::Role => $fn::<RoleCollection>($($arg),*).await?,
@@ -390,6 +391,7 @@ async fn dump(
         configs,
         databases,
         default_privileges,
+        dropped_items,
         id_allocator,
         items,
         roles,
@@ -412,6 +414,7 @@ async fn dump(
     dump_col(&mut data, configs, &ignore);
     dump_col(&mut data, databases, &ignore);
     dump_col(&mut data, default_privileges, &ignore);
+    dump_col(&mut data, dropped_items, &ignore);
     dump_col(&mut data, id_allocator, &ignore);
     dump_col(&mut data, items, &ignore);
     dump_col(&mut data, roles, &ignore);
@@ -47,9 +47,9 @@ use mz_sql::session::user::{
 };
 use mz_storage_client::controller::IntrospectionType;
 use mz_storage_client::healthcheck::{
-    MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY_DESC, MZ_PREPARED_STATEMENT_HISTORY_DESC,
-    MZ_SESSION_HISTORY_DESC, MZ_SINK_STATUS_HISTORY_DESC, MZ_SOURCE_STATUS_HISTORY_DESC,
-    MZ_STATEMENT_EXECUTION_HISTORY_DESC,
+    MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY_DESC, MZ_FRONTIERS_HISTORY_DESC,
+    MZ_PREPARED_STATEMENT_HISTORY_DESC, MZ_SESSION_HISTORY_DESC, MZ_SINK_STATUS_HISTORY_DESC,
+    MZ_SOURCE_STATUS_HISTORY_DESC, MZ_STATEMENT_EXECUTION_HISTORY_DESC,
 };
 use once_cell::sync::Lazy;
 use serde::Serialize;
@@ -2532,7 +2532,7 @@ pub static MZ_STATEMENT_EXECUTION_HISTORY_REDACTED: Lazy<BuiltinView> = Lazy::ne
     sql: "
 SELECT id, prepared_statement_id, sample_rate, cluster_id, application_name,
 cluster_name, transaction_isolation, execution_timestamp, transaction_id,
-transient_index_id, began_at, finished_at, finished_status,
+transient_index_id, dependency_ids, began_at, finished_at, finished_status,
 error_message, rows_returned, execution_strategy
 FROM mz_internal.mz_statement_execution_history",
     access: vec![SUPPORT_SELECT, MONITOR_REDACTED_SELECT, MONITOR_SELECT],
@@ -2574,7 +2574,7 @@ pub static MZ_ACTIVITY_LOG: Lazy<BuiltinView> = Lazy::new(|| {
     column_defs: None,
     sql: "
 SELECT mseh.id AS execution_id, sample_rate, cluster_id, application_name, cluster_name,
-transaction_isolation, execution_timestamp, transient_index_id, params, began_at, finished_at, finished_status,
+transaction_isolation, execution_timestamp, transient_index_id, dependency_ids, params, began_at, finished_at, finished_status,
 error_message, rows_returned, execution_strategy, transaction_id,
 mpsh.id AS prepared_statement_id, sql, mpsh.name AS prepared_statement_name,
 session_id, redacted_sql, prepared_at, statement_type, throttled_count
@@ -2591,7 +2591,7 @@ pub static MZ_ACTIVITY_LOG_REDACTED: Lazy<BuiltinView> = Lazy::new(|| {
     column_defs: None,
     sql: "
 SELECT execution_id, sample_rate, cluster_id, application_name, cluster_name,
-transaction_isolation, execution_timestamp, transient_index_id, began_at, finished_at, finished_status,
+transaction_isolation, execution_timestamp, transient_index_id, dependency_ids, began_at, finished_at, finished_status,
 error_message, rows_returned, execution_strategy, transaction_id, prepared_statement_id,
 prepared_statement_name, session_id, redacted_sql, prepared_at, statement_type, throttled_count
 FROM mz_internal.mz_activity_log",
@@ -2615,7 +2615,7 @@ pub static MZ_RECENT_ACTIVITY_LOG_REDACTED: Lazy<BuiltinView> = Lazy::new(|| {
         schema: MZ_INTERNAL_SCHEMA,
         column_defs: None,
         sql: "SELECT execution_id, sample_rate, cluster_id, application_name, cluster_name,
-transaction_isolation, execution_timestamp, transient_index_id, began_at, finished_at, finished_status,
+transaction_isolation, execution_timestamp, transient_index_id, dependency_ids, began_at, finished_at, finished_status,
 error_message, rows_returned, execution_strategy, transaction_id, prepared_statement_id,
 prepared_statement_name, session_id, redacted_sql, prepared_at, statement_type
 FROM mz_internal.mz_recent_activity_log",
@@ -2623,6 +2623,44 @@ FROM mz_internal.mz_recent_activity_log",
     }
 });
 
+pub static MZ_STATEMENT_EXECUTION_DEPENDENCIES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_statement_execution_dependencies",
+    schema: MZ_INTERNAL_SCHEMA,
+    column_defs: None,
+    // Keep the parsing of `dependency_ids` in sync with `Coordinator::pack_dependency_ids`.
+    sql: "
+WITH dependencies AS (
+    SELECT mseh.id AS execution_id, dependency
+    FROM
+        mz_internal.mz_statement_execution_history AS mseh,
+        unnest(mseh.dependency_ids) AS dependency
+),
+parsed AS (
+    SELECT
+        execution_id,
+        CASE
+            WHEN dependency LIKE '%:%' THEN split_part(dependency, ':', 2)
+            ELSE dependency
+        END AS object_id,
+        CASE
+            WHEN dependency LIKE '%:%' THEN split_part(dependency, ':', 1)
+        END AS cluster_id
+    FROM dependencies
+)
+SELECT
+    parsed.execution_id,
+    parsed.object_id,
+    objects.name AS object_name,
+    objects.type AS object_type,
+    parsed.cluster_id,
+    clusters.name AS cluster_name
+FROM
+    parsed
+        LEFT JOIN mz_catalog.mz_objects AS objects ON parsed.object_id = objects.id
+        LEFT JOIN mz_catalog.mz_clusters AS clusters ON parsed.cluster_id = clusters.id",
+    access: vec![SUPPORT_SELECT, MONITOR_REDACTED_SELECT, MONITOR_SELECT],
+});
+
 pub static MZ_STATEMENT_LIFECYCLE_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
     name: "mz_statement_lifecycle_history",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2641,19 +2679,70 @@ pub static MZ_STATEMENT_LIFECYCLE_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| Bu
     access: vec![SUPPORT_SELECT, MONITOR_REDACTED_SELECT, MONITOR_SELECT],
 });
 
+/// Expands to a SQL expression that ranks how unhealthy an object whose status
+/// is in column `$status` is, with higher values being worse. Objects that have
+/// not yet reported a status rank alongside starting objects.
+///
+/// Keep in sync with `Status::severity`.
+macro_rules! status_severity {
+    ($status:literal) => {
+        concat!(
+            "CASE ",
+            $status,
+            "
+                WHEN 'running' THEN 0
+                WHEN 'created' THEN 1
+                WHEN 'starting' THEN 1
+                WHEN 'paused' THEN 2
+                WHEN 'stalled' THEN 3
+                WHEN 'ceased' THEN 4
+                WHEN 'dropped' THEN 5
+            END"
+        )
+    };
+}
+
 pub static MZ_SOURCE_STATUSES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_source_statuses",
     schema: MZ_INTERNAL_SCHEMA,
     column_defs: None,
-    sql: "
+    sql: concat!(
+        "
     WITH
-    -- Get the latest events
+    -- Get the latest events of each replica, and of the controller, whose
+    -- events have no replica
+    latest_replica_events AS
+    (
+        SELECT DISTINCT ON (source_id, replica_id)
+            occurred_at, source_id, replica_id, status, error, details,
+            ",
+        status_severity!("status"),
+        " AS severity
+        FROM mz_internal.mz_source_status_history
+        ORDER BY source_id, replica_id, occurred_at DESC
+    ),
+    -- A source is only degraded if its healthiest live replica is. Controller
+    -- events apply to all replicas, so they supersede earlier replica events.
     latest_events AS
     (
         SELECT DISTINCT ON (source_id)
             occurred_at, source_id, status, error, details
-        FROM mz_internal.mz_source_status_history
-        ORDER BY source_id, occurred_at DESC
+        FROM
+        (
+            SELECT e.*, 0 AS rank
+            FROM
+                latest_replica_events AS e
+                    JOIN mz_cluster_replicas AS r ON r.id = e.replica_id
+                    LEFT JOIN
+                        latest_replica_events AS c
+                        ON c.source_id = e.source_id AND c.replica_id IS NULL
+            WHERE c.occurred_at IS NULL OR e.occurred_at > c.occurred_at
+            UNION ALL
+            SELECT e.*, 1 AS rank
+            FROM latest_replica_events AS e
+            WHERE e.replica_id IS NULL
+        ) AS candidates
+        ORDER BY source_id, rank, severity, occurred_at DESC
     ),
     -- Determine which sources are subsources and which are parent sources
     subsources AS
@@ -2712,7 +2801,101 @@ SELECT
 FROM
     mz_sources
         LEFT JOIN latest_events_to_use AS e ON mz_sources.id = e.source_id
-WHERE mz_sources.id NOT LIKE 's%';",
+WHERE mz_sources.id NOT LIKE 's%';"
+    ),
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_SOURCE_FAMILY_STATUSES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_source_family_statuses",
+    schema: MZ_INTERNAL_SCHEMA,
+    column_defs: None,
+    sql: concat!(
+        "
+WITH
+    -- Map each subsource to the source that ingests it. Progress subsources
+    -- always report as running, so they don't contribute to the rollup.
+    subsources AS
+    (
+        SELECT subsources.id, sources.id AS family_id
+        FROM
+            mz_sources AS subsources
+                JOIN
+                    mz_internal.mz_object_dependencies AS deps
+                    ON subsources.id = deps.referenced_object_id
+                JOIN mz_sources AS sources ON sources.id = deps.object_id
+        WHERE subsources.type <> 'progress'
+    ),
+    members AS
+    (
+        SELECT id, id AS family_id
+        FROM mz_sources
+        WHERE type <> 'progress' AND id NOT IN (SELECT id FROM subsources)
+        UNION ALL
+        SELECT id, family_id FROM subsources
+    ),
+    member_statuses AS
+    (
+        SELECT
+            members.family_id,
+            s.id,
+            s.status,
+            s.error,
+            s.last_status_change_at,
+            ",
+        status_severity!("s.status"),
+        " AS severity
+        FROM members JOIN mz_internal.mz_source_statuses AS s ON s.id = members.id
+    ),
+    worst_statuses AS
+    (
+        SELECT DISTINCT ON (family_id) family_id, status
+        FROM member_statuses
+        ORDER BY family_id, severity DESC, status
+    ),
+    status_counts AS
+    (
+        SELECT
+            family_id,
+            count(*) AS member_count,
+            count(*) FILTER (WHERE status = 'created') AS created_count,
+            count(*) FILTER (WHERE status = 'starting') AS starting_count,
+            count(*) FILTER (WHERE status = 'running') AS running_count,
+            count(*) FILTER (WHERE status = 'paused') AS paused_count,
+            count(*) FILTER (WHERE status = 'stalled') AS stalled_count,
+            count(*) FILTER (WHERE status = 'ceased') AS ceased_count
+        FROM member_statuses
+        GROUP BY family_id
+    ),
+    latest_errors AS
+    (
+        SELECT DISTINCT ON (family_id)
+            family_id, id AS error_id, error, last_status_change_at AS error_at
+        FROM member_statuses
+        WHERE status IN ('stalled', 'ceased') AND error IS NOT NULL
+        ORDER BY family_id, last_status_change_at DESC, id
+    )
+SELECT
+    mz_sources.id,
+    mz_sources.name,
+    mz_sources.type,
+    w.status,
+    c.member_count,
+    c.created_count,
+    c.starting_count,
+    c.running_count,
+    c.paused_count,
+    c.stalled_count,
+    c.ceased_count,
+    e.error_id,
+    e.error,
+    e.error_at
+FROM
+    mz_sources
+        JOIN worst_statuses AS w ON mz_sources.id = w.family_id
+        JOIN status_counts AS c ON mz_sources.id = c.family_id
+        LEFT JOIN latest_errors AS e ON mz_sources.id = e.family_id"
+    ),
     access: vec![PUBLIC_SELECT],
 });
 
@@ -2729,11 +2912,37 @@ pub static MZ_SINK_STATUSES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_sink_statuses",
     schema: MZ_INTERNAL_SCHEMA,
     column_defs: None,
-    sql: "
-WITH latest_events AS (
-    SELECT DISTINCT ON(sink_id) occurred_at, sink_id, status, error, details
+    sql: concat!(
+        "
+WITH
+-- Get the latest events of each replica, and of the controller, whose events
+-- have no replica
+latest_replica_events AS (
+    SELECT DISTINCT ON (sink_id, replica_id)
+        occurred_at, sink_id, replica_id, status, error, details,
+        ",
+        status_severity!("status"),
+        " AS severity
     FROM mz_internal.mz_sink_status_history
-    ORDER BY sink_id, occurred_at DESC
+    ORDER BY sink_id, replica_id, occurred_at DESC
+),
+-- A sink is only degraded if its healthiest live replica is. Controller events
+-- apply to all replicas, so they supersede earlier replica events.
+latest_events AS (
+    SELECT DISTINCT ON (sink_id) occurred_at, sink_id, status, error, details
+    FROM (
+        SELECT e.*, 0 AS rank
+        FROM latest_replica_events AS e
+        JOIN mz_cluster_replicas AS r ON r.id = e.replica_id
+        LEFT JOIN latest_replica_events AS c
+            ON c.sink_id = e.sink_id AND c.replica_id IS NULL
+        WHERE c.occurred_at IS NULL OR e.occurred_at > c.occurred_at
+        UNION ALL
+        SELECT e.*, 1 AS rank
+        FROM latest_replica_events AS e
+        WHERE e.replica_id IS NULL
+    ) AS candidates
+    ORDER BY sink_id, rank, severity, occurred_at DESC
 )
 SELECT
     mz_sinks.id,
@@ -2747,7 +2956,8 @@ FROM mz_sinks
 LEFT JOIN latest_events ON mz_sinks.id = latest_events.sink_id
 WHERE
     -- This is a convenient way to filter out system sinks, like the status_history table itself.
-    mz_sinks.id NOT LIKE 's%'",
+    mz_sinks.id NOT LIKE 's%'"
+    ),
     access: vec![PUBLIC_SELECT],
 });
 
@@ -2821,7 +3031,8 @@ pub static MZ_CLUSTER_REPLICA_METRICS: Lazy<BuiltinTable> = Lazy::new(|| Builtin
         .with_column("process_id", ScalarType::UInt64.nullable(false))
         .with_column("cpu_nano_cores", ScalarType::UInt64.nullable(true))
         .with_column("memory_bytes", ScalarType::UInt64.nullable(true))
-        .with_column("disk_bytes", ScalarType::UInt64.nullable(true)),
+        .with_column("disk_bytes", ScalarType::UInt64.nullable(true))
+        .with_column("disk_capacity_bytes", ScalarType::UInt64.nullable(true)),
     is_retained_metrics_object: true,
     access: vec![PUBLIC_SELECT],
 });
@@ -2850,6 +3061,136 @@ pub static MZ_FRONTIERS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_WALLCLOCK_LAG: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_wallclock_lag",
+    schema: MZ_INTERNAL_SCHEMA,
+    data_source: IntrospectionType::WallclockLag,
+    desc: RelationDesc::empty()
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("lag", ScalarType::Interval.nullable(false)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_FRONTIER_ADVANCEMENT_RATES: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_frontier_advancement_rates",
+    schema: MZ_INTERNAL_SCHEMA,
+    data_source: IntrospectionType::FrontierAdvancementRates,
+    desc: RelationDesc::empty()
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("rate", ScalarType::Float64.nullable(false))
+        .with_column(
+            "measured_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_CLUSTER_REPLICA_STATUS_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_cluster_replica_status_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    data_source: IntrospectionType::ReplicaStatusHistory,
+    desc: RelationDesc::empty()
+        .with_column("replica_id", ScalarType::String.nullable(false))
+        .with_column("process_id", ScalarType::UInt64.nullable(false))
+        .with_column("status", ScalarType::String.nullable(false))
+        .with_column("reason", ScalarType::String.nullable(true))
+        .with_column(
+            "occurred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_SOURCE_UPSTREAM_LAG: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_source_upstream_lag",
+    schema: MZ_INTERNAL_SCHEMA,
+    data_source: IntrospectionType::SourceUpstreamLag,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::String.nullable(false))
+        .with_column("bytes_behind", ScalarType::UInt64.nullable(false)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_SOURCE_PARAMETER_GENERATIONS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_source_parameter_generations",
+    schema: MZ_INTERNAL_SCHEMA,
+    data_source: IntrospectionType::SourceParameterGenerations,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::String.nullable(false))
+        .with_column("generation", ScalarType::UInt64.nullable(false)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_FRONTIER_DIVERGENCE_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_frontier_divergence_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    data_source: IntrospectionType::FrontierDivergenceHistory,
+    desc: RelationDesc::empty()
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column(
+            "compute_write_frontier",
+            ScalarType::MzTimestamp.nullable(false),
+        )
+        .with_column(
+            "storage_write_frontier",
+            ScalarType::MzTimestamp.nullable(false),
+        )
+        .with_column(
+            "occurred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_TIMESTAMP_ORACLE_STATUS: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_timestamp_oracle_status",
+    schema: MZ_INTERNAL_SCHEMA,
+    data_source: IntrospectionType::TimestampOracleStatus,
+    desc: RelationDesc::empty()
+        .with_column("state", ScalarType::String.nullable(false))
+        .with_column(
+            "changed_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        ),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_FRONTIERS_HISTORY: Lazy<BuiltinSource> = Lazy::new(|| BuiltinSource {
+    name: "mz_frontiers_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    data_source: IntrospectionType::FrontiersHistory,
+    desc: MZ_FRONTIERS_HISTORY_DESC.clone(),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_OBJECT_FRONTIERS_HISTORY: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_object_frontiers_history",
+    schema: MZ_INTERNAL_SCHEMA,
+    column_defs: None,
+    sql: "
+SELECT
+    object_id,
+    resolution,
+    occurred_at AS bucket_start,
+    CASE resolution
+        WHEN 'minute' THEN occurred_at + INTERVAL '1 minute'
+        WHEN 'hour' THEN occurred_at + INTERVAL '1 hour'
+        ELSE occurred_at
+    END AS bucket_end,
+    min_write_frontier,
+    max_write_frontier
+FROM mz_internal.mz_frontiers_history",
+    access: vec![PUBLIC_SELECT],
+});
+
 /// DEPRECATED and scheduled for removal! Use `mz_frontiers` instead.
 pub static MZ_GLOBAL_FRONTIERS: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_global_frontiers",
@@ -2899,6 +3240,31 @@ pub static MZ_SESSIONS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_PORTALS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_portals",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("session_id", ScalarType::UInt32.nullable(false))
+        .with_column("name", ScalarType::String.nullable(false))
+        .with_column(
+            "created_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column("buffered_bytes", ScalarType::UInt64.nullable(false)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
+pub static MZ_PERSIST_TXN_TABLES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_persist_txn_tables",
+    schema: MZ_INTERNAL_SCHEMA,
+    desc: RelationDesc::empty()
+        .with_column("mode", ScalarType::String.nullable(false))
+        .with_column("previous_mode", ScalarType::String.nullable(true)),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_DEFAULT_PRIVILEGES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_default_privileges",
     schema: MZ_CATALOG_SCHEMA,
@@ -2960,7 +3326,9 @@ pub static MZ_SOURCE_STATISTICS_PER_WORKER: Lazy<BuiltinSource> = Lazy::new(|| B
         .with_column("updates_committed", ScalarType::UInt64.nullable(false))
         .with_column("envelope_state_bytes", ScalarType::UInt64.nullable(false))
         .with_column("envelope_state_records", ScalarType::UInt64.nullable(false))
-        .with_column("rehydration_latency", ScalarType::Interval.nullable(true)),
+        .with_column("rehydration_latency", ScalarType::Interval.nullable(true))
+        .with_column("snapshot_records_known", ScalarType::UInt64.nullable(true))
+        .with_column("snapshot_records_staged", ScalarType::UInt64.nullable(true)),
     is_retained_metrics_object: true,
     access: vec![PUBLIC_SELECT],
 });
@@ -5982,6 +6350,36 @@ JOIN root_times r USING (id)",
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_SINK_PROGRESS: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_sink_progress",
+    schema: MZ_INTERNAL_SCHEMA,
+    column_defs: None,
+    sql: "
+SELECT
+    s.id AS sink_id,
+    d.referenced_object_id AS input_id,
+    sf.write_frontier AS emitted_frontier,
+    f.write_frontier AS input_frontier,
+    -- Sinks that have reached the empty frontier have emitted all their input data, and lag is
+    -- never negative, even when the input frontier is before the sink frontier (as can happen
+    -- while the sink is starting up).
+    CASE
+        WHEN sf.write_frontier IS NULL THEN INTERVAL '0'
+        WHEN f.write_frontier IS NULL THEN NULL
+        ELSE greatest(
+            to_timestamp(f.write_frontier::text::double / 1000) -
+                to_timestamp(sf.write_frontier::text::double / 1000),
+            INTERVAL '0'
+        )
+    END AS lag
+FROM mz_sinks s
+JOIN mz_internal.mz_object_dependencies d ON (s.id = d.object_id)
+JOIN mz_internal.mz_frontiers sf ON (s.id = sf.object_id)
+-- Sinks also depend on their connections, which have no frontiers.
+JOIN mz_internal.mz_frontiers f ON (d.referenced_object_id = f.object_id)",
+    access: vec![PUBLIC_SELECT],
+});
+
 pub const MZ_SHOW_DATABASES_IND: BuiltinIndex = BuiltinIndex {
     name: "mz_show_databases_ind",
     schema: MZ_INTERNAL_SCHEMA,
@@ -6204,7 +6602,16 @@ SELECT
     CASE
         WHEN bool_or(rehydration_latency IS NULL) THEN NULL
         ELSE MAX(rehydration_latency)::interval
-    END AS rehydration_latency
+    END AS rehydration_latency,
+    -- Ensure we aggregate to NULL when not all workers know their share of the snapshot.
+    CASE
+        WHEN bool_or(snapshot_records_known IS NULL) THEN NULL
+        ELSE SUM(snapshot_records_known)::uint8
+    END AS snapshot_records_known,
+    CASE
+        WHEN bool_or(snapshot_records_staged IS NULL) THEN NULL
+        ELSE SUM(snapshot_records_staged)::uint8
+    END AS snapshot_records_staged
 FROM mz_internal.mz_source_statistics_per_worker
 GROUP BY id",
     access: vec![PUBLIC_SELECT],
@@ -6584,6 +6991,8 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Table(&MZ_AWS_CONNECTIONS),
         Builtin::Table(&MZ_SUBSCRIPTIONS),
         Builtin::Table(&MZ_SESSIONS),
+        Builtin::Table(&MZ_PORTALS),
+        Builtin::Table(&MZ_PERSIST_TXN_TABLES),
         Builtin::Table(&MZ_DEFAULT_PRIVILEGES),
         Builtin::Table(&MZ_SYSTEM_PRIVILEGES),
         Builtin::Table(&MZ_COMMENTS),
@@ -6725,8 +7134,10 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&MZ_ACTIVITY_LOG_REDACTED),
         Builtin::View(&MZ_RECENT_ACTIVITY_LOG),
         Builtin::View(&MZ_RECENT_ACTIVITY_LOG_REDACTED),
+        Builtin::View(&MZ_STATEMENT_EXECUTION_DEPENDENCIES),
         Builtin::Index(&MZ_RECENT_ACTIVITY_LOG_IND),
         Builtin::View(&MZ_SOURCE_STATUSES),
+        Builtin::View(&MZ_SOURCE_FAMILY_STATUSES),
         Builtin::Source(&MZ_STATEMENT_LIFECYCLE_HISTORY),
         Builtin::Source(&MZ_STORAGE_SHARDS),
         Builtin::Source(&MZ_SOURCE_STATISTICS_PER_WORKER),
@@ -6738,11 +7149,21 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&MZ_STORAGE_USAGE),
         Builtin::Source(&MZ_FRONTIERS),
         Builtin::View(&MZ_GLOBAL_FRONTIERS),
+        Builtin::Source(&MZ_FRONTIERS_HISTORY),
+        Builtin::Source(&MZ_WALLCLOCK_LAG),
+        Builtin::Source(&MZ_FRONTIER_ADVANCEMENT_RATES),
+        Builtin::Source(&MZ_FRONTIER_DIVERGENCE_HISTORY),
+        Builtin::Source(&MZ_CLUSTER_REPLICA_STATUS_HISTORY),
+        Builtin::Source(&MZ_SOURCE_UPSTREAM_LAG),
+        Builtin::Source(&MZ_SOURCE_PARAMETER_GENERATIONS),
+        Builtin::Source(&MZ_TIMESTAMP_ORACLE_STATUS),
+        Builtin::View(&MZ_OBJECT_FRONTIERS_HISTORY),
         Builtin::Source(&MZ_COMPUTE_DEPENDENCIES),
         Builtin::Source(&MZ_COMPUTE_HYDRATION_STATUSES),
         Builtin::Source(&MZ_COMPUTE_OPERATOR_HYDRATION_STATUSES_PER_WORKER),
         Builtin::View(&MZ_HYDRATION_STATUSES),
         Builtin::View(&MZ_MATERIALIZATION_LAG),
+        Builtin::View(&MZ_SINK_PROGRESS),
         Builtin::View(&MZ_COMPUTE_ERROR_COUNTS_PER_WORKER),
         Builtin::View(&MZ_COMPUTE_ERROR_COUNTS),
         Builtin::View(&MZ_COMPUTE_OPERATOR_HYDRATION_STATUSES),
@@ -12,7 +12,7 @@
 use std::fmt::Debug;
 use std::num::NonZeroI64;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use mz_audit_log::{VersionedEvent, VersionedStorageUsage};
@@ -29,25 +29,27 @@ use mz_storage_types::sources::Timeline;
 use uuid::Uuid;
 
 use crate::durable::debug::{DebugCatalogState, Trace};
-pub use crate::durable::error::{CatalogError, DurableCatalogError};
+pub use crate::durable::error::{CatalogError, CommitError, CommitOperation, DurableCatalogError};
 use crate::durable::impls::migrate::{CatalogMigrator, Direction};
 pub use crate::durable::impls::persist::metrics::Metrics;
 use crate::durable::impls::persist::UnopenedPersistCatalogState;
 use crate::durable::impls::shadow::OpenableShadowCatalogState;
+pub use crate::durable::impls::shadow::ShadowSourceOfTruth;
 use crate::durable::impls::stash::{OpenableConnection, TestOpenableConnection};
 pub use crate::durable::impls::stash::{
     StashConfig, ALL_COLLECTIONS, AUDIT_LOG_COLLECTION, CLUSTER_COLLECTION,
     CLUSTER_INTROSPECTION_SOURCE_INDEX_COLLECTION, CLUSTER_REPLICA_COLLECTION, COMMENTS_COLLECTION,
     CONFIG_COLLECTION, DATABASES_COLLECTION, DEFAULT_PRIVILEGES_COLLECTION,
-    ID_ALLOCATOR_COLLECTION, ITEM_COLLECTION, ROLES_COLLECTION, SCHEMAS_COLLECTION,
-    SETTING_COLLECTION, STORAGE_USAGE_COLLECTION, SYSTEM_CONFIGURATION_COLLECTION,
-    SYSTEM_GID_MAPPING_COLLECTION, SYSTEM_PRIVILEGES_COLLECTION, TIMESTAMP_COLLECTION,
+    DROPPED_ITEMS_COLLECTION, ID_ALLOCATOR_COLLECTION, ITEM_COLLECTION, ROLES_COLLECTION,
+    SCHEMAS_COLLECTION, SETTING_COLLECTION, STORAGE_USAGE_COLLECTION,
+    SYSTEM_CONFIGURATION_COLLECTION, SYSTEM_GID_MAPPING_COLLECTION, SYSTEM_PRIVILEGES_COLLECTION,
+    TIMESTAMP_COLLECTION,
 };
 use crate::durable::objects::Snapshot;
 pub use crate::durable::objects::{
     Cluster, ClusterConfig, ClusterReplica, ClusterVariant, ClusterVariantManaged, Comment,
-    Database, DefaultPrivilege, Item, ReplicaConfig, ReplicaLocation, Role, Schema,
-    SystemConfiguration, SystemObjectMapping, TimelineTimestamp,
+    Database, DefaultPrivilege, DroppedItem, Item, ReplicaConfig, ReplicaIncarnation,
+    ReplicaLocation, Role, Schema, SystemConfiguration, SystemObjectMapping, TimelineTimestamp,
 };
 pub use crate::durable::transaction::Transaction;
 use crate::durable::transaction::TransactionBatch;
@@ -260,8 +262,15 @@ pub trait DurableCatalogState: ReadOnlyDurableCatalogState {
     ) -> Result<(Transaction, Vec<VersionedEvent>, Vec<VersionedStorageUsage>), CatalogError>;
 
     /// Commits a durable catalog state transaction.
-    async fn commit_transaction(&mut self, txn_batch: TransactionBatch)
-        -> Result<(), CatalogError>;
+    ///
+    /// If `deadline` passes before the commit completes, the commit is abandoned and
+    /// [`CommitError::Timeout`] is returned. An abandoned commit may still be applied, which is
+    /// detected by the next commit.
+    async fn commit_transaction(
+        &mut self,
+        txn_batch: TransactionBatch,
+        deadline: Option<Instant>,
+    ) -> Result<(), CatalogError>;
 
     /// Confirms that this catalog is connected as the current leader.
     ///
@@ -289,6 +298,19 @@ pub trait DurableCatalogState: ReadOnlyDurableCatalogState {
     /// Allocates and returns `amount` IDs of `id_type`.
     async fn allocate_id(&mut self, id_type: &str, amount: u64) -> Result<Vec<u64>, CatalogError>;
 
+    /// Makes `source_of_truth` the implementation whose results a shadow catalog returns.
+    ///
+    /// Fails without making any changes if the two implementations are not currently identical.
+    /// The cutover is recorded in both implementations along with an audit log event that
+    /// occurred at `occurred_at`.
+    ///
+    /// Only supported by the shadow catalog.
+    async fn cutover(
+        &mut self,
+        source_of_truth: ShadowSourceOfTruth,
+        occurred_at: EpochMillis,
+    ) -> Result<(), CatalogError>;
+
     /// Allocates and returns `amount` system [`GlobalId`]s.
     async fn allocate_system_ids(&mut self, amount: u64) -> Result<Vec<GlobalId>, CatalogError> {
         let id = self.allocate_id(SYSTEM_ITEM_ALLOC_KEY, amount).await?;
@@ -375,18 +397,39 @@ pub async fn test_persist_backed_catalog_state_with_version(
 }
 
 /// Creates an openable durable catalog state implemented using both the stash and persist, that
-/// compares the results. The stash results is used as the source of truth when there's a
-/// discrepancy.
+/// compares the results. Writes go to both implementations and reads are returned from the
+/// implementation that is recorded as the source of truth, which is the stash until a
+/// [`DurableCatalogState::cutover`].
 pub async fn shadow_catalog_state(
     stash_config: StashConfig,
     persist_client: PersistClient,
     organization_id: Uuid,
+) -> impl OpenableDurableCatalogState {
+    let metrics = Arc::new(Metrics::new(&MetricsRegistry::new()));
+    shadow_catalog_state_with_metrics(stash_config, persist_client, organization_id, metrics).await
+}
+
+/// Like [`shadow_catalog_state`], but reports metrics, including the number of reads where the
+/// two implementations disagreed, to `metrics`.
+pub async fn shadow_catalog_state_with_metrics(
+    stash_config: StashConfig,
+    persist_client: PersistClient,
+    organization_id: Uuid,
+    metrics: Arc<Metrics>,
 ) -> impl OpenableDurableCatalogState {
     let stash = Box::new(stash_backed_catalog_state(stash_config));
-    // Shadow catalog is only used for tests, so it's OK to get a test persist catalog state.
-    let persist =
-        Box::new(test_persist_backed_catalog_state(persist_client, organization_id).await);
-    OpenableShadowCatalogState { stash, persist }
+    // Shadow catalog is only used for tests, so it's OK to use a test persist catalog version.
+    let persist = Box::new(
+        persist_backed_catalog_state(
+            persist_client,
+            organization_id,
+            semver::Version::new(0, 0, 0),
+            Arc::clone(&metrics),
+        )
+        .await
+        .expect("failed to open catalog state"),
+    );
+    OpenableShadowCatalogState::new(stash, persist, metrics)
 }
 
 /// Creates an openable durable catalog state that migrates the current state from the stash to
@@ -24,9 +24,10 @@ use crate::durable::{
     CatalogError, AUDIT_LOG_COLLECTION, CLUSTER_COLLECTION,
     CLUSTER_INTROSPECTION_SOURCE_INDEX_COLLECTION, CLUSTER_REPLICA_COLLECTION, COMMENTS_COLLECTION,
     CONFIG_COLLECTION, DATABASES_COLLECTION, DEFAULT_PRIVILEGES_COLLECTION,
-    ID_ALLOCATOR_COLLECTION, ITEM_COLLECTION, ROLES_COLLECTION, SCHEMAS_COLLECTION,
-    SETTING_COLLECTION, STORAGE_USAGE_COLLECTION, SYSTEM_CONFIGURATION_COLLECTION,
-    SYSTEM_GID_MAPPING_COLLECTION, SYSTEM_PRIVILEGES_COLLECTION, TIMESTAMP_COLLECTION,
+    DROPPED_ITEMS_COLLECTION, ID_ALLOCATOR_COLLECTION, ITEM_COLLECTION, ROLES_COLLECTION,
+    SCHEMAS_COLLECTION, SETTING_COLLECTION, STORAGE_USAGE_COLLECTION,
+    SYSTEM_CONFIGURATION_COLLECTION, SYSTEM_GID_MAPPING_COLLECTION, SYSTEM_PRIVILEGES_COLLECTION,
+    TIMESTAMP_COLLECTION,
 };
 
 /// The contents of the catalog are logically separated into separate [`Collection`]s, which
@@ -72,6 +73,7 @@ pub enum CollectionType {
     Config,
     Database,
     DefaultPrivileges,
+    DroppedItem,
     IdAlloc,
     Item,
     Role,
@@ -205,6 +207,15 @@ collection_impl!({
     stash_collection: DEFAULT_PRIVILEGES_COLLECTION,
     persist_update: StateUpdateKind::DefaultPrivilege,
 });
+collection_impl!({
+    name: DroppedItemCollection,
+    key: proto::DroppedItemKey,
+    value: proto::DroppedItemValue,
+    collection_type: CollectionType::DroppedItem,
+    trace_field: dropped_items,
+    stash_collection: DROPPED_ITEMS_COLLECTION,
+    persist_update: StateUpdateKind::DroppedItem,
+});
 collection_impl!({
     name: IdAllocatorCollection,
     key: proto::IdAllocKey,
@@ -322,6 +333,7 @@ pub struct Trace {
     pub configs: CollectionTrace<ConfigCollection>,
     pub databases: CollectionTrace<DatabaseCollection>,
     pub default_privileges: CollectionTrace<DefaultPrivilegeCollection>,
+    pub dropped_items: CollectionTrace<DroppedItemCollection>,
     pub id_allocator: CollectionTrace<IdAllocatorCollection>,
     pub items: CollectionTrace<ItemCollection>,
     pub roles: CollectionTrace<RoleCollection>,
@@ -345,6 +357,7 @@ impl Trace {
             configs: CollectionTrace::new(),
             databases: CollectionTrace::new(),
             default_privileges: CollectionTrace::new(),
+            dropped_items: CollectionTrace::new(),
             id_allocator: CollectionTrace::new(),
             items: CollectionTrace::new(),
             roles: CollectionTrace::new(),
@@ -7,12 +7,15 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
+use std::time::Duration;
 
 use mz_proto::TryFromProtoError;
 use mz_sql::catalog::CatalogError as SqlCatalogError;
 use mz_stash_types::{InternalStashError, StashError};
 
+use crate::durable::Epoch;
+
 #[derive(Debug, thiserror::Error)]
 pub enum CatalogError {
     #[error(transparent)]
@@ -61,6 +64,12 @@ pub enum DurableCatalogError {
     /// Catalog is not in a writable state.
     #[error("{0}")]
     NotWritable(String),
+    /// The stash and persist implementations of a shadow catalog disagree.
+    #[error("{0}")]
+    ShadowDivergence(String),
+    /// A transaction could not be committed.
+    #[error(transparent)]
+    Commit(#[from] CommitError),
     /// Unable to serialize/deserialize Protobuf message.
     #[error("proto: {0}")]
     Proto(TryFromProtoError),
@@ -77,6 +86,7 @@ impl DurableCatalogError {
     pub fn is_unrecoverable(&self) -> bool {
         match self {
             DurableCatalogError::Fence(_) | DurableCatalogError::NotWritable(_) => true,
+            DurableCatalogError::Commit(e) => e.is_unrecoverable(),
             DurableCatalogError::MiscStash(e) => e.is_unrecoverable(),
             _ => false,
         }
@@ -119,6 +129,7 @@ impl From<StashError> for DurableCatalogError {
             InternalStashError::Proto(e) => DurableCatalogError::Proto(e),
             InternalStashError::Postgres(_)
             | InternalStashError::PeekSinceUpper(_)
+            | InternalStashError::DeadlineExceeded
             | InternalStashError::Decoding(_)
             | InternalStashError::Other(_) => DurableCatalogError::MiscStash(e),
         }
@@ -130,3 +141,88 @@ impl From<TryFromProtoError> for DurableCatalogError {
         DurableCatalogError::Proto(e)
     }
 }
+
+/// An error that can occur while committing a transaction to a durable catalog with a deadline.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CommitError {
+    /// The commit did not complete before its deadline. The commit may or may not have been
+    /// applied.
+    #[error("catalog commit timed out after {waited:?} while {operation}")]
+    Timeout {
+        /// How long the commit waited before giving up.
+        waited: Duration,
+        /// The backend operation that was in flight when the deadline passed.
+        operation: CommitOperation,
+    },
+    /// The catalog has been fenced out by another writer.
+    #[error("catalog commit fenced{}: {reason}", epoch.map(|e| format!(" by catalog epoch {e}")).unwrap_or_default())]
+    Fenced {
+        /// The epoch of the writer that fenced out this catalog, if known.
+        epoch: Option<Epoch>,
+        reason: String,
+    },
+    /// The commit conflicted with a concurrent write to the same keys.
+    #[error("catalog commit conflicted with a concurrent write to {}", keys.join(", "))]
+    Conflict {
+        /// Descriptions of the keys written by both the commit and the concurrent write.
+        keys: Vec<String>,
+    },
+}
+
+impl CommitError {
+    /// Reports whether the error is unrecoverable, i.e. whether the catalog must be re-read
+    /// before it can be written again.
+    pub fn is_unrecoverable(&self) -> bool {
+        match self {
+            // Whether a timed out commit was applied is determined by the next commit.
+            CommitError::Timeout { .. } => false,
+            CommitError::Fenced { .. } | CommitError::Conflict { .. } => true,
+        }
+    }
+
+    /// Reports additional details about the error, if any are available.
+    pub fn detail(&self) -> Option<String> {
+        match self {
+            CommitError::Timeout { .. } => Some(
+                "The change may or may not have been applied. If it was, the catalog will be \
+                reloaded before the next change is committed."
+                    .into(),
+            ),
+            CommitError::Fenced { .. } | CommitError::Conflict { .. } => None,
+        }
+    }
+
+    /// Reports a hint for the user about how the error could be fixed.
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            CommitError::Timeout { .. } => Some(
+                "Check whether the change was applied before retrying it. If commits keep \
+                timing out, check the health of the catalog's durable storage or raise \
+                catalog_commit_timeout."
+                    .into(),
+            ),
+            CommitError::Fenced { .. } => {
+                Some("Another environment has taken over the catalog; reconnect to it.".into())
+            }
+            CommitError::Conflict { .. } => Some("Retry the change after reconnecting.".into()),
+        }
+    }
+}
+
+/// The durable catalog backend operation that a commit was performing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitOperation {
+    /// Appending the transaction to the persist shard.
+    CompareAndAppend,
+    /// Executing the transaction against the stash.
+    StashTransaction,
+}
+
+impl fmt::Display for CommitOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommitOperation::CompareAndAppend => f.write_str("appending to the catalog shard"),
+            CommitOperation::StashTransaction => f.write_str("executing the stash transaction"),
+        }
+    }
+}
@@ -7,7 +7,25 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::time::Duration;
+
 pub(crate) mod migrate;
 pub(crate) mod persist;
 pub(crate) mod shadow;
 pub(crate) mod stash;
+
+/// Simulates a hung catalog backend in tests: waits for as long as the `catalog_commit_pause`
+/// failpoint is enabled.
+pub(crate) async fn pause_commit() {
+    while fail::eval("catalog_commit_pause", |_| ()).is_some() {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
+
+/// Simulates a catalog backend that hangs after it applied a commit but before it acknowledged
+/// it in tests: waits for as long as the `catalog_commit_ack_pause` failpoint is enabled.
+pub(crate) async fn pause_commit_ack() {
+    while fail::eval("catalog_commit_ack_pause", |_| ()).is_some() {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+}
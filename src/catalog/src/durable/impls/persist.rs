@@ -13,12 +13,12 @@ pub(crate) mod state_update;
 mod tests;
 
 use std::cmp::max;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Debug;
 use std::future::Future;
 use std::str::FromStr;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use differential_dataflow::lattice::Lattice;
@@ -52,6 +52,7 @@ use crate::durable::debug::{Collection, DebugCatalogState, Trace};
 use crate::durable::impls::persist::metrics::Metrics;
 use crate::durable::impls::persist::state_update::{IntoStateUpdateKindRaw, StateUpdateKindRaw};
 pub use crate::durable::impls::persist::state_update::{StateUpdate, StateUpdateKind};
+use crate::durable::impls::{pause_commit, pause_commit_ack};
 use crate::durable::initialize::{
     CATALOG_KIND_KEY, DEPLOY_GENERATION, PERSIST_TXN_TABLES, SYSTEM_CONFIG_SYNCED_KEY,
     USER_VERSION_KEY,
@@ -61,8 +62,9 @@ use crate::durable::objects::{AuditLogKey, DurableType, Snapshot, StorageUsageKe
 use crate::durable::transaction::TransactionBatch;
 use crate::durable::upgrade::persist::upgrade;
 use crate::durable::{
-    initialize, BootstrapArgs, CatalogError, DurableCatalogError, DurableCatalogState, Epoch,
-    OpenableDurableCatalogState, ReadOnlyDurableCatalogState, TimelineTimestamp, Transaction,
+    initialize, BootstrapArgs, CatalogError, CommitError, CommitOperation, DurableCatalogError,
+    DurableCatalogState, Epoch, OpenableDurableCatalogState, ReadOnlyDurableCatalogState,
+    ShadowSourceOfTruth, TimelineTimestamp, Transaction,
 };
 
 /// New-type used to represent timestamps in persist.
@@ -972,6 +974,55 @@ impl PersistCatalogState {
         Ok(())
     }
 
+    /// Explains why a commit of updates to `keys` was rejected, by reading the updates that
+    /// another writer committed concurrently. This consumes the concurrent updates without
+    /// applying them, so the catalog must not be used afterwards, as after any fence.
+    async fn commit_rejection(
+        &mut self,
+        keys: BTreeSet<String>,
+        deadline: Instant,
+        reason: String,
+    ) -> CommitError {
+        let concurrent_updates = async {
+            let upper = self.current_upper().await;
+            sync::<StateUpdateKind>(&mut self.listen, &mut self.upper, upper).await
+        };
+        let Ok(concurrent_updates) =
+            tokio::time::timeout_at(deadline.into(), concurrent_updates).await
+        else {
+            return CommitError::Fenced {
+                epoch: None,
+                reason,
+            };
+        };
+
+        let mut conflicts = BTreeSet::new();
+        for update in concurrent_updates {
+            if let StateUpdateKind::Epoch(epoch) = update.kind {
+                if epoch > self.epoch {
+                    return CommitError::Fenced {
+                        epoch: Some(epoch),
+                        reason,
+                    };
+                }
+            } else if let Some(key) = update.kind.key_description() {
+                if keys.contains(&key) {
+                    conflicts.insert(key);
+                }
+            }
+        }
+        if conflicts.is_empty() {
+            CommitError::Fenced {
+                epoch: None,
+                reason,
+            }
+        } else {
+            CommitError::Conflict {
+                keys: conflicts.into_iter().collect(),
+            }
+        }
+    }
+
     /// Listen and apply all updates up to `target_upper`.
     #[tracing::instrument(level = "debug", skip(self))]
     async fn sync(&mut self, target_upper: Timestamp) -> Result<(), CatalogError> {
@@ -1046,6 +1097,9 @@ impl PersistCatalogState {
                 StateUpdateKind::DefaultPrivilege(key, value) => {
                     apply(&mut self.snapshot.default_privileges, key, value, diff);
                 }
+                StateUpdateKind::DroppedItem(key, value) => {
+                    apply(&mut self.snapshot.dropped_items, key, value, diff);
+                }
                 StateUpdateKind::Epoch(epoch) => {
                     if epoch > self.epoch {
                         soft_assert_eq_or_log!(diff, 1);
@@ -1299,11 +1353,14 @@ impl DurableCatalogState for PersistCatalogState {
     async fn commit_transaction(
         &mut self,
         txn_batch: TransactionBatch,
+        deadline: Option<Instant>,
     ) -> Result<(), CatalogError> {
         async fn commit_transaction_inner(
             catalog: &mut PersistCatalogState,
             txn_batch: TransactionBatch,
+            deadline: Option<Instant>,
         ) -> Result<(), CatalogError> {
+            let started = Instant::now();
             // If the transaction is empty then we don't error, even in read-only mode. This matches the
             // semantics that the stash uses.
             if !txn_batch.is_empty() && catalog.is_read_only() {
@@ -1320,12 +1377,64 @@ impl DurableCatalogState for PersistCatalogState {
             debug!("committing updates: {updates:?}");
 
             if matches!(catalog.mode, Mode::Writable) {
-                catalog
-                    .compare_and_append(updates, current_upper, next_upper)
-                    .await?;
+                // Only commits with a deadline report why they were rejected, which requires
+                // remembering the keys they write.
+                let keys: BTreeSet<_> = match deadline {
+                    Some(_) => updates
+                        .iter()
+                        .filter_map(|update| update.kind.key_description())
+                        .collect(),
+                    None => BTreeSet::new(),
+                };
+                let append = async {
+                    pause_commit().await;
+                    catalog
+                        .compare_and_append(updates, current_upper, next_upper)
+                        .await?;
+                    pause_commit_ack().await;
+                    Ok(())
+                };
+                let append = with_commit_deadline(
+                    deadline,
+                    started,
+                    CommitOperation::CompareAndAppend,
+                    append,
+                )
+                .await;
+                match (append, deadline) {
+                    (Ok(()), _) => {}
+                    // The append may have landed before the deadline passed, in which case the
+                    // commit succeeded and reporting otherwise would leave the caller's state
+                    // behind the shard's. If the upper did not move, the append did not land;
+                    // should it land after all, the next commit is fenced by its upper mismatch.
+                    (
+                        Err(
+                            err @ CatalogError::Durable(DurableCatalogError::Commit(
+                                CommitError::Timeout { .. },
+                            )),
+                        ),
+                        _,
+                    ) => {
+                        if catalog.current_upper().await == current_upper {
+                            return Err(err);
+                        }
+                    }
+                    (
+                        Err(CatalogError::Durable(DurableCatalogError::Fence(reason))),
+                        Some(deadline),
+                    ) => {
+                        return Err(DurableCatalogError::from(
+                            catalog.commit_rejection(keys, deadline, reason).await,
+                        )
+                        .into());
+                    }
+                    (Err(e), _) => return Err(e),
+                }
                 debug!(
                     "commit successful, upper advanced from {current_upper:?} to {next_upper:?}",
                 );
+                // The commit point has passed, so syncing is not subject to the deadline. Should
+                // another writer have advanced the upper instead, syncing reports the fence.
                 catalog.sync(next_upper).await?;
             } else if matches!(catalog.mode, Mode::Savepoint) {
                 catalog.apply_updates(updates)?;
@@ -1335,7 +1444,7 @@ impl DurableCatalogState for PersistCatalogState {
         }
         self.metrics.transaction_commits.inc();
         let counter = self.metrics.transaction_commit_latency_seconds.clone();
-        commit_transaction_inner(self, txn_batch)
+        commit_transaction_inner(self, txn_batch, deadline)
             .wall_time()
             .inc_by(counter)
             .await
@@ -1450,6 +1559,14 @@ impl DurableCatalogState for PersistCatalogState {
         txn.commit().await?;
         Ok(ids)
     }
+
+    async fn cutover(
+        &mut self,
+        _source_of_truth: ShadowSourceOfTruth,
+        _occurred_at: EpochMillis,
+    ) -> Result<(), CatalogError> {
+        panic!("Persist implementation does not support cutover")
+    }
 }
 
 /// Deterministically generate the a ID for the given `organization_id` and `seed`.
@@ -1485,6 +1602,27 @@ fn as_of(read_handle: &ReadHandle<SourceData, (), Timestamp, Diff>, upper: Times
     as_of
 }
 
+/// Awaits `fut`, a step of a commit that started at `started`, failing with
+/// [`CommitError::Timeout`] if `deadline` passes first.
+async fn with_commit_deadline<T>(
+    deadline: Option<Instant>,
+    started: Instant,
+    operation: CommitOperation,
+    fut: impl Future<Output = Result<T, CatalogError>>,
+) -> Result<T, CatalogError> {
+    match deadline {
+        Some(deadline) => match tokio::time::timeout_at(deadline.into(), fut).await {
+            Ok(result) => result,
+            Err(_) => Err(DurableCatalogError::from(CommitError::Timeout {
+                waited: started.elapsed(),
+                operation,
+            })
+            .into()),
+        },
+        None => fut.await,
+    }
+}
+
 /// Appends `updates` to the catalog state and downgrades the catalog's upper to `next_upper`
 /// iff the current global upper of the catalog is `current_upper`.
 async fn compare_and_append<T: IntoStateUpdateKindRaw>(
@@ -1675,6 +1813,12 @@ impl Trace {
                         .values
                         .push(((k, v), ts.to_string(), diff))
                 }
+                StateUpdateKind::DroppedItem(k, v) => {
+                    trace
+                        .dropped_items
+                        .values
+                        .push(((k, v), ts.to_string(), diff))
+                }
                 StateUpdateKind::Epoch(_) => {
                     // Epoch not included in trace.
                 }
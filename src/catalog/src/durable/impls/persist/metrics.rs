@@ -22,6 +22,7 @@ pub struct Metrics {
     pub snapshot_latency_seconds: Counter,
     pub syncs: IntCounter,
     pub sync_latency_seconds: Counter,
+    pub shadow_mismatched_reads: IntCounter,
 }
 
 impl Metrics {
@@ -56,6 +57,10 @@ impl Metrics {
                 name: "mz_catalog_sync_latency_seconds",
                 help: "Total latency for syncing the in-memory state of the durable catalog with the persisted contents.",
             )),
+            shadow_mismatched_reads: registry.register(metric!(
+                name: "mz_catalog_shadow_mismatched_reads",
+                help: "Count of reads from the shadow catalog where the stash and persist returned different results.",
+            )),
         }
     }
 }
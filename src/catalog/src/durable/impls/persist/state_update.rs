@@ -77,6 +77,7 @@ impl StateUpdate {
             databases,
             schemas,
             items,
+            dropped_items,
             comments,
             roles,
             clusters,
@@ -98,6 +99,7 @@ impl StateUpdate {
         let databases = from_batch(databases, ts, StateUpdateKind::Database);
         let schemas = from_batch(schemas, ts, StateUpdateKind::Schema);
         let items = from_batch(items, ts, StateUpdateKind::Item);
+        let dropped_items = from_batch(dropped_items, ts, StateUpdateKind::DroppedItem);
         let comments = from_batch(comments, ts, StateUpdateKind::Comment);
         let roles = from_batch(roles, ts, StateUpdateKind::Role);
         let clusters = from_batch(clusters, ts, StateUpdateKind::Cluster);
@@ -128,6 +130,7 @@ impl StateUpdate {
         databases
             .chain(schemas)
             .chain(items)
+            .chain(dropped_items)
             .chain(comments)
             .chain(roles)
             .chain(clusters)
@@ -201,6 +204,7 @@ pub enum StateUpdateKind {
     Config(proto::ConfigKey, proto::ConfigValue),
     Database(proto::DatabaseKey, proto::DatabaseValue),
     DefaultPrivilege(proto::DefaultPrivilegesKey, proto::DefaultPrivilegesValue),
+    DroppedItem(proto::DroppedItemKey, proto::DroppedItemValue),
     Epoch(Epoch),
     IdAllocator(proto::IdAllocKey, proto::IdAllocValue),
     IntrospectionSourceIndex(
@@ -221,6 +225,40 @@ pub enum StateUpdateKind {
     Timestamp(proto::TimestampKey, proto::TimestampValue),
 }
 
+impl StateUpdateKind {
+    /// Describes the key that this update writes, for reporting conflicting writes. Audit log
+    /// and storage usage events are append-only, so they never conflict and have no key.
+    pub(crate) fn key_description(&self) -> Option<String> {
+        match self {
+            StateUpdateKind::AuditLog(..) | StateUpdateKind::StorageUsage(..) => None,
+            StateUpdateKind::Epoch(_) => Some("epoch".to_string()),
+            StateUpdateKind::Cluster(key, _) => Some(format!("cluster {key:?}")),
+            StateUpdateKind::ClusterReplica(key, _) => Some(format!("cluster replica {key:?}")),
+            StateUpdateKind::Comment(key, _) => Some(format!("comment {key:?}")),
+            StateUpdateKind::Config(key, _) => Some(format!("config {key:?}")),
+            StateUpdateKind::Database(key, _) => Some(format!("database {key:?}")),
+            StateUpdateKind::DefaultPrivilege(key, _) => Some(format!("default privilege {key:?}")),
+            StateUpdateKind::DroppedItem(key, _) => Some(format!("dropped item {key:?}")),
+            StateUpdateKind::IdAllocator(key, _) => Some(format!("id allocator {key:?}")),
+            StateUpdateKind::IntrospectionSourceIndex(key, _) => {
+                Some(format!("introspection source index {key:?}"))
+            }
+            StateUpdateKind::Item(key, _) => Some(format!("item {key:?}")),
+            StateUpdateKind::Role(key, _) => Some(format!("role {key:?}")),
+            StateUpdateKind::Schema(key, _) => Some(format!("schema {key:?}")),
+            StateUpdateKind::Setting(key, _) => Some(format!("setting {key:?}")),
+            StateUpdateKind::SystemConfiguration(key, _) => {
+                Some(format!("system configuration {key:?}"))
+            }
+            StateUpdateKind::SystemObjectMapping(key, _) => {
+                Some(format!("system object mapping {key:?}"))
+            }
+            StateUpdateKind::SystemPrivilege(key, _) => Some(format!("system privilege {key:?}")),
+            StateUpdateKind::Timestamp(key, _) => Some(format!("timestamp {key:?}")),
+        }
+    }
+}
+
 impl RustType<proto::StateUpdateKind> for StateUpdateKind {
     fn into_proto(&self) -> proto::StateUpdateKind {
         proto::StateUpdateKind {
@@ -270,6 +308,14 @@ impl RustType<proto::StateUpdateKind> for StateUpdateKind {
                         },
                     )
                 }
+                StateUpdateKind::DroppedItem(key, value) => {
+                    proto::state_update_kind::Kind::DroppedItem(
+                        proto::state_update_kind::DroppedItem {
+                            key: Some(key.clone()),
+                            value: Some(value.clone()),
+                        },
+                    )
+                }
                 StateUpdateKind::Epoch(epoch) => {
                     proto::state_update_kind::Kind::Epoch(proto::state_update_kind::Epoch {
                         epoch: epoch.get(),
@@ -436,6 +482,16 @@ impl RustType<proto::StateUpdateKind> for StateUpdateKind {
                         )
                     })?,
                 ),
+                proto::state_update_kind::Kind::DroppedItem(
+                    proto::state_update_kind::DroppedItem { key, value },
+                ) => StateUpdateKind::DroppedItem(
+                    key.ok_or_else(|| {
+                        TryFromProtoError::missing_field("state_update_kind::DroppedItem::key")
+                    })?,
+                    value.ok_or_else(|| {
+                        TryFromProtoError::missing_field("state_update_kind::DroppedItem::value")
+                    })?,
+                ),
                 proto::state_update_kind::Kind::Epoch(proto::state_update_kind::Epoch {
                     epoch,
                 }) => StateUpdateKind::Epoch(Epoch::new(epoch).ok_or_else(|| {
@@ -9,39 +9,43 @@
 
 use std::cmp::max;
 use std::collections::BTreeMap;
-use std::fmt::Debug;
-use std::time::Duration;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use mz_storage_types::controller::PersistTxnTablesImpl;
 use timely::progress::Timestamp as TimelyTimestamp;
 
-use mz_audit_log::{VersionedEvent, VersionedStorageUsage};
+use mz_audit_log::{
+    EventDetails, EventType, IdNameV1, ObjectType, VersionedEvent, VersionedStorageUsage,
+};
 use mz_ore::cast::u64_to_usize;
 use mz_ore::now::EpochMillis;
 use mz_ore::soft_assert_eq_or_log;
-use mz_proto::RustType;
+use mz_proto::{RustType, TryFromProtoError};
 use mz_repr::Timestamp;
 use mz_sql::session::vars::CatalogKind;
 use mz_storage_types::sources::Timeline;
 
 use crate::durable::debug::{DebugCatalogState, Trace};
+use crate::durable::initialize::SHADOW_SOURCE_OF_TRUTH_KEY;
 use crate::durable::objects::serialization::proto;
 use crate::durable::objects::{
     DurableType, Snapshot, TimelineTimestamp, TimestampKey, TimestampValue,
 };
 use crate::durable::transaction::TransactionBatch;
 use crate::durable::{
-    BootstrapArgs, CatalogError, DurableCatalogState, Epoch, OpenableDurableCatalogState,
-    ReadOnlyDurableCatalogState, Transaction, STORAGE_USAGE_ID_ALLOC_KEY,
+    BootstrapArgs, CatalogError, DurableCatalogError, DurableCatalogState, Epoch, Metrics,
+    OpenableDurableCatalogState, ReadOnlyDurableCatalogState, Transaction, AUDIT_LOG_ID_ALLOC_KEY,
+    STORAGE_USAGE_ID_ALLOC_KEY,
 };
 
 macro_rules! compare_and_return {
     ($shadow:expr, $method:ident $(, $arg:expr)*) => {{
         let stash = $shadow.stash.$method($($arg.clone()),*);
         let persist = $shadow.persist.$method($($arg),*);
-        soft_assert_eq_or_log!(stash, persist);
-        stash
+        $shadow.comparator.choose(stash, persist)
     }};
 }
 
@@ -50,16 +54,102 @@ macro_rules! compare_and_return_async {
         let stash = $shadow.stash.$method($($arg),*);
         let persist = $shadow.persist.$method($($arg),*);
         let (stash, persist) = futures::future::join(stash, persist).await;
+        $shadow.comparator.choose_result(stash, persist)
+    }};
+}
+
+/// The implementation of a shadow catalog whose results are returned to callers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShadowSourceOfTruth {
+    Stash,
+    Persist,
+}
+
+impl ShadowSourceOfTruth {
+    /// Reads the source of truth recorded in `snapshot`. Catalogs that have never been cut over
+    /// use the stash as the source of truth.
+    fn from_snapshot(
+        snapshot: &Snapshot,
+    ) -> Result<Option<ShadowSourceOfTruth>, DurableCatalogError> {
+        snapshot
+            .configs
+            .get(&proto::ConfigKey {
+                key: SHADOW_SOURCE_OF_TRUTH_KEY.to_string(),
+            })
+            .map(|value| match value.value {
+                0 => Ok(ShadowSourceOfTruth::Stash),
+                1 => Ok(ShadowSourceOfTruth::Persist),
+                value => Err(DurableCatalogError::Proto(
+                    TryFromProtoError::unknown_enum_variant(format!(
+                        "ShadowSourceOfTruth::{value}"
+                    )),
+                )),
+            })
+            .transpose()
+    }
+}
+
+impl From<ShadowSourceOfTruth> for u64 {
+    fn from(value: ShadowSourceOfTruth) -> Self {
+        match value {
+            ShadowSourceOfTruth::Stash => 0,
+            ShadowSourceOfTruth::Persist => 1,
+        }
+    }
+}
+
+impl fmt::Display for ShadowSourceOfTruth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShadowSourceOfTruth::Stash => f.write_str("stash"),
+            ShadowSourceOfTruth::Persist => f.write_str("persist"),
+        }
+    }
+}
+
+/// Compares the results of the stash and persist implementations and picks the one from the
+/// source of truth.
+#[derive(Debug)]
+struct Comparator {
+    source_of_truth: ShadowSourceOfTruth,
+    metrics: Arc<Metrics>,
+}
+
+impl Comparator {
+    fn choose<T: Debug + PartialEq>(&self, stash: T, persist: T) -> T {
+        if stash != persist {
+            self.metrics.shadow_mismatched_reads.inc();
+        }
+        soft_assert_eq_or_log!(stash, persist);
+        match self.source_of_truth {
+            ShadowSourceOfTruth::Stash => stash,
+            ShadowSourceOfTruth::Persist => persist,
+        }
+    }
+
+    fn choose_result<T: Debug + PartialEq>(
+        &self,
+        stash: Result<T, CatalogError>,
+        persist: Result<T, CatalogError>,
+    ) -> Result<T, CatalogError> {
         soft_assert_eq_or_log!(
             stash.is_ok(),
             persist.is_ok(),
             "stash and persist result variant do not match. stash: {stash:?}. persist: {persist:?}"
         );
-        let stash = stash?;
-        let persist = persist?;
-        soft_assert_eq_or_log!(stash, persist);
-        Ok(stash)
-    }};
+        match (stash, persist) {
+            (Ok(stash), Ok(persist)) => Ok(self.choose(stash, persist)),
+            (stash, persist) => {
+                if stash.is_ok() != persist.is_ok() {
+                    self.metrics.shadow_mismatched_reads.inc();
+                }
+                match self.source_of_truth {
+                    ShadowSourceOfTruth::Stash => stash,
+                    ShadowSourceOfTruth::Persist => persist,
+                }
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -68,8 +158,29 @@ where
     S: OpenableDurableCatalogState,
     P: OpenableDurableCatalogState,
 {
-    pub stash: Box<S>,
-    pub persist: Box<P>,
+    stash: Box<S>,
+    persist: Box<P>,
+    /// The source of truth is recorded in the catalog contents, which are only available once the
+    /// catalog is opened. Until then the stash is used, which is fine because the results of
+    /// unopened catalogs are expected to agree regardless of the source of truth.
+    comparator: Comparator,
+}
+
+impl<S, P> OpenableShadowCatalogState<S, P>
+where
+    S: OpenableDurableCatalogState,
+    P: OpenableDurableCatalogState,
+{
+    pub(crate) fn new(stash: Box<S>, persist: Box<P>, metrics: Arc<Metrics>) -> Self {
+        OpenableShadowCatalogState {
+            stash,
+            persist,
+            comparator: Comparator {
+                source_of_truth: ShadowSourceOfTruth::Stash,
+                metrics,
+            },
+        }
+    }
 }
 
 #[async_trait]
@@ -85,6 +196,7 @@ where
         deploy_generation: Option<u64>,
         epoch_lower_bound: Option<Epoch>,
     ) -> Result<Box<dyn DurableCatalogState>, CatalogError> {
+        let metrics = Arc::clone(&self.comparator.metrics);
         let stash = self.stash.open_savepoint(
             initial_ts.clone(),
             bootstrap_args,
@@ -105,13 +217,16 @@ where
         );
         let stash = stash?;
         let persist = persist?;
-        Ok(Box::new(ShadowCatalogState::new(stash, persist).await?))
+        Ok(Box::new(
+            ShadowCatalogState::new(stash, persist, metrics).await?,
+        ))
     }
 
     async fn open_read_only(
         self: Box<Self>,
         bootstrap_args: &BootstrapArgs,
     ) -> Result<Box<dyn DurableCatalogState>, CatalogError> {
+        let metrics = Arc::clone(&self.comparator.metrics);
         let stash = self.stash.open_read_only(bootstrap_args);
         let persist = self.persist.open_read_only(bootstrap_args);
         let (stash, persist) = futures::future::join(stash, persist).await;
@@ -122,7 +237,9 @@ where
         );
         let stash = stash?;
         let persist = persist?;
-        Ok(Box::new(ShadowCatalogState::new_read_only(stash, persist)))
+        Ok(Box::new(
+            ShadowCatalogState::new_read_only(stash, persist, metrics).await?,
+        ))
     }
 
     async fn open(
@@ -132,6 +249,7 @@ where
         deploy_generation: Option<u64>,
         epoch_lower_bound: Option<Epoch>,
     ) -> Result<Box<dyn DurableCatalogState>, CatalogError> {
+        let metrics = Arc::clone(&self.comparator.metrics);
         let stash = self.stash.open(
             initial_ts.clone(),
             bootstrap_args,
@@ -152,7 +270,9 @@ where
         );
         let stash = stash?;
         let persist = persist?;
-        Ok(Box::new(ShadowCatalogState::new(stash, persist).await?))
+        Ok(Box::new(
+            ShadowCatalogState::new(stash, persist, metrics).await?,
+        ))
     }
 
     async fn open_debug(mut self: Box<Self>) -> Result<DebugCatalogState, CatalogError> {
@@ -200,26 +320,57 @@ where
 pub struct ShadowCatalogState {
     pub stash: Box<dyn DurableCatalogState>,
     pub persist: Box<dyn DurableCatalogState>,
+    comparator: Comparator,
 }
 
 impl ShadowCatalogState {
     async fn new(
         stash: Box<dyn DurableCatalogState>,
         persist: Box<dyn DurableCatalogState>,
+        metrics: Arc<Metrics>,
     ) -> Result<ShadowCatalogState, CatalogError> {
-        let mut state = ShadowCatalogState { stash, persist };
+        let mut state = ShadowCatalogState::new_read_only(stash, persist, metrics).await?;
         state.fix_timestamps().await?;
         state.fix_storage_usage().await?;
         Ok(state)
     }
 
-    fn new_read_only(
+    /// Creates a shadow catalog without fixing any discrepancies between the two
+    /// implementations. We cannot fix timestamp discrepancies in a read-only catalog, so we'll
+    /// just have to ignore them.
+    async fn new_read_only(
         stash: Box<dyn DurableCatalogState>,
         persist: Box<dyn DurableCatalogState>,
-    ) -> ShadowCatalogState {
-        // We cannot fix timestamp discrepancies in a read-only catalog, so we'll just have to
-        // ignore them.
-        ShadowCatalogState { stash, persist }
+        metrics: Arc<Metrics>,
+    ) -> Result<ShadowCatalogState, CatalogError> {
+        let mut state = ShadowCatalogState {
+            stash,
+            persist,
+            comparator: Comparator {
+                source_of_truth: ShadowSourceOfTruth::Stash,
+                metrics,
+            },
+        };
+        state.comparator.source_of_truth = state.recorded_source_of_truth().await?;
+        Ok(state)
+    }
+
+    /// Returns the source of truth recorded in both implementations.
+    ///
+    /// A cutover writes the source of truth to each implementation separately, so a crash or a
+    /// fenced write part way through can leave the two disagreeing. We refuse to open the catalog
+    /// in that case, because we can no longer tell which implementation is authoritative.
+    async fn recorded_source_of_truth(&mut self) -> Result<ShadowSourceOfTruth, CatalogError> {
+        let stash = ShadowSourceOfTruth::from_snapshot(&self.stash.snapshot().await?)?;
+        let persist = ShadowSourceOfTruth::from_snapshot(&self.persist.snapshot().await?)?;
+        if stash != persist {
+            return Err(DurableCatalogError::ShadowDivergence(format!(
+                "stash and persist disagree on the shadow catalog source of truth. \
+                stash: {stash:?}. persist: {persist:?}"
+            ))
+            .into());
+        }
+        Ok(stash.unwrap_or(ShadowSourceOfTruth::Stash))
     }
 
     /// The Coordinator will update the timestamps of every timeline continuously on an interval.
@@ -466,8 +617,7 @@ impl ReadOnlyDurableCatalogState for ShadowCatalogState {
                     next_id: reconciled_storage_usage_id,
                 },
             );
-            soft_assert_eq_or_log!(stash, persist);
-            Ok(stash)
+            Ok(self.comparator.choose(stash, persist))
         } else {
             compare_and_return_async!(self, snapshot)
         }
@@ -524,8 +674,9 @@ impl DurableCatalogState for ShadowCatalogState {
     async fn commit_transaction(
         &mut self,
         txn_batch: TransactionBatch,
+        deadline: Option<Instant>,
     ) -> Result<(), CatalogError> {
-        let res = compare_and_return_async!(self, commit_transaction, txn_batch.clone());
+        let res = compare_and_return_async!(self, commit_transaction, txn_batch.clone(), deadline);
         // After committing a transaction, check that both implementations return the same snapshot
         // to ensure that the commit had the same effect on the underlying state. Call
         // `self.snapshot()` directly to avoid timestamp and storage usage ID discrepancies.
@@ -581,6 +732,65 @@ impl DurableCatalogState for ShadowCatalogState {
     async fn allocate_id(&mut self, id_type: &str, amount: u64) -> Result<Vec<u64>, CatalogError> {
         compare_and_return_async!(self, allocate_id, id_type, amount)
     }
+
+    async fn cutover(
+        &mut self,
+        source_of_truth: ShadowSourceOfTruth,
+        occurred_at: EpochMillis,
+    ) -> Result<(), CatalogError> {
+        if self.is_read_only() {
+            return Err(DurableCatalogError::NotWritable(
+                "cannot cut over a read-only shadow catalog".to_string(),
+            )
+            .into());
+        }
+        // Make sure that nobody else has opened the catalog since we did, before comparing
+        // contents that another writer may be changing.
+        self.confirm_leadership().await?;
+
+        let stash = (
+            self.stash.snapshot().await?,
+            self.stash.get_audit_logs().await?,
+            self.stash
+                .get_and_prune_storage_usage(None, Timestamp::minimum(), false)
+                .await?,
+        );
+        let persist = (
+            self.persist.snapshot().await?,
+            self.persist.get_audit_logs().await?,
+            self.persist
+                .get_and_prune_storage_usage(None, Timestamp::minimum(), false)
+                .await?,
+        );
+        if stash != persist {
+            self.comparator.metrics.shadow_mismatched_reads.inc();
+            return Err(DurableCatalogError::ShadowDivergence(format!(
+                "refusing to cut over to {source_of_truth}, stash and persist contents differ"
+            ))
+            .into());
+        }
+
+        // The transaction is committed to both implementations, each of which checks its own
+        // epoch, so a fenced catalog cannot record the cutover.
+        let mut txn = self.transaction().await?;
+        let id = txn.get_and_increment_id(AUDIT_LOG_ID_ALLOC_KEY.to_string())?;
+        txn.set_shadow_source_of_truth(source_of_truth)?;
+        txn.insert_audit_log_event(VersionedEvent::new(
+            id,
+            EventType::Alter,
+            ObjectType::System,
+            EventDetails::IdNameV1(IdNameV1 {
+                id: SHADOW_SOURCE_OF_TRUTH_KEY.to_string(),
+                name: source_of_truth.to_string(),
+            }),
+            None,
+            occurred_at,
+        ));
+        txn.commit().await?;
+
+        self.comparator.source_of_truth = source_of_truth;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -594,10 +804,12 @@ mod tests {
     use timely::progress::Timestamp as TimelyTimestamp;
     use uuid::Uuid;
 
+    use crate::durable::initialize::SHADOW_SOURCE_OF_TRUTH_KEY;
     use crate::durable::{
         shadow_catalog_state, test_bootstrap_args, test_persist_backed_catalog_state,
-        test_stash_backed_catalog_state, test_stash_config, OpenableDurableCatalogState,
-        TimelineTimestamp, STORAGE_USAGE_ID_ALLOC_KEY,
+        test_stash_backed_catalog_state, test_stash_config, CatalogError, DurableCatalogError,
+        OpenableDurableCatalogState, ShadowSourceOfTruth, TimelineTimestamp,
+        STORAGE_USAGE_ID_ALLOC_KEY,
     };
 
     #[mz_ore::test(tokio::test)]
@@ -823,4 +1035,109 @@ mod tests {
             assert_eq!(ts, ahead_ts)
         }
     }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait
+    async fn test_disagreeing_source_of_truth() {
+        let persist_client = PersistClient::new_for_tests().await;
+        let organization_id = Uuid::new_v4();
+        let (debug_factory, stash_config) = test_stash_config().await;
+
+        {
+            let openable_shadow_state = shadow_catalog_state(
+                stash_config.clone(),
+                persist_client.clone(),
+                organization_id,
+            )
+            .await;
+            let _ = Box::new(openable_shadow_state)
+                .open(SYSTEM_TIME(), &test_bootstrap_args(), None, None)
+                .await
+                .expect("failed to open");
+        }
+
+        // Record a cutover in only one of the implementations.
+        {
+            let openable_persist_state =
+                test_persist_backed_catalog_state(persist_client.clone(), organization_id).await;
+            let mut persist_state = Box::new(openable_persist_state)
+                .open(SYSTEM_TIME(), &test_bootstrap_args(), None, None)
+                .await
+                .expect("failed to open");
+            let mut tx = persist_state
+                .transaction()
+                .await
+                .expect("failed to open transaction");
+            tx.set_shadow_source_of_truth(ShadowSourceOfTruth::Persist)
+                .expect("failed to set source of truth");
+            tx.commit().await.expect("failed to commit transaction");
+        }
+
+        let openable_shadow_state =
+            shadow_catalog_state(stash_config, persist_client, organization_id).await;
+        let err = Box::new(openable_shadow_state)
+            .open(SYSTEM_TIME(), &test_bootstrap_args(), None, None)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                CatalogError::Durable(DurableCatalogError::ShadowDivergence(_))
+            ),
+            "unexpected err: {err:?}"
+        );
+
+        debug_factory.drop().await;
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait
+    async fn test_invalid_source_of_truth() {
+        let persist_client = PersistClient::new_for_tests().await;
+        let organization_id = Uuid::new_v4();
+        let (debug_factory, stash_config) = test_stash_config().await;
+
+        {
+            let openable_shadow_state = shadow_catalog_state(
+                stash_config.clone(),
+                persist_client.clone(),
+                organization_id,
+            )
+            .await;
+            let _ = Box::new(openable_shadow_state)
+                .open(SYSTEM_TIME(), &test_bootstrap_args(), None, None)
+                .await
+                .expect("failed to open");
+        }
+
+        // Record a source of truth that this version does not know about.
+        {
+            let openable_persist_state =
+                test_persist_backed_catalog_state(persist_client.clone(), organization_id).await;
+            let mut persist_state = Box::new(openable_persist_state)
+                .open(SYSTEM_TIME(), &test_bootstrap_args(), None, None)
+                .await
+                .expect("failed to open");
+            let mut tx = persist_state
+                .transaction()
+                .await
+                .expect("failed to open transaction");
+            tx.set_config(SHADOW_SOURCE_OF_TRUTH_KEY.to_string(), Some(2))
+                .expect("failed to set source of truth");
+            tx.commit().await.expect("failed to commit transaction");
+        }
+
+        let openable_shadow_state =
+            shadow_catalog_state(stash_config, persist_client, organization_id).await;
+        let err = Box::new(openable_shadow_state)
+            .open(SYSTEM_TIME(), &test_bootstrap_args(), None, None)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, CatalogError::Durable(DurableCatalogError::Proto(_))),
+            "unexpected err: {err:?}"
+        );
+
+        debug_factory.drop().await;
+    }
 }
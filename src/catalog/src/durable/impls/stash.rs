@@ -13,7 +13,7 @@ use mz_storage_types::controller::PersistTxnTablesImpl;
 use std::collections::{BTreeMap, BTreeSet};
 use std::pin;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use futures::StreamExt;
 use itertools::Itertools;
@@ -35,6 +35,7 @@ use mz_stash_types::StashError;
 use mz_storage_types::sources::Timeline;
 
 use crate::durable::debug::{Collection, CollectionTrace, Trace};
+use crate::durable::impls::pause_commit;
 use crate::durable::initialize::{
     CATALOG_KIND_KEY, DEPLOY_GENERATION, PERSIST_TXN_TABLES, SYSTEM_CONFIG_SYNCED_KEY,
     TOMBSTONE_KEY, USER_VERSION_KEY,
@@ -47,8 +48,9 @@ use crate::durable::objects::{
 use crate::durable::transaction::{Transaction, TransactionBatch};
 use crate::durable::upgrade::stash::upgrade;
 use crate::durable::{
-    initialize, BootstrapArgs, CatalogError, DebugCatalogState, DurableCatalogError,
-    DurableCatalogState, Epoch, OpenableDurableCatalogState, ReadOnlyDurableCatalogState,
+    initialize, BootstrapArgs, CatalogError, CommitError, CommitOperation, DebugCatalogState,
+    DurableCatalogError, DurableCatalogState, Epoch, OpenableDurableCatalogState,
+    ReadOnlyDurableCatalogState, ShadowSourceOfTruth,
 };
 
 pub const SETTING_COLLECTION: TypedCollection<proto::SettingKey, proto::SettingValue> =
@@ -71,6 +73,10 @@ pub const ITEM_COLLECTION: TypedCollection<proto::ItemKey, proto::ItemValue> =
     TypedCollection::new("item");
 pub const COMMENTS_COLLECTION: TypedCollection<proto::CommentKey, proto::CommentValue> =
     TypedCollection::new("comments");
+pub const DROPPED_ITEMS_COLLECTION: TypedCollection<
+    proto::DroppedItemKey,
+    proto::DroppedItemValue,
+> = TypedCollection::new("dropped_item");
 pub const TIMESTAMP_COLLECTION: TypedCollection<proto::TimestampKey, proto::TimestampValue> =
     TypedCollection::new("timestamp");
 pub const SYSTEM_CONFIGURATION_COLLECTION: TypedCollection<
@@ -327,6 +333,7 @@ impl OpenableDurableCatalogState for OpenableConnection {
             configs,
             databases,
             default_privileges,
+            dropped_items,
             id_allocator,
             items,
             roles,
@@ -357,6 +364,7 @@ impl OpenableDurableCatalogState for OpenableConnection {
                 _,
                 _,
             )>,
+            Vec<((proto::DroppedItemKey, proto::DroppedItemValue), _, _)>,
             Vec<((proto::IdAllocKey, proto::IdAllocValue), _, _)>,
             Vec<((proto::ItemKey, proto::ItemValue), _, _)>,
             Vec<((proto::RoleKey, proto::RoleValue), _, _)>,
@@ -394,6 +402,7 @@ impl OpenableDurableCatalogState for OpenableConnection {
                         tx.iter(tx.collection(CONFIG_COLLECTION.name()).await?),
                         tx.iter(tx.collection(DATABASES_COLLECTION.name()).await?),
                         tx.iter(tx.collection(DEFAULT_PRIVILEGES_COLLECTION.name()).await?),
+                        tx.iter(tx.collection(DROPPED_ITEMS_COLLECTION.name()).await?),
                         tx.iter(tx.collection(ID_ALLOCATOR_COLLECTION.name()).await?),
                         tx.iter(tx.collection(ITEM_COLLECTION.name()).await?),
                         tx.iter(tx.collection(ROLES_COLLECTION.name()).await?),
@@ -421,6 +430,7 @@ impl OpenableDurableCatalogState for OpenableConnection {
             configs: stringify(configs),
             databases: stringify(databases),
             default_privileges: stringify(default_privileges),
+            dropped_items: stringify(dropped_items),
             id_allocator: stringify(id_allocator),
             items: stringify(items),
             roles: stringify(roles),
@@ -674,6 +684,7 @@ impl ReadOnlyDurableCatalogState for Connection {
             schemas,
             roles,
             items,
+            dropped_items,
             comments,
             clusters,
             cluster_replicas,
@@ -691,6 +702,7 @@ impl ReadOnlyDurableCatalogState for Connection {
             BTreeMap<proto::SchemaKey, proto::SchemaValue>,
             BTreeMap<proto::RoleKey, proto::RoleValue>,
             BTreeMap<proto::ItemKey, proto::ItemValue>,
+            BTreeMap<proto::DroppedItemKey, proto::DroppedItemValue>,
             BTreeMap<proto::CommentKey, proto::CommentValue>,
             BTreeMap<proto::ClusterKey, proto::ClusterValue>,
             BTreeMap<proto::ClusterReplicaKey, proto::ClusterReplicaValue>,
@@ -716,6 +728,7 @@ impl ReadOnlyDurableCatalogState for Connection {
                         tx.peek_one(tx.collection(SCHEMAS_COLLECTION.name()).await?),
                         tx.peek_one(tx.collection(ROLES_COLLECTION.name()).await?),
                         tx.peek_one(tx.collection(ITEM_COLLECTION.name()).await?),
+                        tx.peek_one(tx.collection(DROPPED_ITEMS_COLLECTION.name()).await?),
                         tx.peek_one(tx.collection(COMMENTS_COLLECTION.name()).await?),
                         tx.peek_one(tx.collection(CLUSTER_COLLECTION.name()).await?),
                         tx.peek_one(tx.collection(CLUSTER_REPLICA_COLLECTION.name()).await?),
@@ -744,6 +757,7 @@ impl ReadOnlyDurableCatalogState for Connection {
             schemas,
             roles,
             items,
+            dropped_items,
             comments,
             clusters,
             cluster_replicas,
@@ -768,6 +782,7 @@ impl ReadOnlyDurableCatalogState for Connection {
             schemas,
             roles,
             items,
+            dropped_items,
             comments,
             clusters,
             cluster_replicas,
@@ -787,6 +802,7 @@ impl ReadOnlyDurableCatalogState for Connection {
             BTreeMap<proto::SchemaKey, proto::SchemaValue>,
             BTreeMap<proto::RoleKey, proto::RoleValue>,
             BTreeMap<proto::ItemKey, proto::ItemValue>,
+            BTreeMap<proto::DroppedItemKey, proto::DroppedItemValue>,
             BTreeMap<proto::CommentKey, proto::CommentValue>,
             BTreeMap<proto::ClusterKey, proto::ClusterValue>,
             BTreeMap<proto::ClusterReplicaKey, proto::ClusterReplicaValue>,
@@ -814,6 +830,7 @@ impl ReadOnlyDurableCatalogState for Connection {
                         tx.peek_one(tx.collection(SCHEMAS_COLLECTION.name()).await?),
                         tx.peek_one(tx.collection(ROLES_COLLECTION.name()).await?),
                         tx.peek_one(tx.collection(ITEM_COLLECTION.name()).await?),
+                        tx.peek_one(tx.collection(DROPPED_ITEMS_COLLECTION.name()).await?),
                         tx.peek_one(tx.collection(COMMENTS_COLLECTION.name()).await?),
                         tx.peek_one(tx.collection(CLUSTER_COLLECTION.name()).await?),
                         tx.peek_one(tx.collection(CLUSTER_REPLICA_COLLECTION.name()).await?),
@@ -856,6 +873,7 @@ impl ReadOnlyDurableCatalogState for Connection {
                 schemas,
                 roles,
                 items,
+                dropped_items,
                 comments,
                 clusters,
                 cluster_replicas,
@@ -900,6 +918,7 @@ impl DurableCatalogState for Connection {
     async fn commit_transaction(
         &mut self,
         txn_batch: TransactionBatch,
+        deadline: Option<Instant>,
     ) -> Result<(), CatalogError> {
         async fn add_batch<'tx, K, V>(
             tx: &'tx mz_stash::Transaction<'tx>,
@@ -934,7 +953,9 @@ impl DurableCatalogState for Connection {
         async fn commit_transaction_inner(
             catalog: &mut Connection,
             txn_batch: TransactionBatch,
+            deadline: Option<Instant>,
         ) -> Result<(), CatalogError> {
+            let started = Instant::now();
             // The with_transaction fn below requires a Fn that can be cloned,
             // meaning anything it closes over must be Clone. TransactionBatch
             // implements Clone, thus, the Arcs here aren't strictly necessary.
@@ -949,10 +970,11 @@ impl DurableCatalogState for Connection {
                 catalog.stash.set_connect_timeout(connection_timeout).await;
             }
 
-            catalog
+            let result = catalog
                 .stash
-                .with_transaction(move |tx| {
+                .with_transaction_deadline(deadline, move |tx| {
                     Box::pin(async move {
+                        pause_commit().await;
                         let mut batches = Vec::new();
 
                         add_batch(
@@ -979,6 +1001,14 @@ impl DurableCatalogState for Connection {
                             is_initialized,
                         )
                         .await?;
+                        add_batch(
+                            &tx,
+                            &mut batches,
+                            &DROPPED_ITEMS_COLLECTION,
+                            &txn_batch.dropped_items,
+                            is_initialized,
+                        )
+                        .await?;
                         add_batch(
                             &tx,
                             &mut batches,
@@ -1104,9 +1134,32 @@ impl DurableCatalogState for Connection {
                         Ok(())
                     })
                 })
-                .await?;
-
-            Ok(())
+                .await;
+
+            match result {
+                Ok(()) => Ok(()),
+                Err(e) if e.is_deadline_exceeded() => {
+                    Err(DurableCatalogError::from(CommitError::Timeout {
+                        waited: started.elapsed(),
+                        operation: CommitOperation::StashTransaction,
+                    })
+                    .into())
+                }
+                Err(e) => match (DurableCatalogError::from(e), deadline) {
+                    // Report the epoch that fenced us out, if it can be read in time.
+                    (DurableCatalogError::Fence(reason), Some(deadline)) => {
+                        let fence_epoch =
+                            tokio::time::timeout_at(deadline.into(), catalog.stash.fence_epoch())
+                                .await;
+                        let epoch = match fence_epoch {
+                            Ok(Ok(epoch)) if Some(epoch) != catalog.stash.epoch() => Some(epoch),
+                            _ => None,
+                        };
+                        Err(DurableCatalogError::from(CommitError::Fenced { epoch, reason }).into())
+                    }
+                    (e, _) => Err(e.into()),
+                },
+            }
         }
         self.stash.metrics.catalog_transaction_commits.inc();
         let counter = self
@@ -1114,7 +1167,7 @@ impl DurableCatalogState for Connection {
             .metrics
             .catalog_transaction_commit_latency_seconds
             .clone();
-        commit_transaction_inner(self, txn_batch)
+        commit_transaction_inner(self, txn_batch, deadline)
             .wall_time()
             .inc_by(counter)
             .await
@@ -1228,6 +1281,14 @@ impl DurableCatalogState for Connection {
         let id = prev.expect("must exist").next_id;
         Ok((id..next.next_id).collect())
     }
+
+    async fn cutover(
+        &mut self,
+        _source_of_truth: ShadowSourceOfTruth,
+        _occurred_at: EpochMillis,
+    ) -> Result<(), CatalogError> {
+        panic!("Stash implementation does not support cutover")
+    }
 }
 
 // Debug methods.
@@ -1276,6 +1337,7 @@ pub const ALL_COLLECTIONS: &[&str] = &[
     CONFIG_COLLECTION.name(),
     DATABASES_COLLECTION.name(),
     DEFAULT_PRIVILEGES_COLLECTION.name(),
+    DROPPED_ITEMS_COLLECTION.name(),
     ID_ALLOCATOR_COLLECTION.name(),
     ITEM_COLLECTION.name(),
     ROLES_COLLECTION.name(),
@@ -55,6 +55,10 @@ pub(crate) const PERSIST_TXN_TABLES: &str = "persist_txn_tables";
 /// being used for the catalog. This is used to implement migrations/rollbacks to/from persist.
 pub(crate) const TOMBSTONE_KEY: &str = "tombstone";
 
+/// The key within the "config" collection that stores which implementation of a shadow catalog is
+/// the source of truth. It is stored in both implementations and must agree between them.
+pub(crate) const SHADOW_SOURCE_OF_TRUTH_KEY: &str = "shadow_source_of_truth";
+
 /// The key used within the "config" collection where we store a mirror of the
 /// `catalog_kind` "system var" value. This is mirrored so that we
 /// can toggle the flag with Launch Darkly, but use it in boot before Launch
@@ -629,6 +633,7 @@ fn default_cluster_config(args: &BootstrapArgs) -> ClusterConfig {
             idle_arrangement_merge_effort: None,
             disk: false,
         }),
+        transaction_isolation: None,
     }
 }
 
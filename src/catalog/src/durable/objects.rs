@@ -14,6 +14,7 @@ use std::collections::BTreeMap;
 use mz_audit_log::{VersionedEvent, VersionedStorageUsage};
 use mz_controller::clusters::ReplicaLogging;
 use mz_controller_types::{ClusterId, ReplicaId};
+use mz_proto::{IntoRustIfSome, RustType, TryFromProtoError};
 use mz_repr::adt::mz_acl_item::{AclMode, MzAclItem};
 use mz_repr::role_id::RoleId;
 use mz_repr::GlobalId;
@@ -22,6 +23,7 @@ use mz_sql::catalog::{
     RoleMembership, RoleVars,
 };
 use mz_sql::names::{CommentObjectId, DatabaseId, SchemaId};
+use mz_sql::session::vars::IsolationLevel;
 use mz_storage_types::sources::Timeline;
 use proptest_derive::Arbitrary;
 
@@ -182,6 +184,7 @@ impl DurableType<ClusterKey, ClusterValue> for Cluster {
 #[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Ord)]
 pub struct ClusterConfig {
     pub variant: ClusterVariant,
+    pub transaction_isolation: Option<IsolationLevel>,
 }
 
 #[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Ord)]
@@ -256,6 +259,7 @@ pub struct ClusterReplica {
     pub name: String,
     pub config: ReplicaConfig,
     pub owner_id: RoleId,
+    pub incarnation: ReplicaIncarnation,
 }
 
 impl DurableType<ClusterReplicaKey, ClusterReplicaValue> for ClusterReplica {
@@ -269,6 +273,7 @@ impl DurableType<ClusterReplicaKey, ClusterReplicaValue> for ClusterReplica {
                 name: self.name,
                 config: self.config,
                 owner_id: self.owner_id,
+                incarnation: self.incarnation,
             },
         )
     }
@@ -280,10 +285,26 @@ impl DurableType<ClusterReplicaKey, ClusterReplicaValue> for ClusterReplica {
             name: value.name,
             config: value.config,
             owner_id: value.owner_id,
+            incarnation: value.incarnation,
         }
     }
 }
 
+/// Tracks how a cluster replica is reconfigured in place.
+///
+/// Reconfiguring a cluster gracefully creates the replicas of its new configuration as the next
+/// incarnation of the replicas they replace, and marks the replaced ones as pending removal until
+/// the new ones are ready.
+#[derive(Clone, Copy, Debug, Default, PartialOrd, PartialEq, Eq, Ord)]
+pub struct ReplicaIncarnation {
+    /// The number of replicas that this replica succeeds.
+    pub incarnation: u64,
+    /// The number of times the configuration of the replica was updated.
+    pub config_version: u64,
+    /// Whether the replica is to be removed once its successor is ready.
+    pub pending_removal: bool,
+}
+
 // The on-disk replica configuration does not match the in-memory replica configuration, so we need
 // separate structs. As of writing this comment, it is mainly due to the fact that we don't persist
 // the replica allocation.
@@ -405,6 +426,37 @@ impl DurableType<ItemKey, ItemValue> for Item {
     }
 }
 
+/// An item that has been dropped, but whose definition is retained so that it
+/// can be restored until its restore window ends at `expires_at`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DroppedItem {
+    pub item: Item,
+    pub dropped_at: u64,
+    pub expires_at: u64,
+}
+
+impl DurableType<DroppedItemKey, DroppedItemValue> for DroppedItem {
+    fn into_key_value(self) -> (DroppedItemKey, DroppedItemValue) {
+        let (key, value) = self.item.into_key_value();
+        (
+            DroppedItemKey { gid: key.gid },
+            DroppedItemValue {
+                item: value,
+                dropped_at: self.dropped_at,
+                expires_at: self.expires_at,
+            },
+        )
+    }
+
+    fn from_key_value(key: DroppedItemKey, value: DroppedItemValue) -> Self {
+        Self {
+            item: Item::from_key_value(ItemKey { gid: key.gid }, value.item),
+            dropped_at: value.dropped_at,
+            expires_at: value.expires_at,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialOrd, Ord, PartialEq, Eq)]
 pub struct SystemObjectDescription {
     pub schema_name: String,
@@ -678,6 +730,7 @@ pub struct Snapshot {
     pub schemas: BTreeMap<proto::SchemaKey, proto::SchemaValue>,
     pub roles: BTreeMap<proto::RoleKey, proto::RoleValue>,
     pub items: BTreeMap<proto::ItemKey, proto::ItemValue>,
+    pub dropped_items: BTreeMap<proto::DroppedItemKey, proto::DroppedItemValue>,
     pub comments: BTreeMap<proto::CommentKey, proto::CommentValue>,
     pub clusters: BTreeMap<proto::ClusterKey, proto::ClusterValue>,
     pub cluster_replicas: BTreeMap<proto::ClusterReplicaKey, proto::ClusterReplicaValue>,
@@ -703,6 +756,7 @@ impl Snapshot {
             schemas: BTreeMap::new(),
             roles: BTreeMap::new(),
             items: BTreeMap::new(),
+            dropped_items: BTreeMap::new(),
             comments: BTreeMap::new(),
             clusters: BTreeMap::new(),
             cluster_replicas: BTreeMap::new(),
@@ -718,12 +772,46 @@ impl Snapshot {
         }
     }
 
+    /// Returns the incarnation of cluster replica `id`, if the replica exists.
+    pub fn replica_incarnation(
+        &self,
+        id: ReplicaId,
+    ) -> Result<Option<ReplicaIncarnation>, TryFromProtoError> {
+        let key = proto::ClusterReplicaKey {
+            id: Some(id.into_proto()),
+        };
+        self.cluster_replicas
+            .get(&key)
+            .map(|value| {
+                value
+                    .incarnation
+                    .clone()
+                    .into_rust_if_some("ClusterReplicaValue::incarnation")
+            })
+            .transpose()
+    }
+
+    /// Returns the cluster replicas that are pending removal.
+    pub fn replicas_pending_removal(&self) -> Result<Vec<ReplicaId>, TryFromProtoError> {
+        self.cluster_replicas
+            .iter()
+            .filter(|(_key, value)| {
+                value
+                    .incarnation
+                    .as_ref()
+                    .map_or(false, |incarnation| incarnation.pending_removal)
+            })
+            .map(|(key, _value)| key.id.clone().into_rust_if_some("ClusterReplicaKey::id"))
+            .collect()
+    }
+
     pub fn is_empty(&self) -> bool {
         let Snapshot {
             databases,
             schemas,
             roles,
             items,
+            dropped_items,
             comments,
             clusters,
             cluster_replicas,
@@ -741,6 +829,7 @@ impl Snapshot {
             && schemas.is_empty()
             && roles.is_empty()
             && items.is_empty()
+            && dropped_items.is_empty()
             && comments.is_empty()
             && clusters.is_empty()
             && cluster_replicas.is_empty()
@@ -824,6 +913,7 @@ pub struct ClusterReplicaValue {
     pub(crate) name: String,
     pub(crate) config: ReplicaConfig,
     pub(crate) owner_id: RoleId,
+    pub(crate) incarnation: ReplicaIncarnation,
 }
 
 #[derive(Clone, Copy, Debug, PartialOrd, PartialEq, Eq, Ord, Hash, Arbitrary)]
@@ -891,6 +981,18 @@ impl ItemValue {
     }
 }
 
+#[derive(Clone, PartialOrd, PartialEq, Eq, Ord, Hash, Debug, Arbitrary)]
+pub struct DroppedItemKey {
+    pub(crate) gid: GlobalId,
+}
+
+#[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Ord, Arbitrary)]
+pub struct DroppedItemValue {
+    pub(crate) item: ItemValue,
+    pub(crate) dropped_at: u64,
+    pub(crate) expires_at: u64,
+}
+
 #[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Ord)]
 pub struct CommentKey {
     pub(crate) object_id: CommentObjectId,
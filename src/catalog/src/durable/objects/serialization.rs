@@ -28,7 +28,7 @@ use mz_sql::catalog::{CatalogItemType, ObjectType, RoleAttributes, RoleMembershi
 use mz_sql::names::{
     CommentObjectId, DatabaseId, ResolvedDatabaseSpecifier, SchemaId, SchemaSpecifier,
 };
-use mz_sql::session::vars::OwnedVarInput;
+use mz_sql::session::vars::{IsolationLevel, OwnedVarInput};
 use mz_storage_types::instances::StorageInstanceId;
 use std::time::Duration;
 
@@ -37,13 +37,14 @@ use crate::durable::objects::{
     AuditLogKey, ClusterIntrospectionSourceIndexKey, ClusterIntrospectionSourceIndexValue,
     ClusterKey, ClusterReplicaKey, ClusterReplicaValue, ClusterValue, CommentKey, CommentValue,
     ConfigKey, ConfigValue, DatabaseKey, DatabaseValue, DefaultPrivilegesKey,
-    DefaultPrivilegesValue, GidMappingKey, GidMappingValue, IdAllocKey, IdAllocValue, ItemKey,
-    ItemValue, RoleKey, RoleValue, SchemaKey, SchemaValue, ServerConfigurationKey,
-    ServerConfigurationValue, SettingKey, SettingValue, StorageUsageKey, SystemPrivilegesKey,
-    SystemPrivilegesValue, TimestampKey, TimestampValue,
+    DefaultPrivilegesValue, DroppedItemKey, DroppedItemValue, GidMappingKey, GidMappingValue,
+    IdAllocKey, IdAllocValue, ItemKey, ItemValue, RoleKey, RoleValue, SchemaKey, SchemaValue,
+    ServerConfigurationKey, ServerConfigurationValue, SettingKey, SettingValue, StorageUsageKey,
+    SystemPrivilegesKey, SystemPrivilegesValue, TimestampKey, TimestampValue,
 };
 use crate::durable::{
-    ClusterConfig, ClusterVariant, ClusterVariantManaged, ReplicaConfig, ReplicaLocation,
+    ClusterConfig, ClusterVariant, ClusterVariantManaged, ReplicaConfig, ReplicaIncarnation,
+    ReplicaLocation,
 };
 
 pub mod proto {
@@ -68,12 +69,23 @@ impl RustType<proto::ClusterConfig> for ClusterConfig {
     fn into_proto(&self) -> proto::ClusterConfig {
         proto::ClusterConfig {
             variant: Some(self.variant.into_proto()),
+            transaction_isolation: self
+                .transaction_isolation
+                .map(|level| level.as_str().to_string()),
         }
     }
 
     fn from_proto(proto: proto::ClusterConfig) -> Result<Self, TryFromProtoError> {
         Ok(Self {
             variant: proto.variant.into_rust_if_some("ClusterConfig::variant")?,
+            transaction_isolation: proto
+                .transaction_isolation
+                .map(|level| {
+                    IsolationLevel::parse_str(&level).map_err(|_| {
+                        TryFromProtoError::unknown_enum_variant(format!("IsolationLevel::{level}"))
+                    })
+                })
+                .transpose()?,
         })
     }
 }
@@ -144,6 +156,24 @@ impl RustType<proto::ReplicaConfig> for ReplicaConfig {
     }
 }
 
+impl RustType<proto::ReplicaIncarnation> for ReplicaIncarnation {
+    fn into_proto(&self) -> proto::ReplicaIncarnation {
+        proto::ReplicaIncarnation {
+            incarnation: self.incarnation,
+            config_version: self.config_version,
+            pending_removal: self.pending_removal,
+        }
+    }
+
+    fn from_proto(proto: proto::ReplicaIncarnation) -> Result<Self, TryFromProtoError> {
+        Ok(ReplicaIncarnation {
+            incarnation: proto.incarnation,
+            config_version: proto.config_version,
+            pending_removal: proto.pending_removal,
+        })
+    }
+}
+
 impl RustType<proto::replica_config::Location> for ReplicaLocation {
     fn into_proto(&self) -> proto::replica_config::Location {
         match self {
@@ -379,6 +409,7 @@ impl RustType<proto::ClusterReplicaValue> for ClusterReplicaValue {
             name: self.name.to_string(),
             config: Some(self.config.into_proto()),
             owner_id: Some(self.owner_id.into_proto()),
+            incarnation: Some(self.incarnation.into_proto()),
         }
     }
 
@@ -394,6 +425,9 @@ impl RustType<proto::ClusterReplicaValue> for ClusterReplicaValue {
             owner_id: proto
                 .owner_id
                 .into_rust_if_some("ClusterReplicaValue::owner_id")?,
+            incarnation: proto
+                .incarnation
+                .into_rust_if_some("ClusterReplicaValue::incarnation")?,
         })
     }
 }
@@ -517,6 +551,42 @@ impl RustType<proto::ItemValue> for ItemValue {
     }
 }
 
+impl RustType<proto::DroppedItemKey> for DroppedItemKey {
+    fn into_proto(&self) -> proto::DroppedItemKey {
+        proto::DroppedItemKey {
+            gid: Some(self.gid.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: proto::DroppedItemKey) -> Result<Self, TryFromProtoError> {
+        Ok(DroppedItemKey {
+            gid: proto.gid.into_rust_if_some("DroppedItemKey::gid")?,
+        })
+    }
+}
+
+impl RustType<proto::DroppedItemValue> for DroppedItemValue {
+    fn into_proto(&self) -> proto::DroppedItemValue {
+        proto::DroppedItemValue {
+            item: Some(self.item.into_proto()),
+            dropped_at: Some(self.dropped_at.into_proto()),
+            expires_at: Some(self.expires_at.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: proto::DroppedItemValue) -> Result<Self, TryFromProtoError> {
+        Ok(DroppedItemValue {
+            item: proto.item.into_rust_if_some("DroppedItemValue::item")?,
+            dropped_at: proto
+                .dropped_at
+                .into_rust_if_some("DroppedItemValue::dropped_at")?,
+            expires_at: proto
+                .expires_at
+                .into_rust_if_some("DroppedItemValue::expires_at")?,
+        })
+    }
+}
+
 impl RustType<proto::CommentKey> for CommentKey {
     fn into_proto(&self) -> proto::CommentKey {
         let sub_component = match &self.sub_component {
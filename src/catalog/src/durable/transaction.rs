@@ -29,11 +29,12 @@ use mz_stash::TableTransaction;
 use mz_storage_types::controller::PersistTxnTablesImpl;
 use mz_storage_types::sources::Timeline;
 use std::collections::{BTreeMap, BTreeSet};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::builtin::BuiltinLog;
 use crate::durable::initialize::{
-    CATALOG_KIND_KEY, PERSIST_TXN_TABLES, SYSTEM_CONFIG_SYNCED_KEY, TOMBSTONE_KEY,
+    CATALOG_KIND_KEY, PERSIST_TXN_TABLES, SHADOW_SOURCE_OF_TRUTH_KEY, SYSTEM_CONFIG_SYNCED_KEY,
+    TOMBSTONE_KEY,
 };
 use crate::durable::objects::serialization::proto;
 use crate::durable::objects::{
@@ -41,16 +42,16 @@ use crate::durable::objects::{
     ClusterIntrospectionSourceIndexValue, ClusterKey, ClusterReplica, ClusterReplicaKey,
     ClusterReplicaValue, ClusterValue, CommentKey, CommentValue, Config, ConfigKey, ConfigValue,
     Database, DatabaseKey, DatabaseValue, DefaultPrivilegesKey, DefaultPrivilegesValue,
-    DurableType, GidMappingKey, GidMappingValue, IdAllocKey, IdAllocValue,
-    IntrospectionSourceIndex, Item, ItemKey, ItemValue, ReplicaConfig, Role, RoleKey, RoleValue,
-    Schema, SchemaKey, SchemaValue, ServerConfigurationKey, ServerConfigurationValue, SettingKey,
-    SettingValue, StorageUsageKey, SystemObjectMapping, SystemPrivilegesKey, SystemPrivilegesValue,
-    TimestampKey, TimestampValue,
+    DroppedItem, DroppedItemKey, DroppedItemValue, DurableType, GidMappingKey, GidMappingValue,
+    IdAllocKey, IdAllocValue, IntrospectionSourceIndex, Item, ItemKey, ItemValue, ReplicaConfig,
+    ReplicaIncarnation, Role, RoleKey, RoleValue, Schema, SchemaKey, SchemaValue,
+    ServerConfigurationKey, ServerConfigurationValue, SettingKey, SettingValue, StorageUsageKey,
+    SystemObjectMapping, SystemPrivilegesKey, SystemPrivilegesValue, TimestampKey, TimestampValue,
 };
 use crate::durable::{
-    CatalogError, Comment, DefaultPrivilege, DurableCatalogState, Snapshot, SystemConfiguration,
-    TimelineTimestamp, CATALOG_CONTENT_VERSION_KEY, DATABASE_ID_ALLOC_KEY, SCHEMA_ID_ALLOC_KEY,
-    SYSTEM_ITEM_ALLOC_KEY, USER_ITEM_ALLOC_KEY, USER_ROLE_ID_ALLOC_KEY,
+    CatalogError, Comment, DefaultPrivilege, DurableCatalogState, ShadowSourceOfTruth, Snapshot,
+    SystemConfiguration, TimelineTimestamp, CATALOG_CONTENT_VERSION_KEY, DATABASE_ID_ALLOC_KEY,
+    SCHEMA_ID_ALLOC_KEY, SYSTEM_ITEM_ALLOC_KEY, USER_ITEM_ALLOC_KEY, USER_ROLE_ID_ALLOC_KEY,
 };
 
 /// A [`Transaction`] batches multiple catalog operations together and commits them atomically.
@@ -63,6 +64,7 @@ pub struct Transaction<'a> {
     databases: TableTransaction<DatabaseKey, DatabaseValue>,
     schemas: TableTransaction<SchemaKey, SchemaValue>,
     items: TableTransaction<ItemKey, ItemValue>,
+    dropped_items: TableTransaction<DroppedItemKey, DroppedItemValue>,
     comments: TableTransaction<CommentKey, CommentValue>,
     roles: TableTransaction<RoleKey, RoleValue>,
     clusters: TableTransaction<ClusterKey, ClusterValue>,
@@ -92,6 +94,7 @@ impl<'a> Transaction<'a> {
             schemas,
             roles,
             items,
+            dropped_items,
             comments,
             clusters,
             cluster_replicas,
@@ -121,6 +124,7 @@ impl<'a> Transaction<'a> {
                         || (a_type == CatalogItemType::Type && b_type.conflicts_with_type())
                         || (b_type == CatalogItemType::Type && a_type.conflicts_with_type()))
             })?,
+            dropped_items: TableTransaction::new(dropped_items, |_a, _b| false)?,
             comments: TableTransaction::new(comments, |_a, _b| false)?,
             roles: TableTransaction::new(roles, |a: &RoleValue, b| a.name == b.name)?,
             clusters: TableTransaction::new(clusters, |a: &ClusterValue, b| a.name == b.name)?,
@@ -449,6 +453,27 @@ impl<'a> Transaction<'a> {
         replica_name: &str,
         config: ReplicaConfig,
         owner_id: RoleId,
+    ) -> Result<(), CatalogError> {
+        self.insert_cluster_replica_with_incarnation(
+            cluster_id,
+            replica_id,
+            replica_name,
+            config,
+            owner_id,
+            0,
+        )
+    }
+
+    /// Inserts cluster replica `replica_id` as incarnation `incarnation`, i.e. as the successor
+    /// of `incarnation` earlier replicas.
+    pub fn insert_cluster_replica_with_incarnation(
+        &mut self,
+        cluster_id: ClusterId,
+        replica_id: ReplicaId,
+        replica_name: &str,
+        config: ReplicaConfig,
+        owner_id: RoleId,
+        incarnation: u64,
     ) -> Result<(), CatalogError> {
         if let Err(_) = self.cluster_replicas.insert(
             ClusterReplicaKey { id: replica_id },
@@ -457,6 +482,10 @@ impl<'a> Transaction<'a> {
                 name: replica_name.into(),
                 config,
                 owner_id,
+                incarnation: ReplicaIncarnation {
+                    incarnation,
+                    ..Default::default()
+                },
             },
         ) {
             let cluster = self
@@ -710,6 +739,49 @@ impl<'a> Transaction<'a> {
         }
     }
 
+    /// Moves item `id` out of the live items and into the dropped items, recording that it was
+    /// dropped at `dropped_at` and stays restorable until `expires_at`.
+    ///
+    /// Returns an error if `id` is not found.
+    pub fn soft_remove_item(
+        &mut self,
+        id: GlobalId,
+        dropped_at: u64,
+        expires_at: u64,
+    ) -> Result<(), CatalogError> {
+        let Some(item) = self.items.set(ItemKey { gid: id }, None)? else {
+            return Err(SqlCatalogError::UnknownItem(id.to_string()).into());
+        };
+        self.dropped_items.set(
+            DroppedItemKey { gid: id },
+            Some(DroppedItemValue {
+                item,
+                dropped_at,
+                expires_at,
+            }),
+        )?;
+        Ok(())
+    }
+
+    /// Removes dropped item `id` from the transaction, returning it if it existed.
+    pub fn remove_dropped_item(
+        &mut self,
+        id: GlobalId,
+    ) -> Result<Option<DroppedItem>, CatalogError> {
+        let key = DroppedItemKey { gid: id };
+        let prev = self.dropped_items.set(key.clone(), None)?;
+        Ok(prev.map(|value| DroppedItem::from_key_value(key, value)))
+    }
+
+    /// Removes all dropped items in `ids` from the transaction, returning the ones that existed.
+    pub fn remove_dropped_items(&mut self, ids: &BTreeSet<GlobalId>) -> Vec<DroppedItem> {
+        self.dropped_items
+            .delete(|k, _v| ids.contains(&k.gid))
+            .into_iter()
+            .map(|(k, v)| DroppedItem::from_key_value(k, v))
+            .collect()
+    }
+
     /// Updates item `id` in the transaction to `item_name` and `item`.
     ///
     /// Returns an error if `id` is not found.
@@ -865,6 +937,49 @@ impl<'a> Transaction<'a> {
         }
     }
 
+    /// Updates the configuration of cluster replica `replica_id` in the transaction to `config`,
+    /// returning its new config version.
+    ///
+    /// Returns an error if `replica_id` is not found.
+    pub fn update_replica_config(
+        &mut self,
+        replica_id: ReplicaId,
+        config: ReplicaConfig,
+    ) -> Result<u64, CatalogError> {
+        let mut value = self.get_cluster_replica_value(replica_id)?;
+        value.config = config;
+        value.incarnation.config_version += 1;
+        let config_version = value.incarnation.config_version;
+        self.cluster_replicas
+            .set(ClusterReplicaKey { id: replica_id }, Some(value))?;
+        Ok(config_version)
+    }
+
+    /// Marks cluster replica `replica_id` in the transaction as pending removal, i.e. to be
+    /// removed once its successor is ready.
+    ///
+    /// Returns an error if `replica_id` is not found.
+    pub fn mark_replica_pending_removal(
+        &mut self,
+        replica_id: ReplicaId,
+    ) -> Result<(), CatalogError> {
+        let mut value = self.get_cluster_replica_value(replica_id)?;
+        value.incarnation.pending_removal = true;
+        self.cluster_replicas
+            .set(ClusterReplicaKey { id: replica_id }, Some(value))?;
+        Ok(())
+    }
+
+    fn get_cluster_replica_value(
+        &self,
+        replica_id: ReplicaId,
+    ) -> Result<ClusterReplicaValue, CatalogError> {
+        self.cluster_replicas
+            .get(&ClusterReplicaKey { id: replica_id })
+            .cloned()
+            .ok_or_else(|| SqlCatalogError::UnknownClusterReplica(replica_id.to_string()).into())
+    }
+
     /// Updates database `id` in the transaction to `database`.
     ///
     /// Returns an error if `id` is not found.
@@ -1112,6 +1227,14 @@ impl<'a> Transaction<'a> {
         Ok(())
     }
 
+    /// Updates the shadow catalog `shadow_source_of_truth` "config" value.
+    pub(crate) fn set_shadow_source_of_truth(
+        &mut self,
+        value: ShadowSourceOfTruth,
+    ) -> Result<(), CatalogError> {
+        self.set_config(SHADOW_SOURCE_OF_TRUTH_KEY.into(), Some(value.into()))
+    }
+
     pub fn update_comment(
         &mut self,
         object_id: CommentObjectId,
@@ -1283,6 +1406,14 @@ impl<'a> Transaction<'a> {
             .map(|value| value.value.clone())
     }
 
+    pub fn get_dropped_items(&self) -> impl Iterator<Item = DroppedItem> {
+        self.dropped_items
+            .items()
+            .clone()
+            .into_iter()
+            .map(|(k, v)| DurableType::from_key_value(k, v))
+    }
+
     pub fn set_connection_timeout(&mut self, timeout: Duration) {
         self.connection_timeout = Some(timeout);
     }
@@ -1298,6 +1429,7 @@ impl<'a> Transaction<'a> {
             databases,
             schemas,
             items,
+            dropped_items,
             comments,
             roles,
             clusters,
@@ -1318,6 +1450,7 @@ impl<'a> Transaction<'a> {
         databases.clear();
         schemas.clear();
         items.clear();
+        dropped_items.clear();
         comments.clear();
         roles.clear();
         clusters.clear();
@@ -1355,6 +1488,7 @@ impl<'a> Transaction<'a> {
             schemas,
             roles,
             items,
+            dropped_items,
             comments,
             clusters,
             cluster_replicas,
@@ -1400,6 +1534,7 @@ impl<'a> Transaction<'a> {
         set_collection(&mut self.schemas, schemas)?;
         set_collection(&mut self.roles, roles)?;
         set_collection(&mut self.items, items)?;
+        set_collection(&mut self.dropped_items, dropped_items)?;
         set_collection(&mut self.comments, comments)?;
         set_collection(&mut self.clusters, clusters)?;
         set_collection(&mut self.cluster_replicas, cluster_replicas)?;
@@ -1424,6 +1559,7 @@ impl<'a> Transaction<'a> {
             databases: self.databases.pending(),
             schemas: self.schemas.pending(),
             items: self.items.pending(),
+            dropped_items: self.dropped_items.pending(),
             comments: self.comments.pending(),
             roles: self.roles.pending(),
             clusters: self.clusters.pending(),
@@ -1450,11 +1586,27 @@ impl<'a> Transaction<'a> {
     /// that errors can bubble up during initialization.
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn commit(self) -> Result<(), CatalogError> {
+        self.commit_inner(None).await
+    }
+
+    /// Like [`Transaction::commit`], but abandons the commit if it does not complete before
+    /// `deadline`, returning [`CommitError::Timeout`](crate::durable::CommitError::Timeout).
+    ///
+    /// Unlike other errors, a timeout leaves the catalog usable: whether the abandoned commit was
+    /// applied is determined by the next commit, which fails with an unrecoverable error if it
+    /// was.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn commit_with_deadline(self, deadline: Instant) -> Result<(), CatalogError> {
+        self.commit_inner(Some(deadline)).await
+    }
+
+    async fn commit_inner(self, deadline: Option<Instant>) -> Result<(), CatalogError> {
         let (mut txn_batch, durable_catalog) = self.into_parts();
         let TransactionBatch {
             databases,
             schemas,
             items,
+            dropped_items,
             comments,
             roles,
             clusters,
@@ -1477,6 +1629,7 @@ impl<'a> Transaction<'a> {
         differential_dataflow::consolidation::consolidate_updates(databases);
         differential_dataflow::consolidation::consolidate_updates(schemas);
         differential_dataflow::consolidation::consolidate_updates(items);
+        differential_dataflow::consolidation::consolidate_updates(dropped_items);
         differential_dataflow::consolidation::consolidate_updates(comments);
         differential_dataflow::consolidation::consolidate_updates(roles);
         differential_dataflow::consolidation::consolidate_updates(clusters);
@@ -1492,7 +1645,9 @@ impl<'a> Transaction<'a> {
         differential_dataflow::consolidation::consolidate_updates(system_privileges);
         differential_dataflow::consolidation::consolidate_updates(audit_log_updates);
         differential_dataflow::consolidation::consolidate_updates(storage_usage_updates);
-        durable_catalog.commit_transaction(txn_batch).await
+        durable_catalog
+            .commit_transaction(txn_batch, deadline)
+            .await
     }
 }
 
@@ -1502,6 +1657,7 @@ pub struct TransactionBatch {
     pub(crate) databases: Vec<(proto::DatabaseKey, proto::DatabaseValue, Diff)>,
     pub(crate) schemas: Vec<(proto::SchemaKey, proto::SchemaValue, Diff)>,
     pub(crate) items: Vec<(proto::ItemKey, proto::ItemValue, Diff)>,
+    pub(crate) dropped_items: Vec<(proto::DroppedItemKey, proto::DroppedItemValue, Diff)>,
     pub(crate) comments: Vec<(proto::CommentKey, proto::CommentValue, Diff)>,
     pub(crate) roles: Vec<(proto::RoleKey, proto::RoleValue, Diff)>,
     pub(crate) clusters: Vec<(proto::ClusterKey, proto::ClusterValue, Diff)>,
@@ -1542,6 +1698,7 @@ impl TransactionBatch {
             databases,
             schemas,
             items,
+            dropped_items,
             comments,
             roles,
             clusters,
@@ -1564,6 +1721,7 @@ impl TransactionBatch {
         databases.is_empty()
             && schemas.is_empty()
             && items.is_empty()
+            && dropped_items.is_empty()
             && comments.is_empty()
             && roles.is_empty()
             && clusters.is_empty()
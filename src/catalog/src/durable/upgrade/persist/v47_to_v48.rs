@@ -0,0 +1,18 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use crate::durable::upgrade::persist::MigrationAction;
+use crate::durable::upgrade::{objects_v47 as v47, objects_v48 as v48};
+
+/// No-op migration. The `transaction_isolation` field added to `ClusterConfig` is optional.
+pub fn upgrade(
+    _snapshot: Vec<v47::StateUpdateKind>,
+) -> Vec<MigrationAction<v47::StateUpdateKind, v48::StateUpdateKind>> {
+    Vec::new()
+}
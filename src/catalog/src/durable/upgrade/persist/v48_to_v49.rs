@@ -0,0 +1,233 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use mz_stash::upgrade::WireCompatible;
+
+use crate::durable::upgrade::persist::MigrationAction;
+use crate::durable::upgrade::{objects_v48 as v48, objects_v49 as v49};
+
+/// Add `ClusterReplicaValue`'s `incarnation` field and `DroppedItemValue`'s `expires_at` field.
+/// Existing replicas are in their first incarnation, at their first config version, and not
+/// pending removal. The collections of items dropped before `expires_at` existed were not
+/// retained across restarts, so their restore window ends when they were dropped.
+pub fn upgrade(
+    snapshot: Vec<v48::StateUpdateKind>,
+) -> Vec<MigrationAction<v48::StateUpdateKind, v49::StateUpdateKind>> {
+    snapshot
+        .into_iter()
+        .filter_map(
+            |update| match update.kind.as_ref().expect("missing field") {
+                v48::state_update_kind::Kind::ClusterReplica(replica) => {
+                    Some(upgrade_cluster_replica(replica))
+                }
+                v48::state_update_kind::Kind::DroppedItem(dropped_item) => {
+                    Some(upgrade_dropped_item(dropped_item))
+                }
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+fn upgrade_cluster_replica(
+    v48::state_update_kind::ClusterReplica { key, value }: &v48::state_update_kind::ClusterReplica,
+) -> MigrationAction<v48::StateUpdateKind, v49::StateUpdateKind> {
+    let current = v48::StateUpdateKind {
+        kind: Some(v48::state_update_kind::Kind::ClusterReplica(
+            v48::state_update_kind::ClusterReplica {
+                key: key.clone(),
+                value: value.clone(),
+            },
+        )),
+    };
+
+    let new = v49::StateUpdateKind {
+        kind: Some(v49::state_update_kind::Kind::ClusterReplica(
+            v49::state_update_kind::ClusterReplica {
+                key: key.as_ref().map(WireCompatible::convert),
+                value: value.as_ref().map(
+                    |v48::ClusterReplicaValue {
+                         cluster_id,
+                         name,
+                         config,
+                         owner_id,
+                     }| v49::ClusterReplicaValue {
+                        cluster_id: cluster_id.as_ref().map(WireCompatible::convert),
+                        name: name.clone(),
+                        config: config.as_ref().map(WireCompatible::convert),
+                        owner_id: owner_id.as_ref().map(WireCompatible::convert),
+                        incarnation: Some(v49::ReplicaIncarnation {
+                            incarnation: 0,
+                            config_version: 0,
+                            pending_removal: false,
+                        }),
+                    },
+                ),
+            },
+        )),
+    };
+
+    MigrationAction::Update(current, new)
+}
+
+fn upgrade_dropped_item(
+    v48::state_update_kind::DroppedItem { key, value }: &v48::state_update_kind::DroppedItem,
+) -> MigrationAction<v48::StateUpdateKind, v49::StateUpdateKind> {
+    let current = v48::StateUpdateKind {
+        kind: Some(v48::state_update_kind::Kind::DroppedItem(
+            v48::state_update_kind::DroppedItem {
+                key: key.clone(),
+                value: value.clone(),
+            },
+        )),
+    };
+
+    let new = v49::StateUpdateKind {
+        kind: Some(v49::state_update_kind::Kind::DroppedItem(
+            v49::state_update_kind::DroppedItem {
+                key: key.as_ref().map(WireCompatible::convert),
+                value: value
+                    .as_ref()
+                    .map(
+                        |v48::DroppedItemValue { item, dropped_at }| v49::DroppedItemValue {
+                            item: item.as_ref().map(WireCompatible::convert),
+                            dropped_at: dropped_at.as_ref().map(WireCompatible::convert),
+                            expires_at: dropped_at.as_ref().map(WireCompatible::convert),
+                        },
+                    ),
+            },
+        )),
+    };
+
+    MigrationAction::Update(current, new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+    async fn smoke_test_migration() {
+        let v48 = v48::StateUpdateKind {
+            kind: Some(v48::state_update_kind::Kind::ClusterReplica(
+                v48::state_update_kind::ClusterReplica {
+                    key: Some(v48::ClusterReplicaKey {
+                        id: Some(v48::ReplicaId {
+                            value: Some(v48::replica_id::Value::User(Default::default())),
+                        }),
+                    }),
+                    value: Some(v48::ClusterReplicaValue {
+                        cluster_id: Some(v48::ClusterId {
+                            value: Some(v48::cluster_id::Value::User(Default::default())),
+                        }),
+                        name: "r1".to_string(),
+                        config: Some(v48::ReplicaConfig {
+                            location: None,
+                            logging: None,
+                            idle_arrangement_merge_effort: None,
+                        }),
+                        owner_id: Some(v48::RoleId {
+                            value: Some(v48::role_id::Value::Public(Default::default())),
+                        }),
+                    }),
+                },
+            )),
+        };
+
+        let v49 = v49::StateUpdateKind {
+            kind: Some(v49::state_update_kind::Kind::ClusterReplica(
+                v49::state_update_kind::ClusterReplica {
+                    key: Some(v49::ClusterReplicaKey {
+                        id: Some(v49::ReplicaId {
+                            value: Some(v49::replica_id::Value::User(Default::default())),
+                        }),
+                    }),
+                    value: Some(v49::ClusterReplicaValue {
+                        cluster_id: Some(v49::ClusterId {
+                            value: Some(v49::cluster_id::Value::User(Default::default())),
+                        }),
+                        name: "r1".to_string(),
+                        config: Some(v49::ReplicaConfig {
+                            location: None,
+                            logging: None,
+                            idle_arrangement_merge_effort: None,
+                        }),
+                        owner_id: Some(v49::RoleId {
+                            value: Some(v49::role_id::Value::Public(Default::default())),
+                        }),
+                        incarnation: Some(v49::ReplicaIncarnation {
+                            incarnation: 0,
+                            config_version: 0,
+                            pending_removal: false,
+                        }),
+                    }),
+                },
+            )),
+        };
+
+        let dropped_item_v48 = v48::StateUpdateKind {
+            kind: Some(v48::state_update_kind::Kind::DroppedItem(
+                v48::state_update_kind::DroppedItem {
+                    key: Some(v48::DroppedItemKey {
+                        gid: Some(v48::GlobalId {
+                            value: Some(v48::global_id::Value::User(1)),
+                        }),
+                    }),
+                    value: Some(v48::DroppedItemValue {
+                        item: Some(v48::ItemValue {
+                            schema_id: None,
+                            name: "mv".to_string(),
+                            definition: None,
+                            owner_id: None,
+                            privileges: Vec::new(),
+                        }),
+                        dropped_at: Some(v48::EpochMillis { millis: 42 }),
+                    }),
+                },
+            )),
+        };
+
+        let dropped_item_v49 = v49::StateUpdateKind {
+            kind: Some(v49::state_update_kind::Kind::DroppedItem(
+                v49::state_update_kind::DroppedItem {
+                    key: Some(v49::DroppedItemKey {
+                        gid: Some(v49::GlobalId {
+                            value: Some(v49::global_id::Value::User(1)),
+                        }),
+                    }),
+                    value: Some(v49::DroppedItemValue {
+                        item: Some(v49::ItemValue {
+                            schema_id: None,
+                            name: "mv".to_string(),
+                            definition: None,
+                            owner_id: None,
+                            privileges: Vec::new(),
+                        }),
+                        dropped_at: Some(v49::EpochMillis { millis: 42 }),
+                        expires_at: Some(v49::EpochMillis { millis: 42 }),
+                    }),
+                },
+            )),
+        };
+
+        let actions = upgrade(vec![v48.clone(), dropped_item_v48.clone()]);
+
+        match &actions[..] {
+            [MigrationAction::Update(old_replica, new_replica), MigrationAction::Update(old_dropped_item, new_dropped_item)] =>
+            {
+                assert_eq!(old_replica, &v48);
+                assert_eq!(new_replica, &v49);
+                assert_eq!(old_dropped_item, &dropped_item_v48);
+                assert_eq!(new_dropped_item, &dropped_item_v49);
+            }
+            o => panic!("expected two MigrationAction::Updates, got {:?}", o),
+        }
+    }
+}
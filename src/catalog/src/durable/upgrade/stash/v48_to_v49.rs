@@ -0,0 +1,252 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use mz_stash::upgrade::{wire_compatible, MigrationAction, WireCompatible};
+use mz_stash::{Transaction, TypedCollection};
+use mz_stash_types::StashError;
+
+use crate::durable::upgrade::{objects_v48 as v48, objects_v49 as v49};
+
+wire_compatible!(v48::ClusterId with v49::ClusterId);
+wire_compatible!(v48::ClusterReplicaKey with v49::ClusterReplicaKey);
+wire_compatible!(v48::DroppedItemKey with v49::DroppedItemKey);
+wire_compatible!(v48::EpochMillis with v49::EpochMillis);
+wire_compatible!(v48::ItemValue with v49::ItemValue);
+wire_compatible!(v48::ReplicaConfig with v49::ReplicaConfig);
+wire_compatible!(v48::RoleId with v49::RoleId);
+
+const CLUSTER_REPLICA_COLLECTION: TypedCollection<
+    v48::ClusterReplicaKey,
+    v48::ClusterReplicaValue,
+> = TypedCollection::new("compute_replicas");
+
+const DROPPED_ITEMS_COLLECTION: TypedCollection<v48::DroppedItemKey, v48::DroppedItemValue> =
+    TypedCollection::new("dropped_item");
+
+/// Add `ClusterReplicaValue`'s `incarnation` field and `DroppedItemValue`'s `expires_at` field.
+/// Existing replicas are in their first incarnation, at their first config version, and not
+/// pending removal. The collections of items dropped before `expires_at` existed were not
+/// retained across restarts, so their restore window ends when they were dropped.
+pub async fn upgrade(tx: &Transaction<'_>) -> Result<(), StashError> {
+    CLUSTER_REPLICA_COLLECTION
+        .migrate_to::<v49::ClusterReplicaKey, v49::ClusterReplicaValue>(tx, |entries| {
+            entries
+                .iter()
+                .map(|(replica_key, replica_value)| {
+                    MigrationAction::Update(
+                        replica_key.clone(),
+                        (
+                            WireCompatible::convert(replica_key),
+                            upgrade_replica_value(replica_value),
+                        ),
+                    )
+                })
+                .collect()
+        })
+        .await?;
+    DROPPED_ITEMS_COLLECTION
+        .migrate_to::<v49::DroppedItemKey, v49::DroppedItemValue>(tx, |entries| {
+            entries
+                .iter()
+                .map(|(key, value)| {
+                    MigrationAction::Update(
+                        key.clone(),
+                        (
+                            WireCompatible::convert(key),
+                            upgrade_dropped_item_value(value),
+                        ),
+                    )
+                })
+                .collect()
+        })
+        .await?;
+    Ok(())
+}
+
+fn upgrade_replica_value(
+    v48::ClusterReplicaValue {
+        cluster_id,
+        name,
+        config,
+        owner_id,
+    }: &v48::ClusterReplicaValue,
+) -> v49::ClusterReplicaValue {
+    v49::ClusterReplicaValue {
+        cluster_id: cluster_id.as_ref().map(WireCompatible::convert),
+        name: name.clone(),
+        config: config.as_ref().map(WireCompatible::convert),
+        owner_id: owner_id.as_ref().map(WireCompatible::convert),
+        incarnation: Some(v49::ReplicaIncarnation {
+            incarnation: 0,
+            config_version: 0,
+            pending_removal: false,
+        }),
+    }
+}
+
+fn upgrade_dropped_item_value(
+    v48::DroppedItemValue { item, dropped_at }: &v48::DroppedItemValue,
+) -> v49::DroppedItemValue {
+    v49::DroppedItemValue {
+        item: item.as_ref().map(WireCompatible::convert),
+        dropped_at: dropped_at.as_ref().map(WireCompatible::convert),
+        expires_at: dropped_at.as_ref().map(WireCompatible::convert),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mz_stash::Stash;
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+    async fn smoke_test_migration() {
+        const CLUSTER_REPLICA_COLLECTION_V49: TypedCollection<
+            v49::ClusterReplicaKey,
+            v49::ClusterReplicaValue,
+        > = TypedCollection::new("compute_replicas");
+        const DROPPED_ITEMS_COLLECTION_V49: TypedCollection<
+            v49::DroppedItemKey,
+            v49::DroppedItemValue,
+        > = TypedCollection::new("dropped_item");
+
+        let replica_key_v48 = v48::ClusterReplicaKey {
+            id: Some(v48::ReplicaId {
+                value: Some(v48::replica_id::Value::User(Default::default())),
+            }),
+        };
+
+        let replica_value_v48 = v48::ClusterReplicaValue {
+            cluster_id: Some(v48::ClusterId {
+                value: Some(v48::cluster_id::Value::User(Default::default())),
+            }),
+            name: "r1".to_string(),
+            config: Some(v48::ReplicaConfig {
+                location: None,
+                logging: None,
+                idle_arrangement_merge_effort: None,
+            }),
+            owner_id: Some(v48::RoleId {
+                value: Some(v48::role_id::Value::Public(Default::default())),
+            }),
+        };
+
+        let replica_key_v49 = v49::ClusterReplicaKey {
+            id: Some(v49::ReplicaId {
+                value: Some(v49::replica_id::Value::User(Default::default())),
+            }),
+        };
+
+        let replica_value_v49 = v49::ClusterReplicaValue {
+            cluster_id: Some(v49::ClusterId {
+                value: Some(v49::cluster_id::Value::User(Default::default())),
+            }),
+            name: "r1".to_string(),
+            config: Some(v49::ReplicaConfig {
+                location: None,
+                logging: None,
+                idle_arrangement_merge_effort: None,
+            }),
+            owner_id: Some(v49::RoleId {
+                value: Some(v49::role_id::Value::Public(Default::default())),
+            }),
+            incarnation: Some(v49::ReplicaIncarnation {
+                incarnation: 0,
+                config_version: 0,
+                pending_removal: false,
+            }),
+        };
+
+        let dropped_item_key_v48 = v48::DroppedItemKey {
+            gid: Some(v48::GlobalId {
+                value: Some(v48::global_id::Value::User(1)),
+            }),
+        };
+        let dropped_item_value_v48 = v48::DroppedItemValue {
+            item: Some(v48::ItemValue {
+                schema_id: None,
+                name: "mv".to_string(),
+                definition: None,
+                owner_id: None,
+                privileges: Vec::new(),
+            }),
+            dropped_at: Some(v48::EpochMillis { millis: 42 }),
+        };
+
+        let dropped_item_key_v49 = v49::DroppedItemKey {
+            gid: Some(v49::GlobalId {
+                value: Some(v49::global_id::Value::User(1)),
+            }),
+        };
+        let dropped_item_value_v49 = v49::DroppedItemValue {
+            item: Some(v49::ItemValue {
+                schema_id: None,
+                name: "mv".to_string(),
+                definition: None,
+                owner_id: None,
+                privileges: Vec::new(),
+            }),
+            dropped_at: Some(v49::EpochMillis { millis: 42 }),
+            expires_at: Some(v49::EpochMillis { millis: 42 }),
+        };
+
+        Stash::with_debug_stash(|mut stash: Stash| async move {
+            CLUSTER_REPLICA_COLLECTION
+                .insert_without_overwrite(
+                    &mut stash,
+                    [(replica_key_v48.clone(), replica_value_v48.clone())],
+                )
+                .await
+                .expect("insert success");
+            DROPPED_ITEMS_COLLECTION
+                .insert_without_overwrite(
+                    &mut stash,
+                    [(dropped_item_key_v48.clone(), dropped_item_value_v48.clone())],
+                )
+                .await
+                .expect("insert success");
+
+            // Run the migration.
+            stash
+                .with_transaction(|tx| {
+                    Box::pin(async move {
+                        upgrade(&tx).await?;
+                        Ok(())
+                    })
+                })
+                .await
+                .expect("transaction failed");
+
+            let replicas_v49: Vec<_> = CLUSTER_REPLICA_COLLECTION_V49
+                .peek_one(&mut stash)
+                .await
+                .expect("read v49")
+                .into_iter()
+                .collect();
+            assert_eq!(
+                replicas_v49.as_slice(),
+                [(replica_key_v49, replica_value_v49)]
+            );
+
+            let dropped_items_v49: Vec<_> = DROPPED_ITEMS_COLLECTION_V49
+                .peek_one(&mut stash)
+                .await
+                .expect("read v49")
+                .into_iter()
+                .collect();
+            assert_eq!(
+                dropped_items_v49.as_slice(),
+                [(dropped_item_key_v49, dropped_item_value_v49)]
+            );
+        })
+        .await
+        .expect("stash failed");
+    }
+}
@@ -125,6 +125,7 @@ impl Error {
                 )
             ),
             ErrorKind::VarError(e) => e.detail(),
+            ErrorKind::Durable(crate::durable::DurableCatalogError::Commit(e)) => e.detail(),
             _ => None,
         }
     }
@@ -133,6 +134,7 @@ impl Error {
     pub fn hint(&self) -> Option<String> {
         match &self.kind {
             ErrorKind::VarError(e) => e.hint(),
+            ErrorKind::Durable(crate::durable::DurableCatalogError::Commit(e)) => e.hint(),
             ErrorKind::InvalidClusterReplicaSize { expected, .. } => Some(format!(
                 "Valid cluster replica sizes are: {}",
                 expected.join(", ")
@@ -23,7 +23,7 @@ use timely::progress::Antichain;
 
 use mz_compute_client::logging::LogVariant;
 use mz_controller::clusters::{
-    ClusterRole, ClusterStatus, ProcessId, ReplicaConfig, ReplicaLogging,
+    ClusterEvent, ClusterRole, ClusterStatus, ProcessId, ReplicaConfig, ReplicaLogging,
 };
 use mz_controller_types::{ClusterId, ReplicaId};
 use mz_expr::refresh_schedule::RefreshSchedule;
@@ -49,9 +49,10 @@ use mz_sql::plan::{
     WebhookHeaders, WebhookValidation,
 };
 use mz_sql::rbac;
-use mz_sql::session::vars::OwnedVarInput;
+use mz_sql::session::vars::{IsolationLevel, OwnedVarInput};
 use mz_storage_client::controller::IntrospectionType;
 use mz_storage_types::connections::inline::ReferencedConnection;
+use mz_storage_types::parameters::SourceParameterOverrides;
 use mz_storage_types::sinks::{KafkaSinkFormat, SinkEnvelope, StorageSinkConnection};
 use mz_storage_types::sources::{
     IngestionDescription, SourceConnection, SourceDesc, SourceEnvelope, SourceExport, Timeline,
@@ -269,6 +270,8 @@ pub struct ClusterReplica {
     #[serde(skip)]
     pub process_status: BTreeMap<ProcessId, ClusterReplicaProcessStatus>,
     pub owner_id: RoleId,
+    #[serde(skip)]
+    pub incarnation: durable::ReplicaIncarnation,
 }
 
 impl ClusterReplica {
@@ -292,6 +295,12 @@ impl ClusterReplica {
                 }
             })
     }
+
+    /// Reports whether `event` changes the status of one of the replica's processes, rather than
+    /// repeating the status it already has.
+    pub fn is_status_change(&self, event: &ClusterEvent) -> bool {
+        event.status != self.process_status[&event.process_id].status
+    }
 }
 
 impl From<ClusterReplica> for durable::ClusterReplica {
@@ -302,6 +311,7 @@ impl From<ClusterReplica> for durable::ClusterReplica {
             name: replica.name,
             config: replica.config.into(),
             owner_id: replica.owner_id,
+            incarnation: replica.incarnation,
         }
     }
 }
@@ -449,6 +459,8 @@ pub struct Source {
     /// Whether the source's logical compaction window is controlled by
     /// METRICS_RETENTION
     pub is_retained_metrics_object: bool,
+    /// The tuning parameters the source overrides with the options in its `create_sql`.
+    pub parameter_overrides: SourceParameterOverrides,
 }
 
 impl Source {
@@ -509,6 +521,7 @@ impl Source {
                 .compaction_window
                 .or(custom_logical_compaction_window),
             is_retained_metrics_object,
+            parameter_overrides: plan.source.parameter_overrides,
         }
     }
 
@@ -1727,12 +1740,16 @@ impl DefaultPrivileges {
 #[derive(Clone, Debug, Deserialize, Serialize, PartialOrd, PartialEq, Eq, Ord)]
 pub struct ClusterConfig {
     pub variant: ClusterVariant,
+    /// The default isolation level of reads on the cluster, which applies to sessions that don't
+    /// set `transaction_isolation` themselves.
+    pub transaction_isolation: Option<IsolationLevel>,
 }
 
 impl From<ClusterConfig> for durable::ClusterConfig {
     fn from(config: ClusterConfig) -> Self {
         Self {
             variant: config.variant.into(),
+            transaction_isolation: config.transaction_isolation,
         }
     }
 }
@@ -1741,6 +1758,7 @@ impl From<durable::ClusterConfig> for ClusterConfig {
     fn from(config: durable::ClusterConfig) -> Self {
         Self {
             variant: config.variant.into(),
+            transaction_isolation: config.transaction_isolation,
         }
     }
 }
@@ -0,0 +1,145 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::time::{Duration, Instant};
+
+use fail::FailScenario;
+use mz_catalog::durable::{
+    test_bootstrap_args, test_persist_backed_catalog_state, test_stash_backed_catalog_state,
+    CatalogError, CommitError, CommitOperation, DurableCatalogError, OpenableDurableCatalogState,
+};
+use mz_ore::now::SYSTEM_TIME;
+use mz_persist_client::PersistClient;
+use mz_stash::DebugStashFactory;
+use uuid::Uuid;
+
+#[mz_ore::test(tokio::test)]
+#[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+async fn test_stash_commit_deadline() {
+    let debug_factory = DebugStashFactory::new().await;
+    let openable_state = test_stash_backed_catalog_state(&debug_factory);
+    test_commit_deadline(openable_state, CommitOperation::StashTransaction).await;
+    debug_factory.drop().await;
+}
+
+#[mz_ore::test(tokio::test)]
+#[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+async fn test_persist_commit_deadline() {
+    let persist_client = PersistClient::new_for_tests().await;
+    let organization_id = Uuid::new_v4();
+    let openable_state = test_persist_backed_catalog_state(persist_client, organization_id).await;
+    test_commit_deadline(openable_state, CommitOperation::CompareAndAppend).await;
+}
+
+#[mz_ore::test(tokio::test)]
+#[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+async fn test_persist_commit_deadline_after_append() {
+    let scenario = FailScenario::setup();
+    let persist_client = PersistClient::new_for_tests().await;
+    let organization_id = Uuid::new_v4();
+    let openable_state = test_persist_backed_catalog_state(persist_client, organization_id).await;
+    let mut state = Box::new(openable_state)
+        .open(SYSTEM_TIME(), &test_bootstrap_args(), None, None)
+        .await
+        .unwrap();
+
+    // A commit whose append landed before its deadline passed succeeded, even if the backend
+    // hangs before acknowledging it.
+    fail::cfg("catalog_commit_ack_pause", "return").unwrap();
+    let mut txn = state.transaction().await.unwrap();
+    txn.upsert_system_config("commit_deadline", "landed".to_string())
+        .unwrap();
+    txn.commit_with_deadline(Instant::now() + Duration::from_millis(200))
+        .await
+        .unwrap();
+    fail::cfg("catalog_commit_ack_pause", "off").unwrap();
+
+    // The catalog's state includes the commit, and later commits are not fenced by it.
+    let mut txn = state.transaction().await.unwrap();
+    txn.upsert_system_config("commit_deadline_next", "next".to_string())
+        .unwrap();
+    txn.commit_with_deadline(Instant::now() + Duration::from_secs(60))
+        .await
+        .unwrap();
+    let mut values: Vec<_> = state
+        .snapshot()
+        .await
+        .unwrap()
+        .system_configurations
+        .into_iter()
+        .filter(|(key, _)| key.name.starts_with("commit_deadline"))
+        .map(|(key, value)| (key.name, value.value))
+        .collect();
+    values.sort();
+    assert_eq!(
+        values,
+        vec![
+            ("commit_deadline".to_string(), "landed".to_string()),
+            ("commit_deadline_next".to_string(), "next".to_string()),
+        ]
+    );
+
+    Box::new(state).expire().await;
+    scenario.teardown();
+}
+
+async fn test_commit_deadline(
+    openable_state: impl OpenableDurableCatalogState,
+    expected_operation: CommitOperation,
+) {
+    let scenario = FailScenario::setup();
+    let mut state = Box::new(openable_state)
+        .open(SYSTEM_TIME(), &test_bootstrap_args(), None, None)
+        .await
+        .unwrap();
+
+    // A commit against a hung backend gives up at its deadline.
+    fail::cfg("catalog_commit_pause", "return").unwrap();
+    let mut txn = state.transaction().await.unwrap();
+    txn.upsert_system_config("commit_deadline", "hung".to_string())
+        .unwrap();
+    let timeout = Duration::from_millis(200);
+    let err = txn
+        .commit_with_deadline(Instant::now() + timeout)
+        .await
+        .unwrap_err();
+    match err {
+        CatalogError::Durable(DurableCatalogError::Commit(
+            ref e @ CommitError::Timeout { waited, operation },
+        )) => {
+            assert!(waited >= timeout, "unexpected wait: {waited:?}");
+            assert_eq!(operation, expected_operation);
+            assert!(!e.is_unrecoverable());
+        }
+        err => panic!("unexpected err: {err:?}"),
+    }
+
+    // Once the backend recovers, the catalog remains usable and the abandoned commit was not
+    // applied.
+    fail::cfg("catalog_commit_pause", "off").unwrap();
+    let mut txn = state.transaction().await.unwrap();
+    txn.upsert_system_config("commit_deadline", "recovered".to_string())
+        .unwrap();
+    txn.commit_with_deadline(Instant::now() + Duration::from_secs(60))
+        .await
+        .unwrap();
+    let values: Vec<_> = state
+        .snapshot()
+        .await
+        .unwrap()
+        .system_configurations
+        .into_iter()
+        .filter(|(key, _)| key.name == "commit_deadline")
+        .map(|(_, value)| value.value)
+        .collect();
+    assert_eq!(values, vec!["recovered".to_string()]);
+
+    Box::new(state).expire().await;
+    scenario.teardown();
+}
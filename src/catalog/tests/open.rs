@@ -7,21 +7,32 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
 use futures::future::BoxFuture;
 use futures::FutureExt;
+use mz_audit_log::{EventDetails, IdNameV1, VersionedEvent};
+use mz_catalog::durable::debug::{ClusterReplicaCollection, ConfigCollection};
 use mz_catalog::durable::objects::serialization::proto;
 use mz_catalog::durable::{
-    shadow_catalog_state, stash_backed_catalog_state, test_bootstrap_args,
-    test_persist_backed_catalog_state, test_persist_backed_catalog_state_with_version,
-    test_stash_backed_catalog_state, test_stash_config, CatalogError, DurableCatalogError,
-    DurableCatalogState, Epoch, OpenableDurableCatalogState,
+    shadow_catalog_state, shadow_catalog_state_with_metrics, stash_backed_catalog_state,
+    test_bootstrap_args, test_persist_backed_catalog_state,
+    test_persist_backed_catalog_state_with_version, test_stash_backed_catalog_state,
+    test_stash_config, CatalogError, DurableCatalogError, DurableCatalogState, Epoch, Metrics,
+    OpenableDurableCatalogState, ReplicaConfig, ReplicaIncarnation, ReplicaLocation,
+    ShadowSourceOfTruth,
 };
+use mz_controller::clusters::ReplicaLogging;
+use mz_controller_types::ReplicaId;
+use mz_ore::collections::CollectionExt;
+use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::{NOW_ZERO, SYSTEM_TIME};
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::{PersistClient, PersistLocation};
 use mz_proto::RustType;
 use mz_repr::role_id::RoleId;
-use mz_sql::catalog::{RoleAttributes, RoleMembership, RoleVars};
+use mz_sql::catalog::{CatalogError as SqlCatalogError, RoleAttributes, RoleMembership, RoleVars};
 use mz_stash::DebugStashFactory;
 use uuid::Uuid;
 
@@ -819,3 +830,345 @@ async fn test_persist_fencing() {
     testcase("0.10.0", "0.11.0", Ok(())).await;
     testcase("0.10.0", "0.12.0", Err(())).await;
 }
+
+#[mz_ore::test(tokio::test)]
+#[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+async fn test_shadow_migration_rehearsal() {
+    let persist_client = PersistClient::new_for_tests().await;
+    let organization_id = Uuid::new_v4();
+    let (debug_factory, stash_config) = test_stash_config().await;
+    let metrics = Arc::new(Metrics::new(&MetricsRegistry::new()));
+    let shadow_openable_state = || {
+        shadow_catalog_state_with_metrics(
+            stash_config.clone(),
+            persist_client.clone(),
+            organization_id,
+            Arc::clone(&metrics),
+        )
+    };
+
+    async fn insert_role(state: &mut Box<dyn DurableCatalogState>, name: &str) -> RoleId {
+        let mut txn = state.transaction().await.unwrap();
+        let role_id = txn
+            .insert_user_role(
+                name.to_string(),
+                RoleAttributes::new(),
+                RoleMembership::new(),
+                RoleVars::default(),
+            )
+            .unwrap();
+        txn.commit().await.unwrap();
+        role_id
+    }
+
+    // Write with the stash as the source of truth, then cut over to persist and write some more.
+    let (snapshot, role_ids) = {
+        let mut state = Box::new(shadow_openable_state().await)
+            .open(SYSTEM_TIME(), &test_bootstrap_args(), None, None)
+            .await
+            .unwrap();
+        let before = insert_role(&mut state, "before_cutover").await;
+        state
+            .cutover(ShadowSourceOfTruth::Persist, SYSTEM_TIME())
+            .await
+            .unwrap();
+        let after = insert_role(&mut state, "after_cutover").await;
+        let snapshot = state.snapshot().await.unwrap();
+        state.expire().await;
+        (snapshot, [before, after])
+    };
+
+    // Reopening observes every write and the recorded cutover.
+    let mut state = Box::new(shadow_openable_state().await)
+        .open(SYSTEM_TIME(), &test_bootstrap_args(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(state.snapshot().await.unwrap(), snapshot);
+    for role_id in role_ids {
+        assert!(snapshot.roles.contains_key(&proto::RoleKey {
+            id: Some(role_id.into_proto()),
+        }));
+    }
+    let cutover_event =
+        state
+            .get_audit_logs()
+            .await
+            .unwrap()
+            .into_iter()
+            .find_map(|VersionedEvent::V1(event)| match event.details {
+                EventDetails::IdNameV1(IdNameV1 { id, name }) if id == "shadow_source_of_truth" => {
+                    Some(name)
+                }
+                _ => None,
+            });
+    assert_eq!(cutover_event.as_deref(), Some("persist"));
+
+    // A newer opener fences the stale one, which can neither write nor cut over any more.
+    let _new_state = Box::new(shadow_openable_state().await)
+        .open(SYSTEM_TIME(), &test_bootstrap_args(), None, None)
+        .await
+        .unwrap();
+    let err = state.confirm_leadership().await.unwrap_err();
+    assert!(
+        matches!(err, CatalogError::Durable(DurableCatalogError::Fence(_))),
+        "unexpected err: {err:?}"
+    );
+    let err = state
+        .cutover(ShadowSourceOfTruth::Stash, SYSTEM_TIME())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, CatalogError::Durable(DurableCatalogError::Fence(_))),
+        "unexpected err: {err:?}"
+    );
+
+    assert_eq!(metrics.shadow_mismatched_reads.get(), 0);
+    debug_factory.drop().await;
+}
+
+#[mz_ore::test(tokio::test)]
+#[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+async fn test_stash_replica_incarnations() {
+    let (debug_factory, stash_config) = test_stash_config().await;
+    let openable_state1 = stash_backed_catalog_state(stash_config.clone());
+    let openable_state2 = stash_backed_catalog_state(stash_config);
+    test_replica_incarnations(openable_state1, openable_state2).await;
+    debug_factory.drop().await;
+}
+
+#[mz_ore::test(tokio::test)]
+#[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+async fn test_debug_stash_replica_incarnations() {
+    let debug_factory = DebugStashFactory::new().await;
+    let debug_openable_state1 = test_stash_backed_catalog_state(&debug_factory);
+    let debug_openable_state2 = test_stash_backed_catalog_state(&debug_factory);
+    test_replica_incarnations(debug_openable_state1, debug_openable_state2).await;
+    debug_factory.drop().await;
+}
+
+#[mz_ore::test(tokio::test)]
+#[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+async fn test_persist_replica_incarnations() {
+    let persist_client = PersistClient::new_for_tests().await;
+    let organization_id = Uuid::new_v4();
+    let persist_openable_state1 =
+        test_persist_backed_catalog_state(persist_client.clone(), organization_id).await;
+    let persist_openable_state2 =
+        test_persist_backed_catalog_state(persist_client, organization_id).await;
+    test_replica_incarnations(persist_openable_state1, persist_openable_state2).await;
+}
+
+fn replica_config(size: &str) -> ReplicaConfig {
+    ReplicaConfig {
+        location: ReplicaLocation::Managed {
+            size: size.to_string(),
+            availability_zone: None,
+            disk: false,
+            internal: false,
+            billed_as: None,
+        },
+        logging: ReplicaLogging {
+            log_logging: false,
+            interval: None,
+        },
+        idle_arrangement_merge_effort: None,
+    }
+}
+
+async fn test_replica_incarnations(
+    openable_state1: impl OpenableDurableCatalogState,
+    openable_state2: impl OpenableDurableCatalogState,
+) {
+    let (new_id, unknown_id) = (ReplicaId::User(100), ReplicaId::User(101));
+
+    // Gracefully resize the replica of the default cluster, and then reconfigure its successor.
+    let (snapshot, old_id) = {
+        let mut state = Box::new(openable_state1)
+            .open(SYSTEM_TIME(), &test_bootstrap_args(), None, None)
+            .await
+            .unwrap();
+        let mut txn = state.transaction().await.unwrap();
+        let old = txn.get_cluster_replicas().into_element();
+        assert_eq!(old.incarnation, ReplicaIncarnation::default());
+        txn.insert_cluster_replica_with_incarnation(
+            old.cluster_id,
+            new_id,
+            "r2",
+            replica_config("2"),
+            old.owner_id,
+            old.incarnation.incarnation + 1,
+        )
+        .unwrap();
+        txn.mark_replica_pending_removal(old.replica_id).unwrap();
+        assert_eq!(
+            txn.update_replica_config(new_id, replica_config("4"))
+                .unwrap(),
+            1
+        );
+
+        let err = txn
+            .update_replica_config(unknown_id, replica_config("4"))
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                CatalogError::Catalog(SqlCatalogError::UnknownClusterReplica(_))
+            ),
+            "unexpected err: {err:?}"
+        );
+        let err = txn.mark_replica_pending_removal(unknown_id).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                CatalogError::Catalog(SqlCatalogError::UnknownClusterReplica(_))
+            ),
+            "unexpected err: {err:?}"
+        );
+        txn.commit().await.unwrap();
+
+        let snapshot = state.snapshot().await.unwrap();
+        assert_eq!(
+            snapshot.replica_incarnation(old.replica_id).unwrap(),
+            Some(ReplicaIncarnation {
+                incarnation: 0,
+                config_version: 0,
+                pending_removal: true,
+            })
+        );
+        assert_eq!(
+            snapshot.replica_incarnation(new_id).unwrap(),
+            Some(ReplicaIncarnation {
+                incarnation: 1,
+                config_version: 1,
+                pending_removal: false,
+            })
+        );
+        assert_eq!(snapshot.replica_incarnation(unknown_id).unwrap(), None);
+        assert_eq!(
+            snapshot.replicas_pending_removal().unwrap(),
+            vec![old.replica_id]
+        );
+        Box::new(state).expire().await;
+        (snapshot, old.replica_id)
+    };
+
+    // Reopening the catalog round-trips the incarnations.
+    let mut state = Box::new(openable_state2)
+        .open(SYSTEM_TIME(), &test_bootstrap_args(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(state.snapshot().await.unwrap(), snapshot);
+    let txn = state.transaction().await.unwrap();
+    let replicas: BTreeMap<_, _> = txn
+        .get_cluster_replicas()
+        .map(|replica| (replica.replica_id, replica))
+        .collect();
+    assert!(replicas[&old_id].incarnation.pending_removal);
+    let new = &replicas[&new_id];
+    assert_eq!(new.name, "r2");
+    assert_eq!(new.config, replica_config("4"));
+    assert_eq!(
+        new.incarnation,
+        ReplicaIncarnation {
+            incarnation: 1,
+            config_version: 1,
+            pending_removal: false,
+        }
+    );
+    drop(txn);
+    Box::new(state).expire().await;
+}
+
+#[mz_ore::test(tokio::test)]
+#[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+async fn test_stash_replica_incarnation_migration() {
+    let debug_factory = DebugStashFactory::new().await;
+    let debug_openable_state1 = test_stash_backed_catalog_state(&debug_factory);
+    let debug_openable_state2 = test_stash_backed_catalog_state(&debug_factory);
+    let debug_openable_state3 = test_stash_backed_catalog_state(&debug_factory);
+    test_replica_incarnation_migration(
+        debug_openable_state1,
+        debug_openable_state2,
+        debug_openable_state3,
+    )
+    .await;
+    debug_factory.drop().await;
+}
+
+#[mz_ore::test(tokio::test)]
+#[cfg_attr(miri, ignore)] //  unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+async fn test_persist_replica_incarnation_migration() {
+    let persist_client = PersistClient::new_for_tests().await;
+    let organization_id = Uuid::new_v4();
+    let persist_openable_state1 =
+        test_persist_backed_catalog_state(persist_client.clone(), organization_id).await;
+    let persist_openable_state2 =
+        test_persist_backed_catalog_state(persist_client.clone(), organization_id).await;
+    let persist_openable_state3 =
+        test_persist_backed_catalog_state(persist_client, organization_id).await;
+    test_replica_incarnation_migration(
+        persist_openable_state1,
+        persist_openable_state2,
+        persist_openable_state3,
+    )
+    .await;
+}
+
+async fn test_replica_incarnation_migration(
+    openable_state1: impl OpenableDurableCatalogState,
+    mut openable_state2: impl OpenableDurableCatalogState,
+    openable_state3: impl OpenableDurableCatalogState,
+) {
+    let state = Box::new(openable_state1)
+        .open(SYSTEM_TIME(), &test_bootstrap_args(), None, None)
+        .await
+        .unwrap();
+    Box::new(state).expire().await;
+
+    // Rewrite the catalog as version 48 wrote it, i.e. with replicas that have no incarnation.
+    let mut replicas = openable_state2
+        .trace()
+        .await
+        .unwrap()
+        .cluster_replicas
+        .values;
+    differential_dataflow::consolidation::consolidate_updates(&mut replicas);
+    assert!(!replicas.is_empty());
+    let mut debug_state = Box::new(openable_state2).open_debug().await.unwrap();
+    for ((key, mut value), _ts, _diff) in replicas {
+        value.incarnation = None;
+        debug_state
+            .edit::<ClusterReplicaCollection>(key, value)
+            .await
+            .unwrap();
+    }
+    debug_state
+        .edit::<ConfigCollection>(
+            proto::ConfigKey {
+                key: "user_version".to_string(),
+            },
+            proto::ConfigValue { value: 48 },
+        )
+        .await
+        .unwrap();
+
+    // Opening the catalog migrates the replicas to their first incarnation.
+    let mut state = Box::new(openable_state3)
+        .open(SYSTEM_TIME(), &test_bootstrap_args(), None, None)
+        .await
+        .unwrap();
+    let snapshot = state.snapshot().await.unwrap();
+    assert!(snapshot.replicas_pending_removal().unwrap().is_empty());
+    let txn = state.transaction().await.unwrap();
+    let replicas: Vec<_> = txn.get_cluster_replicas().collect();
+    assert!(!replicas.is_empty());
+    for replica in replicas {
+        assert_eq!(replica.incarnation, ReplicaIncarnation::default());
+        assert_eq!(
+            snapshot.replica_incarnation(replica.replica_id).unwrap(),
+            Some(ReplicaIncarnation::default())
+        );
+    }
+    drop(txn);
+    Box::new(state).expire().await;
+}
@@ -0,0 +1,303 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Recording of the commands controllers send to their instances, for replaying them offline.
+//!
+//! Recording is disabled by default. While it is disabled, recording a command costs a single
+//! atomic load. Once enabled, each command is appended to the configured sink as a bincode-encoded
+//! [`RecordedCommand`], in the order in which the commands are sent. The command is encoded last,
+//! so a reader can decode it based on the protocol it belongs to, see [`LoggedCommand`]. The
+//! storage and compute controllers share a [`CommandRecorder`], so their commands are recorded in
+//! a single log.
+
+use std::fmt;
+use std::io::{BufRead, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use mz_ore::now::{EpochMillis, NowFn};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// The protocol a recorded command belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommandProtocol {
+    /// A command sent by the storage controller.
+    Storage,
+    /// A command sent by the compute controller.
+    Compute,
+}
+
+/// A command, as recorded in a command log.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedCommand<C> {
+    /// The position of the command in the log, starting at zero.
+    pub seq: u64,
+    /// The wall-clock time at which the command was sent.
+    pub timestamp: EpochMillis,
+    /// The protocol the command belongs to.
+    pub protocol: CommandProtocol,
+    /// The ID of the instance the command was sent to.
+    pub instance: String,
+    /// The command itself.
+    pub command: C,
+}
+
+/// Commands that can be read from a command log.
+///
+/// A log can contain the commands of several protocols, so implementors decide how to decode a
+/// command based on its protocol.
+pub trait LoggedCommand: Sized {
+    /// Decodes a command of `protocol` from `log`.
+    fn read(protocol: CommandProtocol, log: &mut dyn Read) -> Result<Self, bincode::Error>;
+}
+
+/// Records commands to a command log, if recording is enabled.
+///
+/// Clones share the log, so recording that is enabled through one clone is observed by all of
+/// them.
+#[derive(Clone, Default)]
+pub struct CommandRecorder {
+    enabled: Arc<AtomicBool>,
+    log: Arc<Mutex<Option<CommandLog>>>,
+}
+
+/// The sink of an enabled [`CommandRecorder`].
+struct CommandLog {
+    sink: Box<dyn Write + Send>,
+    next_seq: u64,
+    now: NowFn,
+}
+
+impl CommandRecorder {
+    /// Starts recording commands to `sink`, timestamping them with `now`.
+    ///
+    /// Replaces any previously enabled log, which is flushed. Sequence numbers restart at zero
+    /// for the new log.
+    pub fn enable(&self, sink: impl Write + Send + 'static, now: NowFn) {
+        let mut log = self.log.lock().expect("lock poisoned");
+        if let Some(previous) = log.as_mut() {
+            previous.flush();
+        }
+        *log = Some(CommandLog {
+            sink: Box::new(sink),
+            next_seq: 0,
+            now,
+        });
+        self.enabled.store(true, Ordering::Relaxed);
+    }
+
+    /// Stops recording commands, flushing the log.
+    pub fn disable(&self) {
+        let mut log = self.log.lock().expect("lock poisoned");
+        self.enabled.store(false, Ordering::Relaxed);
+        if let Some(mut previous) = log.take() {
+            previous.flush();
+        }
+    }
+
+    /// Reports whether commands are being recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Records `command`, which was sent to `instance`, if recording is enabled.
+    ///
+    /// A failure to write to the log is logged and disables recording, rather than interfering
+    /// with the controller.
+    pub fn record<C: Serialize>(
+        &self,
+        protocol: CommandProtocol,
+        instance: impl fmt::Display,
+        command: &C,
+    ) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let mut log = self.log.lock().expect("lock poisoned");
+        let Some(enabled_log) = log.as_mut() else {
+            return;
+        };
+        let recorded = RecordedCommand {
+            seq: enabled_log.next_seq,
+            timestamp: (enabled_log.now)(),
+            protocol,
+            instance: instance.to_string(),
+            command,
+        };
+        enabled_log.next_seq += 1;
+        if let Err(error) = bincode::serialize_into(&mut enabled_log.sink, &recorded) {
+            warn!("disabling command recording after failing to write to the log: {error}");
+            self.enabled.store(false, Ordering::Relaxed);
+            *log = None;
+        }
+    }
+}
+
+impl fmt::Debug for CommandRecorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandRecorder")
+            .field("enabled", &self.is_enabled())
+            .finish()
+    }
+}
+
+impl CommandLog {
+    fn flush(&mut self) {
+        if let Err(error) = self.sink.flush() {
+            warn!("failed to flush the command log: {error}");
+        }
+    }
+}
+
+/// A command log that is kept in memory, e.g. for tests.
+///
+/// Clones share the log, so its contents remain accessible after a clone is handed to a
+/// [`CommandRecorder`].
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryLog(Arc<Mutex<Vec<u8>>>);
+
+impl InMemoryLog {
+    /// Returns the contents of the log.
+    pub fn contents(&self) -> Vec<u8> {
+        self.0.lock().expect("lock poisoned").clone()
+    }
+}
+
+impl Write for InMemoryLog {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().expect("lock poisoned").write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Reads the commands of a command log, in the order in which they were recorded.
+///
+/// Reading stops after the first error.
+pub fn read_command_log<C, R>(
+    mut log: R,
+) -> impl Iterator<Item = Result<RecordedCommand<C>, anyhow::Error>>
+where
+    C: LoggedCommand,
+    R: BufRead,
+{
+    let mut failed = false;
+    std::iter::from_fn(move || {
+        if failed {
+            return None;
+        }
+        let result = match log.fill_buf() {
+            Ok(buf) if buf.is_empty() => return None,
+            Ok(_) => read_recorded_command(&mut log),
+            Err(error) => Err(error.into()),
+        };
+        failed = result.is_err();
+        Some(result)
+    })
+}
+
+/// Reads the next command from `log`, which must not be at its end.
+fn read_recorded_command<C: LoggedCommand>(
+    log: &mut dyn Read,
+) -> Result<RecordedCommand<C>, anyhow::Error> {
+    // The fields preceding the command, in the order they are encoded in.
+    let (seq, timestamp, protocol, instance): (u64, EpochMillis, CommandProtocol, String) =
+        bincode::deserialize_from(&mut *log)?;
+    let command = C::read(protocol, log)?;
+    Ok(RecordedCommand {
+        seq,
+        timestamp,
+        protocol,
+        instance,
+        command,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use mz_ore::now::NOW_ZERO;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum TestCommand {
+        Storage(String),
+        Compute(u64),
+    }
+
+    impl LoggedCommand for TestCommand {
+        fn read(protocol: CommandProtocol, log: &mut dyn Read) -> Result<Self, bincode::Error> {
+            Ok(match protocol {
+                CommandProtocol::Storage => TestCommand::Storage(bincode::deserialize_from(log)?),
+                CommandProtocol::Compute => TestCommand::Compute(bincode::deserialize_from(log)?),
+            })
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_record_and_read() {
+        let recorder = CommandRecorder::default();
+        // Commands are not recorded until recording is enabled.
+        recorder.record(CommandProtocol::Storage, "s1", &"ignored");
+
+        let log = InMemoryLog::default();
+        recorder.enable(log.clone(), NOW_ZERO.clone());
+        let clone = recorder.clone();
+        recorder.record(CommandProtocol::Storage, "s1", &"first");
+        clone.record(CommandProtocol::Compute, "u1", &2u64);
+        recorder.disable();
+        recorder.record(CommandProtocol::Compute, "u1", &3u64);
+
+        let recorded: Vec<RecordedCommand<TestCommand>> =
+            read_command_log(Cursor::new(log.contents()))
+                .collect::<Result<_, _>>()
+                .unwrap();
+        assert_eq!(
+            recorded,
+            vec![
+                RecordedCommand {
+                    seq: 0,
+                    timestamp: 0,
+                    protocol: CommandProtocol::Storage,
+                    instance: "s1".into(),
+                    command: TestCommand::Storage("first".into()),
+                },
+                RecordedCommand {
+                    seq: 1,
+                    timestamp: 0,
+                    protocol: CommandProtocol::Compute,
+                    instance: "u1".into(),
+                    command: TestCommand::Compute(2),
+                },
+            ]
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_truncated_log() {
+        let log = InMemoryLog::default();
+        let recorder = CommandRecorder::default();
+        recorder.enable(log.clone(), NOW_ZERO.clone());
+        recorder.record(CommandProtocol::Storage, "s1", &"first");
+        recorder.record(CommandProtocol::Storage, "s1", &"second");
+
+        let mut contents = log.contents();
+        contents.pop();
+        let recorded: Vec<_> = read_command_log::<TestCommand, _>(Cursor::new(contents)).collect();
+        assert_eq!(recorded.len(), 2);
+        assert!(recorded[0].is_ok());
+        assert!(recorded[1].is_err());
+    }
+}
@@ -18,6 +18,7 @@ use anyhow::bail;
 use serde::{Deserialize, Serialize};
 
 pub mod client;
+pub mod command_log;
 
 /// Identifier of a replica.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
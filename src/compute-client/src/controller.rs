@@ -31,6 +31,7 @@
 use std::collections::BTreeMap;
 use std::num::NonZeroI64;
 use std::pin::Pin;
+use std::task::{Context, Poll};
 use std::time::Duration;
 
 use differential_dataflow::consolidation::consolidate;
@@ -38,6 +39,7 @@ use differential_dataflow::lattice::Lattice;
 use futures::{future, Future, FutureExt};
 use mz_build_info::BuildInfo;
 use mz_cluster_client::client::ClusterReplicaLocation;
+use mz_cluster_client::command_log::CommandRecorder;
 use mz_cluster_client::ReplicaId;
 use mz_compute_types::dataflows::DataflowDescription;
 use mz_compute_types::ComputeInstanceId;
@@ -50,14 +52,15 @@ use mz_storage_types::read_policy::ReadPolicy;
 use serde::{Deserialize, Serialize};
 use timely::progress::frontier::{AntichainRef, MutableAntichain};
 use timely::progress::{Antichain, Timestamp};
+use tokio::sync::oneshot;
 use tokio::time::{self, MissedTickBehavior};
 use tracing::warn;
 use uuid::Uuid;
 
 use crate::controller::error::{
     CollectionLookupError, CollectionMissing, CollectionUpdateError, DataflowCreationError,
-    InstanceExists, InstanceMissing, PeekError, ReadPolicyError, ReplicaCreationError,
-    ReplicaDropError, SubscribeTargetError,
+    InstanceExists, InstanceMissing, PeekError, QuiesceCanceled, ReadPolicyError,
+    ReplicaCreationError, ReplicaDropError, ReplicaUpdateError, SubscribeTargetError,
 };
 use crate::controller::instance::{ActiveInstance, Instance};
 use crate::controller::replica::ReplicaConfig;
@@ -170,6 +173,9 @@ pub struct ComputeController<T> {
     /// This flag exists to derisk the rollout of the aggressive downgrading approach.
     /// TODO(teskje): Remove this after a couple weeks.
     enable_aggressive_readhold_downgrades: bool,
+
+    /// The recorder of the commands sent to compute instances.
+    command_recorder: CommandRecorder,
 }
 
 impl<T: Timestamp> ComputeController<T> {
@@ -178,6 +184,7 @@ impl<T: Timestamp> ComputeController<T> {
         build_info: &'static BuildInfo,
         envd_epoch: NonZeroI64,
         metrics_registry: MetricsRegistry,
+        command_recorder: CommandRecorder,
     ) -> Self {
         let (response_tx, response_rx) = crossbeam_channel::unbounded();
         let (introspection_tx, introspection_rx) = crossbeam_channel::unbounded();
@@ -202,6 +209,7 @@ impl<T: Timestamp> ComputeController<T> {
             maintenance_ticker,
             maintenance_scheduled: false,
             enable_aggressive_readhold_downgrades: true,
+            command_recorder,
         }
     }
 
@@ -220,6 +228,44 @@ impl<T: Timestamp> ComputeController<T> {
         self.instances.get_mut(&id).ok_or(InstanceMissing(id))
     }
 
+    /// Mark the indicated compute instance as quiescing, in preparation for maintenance.
+    ///
+    /// Callers are expected to refrain from issuing new peeks, subscribes, and copy tos against a
+    /// quiescing instance, see [`ComputeController::instance_is_quiescing`]. Work that is in
+    /// progress is allowed to complete, and the returned handle resolves once none remains.
+    pub fn quiesce_instance(
+        &mut self,
+        id: ComputeInstanceId,
+    ) -> Result<QuiesceHandle, InstanceMissing> {
+        let rx = self.instance_mut(id)?.quiesce();
+        Ok(QuiesceHandle {
+            instance_id: id,
+            rx,
+        })
+    }
+
+    /// End quiescing of the indicated compute instance.
+    ///
+    /// Outstanding [`QuiesceHandle`]s for the instance resolve with an error.
+    pub fn unquiesce_instance(&mut self, id: ComputeInstanceId) -> Result<(), InstanceMissing> {
+        self.instance_mut(id)?.unquiesce();
+        Ok(())
+    }
+
+    /// Return whether the indicated compute instance is quiescing.
+    pub fn instance_is_quiescing(&self, id: ComputeInstanceId) -> Result<bool, InstanceMissing> {
+        Ok(self.instance(id)?.is_quiescing())
+    }
+
+    /// Return the number of peeks, subscribes, and copy tos outstanding on the indicated compute
+    /// instance.
+    pub fn instance_outstanding_work(
+        &self,
+        id: ComputeInstanceId,
+    ) -> Result<usize, InstanceMissing> {
+        Ok(self.instance(id)?.outstanding_work())
+    }
+
     /// Return a read-only handle to the indicated compute instance.
     pub fn instance_ref(
         &self,
@@ -295,6 +341,17 @@ impl<T: Timestamp> ComputeController<T> {
         self.enable_aggressive_readhold_downgrades = value;
     }
 
+    /// Returns the collections installed on each compute instance.
+    pub fn collections(
+        &self,
+    ) -> impl Iterator<Item = (ComputeInstanceId, &GlobalId, &CollectionState<T>)> {
+        self.instances.iter().flat_map(|(instance_id, instance)| {
+            instance
+                .collections_iter()
+                .map(move |(id, collection)| (*instance_id, id, collection))
+        })
+    }
+
     /// Returns the read and write frontiers for each collection.
     pub fn collection_frontiers(&self) -> BTreeMap<GlobalId, (Antichain<T>, Antichain<T>)> {
         let collections = self.instances.values().flat_map(|i| i.collections_iter());
@@ -307,6 +364,14 @@ impl<T: Timestamp> ComputeController<T> {
             .collect()
     }
 
+    /// Returns the number of commands not yet forwarded to the replicas of each instance.
+    pub fn pending_command_counts(&self) -> BTreeMap<ComputeInstanceId, usize> {
+        self.instances
+            .iter()
+            .map(|(id, instance)| (*id, instance.pending_command_count()))
+            .collect()
+    }
+
     /// Returns the write frontier for each collection installed on each replica.
     pub fn replica_write_frontiers(&self) -> BTreeMap<(GlobalId, ReplicaId), Antichain<T>> {
         let mut result = BTreeMap::new();
@@ -338,6 +403,7 @@ where
         self.instances.insert(
             id,
             Instance::new(
+                id,
                 self.build_info,
                 arranged_logs,
                 self.envd_epoch,
@@ -345,6 +411,7 @@ where
                 self.response_tx.clone(),
                 self.introspection_tx.clone(),
                 self.enable_aggressive_readhold_downgrades,
+                self.command_recorder.clone(),
             ),
         );
 
@@ -532,6 +599,19 @@ where
         Ok(())
     }
 
+    /// Sets the idle arrangement merge effort of a single replica, overriding the default it was
+    /// created with.
+    pub fn set_idle_arrangement_merge_effort(
+        &mut self,
+        instance_id: ComputeInstanceId,
+        replica_id: ReplicaId,
+        value: u32,
+    ) -> Result<(), ReplicaUpdateError> {
+        self.instance(instance_id)?
+            .set_idle_arrangement_merge_effort(replica_id, value)?;
+        Ok(())
+    }
+
     /// Removes a replica from an instance, including its service in the orchestrator.
     pub fn drop_replica(
         &mut self,
@@ -542,6 +622,18 @@ where
         Ok(())
     }
 
+    /// Makes all replicas of an instance drop their dataflows and rebuild them, e.g. after a
+    /// suspected corruption, without recreating the instance.
+    ///
+    /// Peeks, subscribes, and `COPY TO`s that are active on the instance fail.
+    pub fn rehydrate_instance(
+        &mut self,
+        instance_id: ComputeInstanceId,
+    ) -> Result<(), InstanceMissing> {
+        self.instance(instance_id)?.rehydrate();
+        Ok(())
+    }
+
     /// Create and maintain the described dataflows, and initialize state for their output.
     ///
     /// This method creates dataflows whose inputs are still readable at the dataflow `as_of`
@@ -720,6 +812,34 @@ where
     }
 }
 
+/// A handle to a quiescing compute instance, returned by [`ComputeController::quiesce_instance`].
+///
+/// The handle resolves once no peeks, subscribes, or copy tos remain outstanding on the instance,
+/// or with an error if the instance stops quiescing or is dropped before that.
+#[derive(Debug)]
+pub struct QuiesceHandle {
+    instance_id: ComputeInstanceId,
+    rx: oneshot::Receiver<()>,
+}
+
+impl QuiesceHandle {
+    /// Returns the ID of the quiescing instance.
+    pub fn instance_id(&self) -> ComputeInstanceId {
+        self.instance_id
+    }
+}
+
+impl Future for QuiesceHandle {
+    type Output = Result<(), QuiesceCanceled>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let instance_id = self.instance_id;
+        self.rx
+            .poll_unpin(cx)
+            .map_err(|_| QuiesceCanceled(instance_id))
+    }
+}
+
 /// A read-only handle to a compute instance.
 #[derive(Debug, Clone, Copy)]
 pub struct ComputeInstanceRef<'a, T> {
@@ -810,6 +930,19 @@ impl<T> CollectionState<T> {
         self.write_frontier.borrow()
     }
 
+    /// Reports the write frontier of this collection on the given replica, if the replica has
+    /// reported one.
+    pub fn replica_write_frontier(&self, replica_id: ReplicaId) -> Option<AntichainRef<T>> {
+        self.replica_write_frontiers
+            .get(&replica_id)
+            .map(|frontier| frontier.borrow())
+    }
+
+    /// Reports whether this collection is a log collection.
+    pub fn is_log_collection(&self) -> bool {
+        self.log_collection
+    }
+
     /// Reports the IDs of the dependencies of this collection.
     fn dependency_ids(&self) -> impl Iterator<Item = GlobalId> + '_ {
         let compute = self.compute_dependencies.iter().copied();
@@ -33,6 +33,12 @@ pub struct InstanceMissing(pub ComputeInstanceId);
 #[error("instance exists already: {0}")]
 pub struct InstanceExists(pub ComputeInstanceId);
 
+/// Error returned by a [`QuiesceHandle`](crate::controller::QuiesceHandle) if the compute instance
+/// stopped quiescing, or was dropped, before it became idle.
+#[derive(Error, Debug)]
+#[error("quiescing was canceled for instance: {0}")]
+pub struct QuiesceCanceled(pub ComputeInstanceId);
+
 /// Error returned in response to a reference to an unknown compute collection.
 #[derive(Error, Debug)]
 #[error("collection does not exist: {0}")]
@@ -116,6 +122,29 @@ impl From<instance::ReplicaMissing> for ReplicaDropError {
     }
 }
 
+/// Errors arising during compute replica configuration updates.
+#[derive(Error, Debug)]
+pub enum ReplicaUpdateError {
+    /// The targeted compute instance does not exist.
+    #[error("instance does not exist: {0}")]
+    InstanceMissing(ComputeInstanceId),
+    /// The targeted replica does not exist.
+    #[error("replica does not exist: {0}")]
+    ReplicaMissing(ReplicaId),
+}
+
+impl From<InstanceMissing> for ReplicaUpdateError {
+    fn from(error: InstanceMissing) -> Self {
+        Self::InstanceMissing(error.0)
+    }
+}
+
+impl From<instance::ReplicaMissing> for ReplicaUpdateError {
+    fn from(error: instance::ReplicaMissing) -> Self {
+        Self::ReplicaMissing(error.0)
+    }
+}
+
 /// Errors arising during dataflow creation.
 #[derive(Error, Debug)]
 pub enum DataflowCreationError {
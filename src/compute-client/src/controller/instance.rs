@@ -19,10 +19,12 @@ use futures::stream::FuturesUnordered;
 use futures::{future, StreamExt};
 use mz_build_info::BuildInfo;
 use mz_cluster_client::client::{ClusterStartupEpoch, TimelyConfig};
+use mz_cluster_client::command_log::{CommandProtocol, CommandRecorder};
 use mz_compute_types::dataflows::DataflowDescription;
 use mz_compute_types::plan::NodeId;
 use mz_compute_types::sinks::{ComputeSinkConnection, ComputeSinkDesc, PersistSinkConnection};
 use mz_compute_types::sources::SourceInstanceDesc;
+use mz_compute_types::ComputeInstanceId;
 use mz_expr::RowSetFinishing;
 use mz_ore::cast::CastFrom;
 use mz_ore::tracing::OpenTelemetryContext;
@@ -32,6 +34,7 @@ use mz_storage_types::read_policy::ReadPolicy;
 use thiserror::Error;
 use timely::progress::{Antichain, ChangeBatch, Timestamp};
 use timely::PartialOrder;
+use tokio::sync::oneshot;
 use uuid::Uuid;
 
 use crate::controller::error::CollectionMissing;
@@ -119,6 +122,8 @@ pub(super) enum SubscribeTargetError {
 /// The state we keep for a compute instance.
 #[derive(Debug)]
 pub(super) struct Instance<T> {
+    /// The ID of this compute instance.
+    id: ComputeInstanceId,
     /// Build info for spawning replicas
     build_info: &'static BuildInfo,
     /// Whether instance initialization has been completed.
@@ -166,6 +171,12 @@ pub(super) struct Instance<T> {
     /// The entry for a copy to is removed once at least one replica has finished
     /// or the exporting collection is dropped.
     copy_tos: BTreeSet<GlobalId>,
+    /// Senders notifying the handles returned by [`Instance::quiesce`], if the instance is
+    /// quiescing.
+    ///
+    /// While the instance is quiescing, no new peeks, subscribes, or copy tos are expected to be
+    /// issued against it. The senders are notified once none of them remain outstanding.
+    quiesce_waiters: Option<Vec<oneshot::Sender<()>>>,
     /// The command history, used when introducing new replicas or restarting existing replicas.
     history: ComputeCommandHistory<UIntGauge, T>,
     /// Sender for responses to be delivered.
@@ -183,6 +194,8 @@ pub(super) struct Instance<T> {
     /// This flag exists to derisk the rollout of the aggressive downgrading approach.
     /// TODO(teskje): Remove this after a couple weeks.
     enable_aggressive_readhold_downgrades: bool,
+    /// The recorder of the commands sent to this instance.
+    command_recorder: CommandRecorder,
 }
 
 impl<T: Timestamp> Instance<T> {
@@ -340,6 +353,19 @@ impl<T: Timestamp> Instance<T> {
         self.replicas.keys().copied()
     }
 
+    /// Returns the number of commands not yet forwarded to the replica that is furthest behind
+    /// in receiving them.
+    ///
+    /// Commands are broadcast to all replicas, so this is the number of commands that have not
+    /// reached every replica yet.
+    pub fn pending_command_count(&self) -> usize {
+        self.replicas
+            .values()
+            .map(|replica| replica.client.pending_commands())
+            .max()
+            .unwrap_or(0)
+    }
+
     /// Return the IDs of pending peeks targeting the specified replica.
     fn peeks_targeting(
         &self,
@@ -388,6 +414,45 @@ impl<T: Timestamp> Instance<T> {
             .set(u64::cast_from(self.copy_tos.len()));
     }
 
+    /// Returns the number of outstanding peeks, subscribes, and copy tos.
+    pub fn outstanding_work(&self) -> usize {
+        self.peeks.len() + self.subscribes.len() + self.copy_tos.len()
+    }
+
+    /// Returns whether the instance is quiescing.
+    pub fn is_quiescing(&self) -> bool {
+        self.quiesce_waiters.is_some()
+    }
+
+    /// Marks the instance as quiescing.
+    ///
+    /// The returned receiver is notified once no peeks, subscribes, or copy tos are outstanding
+    /// on the instance. It is dropped without notification if quiescing is ended first.
+    pub fn quiesce(&mut self) -> oneshot::Receiver<()> {
+        let (tx, rx) = oneshot::channel();
+        self.quiesce_waiters.get_or_insert_with(Vec::new).push(tx);
+        self.notify_quiesced();
+        rx
+    }
+
+    /// Ends quiescing of the instance.
+    pub fn unquiesce(&mut self) {
+        self.quiesce_waiters = None;
+    }
+
+    /// Notifies waiters for quiescing if no work is outstanding on the instance.
+    fn notify_quiesced(&mut self) {
+        if self.outstanding_work() > 0 {
+            return;
+        }
+        if let Some(waiters) = &mut self.quiesce_waiters {
+            for tx in waiters.drain(..) {
+                // The waiter may have lost interest.
+                let _ = tx.send(());
+            }
+        }
+    }
+
     /// Report updates (inserts or retractions) to the identified collection's dependencies.
     ///
     /// # Panics
@@ -526,6 +591,7 @@ where
     ComputeGrpcClient: ComputeClient<T>,
 {
     pub fn new(
+        id: ComputeInstanceId,
         build_info: &'static BuildInfo,
         arranged_logs: BTreeMap<LogVariant, GlobalId>,
         envd_epoch: NonZeroI64,
@@ -533,6 +599,7 @@ where
         response_tx: crossbeam_channel::Sender<ComputeControllerResponse<T>>,
         introspection_tx: crossbeam_channel::Sender<IntrospectionUpdates>,
         enable_aggressive_readhold_downgrades: bool,
+        command_recorder: CommandRecorder,
     ) -> Self {
         let collections = arranged_logs
             .iter()
@@ -544,6 +611,7 @@ where
         let history = ComputeCommandHistory::new(metrics.for_history());
 
         let mut instance = Self {
+            id,
             build_info,
             initialized: false,
             replicas: Default::default(),
@@ -552,6 +620,7 @@ where
             peeks: Default::default(),
             subscribes: Default::default(),
             copy_tos: Default::default(),
+            quiesce_waiters: None,
             history,
             response_tx,
             introspection_tx,
@@ -559,6 +628,7 @@ where
             replica_epochs: Default::default(),
             metrics,
             enable_aggressive_readhold_downgrades,
+            command_recorder,
         };
 
         instance.send(ComputeCommand::CreateTimely {
@@ -614,6 +684,9 @@ where
     /// Sends a command to all replicas of this instance.
     #[tracing::instrument(level = "debug", skip(self))]
     pub fn send(&mut self, cmd: ComputeCommand<T>) {
+        self.command_recorder
+            .record(CommandProtocol::Compute, self.id, &cmd);
+
         // Record the command so that new replicas can be brought up to speed.
         self.history.push(cmd.clone());
 
@@ -846,6 +919,26 @@ where
         Ok(())
     }
 
+    /// Set the idle arrangement merge effort of the given replica.
+    ///
+    /// The merge effort is part of the replica's timely configuration, which cannot change while
+    /// the replica process runs, so a changed value restarts the replica.
+    pub fn set_idle_arrangement_merge_effort(
+        &mut self,
+        id: ReplicaId,
+        value: u32,
+    ) -> Result<(), ReplicaMissing> {
+        let replica = self
+            .compute
+            .replicas
+            .get_mut(&id)
+            .ok_or(ReplicaMissing(id))?;
+        if replica.config.set_idle_arrangement_merge_effort(value) {
+            self.rehydrate_replica(id);
+        }
+        Ok(())
+    }
+
     /// Rehydrate the given instance replica.
     ///
     /// # Panics
@@ -874,6 +967,56 @@ where
         }
     }
 
+    /// Rehydrate all replicas of this instance, making them drop their dataflows and rebuild
+    /// them from the command history.
+    ///
+    /// Peeks, subscribes, and `COPY TO`s are served by the dataflows that are dropped, so they
+    /// are not waited for but fail instead.
+    pub fn rehydrate(&mut self) {
+        const REASON: &str = "compute instance is rehydrating";
+
+        let subscribes = std::mem::take(&mut self.compute.subscribes);
+        for (subscribe_id, subscribe) in subscribes {
+            let response = ComputeControllerResponse::SubscribeResponse(
+                subscribe_id,
+                SubscribeBatch {
+                    lower: subscribe.frontier.clone(),
+                    upper: subscribe.frontier,
+                    updates: Err(REASON.into()),
+                },
+            );
+            self.compute.deliver_response(response);
+        }
+
+        let copy_tos = std::mem::take(&mut self.compute.copy_tos);
+        for sink_id in copy_tos {
+            let response =
+                ComputeControllerResponse::CopyToResponse(sink_id, Err(anyhow::anyhow!(REASON)));
+            self.compute.deliver_response(response);
+        }
+
+        let peeks: Vec<_> = self
+            .compute
+            .peeks
+            .iter()
+            .map(|(uuid, peek)| (*uuid, peek.otel_ctx.clone()))
+            .collect();
+        for (uuid, otel_ctx) in peeks {
+            self.compute
+                .deliver_response(ComputeControllerResponse::PeekResponse(
+                    uuid,
+                    PeekResponse::Error(REASON.into()),
+                    otel_ctx,
+                ));
+            self.remove_peek(uuid);
+        }
+
+        let replica_ids: Vec<_> = self.compute.replica_ids().collect();
+        for replica_id in replica_ids {
+            self.rehydrate_replica(replica_id);
+        }
+    }
+
     /// Create the described dataflows and initializes state for their output.
     pub fn create_dataflow(
         &mut self,
@@ -1497,7 +1640,7 @@ where
         response: ComputeResponse<T>,
         replica_id: ReplicaId,
     ) -> Option<ComputeControllerResponse<T>> {
-        match response {
+        let response = match response {
             ComputeResponse::FrontierUpper { id, upper } => {
                 let old_upper = self
                     .compute
@@ -1534,7 +1677,12 @@ where
                 self.handle_status_response(response, replica_id);
                 None
             }
-        }
+        };
+
+        // The response may have concluded the last outstanding work on a quiescing instance.
+        self.compute.notify_quiesced();
+
+        response
     }
 
     fn handle_frontier_upper(
@@ -1811,6 +1959,7 @@ where
         self.rehydrate_failed_replicas();
         self.downgrade_warmup_capabilities();
         self.compute.cleanup_collections();
+        self.compute.notify_quiesced();
         self.compute.refresh_state_metrics();
     }
 }
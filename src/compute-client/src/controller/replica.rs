@@ -9,6 +9,8 @@
 
 //! A client for replicas of a compute instance.
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::bail;
@@ -44,6 +46,29 @@ pub(super) struct ReplicaConfig {
     pub grpc_client: GrpcClientParameters,
 }
 
+impl ReplicaConfig {
+    /// Overrides the idle arrangement merge effort of the replica.
+    ///
+    /// Returns whether the value changed, in which case the replica must be restarted to apply
+    /// it.
+    pub fn set_idle_arrangement_merge_effort(&mut self, value: u32) -> bool {
+        let changed = self.idle_arrangement_merge_effort != value;
+        self.idle_arrangement_merge_effort = value;
+        changed
+    }
+
+    /// The timely configuration the replica processes are started with.
+    fn timely_config(&self) -> TimelyConfig {
+        TimelyConfig {
+            workers: self.location.workers,
+            process: 0,
+            addresses: self.location.dataflow_addrs.clone(),
+            idle_arrangement_merge_effort: self.idle_arrangement_merge_effort,
+            arrangement_exert_proportionality: self.arrangement_exert_proportionality,
+        }
+    }
+}
+
 /// A client for a replica task.
 #[derive(Debug)]
 pub(super) struct ReplicaClient<T> {
@@ -61,6 +86,9 @@ pub(super) struct ReplicaClient<T> {
     _task: AbortOnDropHandle<()>,
     /// Replica metrics.
     metrics: ReplicaMetrics,
+    /// The number of commands sent to the replica task that it has not yet forwarded to the
+    /// replica.
+    pending_commands: Arc<AtomicUsize>,
 }
 
 impl<T> ReplicaClient<T>
@@ -80,6 +108,7 @@ where
         // the replica.
         let (command_tx, command_rx) = unbounded_channel();
         let (response_tx, response_rx) = unbounded_channel();
+        let pending_commands = Arc::new(AtomicUsize::new(0));
 
         let task = mz_ore::task::spawn(
             || format!("active-replication-replica-{id}"),
@@ -91,6 +120,7 @@ where
                 response_tx,
                 epoch,
                 metrics: metrics.clone(),
+                pending_commands: Arc::clone(&pending_commands),
             }
             .run(),
         );
@@ -100,6 +130,7 @@ where
             response_rx,
             _task: task.abort_on_drop(),
             metrics,
+            pending_commands,
         }
     }
 
@@ -110,6 +141,7 @@ where
     ) -> Result<(), SendError<ComputeCommand<T>>> {
         self.command_tx.send(command).map(|r| {
             self.metrics.inner.command_queue_size.inc();
+            self.pending_commands.fetch_add(1, Ordering::Relaxed);
             r
         })
     }
@@ -125,6 +157,14 @@ where
     }
 }
 
+impl<T> ReplicaClient<T> {
+    /// Returns the number of commands sent to this replica that have not yet been forwarded to
+    /// it.
+    pub(super) fn pending_commands(&self) -> usize {
+        self.pending_commands.load(Ordering::Relaxed)
+    }
+}
+
 /// Configuration for `replica_task`.
 struct ReplicaTask<T> {
     /// The ID of the replica.
@@ -142,6 +182,9 @@ struct ReplicaTask<T> {
     epoch: ClusterStartupEpoch,
     /// Replica metrics.
     metrics: ReplicaMetrics,
+    /// The number of commands sent to the task that it has not yet forwarded to the replica,
+    /// shared with the [`ReplicaClient`].
+    pending_commands: Arc<AtomicUsize>,
 }
 
 impl<T> ReplicaTask<T>
@@ -227,6 +270,7 @@ where
                     self.specialize_command(&mut command);
                     self.observe_command(&command);
                     client.send(command).await?;
+                    self.pending_commands.fetch_sub(1, Ordering::Relaxed);
                 },
                 // Response from replica to forward to controller.
                 response = client.recv() => {
@@ -257,13 +301,7 @@ where
         }
 
         if let ComputeCommand::CreateTimely { config, epoch } = command {
-            *config = TimelyConfig {
-                workers: self.config.location.workers,
-                process: 0,
-                addresses: self.config.location.dataflow_addrs.clone(),
-                idle_arrangement_merge_effort: self.config.idle_arrangement_merge_effort,
-                arrangement_exert_proportionality: self.config.arrangement_exert_proportionality,
-            };
+            *config = self.config.timely_config();
             *epoch = self.epoch;
         }
     }
@@ -290,3 +328,80 @@ where
         self.metrics.inner.response_queue_size.inc();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroI64;
+
+    use mz_build_info::DUMMY_BUILD_INFO;
+    use mz_compute_types::ComputeInstanceId;
+    use mz_ore::metrics::MetricsRegistry;
+
+    use crate::metrics::ComputeControllerMetrics;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_idle_arrangement_merge_effort_override() {
+        let default = ReplicaConfig {
+            location: ClusterReplicaLocation {
+                ctl_addrs: vec!["localhost:2100".into()],
+                dataflow_addrs: vec!["localhost:2102".into()],
+                workers: 1,
+            },
+            logging: LoggingConfig::default(),
+            idle_arrangement_merge_effort: 1000,
+            arrangement_exert_proportionality: 16,
+            grpc_client: GrpcClientParameters::default(),
+        };
+        let mut overridden = default.clone();
+        let other = default.clone();
+
+        assert!(overridden.set_idle_arrangement_merge_effort(5000));
+        // Setting the same value again does not require a restart.
+        assert!(!overridden.set_idle_arrangement_merge_effort(5000));
+
+        assert_eq!(
+            overridden.timely_config().idle_arrangement_merge_effort,
+            5000
+        );
+        assert_eq!(other.timely_config().idle_arrangement_merge_effort, 1000);
+        assert_eq!(
+            overridden.timely_config().arrangement_exert_proportionality,
+            other.timely_config().arrangement_exert_proportionality,
+        );
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `socket` on OS `linux`
+    async fn test_pending_commands() {
+        let config = ReplicaConfig {
+            // Nothing listens here, so the replica task never gets to forward commands.
+            location: ClusterReplicaLocation {
+                ctl_addrs: vec!["localhost:1".into()],
+                dataflow_addrs: vec!["localhost:2".into()],
+                workers: 1,
+            },
+            logging: LoggingConfig::default(),
+            idle_arrangement_merge_effort: 1000,
+            arrangement_exert_proportionality: 16,
+            grpc_client: GrpcClientParameters::default(),
+        };
+        let metrics = ComputeControllerMetrics::new(MetricsRegistry::new())
+            .for_instance(ComputeInstanceId::User(1))
+            .for_replica(ReplicaId::User(1));
+        let client = ReplicaClient::<mz_repr::Timestamp>::spawn(
+            ReplicaId::User(1),
+            &DUMMY_BUILD_INFO,
+            config,
+            ClusterStartupEpoch::new(NonZeroI64::new(1).unwrap(), 0),
+            metrics,
+        );
+        assert_eq!(client.pending_commands(), 0);
+
+        client.send(ComputeCommand::InitializationComplete).unwrap();
+        client.send(ComputeCommand::InitializationComplete).unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(client.pending_commands(), 2);
+    }
+}
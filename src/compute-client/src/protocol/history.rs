@@ -254,3 +254,93 @@ where
         self.commands.iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_cluster_client::client::{ClusterStartupEpoch, TimelyConfig};
+    use mz_compute_types::dataflows::{DataflowDescription, IndexDesc};
+    use mz_compute_types::plan::Plan;
+    use mz_compute_types::ComputeInstanceId;
+    use mz_ore::metrics::MetricsRegistry;
+    use mz_repr::{GlobalId, RelationType, Timestamp};
+
+    use crate::metrics::ComputeControllerMetrics;
+    use crate::protocol::command::InstanceConfig;
+
+    use super::*;
+
+    fn dataflow(id: GlobalId) -> DataflowDescription<Plan<Timestamp>, (), Timestamp> {
+        let index = IndexDesc {
+            on_id: GlobalId::User(0),
+            key: Vec::new(),
+        };
+        DataflowDescription {
+            source_imports: BTreeMap::new(),
+            index_imports: BTreeMap::new(),
+            objects_to_build: Vec::new(),
+            index_exports: BTreeMap::from([(id, (index, RelationType::empty()))]),
+            sink_exports: BTreeMap::new(),
+            as_of: Some(Antichain::from_elem(Timestamp::from(1))),
+            until: Antichain::new(),
+            debug_name: id.to_string(),
+        }
+    }
+
+    /// Rehydrating a replica replays the reduced history, which must create all dataflows that
+    /// are still installed, as of their compacted frontiers.
+    #[mz_ore::test]
+    fn test_reduced_history_recreates_installed_dataflows() {
+        let metrics = ComputeControllerMetrics::new(MetricsRegistry::new())
+            .for_instance(ComputeInstanceId::User(1))
+            .for_history();
+        let mut history = ComputeCommandHistory::<_, Timestamp>::new(metrics);
+        history.push(ComputeCommand::CreateTimely {
+            config: TimelyConfig::default(),
+            epoch: ClusterStartupEpoch::new(1.try_into().unwrap(), 0),
+        });
+        history.push(ComputeCommand::CreateInstance(InstanceConfig {
+            logging: Default::default(),
+        }));
+        let ids: Vec<_> = (1..=3).map(GlobalId::User).collect();
+        for id in &ids {
+            history.push(ComputeCommand::CreateDataflow(dataflow(*id)));
+        }
+        // The second dataflow was dropped, the third one compacted.
+        history.push(ComputeCommand::AllowCompaction {
+            id: ids[1],
+            frontier: Antichain::new(),
+        });
+        history.push(ComputeCommand::AllowCompaction {
+            id: ids[2],
+            frontier: Antichain::from_elem(Timestamp::from(5)),
+        });
+        history.push(ComputeCommand::InitializationComplete);
+
+        history.reduce();
+        let created: Vec<_> = history
+            .iter()
+            .filter_map(|command| match command {
+                ComputeCommand::CreateDataflow(dataflow) => {
+                    let as_of = dataflow.as_of.clone().expect("as_of set");
+                    Some((dataflow.debug_name.clone(), as_of))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            created,
+            vec![
+                (ids[0].to_string(), Antichain::from_elem(Timestamp::from(1))),
+                (ids[2].to_string(), Antichain::from_elem(Timestamp::from(5))),
+            ]
+        );
+        assert!(matches!(
+            history.iter().next(),
+            Some(ComputeCommand::CreateTimely { .. })
+        ));
+        assert!(matches!(
+            history.iter().last(),
+            Some(ComputeCommand::InitializationComplete)
+        ));
+    }
+}
@@ -23,6 +23,7 @@ use differential_dataflow::consolidation::consolidate_updates;
 use differential_dataflow::lattice::Lattice;
 use mz_ore::cast::CastFrom;
 use mz_repr::{Diff, GlobalId, Row};
+use mz_service::chunk::ChunkedCommand;
 use mz_service::client::{GenericClient, Partitionable, PartitionedState};
 use mz_service::grpc::{GrpcClient, GrpcServer, ProtoServiceTypes, ResponseStream};
 use timely::progress::frontier::{Antichain, MutableAntichain};
@@ -69,6 +70,9 @@ impl ProtoServiceTypes for ComputeProtoServiceTypes {
     const URL: &'static str = "/mz_compute_client.service.ProtoCompute/CommandResponseStream";
 }
 
+/// Compute commands are always sent whole.
+impl ChunkedCommand for ProtoComputeCommand {}
+
 /// TODO(#25239): Add documentation.
 pub type ComputeGrpcClient = GrpcClient<ComputeProtoServiceTypes>;
 
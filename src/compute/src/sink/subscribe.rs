@@ -188,8 +188,12 @@ impl SubscribeProtocol {
     /// Attempt to send a batch of rows with the given `upper`.
     ///
     /// This method filters the updates to send based on the provided `upper`. Updates are only
-    /// sent when their times are before `upper`. If `upper` has not advanced far enough, no batch
-    /// will be sent. `rows` and `errors` that have been sent are drained from their respective
+    /// sent when their times are before `upper`. An `upper` before the sink's `as_of` is advanced
+    /// to it: The subscribe never emits updates at times before its `as_of`, so its output is
+    /// complete up to there even while its inputs are still behind. This way, a subscribe that
+    /// waits for its inputs to reach its `as_of` promptly sends a batch without updates, telling
+    /// clients that it is alive. If `upper` has not advanced since the last batch, no batch will
+    /// be sent. `rows` and `errors` that have been sent are drained from their respective
     /// vectors, only entries that have not been sent remain after the call returns. The caller is
     /// expected to re-submit these entries, potentially along with new ones, at a later `upper`.
     ///
@@ -203,10 +207,13 @@ impl SubscribeProtocol {
         rows: &mut Vec<(Timestamp, Row, Diff)>,
         errors: &mut Vec<(Timestamp, DataflowError, Diff)>,
     ) {
-        // Only send a batch if both conditions hold:
-        //  a) `upper` has reached or passed the sink's `as_of` frontier.
-        //  b) `upper` is different from when we last sent a batch.
-        if !PartialOrder::less_equal(&self.sink_as_of, &upper) || upper == self.prev_upper {
+        let upper = if PartialOrder::less_equal(&self.sink_as_of, &upper) {
+            upper
+        } else {
+            self.sink_as_of.clone()
+        };
+        // Only send a batch if `upper` is different from when we last sent a batch.
+        if upper == self.prev_upper {
             return;
         }
 
@@ -270,3 +277,73 @@ impl Drop for SubscribeProtocol {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn protocol(
+        as_of: u64,
+    ) -> (
+        SubscribeProtocol,
+        Rc<RefCell<Vec<(GlobalId, SubscribeResponse)>>>,
+    ) {
+        let buffer = Rc::new(RefCell::new(Vec::new()));
+        let protocol = SubscribeProtocol {
+            sink_id: GlobalId::User(1),
+            sink_as_of: Antichain::from_elem(as_of.into()),
+            subscribe_response_buffer: Some(Rc::clone(&buffer)),
+            prev_upper: Antichain::from_elem(Timestamp::minimum()),
+            poison: None,
+        };
+        (protocol, buffer)
+    }
+
+    fn frontier(t: u64) -> Antichain<Timestamp> {
+        Antichain::from_elem(t.into())
+    }
+
+    #[mz_ore::test]
+    fn test_waiting_subscribe_sends_progress() {
+        let (mut protocol, buffer) = protocol(10);
+
+        // The inputs are behind the `as_of`, yet the subscribe reports progress up to it.
+        protocol.send_batch(frontier(5), &mut Vec::new(), &mut Vec::new());
+        let batches = std::mem::take(&mut *buffer.borrow_mut());
+        assert_eq!(
+            batches,
+            vec![(
+                GlobalId::User(1),
+                SubscribeResponse::Batch(SubscribeBatch {
+                    lower: frontier(0),
+                    upper: frontier(10),
+                    updates: Ok(Vec::new()),
+                }),
+            )]
+        );
+
+        // Only once.
+        protocol.send_batch(frontier(7), &mut Vec::new(), &mut Vec::new());
+        assert!(buffer.borrow().is_empty());
+
+        // Once the inputs pass the `as_of`, updates follow as usual.
+        let mut rows = vec![
+            (10.into(), Row::default(), 1),
+            (12.into(), Row::default(), 1),
+        ];
+        protocol.send_batch(frontier(11), &mut rows, &mut Vec::new());
+        let batches = std::mem::take(&mut *buffer.borrow_mut());
+        assert_eq!(
+            batches,
+            vec![(
+                GlobalId::User(1),
+                SubscribeResponse::Batch(SubscribeBatch {
+                    lower: frontier(10),
+                    upper: frontier(11),
+                    updates: Ok(vec![(10.into(), Row::default(), 1)]),
+                }),
+            )]
+        );
+        assert_eq!(rows, vec![(12.into(), Row::default(), 1)]);
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeSet;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use mz_controller::watch_sets::WatchSets;
+use mz_ore::metrics::MetricsRegistry;
+use mz_repr::{GlobalId, Timestamp};
+use timely::progress::Antichain;
+
+/// Advances the frontier of a collection that is watched by `pending` watch sets at timestamps
+/// far beyond it, one tick at a time. Each tick finishes a single watch set, and a new one is
+/// installed in its place, so the cost of a tick should not depend on the number of pending
+/// watch sets.
+fn bench_frontier_tick(c: &mut Criterion) {
+    let id = GlobalId::User(1);
+    for pending in [100_u64, 1_000, 10_000] {
+        c.bench_with_input(
+            BenchmarkId::new("frontier_tick", pending),
+            &pending,
+            |b, &pending| {
+                let mut watch_sets = WatchSets::new(&MetricsRegistry::new());
+                for t in 0..pending {
+                    watch_sets.install(BTreeSet::from([id]), Timestamp::from(t), Box::new(()));
+                }
+                let mut frontier = 0;
+                b.iter(|| {
+                    frontier += 1;
+                    let update = (id, Antichain::from_elem(Timestamp::from(frontier)));
+                    let finished = watch_sets.update(black_box(&[update]));
+                    assert_eq!(finished.len(), 1);
+                    let t = Timestamp::from(frontier + pending - 1);
+                    watch_sets.install(BTreeSet::from([id]), t, Box::new(()));
+                })
+            },
+        );
+    }
+}
+
+criterion_group!(benches, bench_frontier_tick);
+criterion_main!(benches);
@@ -0,0 +1,291 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Tracks how fast the write frontiers of collections advance relative to the wall clock.
+//!
+//! Whenever frontiers are recorded, the advancement of the write frontier of each collection in
+//! the epoch-milliseconds timeline since the previous recording is divided by the wall-clock time
+//! that passed, and folded into an exponentially weighted moving average over about a minute. A
+//! rate of 1.0 means that the collection keeps up with the wall clock, and a rate below 1.0 that
+//! it falls behind.
+//!
+//! The rates are recorded only when they have changed by more than the hysteresis threshold, so
+//! that the jitter of frontier advancement doesn't churn the introspection collection. Only the
+//! slowest collections are reported as metrics, to bound their cardinality.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use mz_ore::cast::CastLossy;
+use mz_ore::metric;
+use mz_ore::metrics::{DeleteOnDropGauge, GaugeVec, GaugeVecExt, MetricsRegistry};
+use mz_ore::now::EpochMillis;
+use mz_repr::{Diff, GlobalId, Timestamp};
+use timely::progress::Antichain;
+
+/// The default period over which rates are averaged.
+pub const DEFAULT_ADVANCEMENT_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// The default change of a rate beyond which it is recorded again.
+pub const DEFAULT_ADVANCEMENT_RATE_HYSTERESIS: f64 = 0.05;
+
+/// The default number of slowest collections whose rates are reported as metrics.
+pub const DEFAULT_ADVANCEMENT_RATE_GAUGES: usize = 20;
+
+type RateGauge = DeleteOnDropGauge<'static, <GaugeVec as GaugeVecExt>::GaugeType, Vec<String>>;
+
+/// A recorded rate of a collection, to be inserted or retracted.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct RecordedRate {
+    pub(crate) id: GlobalId,
+    pub(crate) rate: f64,
+    pub(crate) measured_at: EpochMillis,
+}
+
+#[derive(Debug)]
+struct RateState {
+    /// The write frontier at the previous observation.
+    frontier: Timestamp,
+    /// The wall-clock time of the previous observation.
+    observed_at: EpochMillis,
+    /// The average rate, once the frontier has been observed twice.
+    rate: Option<f64>,
+}
+
+/// Maintains the rates at which the write frontiers of collections advance.
+#[derive(Debug)]
+pub(crate) struct AdvancementRates {
+    /// The period over which rates are averaged.
+    pub(crate) window: Duration,
+    /// The change of a rate beyond which it is recorded again.
+    pub(crate) hysteresis: f64,
+    /// The number of slowest collections whose rates are reported as metrics.
+    pub(crate) gauge_limit: usize,
+    states: BTreeMap<GlobalId, RateState>,
+    /// The rates that are currently recorded.
+    recorded: BTreeMap<GlobalId, RecordedRate>,
+    rates: GaugeVec,
+    /// The gauges of the slowest collections.
+    gauges: BTreeMap<GlobalId, RateGauge>,
+}
+
+impl AdvancementRates {
+    pub(crate) fn new(metrics_registry: &MetricsRegistry) -> Self {
+        Self {
+            window: DEFAULT_ADVANCEMENT_RATE_WINDOW,
+            hysteresis: DEFAULT_ADVANCEMENT_RATE_HYSTERESIS,
+            gauge_limit: DEFAULT_ADVANCEMENT_RATE_GAUGES,
+            states: BTreeMap::new(),
+            recorded: BTreeMap::new(),
+            rates: metrics_registry.register(metric!(
+                name: "mz_controller_write_frontier_advancement_rate",
+                help: "The rate at which the write frontier of a collection advances relative to \
+                       the wall clock, for the slowest collections.",
+                var_labels: ["collection_id"],
+            )),
+            gauges: BTreeMap::new(),
+        }
+    }
+
+    /// Folds the write `frontiers` observed at `now` into the rates, returning the changes to
+    /// the recorded rates.
+    ///
+    /// Collections that are not observed anymore, e.g. because they were dropped or sealed, are
+    /// forgotten.
+    pub(crate) fn observe(
+        &mut self,
+        now: EpochMillis,
+        frontiers: impl Iterator<Item = (GlobalId, Antichain<Timestamp>)>,
+    ) -> Vec<(RecordedRate, Diff)> {
+        let window = f64::cast_lossy(u64::try_from(self.window.as_millis()).unwrap_or(u64::MAX));
+        let mut states = BTreeMap::new();
+        for (id, frontier) in frontiers {
+            let Some(frontier) = frontier.into_option() else {
+                continue;
+            };
+            let state = match self.states.remove(&id) {
+                Some(state) if now > state.observed_at => {
+                    let elapsed = now - state.observed_at;
+                    let advanced = u64::from(frontier).saturating_sub(state.frontier.into());
+                    let sample = f64::cast_lossy(advanced) / f64::cast_lossy(elapsed);
+                    let rate = match state.rate {
+                        Some(rate) => {
+                            let alpha = 1.0 - (-f64::cast_lossy(elapsed) / window).exp();
+                            rate + alpha * (sample - rate)
+                        }
+                        None => sample,
+                    };
+                    RateState {
+                        frontier: frontier.max(state.frontier),
+                        observed_at: now,
+                        rate: Some(rate),
+                    }
+                }
+                // The wall clock has not advanced since the previous observation.
+                Some(state) => state,
+                None => RateState {
+                    frontier,
+                    observed_at: now,
+                    rate: None,
+                },
+            };
+            states.insert(id, state);
+        }
+        self.states = states;
+
+        let mut updates = Vec::new();
+        let mut recorded = BTreeMap::new();
+        for (id, state) in &self.states {
+            let Some(rate) = state.rate else {
+                continue;
+            };
+            let old = self.recorded.remove(id);
+            let new = match old {
+                Some(old) if (rate - old.rate).abs() <= self.hysteresis => old,
+                old => {
+                    if let Some(old) = old {
+                        updates.push((old, -1));
+                    }
+                    let new = RecordedRate {
+                        id: *id,
+                        rate,
+                        measured_at: now,
+                    };
+                    updates.push((new.clone(), 1));
+                    new
+                }
+            };
+            recorded.insert(*id, new);
+        }
+        for (_id, old) in std::mem::replace(&mut self.recorded, recorded) {
+            updates.push((old, -1));
+        }
+
+        self.update_gauges();
+        updates
+    }
+
+    /// Reports the current rates of the slowest collections as metrics, removing the metrics of
+    /// all others.
+    fn update_gauges(&mut self) {
+        let mut slowest: Vec<_> = self
+            .states
+            .iter()
+            .filter_map(|(id, state)| Some((state.rate?, *id)))
+            .collect();
+        slowest.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        slowest.truncate(self.gauge_limit);
+
+        let mut gauges = BTreeMap::new();
+        for (rate, id) in slowest {
+            let gauge = self
+                .gauges
+                .remove(&id)
+                .unwrap_or_else(|| self.rates.get_delete_on_drop_gauge(vec![id.to_string()]));
+            gauge.set(rate);
+            gauges.insert(id, gauge);
+        }
+        // Dropping the gauges of the other collections removes their metrics.
+        self.gauges = gauges;
+    }
+
+    /// Returns the current rate of collection `id`, if it has been observed at least twice.
+    #[cfg(test)]
+    fn rate(&self, id: GlobalId) -> Option<f64> {
+        self.states.get(&id).and_then(|state| state.rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frontier(t: u64) -> Antichain<Timestamp> {
+        Antichain::from_elem(Timestamp::from(t))
+    }
+
+    #[mz_ore::test]
+    fn test_advancement_rates() {
+        let registry = MetricsRegistry::new();
+        let mut rates = AdvancementRates::new(&registry);
+        rates.gauge_limit = 1;
+
+        let (keeping_up, falling_behind) = (GlobalId::User(1), GlobalId::User(2));
+        let tick = |rates: &mut AdvancementRates, now: u64, fast: u64, slow: u64| {
+            rates.observe(
+                now,
+                [
+                    (keeping_up, frontier(fast)),
+                    (falling_behind, frontier(slow)),
+                ]
+                .into_iter(),
+            )
+        };
+
+        // Nothing is recorded until a collection has been observed twice.
+        let mut now = 1_000_000;
+        let (mut fast, mut slow) = (now - 1_000, now - 60_000);
+        assert!(tick(&mut rates, now, fast, slow).is_empty());
+
+        // One collection advances with the wall clock, the other at half its speed.
+        let mut all_updates = Vec::new();
+        for _ in 0..120 {
+            now += 1_000;
+            fast += 1_000;
+            slow += 500;
+            all_updates.extend(tick(&mut rates, now, fast, slow));
+        }
+        assert!((rates.rate(keeping_up).unwrap() - 1.0).abs() < 1e-9);
+        assert!((rates.rate(falling_behind).unwrap() - 0.5).abs() < 1e-9);
+
+        // The rates are recorded once, as they haven't changed since their first measurement.
+        let inserted: Vec<_> = all_updates
+            .iter()
+            .filter(|(_, diff)| *diff == 1)
+            .map(|(rate, _)| rate.id)
+            .collect();
+        assert_eq!(inserted, vec![keeping_up, falling_behind]);
+
+        // Only the slowest collection is reported as a metric.
+        assert_eq!(
+            rates.gauges.keys().collect::<Vec<_>>(),
+            vec![&falling_behind]
+        );
+        assert!((rates.gauges[&falling_behind].get() - 0.5).abs() < 1e-9);
+
+        // A stalled collection's rate decays over about a minute. Changes within the hysteresis
+        // threshold are not recorded.
+        let updates = tick(&mut rates, now + 1_000, fast, slow + 500);
+        assert!(updates.is_empty(), "{updates:?}");
+        let mut updates = Vec::new();
+        for i in 2..=60 {
+            updates.extend(tick(&mut rates, now + i * 1_000, fast, slow + 500));
+        }
+        let rate = rates.rate(keeping_up).unwrap();
+        assert!(rate > 0.3 && rate < 0.4, "{rate}");
+        // Each recorded change retracts the previous rate, which is more than the threshold away.
+        let changes: Vec<_> = updates
+            .iter()
+            .filter(|(rate, _)| rate.id == keeping_up)
+            .collect();
+        assert!(!changes.is_empty());
+        for pair in changes.chunks(2) {
+            let [(old, -1), (new, 1)] = pair else {
+                panic!("unexpected updates: {pair:?}");
+            };
+            assert!(old.rate - new.rate > rates.hysteresis);
+        }
+
+        // Forgotten collections have their rates retracted.
+        let updates = rates.observe(now + 61_000, std::iter::empty());
+        assert_eq!(updates.len(), 2);
+        assert!(updates.iter().all(|(_, diff)| *diff == -1));
+        assert!(rates.gauges.is_empty());
+    }
+}
@@ -9,7 +9,7 @@
 
 //! Cluster management.
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -23,9 +23,10 @@ use mz_compute_client::logging::LogVariant;
 use mz_compute_client::service::{ComputeClient, ComputeGrpcClient};
 use mz_compute_types::ComputeInstanceId;
 use mz_controller_types::{is_cluster_size_v2, ClusterId, ReplicaId};
+use mz_orchestrator::scheduling_config::ServiceCrashLoopConfig;
 use mz_orchestrator::{
-    CpuLimit, DiskLimit, LabelSelectionLogic, LabelSelector, MemoryLimit, Service, ServiceConfig,
-    ServiceEvent, ServicePort,
+    CpuLimit, DiskLimit, LabelSelectionLogic, LabelSelector, MemoryLimit, NotReadyReason,
+    ResourceLimits, Service, ServiceConfig, ServiceEvent, ServicePort,
 };
 use mz_ore::halt;
 use mz_ore::task::AbortOnDropHandle;
@@ -35,7 +36,8 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use timely::progress::Timestamp;
-use tracing::{error, instrument, warn};
+use tokio::time::Instant;
+use tracing::{error, info, instrument, warn};
 
 use crate::Controller;
 
@@ -200,6 +202,7 @@ impl ReplicaLocation {
 
 /// The "role" of a cluster, which is currently used to determine the
 /// severity of alerts for problems with its replicas.
+#[derive(Clone, Copy, Debug)]
 pub enum ClusterRole {
     /// The existence and proper functioning of the cluster's replicas is
     /// business-critical for Materialize.
@@ -297,6 +300,172 @@ pub struct ClusterEvent {
     pub time: DateTime<Utc>,
 }
 
+/// A replica that was found crash-looping and is held back from restarting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicaCrashLoop {
+    pub cluster_id: ClusterId,
+    pub replica_id: ReplicaId,
+    /// The number of restarts observed within the crash-loop window.
+    pub restarts: usize,
+    /// How long the replica is held back before it is scheduled again.
+    pub backoff: Duration,
+    /// The reason of the last observed failure, if known.
+    pub last_failure: Option<NotReadyReason>,
+    /// The time of the restart that revealed the crash loop.
+    pub time: DateTime<Utc>,
+}
+
+/// The restarts observed for a replica.
+#[derive(Debug, Default)]
+struct ReplicaRestarts {
+    /// The times of the restarts within the crash-loop window.
+    restarts: VecDeque<DateTime<Utc>>,
+    /// The number of times the replica was held back since it was last healthy.
+    backoffs: u32,
+    /// The time since which the replica has been ready, if it is.
+    ready_since: Option<DateTime<Utc>>,
+    /// The reason of the last observed failure, if known.
+    last_failure: Option<NotReadyReason>,
+}
+
+/// Detects replicas that crash-loop, e.g. because their dataflows don't fit into memory, and
+/// tracks for how long they are held back from restarting.
+///
+/// Each report of a replica process not being ready counts as a restart. Once a replica restarts
+/// more than `max_restarts` times within `window`, it is held back for an exponentially growing
+/// backoff. A replica that stays ready for `healthy_period` has its backoff reset.
+#[derive(Debug)]
+pub(crate) struct CrashLoops {
+    pub(crate) config: ServiceCrashLoopConfig,
+    /// The restarts observed for each replica.
+    replicas: BTreeMap<ReplicaId, ReplicaRestarts>,
+    /// The replicas that are held back, and when to schedule them again.
+    held_back: BTreeMap<ReplicaId, Instant>,
+}
+
+impl CrashLoops {
+    pub(crate) fn new(config: ServiceCrashLoopConfig) -> Self {
+        Self {
+            config,
+            replicas: BTreeMap::new(),
+            held_back: BTreeMap::new(),
+        }
+    }
+
+    /// Observes a status change of a replica process, returning the crash loop it reveals, if
+    /// any. Replicas found crash-looping are held back, starting at `now`.
+    pub(crate) fn observe(
+        &mut self,
+        event: &ClusterEvent,
+        now: Instant,
+    ) -> Option<ReplicaCrashLoop> {
+        // Processes of held back replicas are expected to go away.
+        if self.held_back.contains_key(&event.replica_id) {
+            return None;
+        }
+
+        let config = &self.config;
+        let replica = self.replicas.entry(event.replica_id).or_default();
+        let reason = match event.status {
+            ClusterStatus::Ready => {
+                replica.ready_since.get_or_insert(event.time);
+                return None;
+            }
+            ClusterStatus::NotReady(reason) => reason,
+        };
+
+        if let Some(ready_since) = replica.ready_since.take() {
+            let healthy = (event.time - ready_since)
+                .to_std()
+                .map_or(false, |ready_for| ready_for >= config.healthy_period);
+            if healthy {
+                replica.restarts.clear();
+                replica.backoffs = 0;
+            }
+        }
+        replica.restarts.push_back(event.time);
+        while let Some(restart) = replica.restarts.front() {
+            let expired = (event.time - *restart)
+                .to_std()
+                .map_or(false, |age| age > config.window);
+            if !expired {
+                break;
+            }
+            replica.restarts.pop_front();
+        }
+        if reason.is_some() {
+            replica.last_failure = reason;
+        }
+
+        if replica.restarts.len() <= config.max_restarts {
+            return None;
+        }
+        let backoff = 2u32
+            .checked_pow(replica.backoffs)
+            .and_then(|factor| config.initial_backoff.checked_mul(factor))
+            .map_or(config.max_backoff, |backoff| {
+                backoff.min(config.max_backoff)
+            });
+        replica.backoffs = replica.backoffs.saturating_add(1);
+        let restarts = replica.restarts.len();
+        replica.restarts.clear();
+        self.held_back.insert(event.replica_id, now + backoff);
+
+        info!(
+            replica_id = %event.replica_id,
+            restarts,
+            ?backoff,
+            "replica is crash-looping, backing off"
+        );
+        Some(ReplicaCrashLoop {
+            cluster_id: event.cluster_id,
+            replica_id: event.replica_id,
+            restarts,
+            backoff,
+            last_failure: replica.last_failure,
+            time: event.time,
+        })
+    }
+
+    /// Reports whether `replica_id` is held back.
+    pub(crate) fn is_held_back(&self, replica_id: ReplicaId) -> bool {
+        self.held_back.contains_key(&replica_id)
+    }
+
+    /// Returns the earliest time at which a held back replica is to be scheduled again.
+    pub(crate) fn next_reschedule(&self) -> Option<Instant> {
+        self.held_back.values().min().copied()
+    }
+
+    /// Removes and returns the held back replicas that are to be scheduled again at `now`.
+    pub(crate) fn take_due(&mut self, now: Instant) -> Vec<ReplicaId> {
+        let mut due = Vec::new();
+        self.held_back.retain(|replica_id, until| {
+            let held = *until > now;
+            if !held {
+                due.push(*replica_id);
+            }
+            held
+        });
+        due
+    }
+
+    /// Forgets about `replica_id`, e.g. because it was dropped.
+    pub(crate) fn remove_replica(&mut self, replica_id: ReplicaId) {
+        self.replicas.remove(&replica_id);
+        self.held_back.remove(&replica_id);
+    }
+}
+
+/// What is needed to provision a managed replica again after it was held back.
+#[derive(Debug, Clone)]
+pub(crate) struct ManagedReplica {
+    cluster_id: ClusterId,
+    role: ClusterRole,
+    location: ManagedReplicaLocation,
+    enable_worker_core_affinity: bool,
+}
+
 /// A struct describing a replica that needs to be created,
 /// using `Controller::create_replicas`.
 pub struct CreateReplicaConfig {
@@ -355,6 +524,8 @@ where
             storage_location: ClusterReplicaLocation,
             compute_location: ClusterReplicaLocation,
             metrics_task_join_handle: Option<AbortOnDropHandle<()>>,
+            managed: Option<ManagedReplica>,
+            resource_limits: Option<ResourceLimits>,
         }
 
         // Reborrow the `&mut self` as immutable, as all the concurrent work to be processed in
@@ -399,11 +570,19 @@ where
                                 storage_location,
                                 compute_location,
                                 metrics_task_join_handle: None,
+                                managed: None,
+                                resource_limits: None,
                             },
                         ))
                     }
                     ReplicaLocation::Managed(m) => {
                         let workers = m.allocation.workers;
+                        let managed = ManagedReplica {
+                            cluster_id,
+                            role,
+                            location: m.clone(),
+                            enable_worker_core_affinity,
+                        };
                         let (service, metrics_task_join_handle) = this
                             .provision_replica(
                                 cluster_id,
@@ -431,6 +610,8 @@ where
                                 storage_location,
                                 compute_location,
                                 metrics_task_join_handle: Some(metrics_task_join_handle),
+                                managed: Some(managed),
+                                resource_limits: Some(service.resource_limits()),
                             },
                         ))
                     }
@@ -470,11 +651,20 @@ where
                 storage_location: _,
                 compute_location,
                 metrics_task_join_handle,
+                managed,
+                resource_limits,
             } in replicas
             {
                 if let Some(jh) = metrics_task_join_handle {
                     self.metrics_tasks.insert(replica_id, jh);
                 }
+                if let Some(managed) = managed {
+                    self.managed_replicas.insert(replica_id, managed);
+                }
+                if let Some(resource_limits) = resource_limits {
+                    self.replica_resource_limits
+                        .insert(replica_id, resource_limits);
+                }
                 self.active_compute().add_replica_to_instance(
                     cluster_id,
                     replica_id,
@@ -499,6 +689,10 @@ where
         // provisioned.
         self.deprovision_replica(cluster_id, replica_id).await?;
         self.metrics_tasks.remove(&replica_id);
+        self.disk_high_water_marks.remove_replica(replica_id);
+        self.managed_replicas.remove(&replica_id);
+        self.replica_resource_limits.remove(&replica_id);
+        self.crash_loops.remove_replica(replica_id);
 
         self.active_compute().drop_replica(cluster_id, replica_id)?;
         self.storage.drop_replica(cluster_id, replica_id);
@@ -569,6 +763,83 @@ where
         Ok(())
     }
 
+    /// Observes a status change of a replica process, to detect replicas that crash-loop.
+    ///
+    /// Managed replicas found crash-looping are deprovisioned and provisioned again after a
+    /// backoff, and reported in a [`ControllerResponse::ReplicaCrashLooping`].
+    ///
+    /// [`ControllerResponse::ReplicaCrashLooping`]: crate::ControllerResponse::ReplicaCrashLooping
+    pub fn observe_cluster_event(&mut self, event: &ClusterEvent) {
+        // Unmanaged replicas are not under our control.
+        if !self.managed_replicas.contains_key(&event.replica_id) {
+            return;
+        }
+        if let Some(crash_loop) = self.crash_loops.observe(event, Instant::now()) {
+            self.pending_crash_loops.push(crash_loop);
+        }
+    }
+
+    /// Reports whether `replica_id` is held back from restarting because it was crash-looping.
+    pub fn replica_backing_off(&self, replica_id: ReplicaId) -> bool {
+        self.crash_loops.is_held_back(replica_id)
+    }
+
+    /// Returns the resource limits the orchestrator applied to each process of `replica_id`.
+    ///
+    /// Returns `None` for replicas that are not provisioned by the controller, which includes
+    /// unmanaged replicas and replicas that are held back. The limits can be paired with the
+    /// [`ServiceProcessMetrics`] of the replica's processes reported in
+    /// [`ControllerResponse::ComputeReplicaMetrics`] to compute their utilization, see
+    /// [`ResourceLimits::cpu_utilization`] and [`ResourceLimits::memory_utilization`].
+    ///
+    /// [`ServiceProcessMetrics`]: mz_orchestrator::ServiceProcessMetrics
+    /// [`ControllerResponse::ComputeReplicaMetrics`]: crate::ControllerResponse::ComputeReplicaMetrics
+    pub fn replica_resource_limits(&self, replica_id: ReplicaId) -> Option<ResourceLimits> {
+        self.replica_resource_limits.get(&replica_id).copied()
+    }
+
+    /// Deprovisions the replicas of `crash_loops`, to hold them back until their backoff
+    /// elapses.
+    pub(crate) async fn hold_back_replicas(
+        &mut self,
+        crash_loops: &[ReplicaCrashLoop],
+    ) -> Result<(), anyhow::Error> {
+        for crash_loop in crash_loops {
+            // The replica may have been dropped in the meantime.
+            if !self.managed_replicas.contains_key(&crash_loop.replica_id) {
+                continue;
+            }
+            self.deprovision_replica(crash_loop.cluster_id, crash_loop.replica_id)
+                .await?;
+            self.metrics_tasks.remove(&crash_loop.replica_id);
+            self.replica_resource_limits.remove(&crash_loop.replica_id);
+        }
+        Ok(())
+    }
+
+    /// Provisions the held back replicas whose backoff has elapsed again.
+    pub(crate) async fn reschedule_replicas(&mut self) -> Result<(), anyhow::Error> {
+        for replica_id in self.crash_loops.take_due(Instant::now()) {
+            let Some(managed) = self.managed_replicas.get(&replica_id).cloned() else {
+                continue;
+            };
+            info!(%replica_id, "rescheduling crash-looping replica");
+            let (service, metrics_task) = self
+                .provision_replica(
+                    managed.cluster_id,
+                    replica_id,
+                    managed.role,
+                    managed.location,
+                    managed.enable_worker_core_affinity,
+                )
+                .await?;
+            self.metrics_tasks.insert(replica_id, metrics_task);
+            self.replica_resource_limits
+                .insert(replica_id, service.resource_limits());
+        }
+        Ok(())
+    }
+
     pub fn events_stream(&self) -> BoxStream<'static, ClusterEvent> {
         fn translate_event(event: ServiceEvent) -> Result<ClusterEvent, anyhow::Error> {
             let (cluster_id, replica_id) = parse_replica_service_name(&event.service_id)?;
@@ -807,3 +1078,142 @@ fn parse_replica_service_name(
     let replica_id = caps.get(2).unwrap().as_str().parse().unwrap();
     Ok((cluster_id, replica_id))
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn config() -> ServiceCrashLoopConfig {
+        ServiceCrashLoopConfig {
+            max_restarts: 2,
+            window: Duration::from_secs(60),
+            initial_backoff: Duration::from_secs(10),
+            max_backoff: Duration::from_secs(25),
+            healthy_period: Duration::from_secs(120),
+        }
+    }
+
+    /// An event like the orchestrator reports it for process 0 of replica `u1`, `secs` seconds
+    /// after the epoch.
+    fn event(secs: i64, status: ClusterStatus) -> ClusterEvent {
+        ClusterEvent {
+            cluster_id: ClusterId::User(1),
+            replica_id: ReplicaId::User(1),
+            process_id: 0,
+            status,
+            time: Utc.timestamp_opt(secs, 0).unwrap(),
+        }
+    }
+
+    fn oom_killed(secs: i64) -> ClusterEvent {
+        event(
+            secs,
+            ClusterStatus::NotReady(Some(NotReadyReason::OomKilled)),
+        )
+    }
+
+    #[mz_ore::test]
+    fn test_crash_loop_backoff() {
+        let mut crash_loops = CrashLoops::new(config());
+        let replica_id = ReplicaId::User(1);
+        let now = Instant::now();
+
+        // Restarts that are spread out don't reveal a crash loop.
+        assert_eq!(crash_loops.observe(&oom_killed(0), now), None);
+        assert_eq!(crash_loops.observe(&oom_killed(50), now), None);
+        assert_eq!(crash_loops.observe(&oom_killed(100), now), None);
+
+        // Rapid restarts do, and hold the replica back.
+        let crash_loop = crash_loops.observe(&oom_killed(105), now);
+        assert_eq!(
+            crash_loop,
+            Some(ReplicaCrashLoop {
+                cluster_id: ClusterId::User(1),
+                replica_id,
+                restarts: 3,
+                backoff: Duration::from_secs(10),
+                last_failure: Some(NotReadyReason::OomKilled),
+                time: Utc.timestamp_opt(105, 0).unwrap(),
+            })
+        );
+        assert!(crash_loops.is_held_back(replica_id));
+        assert_eq!(
+            crash_loops.next_reschedule(),
+            Some(now + Duration::from_secs(10))
+        );
+
+        // The processes of the held back replica going away are not restarts.
+        assert_eq!(
+            crash_loops.observe(&event(106, ClusterStatus::NotReady(None)), now),
+            None
+        );
+        assert_eq!(crash_loops.take_due(now), vec![]);
+        assert_eq!(
+            crash_loops.take_due(now + Duration::from_secs(10)),
+            vec![replica_id]
+        );
+        assert!(!crash_loops.is_held_back(replica_id));
+
+        // A replica that keeps crash-looping is held back for longer each time, up to the
+        // maximum.
+        for (secs, backoff) in [(200, 20), (300, 25)] {
+            assert_eq!(crash_loops.observe(&oom_killed(secs), now), None);
+            assert_eq!(crash_loops.observe(&oom_killed(secs + 1), now), None);
+            let crash_loop = crash_loops
+                .observe(&event(secs + 2, ClusterStatus::NotReady(None)), now)
+                .expect("crash loop");
+            assert_eq!(crash_loop.backoff, Duration::from_secs(backoff));
+            // The reason of the last failure that had one is retained.
+            assert_eq!(crash_loop.last_failure, Some(NotReadyReason::OomKilled));
+            crash_loops.take_due(now + crash_loop.backoff);
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_crash_loop_reset_after_healthy_period() {
+        let mut crash_loops = CrashLoops::new(config());
+        let now = Instant::now();
+
+        for secs in [0, 1] {
+            assert_eq!(crash_loops.observe(&oom_killed(secs), now), None);
+        }
+        let crash_loop = crash_loops
+            .observe(&oom_killed(2), now)
+            .expect("crash loop");
+        assert_eq!(crash_loop.backoff, Duration::from_secs(10));
+        crash_loops.take_due(now + crash_loop.backoff);
+
+        // A replica that is ready only briefly keeps its backoff.
+        assert_eq!(
+            crash_loops.observe(&event(20, ClusterStatus::Ready), now),
+            None
+        );
+        for secs in [30, 31] {
+            assert_eq!(crash_loops.observe(&oom_killed(secs), now), None);
+        }
+        let crash_loop = crash_loops
+            .observe(&oom_killed(32), now)
+            .expect("crash loop");
+        assert_eq!(crash_loop.backoff, Duration::from_secs(20));
+        crash_loops.take_due(now + crash_loop.backoff);
+
+        // A replica that stays ready for the healthy period has its backoff reset.
+        assert_eq!(
+            crash_loops.observe(&event(100, ClusterStatus::Ready), now),
+            None
+        );
+        for secs in [300, 301] {
+            assert_eq!(crash_loops.observe(&oom_killed(secs), now), None);
+        }
+        let crash_loop = crash_loops
+            .observe(&oom_killed(302), now)
+            .expect("crash loop");
+        assert_eq!(crash_loop.backoff, Duration::from_secs(10));
+
+        // Dropped replicas are forgotten.
+        crash_loops.remove_replica(ReplicaId::User(1));
+        assert_eq!(crash_loops.next_reschedule(), None);
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Offline replay of the command logs recorded by [`Controller::enable_command_recording`].
+//!
+//! [`Controller::enable_command_recording`]: crate::Controller::enable_command_recording
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, Read};
+
+use anyhow::{anyhow, Context};
+use mz_cluster_client::command_log::{
+    read_command_log, CommandProtocol, LoggedCommand, RecordedCommand,
+};
+use mz_compute_client::protocol::command::ComputeCommand;
+use mz_compute_client::service::ComputeClient;
+use mz_compute_types::ComputeInstanceId;
+use mz_service::client::GenericClient;
+use mz_storage_client::client::{StorageClient, StorageCommand};
+use mz_storage_types::instances::StorageInstanceId;
+use serde::de::DeserializeOwned;
+
+/// A command sent by the storage or the compute controller.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ControllerCommand<T> {
+    /// A command sent to a storage instance.
+    Storage(StorageCommand<T>),
+    /// A command sent to a compute instance.
+    Compute(ComputeCommand<T>),
+}
+
+impl<T: DeserializeOwned> LoggedCommand for ControllerCommand<T> {
+    fn read(protocol: CommandProtocol, log: &mut dyn Read) -> Result<Self, bincode::Error> {
+        Ok(match protocol {
+            CommandProtocol::Storage => ControllerCommand::Storage(bincode::deserialize_from(log)?),
+            CommandProtocol::Compute => ControllerCommand::Compute(bincode::deserialize_from(log)?),
+        })
+    }
+}
+
+/// Reads the commands of a command log, in the order in which they were recorded.
+pub fn read_controller_commands<T, R>(
+    log: R,
+) -> impl Iterator<Item = Result<RecordedCommand<ControllerCommand<T>>, anyhow::Error>>
+where
+    T: DeserializeOwned,
+    R: BufRead,
+{
+    read_command_log(log)
+}
+
+/// Replays a command log against the given clients.
+///
+/// The commands are sent in the order in which they were recorded, each to the client of the
+/// instance it was originally sent to. Responses are left to the caller to receive. Returns the
+/// number of replayed commands.
+pub async fn replay<T, R>(
+    log: R,
+    storage: &mut BTreeMap<StorageInstanceId, Box<dyn StorageClient<T>>>,
+    compute: &mut BTreeMap<ComputeInstanceId, Box<dyn ComputeClient<T>>>,
+) -> Result<u64, anyhow::Error>
+where
+    T: DeserializeOwned + Send,
+    R: BufRead,
+{
+    let mut replayed = 0;
+    for recorded in read_controller_commands(log) {
+        let RecordedCommand {
+            seq,
+            instance,
+            command,
+            ..
+        } = recorded?;
+        let instance_id = instance
+            .parse()
+            .with_context(|| format!("command {seq} targets an invalid instance {instance}"))?;
+        match command {
+            ControllerCommand::Storage(command) => {
+                let client = storage
+                    .get_mut(&instance_id)
+                    .ok_or_else(|| anyhow!("no client for storage instance {instance}"))?;
+                client.send(command).await?;
+            }
+            ControllerCommand::Compute(command) => {
+                let client = compute
+                    .get_mut(&instance_id)
+                    .ok_or_else(|| anyhow!("no client for compute instance {instance}"))?;
+                client.send(command).await?;
+            }
+        }
+        replayed += 1;
+    }
+    Ok(replayed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use mz_cluster_client::client::{ClusterStartupEpoch, TimelyConfig};
+    use mz_cluster_client::command_log::{CommandRecorder, InMemoryLog};
+    use mz_ore::now::NOW_ZERO;
+    use mz_repr::{GlobalId, Timestamp};
+    use timely::progress::Antichain;
+
+    use super::*;
+
+    /// A client that collects the commands sent to it.
+    #[derive(Debug)]
+    struct CollectingClient<C>(Arc<Mutex<Vec<C>>>);
+
+    #[async_trait]
+    impl<C: std::fmt::Debug + Send, R: Send> GenericClient<C, R> for CollectingClient<C> {
+        async fn send(&mut self, cmd: C) -> Result<(), anyhow::Error> {
+            self.0.lock().expect("lock poisoned").push(cmd);
+            Ok(())
+        }
+
+        async fn recv(&mut self) -> Result<Option<R>, anyhow::Error> {
+            futures::future::pending().await
+        }
+    }
+
+    fn storage_commands() -> Vec<StorageCommand<Timestamp>> {
+        vec![
+            StorageCommand::CreateTimely {
+                config: TimelyConfig::default(),
+                epoch: ClusterStartupEpoch::new(1.try_into().unwrap(), 0),
+            },
+            StorageCommand::InitializationComplete,
+            StorageCommand::AllowCompaction(vec![(
+                GlobalId::User(1),
+                Antichain::from_elem(Timestamp::new(10)),
+            )]),
+        ]
+    }
+
+    fn compute_commands() -> Vec<ComputeCommand<Timestamp>> {
+        vec![
+            ComputeCommand::InitializationComplete,
+            ComputeCommand::AllowCompaction {
+                id: GlobalId::User(2),
+                frontier: Antichain::new(),
+            },
+        ]
+    }
+
+    /// Records the test commands, interleaving storage and compute commands.
+    fn record() -> Vec<u8> {
+        let log = InMemoryLog::default();
+        let recorder = CommandRecorder::default();
+        recorder.enable(log.clone(), NOW_ZERO.clone());
+        let storage_id = StorageInstanceId::User(1);
+        let compute_id = ComputeInstanceId::User(2);
+        let mut storage = storage_commands().into_iter();
+        let mut compute = compute_commands().into_iter();
+        loop {
+            let command = storage.next();
+            if let Some(command) = &command {
+                recorder.record(CommandProtocol::Storage, storage_id, command);
+            }
+            let other = compute.next();
+            if let Some(other) = &other {
+                recorder.record(CommandProtocol::Compute, compute_id, other);
+            }
+            if command.is_none() && other.is_none() {
+                break;
+            }
+        }
+        recorder.disable();
+        log.contents()
+    }
+
+    #[mz_ore::test]
+    fn test_recorded_commands_round_trip() {
+        let recorded: Vec<_> = read_controller_commands::<Timestamp, _>(Cursor::new(record()))
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let seqs: Vec<_> = recorded.iter().map(|r| r.seq).collect();
+        assert_eq!(seqs, (0..5).collect::<Vec<_>>());
+
+        let mut storage = vec![];
+        let mut compute = vec![];
+        for recorded in recorded {
+            match recorded.command {
+                ControllerCommand::Storage(command) => {
+                    assert_eq!(recorded.instance, "u1");
+                    storage.push(command);
+                }
+                ControllerCommand::Compute(command) => {
+                    assert_eq!(recorded.instance, "u2");
+                    compute.push(command);
+                }
+            }
+        }
+        assert_eq!(storage, storage_commands());
+        assert_eq!(compute, compute_commands());
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `epoll_wait` on OS `linux`
+    async fn test_replay() {
+        let storage_received = Arc::new(Mutex::new(vec![]));
+        let compute_received = Arc::new(Mutex::new(vec![]));
+        let storage_client: Box<dyn StorageClient<Timestamp>> =
+            Box::new(CollectingClient::<StorageCommand<Timestamp>>(Arc::clone(
+                &storage_received,
+            )));
+        let compute_client: Box<dyn ComputeClient<Timestamp>> =
+            Box::new(CollectingClient::<ComputeCommand<Timestamp>>(Arc::clone(
+                &compute_received,
+            )));
+        let mut storage = BTreeMap::from([(StorageInstanceId::User(1), storage_client)]);
+        let mut compute = BTreeMap::from([(ComputeInstanceId::User(2), compute_client)]);
+
+        let replayed = replay(Cursor::new(record()), &mut storage, &mut compute)
+            .await
+            .unwrap();
+        assert_eq!(replayed, 5);
+        assert_eq!(*storage_received.lock().unwrap(), storage_commands());
+        assert_eq!(*compute_received.lock().unwrap(), compute_commands());
+
+        // Commands for instances without a client fail the replay.
+        storage.clear();
+        assert!(replay(Cursor::new(record()), &mut storage, &mut compute)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Tracks when each collection was created, in wall-clock time.
+//!
+//! Clients create collections in the storage and compute controllers directly, so the controller
+//! learns of new collections by observing both sub-controllers whenever it records frontiers. The
+//! creation time of a collection is thus accurate to the frontier recording interval. Collections
+//! that already exist when the controller starts are recorded at their first observation.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use mz_ore::now::EpochMillis;
+use mz_repr::GlobalId;
+
+/// The wall-clock times at which collections were first observed.
+#[derive(Debug, Default)]
+pub(crate) struct CreationTimes {
+    created_at: BTreeMap<GlobalId, EpochMillis>,
+}
+
+impl CreationTimes {
+    /// Records `now` as the creation time of the `present` collections that were not observed
+    /// before, and forgets the collections that are not present anymore, i.e. were dropped.
+    pub(crate) fn observe(&mut self, now: EpochMillis, present: BTreeSet<GlobalId>) {
+        self.created_at.retain(|id, _| present.contains(id));
+        for id in present {
+            self.created_at.entry(id).or_insert(now);
+        }
+    }
+
+    /// Returns the creation time of collection `id`, if it has been observed.
+    pub(crate) fn get(&self, id: GlobalId) -> Option<EpochMillis> {
+        self.created_at.get(&id).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_creation_times() {
+        let mut times = CreationTimes::default();
+        let (t1, t2) = (GlobalId::User(1), GlobalId::User(2));
+
+        times.observe(1_000, [t1].into());
+        assert_eq!(times.get(t1), Some(1_000));
+        assert_eq!(times.get(t2), None);
+
+        // Collections keep their creation time, and new ones are recorded.
+        times.observe(2_000, [t1, t2].into());
+        assert_eq!(times.get(t1), Some(1_000));
+        assert_eq!(times.get(t2), Some(2_000));
+
+        // Dropped collections are forgotten.
+        times.observe(3_000, [t2].into());
+        assert_eq!(times.get(t1), None);
+        assert_eq!(times.get(t2), Some(2_000));
+    }
+}
@@ -0,0 +1,180 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Batched drops of collections maintained by the storage and compute controllers.
+//!
+//! Dropping many objects at once, e.g. a schema and everything in it, issues a single batch of
+//! drop commands to storage and to each compute instance, rather than interleaved commands per
+//! object. The completion of the whole batch is reported once, after all of its collections are
+//! gone from both layers.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use mz_compute_types::ComputeInstanceId;
+use mz_repr::GlobalId;
+
+/// Identifies a batch of collections dropped with
+/// [`Controller::drop_collections_batched`](crate::Controller::drop_collections_batched).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DropBatchId(u64);
+
+/// The drops of a batch of collections, partitioned by the layer and compute instance that
+/// maintain them. Each partition keeps the order in which the collections were given.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct DropPlan {
+    /// Storage collections, whose read capabilities are released together.
+    pub(crate) storage_collections: Vec<GlobalId>,
+    /// Storage exports, i.e. sinks.
+    pub(crate) storage_exports: Vec<GlobalId>,
+    /// Compute collections, by the instance that maintains them.
+    pub(crate) compute: BTreeMap<ComputeInstanceId, Vec<GlobalId>>,
+}
+
+impl DropPlan {
+    /// Partitions `ids` by the layers and compute instances that maintain them. Materialized
+    /// views are maintained by both layers and dropped from both.
+    ///
+    /// Fails with the first id that is maintained by neither layer, before anything is dropped.
+    pub(crate) fn new(
+        ids: &[GlobalId],
+        is_storage_collection: impl Fn(GlobalId) -> bool,
+        is_storage_export: impl Fn(GlobalId) -> bool,
+        compute_instances: &BTreeMap<GlobalId, ComputeInstanceId>,
+    ) -> Result<Self, GlobalId> {
+        let mut plan = DropPlan::default();
+        for id in ids {
+            let mut known = false;
+            if is_storage_collection(*id) {
+                plan.storage_collections.push(*id);
+                known = true;
+            } else if is_storage_export(*id) {
+                plan.storage_exports.push(*id);
+                known = true;
+            }
+            if let Some(instance_id) = compute_instances.get(id) {
+                plan.compute.entry(*instance_id).or_default().push(*id);
+                known = true;
+            }
+            if !known {
+                return Err(*id);
+            }
+        }
+        Ok(plan)
+    }
+}
+
+/// Batches of collections that were dropped, but are not gone from both layers yet.
+#[derive(Debug, Default)]
+pub(crate) struct DropBatches {
+    /// The collections of each batch that are not gone yet.
+    pending: BTreeMap<DropBatchId, BTreeSet<GlobalId>>,
+    /// The id to assign to the next batch.
+    next_id: u64,
+}
+
+impl DropBatches {
+    /// Starts tracking the dropped collections `ids` as a batch.
+    pub(crate) fn insert(&mut self, ids: BTreeSet<GlobalId>) -> DropBatchId {
+        let batch = DropBatchId(self.next_id);
+        self.next_id += 1;
+        self.pending.insert(batch, ids);
+        batch
+    }
+
+    /// Forgets the collections that are gone according to `is_gone`, returning the batches all
+    /// of whose collections are gone now.
+    pub(crate) fn update(&mut self, is_gone: impl Fn(GlobalId) -> bool) -> Vec<DropBatchId> {
+        let mut finished = Vec::new();
+        self.pending.retain(|batch, ids| {
+            ids.retain(|id| !is_gone(*id));
+            let retain = !ids.is_empty();
+            if !retain {
+                finished.push(*batch);
+            }
+            retain
+        });
+        finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_drop_plan() {
+        // A schema with 40 tables, 20 materialized views, 30 indexes, and 10 sinks, spread over
+        // two clusters.
+        let (c1, c2) = (ComputeInstanceId::User(1), ComputeInstanceId::User(2));
+        let tables: Vec<_> = (0..40).map(GlobalId::User).collect();
+        let mviews: Vec<_> = (40..60).map(GlobalId::User).collect();
+        let indexes: Vec<_> = (60..90).map(GlobalId::User).collect();
+        let sinks: Vec<_> = (90..100).map(GlobalId::User).collect();
+        let mut compute_instances = BTreeMap::new();
+        for (i, id) in mviews.iter().chain(&indexes).enumerate() {
+            compute_instances.insert(*id, if i % 2 == 0 { c1 } else { c2 });
+        }
+        let is_storage_collection = |id| tables.contains(&id) || mviews.contains(&id);
+        let is_storage_export = |id| sinks.contains(&id);
+
+        // The objects are dropped in reverse order of their creation.
+        let mut ids: Vec<_> = (0..100).rev().map(GlobalId::User).collect();
+        let plan = DropPlan::new(
+            &ids,
+            is_storage_collection,
+            is_storage_export,
+            &compute_instances,
+        )
+        .unwrap();
+        let storage_collections: Vec<_> = tables.iter().chain(&mviews).rev().copied().collect();
+        assert_eq!(plan.storage_collections, storage_collections);
+        let storage_exports: Vec<_> = sinks.iter().rev().copied().collect();
+        assert_eq!(plan.storage_exports, storage_exports);
+        assert_eq!(plan.compute.len(), 2);
+        for (instance_id, ids) in &plan.compute {
+            let expected: Vec<_> = mviews
+                .iter()
+                .chain(&indexes)
+                .rev()
+                .filter(|id| compute_instances[id] == *instance_id)
+                .copied()
+                .collect();
+            assert_eq!(*ids, expected);
+        }
+
+        // Unknown collections fail the whole batch.
+        ids.push(GlobalId::User(100));
+        assert_eq!(
+            DropPlan::new(
+                &ids,
+                is_storage_collection,
+                is_storage_export,
+                &compute_instances
+            ),
+            Err(GlobalId::User(100))
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_drop_batches() {
+        let mut batches = DropBatches::default();
+        let ids: BTreeSet<_> = (0..100).map(GlobalId::User).collect();
+        let batch = batches.insert(ids);
+
+        // The batch is reported once, after the last of its collections is gone.
+        assert_eq!(batches.update(|id| id != GlobalId::User(42)), vec![]);
+        assert_eq!(batches.update(|_| false), vec![]);
+        assert_eq!(batches.update(|_| true), vec![batch]);
+        assert_eq!(batches.update(|_| true), vec![]);
+
+        // Empty batches are finished right away.
+        let empty = batches.insert(BTreeSet::new());
+        assert_eq!(batches.update(|_| false), vec![empty]);
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A self-check that compares the compute and storage views of the write frontiers of
+//! collections known to both layers, i.e. materialized views.
+//!
+//! The write frontier of the dataflow of a materialized view and that of its persisted output
+//! track each other closely. A persistent divergence, in either direction, indicates a bug or a
+//! stuck `persist_sink`. Divergences are expected while a collection is created or catches up to
+//! its `as_of`, so a collection is only reported once it has diverged by more than the
+//! threshold for a number of consecutive checks.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use mz_ore::metric;
+use mz_ore::metrics::{raw, MetricsRegistry};
+use mz_repr::{GlobalId, Timestamp};
+use timely::progress::Antichain;
+use tracing::warn;
+
+/// The default difference between the compute and storage write frontiers of a collection above
+/// which they are considered to diverge.
+pub const DEFAULT_FRONTIER_DIVERGENCE_THRESHOLD: Duration = Duration::from_secs(60);
+
+/// The default number of consecutive checks for which a collection must diverge before it is
+/// reported.
+pub const DEFAULT_FRONTIER_DIVERGENCE_TICKS: usize = 30;
+
+/// A collection whose compute and storage write frontiers have diverged persistently.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrontierDivergence {
+    pub id: GlobalId,
+    pub compute_write_frontier: Timestamp,
+    pub storage_write_frontier: Timestamp,
+    /// The number of consecutive checks for which the collection has diverged.
+    pub ticks: usize,
+}
+
+/// Tracks for how many consecutive checks the write frontiers of each collection have diverged,
+/// so that each persistent divergence is reported once.
+#[derive(Debug)]
+pub(crate) struct FrontierDivergences {
+    /// The difference between the write frontiers above which they diverge.
+    pub(crate) threshold: Duration,
+    /// The number of consecutive checks for which a collection must diverge to be reported.
+    pub(crate) ticks: usize,
+    /// The number of consecutive checks for which each diverging collection has diverged.
+    streaks: BTreeMap<GlobalId, usize>,
+    /// The collections that have been counted in `divergences_total`, whose labels are removed
+    /// once they no longer exist.
+    counted: BTreeSet<GlobalId>,
+    /// Counts the persistent divergences of each collection.
+    divergences_total: raw::IntCounterVec,
+}
+
+impl FrontierDivergences {
+    pub(crate) fn new(metrics_registry: &MetricsRegistry) -> Self {
+        Self {
+            threshold: DEFAULT_FRONTIER_DIVERGENCE_THRESHOLD,
+            ticks: DEFAULT_FRONTIER_DIVERGENCE_TICKS,
+            streaks: BTreeMap::new(),
+            counted: BTreeSet::new(),
+            divergences_total: metrics_registry.register(metric!(
+                name: "mz_controller_frontier_divergences_total",
+                help: "The number of times the compute and storage write frontiers of a collection \
+                       were found to diverge persistently.",
+                var_labels: ["collection_id"],
+            )),
+        }
+    }
+
+    /// Compares the compute and storage write frontiers of the collections known to both layers,
+    /// returning the collections that have now diverged for `ticks` consecutive checks.
+    ///
+    /// Collections whose write frontier is empty in either layer are not maintained anymore and
+    /// are skipped.
+    pub(crate) fn check(
+        &mut self,
+        frontiers: impl Iterator<Item = (GlobalId, Antichain<Timestamp>, Antichain<Timestamp>)>,
+    ) -> Vec<FrontierDivergence> {
+        let threshold = u64::try_from(self.threshold.as_millis()).unwrap_or(u64::MAX);
+        let mut present = BTreeSet::new();
+        let mut streaks = BTreeMap::new();
+        let mut diverged = Vec::new();
+        for (id, compute, storage) in frontiers {
+            present.insert(id);
+            let (Some(compute), Some(storage)) = (compute.into_option(), storage.into_option())
+            else {
+                continue;
+            };
+            let divergence = u64::from(compute).abs_diff(u64::from(storage));
+            if divergence <= threshold {
+                continue;
+            }
+
+            let streak = self.streaks.get(&id).copied().unwrap_or(0) + 1;
+            streaks.insert(id, streak);
+            if streak == self.ticks {
+                warn!(
+                    %id,
+                    %compute,
+                    %storage,
+                    divergence_ms = divergence,
+                    ticks = streak,
+                    "compute and storage write frontiers of collection diverge"
+                );
+                self.divergences_total
+                    .with_label_values(&[&id.to_string()])
+                    .inc();
+                self.counted.insert(id);
+                diverged.push(FrontierDivergence {
+                    id,
+                    compute_write_frontier: compute,
+                    storage_write_frontier: storage,
+                    ticks: streak,
+                });
+            }
+        }
+        self.streaks = streaks;
+
+        let divergences_total = &self.divergences_total;
+        self.counted.retain(|id| {
+            let retain = present.contains(id);
+            if !retain {
+                let _ = divergences_total.remove_label_values(&[&id.to_string()]);
+            }
+            retain
+        });
+
+        diverged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frontier(t: u64) -> Antichain<Timestamp> {
+        Antichain::from_elem(Timestamp::from(t))
+    }
+
+    #[mz_ore::test]
+    fn test_frontier_divergences() {
+        let registry = MetricsRegistry::new();
+        let mut divergences = FrontierDivergences::new(&registry);
+        divergences.threshold = Duration::from_millis(100);
+        divergences.ticks = 3;
+
+        let (diverging, tracking) = (GlobalId::User(1), GlobalId::User(2));
+        let mut check = |storage: u64| {
+            divergences.check(
+                [
+                    (diverging, frontier(1_000), frontier(storage)),
+                    (tracking, frontier(1_000), frontier(990)),
+                ]
+                .into_iter(),
+            )
+        };
+
+        // A transient divergence, e.g. while catching up to the `as_of`, is not reported.
+        assert!(check(500).is_empty());
+        assert!(check(500).is_empty());
+        assert!(check(950).is_empty());
+        assert!(check(500).is_empty());
+        assert!(check(500).is_empty());
+
+        // A persistent one is, once.
+        assert_eq!(
+            check(500),
+            vec![FrontierDivergence {
+                id: diverging,
+                compute_write_frontier: Timestamp::from(1_000),
+                storage_write_frontier: Timestamp::from(500),
+                ticks: 3,
+            }]
+        );
+        assert!(check(500).is_empty());
+        let total = || {
+            divergences
+                .divergences_total
+                .with_label_values(&[&diverging.to_string()])
+                .get()
+        };
+        assert_eq!(total(), 1);
+
+        // Sealed collections are not maintained anymore, and dropped ones are forgotten.
+        assert!(divergences
+            .check([(diverging, frontier(1_000), Antichain::new())].into_iter())
+            .is_empty());
+        assert!(divergences.check(std::iter::empty()).is_empty());
+        assert!(divergences.counted.is_empty());
+        assert!(divergences.streaks.is_empty());
+    }
+}
@@ -23,22 +23,32 @@
 
 use std::any::Any;
 use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io::Write;
 use std::mem;
 use std::num::NonZeroI64;
-use std::rc::Rc;
 use std::sync::Arc;
 
+use anyhow::bail;
 use differential_dataflow::lattice::Lattice;
 use futures::future::BoxFuture;
-use futures::stream::{Peekable, StreamExt};
+use futures::stream::{Peekable, Stream, StreamExt};
+use futures::FutureExt;
 use mz_build_info::BuildInfo;
 use mz_cluster_client::ReplicaId;
+use mz_compute_client::controller::error::{InstanceMissing, ReplicaUpdateError};
 use mz_compute_client::controller::{
-    ActiveComputeController, ComputeController, ComputeControllerResponse,
+    ActiveComputeController, CollectionState as ComputeCollectionState, ComputeController,
+    ComputeControllerResponse, QuiesceHandle,
 };
 use mz_compute_client::protocol::response::{PeekResponse, SubscribeBatch};
 use mz_compute_client::service::{ComputeClient, ComputeGrpcClient};
-use mz_orchestrator::{NamespacedOrchestrator, Orchestrator, ServiceProcessMetrics};
+use mz_compute_types::ComputeInstanceId;
+use mz_orchestrator::scheduling_config::ServiceSchedulingConfig;
+use mz_orchestrator::{
+    NamespacedOrchestrator, Orchestrator, ResourceLimits, ServiceProcessMetrics,
+};
+use mz_ore::cast::{CastFrom, CastLossy};
 use mz_ore::metrics::MetricsRegistry;
 use mz_ore::now::{EpochMillis, NowFn};
 use mz_ore::task::AbortOnDropHandle;
@@ -47,25 +57,51 @@ use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::PersistLocation;
 use mz_persist_types::Codec64;
 use mz_proto::RustType;
-use mz_repr::{GlobalId, TimestampManipulation};
+use mz_repr::adt::interval::Interval;
+use mz_repr::{Datum, Diff, GlobalId, Row, TimestampManipulation};
 use mz_service::secrets::SecretsReaderCliArgs;
 use mz_stash_types::metrics::Metrics as StashMetrics;
 use mz_storage_client::client::{
-    ProtoStorageCommand, ProtoStorageResponse, StorageCommand, StorageResponse,
+    ProtoStorageCommand, ProtoStorageResponse, StatusUpdate, StorageCommand, StorageResponse,
+};
+use mz_storage_client::controller::{
+    CollectionState as StorageCollectionState, CompactionEstimate, DataSource, DataSourceOther,
+    ExportState, IntrospectionType, NameResolver, StorageController,
 };
-use mz_storage_client::controller::StorageController;
+use mz_storage_client::statistics::{SinkStatisticsUpdate, SourceStatisticsUpdate};
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::connections::ConnectionContext;
-use mz_storage_types::controller::PersistTxnTablesImpl;
+use mz_storage_types::controller::{PersistTxnTablesImpl, StorageError};
+use mz_storage_types::parameters::SourceParameterOverrides;
+use mz_storage_types::read_policy::ReadPolicy;
+use serde::{Deserialize, Serialize};
 use timely::order::TotalOrder;
-use timely::progress::{Antichain, Timestamp};
+use timely::progress::{Antichain, ChangeBatch, Timestamp};
 use tokio::sync::mpsc::{self, UnboundedSender};
 use tokio::time::{self, Duration, Interval, MissedTickBehavior};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::instrument;
+use url::Url;
 use uuid::Uuid;
 
+use crate::advancement_rates::{AdvancementRates, RecordedRate};
+use crate::clusters::{CrashLoops, ManagedReplica, ProcessId, ReplicaCrashLoop};
+use crate::creation_times::CreationTimes;
+use crate::drops::{DropBatchId, DropBatches, DropPlan};
+use crate::frontier_divergence::{FrontierDivergence, FrontierDivergences};
+use crate::response_queue::ResponseQueue;
+use crate::watch_sets::WatchSets;
+
+pub mod advancement_rates;
 pub mod clusters;
+pub mod command_log;
+mod creation_times;
+pub mod drops;
+pub mod frontier_divergence;
+pub mod response_queue;
+pub mod watch_sets;
+
+pub use crate::watch_sets::WatchSetId;
 
 /// Configures a controller.
 #[derive(Debug, Clone)]
@@ -100,6 +136,43 @@ pub struct ControllerConfig {
     pub connection_context: ConnectionContext,
 }
 
+impl ControllerConfig {
+    /// Validates the URLs in the configuration, so that a malformed URL is
+    /// reported at startup rather than as a connection error deep in persist.
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        validate_url(
+            "storage stash",
+            &self.storage_stash_url,
+            &["postgres", "postgresql"],
+        )?;
+        validate_url(
+            "persist pubsub",
+            &self.persist_pubsub_url,
+            &["http", "https"],
+        )?;
+        Ok(())
+    }
+}
+
+/// Parses `url` and checks that its scheme is one of `schemes`.
+fn validate_url(name: &str, url: &str, schemes: &[&str]) -> Result<Url, anyhow::Error> {
+    let parsed = match Url::parse(url) {
+        Ok(parsed) => parsed,
+        Err(e) => bail!("invalid {name} URL {url:?}: {e}"),
+    };
+    if !schemes.contains(&parsed.scheme()) {
+        bail!(
+            "invalid {name} URL {url:?}: scheme must be one of {}",
+            schemes.join(", ")
+        );
+    }
+    Ok(parsed)
+}
+
+/// The default fraction of a replica process's disk capacity at or above which the controller
+/// reports a [`ReplicaDiskHighWaterMark`].
+pub const DEFAULT_REPLICA_DISK_HIGH_WATER_MARK: f64 = 0.9;
+
 /// Responses that [`Controller`] can produce.
 #[derive(Debug)]
 pub enum ControllerResponse<T = mz_repr::Timestamp> {
@@ -116,6 +189,366 @@ pub enum ControllerResponse<T = mz_repr::Timestamp> {
     /// Notification that new resource usage metrics are available for a given replica.
     ComputeReplicaMetrics(ReplicaId, Vec<ServiceProcessMetrics>),
     WatchSetFinished(Vec<Box<dyn Any>>),
+    /// Notification that replica processes have crossed the disk usage high-water mark. The
+    /// controller has already recorded the crossings in the replica status history.
+    ReplicaDiskHighWaterMark(Vec<ReplicaDiskHighWaterMark>),
+    /// Notification that replicas were found crash-looping and are held back from restarting.
+    ReplicaCrashLooping(Vec<ReplicaCrashLoop>),
+    /// Statistics of sources and sinks, as reported by the storage instances that run them.
+    StorageStatistics(Vec<SourceStatisticsUpdate>, Vec<SinkStatisticsUpdate>),
+    /// Notification that the compute and storage write frontiers of collections have diverged
+    /// persistently.
+    FrontierDivergences(Vec<FrontierDivergence>),
+    /// Notification that all collections of the given batches, dropped with
+    /// [`Controller::drop_collections_batched`], are gone from both layers.
+    CollectionsDropped(Vec<DropBatchId>),
+}
+
+/// A replica process whose disk usage has crossed the configured high-water mark.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplicaDiskHighWaterMark {
+    pub replica_id: ReplicaId,
+    pub process_id: ProcessId,
+    pub disk_usage_bytes: u64,
+    pub disk_capacity_bytes: u64,
+}
+
+/// Tracks which replica processes are above the disk usage high-water mark, so that each
+/// crossing is reported only once.
+#[derive(Debug)]
+struct DiskHighWaterMarks {
+    /// The fraction of the disk capacity at or above which a process is reported.
+    threshold: f64,
+    /// The processes currently at or above the threshold.
+    exceeded: BTreeSet<(ReplicaId, ProcessId)>,
+}
+
+impl DiskHighWaterMarks {
+    fn new(threshold: f64) -> Self {
+        Self {
+            threshold,
+            exceeded: BTreeSet::new(),
+        }
+    }
+
+    /// Records the latest `metrics` of `replica_id`, returning the processes that have newly
+    /// crossed the high-water mark.
+    fn observe(
+        &mut self,
+        replica_id: ReplicaId,
+        metrics: &[ServiceProcessMetrics],
+    ) -> Vec<ReplicaDiskHighWaterMark> {
+        let mut crossed = Vec::new();
+        for (process_id, m) in metrics.iter().enumerate() {
+            let process_id = u64::cast_from(process_id);
+            let key = (replica_id, process_id);
+            let (Some(disk_usage_bytes), Some(disk_capacity_bytes)) =
+                (m.disk_usage_bytes, m.disk_capacity_bytes)
+            else {
+                continue;
+            };
+            let above = disk_capacity_bytes > 0
+                && f64::cast_lossy(disk_usage_bytes)
+                    >= self.threshold * f64::cast_lossy(disk_capacity_bytes);
+            if !above {
+                self.exceeded.remove(&key);
+            } else if self.exceeded.insert(key) {
+                crossed.push(ReplicaDiskHighWaterMark {
+                    replica_id,
+                    process_id,
+                    disk_usage_bytes,
+                    disk_capacity_bytes,
+                });
+            }
+        }
+        crossed
+    }
+
+    /// Forgets all processes of `replica_id`.
+    fn remove_replica(&mut self, replica_id: ReplicaId) {
+        self.exceeded.retain(|(id, _)| *id != replica_id);
+    }
+}
+
+/// Collections of soft-dropped objects, whose compute and storage state is retained until their
+/// restore window lapses instead of being dropped (and their shards finalized) right away.
+#[derive(Debug, Default)]
+struct RetainedDrops {
+    /// The compute instance of each retained collection, and the time, in milliseconds since the
+    /// epoch, at which it stops being retained.
+    collections: BTreeMap<GlobalId, (ComputeInstanceId, EpochMillis)>,
+}
+
+impl RetainedDrops {
+    fn retain(&mut self, instance_id: ComputeInstanceId, id: GlobalId, expires_at: EpochMillis) {
+        self.collections.insert(id, (instance_id, expires_at));
+    }
+
+    fn contains(&self, id: GlobalId) -> bool {
+        self.collections.contains_key(&id)
+    }
+
+    fn restore(&mut self, id: GlobalId) -> Option<ComputeInstanceId> {
+        self.collections
+            .remove(&id)
+            .map(|(instance_id, _expires_at)| instance_id)
+    }
+
+    /// Returns the collections retained on `instance_id`.
+    fn on_instance(&self, instance_id: ComputeInstanceId) -> Vec<GlobalId> {
+        self.collections
+            .iter()
+            .filter(|(_id, (id_instance, _expires_at))| *id_instance == instance_id)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Removes and returns the collections retained on `instance_id`.
+    fn release_instance(&mut self, instance_id: ComputeInstanceId) -> Vec<GlobalId> {
+        let released = self.on_instance(instance_id);
+        for id in &released {
+            self.collections.remove(id);
+        }
+        released
+    }
+
+    /// Removes and returns the collections whose retention expired at or before `now`.
+    fn expire(&mut self, now: EpochMillis) -> Vec<(ComputeInstanceId, GlobalId)> {
+        let mut expired = Vec::new();
+        self.collections.retain(|id, (instance_id, expires_at)| {
+            let retain = *expires_at > now;
+            if !retain {
+                expired.push((*instance_id, *id));
+            }
+            retain
+        });
+        expired
+    }
+}
+
+/// Errors arising from [`Controller`] operations.
+#[derive(Debug)]
+pub enum ControllerError<T = mz_repr::Timestamp> {
+    /// The underlying storage controller reported an error.
+    Storage(StorageError),
+    /// The requested read frontier is not beyond the collection's since.
+    ReadFrontierBelowSince {
+        id: GlobalId,
+        /// The name of the collection, if known.
+        name: Option<String>,
+        requested: T,
+        since: Antichain<T>,
+    },
+}
+
+impl<T: fmt::Debug> fmt::Display for ControllerError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ControllerError::Storage(e) => write!(f, "{e}"),
+            ControllerError::ReadFrontierBelowSince {
+                id,
+                name,
+                requested,
+                since,
+            } => {
+                write!(f, "cannot pin read frontier of ")?;
+                match name {
+                    Some(name) => write!(f, "{name} ({id})")?,
+                    None => write!(f, "{id}")?,
+                }
+                write!(f, " at {requested:?}: since is {:?}", since.elements())
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for ControllerError<T> {}
+
+impl<T> From<StorageError> for ControllerError<T> {
+    fn from(e: StorageError) -> Self {
+        ControllerError::Storage(e)
+    }
+}
+
+/// A read hold on a collection, acquired by [`Controller::pin_read_frontier`].
+///
+/// The collection's since will not advance beyond `time` until the token is
+/// dropped, at which point the hold is released.
+#[derive(Debug)]
+pub struct ReadHoldToken<T: Timestamp = mz_repr::Timestamp> {
+    id: GlobalId,
+    time: T,
+    tx: UnboundedSender<(GlobalId, ChangeBatch<T>)>,
+}
+
+impl<T: Timestamp> ReadHoldToken<T> {
+    /// The collection this hold applies to.
+    pub fn id(&self) -> GlobalId {
+        self.id
+    }
+
+    /// The time at which the collection's read frontier is pinned.
+    pub fn time(&self) -> &T {
+        &self.time
+    }
+}
+
+impl<T: Timestamp> Drop for ReadHoldToken<T> {
+    fn drop(&mut self) {
+        let release = ChangeBatch::new_from(self.time.clone(), -1);
+        // The receiver only goes away with the controller, at which point
+        // there are no read holds left to release.
+        let _ = self.tx.send((self.id, release));
+    }
+}
+
+/// The controller that maintains a collection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CollectionOwner {
+    /// The storage controller.
+    Storage,
+    /// The compute controller, on the identified instance.
+    Compute(ComputeInstanceId),
+}
+
+/// The kind of object a collection backs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CollectionKind {
+    /// A source, subsource, or introspection source.
+    Source,
+    /// A table.
+    Table,
+    /// A storage sink.
+    Sink,
+    /// An index.
+    Index,
+    /// A materialized view.
+    MaterializedView,
+    /// A compute log collection.
+    Log,
+    /// A transient compute collection, e.g. a `SUBSCRIBE`, a `COPY TO`, or the
+    /// dataflow of a one-off query.
+    Transient,
+}
+
+/// A collection known to the controller, as reported by
+/// [`Controller::collection_inventory`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionInventoryEntry<T = mz_repr::Timestamp> {
+    pub id: GlobalId,
+    pub owner: CollectionOwner,
+    pub kind: CollectionKind,
+    pub read_frontier: Antichain<T>,
+    pub write_frontier: Antichain<T>,
+    /// The tuning parameters overridden for this collection, if it is a source with overrides.
+    pub parameter_overrides: Option<SourceParameterOverrides>,
+}
+
+/// The number of commands the sub-controllers have queued for their replicas but not yet
+/// delivered, as reported by [`Controller::pending_command_counts`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PendingCommands {
+    /// Commands not yet delivered to storage replicas, summed across storage instances.
+    pub storage: usize,
+    /// Commands not yet delivered to every replica of each compute instance.
+    pub compute_by_instance: BTreeMap<ComputeInstanceId, usize>,
+}
+
+/// Assembles the [`CollectionInventoryEntry`]s for the given storage and compute collections.
+///
+/// Materialized views are known to both controllers and are reported once, as maintained by
+/// the compute controller.
+fn collection_inventory<'a, T: Timestamp>(
+    storage_collections: impl Iterator<Item = (&'a GlobalId, &'a StorageCollectionState<T>)>,
+    source_overrides: &BTreeMap<GlobalId, SourceParameterOverrides>,
+    storage_exports: impl Iterator<Item = (&'a GlobalId, &'a ExportState<T>)>,
+    compute_collections: impl Iterator<
+        Item = (
+            ComputeInstanceId,
+            &'a GlobalId,
+            &'a ComputeCollectionState<T>,
+        ),
+    >,
+) -> Vec<CollectionInventoryEntry<T>> {
+    let mut inventory = Vec::new();
+    let mut materialized_views = BTreeSet::new();
+    for (id, collection) in storage_collections {
+        let kind = match &collection.description.data_source {
+            DataSource::Other(DataSourceOther::Compute) => {
+                materialized_views.insert(*id);
+                continue;
+            }
+            DataSource::Other(DataSourceOther::TableWrites) => CollectionKind::Table,
+            DataSource::Ingestion(_)
+            | DataSource::Introspection(_)
+            | DataSource::Progress
+            | DataSource::Webhook
+            | DataSource::Other(DataSourceOther::Source) => CollectionKind::Source,
+        };
+        inventory.push(CollectionInventoryEntry {
+            id: *id,
+            owner: CollectionOwner::Storage,
+            kind,
+            read_frontier: collection.read_capabilities.frontier().to_owned(),
+            write_frontier: collection.write_frontier.clone(),
+            parameter_overrides: source_overrides.get(id).cloned(),
+        });
+    }
+    for (id, export) in storage_exports {
+        inventory.push(CollectionInventoryEntry {
+            id: *id,
+            owner: CollectionOwner::Storage,
+            kind: CollectionKind::Sink,
+            read_frontier: export.read_capability.clone(),
+            write_frontier: export.write_frontier.clone(),
+            parameter_overrides: None,
+        });
+    }
+    for (instance_id, id, collection) in compute_collections {
+        let kind = if materialized_views.contains(id) {
+            CollectionKind::MaterializedView
+        } else if collection.is_log_collection() {
+            CollectionKind::Log
+        } else if id.is_transient() {
+            CollectionKind::Transient
+        } else {
+            CollectionKind::Index
+        };
+        inventory.push(CollectionInventoryEntry {
+            id: *id,
+            owner: CollectionOwner::Compute(instance_id),
+            kind,
+            read_frontier: collection.read_frontier().to_owned(),
+            write_frontier: collection.write_frontier().to_owned(),
+            parameter_overrides: None,
+        });
+    }
+    inventory
+}
+
+/// Computes how far the given write frontiers lag behind the wall clock at `now`, in
+/// milliseconds.
+///
+/// The write frontiers must be in the epoch-milliseconds timeline. Collections that are sealed
+/// don't lag and are skipped, and write frontiers ahead of `now` have no lag.
+fn wallclock_lags<T: Into<mz_repr::Timestamp>>(
+    now: EpochMillis,
+    write_frontiers: impl Iterator<Item = (GlobalId, Antichain<T>)>,
+) -> BTreeMap<GlobalId, EpochMillis> {
+    write_frontiers
+        .filter_map(|(id, upper)| {
+            let upper: mz_repr::Timestamp = upper.into_option()?.into();
+            Some((id, now.saturating_sub(upper.into())))
+        })
+        .collect()
+}
+
+/// Sleeps until `deadline`, or forever if there is none.
+async fn sleep_until(deadline: Option<time::Instant>) {
+    match deadline {
+        Some(deadline) => time::sleep_until(deadline).await,
+        None => futures::future::pending().await,
+    }
 }
 
 /// Whether one of the underlying controllers is ready for their `process`
@@ -133,6 +566,10 @@ enum Readiness {
     Metrics,
     /// Frontiers are ready for recording.
     Frontiers,
+    /// Released read holds are ready to be applied.
+    ReadHolds,
+    /// Held back replicas are ready to be scheduled again.
+    Reschedule,
     /// An internally-generated message is ready to be returned.
     Internal,
 }
@@ -145,6 +582,8 @@ pub struct Controller<T = mz_repr::Timestamp> {
     clusterd_image: String,
     /// The init container image to use for clusterd.
     init_container_image: Option<String>,
+    /// The epoch of the `environmentd` process that created this controller.
+    envd_epoch: NonZeroI64,
     /// The cluster orchestrator.
     orchestrator: Arc<dyn NamespacedOrchestrator>,
     /// Tracks the readiness of the underlying controllers.
@@ -157,19 +596,68 @@ pub struct Controller<T = mz_repr::Timestamp> {
     metrics_rx: Peekable<UnboundedReceiverStream<(ReplicaId, Vec<ServiceProcessMetrics>)>>,
     /// Periodic notification to record frontiers.
     frontiers_ticker: Interval,
+    /// Tracks replica processes above the disk usage high-water mark.
+    disk_high_water_marks: DiskHighWaterMarks,
+    /// Disk usage high-water mark crossings that have yet to be returned.
+    pending_disk_high_water_marks: Vec<ReplicaDiskHighWaterMark>,
+    /// What is needed to provision each managed replica again.
+    managed_replicas: BTreeMap<ReplicaId, ManagedReplica>,
+    /// The resource limits the orchestrator applied to each provisioned replica.
+    replica_resource_limits: BTreeMap<ReplicaId, ResourceLimits>,
+    /// Detects crash-looping replicas and tracks which of them are held back.
+    crash_loops: CrashLoops,
+    /// Crash loops that have yet to be acted upon and returned.
+    pending_crash_loops: Vec<ReplicaCrashLoop>,
+    /// Checks that the compute and storage write frontiers of collections don't diverge.
+    frontier_divergences: FrontierDivergences,
+    /// Frontier divergences that have yet to be returned.
+    pending_frontier_divergences: Vec<FrontierDivergence>,
+    /// Sender for the channel over which dropped [`ReadHoldToken`]s release
+    /// their holds.
+    read_holds_tx: UnboundedSender<(GlobalId, ChangeBatch<T>)>,
+    /// Receiver for the channel over which dropped [`ReadHoldToken`]s release
+    /// their holds.
+    read_holds_rx: Peekable<UnboundedReceiverStream<(GlobalId, ChangeBatch<T>)>>,
 
     /// The URL for Persist PubSub.
     persist_pubsub_url: String,
     /// Whether to use the new persist-txn tables implementation or the legacy
     /// one.
     persist_txn_tables: PersistTxnTablesImpl,
+    /// The persist-txn tables mode that was durably recorded before this
+    /// controller started, if any.
+    previous_persist_txn_tables: Option<PersistTxnTablesImpl>,
 
     /// Arguments for secrets readers.
     secrets_args: SecretsReaderCliArgs,
 
-    watch_sets: BTreeMap<GlobalId, Vec<Rc<(T, Box<dyn Any>)>>>,
+    /// Watch sets that have not been returned yet.
+    watch_sets: WatchSets<T>,
+    /// Counts the batches of write frontier updates handled so far.
+    frontier_epoch: u64,
 
-    immediate_watch_sets: Vec<Box<dyn Any>>,
+    /// Collections of soft-dropped objects that are retained until their restore window lapses.
+    retained_drops: RetainedDrops,
+    /// Batches of dropped collections that are not gone from both layers yet.
+    drop_batches: DropBatches,
+    /// Batches of dropped collections whose completion has yet to be returned.
+    pending_dropped_batches: Vec<DropBatchId>,
+
+    /// The now function, to compare write frontiers against the wall clock.
+    now: NowFn,
+    /// Collections in the epoch-milliseconds timeline, whose write frontiers are comparable to
+    /// the wall clock.
+    epoch_millis_collections: BTreeSet<GlobalId>,
+    /// Lags in milliseconds that have been recorded in the `WallclockLag` collection, kept to be
+    /// able to retract old rows.
+    recorded_wallclock_lags: BTreeMap<GlobalId, EpochMillis>,
+    /// The rates at which the write frontiers of collections in the epoch-milliseconds timeline
+    /// advance, which are recorded in the `FrontierAdvancementRates` collection.
+    advancement_rates: AdvancementRates,
+    /// The wall-clock times at which collections were created.
+    creation_times: CreationTimes,
+    /// Responses that are staged to be handed out by class.
+    responses: ResponseQueue<T>,
 }
 
 impl<T: Timestamp> Controller<T> {
@@ -177,6 +665,20 @@ impl<T: Timestamp> Controller<T> {
         self.compute.activate(&mut *self.storage)
     }
 
+    /// Returns the epoch of the `environmentd` process that created this controller.
+    pub fn envd_epoch(&self) -> NonZeroI64 {
+        self.envd_epoch
+    }
+
+    /// Returns the wall-clock time at which collection `id` was created, i.e. first observed in
+    /// the storage or compute controller, or `None` if it doesn't exist.
+    ///
+    /// Collections are observed whenever frontiers are recorded, so the creation time of a new
+    /// collection is only known after the next recording.
+    pub fn collection_created_at(&self, id: GlobalId) -> Option<EpochMillis> {
+        self.creation_times.get(id)
+    }
+
     pub fn set_default_idle_arrangement_merge_effort(&mut self, value: u32) {
         self.compute
             .set_default_idle_arrangement_merge_effort(value);
@@ -192,6 +694,20 @@ impl<T: Timestamp> Controller<T> {
             .set_enable_aggressive_readhold_downgrades(value);
     }
 
+    /// Sets the fraction of a replica process's disk capacity at or above which
+    /// [`ControllerResponse::ReplicaDiskHighWaterMark`] is emitted.
+    pub fn set_replica_disk_high_water_mark(&mut self, value: f64) {
+        self.disk_high_water_marks.threshold = value;
+    }
+
+    /// Sets the difference between the compute and storage write frontiers of a collection
+    /// above which they diverge, and the number of consecutive frontier recordings for which they
+    /// must diverge before [`ControllerResponse::FrontierDivergences`] is emitted.
+    pub fn set_frontier_divergence_threshold(&mut self, threshold: Duration, ticks: usize) {
+        self.frontier_divergences.threshold = threshold;
+        self.frontier_divergences.ticks = ticks;
+    }
+
     /// Returns the connection context installed in the controller.
     ///
     /// This is purely a helper, and can be obtained from `self.storage`.
@@ -205,19 +721,219 @@ impl<T: Timestamp> Controller<T> {
     pub fn storage_configuration(&self) -> &StorageConfiguration {
         self.storage.config()
     }
+
+    /// Registers the resolver with which collections are identified by name, in addition to
+    /// their id, in logs and errors.
+    ///
+    /// Replaces any previously registered resolver, including for replicas that are already
+    /// connected.
+    pub fn register_name_resolver(&self, resolver: NameResolver) {
+        self.storage.collection_names().register(resolver);
+    }
+
+    /// Starts recording the commands sent to storage and compute instances to `sink`, for
+    /// reproducing controller bugs by replaying them with [`command_log::replay`].
+    ///
+    /// Recording is disabled by default, in which case it costs nothing beyond checking that it
+    /// is disabled. Enabling it again replaces the previous sink. Commands that were sent before
+    /// recording was enabled are not recorded, so a replayable log must be recorded from startup.
+    pub fn enable_command_recording(&mut self, sink: impl Write + Send + 'static) {
+        self.storage
+            .command_recorder()
+            .enable(sink, self.now.clone());
+    }
+
+    /// Stops recording commands, flushing the sink.
+    pub fn disable_command_recording(&mut self) {
+        self.storage.command_recorder().disable();
+    }
+
+    /// Returns the persist-txn tables mode the controller operates in.
+    pub fn persist_txn_tables(&self) -> PersistTxnTablesImpl {
+        self.persist_txn_tables
+    }
+
+    /// Returns the persist-txn tables mode that was durably recorded before
+    /// this controller started, or `None` if no mode had been recorded.
+    pub fn previous_persist_txn_tables(&self) -> Option<PersistTxnTablesImpl> {
+        self.previous_persist_txn_tables
+    }
+
+    /// Marks the identified cluster as quiescing, in preparation for maintenance.
+    ///
+    /// See [`ComputeController::quiesce_instance`]. The returned handle makes progress only while
+    /// the controller is processed.
+    pub fn quiesce_cluster(
+        &mut self,
+        instance_id: ComputeInstanceId,
+    ) -> Result<QuiesceHandle, InstanceMissing> {
+        self.compute.quiesce_instance(instance_id)
+    }
+
+    /// Ends quiescing of the identified cluster.
+    pub fn unquiesce_cluster(
+        &mut self,
+        instance_id: ComputeInstanceId,
+    ) -> Result<(), InstanceMissing> {
+        self.compute.unquiesce_instance(instance_id)
+    }
+
+    /// Returns whether the identified cluster is quiescing.
+    pub fn cluster_is_quiescing(
+        &self,
+        instance_id: ComputeInstanceId,
+    ) -> Result<bool, InstanceMissing> {
+        self.compute.instance_is_quiescing(instance_id)
+    }
+
+    /// Returns the since the controller enforces for collection `id`, or `None` if no
+    /// collection or export with that ID exists.
+    ///
+    /// The since combines all read holds on the collection with the capability implied by its
+    /// read policy, and matches the frontier the controller sends in `AllowCompaction` commands.
+    pub fn effective_since(&self, id: GlobalId) -> Option<Antichain<T>> {
+        if let Ok(collection) = self.compute.find_collection(id) {
+            return Some(collection.read_frontier().to_owned());
+        }
+        if let Ok(collection) = self.storage.collection(id) {
+            return Some(collection.read_capabilities.frontier().to_owned());
+        }
+        self.storage
+            .export(id)
+            .ok()
+            .map(|export| export.read_capability.clone())
+    }
+
+    /// Retains the compute and storage collections of the soft-dropped object `id`, which was
+    /// maintained on `instance_id`, until `expires_at`.
+    ///
+    /// Retained collections are not dropped until they are returned by
+    /// [`Controller::expire_retained_drops`], at which point the caller is expected to drop them.
+    pub fn retain_dropped_collection(
+        &mut self,
+        instance_id: ComputeInstanceId,
+        id: GlobalId,
+        expires_at: EpochMillis,
+    ) {
+        self.retained_drops.retain(instance_id, id, expires_at);
+    }
+
+    /// Reports whether the collections of the soft-dropped object `id` are still retained.
+    pub fn is_retaining_dropped_collection(&self, id: GlobalId) -> bool {
+        self.retained_drops.contains(id)
+    }
+
+    /// Stops retaining the collections of the soft-dropped object `id`, because the object was
+    /// restored. Returns the compute instance it is maintained on, or `None` if the collections
+    /// were not retained.
+    pub fn restore_dropped_collection(&mut self, id: GlobalId) -> Option<ComputeInstanceId> {
+        self.retained_drops.restore(id)
+    }
+
+    /// Returns the soft-dropped objects whose collections are retained on `instance_id`.
+    pub fn retained_drops_on(&self, instance_id: ComputeInstanceId) -> Vec<GlobalId> {
+        self.retained_drops.on_instance(instance_id)
+    }
+
+    /// Stops retaining the collections of soft-dropped objects on `instance_id`, because the
+    /// instance is being dropped, returning them so that the caller can drop them before the
+    /// instance.
+    pub fn release_retained_drops_on(&mut self, instance_id: ComputeInstanceId) -> Vec<GlobalId> {
+        self.retained_drops.release_instance(instance_id)
+    }
+
+    /// Stops retaining the collections whose retention expired at or before `now`, returning them
+    /// so that the caller can drop them.
+    pub fn expire_retained_drops(
+        &mut self,
+        now: EpochMillis,
+    ) -> Vec<(ComputeInstanceId, GlobalId)> {
+        self.retained_drops.expire(now)
+    }
+
+    /// Returns every collection known to the storage and compute controllers, along with its
+    /// kind and current frontiers.
+    pub fn collection_inventory(&self) -> Vec<CollectionInventoryEntry<T>> {
+        collection_inventory(
+            self.storage.collections(),
+            &self.storage.config().parameters.source_overrides,
+            self.storage.exports(),
+            self.compute.collections(),
+        )
+    }
+
+    /// Returns the number of commands queued for but not yet delivered to the replicas of the
+    /// storage and compute controllers.
+    pub fn pending_command_counts(&self) -> PendingCommands {
+        PendingCommands {
+            storage: self.storage.pending_command_count(),
+            compute_by_instance: self.compute.pending_command_counts(),
+        }
+    }
+
+    /// Reports whether the storage controller is connected to its stash.
+    ///
+    /// While disconnected, the storage controller keeps serving its cached state but its writes
+    /// fail, which readiness probes can detect with this before a write fails.
+    pub fn storage_stash_connected(&self) -> bool {
+        self.storage.stash_connection_status().connected
+    }
+
+    /// Returns the error the connection of the storage controller to its stash most recently
+    /// failed with, if any, even if the stash has reconnected since.
+    pub fn storage_stash_last_error(&self) -> Option<String> {
+        self.storage.stash_connection_status().last_error
+    }
+
+    /// Estimates how much data compaction could reclaim in each of the given storage collections
+    /// if its read policy was changed to the given one. The read policies are left unchanged.
+    pub async fn estimate_compaction(
+        &self,
+        policies: Vec<(GlobalId, ReadPolicy<T>)>,
+    ) -> Result<BTreeMap<GlobalId, CompactionEstimate<T>>, StorageError> {
+        self.storage.estimate_compaction(policies).await
+    }
 }
 
 impl<T> Controller<T>
 where
-    T: TimestampManipulation,
+    T: TimestampManipulation + Into<mz_repr::Timestamp>,
     ComputeGrpcClient: ComputeClient<T>,
 {
     pub fn update_orchestrator_scheduling_config(
         &mut self,
         config: mz_orchestrator::scheduling_config::ServiceSchedulingConfig,
     ) {
+        self.crash_loops.config = config.crash_loop.clone();
         self.orchestrator.update_scheduling_config(config);
     }
+
+    /// Sets the idle arrangement merge effort of a single compute replica, restarting it if the
+    /// value changed.
+    ///
+    /// Replicas created later still use the default set by
+    /// [`Controller::set_default_idle_arrangement_merge_effort`].
+    pub fn set_idle_arrangement_merge_effort(
+        &mut self,
+        instance_id: ComputeInstanceId,
+        replica_id: ReplicaId,
+        value: u32,
+    ) -> Result<(), ReplicaUpdateError> {
+        self.active_compute()
+            .set_idle_arrangement_merge_effort(instance_id, replica_id, value)
+    }
+
+    /// Forces the identified cluster to drop and rebuild all of its dataflows from storage,
+    /// without recreating it.
+    ///
+    /// See [`ActiveComputeController::rehydrate_instance`].
+    pub fn rehydrate_compute_instance(
+        &mut self,
+        instance_id: ComputeInstanceId,
+    ) -> Result<(), InstanceMissing> {
+        self.active_compute().rehydrate_instance(instance_id)
+    }
+
     /// Marks the end of any initialization commands.
     ///
     /// The implementor may wait for this method to be called before implementing prior commands,
@@ -228,6 +944,15 @@ where
         self.compute.initialization_complete();
     }
 
+    /// Returns a stream of the status updates of sources and sinks, which
+    /// yields updates as the controller absorbs them.
+    ///
+    /// All updates are delivered, including terminal ones, before the stream
+    /// ends. The subscription is unregistered once the stream is dropped.
+    pub fn subscribe_status_updates(&mut self) -> impl Stream<Item = StatusUpdate> {
+        self.storage.subscribe_status_updates()
+    }
+
     /// Waits until the controller is ready to process a response.
     ///
     /// This method may block for an arbitrarily long time.
@@ -238,12 +963,24 @@ where
     /// This method is cancellation safe.
     pub async fn ready(&mut self) {
         if let Readiness::NotReady = self.readiness {
-            if !self.immediate_watch_sets.is_empty() {
+            if self.watch_sets.has_immediate()
+                || !self.pending_disk_high_water_marks.is_empty()
+                || !self.pending_crash_loops.is_empty()
+                || !self.pending_frontier_divergences.is_empty()
+                || !self.pending_dropped_batches.is_empty()
+            {
                 self.readiness = Readiness::Internal;
             } else {
                 // The underlying `ready` methods are cancellation safe, so it is
                 // safe to construct this `select!`.
+                //
+                // Staged responses are handed out in turn with the other work rather than ahead
+                // of it, so that a backlog of them does not starve the recording of frontiers or
+                // the release of read holds.
                 tokio::select! {
+                    () = futures::future::ready(()), if !self.responses.is_empty() => {
+                        self.readiness = Readiness::Internal;
+                    }
                     () = self.storage.ready() => {
                         self.readiness = Readiness::Storage;
                     }
@@ -256,6 +993,12 @@ where
                     _ = self.frontiers_ticker.tick() => {
                         self.readiness = Readiness::Frontiers;
                     }
+                    _ = Pin::new(&mut self.read_holds_rx).peek() => {
+                        self.readiness = Readiness::ReadHolds;
+                    }
+                    () = sleep_until(self.crash_loops.next_reschedule()) => {
+                        self.readiness = Readiness::Reschedule;
+                    }
                 }
             }
         }
@@ -266,32 +1009,104 @@ where
         mut objects: BTreeSet<GlobalId>,
         t: T,
         token: Box<dyn Any>,
-    ) {
+    ) -> WatchSetId {
+        self.retain_unadvanced(&mut objects, &t);
+        self.watch_sets.install(objects, t, token)
+    }
+
+    /// Installs a watch set like [`Controller::install_watch_set`], but instead of reporting it
+    /// in a [`ControllerResponse::WatchSetFinished`], returns a future that resolves once the
+    /// write frontiers of all `objects` have advanced beyond `t`.
+    ///
+    /// The future makes progress only while the controller is processed.
+    pub fn install_watch_set_waiter(
+        &mut self,
+        mut objects: BTreeSet<GlobalId>,
+        t: T,
+    ) -> (WatchSetId, BoxFuture<'static, ()>) {
+        self.retain_unadvanced(&mut objects, &t);
+        self.watch_sets.install_waiter(objects, t)
+    }
+
+    /// Cancels the watch set `id` if it has not finished yet. Its token is dropped rather than
+    /// reported, and a future returned by [`Controller::install_watch_set_waiter`] resolves.
+    ///
+    /// Returns whether the watch set was cancelled.
+    pub fn cancel_watch_set(&mut self, id: WatchSetId) -> bool {
+        self.watch_sets.cancel(id)
+    }
+
+    /// Retains the `objects` whose write frontiers have not advanced beyond `t` yet.
+    fn retain_unadvanced(&self, objects: &mut BTreeSet<GlobalId>, t: &T) {
         objects.retain(|id| {
             let frontier = self
                 .compute
                 .find_collection(*id)
                 .map(|s| s.write_frontier())
                 .unwrap_or_else(|_| {
+                    let names = self.storage.collection_names();
                     self.storage
                         .collection(*id)
-                        .expect("some controller must have the collection")
+                        .unwrap_or_else(|_| {
+                            panic!(
+                                "some controller must have collection {}",
+                                names.display(*id)
+                            )
+                        })
                         .write_frontier
                         .borrow()
                 });
-            frontier.less_equal(&t)
+            frontier.less_equal(t)
         });
-        if objects.is_empty() {
-            self.immediate_watch_sets.push(token);
-        } else {
-            let state = Rc::new((t, token));
-            for id in objects {
-                self.watch_sets
+    }
+
+    /// Pins the read frontier of collection `id` at `t`, e.g. for the duration
+    /// of an export.
+    ///
+    /// The collection's since will not advance beyond `t` until the returned
+    /// token is dropped. Fails if `t` is not beyond the collection's current
+    /// since.
+    #[allow(clippy::unused_async)]
+    pub async fn pin_read_frontier(
+        &mut self,
+        id: GlobalId,
+        t: T,
+    ) -> Result<ReadHoldToken<T>, ControllerError<T>> {
+        let since = self.storage.collection(id)?.read_capabilities.frontier();
+        if !since.less_equal(&t) {
+            return Err(ControllerError::ReadFrontierBelowSince {
+                id,
+                name: self.storage.collection_names().resolve(id),
+                requested: t,
+                since: since.to_owned(),
+            });
+        }
+
+        let mut updates = BTreeMap::from([(id, ChangeBatch::new_from(t.clone(), 1))]);
+        self.storage.update_read_capabilities(&mut updates);
+
+        Ok(ReadHoldToken {
+            id,
+            time: t,
+            tx: self.read_holds_tx.clone(),
+        })
+    }
+
+    /// Applies the releases of all dropped [`ReadHoldToken`]s.
+    fn release_read_holds(&mut self) {
+        let mut updates: BTreeMap<GlobalId, ChangeBatch<T>> = BTreeMap::new();
+        while let Some(Some((id, mut release))) = self.read_holds_rx.next().now_or_never() {
+            // A dropped collection no longer has read capabilities to release.
+            if self.storage.collection(id).is_ok() {
+                updates
                     .entry(id)
-                    .or_default()
-                    .push(Rc::clone(&state));
+                    .or_insert_with(ChangeBatch::new)
+                    .extend(release.drain());
             }
         }
+        if !updates.is_empty() {
+            self.storage.update_read_capabilities(&mut updates);
+        }
     }
 
     /// Processes the work queued by [`Controller::ready`].
@@ -303,89 +1118,390 @@ where
     /// be awaited to completion.
     #[tracing::instrument(level = "debug", skip(self))]
     pub async fn process(&mut self) -> Result<Option<ControllerResponse<T>>, anyhow::Error> {
+        let response = self.process_readiness().await;
+        self.watch_sets.update_metrics();
+        response
+    }
+
+    async fn process_readiness(&mut self) -> Result<Option<ControllerResponse<T>>, anyhow::Error> {
         match mem::take(&mut self.readiness) {
             Readiness::NotReady => Ok(None),
             Readiness::Storage => {
-                let maybe_response = self.storage.process().await?;
-                Ok(maybe_response.and_then(
-                    |mz_storage_client::controller::Response::FrontierUpdates(r)| {
-                        self.handle_frontier_updates(&r)
-                    },
-                ))
+                let response = self.process_storage().await?;
+                self.stage_responses(response).await
             }
             Readiness::Compute => {
-                let response = self.active_compute().process().await;
-
-                let response = response.and_then(|r| match r {
-                    ComputeControllerResponse::PeekResponse(uuid, peek, otel_ctx) => {
-                        Some(ControllerResponse::PeekResponse(uuid, peek, otel_ctx))
-                    }
-                    ComputeControllerResponse::SubscribeResponse(id, tail) => {
-                        Some(ControllerResponse::SubscribeResponse(id, tail))
-                    }
-                    ComputeControllerResponse::CopyToResponse(id, tail) => {
-                        Some(ControllerResponse::CopyToResponse(id, tail))
-                    }
-                    ComputeControllerResponse::FrontierUpper { id, upper } => {
-                        self.handle_frontier_updates(&[(id, upper)])
-                    }
-                });
-                Ok(response)
+                let response = self.process_compute().await;
+                self.stage_responses(response).await
+            }
+            Readiness::Metrics => {
+                let response = self
+                    .metrics_rx
+                    .next()
+                    .await
+                    .map(|(id, metrics)| self.process_metrics(id, metrics));
+                self.stage_responses(response).await
             }
-            Readiness::Metrics => Ok(self
-                .metrics_rx
-                .next()
-                .await
-                .map(|(id, metrics)| ControllerResponse::ComputeReplicaMetrics(id, metrics))),
             Readiness::Frontiers => {
                 self.record_frontiers().await;
                 Ok(None)
             }
+            Readiness::ReadHolds => {
+                self.release_read_holds();
+                Ok(None)
+            }
+            Readiness::Reschedule => {
+                self.reschedule_replicas().await?;
+                Ok(None)
+            }
             Readiness::Internal => {
-                let immediate_watch_sets = std::mem::take(&mut self.immediate_watch_sets);
-                Ok((!immediate_watch_sets.is_empty())
-                    .then(|| ControllerResponse::WatchSetFinished(immediate_watch_sets)))
+                let immediate_watch_sets = self.watch_sets.take_immediate();
+                if !immediate_watch_sets.is_empty() {
+                    return Ok(Some(ControllerResponse::WatchSetFinished(
+                        immediate_watch_sets,
+                    )));
+                }
+                let crash_loops = std::mem::take(&mut self.pending_crash_loops);
+                if !crash_loops.is_empty() {
+                    self.hold_back_replicas(&crash_loops).await?;
+                    return Ok(Some(ControllerResponse::ReplicaCrashLooping(crash_loops)));
+                }
+                let divergences = std::mem::take(&mut self.pending_frontier_divergences);
+                if !divergences.is_empty() {
+                    return Ok(Some(ControllerResponse::FrontierDivergences(divergences)));
+                }
+                let dropped = std::mem::take(&mut self.pending_dropped_batches);
+                if !dropped.is_empty() {
+                    return Ok(Some(ControllerResponse::CollectionsDropped(dropped)));
+                }
+                let crossed = std::mem::take(&mut self.pending_disk_high_water_marks);
+                if !crossed.is_empty() {
+                    self.record_disk_high_water_marks(&crossed).await;
+                    return Ok(Some(ControllerResponse::ReplicaDiskHighWaterMark(crossed)));
+                }
+                self.stage_responses(None).await
             }
         }
     }
 
+    async fn process_storage(&mut self) -> Result<Option<ControllerResponse<T>>, anyhow::Error> {
+        let maybe_response = self.storage.process().await?;
+        self.update_drop_batches();
+        Ok(maybe_response.and_then(|r| match r {
+            mz_storage_client::controller::Response::FrontierUpdates(r) => {
+                self.handle_frontier_updates(&r)
+            }
+            mz_storage_client::controller::Response::StatisticsUpdates(
+                source_stats,
+                sink_stats,
+            ) => Some(ControllerResponse::StorageStatistics(
+                source_stats,
+                sink_stats,
+            )),
+        }))
+    }
+
+    async fn process_compute(&mut self) -> Option<ControllerResponse<T>> {
+        let response = self.active_compute().process().await;
+        self.update_drop_batches();
+
+        response.and_then(|r| match r {
+            ComputeControllerResponse::PeekResponse(uuid, peek, otel_ctx) => {
+                Some(ControllerResponse::PeekResponse(uuid, peek, otel_ctx))
+            }
+            ComputeControllerResponse::SubscribeResponse(id, tail) => {
+                Some(ControllerResponse::SubscribeResponse(id, tail))
+            }
+            ComputeControllerResponse::CopyToResponse(id, tail) => {
+                Some(ControllerResponse::CopyToResponse(id, tail))
+            }
+            ComputeControllerResponse::FrontierUpper { id, upper } => {
+                self.handle_frontier_updates(&[(id, upper)])
+            }
+        })
+    }
+
+    fn process_metrics(
+        &mut self,
+        id: ReplicaId,
+        metrics: Vec<ServiceProcessMetrics>,
+    ) -> ControllerResponse<T> {
+        let crossed = self.disk_high_water_marks.observe(id, &metrics);
+        self.pending_disk_high_water_marks.extend(crossed);
+        ControllerResponse::ComputeReplicaMetrics(id, metrics)
+    }
+
+    /// Stages `response`, along with the responses of the storage and compute controllers and
+    /// the replica metrics that are ready right away, and returns the staged response to hand
+    /// out next.
+    ///
+    /// When the coordinator falls behind, this lets peek responses overtake the backlog of
+    /// subscribe batches and statistics, and coalesces statistics that were superseded in the
+    /// meantime. See [`response_queue`] for details.
+    async fn stage_responses(
+        &mut self,
+        response: Option<ControllerResponse<T>>,
+    ) -> Result<Option<ControllerResponse<T>>, anyhow::Error> {
+        if let Some(response) = response {
+            self.responses.push(response);
+        }
+        for _ in 0..self.responses.staging_limit {
+            // The underlying `ready` methods are cancellation safe, so it is safe to poll them
+            // once.
+            let response = if self.storage.ready().now_or_never().is_some() {
+                self.process_storage().await?
+            } else if self.compute.ready().now_or_never().is_some() {
+                self.process_compute().await
+            } else if let Some(Some((id, metrics))) = self.metrics_rx.next().now_or_never() {
+                Some(self.process_metrics(id, metrics))
+            } else {
+                break;
+            };
+            if let Some(response) = response {
+                self.responses.push(response);
+            }
+        }
+        Ok(self.responses.pop())
+    }
+
+    /// Drops the collections `ids` from the storage and compute controllers, issuing a single
+    /// batch of drop commands to storage and to each compute instance.
+    ///
+    /// Storage collections are dropped before storage exports, which are dropped before compute
+    /// collections. Within each batch, collections are dropped in the order of `ids`.
+    ///
+    /// All `ids` are validated before any of them is dropped. Once all of them are gone from both
+    /// layers, the returned batch is reported in a [`ControllerResponse::CollectionsDropped`].
+    pub fn drop_collections_batched(
+        &mut self,
+        ids: Vec<GlobalId>,
+    ) -> Result<DropBatchId, ControllerError<T>> {
+        let compute_instances: BTreeMap<_, _> = self
+            .compute
+            .collections()
+            .map(|(instance_id, id, _collection)| (*id, instance_id))
+            .collect();
+        let storage = &self.storage;
+        let plan = DropPlan::new(
+            &ids,
+            |id| storage.collection(id).is_ok(),
+            |id| storage.export(id).is_ok(),
+            &compute_instances,
+        )
+        .map_err(StorageError::IdentifierMissing)?;
+
+        if !plan.storage_collections.is_empty() {
+            self.storage
+                .drop_sources(plan.storage_collections)
+                .expect("validated above");
+        }
+        if !plan.storage_exports.is_empty() {
+            self.storage
+                .drop_sinks(plan.storage_exports)
+                .expect("validated above");
+        }
+        let mut compute = self.active_compute();
+        for (instance_id, ids) in plan.compute {
+            compute
+                .drop_collections(instance_id, ids)
+                .expect("validated above");
+        }
+
+        Ok(self.drop_batches.insert(ids.into_iter().collect()))
+    }
+
+    /// Records the batches of dropped collections that are now gone from both layers.
+    fn update_drop_batches(&mut self) {
+        let (storage, compute) = (&self.storage, &self.compute);
+        let finished = self.drop_batches.update(|id| {
+            let storage_gone = match storage.export(id) {
+                Ok(export) => export.write_frontier.is_empty(),
+                Err(_) => storage.collection(id).is_err(),
+            };
+            storage_gone && compute.find_collection(id).is_err()
+        });
+        self.pending_dropped_batches.extend(finished);
+    }
+
     fn handle_frontier_updates(
         &mut self,
         updates: &[(GlobalId, Antichain<T>)],
     ) -> Option<ControllerResponse<T>> {
-        let mut finished = vec![];
-        for (id, antichain) in updates {
-            let mut remove = None;
-            if let Some(x) = self.watch_sets.get_mut(id) {
-                let mut i = 0;
-                while i < x.len() {
-                    if !antichain.less_equal(&x[i].0) {
-                        if let Some((_, token)) = Rc::into_inner(x.swap_remove(i)) {
-                            finished.push(token)
-                        }
-                    } else {
-                        i += 1;
-                    }
-                }
-                if x.is_empty() {
-                    remove = Some(id);
-                }
-            }
-            if let Some(id) = remove {
-                self.watch_sets.remove(id);
-            }
+        if !updates.is_empty() {
+            self.frontier_epoch += 1;
         }
+        let finished = self.watch_sets.update(updates);
         (!(finished.is_empty())).then(|| ControllerResponse::WatchSetFinished(finished))
     }
 
+    /// Returns the current frontier epoch, which advances whenever the write frontier of any
+    /// collection moves.
+    ///
+    /// Anything derived from write frontiers remains valid for as long as the epoch doesn't
+    /// change.
+    pub fn frontier_epoch(&self) -> u64 {
+        self.frontier_epoch
+    }
+
+    /// Sets the collections in the epoch-milliseconds timeline, i.e., those whose write
+    /// frontiers are comparable to the wall clock and whose lag behind it is recorded in the
+    /// `WallclockLag` introspection collection.
+    pub fn set_epoch_millis_collections(&mut self, ids: BTreeSet<GlobalId>) {
+        self.epoch_millis_collections = ids;
+    }
+
+    /// Records frontiers right away, instead of waiting for the next tick of the frontiers
+    /// ticker. The ticker is reset, so the next scheduled recording is a full interval away.
+    pub async fn record_frontiers_now(&mut self) {
+        // Recordings are serialized through `&mut self`. A scheduled recording that is ready but
+        // not yet processed is subsumed by this one.
+        if let Readiness::Frontiers = self.readiness {
+            self.readiness = Readiness::NotReady;
+        }
+        self.record_frontiers().await;
+        self.frontiers_ticker.reset();
+    }
+
     async fn record_frontiers(&mut self) {
+        let now = (self.now)();
         let compute_frontiers = self.compute.collection_frontiers();
+        let epoch_millis_uppers: Vec<_> = self
+            .epoch_millis_collections
+            .iter()
+            .filter_map(|id| {
+                let upper = match compute_frontiers.get(id) {
+                    Some((_since, upper)) => upper.clone(),
+                    None => self.storage.collection(*id).ok()?.write_frontier.clone(),
+                };
+                Some((*id, upper))
+            })
+            .collect();
+        let wallclock_lags = wallclock_lags(now, epoch_millis_uppers.iter().cloned());
+        let to_timestamps = |frontier: &Antichain<T>| -> Antichain<mz_repr::Timestamp> {
+            frontier.iter().map(|t| t.clone().into()).collect()
+        };
+        let advancement_rates = self.advancement_rates.observe(
+            now,
+            epoch_millis_uppers
+                .iter()
+                .map(|(id, upper)| (*id, to_timestamps(upper))),
+        );
+        let shared_frontiers = compute_frontiers
+            .iter()
+            .filter_map(|(id, (_since, upper))| {
+                let storage_upper = &self.storage.collection(*id).ok()?.write_frontier;
+                Some((*id, to_timestamps(upper), to_timestamps(storage_upper)))
+            });
+        let divergences = self.frontier_divergences.check(shared_frontiers);
+        self.pending_frontier_divergences.extend(divergences);
+        let present = self
+            .storage
+            .collections()
+            .map(|(id, _)| *id)
+            .chain(compute_frontiers.keys().copied())
+            .collect();
+        self.creation_times.observe(now, present);
         self.storage.record_frontiers(compute_frontiers).await;
 
         let compute_replica_frontiers = self.compute.replica_write_frontiers();
         self.storage
             .record_replica_frontiers(compute_replica_frontiers)
             .await;
+
+        self.record_wallclock_lags(wallclock_lags).await;
+        self.record_advancement_rates(advancement_rates).await;
+    }
+
+    /// Records the changes to the rates at which write frontiers advance in the
+    /// `FrontierAdvancementRates` introspection collection.
+    async fn record_advancement_rates(&mut self, rates: Vec<(RecordedRate, Diff)>) {
+        if rates.is_empty() {
+            return;
+        }
+        let updates = rates
+            .into_iter()
+            .map(|(rate, diff)| {
+                let row = Row::pack_slice(&[
+                    Datum::String(&rate.id.to_string()),
+                    Datum::Float64(rate.rate.into()),
+                    Datum::TimestampTz(
+                        mz_ore::now::to_datetime(rate.measured_at)
+                            .try_into()
+                            .expect("must fit"),
+                    ),
+                ]);
+                (row, diff)
+            })
+            .collect();
+        self.storage
+            .record_introspection_updates(IntrospectionType::FrontierAdvancementRates, updates)
+            .await;
+    }
+
+    /// Records the replica processes that crossed the disk usage high-water mark in the
+    /// `ReplicaStatusHistory` introspection collection.
+    async fn record_disk_high_water_marks(&mut self, crossed: &[ReplicaDiskHighWaterMark]) {
+        let occurred_at = mz_ore::now::to_datetime((self.now)())
+            .try_into()
+            .expect("must fit");
+        let updates = crossed
+            .iter()
+            .map(|mark| {
+                let reason = format!(
+                    "disk usage of {} bytes out of {} crossed the high-water mark",
+                    mark.disk_usage_bytes, mark.disk_capacity_bytes
+                );
+                let row = Row::pack_slice(&[
+                    Datum::String(&mark.replica_id.to_string()),
+                    Datum::UInt64(mark.process_id),
+                    Datum::String("high-disk-usage"),
+                    Datum::String(&reason),
+                    Datum::TimestampTz(occurred_at),
+                ]);
+                (row, 1)
+            })
+            .collect();
+        self.storage
+            .record_introspection_updates(IntrospectionType::ReplicaStatusHistory, updates)
+            .await;
+    }
+
+    /// Records the given lags in the `WallclockLag` collection, retracting the previously
+    /// recorded ones.
+    async fn record_wallclock_lags(&mut self, lags: BTreeMap<GlobalId, EpochMillis>) {
+        let mut updates = Vec::new();
+        let mut push_update = |id: GlobalId, lag: EpochMillis, diff: Diff| {
+            let lag_micros = i64::try_from(lag)
+                .ok()
+                .and_then(|lag| lag.checked_mul(1_000))
+                .unwrap_or(i64::MAX);
+            let row = Row::pack_slice(&[
+                Datum::String(&id.to_string()),
+                Datum::Interval(Interval::new(0, 0, lag_micros)),
+            ]);
+            updates.push((row, diff));
+        };
+
+        let mut old_lags = mem::replace(&mut self.recorded_wallclock_lags, lags);
+        for (&id, &new) in &self.recorded_wallclock_lags {
+            match old_lags.remove(&id) {
+                Some(old) if old != new => {
+                    push_update(id, new, 1);
+                    push_update(id, old, -1);
+                }
+                Some(_) => (),
+                None => push_update(id, new, 1),
+            }
+        }
+        for (id, old) in old_lags {
+            push_update(id, old, -1);
+        }
+
+        if !updates.is_empty() {
+            self.storage
+                .record_introspection_updates(IntrospectionType::WallclockLag, updates)
+                .await;
+        }
     }
 
     /// Produces a timestamp that reflects all data available in
@@ -419,6 +1535,10 @@ where
     T: Into<mz_repr::Timestamp>,
 {
     /// Creates a new controller.
+    ///
+    /// Returns an error if `config` contains a malformed URL, or if
+    /// `persist_txn_tables` cannot safely take over from the mode the table
+    /// shards were last operated in.
     #[instrument(name = "controller::new", skip_all)]
     pub async fn new(
         config: ControllerConfig,
@@ -426,47 +1546,1154 @@ where
         // Whether to use the new persist-txn tables implementation or the
         // legacy one.
         persist_txn_tables: PersistTxnTablesImpl,
-    ) -> Self {
+    ) -> Result<Self, StorageError> {
+        config.validate().map_err(StorageError::Generic)?;
+
         let storage_controller = mz_storage_controller::Controller::new(
             config.build_info,
             config.storage_stash_url,
             config.persist_location,
             config.persist_clients,
-            config.now,
+            config.now.clone(),
             config.stash_metrics,
             envd_epoch,
             config.metrics_registry.clone(),
             persist_txn_tables,
             config.connection_context,
         )
-        .await;
+        .await?;
+        let previous_persist_txn_tables = storage_controller.previous_persist_txn_tables();
 
         let compute_controller = ComputeController::new(
             config.build_info,
             envd_epoch,
             config.metrics_registry.clone(),
+            // Record storage and compute commands to the same log.
+            storage_controller.command_recorder().clone(),
         );
         let (metrics_tx, metrics_rx) = mpsc::unbounded_channel();
+        let (read_holds_tx, read_holds_rx) = mpsc::unbounded_channel();
 
-        let mut frontiers_ticker = time::interval(Duration::from_secs(1));
-        frontiers_ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
-
-        Self {
+        Ok(Self {
             storage: Box::new(storage_controller),
             compute: compute_controller,
             clusterd_image: config.clusterd_image,
             init_container_image: config.init_container_image,
+            envd_epoch,
             orchestrator: config.orchestrator.namespace("cluster"),
             readiness: Readiness::NotReady,
             metrics_tasks: BTreeMap::new(),
             metrics_tx,
             metrics_rx: UnboundedReceiverStream::new(metrics_rx).peekable(),
-            frontiers_ticker,
+            frontiers_ticker: frontiers_ticker(),
+            disk_high_water_marks: DiskHighWaterMarks::new(DEFAULT_REPLICA_DISK_HIGH_WATER_MARK),
+            pending_disk_high_water_marks: Vec::new(),
+            managed_replicas: BTreeMap::new(),
+            replica_resource_limits: BTreeMap::new(),
+            crash_loops: CrashLoops::new(ServiceSchedulingConfig::default().crash_loop),
+            pending_crash_loops: Vec::new(),
+            frontier_divergences: FrontierDivergences::new(&config.metrics_registry),
+            pending_frontier_divergences: Vec::new(),
+            read_holds_tx,
+            read_holds_rx: UnboundedReceiverStream::new(read_holds_rx).peekable(),
             persist_pubsub_url: config.persist_pubsub_url,
             persist_txn_tables,
+            previous_persist_txn_tables,
             secrets_args: config.secrets_args,
-            watch_sets: BTreeMap::new(),
-            immediate_watch_sets: Vec::new(),
+            watch_sets: WatchSets::new(&config.metrics_registry),
+            frontier_epoch: 0,
+            retained_drops: RetainedDrops::default(),
+            drop_batches: DropBatches::default(),
+            pending_dropped_batches: Vec::new(),
+            now: config.now,
+            epoch_millis_collections: BTreeSet::new(),
+            recorded_wallclock_lags: BTreeMap::new(),
+            advancement_rates: AdvancementRates::new(&config.metrics_registry),
+            creation_times: CreationTimes::default(),
+            responses: ResponseQueue::new(&config.metrics_registry),
+        })
+    }
+}
+
+/// Returns the ticker that schedules the periodic recording of frontiers.
+fn frontiers_ticker() -> Interval {
+    let mut ticker = time::interval(Duration::from_secs(1));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    ticker
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use bytesize::ByteSize;
+    use futures::stream::BoxStream;
+    use mz_build_info::DUMMY_BUILD_INFO;
+    use mz_compute_client::controller::{ComputeReplicaConfig, ComputeReplicaLogging};
+    use mz_compute_client::protocol::command::{ComputeCommand, PeekTarget};
+    use mz_compute_client::protocol::response::ComputeResponse;
+    use mz_compute_client::service::proto_compute_server::ProtoComputeServer;
+    use mz_compute_types::dataflows::{DataflowDescription, IndexDesc};
+    use mz_compute_types::plan::Plan;
+    use mz_compute_types::sinks::{
+        ComputeSinkConnection, ComputeSinkDesc, S3OneshotSinkConnection, SubscribeSinkConnection,
+    };
+    use mz_controller_types::ClusterId;
+    use mz_expr::{MapFilterProject, RowSetFinishing};
+    use mz_orchestrator::scheduling_config::ServiceSchedulingConfig;
+    use mz_orchestrator::{CpuLimit, MemoryLimit, Service, ServiceConfig, ServiceEvent};
+    use mz_ore::now::SYSTEM_TIME;
+    use mz_persist_client::ShardId;
+    use mz_repr::{RelationDesc, RelationType, ScalarType};
+    use mz_secrets::{InMemorySecretsController, SecretsController};
+    use mz_service::client::GenericClient;
+    use mz_service::grpc::GrpcServer;
+    use mz_service::secrets::SecretsControllerKind;
+    use mz_storage_client::controller::CollectionDescription;
+    use mz_storage_types::connections::aws::{AwsAssumeRole, AwsAuth, AwsConnection};
+    use mz_storage_types::controller::CollectionMetadata;
+    use mz_storage_types::read_policy::ReadPolicy;
+    use tokio_postgres::NoTls;
+
+    use super::*;
+    use crate::clusters::{
+        ClusterConfig, ClusterRole, CreateReplicaConfig, ManagedReplicaAvailabilityZones,
+        ManagedReplicaLocation, ReplicaAllocation, ReplicaConfig, ReplicaLocation,
+    };
+
+    /// An orchestrator that pretends to run services, and reports `metrics` for the processes
+    /// of each of them. It enforces memory limits, but not CPU or disk limits.
+    #[derive(Debug, Clone, Default)]
+    struct TestOrchestrator {
+        metrics: Vec<ServiceProcessMetrics>,
+        /// The addresses of the services' ports, by port name. Nothing listens on the other
+        /// ports.
+        addresses: BTreeMap<String, String>,
+    }
+
+    /// A service that is not running, except for what listens on its `addresses`.
+    #[derive(Debug)]
+    struct TestService {
+        addresses: BTreeMap<String, String>,
+        resource_limits: ResourceLimits,
+    }
+
+    impl Service for TestService {
+        fn addresses(&self, port: &str) -> Vec<String> {
+            let address = self.addresses.get(port).cloned();
+            vec![address.unwrap_or_else(|| "localhost:0".into())]
+        }
+
+        fn resource_limits(&self) -> ResourceLimits {
+            self.resource_limits
+        }
+    }
+
+    impl Orchestrator for TestOrchestrator {
+        fn namespace(&self, _namespace: &str) -> Arc<dyn NamespacedOrchestrator> {
+            Arc::new(self.clone())
+        }
+    }
+
+    #[async_trait]
+    impl NamespacedOrchestrator for TestOrchestrator {
+        async fn ensure_service(
+            &self,
+            _id: &str,
+            config: ServiceConfig<'_>,
+        ) -> Result<Box<dyn Service>, anyhow::Error> {
+            Ok(Box::new(TestService {
+                addresses: self.addresses.clone(),
+                resource_limits: ResourceLimits {
+                    memory_limit: config.memory_limit,
+                    ..Default::default()
+                },
+            }))
+        }
+
+        async fn drop_service(&self, _id: &str) -> Result<(), anyhow::Error> {
+            Ok(())
         }
+
+        async fn list_services(&self) -> Result<Vec<String>, anyhow::Error> {
+            Ok(vec![])
+        }
+
+        fn watch_services(&self) -> BoxStream<'static, Result<ServiceEvent, anyhow::Error>> {
+            Box::pin(futures::stream::empty())
+        }
+
+        async fn fetch_service_metrics(
+            &self,
+            _id: &str,
+        ) -> Result<Vec<ServiceProcessMetrics>, anyhow::Error> {
+            Ok(self.metrics.clone())
+        }
+
+        fn update_scheduling_config(&self, _config: ServiceSchedulingConfig) {}
+    }
+
+    /// Returns a controller whose storage stash lives in a fresh schema of the CockroachDB at
+    /// `COCKROACH_URL`, and whose persist shards live in memory.
+    async fn test_controller() -> Controller {
+        let envd_epoch = NonZeroI64::new(1).expect("nonzero");
+        test_controller_with(TestOrchestrator::default(), envd_epoch).await
+    }
+
+    /// Like [`test_controller`], but provisions replicas with `orchestrator`, and runs in the
+    /// `environmentd` epoch `envd_epoch`.
+    async fn test_controller_with(
+        orchestrator: TestOrchestrator,
+        envd_epoch: NonZeroI64,
+    ) -> Controller {
+        let cockroach_url =
+            std::env::var("COCKROACH_URL").expect("COCKROACH_URL environment variable is not set");
+        let (client, conn) = tokio_postgres::connect(&cockroach_url, NoTls)
+            .await
+            .expect("connecting to CockroachDB");
+        mz_ore::task::spawn(|| "test-controller-postgres-conn", async move {
+            if let Err(err) = conn.await {
+                panic!("connection error: {}", err);
+            }
+        });
+        let schema = format!("storage_{}", Uuid::new_v4().simple());
+        client
+            .batch_execute(&format!("CREATE SCHEMA {schema}"))
+            .await
+            .expect("creating stash schema");
+
+        let metrics_registry = MetricsRegistry::new();
+        let secrets_controller = InMemorySecretsController::new();
+        let config = ControllerConfig {
+            build_info: &DUMMY_BUILD_INFO,
+            orchestrator: Arc::new(orchestrator),
+            persist_location: PersistLocation::new_in_mem(),
+            persist_clients: Arc::new(PersistClientCache::new_no_metrics()),
+            storage_stash_url: format!("{cockroach_url}?options=--search_path={schema}"),
+            clusterd_image: "clusterd".into(),
+            init_container_image: None,
+            now: SYSTEM_TIME.clone(),
+            stash_metrics: Arc::new(StashMetrics::register_into(&metrics_registry)),
+            metrics_registry,
+            persist_pubsub_url: "http://localhost:6879".into(),
+            secrets_args: SecretsReaderCliArgs {
+                secrets_reader: SecretsControllerKind::LocalFile,
+                secrets_reader_local_file_dir: None,
+                secrets_reader_kubernetes_context: None,
+                secrets_reader_aws_prefix: None,
+            },
+            connection_context: ConnectionContext::for_tests(secrets_controller.reader()),
+        };
+        let mut controller = Controller::new(config, envd_epoch, PersistTxnTablesImpl::Eager)
+            .await
+            .expect("creating controller");
+        controller
+            .storage
+            .init_txns(mz_repr::Timestamp::minimum())
+            .await
+            .expect("initializing txns");
+        controller
+    }
+
+    /// Creates a storage collection maintained by compute with ID `id`.
+    async fn create_collection(controller: &mut Controller, id: GlobalId) {
+        let desc =
+            CollectionDescription::from_desc(RelationDesc::empty(), DataSourceOther::Compute);
+        controller
+            .storage
+            .create_collections(None, vec![(id, desc)])
+            .await
+            .expect("creating collection");
+    }
+
+    /// Creates a cluster with ID `cluster_id` and a single managed replica with ID `replica_id`.
+    async fn create_managed_replica(
+        controller: &mut Controller,
+        cluster_id: ClusterId,
+        replica_id: ReplicaId,
+    ) {
+        create_limited_replica(controller, cluster_id, replica_id, None, None).await;
+    }
+
+    /// Like [`create_managed_replica`], but requests the given memory and CPU limits for the
+    /// replica.
+    async fn create_limited_replica(
+        controller: &mut Controller,
+        cluster_id: ClusterId,
+        replica_id: ReplicaId,
+        memory_limit: Option<MemoryLimit>,
+        cpu_limit: Option<CpuLimit>,
+    ) {
+        controller
+            .create_cluster(
+                cluster_id,
+                ClusterConfig {
+                    arranged_logs: BTreeMap::new(),
+                },
+            )
+            .expect("creating cluster");
+        let location = ManagedReplicaLocation {
+            allocation: ReplicaAllocation {
+                memory_limit,
+                cpu_limit,
+                disk_limit: None,
+                scale: 1,
+                workers: 1,
+                credits_per_hour: 1.into(),
+                cpu_exclusive: false,
+                disabled: false,
+                selectors: BTreeMap::new(),
+            },
+            size: "1".into(),
+            internal: false,
+            billed_as: None,
+            availability_zones: ManagedReplicaAvailabilityZones::FromReplica(None),
+            disk: true,
+        };
+        controller
+            .create_replicas(
+                vec![CreateReplicaConfig {
+                    cluster_id,
+                    replica_id,
+                    role: ClusterRole::User,
+                    config: ReplicaConfig {
+                        location: ReplicaLocation::Managed(location),
+                        compute: ComputeReplicaConfig {
+                            logging: ComputeReplicaLogging::default(),
+                            idle_arrangement_merge_effort: None,
+                        },
+                    },
+                }],
+                false,
+            )
+            .await
+            .expect("creating replica");
+    }
+
+    /// A compute replica that records the commands it receives and never responds.
+    #[derive(Debug)]
+    struct RecordingReplica {
+        commands_tx: UnboundedSender<ComputeCommand>,
+    }
+
+    #[async_trait]
+    impl GenericClient<ComputeCommand, ComputeResponse> for RecordingReplica {
+        async fn send(&mut self, cmd: ComputeCommand) -> Result<(), anyhow::Error> {
+            let _ = self.commands_tx.send(cmd);
+            Ok(())
+        }
+
+        async fn recv(&mut self) -> Result<Option<ComputeResponse>, anyhow::Error> {
+            futures::future::pending().await
+        }
+    }
+
+    /// Serves a [`RecordingReplica`] on a free local port, returning its address and the
+    /// commands it receives.
+    fn spawn_recording_replica() -> (String, mpsc::UnboundedReceiver<ComputeCommand>) {
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .expect("finding a free port");
+        let (commands_tx, commands_rx) = mpsc::unbounded_channel();
+        mz_ore::task::spawn(|| "recording-replica", async move {
+            GrpcServer::serve(
+                mz_ore::netio::SocketAddr::Inet(addr),
+                DUMMY_BUILD_INFO.semver_version(),
+                move || RecordingReplica {
+                    commands_tx: commands_tx.clone(),
+                },
+                ProtoComputeServer::new,
+            )
+            .await
+            .expect("serving replica");
+        });
+        (addr.to_string(), commands_rx)
+    }
+
+    /// Returns the IDs of the dataflows a [`RecordingReplica`] is asked to create, and whether it
+    /// is asked to serve the peek `peek_uuid`, up to the end of its next initialization.
+    async fn next_initialization(
+        commands: &mut mpsc::UnboundedReceiver<ComputeCommand>,
+        peek_uuid: Uuid,
+    ) -> (BTreeSet<GlobalId>, bool) {
+        let mut dataflow_ids = BTreeSet::new();
+        let mut peeked = false;
+        loop {
+            match commands.recv().await.expect("replica serving") {
+                ComputeCommand::CreateDataflow(dataflow) => {
+                    dataflow_ids.extend(dataflow.export_ids());
+                }
+                ComputeCommand::Peek(peek) => peeked |= peek.uuid == peek_uuid,
+                ComputeCommand::InitializationComplete => return (dataflow_ids, peeked),
+                _ => (),
+            }
+        }
+    }
+
+    /// Returns a dataflow as of 1 that exports `index_id` and the given sinks, and builds
+    /// nothing.
+    fn dataflow(
+        index_id: Option<GlobalId>,
+        sinks: Vec<(GlobalId, ComputeSinkConnection)>,
+    ) -> DataflowDescription<Plan> {
+        let index_exports = index_id.into_iter().map(|id| {
+            let index = IndexDesc {
+                on_id: id,
+                key: vec![],
+            };
+            (id, (index, RelationType::empty()))
+        });
+        let sink_exports = sinks.into_iter().map(|(id, connection)| {
+            let sink = ComputeSinkDesc {
+                from: GlobalId::User(1),
+                from_desc: RelationDesc::empty(),
+                connection,
+                with_snapshot: true,
+                up_to: Antichain::new(),
+                non_null_assertions: vec![],
+                refresh_schedule: None,
+            };
+            (id, sink)
+        });
+        DataflowDescription {
+            source_imports: BTreeMap::new(),
+            index_imports: BTreeMap::new(),
+            objects_to_build: vec![],
+            index_exports: index_exports.collect(),
+            sink_exports: sink_exports.collect(),
+            as_of: Some(Antichain::from_elem(1u64.into())),
+            until: Antichain::new(),
+            debug_name: "test".into(),
+        }
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // error: unsupported operation: can't call foreign function `socket` on OS `linux`
+    async fn test_envd_epoch() {
+        let envd_epoch = NonZeroI64::new(7).expect("nonzero");
+        let controller = test_controller_with(TestOrchestrator::default(), envd_epoch).await;
+        assert_eq!(controller.envd_epoch(), envd_epoch);
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // error: unsupported operation: can't call foreign function `socket` on OS `linux`
+    async fn test_effective_since() {
+        let mut controller = test_controller().await;
+        let id = GlobalId::User(1);
+        create_collection(&mut controller, id).await;
+        assert_eq!(controller.effective_since(GlobalId::User(2)), None);
+
+        // A compaction window of 100 lets the since follow the write frontier at a distance.
+        controller.storage.set_read_policy(vec![(
+            id,
+            ReadPolicy::lag_writes_by(100u64.into(), 100u64.into()),
+        )]);
+        let hold = controller
+            .pin_read_frontier(id, 250u64.into())
+            .await
+            .expect("hold at or beyond the since");
+        controller
+            .storage
+            .update_write_frontiers(&[(id, Antichain::from_elem(1000u64.into()))]);
+
+        // The read hold keeps the since from following the compaction window.
+        let since = controller.effective_since(id).expect("collection exists");
+        assert_eq!(since, Antichain::from_elem(250u64.into()));
+        assert_eq!(
+            since,
+            controller
+                .storage
+                .collection(id)
+                .expect("collection exists")
+                .read_capabilities
+                .frontier()
+                .to_owned()
+        );
+
+        // Once the hold is released, the compaction window determines the since.
+        drop(hold);
+        controller.release_read_holds();
+        assert_eq!(
+            controller.effective_since(id),
+            Some(Antichain::from_elem(900u64.into()))
+        );
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // error: unsupported operation: can't call foreign function `socket` on OS `linux`
+    async fn test_ready_with_staged_responses() {
+        let mut controller = test_controller().await;
+        let id = GlobalId::User(1);
+        create_collection(&mut controller, id).await;
+        controller.storage.set_read_policy(vec![(
+            id,
+            ReadPolicy::lag_writes_by(100u64.into(), 100u64.into()),
+        )]);
+        let hold = controller
+            .pin_read_frontier(id, 250u64.into())
+            .await
+            .expect("hold at or beyond the since");
+        controller
+            .storage
+            .update_write_frontiers(&[(id, Antichain::from_elem(1000u64.into()))]);
+        drop(hold);
+
+        // The coordinator falls behind, so that responses stay staged. The released read hold
+        // is applied nonetheless.
+        let released = Antichain::from_elem(900u64.into());
+        for _ in 0..100 {
+            for _ in 0..2 {
+                controller
+                    .responses
+                    .push(ControllerResponse::WatchSetFinished(vec![]));
+            }
+            controller.ready().await;
+            controller.process().await.expect("processing succeeds");
+            if controller.effective_since(id) == Some(released.clone()) {
+                break;
+            }
+        }
+        assert!(!controller.responses.is_empty());
+        assert_eq!(controller.effective_since(id), Some(released));
+    }
+
+    #[mz_ore::test]
+    fn test_wallclock_lags() {
+        let frontier = |t: u64| Antichain::from_elem(mz_repr::Timestamp::from(t));
+        let lags = wallclock_lags(
+            1_000,
+            [
+                (GlobalId::User(1), frontier(400)),
+                (GlobalId::User(2), frontier(1_500)),
+                (GlobalId::User(3), Antichain::new()),
+            ]
+            .into_iter(),
+        );
+        assert_eq!(
+            lags,
+            BTreeMap::from([(GlobalId::User(1), 600), (GlobalId::User(2), 0)])
+        );
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // error: unsupported operation: can't call foreign function `socket` on OS `linux`
+    async fn test_record_frontiers_now() {
+        let mut controller = test_controller().await;
+        let id = GlobalId::User(1);
+        create_collection(&mut controller, id).await;
+        // New collections are observed only when frontiers are recorded.
+        assert_eq!(controller.collection_created_at(id), None);
+
+        // A scheduled recording is ready, but not yet processed.
+        controller.readiness = Readiness::Frontiers;
+        let before = (controller.now)();
+        controller.record_frontiers_now().await;
+
+        // The recording observed the new collection, and subsumed the scheduled one.
+        let created_at = controller
+            .collection_created_at(id)
+            .expect("frontiers recorded");
+        assert!(created_at >= before);
+        assert!(matches!(controller.readiness, Readiness::NotReady));
+
+        // The next scheduled recording is a full interval out.
+        time::pause();
+        assert!(controller.frontiers_ticker.tick().now_or_never().is_none());
+        time::advance(Duration::from_millis(900)).await;
+        assert!(controller.frontiers_ticker.tick().now_or_never().is_none());
+        time::advance(Duration::from_millis(100)).await;
+        assert!(controller.frontiers_ticker.tick().now_or_never().is_some());
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // error: unsupported operation: can't call foreign function `socket` on OS `linux`
+    async fn test_read_hold_token_release() {
+        let mut controller = test_controller().await;
+        let id = GlobalId::User(1);
+        create_collection(&mut controller, id).await;
+        controller.storage.set_read_policy(vec![(
+            id,
+            ReadPolicy::lag_writes_by(100u64.into(), 100u64.into()),
+        )]);
+        let since = |controller: &Controller| {
+            controller
+                .storage
+                .collection(id)
+                .expect("collection exists")
+                .read_capabilities
+                .frontier()
+                .to_owned()
+        };
+
+        let token = controller
+            .pin_read_frontier(id, 250u64.into())
+            .await
+            .expect("hold at or beyond the since");
+        assert_eq!(token.id(), id);
+        assert_eq!(*token.time(), mz_repr::Timestamp::from(250u64));
+        controller
+            .storage
+            .update_write_frontiers(&[(id, Antichain::from_elem(1000u64.into()))]);
+
+        // The token holds the since back, and times before it can no longer be pinned.
+        assert_eq!(since(&controller), Antichain::from_elem(250u64.into()));
+        assert!(matches!(
+            controller.pin_read_frontier(id, 200u64.into()).await,
+            Err(ControllerError::ReadFrontierBelowSince { .. })
+        ));
+
+        // Dropping the token releases the hold once the controller applies the release.
+        drop(token);
+        assert_eq!(since(&controller), Antichain::from_elem(250u64.into()));
+        controller.release_read_holds();
+        assert_eq!(since(&controller), Antichain::from_elem(900u64.into()));
+    }
+
+    fn disk_metrics(usage: Option<u64>, capacity: Option<u64>) -> ServiceProcessMetrics {
+        ServiceProcessMetrics {
+            cpu_nano_cores: None,
+            memory_bytes: None,
+            disk_usage_bytes: usage,
+            disk_capacity_bytes: capacity,
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_disk_high_water_mark() {
+        let mut marks = DiskHighWaterMarks::new(0.9);
+        let replica = ReplicaId::User(1);
+
+        // Below the mark, or with missing numbers, nothing is reported.
+        let below = [
+            disk_metrics(Some(50), Some(100)),
+            disk_metrics(Some(95), None),
+        ];
+        assert_eq!(marks.observe(replica, &below), vec![]);
+
+        // Crossing the mark is reported once per process.
+        let above = [
+            disk_metrics(Some(50), Some(100)),
+            disk_metrics(Some(95), Some(100)),
+        ];
+        let expected = vec![ReplicaDiskHighWaterMark {
+            replica_id: replica,
+            process_id: 1,
+            disk_usage_bytes: 95,
+            disk_capacity_bytes: 100,
+        }];
+        assert_eq!(marks.observe(replica, &above), expected);
+        assert_eq!(marks.observe(replica, &above), vec![]);
+
+        // Dropping back below the mark re-arms the report.
+        assert_eq!(marks.observe(replica, &below), vec![]);
+        assert_eq!(marks.observe(replica, &above), expected);
+
+        // Dropping the replica re-arms the report too.
+        marks.remove_replica(replica);
+        assert_eq!(marks.observe(replica, &above), expected);
+
+        // Lowering the threshold reports processes that are now above it.
+        marks.threshold = 0.5;
+        assert_eq!(
+            marks.observe(replica, &above),
+            vec![ReplicaDiskHighWaterMark {
+                replica_id: replica,
+                process_id: 0,
+                disk_usage_bytes: 50,
+                disk_capacity_bytes: 100,
+            }]
+        );
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // error: unsupported operation: can't call foreign function `socket` on OS `linux`
+    async fn test_disk_high_water_mark_status_history() {
+        let orchestrator = TestOrchestrator {
+            metrics: vec![
+                disk_metrics(Some(50), Some(100)),
+                disk_metrics(Some(95), Some(100)),
+            ],
+            ..Default::default()
+        };
+        let envd_epoch = NonZeroI64::new(1).expect("nonzero");
+        let mut controller = test_controller_with(orchestrator, envd_epoch).await;
+
+        let history_id = GlobalId::System(1);
+        let history_desc = CollectionDescription {
+            desc: RelationDesc::empty()
+                .with_column("replica_id", ScalarType::String.nullable(false))
+                .with_column("process_id", ScalarType::UInt64.nullable(false))
+                .with_column("status", ScalarType::String.nullable(false))
+                .with_column("reason", ScalarType::String.nullable(true))
+                .with_column(
+                    "occurred_at",
+                    ScalarType::TimestampTz { precision: None }.nullable(false),
+                ),
+            data_source: DataSource::Introspection(IntrospectionType::ReplicaStatusHistory),
+            since: None,
+            status_collection_id: None,
+        };
+        controller
+            .storage
+            .create_collections(None, vec![(history_id, history_desc)])
+            .await
+            .expect("creating collection");
+
+        let replica_id = ReplicaId::User(1);
+        create_managed_replica(&mut controller, ClusterId::User(1), replica_id).await;
+
+        // The replica's metrics are fetched right away. The process above the high-water mark
+        // is reported, and recorded in the status history.
+        let mut crossed = None;
+        let mut history = vec![];
+        for _ in 0..300 {
+            if time::timeout(Duration::from_millis(100), controller.ready())
+                .await
+                .is_ok()
+            {
+                let response = controller.process().await.expect("processing succeeds");
+                if let Some(ControllerResponse::ReplicaDiskHighWaterMark(marks)) = response {
+                    crossed = Some(marks);
+                }
+            }
+            let write_frontier = controller
+                .storage
+                .collection(history_id)
+                .expect("collection exists")
+                .write_frontier
+                .clone();
+            if let Some(as_of) = write_frontier.as_option().and_then(|t| t.step_back()) {
+                if let Ok(rows) = controller.storage.snapshot(history_id, as_of).await {
+                    history = rows;
+                }
+            }
+            if !history.is_empty() {
+                break;
+            }
+        }
+
+        assert_eq!(
+            crossed,
+            Some(vec![ReplicaDiskHighWaterMark {
+                replica_id,
+                process_id: 1,
+                disk_usage_bytes: 95,
+                disk_capacity_bytes: 100,
+            }])
+        );
+        assert_eq!(history.len(), 1);
+        let (row, diff) = &history[0];
+        assert_eq!(*diff, 1);
+        let datums = row.unpack();
+        assert_eq!(datums[0], Datum::String("u1"));
+        assert_eq!(datums[1], Datum::UInt64(1));
+        assert_eq!(datums[2], Datum::String("high-disk-usage"));
+        assert_eq!(
+            datums[3],
+            Datum::String("disk usage of 95 bytes out of 100 crossed the high-water mark")
+        );
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // error: unsupported operation: can't call foreign function `socket` on OS `linux`
+    async fn test_rehydrate_compute_instance() {
+        let (replica_addr, mut commands) = spawn_recording_replica();
+        let orchestrator = TestOrchestrator {
+            addresses: BTreeMap::from([("computectl".into(), replica_addr)]),
+            ..Default::default()
+        };
+        let envd_epoch = NonZeroI64::new(1).expect("nonzero");
+        let mut controller = test_controller_with(orchestrator, envd_epoch).await;
+        let cluster_id = ClusterId::User(1);
+        create_managed_replica(&mut controller, cluster_id, ReplicaId::User(1)).await;
+
+        // An index, with a subscribe, a `COPY TO`, and a peek reading from it.
+        let (index_id, subscribe_id, copy_to_id) = (
+            GlobalId::User(1),
+            GlobalId::Transient(2),
+            GlobalId::Transient(3),
+        );
+        let copy_to = S3OneshotSinkConnection {
+            prefix: "s3://bucket/prefix".into(),
+            aws_connection: AwsConnection {
+                auth: AwsAuth::AssumeRole(AwsAssumeRole {
+                    arn: "arn:aws:iam::123456789012:role/test".into(),
+                    session_name: None,
+                }),
+                region: None,
+                endpoint: None,
+            },
+        };
+        let dataflows = [
+            dataflow(Some(index_id), vec![]),
+            dataflow(
+                None,
+                vec![(
+                    subscribe_id,
+                    ComputeSinkConnection::Subscribe(SubscribeSinkConnection::default()),
+                )],
+            ),
+            dataflow(
+                None,
+                vec![(copy_to_id, ComputeSinkConnection::S3Oneshot(copy_to))],
+            ),
+        ];
+        for dataflow in dataflows {
+            controller
+                .active_compute()
+                .create_dataflow(cluster_id, dataflow)
+                .expect("creating dataflow");
+        }
+        let peek_uuid = Uuid::new_v4();
+        controller
+            .active_compute()
+            .peek(
+                cluster_id,
+                index_id,
+                None,
+                peek_uuid,
+                1u64.into(),
+                RowSetFinishing::trivial(0),
+                MapFilterProject::new(0)
+                    .into_plan()
+                    .expect("valid plan")
+                    .into_nontemporal()
+                    .expect("nontemporal plan"),
+                None,
+                PeekTarget::Index { id: index_id },
+            )
+            .expect("peeking");
+
+        controller.initialization_complete();
+
+        // The replica is asked to create the dataflows and to peek.
+        let all_ids = BTreeSet::from([index_id, subscribe_id, copy_to_id]);
+        let initialization = time::timeout(
+            Duration::from_secs(60),
+            next_initialization(&mut commands, peek_uuid),
+        );
+        let initialization = initialization.await.expect("replica initialized");
+        assert_eq!(initialization, (all_ids.clone(), true));
+
+        controller
+            .rehydrate_compute_instance(cluster_id)
+            .expect("instance exists");
+
+        // The active peek, subscribe, and `COPY TO` fail.
+        let reason = "compute instance is rehydrating";
+        let (mut peek_error, mut subscribe_error, mut copy_to_error) = (None, None, None);
+        time::timeout(Duration::from_secs(60), async {
+            while peek_error.is_none() || subscribe_error.is_none() || copy_to_error.is_none() {
+                controller.ready().await;
+                match controller.process().await.expect("processing succeeds") {
+                    Some(ControllerResponse::PeekResponse(uuid, PeekResponse::Error(e), _))
+                        if uuid == peek_uuid =>
+                    {
+                        peek_error = Some(e);
+                    }
+                    Some(ControllerResponse::SubscribeResponse(id, batch))
+                        if id == subscribe_id =>
+                    {
+                        subscribe_error = batch.updates.err();
+                    }
+                    Some(ControllerResponse::CopyToResponse(id, result)) if id == copy_to_id => {
+                        copy_to_error = result.err().map(|e| e.to_string());
+                    }
+                    _ => (),
+                }
+            }
+        })
+        .await
+        .expect("cancellations delivered");
+        assert_eq!(peek_error.as_deref(), Some(reason));
+        assert_eq!(subscribe_error.as_deref(), Some(reason));
+        assert_eq!(copy_to_error.as_deref(), Some(reason));
+
+        // The restarted replica is asked to recreate all dataflows, but not to serve the peek.
+        let initialization = time::timeout(
+            Duration::from_secs(60),
+            next_initialization(&mut commands, peek_uuid),
+        );
+        let initialization = initialization.await.expect("replica rehydrated");
+        assert_eq!(initialization, (all_ids, false));
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // error: unsupported operation: can't call foreign function `socket` on OS `linux`
+    async fn test_drop_collections_batched() {
+        let mut controller = test_controller().await;
+        // A schema with 50 tables and 50 indexes, spread over two clusters without replicas.
+        let cluster_ids = [ClusterId::User(1), ClusterId::User(2)];
+        for cluster_id in cluster_ids {
+            controller
+                .create_cluster(
+                    cluster_id,
+                    ClusterConfig {
+                        arranged_logs: BTreeMap::new(),
+                    },
+                )
+                .expect("creating cluster");
+        }
+        let tables: Vec<_> = (1..=50).map(GlobalId::User).collect();
+        let indexes: Vec<_> = (51..=100).map(GlobalId::User).collect();
+        let descs = tables.iter().map(|id| {
+            let desc = CollectionDescription::from_desc(
+                RelationDesc::empty(),
+                DataSourceOther::TableWrites,
+            );
+            (*id, desc)
+        });
+        controller
+            .storage
+            .create_collections(Some(1u64.into()), descs.collect())
+            .await
+            .expect("creating tables");
+        for (i, id) in indexes.iter().enumerate() {
+            controller
+                .active_compute()
+                .create_dataflow(cluster_ids[i % 2], dataflow(Some(*id), vec![]))
+                .expect("creating dataflow");
+        }
+        controller.initialization_complete();
+        let exists = |controller: &Controller, id| {
+            controller.storage.collection(id).is_ok()
+                || controller.compute.find_collection(id).is_ok()
+        };
+
+        // An unknown collection fails the whole batch, before anything is dropped.
+        let mut ids: Vec<_> = tables.iter().chain(&indexes).copied().collect();
+        ids.push(GlobalId::User(101));
+        assert!(controller.drop_collections_batched(ids).is_err());
+        assert!(tables
+            .iter()
+            .chain(&indexes)
+            .all(|id| exists(&controller, *id)));
+
+        // The batch is reported once, after all of its collections are gone from both layers.
+        let ids: Vec<_> = tables.iter().chain(&indexes).copied().collect();
+        let batch = controller
+            .drop_collections_batched(ids)
+            .expect("collections exist");
+        let dropped = time::timeout(Duration::from_secs(60), async {
+            loop {
+                controller.ready().await;
+                if let Some(ControllerResponse::CollectionsDropped(dropped)) =
+                    controller.process().await.expect("processing succeeds")
+                {
+                    return dropped;
+                }
+            }
+        });
+        assert_eq!(dropped.await.expect("collections dropped"), vec![batch]);
+        assert!(!tables
+            .iter()
+            .chain(&indexes)
+            .any(|id| exists(&controller, *id)));
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // error: unsupported operation: can't call foreign function `socket` on OS `linux`
+    async fn test_replica_resource_limits() {
+        // Each process uses half of its memory limit and one CPU.
+        let metrics = ServiceProcessMetrics {
+            cpu_nano_cores: Some(1_000_000_000),
+            memory_bytes: Some(ByteSize::mib(512).as_u64()),
+            disk_usage_bytes: None,
+            disk_capacity_bytes: None,
+        };
+        let orchestrator = TestOrchestrator {
+            metrics: vec![metrics],
+            ..Default::default()
+        };
+        let envd_epoch = NonZeroI64::new(1).expect("nonzero");
+        let mut controller = test_controller_with(orchestrator, envd_epoch).await;
+        let (cluster_id, replica_id) = (ClusterId::User(1), ReplicaId::User(1));
+        assert_eq!(controller.replica_resource_limits(replica_id), None);
+
+        create_limited_replica(
+            &mut controller,
+            cluster_id,
+            replica_id,
+            Some(MemoryLimit(ByteSize::gib(1))),
+            Some(CpuLimit::from_millicpus(2000)),
+        )
+        .await;
+
+        // Only the limits the orchestrator enforces are reported, and utilization is known only
+        // for the limited resources.
+        let limits = controller
+            .replica_resource_limits(replica_id)
+            .expect("replica provisioned");
+        assert_eq!(
+            limits,
+            ResourceLimits {
+                memory_limit: Some(MemoryLimit(ByteSize::gib(1))),
+                cpu_limit: None,
+                disk_limit: None,
+            }
+        );
+        assert_eq!(limits.memory_utilization(&metrics), Some(0.5));
+        assert_eq!(limits.cpu_utilization(&metrics), None);
+
+        // Dropped replicas have no limits anymore.
+        controller
+            .drop_replica(cluster_id, replica_id)
+            .await
+            .expect("dropping replica");
+        assert_eq!(controller.replica_resource_limits(replica_id), None);
+    }
+
+    #[mz_ore::test]
+    fn test_retained_drops() {
+        let mut drops = RetainedDrops::default();
+        let instance_id = ComputeInstanceId::User(1);
+        let (a, b, c) = (GlobalId::User(1), GlobalId::User(2), GlobalId::User(3));
+        drops.retain(instance_id, a, 10);
+        drops.retain(instance_id, b, 20);
+        drops.retain(instance_id, c, 30);
+
+        // Nothing expires before its time.
+        assert_eq!(drops.expire(9), vec![]);
+        assert!(drops.contains(a));
+
+        // Restored collections are no longer retained, and never expire.
+        assert_eq!(drops.restore(b), Some(instance_id));
+        assert_eq!(drops.restore(b), None);
+        assert!(!drops.contains(b));
+
+        // Expiration is inclusive, and returns each collection once.
+        assert_eq!(drops.expire(30), vec![(instance_id, a), (instance_id, c)]);
+        assert_eq!(drops.expire(30), vec![]);
+        assert!(!drops.contains(a));
+        assert_eq!(drops.restore(c), None);
+
+        // Dropping an instance releases the collections retained on it, and only those.
+        let other_instance_id = ComputeInstanceId::User(2);
+        drops.retain(instance_id, a, 10);
+        drops.retain(other_instance_id, b, 20);
+        assert_eq!(drops.on_instance(instance_id), vec![a]);
+        assert_eq!(drops.release_instance(instance_id), vec![a]);
+        assert_eq!(drops.on_instance(instance_id), vec![]);
+        assert!(!drops.contains(a));
+        assert!(drops.contains(b));
+    }
+
+    #[mz_ore::test]
+    fn test_collection_inventory() {
+        let ts = |t: u64| Antichain::from_elem(mz_repr::Timestamp::from(t));
+        let storage_collection = |data_source, since: u64, upper: u64| {
+            let metadata = CollectionMetadata {
+                persist_location: PersistLocation {
+                    blob_uri: "mem://".into(),
+                    consensus_uri: "mem://".into(),
+                },
+                remap_shard: None,
+                data_shard: ShardId::new(),
+                status_shard: None,
+                relation_desc: RelationDesc::empty(),
+                txns_shard: None,
+            };
+            let description = CollectionDescription {
+                desc: RelationDesc::empty(),
+                data_source,
+                since: None,
+                status_collection_id: None,
+            };
+            StorageCollectionState::new(description, ts(since), ts(upper), vec![], metadata)
+        };
+
+        let (source, table, mv, index, log, subscribe) = (
+            GlobalId::User(1),
+            GlobalId::User(2),
+            GlobalId::User(3),
+            GlobalId::User(4),
+            GlobalId::System(5),
+            GlobalId::Transient(6),
+        );
+        let storage = BTreeMap::from([
+            (
+                source,
+                storage_collection(DataSource::Other(DataSourceOther::Source), 1, 10),
+            ),
+            (
+                table,
+                storage_collection(DataSource::Other(DataSourceOther::TableWrites), 2, 20),
+            ),
+            (
+                mv,
+                storage_collection(DataSource::Other(DataSourceOther::Compute), 3, 30),
+            ),
+        ]);
+        let compute = BTreeMap::from([
+            (mv, ComputeCollectionState::new(ts(3), vec![], vec![])),
+            (index, ComputeCollectionState::new(ts(4), vec![mv], vec![])),
+            (log, ComputeCollectionState::new_log_collection()),
+            (
+                subscribe,
+                ComputeCollectionState::new(ts(6), vec![], vec![index]),
+            ),
+        ]);
+        let instance_id = ComputeInstanceId::User(1);
+        let overrides = SourceParameterOverrides {
+            max_batch_size: Some(1024),
+            ..Default::default()
+        };
+
+        let inventory = collection_inventory(
+            storage.iter(),
+            &BTreeMap::from([(source, overrides.clone())]),
+            std::iter::empty(),
+            compute.iter().map(|(id, c)| (instance_id, id, c)),
+        );
+        let summary: Vec<_> = inventory
+            .iter()
+            .map(|e| (e.id, e.owner, e.kind, e.read_frontier.clone()))
+            .collect();
+        let compute_owner = CollectionOwner::Compute(instance_id);
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    source,
+                    CollectionOwner::Storage,
+                    CollectionKind::Source,
+                    ts(1)
+                ),
+                (
+                    table,
+                    CollectionOwner::Storage,
+                    CollectionKind::Table,
+                    ts(2)
+                ),
+                (log, compute_owner, CollectionKind::Log, ts(0)),
+                (mv, compute_owner, CollectionKind::MaterializedView, ts(3)),
+                (index, compute_owner, CollectionKind::Index, ts(4)),
+                (subscribe, compute_owner, CollectionKind::Transient, ts(6)),
+            ]
+        );
+        assert_eq!(inventory[0].write_frontier, ts(10));
+        assert_eq!(inventory[0].parameter_overrides, Some(overrides));
+        assert_eq!(inventory[1].parameter_overrides, None);
+
+        let json = serde_json::to_string(&inventory).expect("serializable");
+        let roundtrip: Vec<CollectionInventoryEntry> =
+            serde_json::from_str(&json).expect("deserializable");
+        assert_eq!(roundtrip, inventory);
+    }
+
+    #[mz_ore::test]
+    fn test_validate_url() {
+        let schemes = ["http", "https"];
+        assert!(validate_url("persist pubsub", "http://localhost:6879", &schemes).is_ok());
+
+        let err = validate_url("persist pubsub", "localhost:6879", &schemes).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid persist pubsub URL \"localhost:6879\": scheme must be one of http, https"
+        );
+        let err = validate_url("persist pubsub", "http//localhost:6879", &schemes).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid persist pubsub URL \"http//localhost:6879\": relative URL without a base"
+        );
     }
 }
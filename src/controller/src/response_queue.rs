@@ -0,0 +1,285 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Stages the responses of the controller by class, so that latency-critical responses are not
+//! delayed behind bulk ones when the coordinator falls behind.
+//!
+//! Responses that are ready right away are staged and handed out in order of their class rather
+//! than of their arrival:
+//!
+//! * Immediate responses, e.g. peek responses and finished watch sets, are small and waited on by
+//!   clients. They are handed out first, in arrival order.
+//! * Subscribe batches are handed out next, in arrival order, so that the batches of each
+//!   subscribe stay ordered.
+//! * Replica metrics and storage statistics report current values, so a newer report supersedes
+//!   an older one for the same replica, or source or sink worker. They are coalesced and handed
+//!   out last.
+//!
+//! The periodic recording of frontiers needs no staging, as its ticker skips the ticks that were
+//! missed while the controller was busy.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use mz_cluster_client::ReplicaId;
+use mz_orchestrator::ServiceProcessMetrics;
+use mz_ore::metric;
+use mz_ore::metrics::{raw, MetricsRegistry};
+use mz_repr::GlobalId;
+use mz_storage_client::statistics::{SinkStatisticsUpdate, SourceStatisticsUpdate};
+
+use crate::ControllerResponse;
+
+/// The default number of ready responses that are staged at once.
+pub const DEFAULT_RESPONSE_STAGING_LIMIT: usize = 64;
+
+const IMMEDIATE: &str = "immediate";
+const SUBSCRIBE: &str = "subscribe";
+const COALESCABLE: &str = "coalescable";
+
+/// Responses of the controller that have yet to be handed out, by class.
+#[derive(Debug)]
+pub(crate) struct ResponseQueue<T> {
+    /// The number of ready responses that are staged at once.
+    pub(crate) staging_limit: usize,
+    immediate: VecDeque<ControllerResponse<T>>,
+    subscribes: VecDeque<ControllerResponse<T>>,
+    /// The latest metrics of each replica.
+    replica_metrics: BTreeMap<ReplicaId, Vec<ServiceProcessMetrics>>,
+    /// The latest statistics of each source worker.
+    source_statistics: BTreeMap<(GlobalId, usize), SourceStatisticsUpdate>,
+    /// The latest statistics of each sink worker.
+    sink_statistics: BTreeMap<(GlobalId, usize), SinkStatisticsUpdate>,
+    /// The number of staged responses of each class.
+    depth: raw::IntGaugeVec,
+    /// Counts the responses of each class that were superseded by newer ones.
+    coalesced_total: raw::IntCounterVec,
+}
+
+impl<T> ResponseQueue<T> {
+    pub(crate) fn new(metrics_registry: &MetricsRegistry) -> Self {
+        Self {
+            staging_limit: DEFAULT_RESPONSE_STAGING_LIMIT,
+            immediate: VecDeque::new(),
+            subscribes: VecDeque::new(),
+            replica_metrics: BTreeMap::new(),
+            source_statistics: BTreeMap::new(),
+            sink_statistics: BTreeMap::new(),
+            depth: metrics_registry.register(metric!(
+                name: "mz_controller_response_queue_depth",
+                help: "The number of controller responses of a class that have yet to be handed \
+                       out to the coordinator.",
+                var_labels: ["class"],
+            )),
+            coalesced_total: metrics_registry.register(metric!(
+                name: "mz_controller_responses_coalesced_total",
+                help: "The number of controller responses of a class that were superseded by \
+                       newer ones before they were handed out to the coordinator.",
+                var_labels: ["class"],
+            )),
+        }
+    }
+
+    /// Stages `response`, superseding any staged response that it reports newer values for.
+    pub(crate) fn push(&mut self, response: ControllerResponse<T>) {
+        let mut coalesced = 0;
+        match response {
+            ControllerResponse::SubscribeResponse(..) => self.subscribes.push_back(response),
+            ControllerResponse::ComputeReplicaMetrics(id, metrics) => {
+                if self.replica_metrics.insert(id, metrics).is_some() {
+                    coalesced += 1;
+                }
+            }
+            ControllerResponse::StorageStatistics(sources, sinks) => {
+                for stat in sources {
+                    let key = (stat.id, stat.worker_id);
+                    if self.source_statistics.insert(key, stat).is_some() {
+                        coalesced += 1;
+                    }
+                }
+                for stat in sinks {
+                    let key = (stat.id, stat.worker_id);
+                    if self.sink_statistics.insert(key, stat).is_some() {
+                        coalesced += 1;
+                    }
+                }
+            }
+            response => self.immediate.push_back(response),
+        }
+        if coalesced > 0 {
+            self.coalesced_total
+                .with_label_values(&[COALESCABLE])
+                .inc_by(coalesced);
+        }
+        self.update_depth();
+    }
+
+    /// Returns the staged response to hand out next, if any.
+    ///
+    /// All staged statistics are handed out together, in a single response.
+    pub(crate) fn pop(&mut self) -> Option<ControllerResponse<T>> {
+        let response = if let Some(response) = self.immediate.pop_front() {
+            Some(response)
+        } else if let Some(response) = self.subscribes.pop_front() {
+            Some(response)
+        } else if let Some((id, metrics)) = self.replica_metrics.pop_first() {
+            Some(ControllerResponse::ComputeReplicaMetrics(id, metrics))
+        } else if !self.source_statistics.is_empty() || !self.sink_statistics.is_empty() {
+            let sources = std::mem::take(&mut self.source_statistics);
+            let sinks = std::mem::take(&mut self.sink_statistics);
+            Some(ControllerResponse::StorageStatistics(
+                sources.into_values().collect(),
+                sinks.into_values().collect(),
+            ))
+        } else {
+            None
+        };
+        self.update_depth();
+        response
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.immediate.is_empty()
+            && self.subscribes.is_empty()
+            && self.replica_metrics.is_empty()
+            && self.source_statistics.is_empty()
+            && self.sink_statistics.is_empty()
+    }
+
+    fn update_depth(&self) {
+        let coalescable =
+            self.replica_metrics.len() + self.source_statistics.len() + self.sink_statistics.len();
+        for (class, depth) in [
+            (IMMEDIATE, self.immediate.len()),
+            (SUBSCRIBE, self.subscribes.len()),
+            (COALESCABLE, coalescable),
+        ] {
+            self.depth
+                .with_label_values(&[class])
+                .set(i64::try_from(depth).unwrap_or(i64::MAX));
+        }
+    }
+
+    #[cfg(test)]
+    fn coalesced(&self) -> u64 {
+        self.coalesced_total.with_label_values(&[COALESCABLE]).get()
+    }
+
+    #[cfg(test)]
+    fn depth(&self, class: &str) -> i64 {
+        self.depth.with_label_values(&[class]).get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_compute_client::protocol::response::{PeekResponse, SubscribeBatch};
+    use mz_ore::tracing::OpenTelemetryContext;
+    use mz_repr::Timestamp;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn subscribe(i: u64) -> ControllerResponse<Timestamp> {
+        ControllerResponse::SubscribeResponse(
+            GlobalId::User(i),
+            SubscribeBatch {
+                lower: Default::default(),
+                upper: Default::default(),
+                updates: Ok(Vec::new()),
+            },
+        )
+    }
+
+    fn statistics(source: u64, messages_received: u64) -> ControllerResponse<Timestamp> {
+        ControllerResponse::StorageStatistics(
+            vec![SourceStatisticsUpdate {
+                id: GlobalId::User(source),
+                worker_id: 0,
+                snapshot_committed: true,
+                messages_received,
+                bytes_received: 0,
+                updates_staged: 0,
+                updates_committed: 0,
+                envelope_state_bytes: 0,
+                envelope_state_records: 0,
+                rehydration_latency_ms: None,
+                snapshot_records_known: None,
+                snapshot_records_staged: None,
+            }],
+            Vec::new(),
+        )
+    }
+
+    #[mz_ore::test]
+    fn test_response_queue_overload() {
+        let registry = MetricsRegistry::new();
+        let mut queue = ResponseQueue::new(&registry);
+
+        // An overloaded coordinator: every peek response arrives behind a subscribe batch, and
+        // statistics and metrics of the same sources and replicas keep arriving.
+        let mut peeks = Vec::new();
+        for i in 0..100 {
+            queue.push(subscribe(i));
+            queue.push(statistics(i % 10, i));
+            queue.push(ControllerResponse::ComputeReplicaMetrics(
+                ReplicaId::User(i % 2),
+                vec![ServiceProcessMetrics::default()],
+            ));
+            let uuid = Uuid::new_v4();
+            peeks.push(uuid);
+            queue.push(ControllerResponse::PeekResponse(
+                uuid,
+                PeekResponse::Canceled,
+                OpenTelemetryContext::empty(),
+            ));
+        }
+        assert_eq!(queue.depth(IMMEDIATE), 100);
+        assert_eq!(queue.depth(SUBSCRIBE), 100);
+        assert_eq!(queue.depth(COALESCABLE), 12);
+        assert_eq!(queue.coalesced(), 188);
+
+        // Peek responses are handed out first, no matter how much is staged before them.
+        for uuid in peeks {
+            match queue.pop() {
+                Some(ControllerResponse::PeekResponse(popped, ..)) => assert_eq!(popped, uuid),
+                response => panic!("unexpected response: {response:?}"),
+            }
+        }
+        // Subscribe batches are handed out in arrival order.
+        for i in 0..100 {
+            match queue.pop() {
+                Some(ControllerResponse::SubscribeResponse(id, _)) => {
+                    assert_eq!(id, GlobalId::User(i))
+                }
+                response => panic!("unexpected response: {response:?}"),
+            }
+        }
+        // Only the latest metrics of each replica are handed out.
+        for i in 0..2 {
+            match queue.pop() {
+                Some(ControllerResponse::ComputeReplicaMetrics(id, _)) => {
+                    assert_eq!(id, ReplicaId::User(i))
+                }
+                response => panic!("unexpected response: {response:?}"),
+            }
+        }
+        // As are the latest statistics of each source, in a single response.
+        match queue.pop() {
+            Some(ControllerResponse::StorageStatistics(sources, sinks)) => {
+                let received: Vec<_> = sources.iter().map(|s| s.messages_received).collect();
+                assert_eq!(received, (90..100).collect::<Vec<_>>());
+                assert!(sinks.is_empty());
+            }
+            response => panic!("unexpected response: {response:?}"),
+        }
+        assert!(queue.pop().is_none());
+        assert!(queue.is_empty());
+        assert_eq!(queue.depth(COALESCABLE), 0);
+    }
+}
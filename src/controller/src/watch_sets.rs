@@ -0,0 +1,353 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Watch sets that finish once the write frontiers of collections have advanced.
+//!
+//! The pending watch sets of each collection are kept by the timestamp they wait for, so a
+//! frontier update only visits those that it finishes. During bulk DDL a single collection can
+//! be watched by thousands of watch sets whose timestamps are mostly far beyond its frontier,
+//! and rescanning all of them on every frontier update would be quadratic.
+
+use std::any::Any;
+use std::collections::{BTreeMap, BTreeSet};
+use std::rc::Rc;
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use mz_ore::cast::CastFrom;
+use mz_ore::metric;
+use mz_ore::metrics::{MetricsRegistry, UIntGauge};
+use mz_repr::GlobalId;
+use timely::order::TotalOrder;
+use timely::progress::{Antichain, Timestamp};
+use tokio::sync::oneshot;
+
+/// The token of a watch set installed by [`WatchSets::install_waiter`].
+///
+/// Rather than being returned to the client, it is notified once its watch set finishes.
+struct WatchSetWaiter(oneshot::Sender<()>);
+
+/// Identifies an installed watch set, e.g. to cancel it with
+/// [`Controller::cancel_watch_set`](crate::Controller::cancel_watch_set).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct WatchSetId(u64);
+
+/// Watch sets that are waiting for the write frontiers of collections to advance.
+pub struct WatchSets<T> {
+    /// Pending watch sets, by the ids they wait on and the timestamp they wait for. A watch set
+    /// waiting on several ids is shared between their entries and finishes once the frontiers
+    /// of all of them have advanced.
+    pending: BTreeMap<GlobalId, BTreeMap<T, Vec<Rc<(WatchSetId, Box<dyn Any>)>>>>,
+    /// The timestamp each pending watch set waits for, and the ids it was installed to wait on.
+    watched: BTreeMap<WatchSetId, (T, BTreeSet<GlobalId>)>,
+    /// Tokens of watch sets that were already finished when they were installed.
+    immediate: Vec<(WatchSetId, Box<dyn Any>)>,
+    /// The id to assign to the next installed watch set.
+    next_id: u64,
+    /// The number of installed tokens that have not been returned yet.
+    token_count: u64,
+    /// Reports `token_count`.
+    tokens_gauge: UIntGauge,
+    /// Reports the number of distinct ids in `pending`.
+    ids_gauge: UIntGauge,
+}
+
+impl<T: Timestamp + TotalOrder> WatchSets<T> {
+    pub fn new(metrics_registry: &MetricsRegistry) -> Self {
+        Self {
+            pending: BTreeMap::new(),
+            watched: BTreeMap::new(),
+            immediate: Vec::new(),
+            next_id: 0,
+            token_count: 0,
+            tokens_gauge: metrics_registry.register(metric!(
+                name: "mz_controller_watch_set_tokens",
+                help: "The number of installed watch sets that have not finished yet.",
+            )),
+            ids_gauge: metrics_registry.register(metric!(
+                name: "mz_controller_watch_set_ids",
+                help: "The number of distinct collections watched by unfinished watch sets.",
+            )),
+        }
+    }
+
+    /// Installs a watch set that finishes once the write frontiers of all `objects` have
+    /// advanced beyond `t`. If `objects` is empty, the watch set is finished immediately.
+    pub fn install(
+        &mut self,
+        objects: BTreeSet<GlobalId>,
+        t: T,
+        token: Box<dyn Any>,
+    ) -> WatchSetId {
+        let set = WatchSetId(self.next_id);
+        self.next_id += 1;
+        self.token_count += 1;
+        if objects.is_empty() {
+            self.immediate.push((set, token));
+        } else {
+            let state = Rc::new((set, token));
+            for id in &objects {
+                self.pending
+                    .entry(*id)
+                    .or_default()
+                    .entry(t.clone())
+                    .or_default()
+                    .push(Rc::clone(&state));
+            }
+            self.watched.insert(set, (t, objects));
+        }
+        set
+    }
+
+    /// Cancels the watch set `set`, dropping its token instead of returning it. Returns whether
+    /// the watch set had not finished yet.
+    pub(crate) fn cancel(&mut self, set: WatchSetId) -> bool {
+        if let Some(pos) = self.immediate.iter().position(|(s, _)| *s == set) {
+            self.immediate.remove(pos);
+        } else if let Some((t, ids)) = self.watched.remove(&set) {
+            for id in ids {
+                if let Some(by_time) = self.pending.get_mut(&id) {
+                    if let Some(sets) = by_time.get_mut(&t) {
+                        sets.retain(|state| state.0 != set);
+                        if sets.is_empty() {
+                            by_time.remove(&t);
+                        }
+                    }
+                    if by_time.is_empty() {
+                        self.pending.remove(&id);
+                    }
+                }
+            }
+        } else {
+            return false;
+        }
+        self.token_count -= 1;
+        true
+    }
+
+    /// Installs a watch set like [`WatchSets::install`], returning a future that resolves once
+    /// it finishes instead of returning its token.
+    ///
+    /// The future also resolves if the watch set is dropped or cancelled before it finishes.
+    pub(crate) fn install_waiter(
+        &mut self,
+        objects: BTreeSet<GlobalId>,
+        t: T,
+    ) -> (WatchSetId, BoxFuture<'static, ()>) {
+        let (tx, rx) = oneshot::channel();
+        let set = self.install(objects, t, Box::new(WatchSetWaiter(tx)));
+        (set, rx.map(|_| ()).boxed())
+    }
+
+    /// Whether there are finished watch sets that have not been returned yet.
+    pub(crate) fn has_immediate(&self) -> bool {
+        !self.immediate.is_empty()
+    }
+
+    /// Returns the tokens of watch sets that were finished when they were installed.
+    pub(crate) fn take_immediate(&mut self) -> Vec<Box<dyn Any>> {
+        let finished: Vec<_> = std::mem::take(&mut self.immediate)
+            .into_iter()
+            .map(|(_, token)| token)
+            .collect();
+        self.token_count -= u64::cast_from(finished.len());
+        Self::notify_waiters(finished)
+    }
+
+    /// Applies frontier `updates`, returning the tokens of the watch sets they finish.
+    ///
+    /// Only the entries at timestamps that the updated frontiers have advanced beyond are
+    /// visited. Within an id, watch sets finish in the order of their timestamps.
+    pub fn update(&mut self, updates: &[(GlobalId, Antichain<T>)]) -> Vec<Box<dyn Any>> {
+        let mut finished = vec![];
+        for (id, frontier) in updates {
+            let Some(by_time) = self.pending.get_mut(id) else {
+                continue;
+            };
+            // Timestamps are totally ordered, so once the frontier has not advanced beyond one,
+            // it hasn't advanced beyond any later one either. An empty frontier has advanced
+            // beyond all of them.
+            while let Some(entry) = by_time.first_entry() {
+                if frontier.less_equal(entry.key()) {
+                    break;
+                }
+                for state in entry.remove() {
+                    if let Some((set, token)) = Rc::into_inner(state) {
+                        self.watched.remove(&set);
+                        finished.push(token);
+                    }
+                }
+            }
+            if by_time.is_empty() {
+                self.pending.remove(id);
+            }
+        }
+        self.token_count -= u64::cast_from(finished.len());
+        Self::notify_waiters(finished)
+    }
+
+    /// Notifies the waiters among the `finished` tokens, returning the remaining ones.
+    fn notify_waiters(finished: Vec<Box<dyn Any>>) -> Vec<Box<dyn Any>> {
+        finished
+            .into_iter()
+            .filter_map(|token| match token.downcast::<WatchSetWaiter>() {
+                Ok(waiter) => {
+                    // The receiver may have lost interest already.
+                    let _ = waiter.0.send(());
+                    None
+                }
+                Err(token) => Some(token),
+            })
+            .collect()
+    }
+
+    /// Reports the current number of tokens and watched ids. Both are maintained incrementally,
+    /// so this is cheap enough to call on every [`Controller::process`](crate::Controller::process).
+    pub(crate) fn update_metrics(&self) {
+        self.tokens_gauge.set(self.token_count);
+        self.ids_gauge.set(u64::cast_from(self.pending.len()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_watch_set_metrics() {
+        let mut watch_sets = WatchSets::<mz_repr::Timestamp>::new(&MetricsRegistry::new());
+        let gauges = |w: &WatchSets<_>| {
+            w.update_metrics();
+            (w.tokens_gauge.get(), w.ids_gauge.get())
+        };
+        let (a, b) = (GlobalId::User(1), GlobalId::User(2));
+        let advanced_to = |id, t: u64| (id, Antichain::from_elem(mz_repr::Timestamp::from(t)));
+
+        watch_sets.install(BTreeSet::from([a, b]), 5.into(), Box::new(()));
+        watch_sets.install(BTreeSet::from([a]), 10.into(), Box::new(()));
+        watch_sets.install(BTreeSet::new(), 0.into(), Box::new(()));
+        assert_eq!(gauges(&watch_sets), (3, 2));
+
+        assert_eq!(watch_sets.take_immediate().len(), 1);
+        assert_eq!(gauges(&watch_sets), (2, 2));
+
+        // The first watch set only finishes once both of its ids advance.
+        assert_eq!(watch_sets.update(&[advanced_to(a, 6)]).len(), 0);
+        assert_eq!(gauges(&watch_sets), (2, 2));
+        assert_eq!(watch_sets.update(&[advanced_to(b, 6)]).len(), 1);
+        assert_eq!(gauges(&watch_sets), (1, 1));
+
+        assert_eq!(watch_sets.update(&[advanced_to(a, 11)]).len(), 1);
+        assert_eq!(gauges(&watch_sets), (0, 0));
+    }
+
+    #[mz_ore::test]
+    fn test_watch_set_waiter() {
+        let mut watch_sets = WatchSets::<mz_repr::Timestamp>::new(&MetricsRegistry::new());
+        let id = GlobalId::User(1);
+        let advanced_to = |t: u64| (id, Antichain::from_elem(mz_repr::Timestamp::from(t)));
+
+        let (_, mut waiter) = watch_sets.install_waiter(BTreeSet::from([id]), 5.into());
+        watch_sets.install(BTreeSet::from([id]), 5.into(), Box::new(()));
+        assert_eq!((&mut waiter).now_or_never(), None);
+
+        // The frontier reaching the timestamp does not make it readable yet.
+        assert_eq!(watch_sets.update(&[advanced_to(5)]).len(), 0);
+        assert_eq!((&mut waiter).now_or_never(), None);
+
+        // Advancing beyond it resolves the waiter, which is not returned as a token.
+        assert_eq!(watch_sets.update(&[advanced_to(6)]).len(), 1);
+        assert_eq!(waiter.now_or_never(), Some(()));
+        assert_eq!(watch_sets.token_count, 0);
+
+        // Waiters without objects resolve when the immediate watch sets are taken.
+        let (_, waiter) = watch_sets.install_waiter(BTreeSet::new(), 0.into());
+        assert!(watch_sets.has_immediate());
+        assert_eq!(watch_sets.take_immediate().len(), 0);
+        assert_eq!(waiter.now_or_never(), Some(()));
+    }
+
+    #[mz_ore::test]
+    fn test_watch_set_cancel() {
+        let mut watch_sets = WatchSets::<mz_repr::Timestamp>::new(&MetricsRegistry::new());
+        let (a, b) = (GlobalId::User(1), GlobalId::User(2));
+        let advanced_to = |id, t: u64| (id, Antichain::from_elem(mz_repr::Timestamp::from(t)));
+
+        let (cancelled, mut waiter) = watch_sets.install_waiter(BTreeSet::from([a, b]), 5.into());
+        let kept = watch_sets.install(BTreeSet::from([a]), 5.into(), Box::new(()));
+        let immediate = watch_sets.install(BTreeSet::new(), 0.into(), Box::new(()));
+        assert_eq!((&mut waiter).now_or_never(), None);
+
+        // Cancelling resolves the waiter and forgets the ids only it was waiting on.
+        assert!(watch_sets.cancel(cancelled));
+        assert_eq!(waiter.now_or_never(), Some(()));
+        assert_eq!(watch_sets.pending.keys().collect::<Vec<_>>(), vec![&a]);
+        assert!(watch_sets.cancel(immediate));
+        assert!(!watch_sets.has_immediate());
+        assert_eq!(watch_sets.token_count, 1);
+
+        // Cancelled watch sets do not fire once the frontiers advance, and cannot be cancelled
+        // again.
+        assert_eq!(
+            watch_sets
+                .update(&[advanced_to(a, 6), advanced_to(b, 6)])
+                .len(),
+            1
+        );
+        assert_eq!(watch_sets.token_count, 0);
+        assert!(!watch_sets.cancel(cancelled));
+        assert!(!watch_sets.cancel(kept));
+        assert!(watch_sets.watched.is_empty());
+    }
+
+    #[mz_ore::test]
+    fn test_watch_set_ordering() {
+        let mut watch_sets = WatchSets::<mz_repr::Timestamp>::new(&MetricsRegistry::new());
+        let (a, b) = (GlobalId::User(1), GlobalId::User(2));
+        let advanced_to = |id, t: u64| (id, Antichain::from_elem(mz_repr::Timestamp::from(t)));
+        let tokens = |finished: Vec<Box<dyn Any>>| -> Vec<u64> {
+            finished
+                .into_iter()
+                .map(|token| *token.downcast::<u64>().expect("u64 token"))
+                .collect()
+        };
+
+        // Watch sets are installed out of the order of their timestamps, several at the same one.
+        for (token, t) in [(0_u64, 7_u64), (1, 3), (2, 5), (3, 3), (4, 9)] {
+            watch_sets.install(BTreeSet::from([a]), t.into(), Box::new(token));
+        }
+        let cancelled = watch_sets.install(BTreeSet::from([a]), 3.into(), Box::new(5_u64));
+        let shared = watch_sets.install(BTreeSet::from([a, b]), 3.into(), Box::new(6_u64));
+
+        // Empty updates, and frontiers that haven't advanced beyond any timestamp, finish
+        // nothing.
+        assert!(watch_sets.update(&[]).is_empty());
+        assert!(watch_sets.update(&[advanced_to(a, 3)]).is_empty());
+
+        // Cancelling a watch set leaves those at the same timestamp in place.
+        assert!(watch_sets.cancel(cancelled));
+        assert_eq!(watch_sets.watched[&shared].0, mz_repr::Timestamp::from(3));
+
+        // Watch sets at equal timestamps finish together, in the order they were installed,
+        // unless they wait on other ids, too.
+        assert_eq!(tokens(watch_sets.update(&[advanced_to(a, 4)])), [1, 3]);
+        assert_eq!(tokens(watch_sets.update(&[advanced_to(b, 4)])), [6]);
+
+        // A frontier that advances beyond several timestamps at once finishes their watch sets
+        // in the order of the timestamps.
+        assert_eq!(tokens(watch_sets.update(&[advanced_to(a, 8)])), [2, 0]);
+
+        // An empty frontier has advanced beyond all timestamps.
+        watch_sets.install(BTreeSet::from([a]), u64::MAX.into(), Box::new(7_u64));
+        assert_eq!(tokens(watch_sets.update(&[(a, Antichain::new())])), [4, 7]);
+        assert!(watch_sets.pending.is_empty());
+        assert!(watch_sets.watched.is_empty());
+        assert_eq!(watch_sets.token_count, 0);
+    }
+}
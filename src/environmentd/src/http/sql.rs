@@ -41,7 +41,6 @@ use mz_sql::plan::Plan;
 use serde::{Deserialize, Serialize};
 use tokio::{select, time};
 use tokio_postgres::error::SqlState;
-use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::debug;
 use tungstenite::protocol::frame::coding::CloseCode;
 
@@ -1146,6 +1145,7 @@ async fn execute_stmt<S: ResultSender>(
         | ExecuteResponse::AlteredSystemConfiguration
         | ExecuteResponse::Deallocate { .. }
         | ExecuteResponse::ValidatedConnection
+        | ExecuteResponse::Undropped
         | ExecuteResponse::Prepare => SqlResult::ok(
             client,
             tag.expect("ok only called on tag-generating results"),
@@ -1255,7 +1255,7 @@ async fn execute_stmt<S: ResultSender>(
             tag: "SUBSCRIBE".into(),
             desc: desc.relation_desc.unwrap(),
             rx: RecordFirstRowStream::new(
-                Box::new(UnboundedReceiverStream::new(rx)),
+                Box::new(rx),
                 execute_started,
                 client,
             ),
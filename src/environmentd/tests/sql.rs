@@ -13,6 +13,7 @@
 //! scripts. The tests here are simply too complicated to be easily expressed
 //! in testdrive, e.g., because they depend on the current time.
 
+use std::collections::BTreeMap;
 use std::io::Read;
 use std::net::{Ipv4Addr, SocketAddr};
 use std::sync::{Arc, Mutex};
@@ -25,6 +26,7 @@ use chrono::{DateTime, Utc};
 use http::StatusCode;
 use itertools::Itertools;
 use mz_adapter::{TimestampContext, TimestampExplanation};
+use mz_catalog::builtin::{MZ_SINK_STATUSES, MZ_SOURCE_STATUSES};
 use mz_environmentd::test_util::{
     self, get_explain_timestamp, get_explain_timestamp_determination, try_get_explain_timestamp,
     MzTimestamp, PostgresErrorExt, TestServerWithRuntime, KAFKA_ADDRS,
@@ -443,8 +445,10 @@ async fn test_empty_subscribe_notice() {
         .await
         .unwrap();
     let now = test_util::get_explain_timestamp("t", &client).await;
+    // Without an `AS OF`, the subscription starts at the same timestamp as the query above, which
+    // is also its `UP TO`.
     client
-        .batch_execute(&format!("SUBSCRIBE TO t AS OF {now} UP TO {now}"))
+        .batch_execute(&format!("SUBSCRIBE TO t UP TO {now}"))
         .await
         .unwrap();
 
@@ -463,6 +467,51 @@ async fn test_empty_subscribe_notice() {
         .unwrap();
 }
 
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+async fn test_subscribe_rounded_up_notice() {
+    let server = test_util::TestHarness::default().start().await;
+
+    let (tx, mut rx) = futures::channel::mpsc::unbounded();
+    let client = server
+        .connect()
+        .notice_callback(move |notice| tx.unbounded_send(notice).unwrap())
+        .await
+        .unwrap();
+
+    client
+        .batch_execute("CREATE TABLE t (a int)")
+        .await
+        .unwrap();
+    let now = test_util::get_explain_timestamp("t", &client).await;
+    // An `AS OF` half a millisecond before `now` is rounded up to `now`.
+    let as_of = mz_ore::now::to_datetime(now - 1) + chrono::Duration::microseconds(500);
+    client
+        .batch_execute(&format!(
+            "SUBSCRIBE TO t AS OF AT LEAST TIMESTAMPTZ '{}' UP TO {}",
+            as_of.format("%Y-%m-%d %H:%M:%S%.6f+00"),
+            now + 1000,
+        ))
+        .await
+        .unwrap();
+
+    // The bound is evaluated once, so the notice is only sent once.
+    let mut notices = vec![];
+    while let Ok(Some(notice)) = rx.try_next() {
+        notices.push(notice);
+    }
+    let rounded_up: Vec<_> = notices
+        .iter()
+        .filter(|notice| {
+            notice
+                .message()
+                .ends_with(&format!("was rounded up to the mz_timestamp {now}"))
+        })
+        .collect();
+    assert_eq!(rounded_up.len(), 1, "{notices:?}");
+    // The rounding is informational, like other notices about adjusted values.
+    assert_eq!(rounded_up[0].code(), &SqlState::SUCCESSFUL_COMPLETION);
+}
+
 #[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
 async fn test_empty_subscribe_error() {
     let server = test_util::TestHarness::default()
@@ -476,12 +525,23 @@ async fn test_empty_subscribe_error() {
         .await
         .unwrap();
     let now = test_util::get_explain_timestamp("t", &client).await;
-    let e = client
-        .batch_execute(&format!("SUBSCRIBE TO t AS OF {now} UP TO {}", now - 1))
-        .await
-        .expect_err("expected DB error");
-    let e = e.as_db_error().expect("expected DB error");
-    assert!(e.code().code() == "22000")
+
+    // Neither equal nor inverted bounds can produce any data.
+    for up_to in [now, now - 1] {
+        let e = client
+            .batch_execute(&format!("SUBSCRIBE TO t AS OF {now} UP TO {up_to}"))
+            .await
+            .expect_err("expected DB error");
+        let e = e.as_db_error().expect("expected DB error");
+        assert_eq!(e.code().code(), "22000");
+        assert_eq!(
+            e.message(),
+            format!(
+                "UP TO {up_to} must be greater than AS OF {now}; the subscription would never \
+                produce any data"
+            )
+        );
+    }
 }
 
 #[mz_ore::test]
@@ -1267,6 +1327,47 @@ fn test_explain_timestamp_json() {
     let _explain: TimestampExplanation<Timestamp> = serde_json::from_str(&explain).unwrap();
 }
 
+// Test that the default transaction isolation of a cluster doesn't override that of a role.
+#[mz_ore::test]
+fn test_cluster_transaction_isolation_role_default() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    client
+        .batch_execute(
+            "CREATE TABLE t1 (i1 int);
+             CREATE CLUSTER c SIZE '1', TRANSACTION ISOLATION 'serializable';",
+        )
+        .unwrap();
+
+    // Serializable queries don't consult the timestamp oracle, strict serializable ones do.
+    let oracle_read_ts = |client: &mut postgres::Client| {
+        client.batch_execute("SET cluster = c").unwrap();
+        let row = client
+            .query_one("EXPLAIN TIMESTAMP AS JSON FOR SELECT * FROM t1;", &[])
+            .unwrap();
+        let explain: String = row.get(0);
+        let explain: TimestampExplanation<Timestamp> = serde_json::from_str(&explain).unwrap();
+        explain.determination.oracle_read_ts
+    };
+
+    // Without a session or role default, the cluster's default applies.
+    assert_eq!(oracle_read_ts(&mut client), None);
+
+    // Role defaults take effect in new sessions, in which they take precedence over the default
+    // of the cluster.
+    client
+        .batch_execute("ALTER ROLE materialize SET transaction_isolation = 'strict serializable'")
+        .unwrap();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    assert!(oracle_read_ts(&mut client).is_some());
+
+    client
+        .batch_execute("ALTER ROLE materialize RESET transaction_isolation")
+        .unwrap();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    assert_eq!(oracle_read_ts(&mut client), None);
+}
+
 // Verify that `EXPLAIN TIMESTAMP ...` within acts like a peek within a transaction.
 // That is, ensure the following:
 // 1. Consistently returns its transaction timestamp as the "query timestamp"
@@ -1719,6 +1820,65 @@ fn test_read_then_write_serializability() {
     }
 }
 
+/// Test that concurrent `UPDATE`s of the same row don't lose updates, and that each write commits
+/// at a timestamp beyond the one its read was performed at.
+#[mz_ore::test]
+fn test_read_then_write_timestamp_gap() {
+    let server = test_util::TestHarness::default().start_blocking();
+
+    {
+        let mut client = server.connect(postgres::NoTls).unwrap();
+        client.batch_execute("CREATE TABLE t(f bigint)").unwrap();
+        client.batch_execute("INSERT INTO t VALUES (0)").unwrap();
+    }
+
+    let num_threads = 3;
+    let num_loops = 5;
+
+    let mut clients = Vec::new();
+    for _ in 0..num_threads {
+        clients.push(server.connect(postgres::NoTls).unwrap());
+    }
+
+    let handles: Vec<_> = clients
+        .into_iter()
+        .map(|mut client| {
+            std::thread::spawn(move || {
+                for _ in 0..num_loops {
+                    client.batch_execute("UPDATE t SET f = f + 1").unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    {
+        let mut client = server.connect(postgres::NoTls).unwrap();
+        let value = client
+            .query_one("SELECT f FROM t", &[])
+            .unwrap()
+            .get::<_, i64>(0);
+        assert_eq!(value, num_threads * num_loops);
+    }
+
+    // Every update recorded the gap between its read and write timestamps. A gap of zero would
+    // mean the write was not ordered after the read.
+    let gap = server
+        .metrics_registry
+        .gather()
+        .into_iter()
+        .find(|m| m.get_name() == "mz_read_then_write_timestamp_gap_ms")
+        .unwrap();
+    let gap = gap.get_metric()[0].get_histogram();
+    assert_eq!(
+        gap.get_sample_count(),
+        u64::try_from(num_threads * num_loops).unwrap()
+    );
+    assert!(gap.get_sample_sum() >= f64::from(u32::try_from(num_threads * num_loops).unwrap()));
+}
+
 #[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
 async fn test_timestamp_recovery() {
     let now = Arc::new(Mutex::new(1));
@@ -2465,6 +2625,65 @@ fn test_emit_timestamp_notice() {
         .unwrap();
 }
 
+#[mz_ore::test]
+fn test_default_timeline_notice() {
+    let server = test_util::TestHarness::default().start_blocking();
+
+    let (tx, mut rx) = futures::channel::mpsc::unbounded();
+    let mut client = server
+        .pg_config()
+        .notice_callback(move |notice| {
+            tx.unbounded_send(notice).unwrap();
+        })
+        .connect(postgres::NoTls)
+        .unwrap();
+
+    // Returns the details of the default timeline notices received so far.
+    let mut default_timeline_notices = || {
+        let mut details = Vec::new();
+        while let Ok(Some(notice)) = rx.try_next() {
+            if notice.message() == "query timestamp defaulted to the EpochMilliseconds timeline" {
+                details.push(notice.detail().unwrap_or_default().to_string());
+            }
+        }
+        details
+    };
+
+    client.batch_execute("CREATE TABLE t (i INT)").unwrap();
+
+    // No notices unless the session asks for them.
+    client.batch_execute("SELECT mz_now()").unwrap();
+    assert_eq!(default_timeline_notices(), Vec::<String>::new());
+
+    client
+        .batch_execute("SET notice_on_default_timeline = true")
+        .unwrap();
+    client.batch_execute("SELECT mz_now()").unwrap();
+    let details = default_timeline_notices();
+    assert_eq!(details.len(), 1, "{details:?}");
+    assert!(details[0].contains("mz_now()"), "{details:?}");
+
+    // A materialized view over constants is timestamp dependent as well.
+    client
+        .batch_execute("CREATE MATERIALIZED VIEW mv AS SELECT 1 AS a")
+        .unwrap();
+    client.batch_execute("SELECT * FROM mv").unwrap();
+    let details = default_timeline_notices();
+    assert_eq!(details.len(), 1, "{details:?}");
+    assert!(
+        details[0].contains(r#"materialized view "materialize.public.mv""#),
+        "{details:?}"
+    );
+
+    // Queries that read an object in a timeline use that timeline.
+    client.batch_execute("SELECT mz_now(), i FROM t").unwrap();
+    assert_eq!(default_timeline_notices(), Vec::<String>::new());
+
+    // Queries that don't depend on the timestamp don't choose a timeline.
+    client.batch_execute("SELECT 1").unwrap();
+    assert_eq!(default_timeline_notices(), Vec::<String>::new());
+}
+
 #[mz_ore::test]
 fn test_isolation_level_notice() {
     let server = test_util::TestHarness::default().start_blocking();
@@ -2712,6 +2931,41 @@ fn test_timelines_persist_after_failed_transaction() {
     client.batch_execute("DROP SOURCE counter").unwrap();
 }
 
+#[mz_ore::test]
+fn test_catalog_commit_timeout() {
+    let server = test_util::TestHarness::default().start_blocking();
+
+    let mut mz_client = server
+        .pg_config_internal()
+        .user(&SYSTEM_USER.name)
+        .connect(postgres::NoTls)
+        .unwrap();
+    mz_client
+        .batch_execute("ALTER SYSTEM SET catalog_commit_timeout TO '1s'")
+        .unwrap();
+
+    let mut client = server.connect(postgres::NoTls).unwrap();
+
+    // Simulate a hung catalog backend. DDL fails once its commit times out.
+    fail::cfg("catalog_commit_pause", "return").expect("failed to set the fail_point");
+    let err = client.batch_execute("CREATE TABLE t (a int)").unwrap_err();
+    let err = err.as_db_error().unwrap();
+    assert_eq!(err.code(), &SqlState::QUERY_CANCELED);
+    assert!(
+        err.message().contains("catalog commit timed out"),
+        "unexpected error: {err}"
+    );
+    assert!(err.hint().unwrap().contains("catalog_commit_timeout"));
+
+    // The coordinator keeps serving queries while the backend is hung.
+    client.query_one("SELECT 1", &[]).unwrap();
+
+    // Once the backend recovers, DDL succeeds again.
+    fail::remove("catalog_commit_pause");
+    client.batch_execute("CREATE TABLE t (a int)").unwrap();
+    client.query("SELECT * FROM t", &[]).unwrap();
+}
+
 // This can almost be tested with SLT using the simple directive, but
 // we have no way to disconnect sessions using SLT.
 #[mz_ore::test]
@@ -3322,627 +3576,2253 @@ fn test_pg_cancel_backend() {
     );
 }
 
-// Test params in interesting places.
 #[mz_ore::test]
-fn test_params() {
+fn test_quiesce_cluster() {
     mz_ore::test::init_logging();
     let server = test_util::TestHarness::default().start_blocking();
 
-    let mut client = server.connect(postgres::NoTls).unwrap();
+    let mut mz_client = server
+        .pg_config_internal()
+        .user(&SYSTEM_USER.name)
+        .connect(postgres::NoTls)
+        .unwrap();
+    mz_client
+        .batch_execute("ALTER SYSTEM SET enable_rbac_checks TO true")
+        .unwrap();
 
-    assert_eq!(
-        client
-            .query_one("SELECT $1", &[&"test"])
-            .unwrap()
-            .get::<_, String>(0),
-        "test"
-    );
-    assert_eq!(
-        client
-            .query_one("SUBSCRIBE (SELECT $1 as c)", &[&"test"])
-            .unwrap()
-            .get::<_, String>("c"),
-        "test"
+    let mut client1 = server.connect(postgres::NoTls).unwrap();
+    let mut client2 = server.connect(postgres::NoTls).unwrap();
+    let mut client_user = server
+        .pg_config()
+        .user("other")
+        .connect(postgres::NoTls)
+        .unwrap();
+
+    client1.batch_execute("CREATE TABLE t (i INT)").unwrap();
+
+    // Only superusers may quiesce a cluster.
+    assert_contains!(
+        client_user
+            .query_one("SELECT mz_internal.mz_quiesce_cluster('default')", &[])
+            .unwrap_err()
+            .to_string(),
+        "permission denied to quiesce a cluster"
     );
 
-    client.batch_execute("BEGIN").unwrap();
-    client
-        .execute("DECLARE c CURSOR FOR SELECT $1", &[&"test"])
-        .map_err_to_string_with_causes()
+    // Run a SUBSCRIBE that keeps the cluster busy until it is canceled.
+    let subscribe = thread::spawn(move || client1.query("SUBSCRIBE t", &[]).unwrap_err());
+    let conn_id = Retry::default()
+        .retry(|_| {
+            let conn_id: String = client2
+                .query_one(
+                    "SELECT session_id::text FROM mz_internal.mz_subscriptions",
+                    &[],
+                )?
+                .get(0);
+            Ok::<_, postgres::Error>(conn_id)
+        })
         .unwrap();
-    assert_eq!(
-        client
-            .query_one("FETCH c", &[])
-            .map_err_to_string_with_causes()
+
+    let quiesce = thread::spawn(move || {
+        let quiesced: bool = mz_client
+            .query_one("SELECT mz_internal.mz_quiesce_cluster('default')", &[])
             .unwrap()
-            .get::<_, String>(0),
-        "test"
-    );
-    client.batch_execute("COMMIT").unwrap();
+            .get(0);
+        (mz_client, quiesced)
+    });
 
-    client.batch_execute("BEGIN").unwrap();
-    client
-        .execute("DECLARE c CURSOR FOR SUBSCRIBE(SELECT $1 as c)", &[&"test"])
-        .map_err_to_string_with_causes()
+    // New peeks and subscribes on the quiescing cluster are rejected.
+    Retry::default()
+        .retry(|_| match client2.query("SELECT * FROM t", &[]) {
+            Err(e) if e.to_string().contains("is quiescing") => Ok(()),
+            res => Err(format!("unexpected result: {res:?}")),
+        })
         .unwrap();
-    assert_eq!(
-        client
-            .query_one("FETCH c", &[])
-            .map_err_to_string_with_causes()
-            .unwrap()
-            .get::<_, String>("c"),
-        "test"
+    assert_contains!(
+        client2.query("SUBSCRIBE t", &[]).unwrap_err().to_string(),
+        "is quiescing"
     );
-    client.batch_execute("COMMIT").unwrap();
+
+    // The outstanding SUBSCRIBE keeps the handle from resolving.
+    assert!(!quiesce.is_finished());
+
+    let found_conn: bool = client2
+        .query_one(&format!("SELECT pg_cancel_backend({conn_id})"), &[])
+        .unwrap()
+        .get(0);
+    assert!(found_conn);
+    assert_contains!(
+        subscribe.join().unwrap().to_string(),
+        "canceling statement due to user request"
+    );
+
+    let (mut mz_client, quiesced) = quiesce.join().unwrap();
+    assert!(quiesced);
+
+    // The cluster stays quiesced until it is unquiesced.
+    assert_contains!(
+        client2
+            .query("SELECT * FROM t", &[])
+            .unwrap_err()
+            .to_string(),
+        "is quiescing"
+    );
+    let was_quiescing: bool = mz_client
+        .query_one("SELECT mz_internal.mz_unquiesce_cluster('default')", &[])
+        .unwrap()
+        .get(0);
+    assert!(was_quiescing);
+    client2.query("SELECT * FROM t", &[]).unwrap();
+
+    let was_quiescing: bool = mz_client
+        .query_one("SELECT mz_internal.mz_unquiesce_cluster('default')", &[])
+        .unwrap()
+        .get(0);
+    assert!(!was_quiescing);
 }
 
-// Test pg_cancel_backend after the authenticated role is dropped.
+/// Test that the results buffered by a session's portals are reported in
+/// `mz_internal.mz_portals` and are capped by `max_portal_memory`.
 #[mz_ore::test]
-fn test_pg_cancel_dropped_role() {
+fn test_portal_memory_limit() {
+    mz_ore::test::init_logging();
     let server = test_util::TestHarness::default().start_blocking();
-    let dropped_role = "r1";
 
-    let mut query_client = server.connect(postgres::NoTls).unwrap();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    let mut observer = server.connect(postgres::NoTls).unwrap();
 
-    // Create role.
-    query_client
-        .execute(&format!("CREATE ROLE {dropped_role}"), &[])
+    // About 1 MB of results, well above the limit below.
+    client
+        .batch_execute(
+            "CREATE TABLE t (a text);
+             INSERT INTO t SELECT repeat('x', 1000) FROM generate_series(1, 1000);
+             SET max_portal_memory = '100 kB';",
+        )
         .unwrap();
+    let session_id: i32 = client
+        .query_one("SELECT pg_backend_pid()", &[])
+        .unwrap()
+        .get(0);
+    let buffered_bytes = |observer: &mut postgres::Client, name: &str| -> Option<i64> {
+        observer
+            .query_opt(
+                "SELECT buffered_bytes::int8 FROM mz_internal.mz_portals
+                 WHERE session_id = $1::int4::uint4 AND name = $2",
+                &[&session_id, &name],
+            )
+            .unwrap()
+            .map(|row| row.get(0))
+    };
 
-    // Start session using role.
-    let mut dropped_client = server
-        .pg_config()
-        .user(dropped_role)
-        .connect(postgres::NoTls)
+    // Fetching a single row buffers the rest of the result in the cursor.
+    client
+        .batch_execute(
+            "BEGIN;
+             DECLARE c1 CURSOR FOR SELECT * FROM t;
+             DECLARE c2 CURSOR FOR SELECT * FROM t;
+             FETCH 1 c1;",
+        )
+        .unwrap();
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry(|_| match buffered_bytes(&mut observer, "c1") {
+            Some(bytes) if bytes > 100_000 => Ok(()),
+            bytes => Err(format!("unexpected buffered bytes: {bytes:?}")),
+        })
         .unwrap();
+    assert_eq!(buffered_bytes(&mut observer, "c2"), Some(0));
 
-    // Get the connection ID of the new session.
-    let connection_id = dropped_client
-        .query_one(
-            &format!(
-                "SELECT s.id
-         FROM mz_internal.mz_sessions s
-         JOIN mz_roles r ON s.role_id = r.id
-         WHERE r.name = '{dropped_role}'"
-            ),
-            &[],
+    // The cursor that holds the results can still be drained, but other
+    // cursors cannot start.
+    client.batch_execute("FETCH 1 c1").unwrap();
+    let err = client.batch_execute("FETCH 1 c2").unwrap_err();
+    assert_eq!(err.code(), Some(&SqlState::OUT_OF_MEMORY));
+    assert_contains!(err.to_string(), "exceeds max_portal_memory");
+    client.batch_execute("ROLLBACK").unwrap();
+
+    // New cursors cannot be declared either.
+    client
+        .batch_execute(
+            "BEGIN;
+             DECLARE c1 CURSOR FOR SELECT * FROM t;
+             FETCH 1 c1;",
+        )
+        .unwrap();
+    assert_contains!(
+        client
+            .batch_execute("DECLARE c2 CURSOR FOR SELECT * FROM t")
+            .unwrap_err()
+            .to_string(),
+        "exceeds max_portal_memory"
+    );
+    client.batch_execute("ROLLBACK").unwrap();
+
+    // Closing the cursor frees its budget.
+    client
+        .batch_execute(
+            "BEGIN;
+             DECLARE c1 CURSOR FOR SELECT * FROM t;
+             FETCH 1 c1;
+             CLOSE c1;
+             DECLARE c2 CURSOR FOR SELECT * FROM t;
+             FETCH 1 c2;
+             COMMIT;",
+        )
+        .unwrap();
+
+    // Portals that are gone are removed from the introspection relation.
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry(|_| {
+            let count: i64 = observer
+                .query_one(
+                    "SELECT count(*) FROM mz_internal.mz_portals WHERE session_id = $1::int4::uint4",
+                    &[&session_id],
+                )
+                .unwrap()
+                .get(0);
+            if count == 0 {
+                Ok(())
+            } else {
+                Err(format!("{count} portals remain"))
+            }
+        })
+        .unwrap();
+}
+
+/// Test that a SUBSCRIBE whose client falls behind is terminated once its undelivered updates
+/// exceed `max_subscribe_buffer_size`, and that it can be resumed from the reported timestamp
+/// without missing or duplicating updates.
+#[mz_ore::test]
+fn test_subscribe_buffer_limit() {
+    mz_ore::test::init_logging();
+    let server = test_util::TestHarness::default().start_blocking();
+    server.enable_feature_flags(&["enable_index_options", "enable_logical_compaction_window"]);
+
+    let mut client_writes = server.connect(postgres::NoTls).unwrap();
+    let mut client_reads = server.connect(postgres::NoTls).unwrap();
+
+    // Retain history so that the subscribe can be resumed from an earlier timestamp.
+    client_writes
+        .batch_execute(
+            "CREATE TABLE t (i int, pad text);
+             CREATE DEFAULT INDEX ON t WITH (RETAIN HISTORY FOR '1h');",
+        )
+        .unwrap();
+    client_reads
+        .batch_execute(
+            "SET max_subscribe_buffer_size = '100 kB';
+             BEGIN;
+             DECLARE c CURSOR FOR SUBSCRIBE t;
+             FETCH 0 c;",
         )
+        .unwrap();
+
+    // Write about 2 MB of updates while the client isn't reading any of them.
+    const BATCHES: i32 = 20;
+    const BATCH_SIZE: i32 = 100;
+    for batch in 0..BATCHES {
+        client_writes
+            .execute(
+                "INSERT INTO t SELECT i, repeat('x', 1000) FROM generate_series($1, $2) i",
+                &[&(batch * BATCH_SIZE + 1), &((batch + 1) * BATCH_SIZE)],
+            )
+            .unwrap();
+    }
+
+    fn record(seen: &mut BTreeMap<i32, i64>, rows: Vec<Row>) {
+        for row in rows {
+            *seen.entry(row.get("i")).or_default() += row.get::<_, i64>("mz_diff");
+        }
+    }
+    let mut seen = BTreeMap::new();
+
+    // The client receives the updates that were sent before the subscribe was terminated,
+    // followed by an error that reports how far it got.
+    let deadline = Instant::now() + Duration::from_secs(60);
+    let err = loop {
+        assert!(Instant::now() < deadline, "subscribe was not terminated");
+        match client_reads.query("FETCH ALL c WITH (TIMEOUT = '1s')", &[]) {
+            Ok(rows) => record(&mut seen, rows),
+            Err(err) => break err,
+        }
+    };
+    let msg = err.to_string();
+    assert_contains!(msg, "exceeds max_subscribe_buffer_size");
+    let delivered_through: u64 = Regex::new(r"all updates through timestamp (\d+) were delivered")
         .unwrap()
-        .get::<_, UInt4>(0)
-        .0;
+        .captures(&msg)
+        .unwrap_or_else(|| panic!("unexpected error: {msg}"))[1]
+        .parse()
+        .unwrap();
+    client_reads.batch_execute("ROLLBACK").unwrap();
 
-    // Drop role.
-    query_client
-        .execute(&format!("DROP ROLE {dropped_role}"), &[])
+    // Resuming after the reported timestamp delivers exactly the remaining updates.
+    client_reads
+        .batch_execute(&format!(
+            "RESET max_subscribe_buffer_size;
+             BEGIN;
+             DECLARE c CURSOR FOR SUBSCRIBE t WITH (SNAPSHOT = false) AS OF {delivered_through};"
+        ))
+        .unwrap();
+    let expected: BTreeMap<i32, i64> = (1..=BATCHES * BATCH_SIZE).map(|i| (i, 1)).collect();
+    let deadline = Instant::now() + Duration::from_secs(60);
+    while seen.len() < expected.len() {
+        assert!(
+            Instant::now() < deadline,
+            "resumed subscribe missed updates"
+        );
+        let rows = client_reads
+            .query("FETCH ALL c WITH (TIMEOUT = '1s')", &[])
+            .unwrap();
+        record(&mut seen, rows);
+    }
+    assert_eq!(seen, expected);
+    client_reads.batch_execute("ROLLBACK").unwrap();
+}
+
+/// Test that a SUBSCRIBE resumed from a resume token delivers every update after the token exactly
+/// once, and that tokens are rejected once the updates after them are no longer retained.
+#[mz_ore::test]
+fn test_subscribe_resume_token() {
+    mz_ore::test::init_logging();
+    let server = test_util::TestHarness::default().start_blocking();
+    server.enable_feature_flags(&["enable_index_options", "enable_logical_compaction_window"]);
+
+    let mut client_writes = server.connect(postgres::NoTls).unwrap();
+    let mut client_reads = server.connect(postgres::NoTls).unwrap();
+
+    client_writes
+        .batch_execute(
+            "CREATE TABLE t (i int);
+             CREATE DEFAULT INDEX t_primary_idx ON t WITH (RETAIN HISTORY FOR '1h');
+             INSERT INTO t VALUES (1);",
+        )
         .unwrap();
 
-    // Test that pg_cancel_backend doesn't panic. It's expected to fail with a privilege error. To
-    // execute pg_cancel_backend you must be a member of the authenticated role of the connection.
-    // Since the authenticated role doesn't exist, no one can be a member, and no one will have the
-    // required privileges.
-    let err = query_client
-        .query_one(&format!("SELECT pg_cancel_backend({connection_id})"), &[])
+    /// Fetches from the cursor until the updates of all of `expected` were delivered before a
+    /// progress message, returning the token of that message. Updates after it are discarded,
+    /// as a subscribe that resumes from the token delivers them again.
+    fn fetch_through(
+        client: &mut postgres::Client,
+        seen: &mut BTreeMap<i32, i64>,
+        expected: &BTreeMap<i32, i64>,
+    ) -> String {
+        let mut pending = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(60);
+        loop {
+            assert!(Instant::now() < deadline, "subscribe missed updates");
+            for row in client
+                .query("FETCH ALL c WITH (TIMEOUT = '1s')", &[])
+                .unwrap()
+            {
+                if !row.get::<_, bool>("mz_progressed") {
+                    assert_eq!(row.get::<_, Option<String>>("mz_resume_token"), None);
+                    pending.push((row.get::<_, i32>("i"), row.get::<_, i64>("mz_diff")));
+                    continue;
+                }
+                for (i, diff) in pending.drain(..) {
+                    *seen.entry(i).or_default() += diff;
+                }
+                if seen == expected {
+                    return row.get::<_, String>("mz_resume_token");
+                }
+            }
+        }
+    }
+
+    client_reads
+        .batch_execute(
+            "BEGIN;
+             DECLARE c CURSOR FOR SUBSCRIBE t WITH (PROGRESS, RESUME TOKENS);",
+        )
+        .unwrap();
+    for i in 2..=3 {
+        client_writes
+            .execute("INSERT INTO t VALUES ($1)", &[&i])
+            .unwrap();
+    }
+    let mut seen = BTreeMap::new();
+    let expected: BTreeMap<i32, i64> = (1..=3).map(|i| (i, 1)).collect();
+    let token = fetch_through(&mut client_reads, &mut seen, &expected);
+    client_reads.batch_execute("ROLLBACK").unwrap();
+
+    // Updates while no subscribe is running are delivered once the subscribe resumes, without a
+    // snapshot and without the updates before the token.
+    for i in 4..=6 {
+        client_writes
+            .execute("INSERT INTO t VALUES ($1)", &[&i])
+            .unwrap();
+    }
+    client_reads
+        .batch_execute(&format!(
+            "BEGIN;
+             DECLARE c CURSOR FOR SUBSCRIBE t WITH (PROGRESS, RESUME TOKENS, RESUME FROM = '{token}');"
+        ))
+        .unwrap();
+    let expected: BTreeMap<i32, i64> = (1..=6).map(|i| (i, 1)).collect();
+    let token = fetch_through(&mut client_reads, &mut seen, &expected);
+    client_reads.batch_execute("ROLLBACK").unwrap();
+
+    // A subscribe that is disconnected before it delivered its snapshot leaves no token to resume
+    // from, as its first progress message precedes the snapshot.
+    client_reads
+        .batch_execute(
+            "BEGIN;
+             DECLARE c CURSOR FOR SUBSCRIBE t WITH (PROGRESS, RESUME TOKENS);",
+        )
+        .unwrap();
+    let row = client_reads.query_one("FETCH 1 c", &[]).unwrap();
+    assert!(row.get::<_, bool>("mz_progressed"));
+    assert_eq!(row.get::<_, Option<String>>("mz_resume_token"), None);
+    client_reads.batch_execute("ROLLBACK").unwrap();
+
+    // A resumed subscribe that is disconnected before it delivered any update hands out a token
+    // no earlier than the one it resumed from, so resuming from it again delivers each update
+    // once.
+    client_writes
+        .execute("INSERT INTO t VALUES ($1)", &[&7])
+        .unwrap();
+    client_reads
+        .batch_execute(&format!(
+            "BEGIN;
+             DECLARE c CURSOR FOR SUBSCRIBE t WITH (PROGRESS, RESUME TOKENS, RESUME FROM = '{token}');"
+        ))
+        .unwrap();
+    let row = client_reads.query_one("FETCH 1 c", &[]).unwrap();
+    assert!(row.get::<_, bool>("mz_progressed"));
+    let token = row.get::<_, String>("mz_resume_token");
+    client_reads.batch_execute("ROLLBACK").unwrap();
+    client_writes
+        .execute("INSERT INTO t VALUES ($1)", &[&8])
+        .unwrap();
+    client_reads
+        .batch_execute(&format!(
+            "BEGIN;
+             DECLARE c CURSOR FOR SUBSCRIBE t WITH (PROGRESS, RESUME TOKENS, RESUME FROM = '{token}');"
+        ))
+        .unwrap();
+    let expected: BTreeMap<i32, i64> = (1..=8).map(|i| (i, 1)).collect();
+    fetch_through(&mut client_reads, &mut seen, &expected);
+    client_reads.batch_execute("ROLLBACK").unwrap();
+
+    let err = client_reads
+        .batch_execute(
+            "BEGIN;
+             DECLARE c CURSOR FOR SUBSCRIBE t WITH (RESUME FROM = 'not a token');
+             FETCH ALL c;",
+        )
         .unwrap_err();
+    assert_eq!(
+        err.unwrap_db_error().message(),
+        "invalid resume token: not base64"
+    );
+    client_reads.batch_execute("ROLLBACK").unwrap();
+
+    // Once the index is compacted past the token, resuming from it fails and names the earliest
+    // token that can still be resumed from.
+    client_writes
+        .batch_execute("ALTER INDEX t_primary_idx SET (RETAIN HISTORY = FOR '1ms')")
+        .unwrap();
+    let deadline = Instant::now() + Duration::from_secs(60);
+    let err = loop {
+        assert!(Instant::now() < deadline, "token was not invalidated");
+        let result = client_reads.batch_execute(&format!(
+            "BEGIN;
+             DECLARE c CURSOR FOR SUBSCRIBE t WITH (RESUME FROM = '{token}');
+             FETCH 0 c;"
+        ));
+        client_reads.batch_execute("ROLLBACK").unwrap();
+        if let Err(err) = result {
+            break err.unwrap_db_error();
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    };
+    assert_contains!(
+        err.message(),
+        "is before the earliest available timestamp; updates after it are no longer retained"
+    );
+    assert_contains!(err.hint().unwrap(), "Resume from '");
+}
+
+/// Test that canceling a SUBSCRIBE and terminating its session, in both orders, tears the session
+/// down exactly once: the coordinator survives, the subscribe is retracted from
+/// `mz_subscriptions` once, and the session and subscribe gauges return to their baselines.
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 2))]
+async fn test_cancel_terminate_race() {
+    fn gauge(server: &test_util::TestServer, name: &str) -> f64 {
+        server
+            .metrics_registry
+            .gather()
+            .into_iter()
+            .filter(|metric| metric.get_name() == name)
+            .flat_map(|metric| {
+                metric
+                    .get_metric()
+                    .iter()
+                    .map(|m| m.get_gauge().get_value())
+                    .collect::<Vec<_>>()
+            })
+            .sum()
+    }
+
+    async fn wait_for_count(client: &tokio_postgres::Client, query: &str, expected: i64) {
+        Retry::default()
+            .max_duration(Duration::from_secs(30))
+            .retry_async(|_| async {
+                let count: i64 = client.query_one(query, &[]).await?.get(0);
+                if count == expected {
+                    Ok(())
+                } else {
+                    Err(anyhow::anyhow!("{query}: expected {expected}, got {count}"))
+                }
+            })
+            .await
+            .unwrap();
+    }
+
+    let server = test_util::TestHarness::default().start().await;
+    let client = server.connect().await.unwrap();
+    client.batch_execute("CREATE TABLE t ()").await.unwrap();
+
+    let baseline_sessions = gauge(&server, "mz_active_sessions");
+
+    for cancel_first in [true, false] {
+        let (sub_client, conn_task) = server.connect().with_handle().await.unwrap();
+        let conn_id: i32 = sub_client
+            .query_one("SELECT pg_backend_pid()", &[])
+            .await
+            .unwrap()
+            .get(0);
+        let subscriptions = format!(
+            "SELECT count(*) FROM mz_internal.mz_subscriptions WHERE session_id = {conn_id}::uint4"
+        );
+        let sessions =
+            format!("SELECT count(*) FROM mz_internal.mz_sessions WHERE id = {conn_id}::uint4");
+
+        // Launch a SUBSCRIBE that never terminates.
+        let _copy_out = sub_client
+            .copy_out("COPY (SUBSCRIBE t) TO STDOUT")
+            .await
+            .unwrap();
+        wait_for_count(&client, &subscriptions, 1).await;
+        assert_eq!(gauge(&server, "mz_active_subscribes"), 1.0);
+
+        let cancel = format!("SELECT pg_cancel_backend({conn_id})");
+        if cancel_first {
+            let found_conn: bool = client.query_one(&cancel, &[]).await.unwrap().get(0);
+            assert!(found_conn);
+            wait_for_count(&client, &subscriptions, 0).await;
+            conn_task.abort_and_wait().await;
+            wait_for_count(&client, &sessions, 0).await;
+        } else {
+            conn_task.abort_and_wait().await;
+            wait_for_count(&client, &sessions, 0).await;
+            let found_conn: bool = client.query_one(&cancel, &[]).await.unwrap().get(0);
+            assert!(!found_conn);
+        }
+        drop(sub_client);
+
+        // A second retraction of the subscribe would leave a negative count behind.
+        wait_for_count(&client, &subscriptions, 0).await;
+        wait_for_count(
+            &client,
+            "SELECT count(*) FROM mz_internal.mz_subscriptions",
+            0,
+        )
+        .await;
+        assert_eq!(gauge(&server, "mz_active_subscribes"), 0.0);
+        assert_eq!(gauge(&server, "mz_active_sessions"), baseline_sessions);
+    }
+
+    // The coordinator is still healthy.
+    client.batch_execute("SELECT 1").await.unwrap();
+}
+
+// Test params in interesting places.
+#[mz_ore::test]
+fn test_params() {
+    mz_ore::test::init_logging();
+    let server = test_util::TestHarness::default().start_blocking();
+
+    let mut client = server.connect(postgres::NoTls).unwrap();
+
+    assert_eq!(
+        client
+            .query_one("SELECT $1", &[&"test"])
+            .unwrap()
+            .get::<_, String>(0),
+        "test"
+    );
+    assert_eq!(
+        client
+            .query_one("SUBSCRIBE (SELECT $1 as c)", &[&"test"])
+            .unwrap()
+            .get::<_, String>("c"),
+        "test"
+    );
+
+    client.batch_execute("BEGIN").unwrap();
+    client
+        .execute("DECLARE c CURSOR FOR SELECT $1", &[&"test"])
+        .map_err_to_string_with_causes()
+        .unwrap();
+    assert_eq!(
+        client
+            .query_one("FETCH c", &[])
+            .map_err_to_string_with_causes()
+            .unwrap()
+            .get::<_, String>(0),
+        "test"
+    );
+    client.batch_execute("COMMIT").unwrap();
+
+    client.batch_execute("BEGIN").unwrap();
+    client
+        .execute("DECLARE c CURSOR FOR SUBSCRIBE(SELECT $1 as c)", &[&"test"])
+        .map_err_to_string_with_causes()
+        .unwrap();
+    assert_eq!(
+        client
+            .query_one("FETCH c", &[])
+            .map_err_to_string_with_causes()
+            .unwrap()
+            .get::<_, String>("c"),
+        "test"
+    );
+    client.batch_execute("COMMIT").unwrap();
+}
+
+// Test pg_cancel_backend after the authenticated role is dropped.
+#[mz_ore::test]
+fn test_pg_cancel_dropped_role() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let dropped_role = "r1";
+
+    let mut query_client = server.connect(postgres::NoTls).unwrap();
+
+    // Create role.
+    query_client
+        .execute(&format!("CREATE ROLE {dropped_role}"), &[])
+        .unwrap();
+
+    // Start session using role.
+    let mut dropped_client = server
+        .pg_config()
+        .user(dropped_role)
+        .connect(postgres::NoTls)
+        .unwrap();
+
+    // Get the connection ID of the new session.
+    let connection_id = dropped_client
+        .query_one(
+            &format!(
+                "SELECT s.id
+         FROM mz_internal.mz_sessions s
+         JOIN mz_roles r ON s.role_id = r.id
+         WHERE r.name = '{dropped_role}'"
+            ),
+            &[],
+        )
+        .unwrap()
+        .get::<_, UInt4>(0)
+        .0;
+
+    // Drop role.
+    query_client
+        .execute(&format!("DROP ROLE {dropped_role}"), &[])
+        .unwrap();
+
+    // Test that pg_cancel_backend doesn't panic. It's expected to fail with a privilege error. To
+    // execute pg_cancel_backend you must be a member of the authenticated role of the connection.
+    // Since the authenticated role doesn't exist, no one can be a member, and no one will have the
+    // required privileges.
+    let err = query_client
+        .query_one(&format!("SELECT pg_cancel_backend({connection_id})"), &[])
+        .unwrap_err();
+
+    assert_eq!(err.code().unwrap(), &SqlState::INSUFFICIENT_PRIVILEGE);
+}
+
+#[mz_ore::test]
+fn test_peek_on_dropped_indexed_view() {
+    let server = test_util::TestHarness::default().start_blocking();
+
+    let mut ddl_client = server.connect(postgres::NoTls).unwrap();
+    let mut peek_client = server.connect(postgres::NoTls).unwrap();
+
+    ddl_client.batch_execute("CREATE TABLE t (a INT)").unwrap();
+    ddl_client
+        .batch_execute("CREATE VIEW v AS SELECT (a + 1) AS a FROM t")
+        .unwrap();
+    ddl_client.batch_execute("CREATE INDEX i ON v (a)").unwrap();
+
+    // Asynchronously query an indexed view.
+    let handle = thread::spawn(move || {
+        peek_client.query(
+            &format!("SELECT * FROM v AS OF {}", mz_repr::Timestamp::MAX),
+            &[],
+        )
+    });
+
+    let index_id: String = ddl_client
+        .query_one("SELECT id FROM mz_indexes WHERE name = 'i'", &[])
+        .unwrap()
+        .get(0);
+
+    // Wait for SELECT to start.
+    Retry::default()
+        .max_duration(Duration::from_secs(10))
+        .retry(|_| {
+            let count: i64 = ddl_client
+                .query_one(
+                    &format!(
+                "SELECT COUNT(*) FROM mz_internal.mz_active_peeks WHERE object_id = '{index_id}'"
+            ),
+                    &[],
+                )
+                .unwrap()
+                .get(0);
+            if count <= 0 {
+                Err("Peek not active")
+            } else {
+                Ok(())
+            }
+        })
+        .unwrap();
+
+    // Drop the table, view, and index.
+    ddl_client.batch_execute("DROP TABLE t CASCADE").unwrap();
+
+    // Check that the peek is cancelled an all resources are cleaned up.
+    let select_res = handle.join().unwrap();
+    let select_err = select_res.unwrap_err().to_string();
+    assert!(
+        select_err.contains(
+            "query could not complete because relation \"materialize.public.v\" was dropped"
+        ),
+        "unexpected error: {select_err}"
+    );
+    Retry::default()
+        .max_duration(Duration::from_secs(10))
+        .retry(|_| {
+            let count: i64 = ddl_client
+                .query_one(
+                    &format!(
+                        "SELECT COUNT(*) FROM mz_internal.mz_active_peeks WHERE object_id = '{index_id}'"
+                    ),
+                    &[],
+                )
+                .unwrap()
+                .get(0);
+            if count > 0 {
+                Err("Peek still active")
+            } else {
+                Ok(())
+            }
+        })
+        .unwrap();
+}
+
+/// Test AS OF in EXPLAIN. This output will only differ from the non-ASOF versions with RETAIN
+/// HISTORY, where the object and its indexes have differing compaction policies.
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+async fn test_explain_as_of() {
+    // TODO: This would be better in testdrive, but we'd need to support negative intervals in AS
+    // OF first.
+    let server = test_util::TestHarness::default().start().await;
+    let client = server.connect().await.unwrap();
+
+    // Retry until we are able to explain plan and timestamp for a few seconds ago.
+    // mz_cluster_replica_statuses is a retained metrics table which is why a historical AS OF
+    // works.
+    Retry::default()
+        .clamp_backoff(Duration::from_secs(1))
+        .retry_async(|_| async {
+            let now: String = client
+                .query_one("SELECT mz_now()::text", &[])
+                .await
+                .unwrap()
+                .get(0);
+            let now: u64 = now.parse().unwrap();
+            let ts = now - 3000;
+            let query = format!("mz_internal.mz_cluster_replica_statuses AS OF {ts}");
+            let query_ts = try_get_explain_timestamp(&query, &client).await?;
+            assert_eq!(ts, query_ts);
+            client
+                .query_one(&format!("EXPLAIN PLAN FOR SELECT * FROM {query}"), &[])
+                .await?;
+            Ok::<_, anyhow::Error>(())
+        })
+        .await
+        .unwrap();
+}
+
+// Test that RETAIN HISTORY results in the since and upper being separated by the specified amount.
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[ignore] // TODO: Reenable when #24957 is fixed
+async fn test_retain_history() {
+    let server = test_util::TestHarness::default().start().await;
+    let client = server.connect().await.unwrap();
+    let sys_client = server
+        .connect()
+        .internal()
+        .user(&SYSTEM_USER.name)
+        .await
+        .unwrap();
+
+    // Must fail before flag set.
+    assert!(client
+        .batch_execute(
+            "CREATE MATERIALIZED VIEW v WITH (RETAIN HISTORY = FOR '2s') AS SELECT * FROM t",
+        )
+        .await
+        .is_err());
+    assert!(client
+        .batch_execute(
+            "CREATE SOURCE s FROM LOAD GENERATOR COUNTER WITH (RETAIN HISTORY = FOR '2s')",
+        )
+        .await
+        .is_err());
+
+    sys_client
+        .batch_execute("ALTER SYSTEM SET enable_logical_compaction_window = true")
+        .await
+        .unwrap();
+
+    client
+        .batch_execute("CREATE TABLE t (a INT4)")
+        .await
+        .unwrap();
+    client
+        .batch_execute("INSERT INTO t VALUES (1)")
+        .await
+        .unwrap();
+
+    assert_contains!(
+        client
+            .batch_execute(
+                "CREATE MATERIALIZED VIEW v WITH (RETAIN HISTORY = FOR '-2s') AS SELECT * FROM t",
+            )
+            .await
+            .unwrap_err()
+            .to_string(),
+        "invalid RETAIN HISTORY"
+    );
+
+    client
+        .batch_execute(
+            "CREATE MATERIALIZED VIEW v WITH (RETAIN HISTORY = FOR '5s') AS SELECT * FROM t",
+        )
+        .await
+        .unwrap();
+    client
+        .batch_execute(
+            "CREATE SOURCE s FROM LOAD GENERATOR COUNTER WITH (RETAIN HISTORY = FOR '5s')",
+        )
+        .await
+        .unwrap();
+    client
+        .batch_execute(
+            "CREATE SOURCE s_auction FROM LOAD GENERATOR AUCTION FOR ALL TABLES WITH (RETAIN HISTORY = FOR '2s')",
+        )
+        .await
+        .unwrap();
+
+    // users is a subsource on the auction source.
+    for name in ["v", "s", "users"] {
+        // Test compaction and querying without an index present.
+        Retry::default()
+            .retry_async(|_| async {
+                let ts = get_explain_timestamp_determination(name, &client).await?;
+                let source = ts.sources.into_element();
+                let upper = source.write_frontier.into_element();
+                let since = source.read_frontier.into_element();
+                if upper.saturating_sub(since) < Timestamp::from(2000u64) {
+                    anyhow::bail!("{upper} - {since} should be atleast 2s apart")
+                }
+                client
+                    .query(
+                        &format!(
+                            "SELECT 1 FROM {name} LIMIT 1 AS OF {}-2000",
+                            ts.determination.timestamp_context.timestamp_or_default()
+                        ),
+                        &[],
+                    )
+                    .await?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        // With an index the AS OF query should fail because we haven't taught the planner about retain
+        // history yet. Depending on the as-of the index was created at, the requested time may
+        // never have been readable from the index, or may have been compacted away already.
+        client
+            .batch_execute(&format!("CREATE DEFAULT INDEX ON {name}"))
+            .await
+            .unwrap();
+
+        let ts = get_explain_timestamp(name, &client).await;
+        let err = client
+            .query(&format!("SELECT * FROM {name} AS OF {ts}-2000"), &[])
+            .await
+            .unwrap_err();
+        let err = err.as_db_error().expect("expected DB error");
+        if err.code() == &SqlState::SNAPSHOT_TOO_OLD {
+            assert_as_of_before_retention(err, ts - 2000);
+        } else {
+            assert_contains!(err.message(), "not valid for all inputs");
+        }
+
+        // Make sure we didn't fail just because the index didn't have enough time after creation.
+        // By now the requested time was readable from the index and has been compacted away.
+        tokio::time::sleep(Duration::from_secs(3)).await;
+        let ts = get_explain_timestamp(name, &client).await;
+        let err = client
+            .query(&format!("SELECT * FROM {name} AS OF {ts}-2000"), &[])
+            .await
+            .unwrap_err();
+        let err = err.as_db_error().expect("expected DB error");
+        assert_eq!(err.code(), &SqlState::SNAPSHOT_TOO_OLD);
+        assert_as_of_before_retention(err, ts - 2000);
+    }
+}
+
+/// Asserts that `err` reports that `AS OF requested` is before the earliest timestamp still
+/// retained by the inputs of a query.
+fn assert_as_of_before_retention(err: &tokio_postgres::error::DbError, requested: EpochMillis) {
+    let earliest_available: EpochMillis = err
+        .message()
+        .strip_prefix(&format!(
+            "AS OF {requested} is before the earliest available timestamp "
+        ))
+        .and_then(|message| message.strip_suffix("; earlier data is no longer retained"))
+        .and_then(|earliest_available| earliest_available.parse().ok())
+        .unwrap_or_else(|| panic!("unexpected error: {}", err.message()));
+    assert!(
+        requested < earliest_available,
+        "{requested} should be before {earliest_available}"
+    );
+    assert_eq!(
+        err.hint(),
+        Some(
+            format!(
+                "Use AS OF {earliest_available} or later, or AS OF AT LEAST to read at the \
+                earliest available timestamp."
+            )
+            .as_str()
+        )
+    );
+}
+
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+async fn test_temporal_static_queries() {
+    let server = test_util::TestHarness::default().start().await;
+    let client = server.connect().await.unwrap();
+
+    client
+        .batch_execute("CREATE SCHEMA dev_fy2023;")
+        .await
+        .unwrap();
+    client
+        .batch_execute("CREATE SCHEMA dev_warm;")
+        .await
+        .unwrap();
+    client.batch_execute("CREATE SCHEMA dev;").await.unwrap();
+    client
+        .batch_execute(
+            "CREATE VIEW dev.mock_data_days AS
+                WITH
+                    days AS (
+                        SELECT generate_series(
+                            CAST('2022-12-01 11:00:00' AS timestamp),
+                            CAST('2024-02-01' AS timestamp),
+                            CAST('1 hour' AS interval)
+                        ) AS \"day\"
+                    )
+                SELECT
+                    \"day\" AS ts,
+                    datediff('hour', CAST('2020-01-01' AS timestamp), \"day\") AS id
+                FROM days;",
+        )
+        .await
+        .unwrap();
+    client
+        .batch_execute(
+            "CREATE VIEW dev_warm.stg_data_days AS
+                SELECT *
+                FROM dev.mock_data_days
+                WHERE
+                    mz_now() <= date_trunc('year', ts + CAST('1 year' AS interval)) AND
+                    ts + CAST('7 days' AS interval) - CAST('1 month' AS interval) < mz_now();",
+        )
+        .await
+        .unwrap();
+    client
+        .batch_execute(
+            "CREATE VIEW dev_warm.count_by_day AS
+                SELECT
+                    date_trunc('day', ts) AS \"day\",
+                    count(*) AS cnt
+                FROM dev_warm.stg_data_days
+                GROUP BY 1
+                HAVING
+                    NOT (mz_now() <= date_trunc('day', ts) + CAST('7 days' AS interval)) AND
+                    mz_now() <= date_trunc('year', date_trunc('day', ts) + CAST('1 year' AS interval));",
+        )
+        .await
+        .unwrap();
+    client
+        .batch_execute(
+            "CREATE VIEW dev_fy2023.stg_data_days AS
+                SELECT *
+                FROM dev.mock_data_days
+                WHERE
+                    CAST('2023-01-01' AS timestamp) <= ts AND
+                    ts - CAST('1 month' AS interval) < CAST('2024-01-01' AS timestamp) AND
+                    ts - CAST('0 month' AS interval) < CAST('2025-01-01' AS timestamp);",
+        )
+        .await
+        .unwrap();
+    client
+        .batch_execute(
+            "CREATE VIEW dev_fy2023.count_by_day AS
+                SELECT
+                    date_trunc('day', ts) AS \"day\",
+                    count(*) AS cnt
+                FROM dev_fy2023.stg_data_days
+                GROUP BY 1
+                HAVING
+                    NOT (CAST('2024-01-01' AS timestamp) <= date_trunc('day', ts)) AND
+                    CAST('2023-01-01' AS timestamp) <= date_trunc('day', ts);",
+        )
+        .await
+        .unwrap();
+
+    let timestamp = get_explain_timestamp(
+        "(SELECT *, 'fy2023' AS origin FROM dev_fy2023.count_by_day
+        UNION ALL
+        SELECT *, 'warm' AS origin FROM dev_warm.count_by_day)",
+        &client,
+    )
+    .await;
+    assert!(
+        timestamp < EpochMillis::MAX,
+        "expected temporal query to have timestamps less than max timestamp of {}, instead found {}",
+        EpochMillis::MAX,
+        timestamp
+    )
+}
+
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_constant_materialized_view() {
+    let server = test_util::TestHarness::default().start().await;
+    let client = server.connect().await.unwrap();
+    client
+        .batch_execute("CREATE CLUSTER cold (SIZE '1', REPLICATION FACTOR = 0);")
+        .await
+        .unwrap();
+    // The materialized view is created in a cluster with 0 replicas, so it's upper will be stuck
+    // at 0.
+    client
+        .batch_execute(
+            "CREATE MATERIALIZED VIEW mv IN CLUSTER cold AS SELECT generate_series(1, 100);",
+        )
+        .await
+        .unwrap();
+
+    let timestamp_determination = get_explain_timestamp_determination("mv", &client)
+        .await
+        .unwrap();
+
+    assert!(
+        !timestamp_determination.respond_immediately,
+        "upper is stuck at 0 so the query cannot respond immediately"
+    );
+    match timestamp_determination.determination.timestamp_context {
+        TimestampContext::TimelineTimestamp { chosen_ts, .. } => {
+            assert_ne!(Timestamp::MAX, chosen_ts)
+        }
+        TimestampContext::NoTimestamp => {
+            panic!("queries against materialized views always require a timestamp")
+        }
+    }
+}
+
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_explain_timestamp_blocking() {
+    let server = test_util::TestHarness::default().start().await;
+    server
+        .enable_feature_flags(&["enable_refresh_every_mvs"])
+        .await;
+    let client = server.connect().await.unwrap();
+    // This test will break in the year 30,000 after Jan 1st. When that happens, increase the year
+    // to fix the test.
+    client
+        .batch_execute(
+            "CREATE MATERIALIZED VIEW const_mv WITH (REFRESH AT '30000-01-01 23:59') AS SELECT 2;",
+        )
+        .await
+        .unwrap();
+
+    let mv_timestamp = get_explain_timestamp("const_mv", &client).await;
+
+    let row = client
+        .query_one("SELECT mz_now()::text;", &[])
+        .await
+        .unwrap();
+    let mz_now_ts_raw: String = row.get(0);
+    let mz_now_timestamp: EpochMillis = mz_now_ts_raw.parse().unwrap();
+
+    assert!(
+        mv_timestamp > mz_now_timestamp,
+        "read against mv at timestamp {mv_timestamp} should be in the future compared to now, {mz_now_timestamp}"
+    );
+}
+
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_cancel_linearize_reads() {
+    let server = test_util::TestHarness::default().start().await;
+    server
+        .enable_feature_flags(&["enable_refresh_every_mvs"])
+        .await;
+    let client = server.connect().await.unwrap();
+    let client_cancel = client.cancel_token();
+
+    // This test will break in the year 30,000 after Jan 1st. When that happens, increase the year
+    // to fix the test.
+    client
+        .batch_execute(
+            "CREATE MATERIALIZED VIEW const_mv WITH (REFRESH AT '30000-01-01 23:59') AS SELECT 2;",
+        )
+        .await
+        .unwrap();
+
+    let (tx, rx) = oneshot::channel();
+
+    task::spawn(|| "read task", async move {
+        // The since of const_mv will be in the year 30,000, requiring us to select a timestamp in
+        // the year 30,000, requiring us to linearize the read waiting for the timestamp oracle to
+        // catch up to the year 30,000.
+        let res = client.query_one("SELECT * FROM const_mv;", &[]).await;
+        let err = res.unwrap_err();
+        assert_eq!(err.unwrap_db_error().code(), &SqlState::QUERY_CANCELED);
+        tx.send(()).unwrap();
+    });
+
+    // There's a race for when we issue the cancel, so we need to retry. We want to issue the
+    // cancel after the async SELECT.
+    let (_, res) = Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry_async_with_state(
+            (client_cancel, rx),
+            |_, (client_cancel, mut rx)| async move {
+                client_cancel.cancel_query(postgres::NoTls).await.unwrap();
+                let res = rx.try_recv();
+                ((client_cancel, rx), res)
+            },
+        )
+        .await;
+    res.unwrap();
+}
+
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_cancel_linearize_read_then_writes() {
+    let server = test_util::TestHarness::default().start().await;
+    server
+        .enable_feature_flags(&["enable_refresh_every_mvs"])
+        .await;
+    let client = server.connect().await.unwrap();
+    let client_cancel = client.cancel_token();
+
+    client
+        .batch_execute("CREATE TABLE t (a INT);")
+        .await
+        .unwrap();
+
+    // This test will break in the year 30,000 after Jan 1st. When that happens, increase the year
+    // to fix the test.
+    client
+        .batch_execute(
+            "CREATE MATERIALIZED VIEW const_mv WITH (REFRESH AT '30000-01-01 23:59') AS SELECT 2;",
+        )
+        .await
+        .unwrap();
+
+    let handle = task::spawn(|| "read-then-write task", async move {
+        // The since of const_mv will be in the year 30,000, requiring us to select a timestamp in
+        // the year 30,000, requiring us to linearize the read waiting for the timestamp oracle to
+        // catch up to the year 30,000.
+        let res = client
+            .execute("INSERT INTO t SELECT * FROM const_mv;", &[])
+            .await;
+        let err = res.unwrap_err();
+        assert_eq!(err.unwrap_db_error().code(), &SqlState::QUERY_CANCELED);
+    });
+
+    // Sleep for a bit to try and get the `INSERT INTO SELECT` to run. We may get some false
+    // positives, but we won't get false negatives.
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert!(
+        !handle.is_finished(),
+        "INSERT INTO SELECT should be linearized and wait for the year 30,000"
+    );
 
-    assert_eq!(err.code().unwrap(), &SqlState::INSUFFICIENT_PRIVILEGE);
+    // There's a race for when we issue the cancel, so we need to retry. We want to issue the
+    // cancel after the async SELECT.
+    let ((_, handle), res) = Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry_async_with_state(
+            (client_cancel, handle),
+            |_, (client_cancel, handle)| async move {
+                client_cancel.cancel_query(postgres::NoTls).await.unwrap();
+                let res = if handle.is_finished() {
+                    Ok(())
+                } else {
+                    Err("thread unfinished".to_string())
+                };
+                ((client_cancel, handle), res)
+            },
+        )
+        .await;
+    res.unwrap();
+    handle.await.unwrap();
 }
 
-#[mz_ore::test]
-fn test_peek_on_dropped_indexed_view() {
-    let server = test_util::TestHarness::default().start_blocking();
-
-    let mut ddl_client = server.connect(postgres::NoTls).unwrap();
-    let mut peek_client = server.connect(postgres::NoTls).unwrap();
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_soft_drop_undrop() {
+    let server = test_util::TestHarness::default().start().await;
+    server.enable_feature_flags(&["enable_soft_drop"]).await;
+    let client = server.connect().await.unwrap();
+    let internal_client = server.connect().internal().await.unwrap();
 
-    ddl_client.batch_execute("CREATE TABLE t (a INT)").unwrap();
-    ddl_client
-        .batch_execute("CREATE VIEW v AS SELECT (a + 1) AS a FROM t")
+    client
+        .batch_execute(
+            "CREATE TABLE t (a INT);
+            INSERT INTO t VALUES (1), (2), (3);
+            CREATE MATERIALIZED VIEW mv AS SELECT a FROM t;",
+        )
+        .await
         .unwrap();
-    ddl_client.batch_execute("CREATE INDEX i ON v (a)").unwrap();
-
-    // Asynchronously query an indexed view.
-    let handle = thread::spawn(move || {
-        peek_client.query(
-            &format!("SELECT * FROM v AS OF {}", mz_repr::Timestamp::MAX),
+    let id: String = client
+        .query_one(
+            "SELECT id FROM mz_materialized_views WHERE name = 'mv'",
             &[],
         )
-    });
-
-    let index_id: String = ddl_client
-        .query_one("SELECT id FROM mz_indexes WHERE name = 'i'", &[])
+        .await
         .unwrap()
         .get(0);
-
-    // Wait for SELECT to start.
-    Retry::default()
-        .max_duration(Duration::from_secs(10))
-        .retry(|_| {
-            let count: i64 = ddl_client
+    let collection_exists = |id: String| {
+        let client = &client;
+        async move {
+            let count: i64 = client
                 .query_one(
-                    &format!(
-                "SELECT COUNT(*) FROM mz_internal.mz_active_peeks WHERE object_id = '{index_id}'"
-            ),
-                    &[],
+                    "SELECT count(*) FROM mz_internal.mz_frontiers WHERE object_id = $1",
+                    &[&id],
                 )
+                .await
                 .unwrap()
                 .get(0);
-            if count <= 0 {
-                Err("Peek not active")
-            } else {
-                Ok(())
-            }
-        })
+            count > 0
+        }
+    };
+
+    // The dropped materialized view disappears from the catalog, but its data is retained.
+    client
+        .batch_execute("DROP MATERIALIZED VIEW mv")
+        .await
         .unwrap();
+    client.query("SELECT * FROM mv", &[]).await.unwrap_err();
+    assert!(collection_exists(id.clone()).await);
 
-    // Drop the table, view, and index.
-    ddl_client.batch_execute("DROP TABLE t CASCADE").unwrap();
+    internal_client
+        .batch_execute(&format!("UNDROP {id}"))
+        .await
+        .unwrap();
+    let values: Vec<i32> = client
+        .query("SELECT a FROM mv ORDER BY a", &[])
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| row.get(0))
+        .collect();
+    assert_eq!(values, vec![1, 2, 3]);
 
-    // Check that the peek is cancelled an all resources are cleaned up.
-    let select_res = handle.join().unwrap();
-    let select_err = select_res.unwrap_err().to_string();
-    assert!(
-        select_err.contains(
-            "query could not complete because relation \"materialize.public.v\" was dropped"
-        ),
-        "unexpected error: {select_err}"
-    );
+    // Once the restore window lapses, the materialized view is cleaned up for good.
+    internal_client
+        .batch_execute("ALTER SYSTEM SET soft_drop_retention = '100ms'")
+        .await
+        .unwrap();
+    client
+        .batch_execute("DROP MATERIALIZED VIEW mv")
+        .await
+        .unwrap();
     Retry::default()
-        .max_duration(Duration::from_secs(10))
-        .retry(|_| {
-            let count: i64 = ddl_client
-                .query_one(
-                    &format!(
-                        "SELECT COUNT(*) FROM mz_internal.mz_active_peeks WHERE object_id = '{index_id}'"
-                    ),
-                    &[],
-                )
-                .unwrap()
-                .get(0);
-            if count > 0 {
-                Err("Peek still active")
-            } else {
-                Ok(())
+        .max_duration(Duration::from_secs(30))
+        .retry_async(|_| {
+            let id = id.clone();
+            let collection_exists = &collection_exists;
+            async move {
+                if collection_exists(id).await {
+                    Err("collection still exists")
+                } else {
+                    Ok(())
+                }
             }
         })
+        .await
         .unwrap();
+    let err = internal_client
+        .batch_execute(&format!("UNDROP {id}"))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("unknown catalog item"),
+        "unexpected error: {err}"
+    );
 }
 
-/// Test AS OF in EXPLAIN. This output will only differ from the non-ASOF versions with RETAIN
-/// HISTORY, where the object and its indexes have differing compaction policies.
+// Tests that dropping a cluster drops the collections installed on it for good, including those
+// of soft-dropped materialized views, which cannot be restored without their cluster.
 #[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
-async fn test_explain_as_of() {
-    // TODO: This would be better in testdrive, but we'd need to support negative intervals in AS
-    // OF first.
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_soft_drop_drop_cluster() {
     let server = test_util::TestHarness::default().start().await;
+    server.enable_feature_flags(&["enable_soft_drop"]).await;
     let client = server.connect().await.unwrap();
+    let internal_client = server.connect().internal().await.unwrap();
 
-    // Retry until we are able to explain plan and timestamp for a few seconds ago.
-    // mz_cluster_replica_statuses is a retained metrics table which is why a historical AS OF
-    // works.
-    Retry::default()
-        .clamp_backoff(Duration::from_secs(1))
-        .retry_async(|_| async {
-            let now: String = client
-                .query_one("SELECT mz_now()::text", &[])
+    client
+        .batch_execute(
+            "CREATE CLUSTER c REPLICAS (r1 (size '1'));
+            CREATE TABLE t (a INT);
+            INSERT INTO t VALUES (1), (2), (3);
+            CREATE INDEX t_idx IN CLUSTER c ON t (a);
+            CREATE MATERIALIZED VIEW soft_dropped IN CLUSTER c AS SELECT a FROM t;
+            CREATE MATERIALIZED VIEW mv IN CLUSTER c AS SELECT a FROM t;",
+        )
+        .await
+        .unwrap();
+    let mv_id = |name: &'static str| {
+        let client = &client;
+        async move {
+            let id: String = client
+                .query_one(
+                    "SELECT id FROM mz_materialized_views WHERE name = $1",
+                    &[&name],
+                )
                 .await
                 .unwrap()
                 .get(0);
-            let now: u64 = now.parse().unwrap();
-            let ts = now - 3000;
-            let query = format!("mz_internal.mz_cluster_replica_statuses AS OF {ts}");
-            let query_ts = try_get_explain_timestamp(&query, &client).await?;
-            assert_eq!(ts, query_ts);
-            client
-                .query_one(&format!("EXPLAIN PLAN FOR SELECT * FROM {query}"), &[])
-                .await?;
-            Ok::<_, anyhow::Error>(())
-        })
+            id
+        }
+    };
+    let soft_dropped_id = mv_id("soft_dropped").await;
+    let mv_id = mv_id("mv").await;
+    client
+        .batch_execute("DROP MATERIALIZED VIEW soft_dropped")
         .await
         .unwrap();
-}
 
-// Test that RETAIN HISTORY results in the since and upper being separated by the specified amount.
-#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
-#[ignore] // TODO: Reenable when #24957 is fixed
-async fn test_retain_history() {
-    let server = test_util::TestHarness::default().start().await;
-    let client = server.connect().await.unwrap();
-    let sys_client = server
-        .connect()
-        .internal()
-        .user(&SYSTEM_USER.name)
+    // The cluster still has an installed index and the collections of both materialized views.
+    client
+        .batch_execute("DROP CLUSTER c CASCADE")
         .await
         .unwrap();
 
-    // Must fail before flag set.
-    assert!(client
-        .batch_execute(
-            "CREATE MATERIALIZED VIEW v WITH (RETAIN HISTORY = FOR '2s') AS SELECT * FROM t",
-        )
-        .await
-        .is_err());
-    assert!(client
-        .batch_execute(
-            "CREATE SOURCE s FROM LOAD GENERATOR COUNTER WITH (RETAIN HISTORY = FOR '2s')",
-        )
+    // The environment survived the drop, and neither materialized view can be restored.
+    let values: Vec<i32> = client
+        .query("SELECT a FROM t ORDER BY a", &[])
         .await
-        .is_err());
+        .unwrap()
+        .into_iter()
+        .map(|row| row.get(0))
+        .collect();
+    assert_eq!(values, vec![1, 2, 3]);
+    for id in [soft_dropped_id, mv_id] {
+        let err = internal_client
+            .batch_execute(&format!("UNDROP {id}"))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("unknown catalog item"),
+            "unexpected error: {err}"
+        );
+    }
+}
 
-    sys_client
-        .batch_execute("ALTER SYSTEM SET enable_logical_compaction_window = true")
-        .await
-        .unwrap();
+// Tests that soft-dropped materialized views remain restorable across restarts for as long as
+// their restore window lasts, and are cleaned up once it lapses.
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_soft_drop_undrop_across_restart() {
+    let data_dir = tempfile::tempdir().unwrap();
+    let harness = test_util::TestHarness::default().data_directory(data_dir.path());
 
-    client
-        .batch_execute("CREATE TABLE t (a INT4)")
-        .await
-        .unwrap();
-    client
-        .batch_execute("INSERT INTO t VALUES (1)")
-        .await
-        .unwrap();
+    async fn collection_exists(client: &tokio_postgres::Client, id: &str) -> bool {
+        let count: i64 = client
+            .query_one(
+                "SELECT count(*) FROM mz_internal.mz_frontiers WHERE object_id = $1",
+                &[&id],
+            )
+            .await
+            .unwrap()
+            .get(0);
+        count > 0
+    }
 
-    assert_contains!(
+    let id: String = {
+        let server = harness.clone().start().await;
+        server.enable_feature_flags(&["enable_soft_drop"]).await;
+        let client = server.connect().await.unwrap();
         client
             .batch_execute(
-                "CREATE MATERIALIZED VIEW v WITH (RETAIN HISTORY = FOR '-2s') AS SELECT * FROM t",
+                "CREATE TABLE t (a INT);
+                INSERT INTO t VALUES (1), (2), (3);
+                CREATE MATERIALIZED VIEW mv AS SELECT a FROM t;",
             )
             .await
-            .unwrap_err()
-            .to_string(),
-        "invalid RETAIN HISTORY"
-    );
+            .unwrap();
+        let id = client
+            .query_one(
+                "SELECT id FROM mz_materialized_views WHERE name = 'mv'",
+                &[],
+            )
+            .await
+            .unwrap()
+            .get(0);
+        client
+            .batch_execute("DROP MATERIALIZED VIEW mv")
+            .await
+            .unwrap();
+        id
+    };
 
-    client
-        .batch_execute(
-            "CREATE MATERIALIZED VIEW v WITH (RETAIN HISTORY = FOR '5s') AS SELECT * FROM t",
-        )
-        .await
-        .unwrap();
-    client
-        .batch_execute(
-            "CREATE SOURCE s FROM LOAD GENERATOR COUNTER WITH (RETAIN HISTORY = FOR '5s')",
-        )
-        .await
-        .unwrap();
-    client
-        .batch_execute(
-            "CREATE SOURCE s_auction FROM LOAD GENERATOR AUCTION FOR ALL TABLES WITH (RETAIN HISTORY = FOR '2s')",
-        )
-        .await
-        .unwrap();
+    // The restart happens within the restore window, so the view can still be undropped.
+    {
+        let server = harness.clone().start().await;
+        let client = server.connect().await.unwrap();
+        let internal_client = server.connect().internal().await.unwrap();
+        assert!(collection_exists(&client, &id).await);
+
+        internal_client
+            .batch_execute(&format!("UNDROP {id}"))
+            .await
+            .unwrap();
+        client
+            .batch_execute("INSERT INTO t VALUES (4)")
+            .await
+            .unwrap();
+        let values: Vec<i32> = client
+            .query("SELECT a FROM mv ORDER BY a", &[])
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect();
+        assert_eq!(values, vec![1, 2, 3, 4]);
+
+        internal_client
+            .batch_execute("ALTER SYSTEM SET soft_drop_retention = '100ms'")
+            .await
+            .unwrap();
+        client
+            .batch_execute("DROP MATERIALIZED VIEW mv")
+            .await
+            .unwrap();
+    }
 
-    // users is a subsource on the auction source.
-    for name in ["v", "s", "users"] {
-        // Test compaction and querying without an index present.
+    // The restore window lapses by the time the environment restarts, so the view is cleaned up
+    // for good.
+    {
+        let server = harness.clone().start().await;
+        let client = server.connect().await.unwrap();
+        let internal_client = server.connect().internal().await.unwrap();
         Retry::default()
+            .max_duration(Duration::from_secs(30))
             .retry_async(|_| async {
-                let ts = get_explain_timestamp_determination(name, &client).await?;
-                let source = ts.sources.into_element();
-                let upper = source.write_frontier.into_element();
-                let since = source.read_frontier.into_element();
-                if upper.saturating_sub(since) < Timestamp::from(2000u64) {
-                    anyhow::bail!("{upper} - {since} should be atleast 2s apart")
+                if collection_exists(&client, &id).await {
+                    Err("collection still exists")
+                } else {
+                    Ok(())
                 }
-                client
-                    .query(
-                        &format!(
-                            "SELECT 1 FROM {name} LIMIT 1 AS OF {}-2000",
-                            ts.determination.timestamp_context.timestamp_or_default()
-                        ),
-                        &[],
-                    )
-                    .await?;
-                Ok(())
             })
             .await
             .unwrap();
+        let err = internal_client
+            .batch_execute(&format!("UNDROP {id}"))
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("unknown catalog item"),
+            "unexpected error: {err}"
+        );
+    }
+}
 
-        // With an index the AS OF query should fail because we haven't taught the planner about retain
-        // history yet.
+// Tests that the parameter overrides of sources survive restarts.
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_source_parameter_overrides_across_restart() {
+    let data_dir = tempfile::tempdir().unwrap();
+    let harness = test_util::TestHarness::default().data_directory(data_dir.path());
+
+    async fn create_sql(client: &tokio_postgres::Client) -> String {
         client
-            .batch_execute(&format!("CREATE DEFAULT INDEX ON {name}"))
+            .query_one("SHOW CREATE SOURCE counter", &[])
+            .await
+            .unwrap()
+            .get(1)
+    }
+
+    let before = {
+        let server = harness.clone().start().await;
+        server
+            .enable_feature_flags(&["enable_source_parameter_overrides"])
+            .await;
+        let client = server.connect().await.unwrap();
+        client
+            .batch_execute(
+                "CREATE SOURCE counter FROM LOAD GENERATOR COUNTER WITH (MAX BATCH SIZE = 1000);
+                ALTER SOURCE counter SET (YIELD BUDGET = 100, STATISTICS INTERVAL = '1s');
+                ALTER SOURCE counter RESET (MAX BATCH SIZE);",
+            )
             .await
             .unwrap();
+        create_sql(&client).await
+    };
+    assert!(
+        before.contains("YIELD BUDGET = 100") && before.contains("STATISTICS INTERVAL = '1s'"),
+        "unexpected definition: {before}"
+    );
+    assert!(
+        !before.contains("MAX BATCH SIZE"),
+        "unexpected definition: {before}"
+    );
 
-        let ts = get_explain_timestamp(name, &client).await;
-        assert_contains!(
-            client
-                .query(&format!("SELECT * FROM {name} AS OF {ts}-2000"), &[])
-                .await
-                .unwrap_err()
-                .to_string(),
-            "not valid for all inputs"
-        );
+    let server = harness.start().await;
+    let client = server.connect().await.unwrap();
+    assert_eq!(create_sql(&client).await, before);
 
-        // Make sure we didn't fail just because the index didn't have enough time after creation.
-        tokio::time::sleep(Duration::from_secs(3)).await;
-        let ts = get_explain_timestamp(name, &client).await;
-        assert_contains!(
-            client
-                .query(&format!("SELECT * FROM {name} AS OF {ts}-2000"), &[])
+    // The source still works with its overrides.
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry_async(|_| async {
+            let count: i64 = client
+                .query_one("SELECT count(*) FROM counter", &[])
                 .await
-                .unwrap_err()
-                .to_string(),
-            "not valid for all inputs"
-        );
-    }
+                .unwrap()
+                .get(0);
+            if count > 0 {
+                Ok(())
+            } else {
+                Err("no data")
+            }
+        })
+        .await
+        .unwrap();
 }
 
+// Tests that read holds acquired for a statement that then errors out do not hold back
+// compaction of its inputs.
 #[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
-async fn test_temporal_static_queries() {
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_read_holds_released_on_error() {
     let server = test_util::TestHarness::default().start().await;
+    server
+        .enable_feature_flags(&["enable_refresh_every_mvs"])
+        .await;
     let client = server.connect().await.unwrap();
 
     client
-        .batch_execute("CREATE SCHEMA dev_fy2023;")
+        .batch_execute(
+            "CREATE TABLE t (a INT);
+            INSERT INTO t VALUES (1), (2), (3);",
+        )
         .await
         .unwrap();
-    client
-        .batch_execute("CREATE SCHEMA dev_warm;")
+    let id: String = client
+        .query_one("SELECT id FROM mz_tables WHERE name = 't'", &[])
         .await
-        .unwrap();
-    client.batch_execute("CREATE SCHEMA dev;").await.unwrap();
-    client
+        .unwrap()
+        .get(0);
+
+    // The read hold at creation time is acquired before the one at time 1 fails.
+    let err = client
         .batch_execute(
-            "CREATE VIEW dev.mock_data_days AS
-                WITH
-                    days AS (
-                        SELECT generate_series(
-                            CAST('2022-12-01 11:00:00' AS timestamp),
-                            CAST('2024-02-01' AS timestamp),
-                            CAST('1 hour' AS interval)
-                        ) AS \"day\"
-                    )
-                SELECT
-                    \"day\" AS ts,
-                    datediff('hour', CAST('2020-01-01' AS timestamp), \"day\") AS id
-                FROM days;",
+            "CREATE MATERIALIZED VIEW mv WITH (REFRESH AT CREATION, REFRESH AT 1) AS SELECT * FROM t",
         )
         .await
+        .unwrap_err();
+    assert_contains!(err.to_string(), "REFRESH AT");
+
+    let now: String = client
+        .query_one("SELECT mz_now()::text", &[])
+        .await
+        .unwrap()
+        .get(0);
+    let now: u64 = now.parse().unwrap();
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry_async(|_| {
+            let id = id.clone();
+            let client = &client;
+            async move {
+                let since: Option<String> = client
+                    .query_one(
+                        "SELECT read_frontier::text FROM mz_internal.mz_frontiers WHERE object_id = $1",
+                        &[&id],
+                    )
+                    .await
+                    .unwrap()
+                    .get(0);
+                match since.map(|since| since.parse::<u64>().unwrap()) {
+                    Some(since) if since > now => Ok(()),
+                    _ => Err("since has not advanced"),
+                }
+            }
+        })
+        .await
         .unwrap();
+}
+
+// Tests that a query whose timestamp is not readable yet waits for it through the controller,
+// and reports how long it waited.
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_timestamp_wait() {
+    let server = test_util::TestHarness::default()
+        .with_system_parameter_default("timestamp_wait_sample_rate".into(), "1".into())
+        .start()
+        .await;
+    let client = server.connect().await.unwrap();
+
     client
         .batch_execute(
-            "CREATE VIEW dev_warm.stg_data_days AS
-                SELECT *
-                FROM dev.mock_data_days
-                WHERE
-                    mz_now() <= date_trunc('year', ts + CAST('1 year' AS interval)) AND
-                    ts + CAST('7 days' AS interval) - CAST('1 month' AS interval) < mz_now();",
+            "CREATE TABLE t (a INT);
+            INSERT INTO t VALUES (1);",
         )
         .await
         .unwrap();
-    client
-        .batch_execute(
-            "CREATE VIEW dev_warm.count_by_day AS
-                SELECT
-                    date_trunc('day', ts) AS \"day\",
-                    count(*) AS cnt
-                FROM dev_warm.stg_data_days
-                GROUP BY 1
-                HAVING
-                    NOT (mz_now() <= date_trunc('day', ts) + CAST('7 days' AS interval)) AND
-                    mz_now() <= date_trunc('year', date_trunc('day', ts) + CAST('1 year' AS interval));",
-        )
+    let now: String = client
+        .query_one("SELECT mz_now()::text", &[])
+        .await
+        .unwrap()
+        .get(0);
+    let as_of = now.parse::<u64>().unwrap() + 2_000;
+    let rows = client
+        .query(&format!("SELECT a FROM t AS OF {as_of}"), &[])
         .await
         .unwrap();
+    assert_eq!(rows.len(), 1);
+
+    // The wait finishes once the frontier of `t` advances beyond the timestamp, about two
+    // seconds later.
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry(|_| {
+            let metric = server
+                .metrics_registry
+                .gather()
+                .into_iter()
+                .find(|m| m.get_name() == "mz_timestamp_wait_seconds")
+                .ok_or("no waits observed")?;
+            let waits = metric.get_metric()[0].get_histogram();
+            if waits.get_sample_count() > 0 && waits.get_sample_sum() >= 1.0 {
+                Ok(())
+            } else {
+                Err("wait not observed")
+            }
+        })
+        .unwrap();
+}
+
+// Tests that the waits of queries that are not sampled are not timed.
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_timestamp_wait_unsampled() {
+    let server = test_util::TestHarness::default()
+        .with_system_parameter_default("timestamp_wait_sample_rate".into(), "0".into())
+        .start()
+        .await;
+    let client = server.connect().await.unwrap();
+
     client
         .batch_execute(
-            "CREATE VIEW dev_fy2023.stg_data_days AS
-                SELECT *
-                FROM dev.mock_data_days
-                WHERE
-                    CAST('2023-01-01' AS timestamp) <= ts AND
-                    ts - CAST('1 month' AS interval) < CAST('2024-01-01' AS timestamp) AND
-                    ts - CAST('0 month' AS interval) < CAST('2025-01-01' AS timestamp);",
+            "CREATE TABLE t (a INT);
+            INSERT INTO t VALUES (1);",
         )
         .await
         .unwrap();
-    client
-        .batch_execute(
-            "CREATE VIEW dev_fy2023.count_by_day AS
-                SELECT
-                    date_trunc('day', ts) AS \"day\",
-                    count(*) AS cnt
-                FROM dev_fy2023.stg_data_days
-                GROUP BY 1
-                HAVING
-                    NOT (CAST('2024-01-01' AS timestamp) <= date_trunc('day', ts)) AND
-                    CAST('2023-01-01' AS timestamp) <= date_trunc('day', ts);",
-        )
+    let now: String = client
+        .query_one("SELECT mz_now()::text", &[])
+        .await
+        .unwrap()
+        .get(0);
+    let as_of = now.parse::<u64>().unwrap() + 2_000;
+    let rows = client
+        .query(&format!("SELECT a FROM t AS OF {as_of}"), &[])
         .await
         .unwrap();
+    assert_eq!(rows.len(), 1);
 
-    let timestamp = get_explain_timestamp(
-        "(SELECT *, 'fy2023' AS origin FROM dev_fy2023.count_by_day
-        UNION ALL
-        SELECT *, 'warm' AS origin FROM dev_warm.count_by_day)",
-        &client,
-    )
-    .await;
-    assert!(
-        timestamp < EpochMillis::MAX,
-        "expected temporal query to have timestamps less than max timestamp of {}, instead found {}",
-        EpochMillis::MAX,
-        timestamp
-    )
+    let waits = server
+        .metrics_registry
+        .gather()
+        .into_iter()
+        .find(|m| m.get_name() == "mz_timestamp_wait_seconds")
+        .map(|m| m.get_metric()[0].get_histogram().get_sample_count())
+        .unwrap_or(0);
+    assert_eq!(waits, 0);
 }
 
+// Tests that a client disconnecting while its query waits for its timestamp to become readable
+// cancels the wait, rather than leaving it installed until the timestamp becomes readable.
 #[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
 #[cfg_attr(miri, ignore)] // too slow
-async fn test_constant_materialized_view() {
-    let server = test_util::TestHarness::default().start().await;
+async fn test_timestamp_wait_cancelled_on_disconnect() {
+    let server = test_util::TestHarness::default()
+        .with_system_parameter_default("timestamp_wait_sample_rate".into(), "1".into())
+        .start()
+        .await;
     let client = server.connect().await.unwrap();
-    client
-        .batch_execute("CREATE CLUSTER cold (SIZE '1', REPLICATION FACTOR = 0);")
-        .await
-        .unwrap();
-    // The materialized view is created in a cluster with 0 replicas, so it's upper will be stuck
-    // at 0.
+    let watch_set_tokens = || {
+        server
+            .metrics_registry
+            .gather()
+            .into_iter()
+            .find(|m| m.get_name() == "mz_controller_watch_set_tokens")
+            .map(|m| m.get_metric()[0].get_gauge().get_value())
+            .unwrap_or(0.0)
+    };
+    let wait_for_tokens = |expected: f64| {
+        Retry::default()
+            .max_duration(Duration::from_secs(30))
+            .retry(|_| {
+                let tokens = watch_set_tokens();
+                if tokens == expected {
+                    Ok(())
+                } else {
+                    Err(format!("{tokens} watch set tokens, expected {expected}"))
+                }
+            })
+            .unwrap();
+    };
+
     client
         .batch_execute(
-            "CREATE MATERIALIZED VIEW mv IN CLUSTER cold AS SELECT generate_series(1, 100);",
+            "CREATE TABLE t (a INT);
+            INSERT INTO t VALUES (1);",
         )
         .await
         .unwrap();
-
-    let timestamp_determination = get_explain_timestamp_determination("mv", &client)
+    let now: String = client
+        .query_one("SELECT mz_now()::text", &[])
         .await
-        .unwrap();
+        .unwrap()
+        .get(0);
+    let now = now.parse::<u64>().unwrap();
 
-    assert!(
-        !timestamp_determination.respond_immediately,
-        "upper is stuck at 0 so the query cannot respond immediately"
-    );
-    match timestamp_determination.determination.timestamp_context {
-        TimestampContext::TimelineTimestamp { chosen_ts, .. } => {
-            assert_ne!(Timestamp::MAX, chosen_ts)
-        }
-        TimestampContext::NoTimestamp => {
-            panic!("queries against materialized views always require a timestamp")
-        }
-    }
+    // A wait that resolves releases its watch set.
+    let tokens = watch_set_tokens();
+    client
+        .query(&format!("SELECT a FROM t AS OF {}", now + 2_000), &[])
+        .await
+        .unwrap();
+    wait_for_tokens(tokens);
+
+    // A query at a timestamp an hour ahead waits for that long.
+    let waiter = server.connect().await.unwrap();
+    let query = format!("SELECT a FROM t AS OF {}", now + 60 * 60 * 1_000);
+    let blocked = task::spawn(|| "blocked_query", async move {
+        let _ = waiter.query(&query, &[]).await;
+    });
+    wait_for_tokens(tokens + 1.0);
+
+    // Disconnecting cancels the wait.
+    blocked.abort_and_wait().await;
+    wait_for_tokens(tokens);
+    client.query_one("SELECT 1", &[]).await.unwrap();
 }
 
+// Tests that a SET TRANSACTION that is rejected because the transaction already read keeps the
+// timestamp the transaction pinned with its first read, along with its read holds, until the
+// transaction ends.
 #[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
 #[cfg_attr(miri, ignore)] // too slow
-async fn test_explain_timestamp_blocking() {
+async fn test_rejected_set_transaction_keeps_timestamp() {
     let server = test_util::TestHarness::default().start().await;
-    server
-        .enable_feature_flags(&["enable_refresh_every_mvs"])
-        .await;
     let client = server.connect().await.unwrap();
-    // This test will break in the year 30,000 after Jan 1st. When that happens, increase the year
-    // to fix the test.
+    let observer = server.connect().await.unwrap();
+
     client
         .batch_execute(
-            "CREATE MATERIALIZED VIEW const_mv WITH (REFRESH AT '30000-01-01 23:59') AS SELECT 2;",
+            "CREATE TABLE t (a INT);
+            INSERT INTO t VALUES (1);",
         )
         .await
         .unwrap();
+    let id: String = client
+        .query_one("SELECT id FROM mz_tables WHERE name = 't'", &[])
+        .await
+        .unwrap()
+        .get(0);
+    let since = || async {
+        let since: Option<String> = observer
+            .query_one(
+                "SELECT read_frontier::text FROM mz_internal.mz_frontiers WHERE object_id = $1",
+                &[&id],
+            )
+            .await
+            .unwrap()
+            .get(0);
+        since.map(|since| since.parse::<u64>().unwrap())
+    };
 
-    let mv_timestamp = get_explain_timestamp("const_mv", &client).await;
+    client.batch_execute("BEGIN").await.unwrap();
+    client.query("SELECT * FROM t", &[]).await.unwrap();
+    let pinned: String = client
+        .query_one("SELECT mz_now()::text", &[])
+        .await
+        .unwrap()
+        .get(0);
+    let pinned = pinned.parse::<u64>().unwrap();
 
-    let row = client
-        .query_one("SELECT mz_now()::text;", &[])
+    let err = client
+        .batch_execute("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE")
+        .await
+        .unwrap_err();
+    assert_contains!(
+        err.to_string(),
+        "SET TRANSACTION ISOLATION LEVEL must be called before any query"
+    );
+
+    // While the failed transaction is open, its read holds keep `t` readable at its timestamp.
+    for _ in 0..5 {
+        observer
+            .batch_execute("INSERT INTO t VALUES (2)")
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let since = since().await.unwrap();
+        assert!(since <= pinned, "{since} > {pinned}");
+    }
+
+    // Ending the transaction releases them.
+    client.batch_execute("ROLLBACK").await.unwrap();
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry_async(|_| async {
+            observer
+                .batch_execute("INSERT INTO t VALUES (3)")
+                .await
+                .unwrap();
+            match since().await {
+                Some(since) if since > pinned => Ok(()),
+                _ => Err("since has not advanced"),
+            }
+        })
         .await
         .unwrap();
-    let mz_now_ts_raw: String = row.get(0);
-    let mz_now_timestamp: EpochMillis = mz_now_ts_raw.parse().unwrap();
+}
 
+// Tests that the oracle read timestamp reported by SHOW TIMESTAMP does not go backwards.
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_show_timestamp() {
+    let server = test_util::TestHarness::default().start().await;
+    let client = server.connect().await.unwrap();
+
+    let oracle_read_ts = || async {
+        let message = client
+            .simple_query("SHOW TIMESTAMP")
+            .await
+            .unwrap()
+            .into_first();
+        match message {
+            tokio_postgres::SimpleQueryMessage::Row(row) => {
+                assert!(row.get("session_wall_time").is_some());
+                row.get("oracle_read_ts").unwrap().parse::<u64>().unwrap()
+            }
+            _ => panic!("unexpected simple query message"),
+        }
+    };
+
+    let first = oracle_read_ts().await;
+    let second = oracle_read_ts().await;
     assert!(
-        mv_timestamp > mz_now_timestamp,
-        "read against mv at timestamp {mv_timestamp} should be in the future compared to now, {mz_now_timestamp}"
+        first <= second,
+        "oracle read timestamp went backwards: {first} > {second}"
     );
 }
 
+// Tests that canceling a statement that is blocked on a slow timestamp oracle retires it promptly,
+// and doesn't leak the read holds of its transaction.
 #[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
 #[cfg_attr(miri, ignore)] // too slow
-async fn test_cancel_linearize_reads() {
+async fn test_cancel_slow_oracle_read() {
     let server = test_util::TestHarness::default().start().await;
-    server
-        .enable_feature_flags(&["enable_refresh_every_mvs"])
-        .await;
     let client = server.connect().await.unwrap();
     let client_cancel = client.cancel_token();
-
-    // This test will break in the year 30,000 after Jan 1st. When that happens, increase the year
-    // to fix the test.
     client
-        .batch_execute(
-            "CREATE MATERIALIZED VIEW const_mv WITH (REFRESH AT '30000-01-01 23:59') AS SELECT 2;",
-        )
+        .batch_execute("CREATE TABLE t (a INT)")
         .await
         .unwrap();
 
-    let (tx, rx) = oneshot::channel();
+    // The transaction holds back the since of `t` to the timestamp it reads at.
+    client.batch_execute("BEGIN").await.unwrap();
+    let read_ts: String = client
+        .query_one("SELECT mz_now()::text FROM (SELECT count(*) FROM t)", &[])
+        .await
+        .unwrap()
+        .get(0);
+    let read_ts: u64 = read_ts.parse().unwrap();
 
-    task::spawn(|| "read task", async move {
-        // The since of const_mv will be in the year 30,000, requiring us to select a timestamp in
-        // the year 30,000, requiring us to linearize the read waiting for the timestamp oracle to
-        // catch up to the year 30,000.
-        let res = client.query_one("SELECT * FROM const_mv;", &[]).await;
-        let err = res.unwrap_err();
+    // Oracle reads now take an hour.
+    fail::cfg("slow_oracle_read_ts", "return(3600000)").unwrap();
+    let handle = task::spawn(|| "slow oracle read task", async move {
+        let start = Instant::now();
+        let err = client.simple_query("SHOW TIMESTAMP").await.unwrap_err();
         assert_eq!(err.unwrap_db_error().code(), &SqlState::QUERY_CANCELED);
-        tx.send(()).unwrap();
+        (client, start.elapsed())
     });
 
     // There's a race for when we issue the cancel, so we need to retry. We want to issue the
-    // cancel after the async SELECT.
-    let (_, res) = Retry::default()
+    // cancel after the SHOW TIMESTAMP.
+    let ((_, handle), res) = Retry::default()
         .max_duration(Duration::from_secs(30))
         .retry_async_with_state(
-            (client_cancel, rx),
-            |_, (client_cancel, mut rx)| async move {
+            (client_cancel, handle),
+            |_, (client_cancel, handle)| async move {
                 client_cancel.cancel_query(postgres::NoTls).await.unwrap();
-                let res = rx.try_recv();
-                ((client_cancel, rx), res)
+                let res = if handle.is_finished() {
+                    Ok(())
+                } else {
+                    Err("task unfinished".to_string())
+                };
+                ((client_cancel, handle), res)
             },
         )
         .await;
     res.unwrap();
+    fail::remove("slow_oracle_read_ts");
+
+    let (client, elapsed) = handle.await.unwrap();
+    assert!(
+        elapsed < Duration::from_secs(30),
+        "canceled oracle read took {elapsed:?}"
+    );
+
+    // Ending the canceled transaction releases its read holds, so the since of `t` advances.
+    client.batch_execute("ROLLBACK").await.unwrap();
+    Retry::default()
+        .max_duration(Duration::from_secs(30))
+        .retry_async(|_| async {
+            let ts = get_explain_timestamp_determination("t", &client).await?;
+            let since = ts.sources.into_element().read_frontier.into_element();
+            if since <= Timestamp::from(read_ts) {
+                anyhow::bail!("since {since} of t is held back to {read_ts}");
+            }
+            Ok(())
+        })
+        .await
+        .unwrap();
+}
+
+// Tests that `DISCARD ALL` between two clients of a connection pooler, which share a connection,
+// doesn't leak the state of the first client to the second.
+#[mz_ore::test]
+#[cfg_attr(miri, ignore)] // too slow
+fn test_discard_all_between_pooled_clients() {
+    let server = test_util::TestHarness::default().start_blocking();
+    server.enable_feature_flags(&["enable_session_timelines"]);
+    let mut conn = server.connect(postgres::NoTls).unwrap();
+    conn.batch_execute("CREATE TABLE t (a INT)").unwrap();
+
+    let session_oracle_read_ts = |conn: &mut postgres::Client| {
+        let row = conn
+            .query_one("EXPLAIN TIMESTAMP AS JSON FOR SELECT * FROM t", &[])
+            .unwrap();
+        let explain: String = row.get(0);
+        let explain: TimestampExplanation<Timestamp> = serde_json::from_str(&explain).unwrap();
+        explain.determination.session_oracle_read_ts
+    };
+
+    // The first client writes under strong session serializable, which advances its session
+    // timestamp oracle, prepares a statement, and sets a variable.
+    conn.batch_execute("SET transaction_isolation = 'strong session serializable'")
+        .unwrap();
+    conn.batch_execute("INSERT INTO t VALUES (1)").unwrap();
+    conn.batch_execute("PREPARE p AS SELECT 1").unwrap();
+    conn.batch_execute("SET extra_float_digits = 1").unwrap();
+    assert!(session_oracle_read_ts(&mut conn).is_some());
+
+    // `DISCARD ALL` is rejected in a transaction block, which stays intact.
+    conn.batch_execute("BEGIN").unwrap();
+    let err = conn.batch_execute("DISCARD ALL").unwrap_db_error();
+    assert_eq!(err.code(), &SqlState::ACTIVE_SQL_TRANSACTION);
+    conn.batch_execute("ROLLBACK").unwrap();
+    conn.batch_execute("EXECUTE p").unwrap();
+
+    // The pooler resets the connection before handing it to the second client.
+    conn.batch_execute("DISCARD ALL").unwrap();
+
+    let show = |conn: &mut postgres::Client, name: &str| -> String {
+        conn.query_one(&format!("SHOW {name}"), &[]).unwrap().get(0)
+    };
+    assert_eq!(
+        show(&mut conn, "transaction_isolation"),
+        "strict serializable"
+    );
+    assert_eq!(show(&mut conn, "extra_float_digits"), "3");
+    let err = conn.batch_execute("EXECUTE p").unwrap_db_error();
+    assert_eq!(err.code(), &SqlState::UNDEFINED_PSTATEMENT);
+
+    // The second client starts out with a fresh session timestamp oracle.
+    conn.batch_execute("SET transaction_isolation = 'strong session serializable'")
+        .unwrap();
+    assert_eq!(session_oracle_read_ts(&mut conn), None);
 }
 
+/// Test that a `READ ONLY` transaction pins the timestamp of its first read, so that only that
+/// read reads the timestamp oracle.
+#[mz_ore::test]
+fn test_read_only_transaction_pins_timestamp() {
+    let server = test_util::TestHarness::default().start_blocking();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    client.batch_execute("CREATE TABLE t (a int)").unwrap();
+    client.batch_execute("INSERT INTO t VALUES (1)").unwrap();
+
+    let oracle_reads = || -> f64 {
+        server
+            .metrics_registry
+            .gather()
+            .into_iter()
+            .find(|m| m.get_name() == "mz_peek_oracle_reads_total")
+            .map(|m| m.get_metric()[0].get_counter().get_value())
+            .unwrap_or(0.0)
+    };
+    let run_reads = |client: &mut postgres::Client, begin: &str| -> f64 {
+        let before = oracle_reads();
+        client.batch_execute(begin).unwrap();
+        for _ in 0..3 {
+            let count: i64 = client
+                .query_one("SELECT count(*) FROM t", &[])
+                .unwrap()
+                .get(0);
+            assert_eq!(count, 1);
+        }
+        client.batch_execute("COMMIT").unwrap();
+        oracle_reads() - before
+    };
+
+    // Every read of a read-write transaction reads the oracle.
+    assert_eq!(run_reads(&mut client, "BEGIN"), 3.0);
+    // Only the first read of a read-only transaction does.
+    assert_eq!(run_reads(&mut client, "BEGIN READ ONLY"), 1.0);
+}
+
+// Tests that `mz_source_statuses` and `mz_sink_statuses` only report an object as degraded if
+// its healthiest replica is. Sources and sinks can't run on more than one replica yet, so this
+// runs the definitions of the views over tables of status history with conflicting replicas.
 #[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
 #[cfg_attr(miri, ignore)] // too slow
-async fn test_cancel_linearize_read_then_writes() {
+async fn test_status_roll_up_across_replicas() {
     let server = test_util::TestHarness::default().start().await;
-    server
-        .enable_feature_flags(&["enable_refresh_every_mvs"])
-        .await;
     let client = server.connect().await.unwrap();
-    let client_cancel = client.cancel_token();
 
+    let history_columns = |id: &str| {
+        format!(
+            "occurred_at timestamptz, {id} text, status text, error text, details jsonb, \
+            owner text, comment text, cluster_id text, replica_id text"
+        )
+    };
     client
-        .batch_execute("CREATE TABLE t (a INT);")
+        .batch_execute(&format!(
+            "CREATE CLUSTER c REPLICAS (r1 (SIZE '1'), r2 (SIZE '1'));
+            CREATE SOURCE counter FROM LOAD GENERATOR COUNTER;
+            CREATE TABLE source_status_history ({});
+            CREATE TABLE sink_status_history ({});
+            CREATE TABLE sinks (id text, name text, type text);
+            INSERT INTO sinks VALUES ('u1000', 'sink', 'kafka');",
+            history_columns("source_id"),
+            history_columns("sink_id"),
+        ))
         .await
         .unwrap();
-
-    // This test will break in the year 30,000 after Jan 1st. When that happens, increase the year
-    // to fix the test.
+    let source_statuses = MZ_SOURCE_STATUSES
+        .sql
+        .trim_end()
+        .trim_end_matches(';')
+        .replace(
+            "mz_internal.mz_source_status_history",
+            "source_status_history",
+        );
+    let sink_statuses = MZ_SINK_STATUSES
+        .sql
+        .replace("mz_internal.mz_sink_status_history", "sink_status_history")
+        .replace("mz_sinks", "sinks");
     client
-        .batch_execute(
-            "CREATE MATERIALIZED VIEW const_mv WITH (REFRESH AT '30000-01-01 23:59') AS SELECT 2;",
-        )
+        .batch_execute(&format!(
+            "CREATE VIEW source_statuses AS {source_statuses};
+            CREATE VIEW sink_statuses AS {sink_statuses};"
+        ))
         .await
         .unwrap();
 
-    let handle = task::spawn(|| "read-then-write task", async move {
-        // The since of const_mv will be in the year 30,000, requiring us to select a timestamp in
-        // the year 30,000, requiring us to linearize the read waiting for the timestamp oracle to
-        // catch up to the year 30,000.
-        let res = client
-            .execute("INSERT INTO t SELECT * FROM const_mv;", &[])
-            .await;
-        let err = res.unwrap_err();
-        assert_eq!(err.unwrap_db_error().code(), &SqlState::QUERY_CANCELED);
-    });
+    let source_id: String = client
+        .query_one("SELECT id FROM mz_sources WHERE name = 'counter'", &[])
+        .await
+        .unwrap()
+        .get(0);
+    let replica_ids: Vec<(String, String)> = client
+        .query(
+            "SELECT r.cluster_id, r.id
+            FROM mz_cluster_replicas AS r JOIN mz_clusters AS c ON r.cluster_id = c.id
+            WHERE c.name = 'c'
+            ORDER BY r.name",
+            &[],
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| (row.get(0), row.get(1)))
+        .collect();
+    let [(cluster_id, r1), (_, r2)] = &replica_ids[..] else {
+        panic!("unexpected replicas: {replica_ids:?}");
+    };
+    let (r1, r2) = (r1.as_str(), r2.as_str());
+
+    let cases = [
+        // A replica that stalls doesn't degrade an object that another replica keeps running.
+        (1, Some(r1), "running", "running"),
+        (1, Some(r2), "running", "running"),
+        (2, Some(r1), "stalled", "running"),
+        // Once all replicas stall, so does the object.
+        (3, Some(r2), "stalled", "stalled"),
+        // Replicas that no longer exist don't count.
+        (4, Some("u1000"), "running", "stalled"),
+        // A status recorded by the controller applies until a replica reports again.
+        (5, None, "starting", "starting"),
+        (6, Some(r1), "stalled", "stalled"),
+        (7, Some(r2), "running", "running"),
+    ];
+    for (second, replica_id, status, expected) in cases {
+        for (history, statuses, id) in [
+            (
+                "source_status_history",
+                "source_statuses",
+                source_id.as_str(),
+            ),
+            ("sink_status_history", "sink_statuses", "u1000"),
+        ] {
+            let cluster_id = replica_id.map(|_| cluster_id.as_str());
+            client
+                .execute(
+                    &format!(
+                        "INSERT INTO {history}
+                        VALUES ('2024-01-01 00:00:0{second}+00', $1, $2, NULL, NULL, NULL, NULL, $3, $4)"
+                    ),
+                    &[&id, &status, &cluster_id, &replica_id],
+                )
+                .await
+                .unwrap();
+            let rolled_up: String = client
+                .query_one(
+                    &format!("SELECT status FROM {statuses} WHERE id = $1"),
+                    &[&id],
+                )
+                .await
+                .unwrap()
+                .get(0);
+            assert_eq!(
+                rolled_up, expected,
+                "{statuses} after {status} on {replica_id:?} at second {second}"
+            );
+        }
+    }
+}
 
-    // Sleep for a bit to try and get the `INSERT INTO SELECT` to run. We may get some false
-    // positives, but we won't get false negatives.
-    tokio::time::sleep(Duration::from_secs(1)).await;
-    assert!(
-        !handle.is_finished(),
-        "INSERT INTO SELECT should be linearized and wait for the year 30,000"
-    );
+// Tests that source statistics are written as the storage controller forwards them, rather than
+// when the coordinator next reconciles them, and that they are retracted when the source is
+// dropped.
+#[mz_ore::test(tokio::test(flavor = "multi_thread", worker_threads = 1))]
+#[cfg_attr(miri, ignore)] // too slow
+async fn test_storage_statistics_pushed() {
+    let server = test_util::TestHarness::default()
+        .with_system_parameter_default("storage_statistics_interval".into(), "1s".into())
+        .start()
+        .await;
+    let client = server.connect().await.unwrap();
+    client
+        .batch_execute("CREATE SOURCE counter FROM LOAD GENERATOR COUNTER (TICK INTERVAL '100ms')")
+        .await
+        .unwrap();
+    let id: String = client
+        .query_one("SELECT id FROM mz_sources WHERE name = 'counter'", &[])
+        .await
+        .unwrap()
+        .get(0);
 
-    // There's a race for when we issue the cancel, so we need to retry. We want to issue the
-    // cancel after the async SELECT.
-    let ((_, handle), res) = Retry::default()
-        .max_duration(Duration::from_secs(30))
-        .retry_async_with_state(
-            (client_cancel, handle),
-            |_, (client_cancel, handle)| async move {
-                client_cancel.cancel_query(postgres::NoTls).await.unwrap();
-                let res = if handle.is_finished() {
-                    Ok(())
+    // Returns the number of statistics rows of the source, the number of workers they are of,
+    // and the most messages a worker received.
+    let statistics = |id: String| {
+        let client = &client;
+        async move {
+            let row = client
+                .query_one(
+                    "SELECT
+                        count(*)::int8,
+                        count(DISTINCT worker_id)::int8,
+                        COALESCE(max(messages_received), 0)::int8
+                    FROM mz_internal.mz_source_statistics_per_worker
+                    WHERE id = $1",
+                    &[&id],
+                )
+                .await
+                .unwrap();
+            (
+                row.get::<_, i64>(0),
+                row.get::<_, i64>(1),
+                row.get::<_, i64>(2),
+            )
+        }
+    };
+
+    // Statistics arrive well before the coordinator reconciles them, and keep being updated.
+    // Every worker only ever has a single row, as the previous statistics are retracted.
+    let mut seen_messages = 0;
+    for _ in 0..2 {
+        seen_messages = Retry::default()
+            .max_duration(Duration::from_secs(60))
+            .retry_async(|_| async {
+                let (rows, workers, messages) = statistics(id.clone()).await;
+                assert_eq!(rows, workers);
+                if rows > 0 && messages > seen_messages {
+                    Ok(messages)
                 } else {
-                    Err("thread unfinished".to_string())
-                };
-                ((client_cancel, handle), res)
-            },
-        )
-        .await;
-    res.unwrap();
-    handle.await.unwrap();
+                    Err(format!("no new statistics: {messages} messages received"))
+                }
+            })
+            .await
+            .unwrap();
+    }
+
+    client.batch_execute("DROP SOURCE counter").await.unwrap();
+    Retry::default()
+        .max_duration(Duration::from_secs(60))
+        .retry_async(|_| async {
+            match statistics(id.clone()).await {
+                (0, _, _) => Ok(()),
+                (rows, _, _) => Err(format!("{rows} statistics rows left")),
+            }
+        })
+        .await
+        .unwrap();
 }
@@ -39,8 +39,8 @@ use mz_cloud_resources::crd::vpc_endpoint::v1::VpcEndpoint;
 use mz_cloud_resources::AwsExternalIdPrefix;
 use mz_orchestrator::{
     scheduling_config::*, DiskLimit, LabelSelectionLogic, LabelSelector as MzLabelSelector,
-    NamespacedOrchestrator, NotReadyReason, Orchestrator, Service, ServiceConfig, ServiceEvent,
-    ServiceProcessMetrics, ServiceStatus,
+    NamespacedOrchestrator, NotReadyReason, Orchestrator, ResourceLimits, Service, ServiceConfig,
+    ServiceEvent, ServiceProcessMetrics, ServiceStatus,
 };
 use serde::Deserialize;
 use sha2::{Digest, Sha256};
@@ -668,10 +668,15 @@ impl NamespacedOrchestrator for NamespacedKubernetesOrchestrator {
                 _ => None,
             };
 
+            // The usage above is corrected against the disk limit, so the limit is the capacity
+            // the usage should be compared with.
+            let disk_capacity_bytes = disk_usage.and(disk_limit.map(|DiskLimit(limit)| limit.0));
+
             ServiceProcessMetrics {
                 cpu_nano_cores: cpu,
                 memory_bytes: memory,
                 disk_usage_bytes: disk_usage,
+                disk_capacity_bytes,
             }
         }
         let ret = futures::future::join_all(
@@ -1304,7 +1309,16 @@ impl NamespacedOrchestrator for NamespacedKubernetesOrchestrator {
                 disk_limit,
             },
         );
-        Ok(Box::new(KubernetesService { hosts, ports }))
+        let limits = ResourceLimits {
+            memory_limit,
+            cpu_limit,
+            disk_limit: if disk { disk_limit } else { None },
+        };
+        Ok(Box::new(KubernetesService {
+            hosts,
+            ports,
+            limits,
+        }))
     }
 
     /// Drops the identified service, if it exists.
@@ -1443,6 +1457,7 @@ impl NamespacedOrchestrator for NamespacedKubernetesOrchestrator {
 struct KubernetesService {
     hosts: Vec<String>,
     ports: BTreeMap<String, u16>,
+    limits: ResourceLimits,
 }
 
 impl Service for KubernetesService {
@@ -1453,4 +1468,8 @@ impl Service for KubernetesService {
             .map(|host| format!("{host}:{port}"))
             .collect()
     }
+
+    fn resource_limits(&self) -> ResourceLimits {
+        self.limits
+    }
 }
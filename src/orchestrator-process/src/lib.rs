@@ -32,8 +32,8 @@ use itertools::Itertools;
 use libc::{SIGABRT, SIGBUS, SIGILL, SIGSEGV, SIGTRAP};
 use maplit::btreemap;
 use mz_orchestrator::{
-    CpuLimit, MemoryLimit, NamespacedOrchestrator, Orchestrator, Service, ServiceConfig,
-    ServiceEvent, ServiceProcessMetrics, ServiceStatus,
+    CpuLimit, MemoryLimit, NamespacedOrchestrator, Orchestrator, ResourceLimits, Service,
+    ServiceConfig, ServiceEvent, ServiceProcessMetrics, ServiceStatus,
 };
 use mz_ore::cast::{CastFrom, ReinterpretCast, TryCastFrom};
 use mz_ore::error::ErrorExt;
@@ -342,6 +342,7 @@ impl NamespacedOrchestrator for NamespacedProcessOrchestrator {
                 memory_bytes,
                 // Process orchestrator does not support this right now.
                 disk_usage_bytes: None,
+                disk_capacity_bytes: None,
             });
         }
         Ok(metrics)
@@ -451,7 +452,21 @@ impl NamespacedOrchestrator for NamespacedProcessOrchestrator {
 
         self.maybe_write_prometheus_service_discovery_file().await;
 
-        Ok(Box::new(ProcessService { run_dir, scale }))
+        let limits = match self.launch_spec {
+            // Processes that are executed directly are not limited.
+            LaunchSpec::Direct => ResourceLimits::default(),
+            LaunchSpec::Systemd => ResourceLimits {
+                memory_limit,
+                cpu_limit,
+                // Scratch disk space is not limited.
+                disk_limit: None,
+            },
+        };
+        Ok(Box::new(ProcessService {
+            run_dir,
+            scale,
+            limits,
+        }))
     }
 
     async fn drop_service(&self, id: &str) -> Result<(), anyhow::Error> {
@@ -925,6 +940,7 @@ struct AddressedTcpListener {
 struct ProcessService {
     run_dir: PathBuf,
     scale: u16,
+    limits: ResourceLimits,
 }
 
 impl Service for ProcessService {
@@ -933,4 +949,8 @@ impl Service for ProcessService {
             .map(|i| socket_path(&self.run_dir, port, i.into()))
             .collect()
     }
+
+    fn resource_limits(&self) -> ResourceLimits {
+        self.limits
+    }
 }
@@ -17,7 +17,7 @@ use bytesize::ByteSize;
 use chrono::{DateTime, Utc};
 use derivative::Derivative;
 use futures_core::stream::BoxStream;
-use mz_ore::cast::CastFrom;
+use mz_ore::cast::{CastFrom, CastLossy};
 use serde::de::Unexpected;
 use serde::{Deserialize, Deserializer, Serialize};
 
@@ -99,6 +99,12 @@ pub struct ServiceEvent {
 #[derive(Debug, Clone, Copy, Serialize, Eq, PartialEq)]
 pub enum NotReadyReason {
     OomKilled,
+    /// The service kept restarting and is held back from being scheduled again for a while.
+    /// `oom_killed` reports whether the last observed failure was the service running out of
+    /// memory.
+    CrashLooping {
+        oom_killed: bool,
+    },
 }
 
 /// Describes the status of an orchestrated service.
@@ -129,6 +135,14 @@ pub trait Service: fmt::Debug + Send + Sync {
     ///
     /// Panics if `port` does not name a valid port.
     fn addresses(&self, port: &str) -> Vec<String>;
+
+    /// Returns the resource limits the orchestrator applied to each of the service's processes.
+    ///
+    /// Orchestrators may not enforce all the limits requested in the [`ServiceConfig`], so the
+    /// limits can differ from the requested ones. By default, no limits are reported.
+    fn resource_limits(&self) -> ResourceLimits {
+        ResourceLimits::default()
+    }
 }
 
 #[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
@@ -136,6 +150,39 @@ pub struct ServiceProcessMetrics {
     pub cpu_nano_cores: Option<u64>,
     pub memory_bytes: Option<u64>,
     pub disk_usage_bytes: Option<u64>,
+    pub disk_capacity_bytes: Option<u64>,
+}
+
+/// The resource limits that apply to each process of a service.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ResourceLimits {
+    /// The limit on the memory of each process, if any.
+    pub memory_limit: Option<MemoryLimit>,
+    /// The limit on the CPU of each process, if any.
+    pub cpu_limit: Option<CpuLimit>,
+    /// The limit on the scratch disk space of each process, if any.
+    pub disk_limit: Option<DiskLimit>,
+}
+
+impl ResourceLimits {
+    /// Returns the fraction of the CPU limit that a process with the given `metrics` uses, if
+    /// both are known.
+    pub fn cpu_utilization(&self, metrics: &ServiceProcessMetrics) -> Option<f64> {
+        let limit = self.cpu_limit?.as_nanocpus();
+        utilization(metrics.cpu_nano_cores?, limit)
+    }
+
+    /// Returns the fraction of the memory limit that a process with the given `metrics` uses,
+    /// if both are known.
+    pub fn memory_utilization(&self, metrics: &ServiceProcessMetrics) -> Option<f64> {
+        let limit = self.memory_limit?.0.as_u64();
+        utilization(metrics.memory_bytes?, limit)
+    }
+}
+
+/// Returns `usage` as a fraction of `limit`, unless the limit is zero.
+fn utilization(usage: u64, limit: u64) -> Option<f64> {
+    (limit > 0).then(|| f64::cast_lossy(usage) / f64::cast_lossy(limit))
 }
 
 /// A simple language for describing assertions about a label's existence and value.
@@ -386,6 +433,8 @@ impl Serialize for DiskLimit {
 /// Configuration for how services are scheduled. These may be ignored by orchestrator
 /// implementations.
 pub mod scheduling_config {
+    use std::time::Duration;
+
     #[derive(Debug, Clone)]
     pub struct ServiceTopologySpreadConfig {
         /// If `true`, enable spread for replicated services.
@@ -441,6 +490,34 @@ pub mod scheduling_config {
         ///
         /// Defaults to `false`.
         pub always_use_disk: bool,
+        /// Configuration for holding back crash-looping services.
+        pub crash_loop: ServiceCrashLoopConfig,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct ServiceCrashLoopConfig {
+        /// The number of restarts within `window` above which a service is considered
+        /// crash-looping.
+        ///
+        /// Defaults to `5`.
+        pub max_restarts: usize,
+        /// The period over which restarts are counted.
+        ///
+        /// Defaults to 10 minutes.
+        pub window: Duration,
+        /// How long a crash-looping service is held back before it is scheduled again. Doubles
+        /// each time the service is found crash-looping again.
+        ///
+        /// Defaults to 1 minute.
+        pub initial_backoff: Duration,
+        /// The maximum time a crash-looping service is held back.
+        ///
+        /// Defaults to 1 hour.
+        pub max_backoff: Duration,
+        /// How long a service must stay ready for its backoff to be reset.
+        ///
+        /// Defaults to 10 minutes.
+        pub healthy_period: Duration,
     }
 
     pub const DEFAULT_POD_AZ_AFFINITY_WEIGHT: Option<i32> = Some(100);
@@ -456,6 +533,12 @@ pub mod scheduling_config {
     pub const DEFAULT_SOFTEN_AZ_AFFINITY_WEIGHT: i32 = 100;
     pub const DEFAULT_ALWAYS_USE_DISK: bool = false;
 
+    pub const DEFAULT_CRASH_LOOP_MAX_RESTARTS: usize = 5;
+    pub const DEFAULT_CRASH_LOOP_WINDOW: Duration = Duration::from_secs(10 * 60);
+    pub const DEFAULT_CRASH_LOOP_INITIAL_BACKOFF: Duration = Duration::from_secs(60);
+    pub const DEFAULT_CRASH_LOOP_MAX_BACKOFF: Duration = Duration::from_secs(60 * 60);
+    pub const DEFAULT_CRASH_LOOP_HEALTHY_PERIOD: Duration = Duration::from_secs(10 * 60);
+
     impl Default for ServiceSchedulingConfig {
         fn default() -> Self {
             ServiceSchedulingConfig {
@@ -472,6 +555,13 @@ pub mod scheduling_config {
                 soften_az_affinity: DEFAULT_SOFTEN_AZ_AFFINITY,
                 soften_az_affinity_weight: DEFAULT_SOFTEN_AZ_AFFINITY_WEIGHT,
                 always_use_disk: DEFAULT_ALWAYS_USE_DISK,
+                crash_loop: ServiceCrashLoopConfig {
+                    max_restarts: DEFAULT_CRASH_LOOP_MAX_RESTARTS,
+                    window: DEFAULT_CRASH_LOOP_WINDOW,
+                    initial_backoff: DEFAULT_CRASH_LOOP_INITIAL_BACKOFF,
+                    max_backoff: DEFAULT_CRASH_LOOP_MAX_BACKOFF,
+                    healthy_period: DEFAULT_CRASH_LOOP_HEALTHY_PERIOD,
+                },
             }
         }
     }
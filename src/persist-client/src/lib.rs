@@ -45,10 +45,12 @@ use crate::internal::compact::Compactor;
 use crate::internal::encoding::{parse_id, Schemas};
 use crate::internal::gc::GarbageCollector;
 use crate::internal::machine::{retry_external, Machine};
+use crate::internal::state::State;
 use crate::internal::state_versions::StateVersions;
 use crate::metrics::Metrics;
 use crate::read::{LeasedReaderId, ReadHandle, READER_LEASE_DURATION};
 use crate::rpc::PubSubSender;
+use crate::stats::BatchStats;
 use crate::write::{WriteHandle, WriterId};
 
 pub mod async_runtime;
@@ -675,6 +677,39 @@ impl PersistClient {
         &self,
         shard_id: &ShardId,
     ) -> Result<impl serde::Serialize, anyhow::Error> {
+        self.fetch_current_state::<T>(shard_id).await
+    }
+
+    /// Returns the extent and size of each batch in the latest state of the
+    /// shard, in time order.
+    ///
+    /// See [BatchStats] for the caveats of relying on batches.
+    pub async fn batch_stats<T: Timestamp + Lattice + Codec64>(
+        &self,
+        shard_id: &ShardId,
+    ) -> Result<Vec<BatchStats<T>>, anyhow::Error> {
+        let state = self.fetch_current_state::<T>(shard_id).await?;
+        let stats = state
+            .collections
+            .trace
+            .batches()
+            .into_iter()
+            .map(|batch| BatchStats {
+                lower: batch.desc.lower().clone(),
+                upper: batch.desc.upper().clone(),
+                since: batch.desc.since().clone(),
+                encoded_size_bytes: batch.parts.iter().map(|p| p.encoded_size_bytes).sum(),
+                num_updates: batch.len,
+            })
+            .collect();
+        Ok(stats)
+    }
+
+    /// Fetches the latest state of a shard, which must exist.
+    async fn fetch_current_state<T: Timestamp + Lattice + Codec64>(
+        &self,
+        shard_id: &ShardId,
+    ) -> Result<State<T>, anyhow::Error> {
         let state_versions = StateVersions::new(
             self.cfg.clone(),
             Arc::clone(&self.consensus),
@@ -1373,6 +1408,40 @@ mod tests {
         assert_eq!(write2.upper(), &Antichain::from_elem(3));
     }
 
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    async fn batch_stats() {
+        let data = vec![
+            (("1".to_owned(), "one".to_owned()), 1, 1),
+            (("2".to_owned(), "two".to_owned()), 2, 1),
+            (("3".to_owned(), "three".to_owned()), 3, 1),
+        ];
+
+        let client = new_test_client().await;
+        let shard_id = ShardId::new();
+
+        // Stats for a shard that doesn't exist are an error.
+        assert!(client.batch_stats::<u64>(&shard_id).await.is_err());
+
+        let (mut write, _read) = client
+            .expect_open::<String, String, u64, i64>(shard_id)
+            .await;
+        write.expect_append(&data[..1], vec![0], vec![2]).await;
+        write.expect_append(&data[1..], vec![2], vec![4]).await;
+
+        // Compaction might have merged the batches in the meantime, but they
+        // always cover the shard without gaps and don't lose updates.
+        let stats = client.batch_stats::<u64>(&shard_id).await.unwrap();
+        assert!(!stats.is_empty());
+        assert_eq!(stats[0].lower, Antichain::from_elem(0));
+        assert_eq!(stats[stats.len() - 1].upper, Antichain::from_elem(4));
+        for pair in stats.windows(2) {
+            assert_eq!(pair[0].upper, pair[1].lower);
+        }
+        assert_eq!(stats.iter().map(|b| b.num_updates).sum::<usize>(), 3);
+        assert!(stats.iter().map(|b| b.encoded_size_bytes).sum::<usize>() > 0);
+    }
+
     #[mz_ore::test(tokio::test)]
     #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
     async fn append_with_invalid_upper() {
@@ -615,6 +615,19 @@ where
         &self.since
     }
 
+    /// Fetches and returns a recent shard-global `upper`. Importantly, this operation is
+    /// linearized with write operations.
+    ///
+    /// This requires fetching the latest state from consensus and is therefore a potentially
+    /// expensive operation.
+    #[instrument(level = "debug", skip_all, fields(shard = %self.machine.shard_id()))]
+    pub async fn fetch_recent_upper(&mut self) -> Antichain<T> {
+        self.machine
+            .applier
+            .fetch_upper(|current_upper| current_upper.clone())
+            .await
+    }
+
     /// Forwards the since frontier of this handle, giving up the ability to
     /// read at times not greater or equal to `new_since`.
     ///
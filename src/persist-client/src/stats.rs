@@ -18,6 +18,7 @@ use mz_persist_types::part::{Part, PartBuilder};
 use mz_persist_types::stats::StructStats;
 use mz_persist_types::Codec;
 use proptest_derive::Arbitrary;
+use timely::progress::Antichain;
 
 use crate::batch::UntrimmableColumns;
 use crate::internal::encoding::Schemas;
@@ -184,3 +185,24 @@ pub struct SnapshotStats {
     /// can only go down.
     pub num_updates: usize,
 }
+
+/// The extent and size of one batch of a shard.
+///
+/// Batches are an internal detail of how persist lays out a shard, which
+/// changes as persist compacts it. These are meant for coarse estimates about
+/// the contents of a shard, not for anything that relies on a particular
+/// layout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchStats<T> {
+    /// The times of the updates in the batch are beyond this frontier.
+    pub lower: Antichain<T>,
+    /// The times of the updates in the batch are not beyond this frontier.
+    pub upper: Antichain<T>,
+    /// The frontier the times of the updates in the batch have been advanced
+    /// to.
+    pub since: Antichain<T>,
+    /// The encoded size of the parts of the batch.
+    pub encoded_size_bytes: usize,
+    /// The number of updates in the batch, before consolidation.
+    pub num_updates: usize,
+}
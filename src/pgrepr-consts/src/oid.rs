@@ -375,3 +375,6 @@ pub const FUNC_MAP_BUILD: u32 = 16_652;
 pub const FUNC_MAP_AGG: u32 = 16_653;
 pub const FUNC_UNNEST_MAP_OID: u32 = 16_654;
 pub const FUNC_MZ_NORMALIZE_SCHEMA_NAME: u32 = 16_655;
+pub const FUNC_MZ_QUIESCE_CLUSTER_OID: u32 = 16_656;
+pub const FUNC_MZ_UNQUIESCE_CLUSTER_OID: u32 = 16_657;
+pub const FUNC_MZ_ESTIMATE_COMPACTION_OID: u32 = 16_658;
@@ -38,7 +38,7 @@ use mz_repr::user::ExternalUserMetadata;
 use mz_repr::{Datum, GlobalId, RelationDesc, RelationType, Row, RowArena, ScalarType};
 use mz_server_core::TlsMode;
 use mz_sql::ast::display::AstDisplay;
-use mz_sql::ast::{FetchDirection, Ident, Raw, Statement};
+use mz_sql::ast::{DiscardStatement, DiscardTarget, FetchDirection, Ident, Raw, Statement};
 use mz_sql::parse::StatementParseResult;
 use mz_sql::plan::{CopyFormat, ExecuteTimeout, StatementDesc};
 use mz_sql::session::user::{User, INTERNAL_USER_NAMES};
@@ -606,6 +606,15 @@ where
 
     #[instrument(level = "debug", skip_all)]
     async fn one_query(&mut self, stmt: Statement<Raw>, sql: String) -> Result<State, io::Error> {
+        // Connection poolers issue `DISCARD ALL` between clients, so reset the session directly
+        // rather than planning and sequencing the statement.
+        if let Statement::Discard(DiscardStatement {
+            target: DiscardTarget::All,
+        }) = stmt
+        {
+            return self.discard_all().await;
+        }
+
         // Bind the portal. Note that this does not set the empty string prepared
         // statement.
         const EMPTY_PORTAL: &str = "";
@@ -674,6 +683,27 @@ where
         result
     }
 
+    /// Resets the session for `DISCARD ALL`, and reports the reset values of the parameters
+    /// that the client is notified about.
+    async fn discard_all(&mut self) -> Result<State, io::Error> {
+        if let Err(e) = self.adapter_client.reset_session().await {
+            return self.error(e.into_response(Severity::Error)).await;
+        }
+        let params: Vec<_> = self
+            .adapter_client
+            .session()
+            .vars()
+            .notify_set()
+            .map(|var| BackendMessage::ParameterStatus(var.name(), var.value()))
+            .collect();
+        self.send_all(params).await?;
+        self.send(BackendMessage::CommandComplete {
+            tag: "DISCARD ALL".into(),
+        })
+        .await?;
+        Ok(State::Ready)
+    }
+
     async fn ensure_transaction(&mut self, num_stmts: usize) -> Result<(), io::Error> {
         if self.txn_needs_commit {
             self.commit_transaction().await?;
@@ -1230,6 +1260,9 @@ where
             .get_portal_unverified_mut(name)
             .expect("portal should exist");
         portal.state = PortalState::Completed(None);
+        self.adapter_client
+            .session()
+            .update_portal_buffered_bytes(name);
     }
 
     async fn fetch(
@@ -1529,7 +1562,7 @@ where
                         row_desc,
                         portal_name,
                         InProgressRows::new(RecordFirstRowStream::new(
-                            Box::new(UnboundedReceiverStream::new(rx)),
+                            Box::new(rx),
                             execute_started,
                             &self.adapter_client,
                         )),
@@ -1573,7 +1606,7 @@ where
                                 format,
                                 row_desc,
                                 RecordFirstRowStream::new(
-                                    Box::new(UnboundedReceiverStream::new(rx)),
+                                    Box::new(rx),
                                     execute_started,
                                     &self.adapter_client,
                                 ),
@@ -1726,6 +1759,7 @@ where
             | ExecuteResponse::RevokedPrivilege
             | ExecuteResponse::RevokedRole
             | ExecuteResponse::StartedTransaction { .. }
+            | ExecuteResponse::Undropped
             | ExecuteResponse::Updated(..)
             | ExecuteResponse::ValidatedConnection => {
                 command_complete!()
@@ -1911,6 +1945,9 @@ where
         // Always return rows back, even if it's empty. This prevents an unclosed
         // portal from re-executing after it has been emptied.
         portal.state = PortalState::InProgress(Some(rows));
+        self.adapter_client
+            .session()
+            .update_portal_buffered_bytes(&portal_name);
 
         let fetch_portal = fetch_portal_name.map(|name| {
             self.adapter_client
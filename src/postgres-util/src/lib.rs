@@ -13,8 +13,8 @@
 pub mod replication;
 #[cfg(feature = "replication")]
 pub use replication::{
-    available_replication_slots, drop_replication_slots, get_max_wal_senders, get_timeline_id,
-    get_wal_level,
+    available_replication_slots, drop_replication_slots, ensure_replication_slot, export_snapshot,
+    get_max_wal_senders, get_timeline_id, get_wal_level,
 };
 #[cfg(feature = "schemas")]
 pub mod desc;
@@ -27,8 +27,8 @@ pub mod tunnel;
 #[cfg(feature = "tunnel")]
 pub use tunnel::{
     Config, TcpTimeoutConfig, TunnelConfig, DEFAULT_CONNECT_TIMEOUT, DEFAULT_KEEPALIVE_IDLE,
-    DEFAULT_KEEPALIVE_INTERVAL, DEFAULT_KEEPALIVE_RETRIES, DEFAULT_SNAPSHOT_STATEMENT_TIMEOUT,
-    DEFAULT_TCP_USER_TIMEOUT,
+    DEFAULT_KEEPALIVE_INTERVAL, DEFAULT_KEEPALIVE_RETRIES, DEFAULT_SNAPSHOT_LOCK_TIMEOUT,
+    DEFAULT_SNAPSHOT_STATEMENT_TIMEOUT, DEFAULT_TCP_USER_TIMEOUT,
 };
 
 pub mod query;
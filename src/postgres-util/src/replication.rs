@@ -8,6 +8,8 @@
 // by the Apache License, Version 2.0.
 
 use std::str::FromStr;
+use tokio_postgres::error::SqlState;
+use tokio_postgres::types::PgLsn;
 use tokio_postgres::Client;
 
 use mz_ssh_util::tunnel_manager::SshTunnelManager;
@@ -146,6 +148,67 @@ pub async fn drop_replication_slots(
     Ok(())
 }
 
+/// Creates the logical replication `slot` on the server `replication_client` is connected to,
+/// unless it already exists.
+pub async fn ensure_replication_slot(
+    replication_client: &Client,
+    slot: &str,
+) -> Result<(), PostgresError> {
+    let query = format!(
+        "CREATE_REPLICATION_SLOT {} LOGICAL \"pgoutput\" NOEXPORT_SNAPSHOT",
+        quote_slot(slot)
+    );
+    match simple_query_opt(replication_client, &query).await {
+        Ok(_) => Ok(()),
+        // If the slot already exists that's still ok
+        Err(PostgresError::Postgres(err)) if err.code() == Some(&SqlState::DUPLICATE_OBJECT) => {
+            tracing::trace!("replication slot {slot} already existed");
+            Ok(())
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Starts a read-only transaction on the SQL session of `replication_client` at a consistent LSN
+/// point by creating the temporary replication slot `temporary_slot`. Returns a snapshot
+/// identifier that can be imported in other SQL sessions and the LSN the snapshot is at.
+pub async fn export_snapshot(
+    replication_client: &Client,
+    temporary_slot: &str,
+) -> Result<(String, PgLsn), PostgresError> {
+    replication_client
+        .simple_query("BEGIN READ ONLY ISOLATION LEVEL REPEATABLE READ;")
+        .await?;
+    // A temporary replication slot is the only way to get the tx in a consistent LSN point
+    let query = format!(
+        "CREATE_REPLICATION_SLOT {} TEMPORARY LOGICAL \"pgoutput\" USE_SNAPSHOT",
+        quote_slot(temporary_slot)
+    );
+    let row = simple_query_opt(replication_client, &query).await?.unwrap();
+    let consistent_point: PgLsn = row.get("consistent_point").unwrap().parse().unwrap();
+
+    let row = simple_query_opt(replication_client, "SELECT pg_export_snapshot();")
+        .await?
+        .unwrap();
+    let snapshot = row.get("pg_export_snapshot").unwrap().to_owned();
+
+    // When creating a replication slot postgres returns the LSN of its consistent point, which is
+    // the LSN that must be passed to `START_REPLICATION` to cleanly transition from the snapshot
+    // phase to the replication phase. `START_REPLICATION` includes all transactions that commit at
+    // LSNs *greater than or equal* to the passed LSN. Therefore the snapshot phase must happen at
+    // the greatest LSN that is not beyond the consistent point. That LSN is `consistent_point - 1`
+    let snapshot_lsn = u64::from(consistent_point)
+        .checked_sub(1)
+        .expect("consistent point is always non-zero");
+    Ok((snapshot, PgLsn::from(snapshot_lsn)))
+}
+
+/// Quotes the name of a replication slot for use in a replication command. Slot names may only
+/// contain lower case letters, numbers, and underscores, so quoting does not change their meaning.
+fn quote_slot(slot: &str) -> String {
+    format!("\"{}\"", slot.replace('"', "\"\""))
+}
+
 pub async fn get_timeline_id(replication_client: &Client) -> Result<u64, PostgresError> {
     if let Some(r) = simple_query_opt(replication_client, "IDENTIFY_SYSTEM").await? {
         r.get("timeline")
@@ -39,7 +39,9 @@ pub async fn get_schemas(
 /// publication name.
 ///
 /// If `oid_filter` is `None`, returns all tables, otherwise returns only the
-/// details for the identified oid.
+/// details for the identified oids. Callers that only care about a few tables of
+/// a large publication should pass a filter, as column and key metadata is
+/// fetched per table.
 ///
 /// # Errors
 ///
@@ -49,7 +51,7 @@ pub async fn publication_info(
     ssh_tunnel_manager: &SshTunnelManager,
     config: &Config,
     publication: &str,
-    oid_filter: Option<u32>,
+    oid_filter: Option<&[Oid]>,
 ) -> Result<Vec<PostgresTableDesc>, PostgresError> {
     let client = config
         .connect("postgres_publication_info", ssh_tunnel_manager)
@@ -76,7 +78,7 @@ pub async fn publication_info(
                         c.relname = p.tablename AND n.nspname = p.schemaname
             WHERE
                 p.pubname = $1
-                AND ($2::oid IS NULL OR c.oid = $2::oid)",
+                AND ($2::oid[] IS NULL OR c.oid = ANY ($2::oid[]))",
             &[&publication, &oid_filter],
         )
         .await
@@ -87,6 +87,7 @@ impl Default for TcpTimeoutConfig {
 }
 
 pub const DEFAULT_SNAPSHOT_STATEMENT_TIMEOUT: Duration = Duration::ZERO;
+pub const DEFAULT_SNAPSHOT_LOCK_TIMEOUT: Duration = Duration::ZERO;
 
 /// Configuration for PostgreSQL connections.
 ///
@@ -0,0 +1,253 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Chunked transfer of commands that are too large to send as a single message.
+//!
+//! A command whose encoding exceeds the chunk size is encoded once, and its bytes are sent as a
+//! sequence of [`ProtoCommandChunk`]s, each wrapped in a command of the same protocol. The
+//! receiving end buffers the chunks until it has received all of them, and then decodes the
+//! command. Commands are sent in order over a single stream, so the chunks of a command are
+//! never interleaved with other commands. The buffer is capped at
+//! [`MAX_CHUNKED_COMMAND_BYTES`].
+
+use anyhow::bail;
+use mz_ore::cast::CastFrom;
+use prost::Message;
+
+include!(concat!(env!("OUT_DIR"), "/mz_service.chunk.rs"));
+
+/// The largest encoded command that can be reassembled from chunks.
+pub const MAX_CHUNKED_COMMAND_BYTES: usize = 1 << 30;
+
+/// Protobuf commands that can be transferred in chunks.
+///
+/// The provided methods describe a protocol that does not support chunks, whose commands are
+/// always sent whole.
+pub trait ChunkedCommand: Message + Default {
+    /// Wraps `chunk` in a command, or returns `None` if the protocol does not support chunks.
+    fn from_chunk(_chunk: ProtoCommandChunk) -> Option<Self> {
+        None
+    }
+
+    /// Returns the chunk wrapped in the command, or the command itself if it is whole.
+    fn into_chunk(self) -> Result<ProtoCommandChunk, Self> {
+        Err(self)
+    }
+}
+
+/// Splits large commands into chunks.
+#[derive(Debug, Default)]
+pub struct ChunkSplitter {
+    /// The ID of the next command that is split.
+    next_command_id: u64,
+}
+
+impl ChunkSplitter {
+    /// Splits `command` into commands that each wrap at most `chunk_bytes` of its encoding.
+    ///
+    /// Commands whose encoding doesn't exceed `chunk_bytes`, and commands of protocols that don't
+    /// support chunks, are returned whole.
+    pub fn split<C: ChunkedCommand>(&mut self, command: C, chunk_bytes: usize) -> Vec<C> {
+        let chunk_bytes = std::cmp::max(chunk_bytes, 1);
+        if command.encoded_len() <= chunk_bytes
+            || C::from_chunk(ProtoCommandChunk::default()).is_none()
+        {
+            return vec![command];
+        }
+
+        let command_id = self.next_command_id;
+        self.next_command_id += 1;
+        let data = command.encode_to_vec();
+        let total = u64::cast_from(data.chunks(chunk_bytes).len());
+        data.chunks(chunk_bytes)
+            .enumerate()
+            .map(|(sequence, data)| {
+                let chunk = ProtoCommandChunk {
+                    command_id,
+                    sequence: u64::cast_from(sequence),
+                    total,
+                    data: data.to_vec(),
+                };
+                C::from_chunk(chunk).expect("protocol supports chunks")
+            })
+            .collect()
+    }
+}
+
+/// Reassembles commands from their chunks.
+#[derive(Debug)]
+pub struct ChunkReassembler {
+    /// The largest encoded command that is reassembled.
+    max_bytes: usize,
+    /// The command whose chunks are being received, if any.
+    pending: Option<PendingCommand>,
+}
+
+/// A command that has been received partially.
+#[derive(Debug)]
+struct PendingCommand {
+    command_id: u64,
+    total: u64,
+    next_sequence: u64,
+    data: Vec<u8>,
+}
+
+impl ChunkReassembler {
+    /// Creates a reassembler for commands whose encoding is at most `max_bytes`.
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            pending: None,
+        }
+    }
+
+    /// Absorbs the next command received, and returns the next whole command, if there is one.
+    ///
+    /// Whole commands are returned as they are, and chunked ones once their last chunk is
+    /// absorbed. Chunks that arrive out of order or interleaved with other commands, and
+    /// commands that exceed the size limit, are errors.
+    pub fn absorb<C: ChunkedCommand>(&mut self, command: C) -> Result<Option<C>, anyhow::Error> {
+        let chunk = match command.into_chunk() {
+            Ok(chunk) => chunk,
+            Err(command) => {
+                if let Some(pending) = &self.pending {
+                    bail!(
+                        "received a whole command while command {} was missing {} of {} chunks",
+                        pending.command_id,
+                        pending.total - pending.next_sequence,
+                        pending.total,
+                    );
+                }
+                return Ok(Some(command));
+            }
+        };
+
+        let pending = self.pending.get_or_insert_with(|| PendingCommand {
+            command_id: chunk.command_id,
+            total: chunk.total,
+            next_sequence: 0,
+            data: Vec::new(),
+        });
+        if chunk.command_id != pending.command_id
+            || chunk.total != pending.total
+            || chunk.sequence != pending.next_sequence
+        {
+            bail!(
+                "received chunk {} of {} of command {}, expected chunk {} of {} of command {}",
+                chunk.sequence,
+                chunk.total,
+                chunk.command_id,
+                pending.next_sequence,
+                pending.total,
+                pending.command_id,
+            );
+        }
+        if pending.data.len() + chunk.data.len() > self.max_bytes {
+            bail!(
+                "chunked command {} exceeds the limit of {} bytes",
+                pending.command_id,
+                self.max_bytes,
+            );
+        }
+        pending.data.extend_from_slice(&chunk.data);
+        pending.next_sequence += 1;
+        if pending.next_sequence < pending.total {
+            return Ok(None);
+        }
+
+        let pending = self.pending.take().expect("known to exist");
+        Ok(Some(C::decode(pending.data.as_slice())?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    enum TestKind {
+        #[prost(bytes, tag = "1")]
+        Payload(Vec<u8>),
+        #[prost(message, tag = "2")]
+        Chunk(ProtoCommandChunk),
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct TestCommand {
+        #[prost(oneof = "TestKind", tags = "1, 2")]
+        kind: Option<TestKind>,
+    }
+
+    impl ChunkedCommand for TestCommand {
+        fn from_chunk(chunk: ProtoCommandChunk) -> Option<Self> {
+            Some(TestCommand {
+                kind: Some(TestKind::Chunk(chunk)),
+            })
+        }
+
+        fn into_chunk(self) -> Result<ProtoCommandChunk, Self> {
+            match self.kind {
+                Some(TestKind::Chunk(chunk)) => Ok(chunk),
+                kind => Err(TestCommand { kind }),
+            }
+        }
+    }
+
+    fn payload(len: usize) -> TestCommand {
+        TestCommand {
+            kind: Some(TestKind::Payload(vec![7; len])),
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_split_and_reassemble() {
+        let mut splitter = ChunkSplitter::default();
+        let mut reassembler = ChunkReassembler::new(MAX_CHUNKED_COMMAND_BYTES);
+
+        // Commands that fit are sent whole.
+        let small = payload(10);
+        assert_eq!(splitter.split(small.clone(), 100), vec![small.clone()]);
+        assert_eq!(reassembler.absorb(small.clone()).unwrap(), Some(small));
+
+        for large in [payload(1000), payload(1001)] {
+            let chunks = splitter.split(large.clone(), 100);
+            assert_eq!(chunks.len(), large.encoded_len().div_ceil(100));
+            let (last, rest) = chunks.split_last().unwrap();
+            for chunk in rest {
+                assert_eq!(reassembler.absorb(chunk.clone()).unwrap(), None);
+            }
+            assert_eq!(reassembler.absorb(last.clone()).unwrap(), Some(large));
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_reassembly_errors() {
+        let mut splitter = ChunkSplitter::default();
+        let chunks = splitter.split(payload(1000), 100);
+
+        // Chunks must arrive in order.
+        let mut reassembler = ChunkReassembler::new(MAX_CHUNKED_COMMAND_BYTES);
+        reassembler.absorb(chunks[0].clone()).unwrap();
+        assert!(reassembler.absorb(chunks[2].clone()).is_err());
+
+        // Chunks must not be interleaved with other commands.
+        let mut reassembler = ChunkReassembler::new(MAX_CHUNKED_COMMAND_BYTES);
+        reassembler.absorb(chunks[0].clone()).unwrap();
+        assert!(reassembler.absorb(payload(10)).is_err());
+
+        // Commands must not exceed the limit.
+        let mut reassembler = ChunkReassembler::new(500);
+        let results: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| reassembler.absorb(chunk))
+            .collect();
+        assert!(results[..5].iter().all(|result| result.is_ok()));
+        assert!(results[5].is_err());
+    }
+}
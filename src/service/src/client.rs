@@ -113,6 +113,11 @@ where
             parts,
         }
     }
+
+    /// Returns a mutable reference to the partitioned state.
+    pub fn state_mut(&mut self) -> &mut <(C, R) as Partitionable<C, R>>::PartitionedState {
+        &mut self.state
+    }
 }
 
 #[async_trait]
@@ -35,6 +35,7 @@ use tonic::{IntoStreamingRequest, Request, Response, Status, Streaming};
 use tower::Service;
 use tracing::{debug, error, info};
 
+use crate::chunk::{ChunkReassembler, ChunkSplitter, ChunkedCommand, MAX_CHUNKED_COMMAND_BYTES};
 use crate::client::{GenericClient, Partitionable, Partitioned};
 use crate::codec::{StatCodec, StatsCollector};
 use crate::params::GrpcClientParameters;
@@ -50,7 +51,7 @@ pub type ClientTransport = InterceptedService<Channel, VersionAttachInterceptor>
 
 /// Types that we send and receive over a service endpoint.
 pub trait ProtoServiceTypes: Debug + Clone + Send {
-    type PC: prost::Message + Clone + 'static;
+    type PC: ChunkedCommand + Clone + 'static;
     type PR: prost::Message + Clone + Default + 'static;
     type STATS: StatsCollector<Self::PC, Self::PR> + 'static;
     const URL: &'static str;
@@ -70,6 +71,10 @@ pub trait ProtoServiceTypes: Debug + Clone + Send {
 /// (represented directly as a `Streaming<Response>` instance). The recv and send
 /// functions interact with the two mpsc channels or the streaming instance
 /// respectively.
+///
+/// If chunking is enabled with [`GrpcClient::set_command_chunk_bytes`], commands
+/// that exceed the chunk size are sent in chunks, which the [`GrpcServer`]
+/// reassembles, see the [chunk](crate::chunk) module.
 #[derive(Debug)]
 pub struct GrpcClient<G>
 where
@@ -79,6 +84,10 @@ where
     tx: UnboundedSender<G::PC>,
     /// The receiver for responses.
     rx: Streaming<G::PR>,
+    /// The size above which commands are sent in chunks, if chunking is enabled.
+    command_chunk_bytes: Option<usize>,
+    /// Splits commands into chunks.
+    splitter: ChunkSplitter,
 }
 
 impl<G> GrpcClient<G>
@@ -126,7 +135,18 @@ where
             .await?
             .into_inner();
         info!("GrpcClient {}: connected", &addr);
-        Ok(GrpcClient { tx, rx })
+        Ok(GrpcClient {
+            tx,
+            rx,
+            command_chunk_bytes: None,
+            splitter: ChunkSplitter::default(),
+        })
+    }
+
+    /// Sets the size above which commands are sent in chunks, or disables
+    /// chunking if `None`.
+    pub fn set_command_chunk_bytes(&mut self, chunk_bytes: Option<usize>) {
+        self.command_chunk_bytes = chunk_bytes;
     }
 
     /// Like [`GrpcClient::connect`], but for multiple partitioned servers.
@@ -148,6 +168,23 @@ where
     }
 }
 
+impl<G, C, R> Partitioned<GrpcClient<G>, C, R>
+where
+    G: ProtoServiceTypes,
+    (C, R): Partitionable<C, R>,
+{
+    /// Sets the size above which the commands sent to each partition are sent
+    /// in chunks, or disables chunking if `None`.
+    ///
+    /// Commands are split into partitions first, so each partition receives
+    /// the chunks of the commands meant for it.
+    pub fn set_command_chunk_bytes(&mut self, chunk_bytes: Option<usize>) {
+        for part in &mut self.parts {
+            part.set_command_chunk_bytes(chunk_bytes);
+        }
+    }
+}
+
 #[async_trait]
 impl<G, C, R> GenericClient<C, R> for GrpcClient<G>
 where
@@ -156,7 +193,15 @@ where
     G: ProtoServiceTypes,
 {
     async fn send(&mut self, cmd: C) -> Result<(), anyhow::Error> {
-        self.tx.send(cmd.into_proto())?;
+        let cmd = cmd.into_proto();
+        match self.command_chunk_bytes {
+            Some(chunk_bytes) => {
+                for chunk in self.splitter.split(cmd, chunk_bytes) {
+                    self.tx.send(chunk)?;
+                }
+            }
+            None => self.tx.send(cmd)?,
+        }
         Ok(())
     }
 
@@ -294,6 +339,9 @@ where
     /// Handles a bidirectional stream request by forwarding commands to and
     /// responses from the server's underlying client.
     ///
+    /// Commands that the client sent in chunks are reassembled before they are
+    /// forwarded.
+    ///
     /// Call this method from the implementation of the tonic-generated
     /// `ProtoService`.
     pub async fn forward_bidi_stream<C, R, PC, PR>(
@@ -304,7 +352,7 @@ where
         G: GenericClient<C, R> + 'static,
         C: RustType<PC> + Send + Sync + 'static + fmt::Debug,
         R: RustType<PR> + Send + Sync + 'static + fmt::Debug,
-        PC: fmt::Debug + Send + Sync + 'static,
+        PC: ChunkedCommand + fmt::Debug + Send + Sync + 'static,
         PR: fmt::Debug + Send + Sync + 'static,
     {
         info!("GrpcServer: remote client connected");
@@ -326,6 +374,7 @@ where
         let state = Arc::clone(&self.state);
         let response = stream! {
             let mut client = (state.client_builder)();
+            let mut reassembler = ChunkReassembler::new(MAX_CHUNKED_COMMAND_BYTES);
             loop {
                 select! {
                     command = request.next() => {
@@ -337,6 +386,14 @@ where
                                 break;
                             }
                         };
+                        let command = match reassembler.absorb(command) {
+                            Ok(Some(command)) => command,
+                            Ok(None) => continue,
+                            Err(e) => {
+                                error!("error reassembling command: {e}");
+                                break;
+                            }
+                        };
                         let command = match command.into_rust() {
                             Ok(command) => command,
                             Err(e) => {
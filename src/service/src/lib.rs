@@ -14,6 +14,7 @@
 //! obviously reusable by future services we may split out of environmentd.
 
 pub mod boot;
+pub mod chunk;
 pub mod client;
 pub mod codec;
 pub mod grpc;
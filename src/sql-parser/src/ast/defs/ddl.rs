@@ -672,6 +672,7 @@ pub enum ConnectionOptionName {
     SecretAccessKey,
     SecurityProtocol,
     ServiceName,
+    SnapshotHost,
     SshTunnel,
     SslCertificate,
     SslCertificateAuthority,
@@ -705,6 +706,7 @@ impl AstDisplay for ConnectionOptionName {
             ConnectionOptionName::SecurityProtocol => "SECURITY PROTOCOL",
             ConnectionOptionName::SecretAccessKey => "SECRET ACCESS KEY",
             ConnectionOptionName::ServiceName => "SERVICE NAME",
+            ConnectionOptionName::SnapshotHost => "SNAPSHOT HOST",
             ConnectionOptionName::SshTunnel => "SSH TUNNEL",
             ConnectionOptionName::SslCertificate => "SSL CERTIFICATE",
             ConnectionOptionName::SslCertificateAuthority => "SSL CERTIFICATE AUTHORITY",
@@ -895,6 +897,9 @@ pub enum PgConfigOptionName {
     Details,
     /// The name of the publication to sync
     Publication,
+    /// The name of a group of sources whose initial snapshots are taken at the
+    /// same upstream LSN
+    SnapshotGroup,
     /// Columns whose types you want to unconditionally format as text
     TextColumns,
 }
@@ -904,6 +909,7 @@ impl AstDisplay for PgConfigOptionName {
         f.write_str(match self {
             PgConfigOptionName::Details => "DETAILS",
             PgConfigOptionName::Publication => "PUBLICATION",
+            PgConfigOptionName::SnapshotGroup => "SNAPSHOT GROUP",
             PgConfigOptionName::TextColumns => "TEXT COLUMNS",
         })
     }
@@ -1248,6 +1254,12 @@ pub enum CreateSourceOptionName {
     Timeline,
     TimestampInterval,
     RetainHistory,
+    MaxInflightBytes,
+    SnapshotMaxInflightBytes,
+    SnapshotReadCommitted,
+    MaxBatchSize,
+    YieldBudget,
+    StatisticsInterval,
 }
 
 impl AstDisplay for CreateSourceOptionName {
@@ -1257,6 +1269,12 @@ impl AstDisplay for CreateSourceOptionName {
             CreateSourceOptionName::Timeline => "TIMELINE",
             CreateSourceOptionName::TimestampInterval => "TIMESTAMP INTERVAL",
             CreateSourceOptionName::RetainHistory => "RETAIN HISTORY",
+            CreateSourceOptionName::MaxInflightBytes => "MAX INFLIGHT BYTES",
+            CreateSourceOptionName::SnapshotMaxInflightBytes => "SNAPSHOT MAX INFLIGHT BYTES",
+            CreateSourceOptionName::SnapshotReadCommitted => "SNAPSHOT READ COMMITTED",
+            CreateSourceOptionName::MaxBatchSize => "MAX BATCH SIZE",
+            CreateSourceOptionName::YieldBudget => "YIELD BUDGET",
+            CreateSourceOptionName::StatisticsInterval => "STATISTICS INTERVAL",
         })
     }
 }
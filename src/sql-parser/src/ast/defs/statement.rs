@@ -103,6 +103,7 @@ pub enum Statement<T: AstInfo> {
     ReassignOwned(ReassignOwnedStatement<T>),
     ValidateConnection(ValidateConnectionStatement<T>),
     Comment(CommentStatement<T>),
+    Undrop(UndropStatement),
 }
 
 impl<T: AstInfo> AstDisplay for Statement<T> {
@@ -172,6 +173,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::ReassignOwned(stmt) => f.write_node(stmt),
             Statement::ValidateConnection(stmt) => f.write_node(stmt),
             Statement::Comment(stmt) => f.write_node(stmt),
+            Statement::Undrop(stmt) => f.write_node(stmt),
         }
     }
 }
@@ -244,6 +246,7 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::ReassignOwned => "reassign_owned",
         StatementKind::ValidateConnection => "validate_connection",
         StatementKind::Comment => "comment",
+        StatementKind::Undrop => "undrop",
     }
 }
 
@@ -1683,6 +1686,8 @@ pub enum ClusterOptionName {
     ReplicationFactor,
     /// The `SIZE` option.
     Size,
+    /// The `TRANSACTION ISOLATION [=] <level>` option.
+    TransactionIsolation,
 }
 
 impl AstDisplay for ClusterOptionName {
@@ -1699,6 +1704,7 @@ impl AstDisplay for ClusterOptionName {
             ClusterOptionName::Replicas => f.write_str("REPLICAS"),
             ClusterOptionName::ReplicationFactor => f.write_str("REPLICATION FACTOR"),
             ClusterOptionName::Size => f.write_str("SIZE"),
+            ClusterOptionName::TransactionIsolation => f.write_str("TRANSACTION ISOLATION"),
         }
     }
 }
@@ -2093,6 +2099,7 @@ impl_display_t!(AlterIndexStatement);
 pub enum AlterSinkAction<T: AstInfo> {
     SetOptions(Vec<CreateSinkOption<T>>),
     ResetOptions(Vec<CreateSinkOptionName>),
+    ResetProgress,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -2122,6 +2129,7 @@ impl<T: AstInfo> AstDisplay for AlterSinkStatement<T> {
                 f.write_node(&display::comma_separated(options));
                 f.write_str(")");
             }
+            AlterSinkAction::ResetProgress => f.write_str("RESET PROGRESS"),
         }
     }
 }
@@ -2406,6 +2414,20 @@ impl AstDisplay for DiscardStatement {
 }
 impl_display!(DiscardStatement);
 
+/// `UNDROP <id>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UndropStatement {
+    pub id: Ident,
+}
+
+impl AstDisplay for UndropStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("UNDROP ");
+        f.write_node(&self.id);
+    }
+}
+impl_display!(UndropStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum DiscardTarget {
     Plans,
@@ -2566,6 +2588,31 @@ impl AstDisplay for InspectShardStatement {
 }
 impl_display!(InspectShardStatement);
 
+/// `INSPECT COLLECTION <name>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InspectCollectionStatement<T: AstInfo> {
+    pub name: T::ItemName,
+}
+
+impl<T: AstInfo> AstDisplay for InspectCollectionStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("INSPECT COLLECTION ");
+        f.write_node(&self.name);
+    }
+}
+impl_display_t!(InspectCollectionStatement);
+
+/// `SHOW TIMESTAMP`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ShowTimestampStatement {}
+
+impl AstDisplay for ShowTimestampStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW TIMESTAMP");
+    }
+}
+impl_display!(ShowTimestampStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum ShowObjectType<T: AstInfo> {
     MaterializedView {
@@ -2924,6 +2971,8 @@ impl_display!(RollbackStatement);
 pub enum SubscribeOptionName {
     Snapshot,
     Progress,
+    ResumeFrom,
+    ResumeTokens,
 }
 
 impl AstDisplay for SubscribeOptionName {
@@ -2931,6 +2980,8 @@ impl AstDisplay for SubscribeOptionName {
         match self {
             SubscribeOptionName::Snapshot => f.write_str("SNAPSHOT"),
             SubscribeOptionName::Progress => f.write_str("PROGRESS"),
+            SubscribeOptionName::ResumeFrom => f.write_str("RESUME FROM"),
+            SubscribeOptionName::ResumeTokens => f.write_str("RESUME TOKENS"),
         }
     }
 }
@@ -3898,7 +3949,9 @@ pub enum ShowStatement<T: AstInfo> {
     ShowCreateIndex(ShowCreateIndexStatement<T>),
     ShowCreateConnection(ShowCreateConnectionStatement<T>),
     ShowVariable(ShowVariableStatement),
+    ShowTimestamp(ShowTimestampStatement),
     InspectShard(InspectShardStatement),
+    InspectCollection(InspectCollectionStatement<T>),
 }
 
 impl<T: AstInfo> AstDisplay for ShowStatement<T> {
@@ -3914,7 +3967,9 @@ impl<T: AstInfo> AstDisplay for ShowStatement<T> {
             ShowStatement::ShowCreateIndex(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateConnection(stmt) => f.write_node(stmt),
             ShowStatement::ShowVariable(stmt) => f.write_node(stmt),
+            ShowStatement::ShowTimestamp(stmt) => f.write_node(stmt),
             ShowStatement::InspectShard(stmt) => f.write_node(stmt),
+            ShowStatement::InspectCollection(stmt) => f.write_node(stmt),
         }
     }
 }
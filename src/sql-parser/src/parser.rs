@@ -442,6 +442,9 @@ impl<'a> Parser<'a> {
                 Token::Keyword(COMMENT) => Ok(self
                     .parse_comment()
                     .map_parser_err(StatementKind::Comment)?),
+                Token::Keyword(UNDROP) => {
+                    Ok(self.parse_undrop().map_parser_err(StatementKind::Undrop)?)
+                }
                 Token::Keyword(k) if QUERY_START_KEYWORDS.contains(&k) => {
                     self.prev_token();
                     Ok(Statement::Select(
@@ -2424,6 +2427,7 @@ impl<'a> Parser<'a> {
                 SECURITY,
                 SERVICE,
                 SESSION,
+                SNAPSHOT,
                 SSH,
                 SSL,
                 URL,
@@ -2487,6 +2491,10 @@ impl<'a> Parser<'a> {
                     self.expect_keyword(TOKEN)?;
                     ConnectionOptionName::SessionToken
                 }
+                SNAPSHOT => {
+                    self.expect_keyword(HOST)?;
+                    ConnectionOptionName::SnapshotHost
+                }
                 SSH => {
                     self.expect_keyword(TUNNEL)?;
                     ConnectionOptionName::SshTunnel
@@ -2744,7 +2752,9 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_source_option_name(&mut self) -> Result<CreateSourceOptionName, ParserError> {
-        let name = match self.expect_one_of_keywords(&[IGNORE, TIMELINE, TIMESTAMP, RETAIN])? {
+        let name = match self.expect_one_of_keywords(&[
+            IGNORE, TIMELINE, TIMESTAMP, RETAIN, MAX, SNAPSHOT, YIELD, STATISTICS,
+        ])? {
             IGNORE => {
                 self.expect_keyword(KEYS)?;
                 CreateSourceOptionName::IgnoreKeys
@@ -2758,6 +2768,36 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(HISTORY)?;
                 CreateSourceOptionName::RetainHistory
             }
+            MAX => match self.expect_one_of_keywords(&[INFLIGHT, BATCH])? {
+                INFLIGHT => {
+                    self.expect_keyword(BYTES)?;
+                    CreateSourceOptionName::MaxInflightBytes
+                }
+                BATCH => {
+                    self.expect_keyword(SIZE)?;
+                    CreateSourceOptionName::MaxBatchSize
+                }
+                _ => unreachable!(),
+            },
+            SNAPSHOT => match self.expect_one_of_keywords(&[MAX, READ])? {
+                MAX => {
+                    self.expect_keywords(&[INFLIGHT, BYTES])?;
+                    CreateSourceOptionName::SnapshotMaxInflightBytes
+                }
+                READ => {
+                    self.expect_keyword(COMMITTED)?;
+                    CreateSourceOptionName::SnapshotReadCommitted
+                }
+                _ => unreachable!(),
+            },
+            YIELD => {
+                self.expect_keyword(BUDGET)?;
+                CreateSourceOptionName::YieldBudget
+            }
+            STATISTICS => {
+                self.expect_keyword(INTERVAL)?;
+                CreateSourceOptionName::StatisticsInterval
+            }
             _ => unreachable!(),
         };
         Ok(name)
@@ -3082,9 +3122,13 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_pg_connection_option(&mut self) -> Result<PgConfigOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[DETAILS, PUBLICATION, TEXT])? {
+        let name = match self.expect_one_of_keywords(&[DETAILS, PUBLICATION, SNAPSHOT, TEXT])? {
             DETAILS => PgConfigOptionName::Details,
             PUBLICATION => PgConfigOptionName::Publication,
+            SNAPSHOT => {
+                self.expect_keyword(GROUP)?;
+                PgConfigOptionName::SnapshotGroup
+            }
             TEXT => {
                 self.expect_keyword(COLUMNS)?;
 
@@ -3604,6 +3648,7 @@ impl<'a> Parser<'a> {
             REPLICAS,
             REPLICATION,
             SIZE,
+            TRANSACTION,
         ])?;
         let name = match option {
             AVAILABILITY => {
@@ -3627,6 +3672,10 @@ impl<'a> Parser<'a> {
                 ClusterOptionName::ReplicationFactor
             }
             SIZE => ClusterOptionName::Size,
+            TRANSACTION => {
+                self.expect_keyword(ISOLATION)?;
+                ClusterOptionName::TransactionIsolation
+            }
             _ => unreachable!(),
         };
         Ok(name)
@@ -3818,6 +3867,11 @@ impl<'a> Parser<'a> {
         Ok(Statement::Discard(DiscardStatement { target }))
     }
 
+    fn parse_undrop(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let id = self.parse_identifier()?;
+        Ok(Statement::Undrop(UndropStatement { id }))
+    }
+
     fn parse_drop(&mut self) -> Result<Statement<Raw>, ParserStatementError> {
         if self.parse_keyword(OWNED) {
             self.parse_drop_owned()
@@ -4733,6 +4787,13 @@ impl<'a> Parser<'a> {
                 .map_no_statement_parser_err()?
             {
                 RESET => {
+                    if self.parse_keyword(PROGRESS) {
+                        return Ok(Statement::AlterSink(AlterSinkStatement {
+                            sink_name: name,
+                            if_exists,
+                            action: AlterSinkAction::ResetProgress,
+                        }));
+                    }
                     self.expect_token(&Token::LParen)
                         .map_parser_err(StatementKind::AlterSink)?;
                     let reset_options = self
@@ -6521,6 +6582,8 @@ impl<'a> Parser<'a> {
             Ok(ShowStatement::ShowVariable(ShowVariableStatement {
                 variable: ident!("cluster"),
             }))
+        } else if self.parse_keyword(TIMESTAMP) {
+            Ok(ShowStatement::ShowTimestamp(ShowTimestampStatement {}))
         } else if self.parse_keyword(PRIVILEGES) {
             self.parse_show_privileges()
         } else if self.parse_keywords(&[DEFAULT, PRIVILEGES]) {
@@ -6647,9 +6710,19 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_inspect(&mut self) -> Result<ShowStatement<Raw>, ParserError> {
-        self.expect_keyword(SHARD)?;
-        let id = self.parse_literal_string()?;
-        Ok(ShowStatement::InspectShard(InspectShardStatement { id }))
+        match self.expect_one_of_keywords(&[SHARD, COLLECTION])? {
+            SHARD => {
+                let id = self.parse_literal_string()?;
+                Ok(ShowStatement::InspectShard(InspectShardStatement { id }))
+            }
+            COLLECTION => {
+                let name = self.parse_raw_name()?;
+                Ok(ShowStatement::InspectCollection(
+                    InspectCollectionStatement { name },
+                ))
+            }
+            _ => unreachable!(),
+        }
     }
 
     fn parse_table_and_joins(&mut self) -> Result<TableWithJoins<Raw>, ParserError> {
@@ -7208,8 +7281,13 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_subscribe_option(&mut self) -> Result<SubscribeOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[PROGRESS, SNAPSHOT])? {
+        let name = match self.expect_one_of_keywords(&[PROGRESS, RESUME, SNAPSHOT])? {
             PROGRESS => SubscribeOptionName::Progress,
+            RESUME => match self.expect_one_of_keywords(&[FROM, TOKENS])? {
+                FROM => SubscribeOptionName::ResumeFrom,
+                TOKENS => SubscribeOptionName::ResumeTokens,
+                _ => unreachable!(),
+            },
             SNAPSHOT => SubscribeOptionName::Snapshot,
             _ => unreachable!(),
         };
@@ -33,7 +33,9 @@ use crate::plan::expr::{
 };
 use crate::plan::query::{self, ExprContext, QueryContext};
 use crate::plan::scope::Scope;
-use crate::plan::side_effecting_func::PG_CATALOG_SEF_BUILTINS;
+use crate::plan::side_effecting_func::{
+    SideEffectingFuncImpl, MZ_INTERNAL_SEF_BUILTINS, PG_CATALOG_SEF_BUILTINS,
+};
 use crate::plan::transform_ast;
 use crate::plan::typeconv::{self, CastContext};
 use crate::session::vars::{self, ENABLE_TIME_AT_TIME_ZONE};
@@ -3381,8 +3383,22 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
     // Add side-effecting functions, which are defined in a separate module
     // using a restricted set of function definition features (e.g., no
     // overloads) to make them easier to plan.
-    for sef_builtin in PG_CATALOG_SEF_BUILTINS.values() {
-        builtins.insert(
+    insert_sef_builtins(&mut builtins, &PG_CATALOG_SEF_BUILTINS);
+
+    builtins
+});
+
+/// Adds the given side-effecting functions to a builtin function map.
+///
+/// Calls to side-effecting functions are planned specially, so the function
+/// definitions added here only serve name resolution and error out when
+/// planned in any other position.
+fn insert_sef_builtins(
+    builtins: &mut BTreeMap<&'static str, Func>,
+    sef_builtins: &'static BTreeMap<u32, SideEffectingFuncImpl>,
+) {
+    for sef_builtin in sef_builtins.values() {
+        let old = builtins.insert(
             sef_builtin.name,
             Func::Scalar(vec![FuncImpl {
                 oid: sef_builtin.oid,
@@ -3401,10 +3417,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
                 }),
             }]),
         );
+        assert!(old.is_none(), "duplicate entry in builtins list {:?}", old);
     }
-
-    builtins
-});
+}
 
 pub static INFORMATION_SCHEMA_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
     use ParamType::*;
@@ -3825,7 +3840,7 @@ pub static MZ_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
 pub static MZ_INTERNAL_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
     use ParamType::*;
     use ScalarBaseType::*;
-    builtins! {
+    let mut builtins = builtins! {
         "aclitem_grantor" => Scalar {
             params!(AclItem) => UnaryFunc::AclItemGrantor(func::AclItemGrantor) => Oid, oid::FUNC_ACL_ITEM_GRANTOR_OID;
         },
@@ -4201,7 +4216,11 @@ pub static MZ_INTERNAL_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(
         "mz_validate_role_privilege" => Scalar {
             params!(String) => UnaryFunc::MzValidateRolePrivilege(func::MzValidateRolePrivilege) => Bool, oid::FUNC_MZ_VALIDATE_ROLE_PRIVILEGE_OID;
         }
-    }
+    };
+
+    insert_sef_builtins(&mut builtins, &MZ_INTERNAL_SEF_BUILTINS);
+
+    builtins
 });
 
 pub static MZ_UNSAFE_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
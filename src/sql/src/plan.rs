@@ -44,10 +44,12 @@ use mz_repr::explain::{ExplainConfig, ExplainFormat};
 use mz_repr::role_id::RoleId;
 use mz_repr::{ColumnName, Diff, GlobalId, RelationDesc, Row, ScalarType, Timestamp};
 use mz_sql_parser::ast::{
-    AlterSourceAddSubsourceOption, ConnectionOptionName, CreateSourceSubsource, QualifiedReplica,
-    TransactionIsolationLevel, TransactionMode, WithOptionValue,
+    AlterSourceAddSubsourceOption, ConnectionOptionName, CreateSourceOption,
+    CreateSourceOptionName, CreateSourceSubsource, QualifiedReplica, TransactionIsolationLevel,
+    TransactionMode, WithOptionValue,
 };
 use mz_storage_types::connections::inline::ReferencedConnection;
+use mz_storage_types::parameters::SourceParameterOverrides;
 use mz_storage_types::sinks::{SinkEnvelope, StorageSinkConnection};
 use mz_storage_types::sources::{SourceDesc, Timeline};
 use proptest_derive::Arbitrary;
@@ -84,6 +86,7 @@ pub(crate) mod with_options;
 
 use crate::plan;
 use crate::plan::with_options::OptionalDuration;
+use crate::session::vars::IsolationLevel;
 pub use error::PlanError;
 pub use explain::normalize_subqueries;
 pub use expr::{
@@ -130,7 +133,9 @@ pub enum Plan {
     ShowCreate(ShowCreatePlan),
     ShowColumns(ShowColumnsPlan),
     ShowVariable(ShowVariablePlan),
+    ShowTimestamp,
     InspectShard(InspectShardPlan),
+    InspectCollection(InspectCollectionPlan),
     SetVariable(SetVariablePlan),
     ResetVariable(ResetVariablePlan),
     SetTransaction(SetTransactionPlan),
@@ -152,6 +157,7 @@ pub enum Plan {
     AlterIndexResetOptions(AlterIndexResetOptionsPlan),
     AlterSetCluster(AlterSetClusterPlan),
     AlterConnection(AlterConnectionPlan),
+    AlterSink(AlterSinkPlan),
     AlterSource(AlterSourcePlan),
     PurifiedAlterSource {
         // The `ALTER SOURCE` plan
@@ -187,6 +193,7 @@ pub enum Plan {
     ReassignOwned(ReassignOwnedPlan),
     SideEffectingFunc(SideEffectingFunc),
     ValidateConnection(ValidateConnectionPlan),
+    Undrop(UndropPlan),
 }
 
 impl Plan {
@@ -227,7 +234,7 @@ impl Plan {
                 vec![PlanKind::AlterNoop, PlanKind::AlterSetCluster]
             }
             // TODO: If we ever support ALTER SINK again, this will need to be changed
-            StatementKind::AlterSink => vec![PlanKind::AlterNoop],
+            StatementKind::AlterSink => vec![PlanKind::AlterNoop, PlanKind::AlterSink],
             StatementKind::AlterSource => vec![PlanKind::AlterNoop, PlanKind::AlterSource],
             StatementKind::AlterSystemReset => {
                 vec![PlanKind::AlterNoop, PlanKind::AlterSystemReset]
@@ -294,12 +301,15 @@ impl Plan {
                 PlanKind::ShowCreate,
                 PlanKind::ShowColumns,
                 PlanKind::ShowAllVariables,
+                PlanKind::ShowTimestamp,
                 PlanKind::InspectShard,
+                PlanKind::InspectCollection,
             ],
             StatementKind::StartTransaction => vec![PlanKind::StartTransaction],
             StatementKind::Subscribe => vec![PlanKind::Subscribe],
             StatementKind::Update => vec![PlanKind::ReadThenWrite],
             StatementKind::ValidateConnection => vec![PlanKind::ValidateConnection],
+            StatementKind::Undrop => vec![PlanKind::Undrop],
         }
     }
 
@@ -347,7 +357,10 @@ impl Plan {
             Plan::ShowCreate(_) => "show create",
             Plan::ShowColumns(_) => "show columns",
             Plan::ShowVariable(_) => "show variable",
+            Plan::ShowTimestamp => "show timestamp",
             Plan::InspectShard(_) => "inspect shard",
+            Plan::InspectCollection(_) => "inspect collection",
+            Plan::Undrop(_) => "undrop",
             Plan::SetVariable(_) => "set variable",
             Plan::ResetVariable(_) => "reset variable",
             Plan::SetTransaction(_) => "set transaction",
@@ -387,6 +400,7 @@ impl Plan {
             Plan::AlterIndexSetOptions(_) => "alter index",
             Plan::AlterIndexResetOptions(_) => "alter index",
             Plan::AlterConnection(_) => "alter connection",
+            Plan::AlterSink(_) => "alter sink",
             Plan::AlterSource(_) | Plan::PurifiedAlterSource { .. } => "alter source",
             Plan::AlterItemRename(_) => "rename item",
             Plan::AlterItemSwap(_) => "swap item",
@@ -493,6 +507,8 @@ pub struct CreateRolePlan {
 pub struct CreateClusterPlan {
     pub name: String,
     pub variant: CreateClusterVariant,
+    /// The default isolation level of reads on the cluster.
+    pub transaction_isolation: Option<IsolationLevel>,
 }
 
 #[derive(Debug)]
@@ -686,6 +702,17 @@ pub struct InspectShardPlan {
     pub id: GlobalId,
 }
 
+#[derive(Debug)]
+pub struct InspectCollectionPlan {
+    pub id: GlobalId,
+}
+
+#[derive(Debug)]
+pub struct UndropPlan {
+    /// The soft-dropped item to restore.
+    pub id: GlobalId,
+}
+
 #[derive(Debug)]
 pub struct SetVariablePlan {
     pub name: String,
@@ -743,6 +770,11 @@ pub struct SubscribePlan {
     pub up_to: Option<MirScalarExpr>,
     pub copy_to: Option<CopyFormat>,
     pub emit_progress: bool,
+    /// Whether progress messages include a token to resume from.
+    pub emit_resume_tokens: bool,
+    /// The token of a previous subscribe to resume from, which overrides `when` and
+    /// `with_snapshot`.
+    pub resume_from: Option<String>,
     pub output: SubscribeOutput,
 }
 
@@ -996,6 +1028,22 @@ pub enum AlterSourceAction {
         details: Option<WithOptionValue<Aug>>,
         options: Vec<AlterSourceAddSubsourceOption<Aug>>,
     },
+    /// Sets and resets tuning parameters of the source, which are options of its definition.
+    SetParameterOverrides {
+        set: Vec<CreateSourceOption<Aug>>,
+        reset: Vec<CreateSourceOptionName>,
+    },
+}
+
+#[derive(Debug)]
+pub enum AlterSinkAction {
+    ResetProgress,
+}
+
+#[derive(Debug)]
+pub struct AlterSinkPlan {
+    pub id: GlobalId,
+    pub action: AlterSinkAction,
 }
 
 #[derive(Debug)]
@@ -1237,6 +1285,8 @@ pub struct Source {
     pub data_source: DataSourceDesc,
     pub desc: RelationDesc,
     pub compaction_window: Option<CompactionWindow>,
+    /// The tuning parameters the source overrides with the options in its definition.
+    pub parameter_overrides: SourceParameterOverrides,
 }
 
 #[derive(Debug, Clone)]
@@ -1420,7 +1470,7 @@ pub struct Type {
 }
 
 /// Specifies when a `Peek` or `Subscribe` should occur.
-#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[derive(Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum QueryWhen {
     /// The peek should occur at the latest possible timestamp that allows the
     /// peek to complete immediately.
@@ -1534,6 +1584,7 @@ pub struct PlanClusterOption {
     pub replication_factor: AlterOptionParameter<u32>,
     pub size: AlterOptionParameter,
     pub disk: AlterOptionParameter<bool>,
+    pub transaction_isolation: AlterOptionParameter<IsolationLevel>,
 }
 
 impl Default for PlanClusterOption {
@@ -1548,6 +1599,7 @@ impl Default for PlanClusterOption {
             replication_factor: AlterOptionParameter::Unchanged,
             size: AlterOptionParameter::Unchanged,
             disk: AlterOptionParameter::Unchanged,
+            transaction_isolation: AlterOptionParameter::Unchanged,
         }
     }
 }
@@ -1808,7 +1808,12 @@ fn plan_set_expr(
                     show::show_objects(qcx.scx, stmt)?.plan_hir(qcx)
                 }
                 ShowStatement::ShowVariable(_) => bail_unsupported!("SHOW variable in subqueries"),
-                ShowStatement::InspectShard(_) => sql_bail!("unsupported INSPECT statement"),
+                ShowStatement::ShowTimestamp(_) => {
+                    bail_unsupported!("SHOW TIMESTAMP in subqueries")
+                }
+                ShowStatement::InspectShard(_) | ShowStatement::InspectCollection(_) => {
+                    sql_bail!("unsupported INSPECT statement")
+                }
             }
         }
     }
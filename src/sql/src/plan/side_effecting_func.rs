@@ -35,6 +35,8 @@ use enum_kinds::EnumKind;
 use mz_ore::cast::ReinterpretCast;
 use mz_ore::collections::CollectionExt;
 use mz_ore::result::ResultExt;
+use mz_pgrepr::oid;
+use mz_repr::adt::interval::Interval;
 use mz_repr::RelationType;
 use mz_repr::{ColumnType, Datum, RelationDesc, RowArena, ScalarType};
 use mz_sql_parser::ast::{CteBlock, Expr, Function, FunctionArgs, Select, SelectItem, SetExpr};
@@ -62,6 +64,27 @@ pub enum SideEffectingFunc {
         // The ID of the connection to cancel.
         connection_id: u32,
     },
+    /// The `mz_quiesce_cluster` function.
+    MzQuiesceCluster {
+        // The name of the cluster to quiesce.
+        cluster_name: String,
+    },
+    /// The `mz_unquiesce_cluster` function.
+    MzUnquiesceCluster {
+        // The name of the cluster to stop quiescing.
+        cluster_name: String,
+    },
+    /// The `mz_estimate_compaction` function.
+    ///
+    /// It has no side effects, but needs the storage controller to inspect the
+    /// collection's shard, which only the adapter layer can reach.
+    MzEstimateCompaction {
+        // The name of the storage collection to estimate compaction for, or
+        // `None` if it is NULL.
+        name: Option<String>,
+        // The proposed compaction window, or `None` if it is NULL.
+        retain_history: Option<Interval>,
+    },
 }
 
 /// Describes a `SELECT` if it contains calls to side-effecting functions.
@@ -188,7 +211,10 @@ fn extract_sef_call(
     };
 
     // Check whether the implementation is a known side-effecting function.
-    let Some(sef_impl) = PG_CATALOG_SEF_BUILTINS.get(&func_impl.oid) else {
+    let Some(sef_impl) = PG_CATALOG_SEF_BUILTINS
+        .get(&func_impl.oid)
+        .or_else(|| MZ_INTERNAL_SEF_BUILTINS.get(&func_impl.oid))
+    else {
         return Ok(None);
     };
 
@@ -273,9 +299,23 @@ pub static PG_CATALOG_SEF_BUILTINS: Lazy<BTreeMap<u32, SideEffectingFuncImpl>> =
         .collect()
 });
 
+/// A map of the side-effecting functions in the `mz_internal` schema, keyed by
+/// OID.
+pub static MZ_INTERNAL_SEF_BUILTINS: Lazy<BTreeMap<u32, SideEffectingFuncImpl>> = Lazy::new(|| {
+    [
+        MZ_QUIESCE_CLUSTER,
+        MZ_UNQUIESCE_CLUSTER,
+        MZ_ESTIMATE_COMPACTION,
+    ]
+    .into_iter()
+    .map(|f| (f.oid, f))
+    .collect()
+});
+
 // Implementations of each side-effecting function follow.
 //
-// If you add a new side-effecting function, be sure to add it to the map above.
+// If you add a new side-effecting function, be sure to add it to one of the
+// maps above.
 
 const PG_CANCEL_BACKEND: SideEffectingFuncImpl = SideEffectingFuncImpl {
     name: "pg_cancel_backend",
@@ -288,3 +328,40 @@ const PG_CANCEL_BACKEND: SideEffectingFuncImpl = SideEffectingFuncImpl {
         }
     },
 };
+
+const MZ_QUIESCE_CLUSTER: SideEffectingFuncImpl = SideEffectingFuncImpl {
+    name: "mz_quiesce_cluster",
+    oid: oid::FUNC_MZ_QUIESCE_CLUSTER_OID,
+    param_types: &[ScalarType::String],
+    return_type: ScalarType::Bool.nullable(false),
+    plan_fn: |datums| -> SideEffectingFunc {
+        SideEffectingFunc::MzQuiesceCluster {
+            cluster_name: datums[0].unwrap_str().to_string(),
+        }
+    },
+};
+
+const MZ_UNQUIESCE_CLUSTER: SideEffectingFuncImpl = SideEffectingFuncImpl {
+    name: "mz_unquiesce_cluster",
+    oid: oid::FUNC_MZ_UNQUIESCE_CLUSTER_OID,
+    param_types: &[ScalarType::String],
+    return_type: ScalarType::Bool.nullable(false),
+    plan_fn: |datums| -> SideEffectingFunc {
+        SideEffectingFunc::MzUnquiesceCluster {
+            cluster_name: datums[0].unwrap_str().to_string(),
+        }
+    },
+};
+
+const MZ_ESTIMATE_COMPACTION: SideEffectingFuncImpl = SideEffectingFuncImpl {
+    name: "mz_estimate_compaction",
+    oid: oid::FUNC_MZ_ESTIMATE_COMPACTION_OID,
+    param_types: &[ScalarType::String, ScalarType::Interval],
+    return_type: ScalarType::Jsonb.nullable(true),
+    plan_fn: |datums| -> SideEffectingFunc {
+        SideEffectingFunc::MzEstimateCompaction {
+            name: (!datums[0].is_null()).then(|| datums[0].unwrap_str().to_string()),
+            retain_history: (!datums[1].is_null()).then(|| datums[1].unwrap_interval()),
+        }
+    },
+};
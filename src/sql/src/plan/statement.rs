@@ -207,6 +207,9 @@ pub fn describe(
         Statement::Show(ShowStatement::ShowVariable(stmt)) => {
             scl::describe_show_variable(&scx, stmt)?
         }
+        Statement::Show(ShowStatement::ShowTimestamp(stmt)) => {
+            scl::describe_show_timestamp(&scx, stmt)?
+        }
 
         // DML statements.
         Statement::Copy(stmt) => dml::describe_copy(&scx, stmt)?,
@@ -230,7 +233,11 @@ pub fn describe(
         Statement::Show(ShowStatement::InspectShard(stmt)) => {
             scl::describe_inspect_shard(&scx, stmt)?
         }
+        Statement::Show(ShowStatement::InspectCollection(stmt)) => {
+            scl::describe_inspect_collection(&scx, stmt)?
+        }
         Statement::ValidateConnection(stmt) => validate::describe_validate_connection(&scx, stmt)?,
+        Statement::Undrop(stmt) => ddl::describe_undrop(&scx, stmt)?,
     };
 
     let desc = desc.with_params(scx.finalize_param_types()?);
@@ -387,6 +394,7 @@ pub fn plan(
         Statement::ResetVariable(stmt) => scl::plan_reset_variable(scx, stmt),
         Statement::SetVariable(stmt) => scl::plan_set_variable(scx, stmt),
         Statement::Show(ShowStatement::ShowVariable(stmt)) => scl::plan_show_variable(scx, stmt),
+        Statement::Show(ShowStatement::ShowTimestamp(stmt)) => scl::plan_show_timestamp(scx, stmt),
 
         // TCL statements.
         Statement::Commit(stmt) => tcl::plan_commit(scx, stmt),
@@ -397,7 +405,11 @@ pub fn plan(
         // Other statements.
         Statement::Raise(stmt) => raise::plan_raise(scx, stmt),
         Statement::Show(ShowStatement::InspectShard(stmt)) => scl::plan_inspect_shard(scx, stmt),
+        Statement::Show(ShowStatement::InspectCollection(stmt)) => {
+            scl::plan_inspect_collection(scx, stmt)
+        }
         Statement::ValidateConnection(stmt) => validate::plan_validate_connection(scx, stmt),
+        Statement::Undrop(stmt) => ddl::plan_undrop(scx, stmt),
     };
 
     if let Ok(plan) = &plan {
@@ -33,6 +33,7 @@ use mz_proto::RustType;
 use mz_repr::adt::interval::Interval;
 use mz_repr::adt::mz_acl_item::{MzAclItem, PrivilegeMap};
 use mz_repr::adt::system::Oid;
+use mz_repr::bytes::ByteSize;
 use mz_repr::role_id::RoleId;
 use mz_repr::{
     strconv, ColumnName, ColumnType, GlobalId, RelationDesc, RelationType, ScalarType, Timestamp,
@@ -42,34 +43,36 @@ use mz_sql_parser::ast::{
     self, AlterClusterAction, AlterClusterStatement, AlterConnectionAction, AlterConnectionOption,
     AlterConnectionOptionName, AlterConnectionStatement, AlterIndexAction, AlterIndexStatement,
     AlterObjectRenameStatement, AlterObjectSwapStatement, AlterRoleOption, AlterRoleStatement,
-    AlterSecretStatement, AlterSetClusterStatement, AlterSinkStatement, AlterSourceAction,
-    AlterSourceAddSubsourceOption, AlterSourceAddSubsourceOptionName, AlterSourceStatement,
-    AlterSystemResetAllStatement, AlterSystemResetStatement, AlterSystemSetStatement, AvroSchema,
-    AvroSchemaOption, AvroSchemaOptionName, ClusterOption, ClusterOptionName, ColumnOption,
-    CommentObjectType, CommentStatement, CreateClusterReplicaStatement, CreateClusterStatement,
-    CreateConnectionOption, CreateConnectionOptionName, CreateConnectionStatement,
-    CreateConnectionType, CreateDatabaseStatement, CreateIndexStatement,
-    CreateMaterializedViewStatement, CreateRoleStatement, CreateSchemaStatement,
-    CreateSecretStatement, CreateSinkConnection, CreateSinkOption, CreateSinkOptionName,
-    CreateSinkStatement, CreateSourceConnection, CreateSourceFormat, CreateSourceOption,
-    CreateSourceOptionName, CreateSourceStatement, CreateSubsourceOption,
-    CreateSubsourceOptionName, CreateSubsourceStatement, CreateTableStatement, CreateTypeAs,
-    CreateTypeListOption, CreateTypeListOptionName, CreateTypeMapOption, CreateTypeMapOptionName,
-    CreateTypeStatement, CreateViewStatement, CreateWebhookSourceStatement, CsrConfigOption,
-    CsrConfigOptionName, CsrConnection, CsrConnectionAvro, CsrConnectionProtobuf, CsrSeedProtobuf,
-    CsvColumns, DeferredItemName, DocOnIdentifier, DocOnSchema, DropObjectsStatement,
-    DropOwnedStatement, Expr, Format, Ident, IfExistsBehavior, IndexOption, IndexOptionName,
-    KafkaSinkConfigOption, KeyConstraint, LoadGeneratorOption, LoadGeneratorOptionName,
-    MaterializedViewOption, MaterializedViewOptionName, MySqlConfigOption, MySqlConfigOptionName,
-    PgConfigOption, PgConfigOptionName, ProtobufSchema, QualifiedReplica, ReferencedSubsources,
+    AlterSecretStatement, AlterSetClusterStatement, AlterSinkAction, AlterSinkStatement,
+    AlterSourceAction, AlterSourceAddSubsourceOption, AlterSourceAddSubsourceOptionName,
+    AlterSourceStatement, AlterSystemResetAllStatement, AlterSystemResetStatement,
+    AlterSystemSetStatement, AvroSchema, AvroSchemaOption, AvroSchemaOptionName, ClusterOption,
+    ClusterOptionName, ColumnOption, CommentObjectType, CommentStatement,
+    CreateClusterReplicaStatement, CreateClusterStatement, CreateConnectionOption,
+    CreateConnectionOptionName, CreateConnectionStatement, CreateConnectionType,
+    CreateDatabaseStatement, CreateIndexStatement, CreateMaterializedViewStatement,
+    CreateRoleStatement, CreateSchemaStatement, CreateSecretStatement, CreateSinkConnection,
+    CreateSinkOption, CreateSinkOptionName, CreateSinkStatement, CreateSourceConnection,
+    CreateSourceFormat, CreateSourceOption, CreateSourceOptionName, CreateSourceStatement,
+    CreateSubsourceOption, CreateSubsourceOptionName, CreateSubsourceStatement,
+    CreateTableStatement, CreateTypeAs, CreateTypeListOption, CreateTypeListOptionName,
+    CreateTypeMapOption, CreateTypeMapOptionName, CreateTypeStatement, CreateViewStatement,
+    CreateWebhookSourceStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection,
+    CsrConnectionAvro, CsrConnectionProtobuf, CsrSeedProtobuf, CsvColumns, DeferredItemName,
+    DocOnIdentifier, DocOnSchema, DropObjectsStatement, DropOwnedStatement, Expr, Format, Ident,
+    IfExistsBehavior, IndexOption, IndexOptionName, KafkaSinkConfigOption, KeyConstraint,
+    LoadGeneratorOption, LoadGeneratorOptionName, MaterializedViewOption,
+    MaterializedViewOptionName, MySqlConfigOption, MySqlConfigOptionName, PgConfigOption,
+    PgConfigOptionName, ProtobufSchema, QualifiedReplica, ReferencedSubsources,
     RefreshAtOptionValue, RefreshEveryOptionValue, RefreshOptionValue, ReplicaDefinition,
     ReplicaOption, ReplicaOptionName, RoleAttribute, SetRoleVar, SourceIncludeMetadata, Statement,
-    TableConstraint, TableOption, TableOptionName, UnresolvedDatabaseName, UnresolvedItemName,
-    UnresolvedObjectName, UnresolvedSchemaName, Value, ViewDefinition,
+    TableConstraint, TableOption, TableOptionName, UndropStatement, UnresolvedDatabaseName,
+    UnresolvedItemName, UnresolvedObjectName, UnresolvedSchemaName, Value, ViewDefinition,
 };
 use mz_sql_parser::ident;
 use mz_storage_types::connections::inline::{ConnectionAccess, ReferencedConnection};
 use mz_storage_types::connections::Connection;
+use mz_storage_types::parameters::SourceParameterOverrides;
 use mz_storage_types::sinks::{
     KafkaIdStyle, KafkaSinkConnection, KafkaSinkFormat, SinkEnvelope, StorageSinkConnection,
 };
@@ -117,7 +120,7 @@ use crate::plan::{
     AlterClusterReplicaRenamePlan, AlterClusterSwapPlan, AlterConnectionPlan,
     AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan, AlterNoopPlan,
     AlterOptionParameter, AlterRolePlan, AlterSchemaRenamePlan, AlterSchemaSwapPlan,
-    AlterSecretPlan, AlterSetClusterPlan, AlterSourcePlan, AlterSystemResetAllPlan,
+    AlterSecretPlan, AlterSetClusterPlan, AlterSinkPlan, AlterSourcePlan, AlterSystemResetAllPlan,
     AlterSystemResetPlan, AlterSystemSetPlan, CommentPlan, ComputeReplicaConfig,
     ComputeReplicaIntrospectionConfig, CreateClusterManagedPlan, CreateClusterPlan,
     CreateClusterReplicaPlan, CreateClusterUnmanagedPlan, CreateClusterVariant,
@@ -126,7 +129,7 @@ use crate::plan::{
     CreateTablePlan, CreateTypePlan, CreateViewPlan, DataSourceDesc, DropObjectsPlan,
     DropOwnedPlan, FullItemName, HirScalarExpr, Index, Ingestion, MaterializedView, Params, Plan,
     PlanClusterOption, PlanNotice, QueryContext, ReplicaConfig, Secret, Sink, Source, Table, Type,
-    VariableValue, View, WebhookBodyFormat, WebhookHeaderFilters, WebhookHeaders,
+    UndropPlan, VariableValue, View, WebhookBodyFormat, WebhookHeaderFilters, WebhookHeaders,
     WebhookValidation,
 };
 use crate::session::vars;
@@ -414,13 +417,20 @@ generate_extracted_config!(
     (IgnoreKeys, bool),
     (Timeline, String),
     (TimestampInterval, Duration),
-    (RetainHistory, Duration)
+    (RetainHistory, Duration),
+    (MaxInflightBytes, ByteSize),
+    (SnapshotMaxInflightBytes, ByteSize),
+    (SnapshotReadCommitted, bool),
+    (MaxBatchSize, u64),
+    (YieldBudget, u64),
+    (StatisticsInterval, Duration)
 );
 
 generate_extracted_config!(
     PgConfigOption,
     (Details, String),
     (Publication, String),
+    (SnapshotGroup, String),
     (TextColumns, Vec::<UnresolvedItemName>, Default(vec![]))
 );
 
@@ -576,6 +586,7 @@ pub fn plan_create_webhook_source(
             },
             desc,
             compaction_window: None,
+            parameter_overrides: Default::default(),
         },
         if_not_exists,
         timeline,
@@ -768,6 +779,7 @@ pub fn plan_create_source(
             let PgConfigOptionExtracted {
                 details,
                 publication,
+                snapshot_group,
                 text_columns,
                 seen: _,
             } = options.clone().try_into()?;
@@ -977,6 +989,9 @@ pub fn plan_create_source(
                     table_casts,
                     publication: publication.expect("validated exists during purification"),
                     publication_details,
+                    snapshot_group,
+                    // Set below if the source is in the LSN timeline.
+                    lsn_timestamps: false,
                 });
 
             (connection, Some(available_subsources))
@@ -1129,9 +1144,72 @@ pub fn plan_create_source(
         timestamp_interval,
         ignore_keys,
         retain_history,
+        max_inflight_bytes,
+        snapshot_max_inflight_bytes,
+        snapshot_read_committed,
+        max_batch_size,
+        yield_budget,
+        statistics_interval,
         seen: _,
     } = CreateSourceOptionExtracted::try_from(with_options.clone())?;
 
+    if snapshot_read_committed.is_some()
+        && !matches!(external_connection, GenericSourceConnection::Postgres(_))
+    {
+        sql_bail!("SNAPSHOT READ COMMITTED is only supported for PostgreSQL sources");
+    }
+    if max_inflight_bytes.is_some()
+        || snapshot_max_inflight_bytes.is_some()
+        || max_batch_size.is_some()
+        || yield_budget.is_some()
+        || statistics_interval.is_some()
+    {
+        scx.require_feature_flag(&vars::ENABLE_SOURCE_PARAMETER_OVERRIDES)?;
+    }
+    for (name, value) in [
+        (CreateSourceOptionName::MaxBatchSize, max_batch_size),
+        (CreateSourceOptionName::YieldBudget, yield_budget),
+    ] {
+        if value == Some(0) {
+            sql_bail!("{} must be greater than zero", name.to_ast_string());
+        }
+    }
+    if statistics_interval == Some(Duration::ZERO) {
+        sql_bail!("STATISTICS INTERVAL must be greater than zero");
+    }
+    // The tuning parameters are part of the source's definition, so they survive restarts and
+    // show up in `SHOW CREATE SOURCE`. `ALTER SOURCE` changes them by amending the definition.
+    let parameter_overrides = SourceParameterOverrides {
+        max_inflight_bytes: max_inflight_bytes.map(|b| usize::cast_from(b.as_bytes())),
+        snapshot_max_inflight_bytes: snapshot_max_inflight_bytes
+            .map(|b| usize::cast_from(b.as_bytes())),
+        snapshot_read_committed,
+        max_batch_size: max_batch_size.map(usize::cast_from),
+        yield_budget: yield_budget.map(usize::cast_from),
+        statistics_interval,
+    };
+
+    // PostgreSQL sources can be placed in the timeline of their upstream LSNs, in which case their
+    // data is timestamped with the LSNs it was committed at rather than reclocked. The LSNs of
+    // sources that use the same connection are comparable, so they share a timeline.
+    let lsn_timeline_connection = if timeline.as_deref() == Some("mz_pg_lsn") {
+        scx.require_feature_flag(&vars::ENABLE_PG_LSN_TIMELINE)?;
+        let connection_id = match &mut external_connection {
+            GenericSourceConnection::Postgres(conn) => {
+                conn.lsn_timestamps = true;
+                conn.connection_id
+            }
+            _ => sql_bail!("TIMELINE 'mz_pg_lsn' is only supported for PostgreSQL sources"),
+        };
+        // Compaction windows are durations of wall clock time, which LSNs don't measure.
+        if retain_history.is_some() {
+            sql_bail!("RETAIN HISTORY is not supported for sources in TIMELINE 'mz_pg_lsn'");
+        }
+        Some(connection_id)
+    } else {
+        None
+    };
+
     let encoding = match format {
         Some(format) => Some(get_encoding(scx, format, &envelope)?),
         None => None,
@@ -1362,6 +1440,10 @@ pub fn plan_create_source(
     // Allow users to specify a timeline. If they do not, determine a default
     // timeline for the source.
     let timeline = match timeline {
+        _ if lsn_timeline_connection.is_some() => {
+            let connection_id = lsn_timeline_connection.expect("checked above");
+            Timeline::PostgresLsn(connection_id.to_string())
+        }
         None => match envelope {
             SourceEnvelope::CdcV2 => {
                 Timeline::External(scx.catalog.resolve_full_name(&name).to_string())
@@ -1393,6 +1475,7 @@ pub fn plan_create_source(
         }),
         desc,
         compaction_window,
+        parameter_overrides,
     };
 
     Ok(Plan::CreateSource(CreateSourcePlan {
@@ -1553,6 +1636,7 @@ pub fn plan_create_subsource(
         },
         desc,
         compaction_window: None,
+        parameter_overrides: Default::default(),
     };
 
     Ok(Plan::CreateSource(CreateSourcePlan {
@@ -3273,7 +3357,8 @@ generate_extracted_config!(
     (Managed, bool),
     (Replicas, Vec<ReplicaDefinition<Aug>>),
     (ReplicationFactor, u32),
-    (Size, String)
+    (Size, String),
+    (TransactionIsolation, String)
 );
 
 pub fn plan_create_cluster(
@@ -3291,9 +3376,13 @@ pub fn plan_create_cluster(
         seen: _,
         size,
         disk: disk_in,
+        transaction_isolation,
     }: ClusterOptionExtracted = options.try_into()?;
 
     let managed = managed.unwrap_or_else(|| replicas.is_none());
+    let transaction_isolation = transaction_isolation
+        .map(|level| plan_cluster_transaction_isolation(&level))
+        .transpose()?;
 
     if managed {
         if replicas.is_some() {
@@ -3344,6 +3433,7 @@ pub fn plan_create_cluster(
                 compute,
                 disk,
             }),
+            transaction_isolation,
         }))
     } else {
         let Some(replica_defs) = replicas else {
@@ -3378,10 +3468,22 @@ pub fn plan_create_cluster(
         Ok(Plan::CreateCluster(CreateClusterPlan {
             name: normalize::ident(name),
             variant: CreateClusterVariant::Unmanaged(CreateClusterUnmanagedPlan { replicas }),
+            transaction_isolation,
         }))
     }
 }
 
+/// Plans the default isolation level of reads on a cluster.
+fn plan_cluster_transaction_isolation(level: &str) -> Result<vars::IsolationLevel, PlanError> {
+    let level = vars::IsolationLevel::parse_str(level)?;
+    // Strong session serializable relies on timestamp oracles local to the session, which a
+    // cluster can't opt sessions into.
+    if level == vars::IsolationLevel::StrongSessionSerializable {
+        sql_bail!("TRANSACTION ISOLATION '{level}' not supported for clusters");
+    }
+    Ok(level)
+}
+
 generate_extracted_config!(
     ReplicaOption,
     (AvailabilityZone, String),
@@ -4057,6 +4159,25 @@ fn dependency_prevents_drop(object_type: ObjectType, dep: &dyn CatalogItem) -> b
     }
 }
 
+pub fn describe_undrop(
+    _: &StatementContext,
+    _: UndropStatement,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_undrop(
+    scx: &StatementContext,
+    UndropStatement { id }: UndropStatement,
+) -> Result<Plan, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_SOFT_DROP)?;
+    let id: GlobalId = id
+        .as_str()
+        .parse()
+        .map_err(|_| sql_err!("invalid id {}", id.as_str().quoted()))?;
+    Ok(Plan::Undrop(UndropPlan { id }))
+}
+
 pub fn describe_alter_index_options(
     _: &StatementContext,
     _: AlterIndexStatement<Aug>,
@@ -4440,6 +4561,7 @@ pub fn plan_alter_cluster(
                 seen: _,
                 size,
                 disk,
+                transaction_isolation,
             }: ClusterOptionExtracted = set_options.try_into()?;
 
             match managed.unwrap_or_else(|| cluster.is_managed()) {
@@ -4560,6 +4682,11 @@ pub fn plan_alter_cluster(
             if !replicas.is_empty() {
                 options.replicas = AlterOptionParameter::Set(replicas);
             }
+            if let Some(transaction_isolation) = transaction_isolation {
+                options.transaction_isolation = AlterOptionParameter::Set(
+                    plan_cluster_transaction_isolation(&transaction_isolation)?,
+                );
+            }
         }
         AlterClusterAction::ResetOptions(reset_options) => {
             use AlterOptionParameter::Reset;
@@ -4575,6 +4702,7 @@ pub fn plan_alter_cluster(
                     Replicas => options.replicas = Reset,
                     ReplicationFactor => options.replication_factor = Reset,
                     Size => options.size = Reset,
+                    TransactionIsolation => options.transaction_isolation = Reset,
                 }
             }
         }
@@ -5237,14 +5365,28 @@ pub fn plan_alter_sink(
     let AlterSinkStatement {
         sink_name,
         if_exists,
-        action: _,
+        action,
     } = stmt;
 
+    let action = match action {
+        AlterSinkAction::ResetProgress => crate::plan::AlterSinkAction::ResetProgress,
+        AlterSinkAction::SetOptions(_) | AlterSinkAction::ResetOptions(_) => {
+            bail_unsupported!("ALTER SINK");
+        }
+    };
+
     let sink_name = normalize::unresolved_item_name(sink_name)?;
     let entry = match scx.catalog.resolve_item(&sink_name) {
         Ok(sink) => sink,
         Err(_) if if_exists => {
-            bail_unsupported!("ALTER SINK");
+            scx.catalog.add_notice(PlanNotice::ObjectDoesNotExist {
+                name: sink_name.to_string(),
+                object_type: ObjectType::Sink,
+            });
+
+            return Ok(Plan::AlterNoop(AlterNoopPlan {
+                object_type: ObjectType::Sink,
+            }));
         }
         Err(e) => return Err(e.into()),
     };
@@ -5256,7 +5398,10 @@ pub fn plan_alter_sink(
         )
     }
 
-    bail_unsupported!("ALTER SINK");
+    Ok(Plan::AlterSink(AlterSinkPlan {
+        id: entry.id(),
+        action,
+    }))
 }
 
 pub fn describe_alter_source(
@@ -5272,6 +5417,19 @@ generate_extracted_config!(
     (TextColumns, Vec::<UnresolvedItemName>, Default(vec![]))
 );
 
+/// Whether `name` is a tuning parameter that `ALTER SOURCE` can override for a single source.
+fn is_source_parameter_override(name: &CreateSourceOptionName) -> bool {
+    matches!(
+        name,
+        CreateSourceOptionName::MaxInflightBytes
+            | CreateSourceOptionName::SnapshotMaxInflightBytes
+            | CreateSourceOptionName::SnapshotReadCommitted
+            | CreateSourceOptionName::MaxBatchSize
+            | CreateSourceOptionName::YieldBudget
+            | CreateSourceOptionName::StatisticsInterval
+    )
+}
+
 pub fn plan_alter_source(
     scx: &mut StatementContext,
     stmt: AlterSourceStatement<Aug>,
@@ -5307,15 +5465,31 @@ pub fn plan_alter_source(
 
     let action = match action {
         AlterSourceAction::SetOptions(options) => {
-            let option = options.into_iter().next().unwrap();
-            sql_bail!(
-                "Cannot modify the {} of a SOURCE.",
-                option.name.to_ast_string()
-            );
+            if let Some(option) = options
+                .iter()
+                .find(|o| !is_source_parameter_override(&o.name))
+            {
+                sql_bail!(
+                    "Cannot modify the {} of a SOURCE.",
+                    option.name.to_ast_string()
+                );
+            }
+            scx.require_feature_flag(&vars::ENABLE_SOURCE_PARAMETER_OVERRIDES)?;
+
+            // Validate the values here; they are applied by replanning the source's definition.
+            CreateSourceOptionExtracted::try_from(options.clone())?;
+            crate::plan::AlterSourceAction::SetParameterOverrides {
+                set: options,
+                reset: vec![],
+            }
         }
         AlterSourceAction::ResetOptions(reset) => {
-            let option = reset.into_iter().next().unwrap();
-            sql_bail!("Cannot modify the {} of a SOURCE.", option.to_ast_string());
+            if let Some(option) = reset.iter().find(|o| !is_source_parameter_override(o)) {
+                sql_bail!("Cannot modify the {} of a SOURCE.", option.to_ast_string());
+            }
+            scx.require_feature_flag(&vars::ENABLE_SOURCE_PARAMETER_OVERRIDES)?;
+
+            crate::plan::AlterSourceAction::SetParameterOverrides { set: vec![], reset }
         }
         AlterSourceAction::DropSubsources {
             if_exists,
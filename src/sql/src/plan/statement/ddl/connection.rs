@@ -57,6 +57,7 @@ generate_extracted_config!(
     (SecretAccessKey, with_options::Secret),
     (SecurityProtocol, String),
     (ServiceName, String),
+    (SnapshotHost, String),
     (SshTunnel, with_options::Object),
     (SslCertificate, StringOrSecret),
     (SslCertificateAuthority, StringOrSecret),
@@ -127,6 +128,7 @@ pub(super) fn validate_options_per_connection_type(
             Host,
             Password,
             Port,
+            SnapshotHost,
             SshTunnel,
             SslCertificate,
             SslCertificateAuthority,
@@ -351,6 +353,13 @@ impl ConnectionOptionExtracted {
                     }
                 }
                 let tunnel = scx.build_tunnel_definition(self.ssh_tunnel, self.aws_privatelink)?;
+                // PrivateLink endpoints determine the host to connect to, so there is no way to
+                // reach a different host for the snapshot.
+                if self.snapshot_host.is_some() && matches!(tunnel, Tunnel::AwsPrivatelink(_)) {
+                    sql_bail!(
+                        "invalid CONNECTION: SNAPSHOT HOST is not supported with AWS PRIVATELINK"
+                    )
+                }
 
                 Connection::Postgres(PostgresConnection {
                     database: self
@@ -368,6 +377,7 @@ impl ConnectionOptionExtracted {
                     user: self
                         .user
                         .ok_or_else(|| sql_err!("USER option is required"))?,
+                    snapshot_host: self.snapshot_host,
                 })
             }
             CreateConnectionType::Ssh => Connection::Ssh(SshConnection {
@@ -544,7 +544,13 @@ pub fn plan_query(
     })
 }
 
-generate_extracted_config!(SubscribeOption, (Snapshot, bool), (Progress, bool));
+generate_extracted_config!(
+    SubscribeOption,
+    (Snapshot, bool),
+    (Progress, bool),
+    (ResumeFrom, String),
+    (ResumeTokens, bool)
+);
 
 pub fn describe_subscribe(
     scx: &StatementContext,
@@ -562,7 +568,11 @@ pub fn describe_subscribe(
             desc
         }
     };
-    let SubscribeOptionExtracted { progress, .. } = stmt.options.try_into()?;
+    let SubscribeOptionExtracted {
+        progress,
+        resume_tokens,
+        ..
+    } = stmt.options.try_into()?;
     let progress = progress.unwrap_or(false);
     let mut desc = RelationDesc::empty().with_column(
         "mz_timestamp",
@@ -573,6 +583,9 @@ pub fn describe_subscribe(
     );
     if progress {
         desc = desc.with_column("mz_progressed", ScalarType::Bool.nullable(false));
+        if resume_tokens.unwrap_or(false) {
+            desc = desc.with_column("mz_resume_token", ScalarType::String.nullable(true));
+        }
     }
 
     let debezium = matches!(stmt.output, SubscribeOutput::EnvelopeDebezium { .. });
@@ -772,15 +785,36 @@ pub fn plan_subscribe(
     };
 
     let SubscribeOptionExtracted {
-        progress, snapshot, ..
+        progress,
+        snapshot,
+        resume_from,
+        resume_tokens,
+        ..
     } = options.try_into()?;
+    let emit_progress = progress.unwrap_or(false);
+    let emit_resume_tokens = resume_tokens.unwrap_or(false);
+    if emit_resume_tokens && !emit_progress {
+        sql_bail!("RESUME TOKENS requires PROGRESS");
+    }
+    if resume_from.is_some() {
+        // The token determines both the timestamp the subscribe starts at and that it starts
+        // without a snapshot.
+        if when.advance_to_timestamp().is_some() {
+            sql_bail!("RESUME FROM cannot be combined with AS OF");
+        }
+        if snapshot.is_some() {
+            sql_bail!("RESUME FROM cannot be combined with SNAPSHOT");
+        }
+    }
     Ok(Plan::Subscribe(SubscribePlan {
         from,
         when,
         up_to,
         with_snapshot: snapshot.unwrap_or(true),
         copy_to,
-        emit_progress: progress.unwrap_or(false),
+        emit_progress,
+        emit_resume_tokens,
+        resume_from,
         output,
     }))
 }
@@ -13,7 +13,7 @@
 //! like `DISCARD` and `SET`.
 
 use mz_repr::{GlobalId, RelationDesc, ScalarType};
-use mz_sql_parser::ast::InspectShardStatement;
+use mz_sql_parser::ast::{InspectCollectionStatement, InspectShardStatement};
 use std::time::Duration;
 use uncased::UncasedStr;
 
@@ -21,15 +21,16 @@ use crate::ast::display::AstDisplay;
 use crate::ast::{
     CloseStatement, DeallocateStatement, DeclareStatement, DiscardStatement, DiscardTarget,
     ExecuteStatement, FetchOption, FetchOptionName, FetchStatement, PrepareStatement,
-    ResetVariableStatement, SetVariableStatement, SetVariableTo, ShowVariableStatement,
+    ResetVariableStatement, SetVariableStatement, SetVariableTo, ShowTimestampStatement,
+    ShowVariableStatement,
 };
 use crate::names::{self, Aug};
 use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::with_options::TryFromValue;
 use crate::plan::{
     describe, query, ClosePlan, DeallocatePlan, DeclarePlan, ExecutePlan, ExecuteTimeout,
-    FetchPlan, InspectShardPlan, Params, Plan, PlanError, PreparePlan, ResetVariablePlan,
-    SetVariablePlan, ShowVariablePlan, VariableValue,
+    FetchPlan, InspectCollectionPlan, InspectShardPlan, Params, Plan, PlanError, PreparePlan,
+    ResetVariablePlan, SetVariablePlan, ShowVariablePlan, VariableValue,
 };
 use crate::session::vars;
 use crate::session::vars::{IsolationLevel, SCHEMA_ALIAS, TRANSACTION_ISOLATION_VAR_NAME};
@@ -129,6 +130,30 @@ pub fn plan_show_variable(
     }
 }
 
+pub fn describe_show_timestamp(
+    _: &StatementContext,
+    _: ShowTimestampStatement,
+) -> Result<StatementDesc, PlanError> {
+    let desc = RelationDesc::empty()
+        .with_column("oracle_read_ts", ScalarType::MzTimestamp.nullable(true))
+        .with_column(
+            "largest_not_in_advance_of_upper",
+            ScalarType::MzTimestamp.nullable(true),
+        )
+        .with_column(
+            "session_wall_time",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        );
+    Ok(StatementDesc::new(Some(desc)))
+}
+
+pub fn plan_show_timestamp(
+    _: &StatementContext,
+    _: ShowTimestampStatement,
+) -> Result<Plan, PlanError> {
+    Ok(Plan::ShowTimestamp)
+}
+
 pub fn describe_inspect_shard(
     _: &StatementContext,
     InspectShardStatement { .. }: InspectShardStatement,
@@ -145,6 +170,24 @@ pub fn plan_inspect_shard(
     Ok(Plan::InspectShard(InspectShardPlan { id }))
 }
 
+pub fn describe_inspect_collection(
+    _: &StatementContext,
+    InspectCollectionStatement { .. }: InspectCollectionStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    let desc = RelationDesc::empty().with_column("id", ScalarType::String.nullable(false));
+    Ok(StatementDesc::new(Some(desc)))
+}
+
+pub fn plan_inspect_collection(
+    scx: &StatementContext,
+    InspectCollectionStatement { name }: InspectCollectionStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    let item = scx.get_item_by_resolved_name(&name)?;
+    Ok(Plan::InspectCollection(InspectCollectionPlan {
+        id: item.id(),
+    }))
+}
+
 pub fn describe_discard(
     _: &StatementContext,
     _: DiscardStatement,
@@ -20,6 +20,7 @@ use anyhow::anyhow;
 use itertools::Itertools;
 use mz_ccsr::{Client, GetByIdError, GetBySubjectError, Schema as CcsrSchema};
 use mz_kafka_util::client::MzClientContext;
+use mz_ore::cast::CastFrom;
 use mz_ore::error::ErrorExt;
 use mz_ore::iter::IteratorExt;
 use mz_ore::str::StrExt;
@@ -493,6 +494,7 @@ async fn purify_create_source(
         include_metadata: _,
         referenced_subsources,
         progress_subsource,
+        with_options,
         ..
     } = &mut stmt;
 
@@ -706,9 +708,23 @@ async fn purify_create_source(
             )
             .await?;
 
-            // We need 1 replication slot for the snapshots and 1 for the continuing replication
-            if available_replication_slots < 2 {
-                Err(PgSourcePurificationError::InsufficientReplicationSlotsAvailable { count: 2 })?;
+            // We need 1 replication slot for the snapshots and 1 for the continuing replication,
+            // unless the snapshots are taken in `READ COMMITTED` mode, which needs no slot.
+            let crate::plan::statement::ddl::CreateSourceOptionExtracted {
+                snapshot_read_committed,
+                ..
+            } = with_options.clone().try_into()?;
+            let slots_needed = if snapshot_read_committed.unwrap_or(false) {
+                1
+            } else {
+                2
+            };
+            if available_replication_slots < i64::cast_from(slots_needed) {
+                Err(
+                    PgSourcePurificationError::InsufficientReplicationSlotsAvailable {
+                        count: slots_needed,
+                    },
+                )?;
             }
 
             let publication_tables = mz_postgres_util::publication_info(
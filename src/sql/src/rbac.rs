@@ -381,6 +381,7 @@ fn generate_rbac_requirements(
         Plan::CreateCluster(plan::CreateClusterPlan {
             name: _,
             variant: _,
+            transaction_isolation: _,
         }) => RbacRequirements {
             privileges: vec![(SystemObjectId::System, AclMode::CREATE_CLUSTER, role_id)],
             item_usage: &CREATE_ITEM_USAGE,
@@ -704,6 +705,8 @@ fn generate_rbac_requirements(
             up_to: _,
             copy_to: _,
             emit_progress: _,
+            emit_resume_tokens: _,
+            resume_from: _,
             output: _,
         }) => {
             let mut privileges =
@@ -912,6 +915,10 @@ fn generate_rbac_requirements(
             ownership: vec![ObjectId::Item(*id)],
             ..Default::default()
         },
+        Plan::AlterSink(plan::AlterSinkPlan { id, action: _ }) => RbacRequirements {
+            ownership: vec![ObjectId::Item(*id)],
+            ..Default::default()
+        },
         Plan::AlterSource(plan::AlterSourcePlan { id, action: _ }) => RbacRequirements {
             ownership: vec![ObjectId::Item(*id)],
             item_usage: &CREATE_ITEM_USAGE,
@@ -1299,20 +1306,43 @@ fn generate_rbac_requirements(
                 .collect(),
             ..Default::default()
         },
-        Plan::SideEffectingFunc(func) => {
-            let role_membership = match func {
-                SideEffectingFunc::PgCancelBackend { connection_id } => {
-                    match active_conns.get(connection_id) {
-                        Some(authenticated_role) => BTreeSet::from([*authenticated_role]),
-                        None => BTreeSet::new(),
-                    }
+        Plan::SideEffectingFunc(func) => match func {
+            SideEffectingFunc::PgCancelBackend { connection_id } => {
+                let role_membership = match active_conns.get(connection_id) {
+                    Some(authenticated_role) => BTreeSet::from([*authenticated_role]),
+                    None => BTreeSet::new(),
+                };
+                RbacRequirements {
+                    role_membership,
+                    ..Default::default()
                 }
-            };
-            RbacRequirements {
-                role_membership,
-                ..Default::default()
             }
-        }
+            // Quiescing a cluster affects every user of that cluster, so it is
+            // reserved for operators.
+            SideEffectingFunc::MzQuiesceCluster { cluster_name: _ } => RbacRequirements {
+                superuser_action: Some("quiesce a cluster".to_string()),
+                ..Default::default()
+            },
+            SideEffectingFunc::MzUnquiesceCluster { cluster_name: _ } => RbacRequirements {
+                superuser_action: Some("unquiesce a cluster".to_string()),
+                ..Default::default()
+            },
+            // Estimates reveal the size of any collection, regardless of its
+            // privileges.
+            SideEffectingFunc::MzEstimateCompaction {
+                name: _,
+                retain_history: _,
+            } => RbacRequirements {
+                superuser_action: Some("estimate compaction".to_string()),
+                ..Default::default()
+            },
+        },
+        // Soft-dropped items no longer have an owner or privileges to check
+        // against, so restoring them is reserved for operators.
+        Plan::Undrop(plan::UndropPlan { id: _ }) => RbacRequirements {
+            superuser_action: Some("undrop an object".to_string()),
+            ..Default::default()
+        },
         Plan::ValidateConnection(plan::ValidateConnectionPlan { id, connection: _ }) => {
             let schema_id: ObjectId = catalog.get_item(id).name().qualifiers.clone().into();
             RbacRequirements {
@@ -1328,7 +1358,9 @@ fn generate_rbac_requirements(
         | Plan::EmptyQuery
         | Plan::ShowAllVariables
         | Plan::ShowVariable(plan::ShowVariablePlan { name: _ })
+        | Plan::ShowTimestamp
         | Plan::InspectShard(plan::InspectShardPlan { id: _ })
+        | Plan::InspectCollection(plan::InspectCollectionPlan { id: _ })
         | Plan::SetVariable(plan::SetVariablePlan {
             name: _,
             value: _,
@@ -79,6 +79,9 @@ use clap::clap_derive::ArgEnum;
 use clap::ValueEnum;
 use itertools::Itertools;
 use mz_adapter_types::timestamp_oracle::{
+    DEFAULT_PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_FAILURE_THRESHOLD,
+    DEFAULT_PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_LATENCY_THRESHOLD,
+    DEFAULT_PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_RECOVERY_THRESHOLD,
     DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_MAX_SIZE, DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_MAX_WAIT,
     DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_TTL, DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_TTL_STAGGER,
 };
@@ -100,7 +103,7 @@ use mz_storage_types::controller::PersistTxnTablesImpl;
 use mz_tracing::{CloneableEnvFilter, SerializableDirective};
 use once_cell::sync::Lazy;
 use proptest_derive::Arbitrary;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use uncased::UncasedStr;
 
 use crate::ast::Ident;
@@ -537,6 +540,22 @@ pub const MAX_QUERY_RESULT_SIZE: ServerVar<ByteSize> = ServerVar {
     internal: false,
 };
 
+pub const MAX_PORTAL_MEMORY: ServerVar<ByteSize> = ServerVar {
+    name: UncasedStr::new("max_portal_memory"),
+    value: ByteSize::gb(1),
+    description: "The maximum size in bytes of results buffered by the open portals of a single \
+        connection (Materialize).",
+    internal: false,
+};
+
+pub const MAX_SUBSCRIBE_BUFFER_SIZE: ServerVar<ByteSize> = ServerVar {
+    name: UncasedStr::new("max_subscribe_buffer_size"),
+    value: ByteSize::gb(1),
+    description: "The maximum size in bytes of updates buffered for a single SUBSCRIBE whose \
+        client has not yet received them (Materialize).",
+    internal: false,
+};
+
 pub const MAX_COPY_FROM_SIZE: ServerVar<u32> = ServerVar {
     name: UncasedStr::new("max_copy_from_size"),
     // 1 GiB, this limit is noted in the docs, if you change it make sure to update our docs.
@@ -572,6 +591,17 @@ pub const METRICS_RETENTION: ServerVar<Duration> = ServerVar {
     internal: true,
 };
 
+/// How long soft-dropped items remain restorable with `UNDROP` before they are
+/// dropped for good.
+pub const SOFT_DROP_RETENTION: ServerVar<Duration> = ServerVar {
+    name: UncasedStr::new("soft_drop_retention"),
+    // 1 hour
+    value: Duration::from_secs(60 * 60),
+    description:
+        "The time soft-dropped materialized views remain restorable with UNDROP (Materialize).",
+    internal: true,
+};
+
 static ALLOWED_CLUSTER_REPLICA_SIZES: Lazy<ServerVar<Vec<Ident>>> = Lazy::new(|| ServerVar {
     name: UncasedStr::new("allowed_cluster_replica_sizes"),
     value: Vec::new(),
@@ -651,6 +681,66 @@ const PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL_STAGGER: ServerVar<Duration> = Ser
     internal: true,
 };
 
+/// Controls `mz_adapter::coord::timestamp_oracle::postgres_oracle::DynamicConfig::circuit_breaker_failure_threshold`.
+const PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_FAILURE_THRESHOLD: ServerVar<usize> = ServerVar {
+    name: UncasedStr::new("pg_timestamp_oracle_circuit_breaker_failure_threshold"),
+    value: DEFAULT_PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_FAILURE_THRESHOLD,
+    description: "The number of consecutive failed or slow Postgres/CRDB operations after which the timestamp oracle is considered unavailable.",
+    internal: true,
+};
+
+/// Controls `mz_adapter::coord::timestamp_oracle::postgres_oracle::DynamicConfig::circuit_breaker_latency_threshold`.
+const PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_LATENCY_THRESHOLD: ServerVar<Duration> = ServerVar {
+    name: UncasedStr::new("pg_timestamp_oracle_circuit_breaker_latency_threshold"),
+    value: DEFAULT_PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_LATENCY_THRESHOLD,
+    description: "The latency above which a Postgres/CRDB operation of the timestamp oracle counts as failed.",
+    internal: true,
+};
+
+/// Controls `mz_adapter::coord::timestamp_oracle::postgres_oracle::DynamicConfig::circuit_breaker_recovery_threshold`.
+const PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_RECOVERY_THRESHOLD: ServerVar<usize> = ServerVar {
+    name: UncasedStr::new("pg_timestamp_oracle_circuit_breaker_recovery_threshold"),
+    value: DEFAULT_PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_RECOVERY_THRESHOLD,
+    description: "The number of consecutive successful Postgres/CRDB operations after which an unavailable timestamp oracle is considered available again.",
+    internal: true,
+};
+
+/// The maximum number of strict serializable reads that wait on an unavailable timestamp oracle.
+const TIMESTAMP_ORACLE_DEGRADED_READ_QUEUE_SIZE: ServerVar<usize> = ServerVar {
+    name: UncasedStr::new("timestamp_oracle_degraded_read_queue_size"),
+    value: 100,
+    description: "The maximum number of strict serializable reads that wait for an unavailable timestamp oracle to recover.",
+    internal: true,
+};
+
+/// How long a strict serializable read waits on an unavailable timestamp oracle.
+const TIMESTAMP_ORACLE_DEGRADED_READ_TIMEOUT: ServerVar<Duration> = ServerVar {
+    name: UncasedStr::new("timestamp_oracle_degraded_read_timeout"),
+    value: Duration::from_secs(10),
+    description: "How long a strict serializable read waits for an unavailable timestamp oracle to recover before it fails.",
+    internal: true,
+};
+
+/// Whether strict serializable reads are served with serializable semantics while the timestamp
+/// oracle is unavailable.
+const TIMESTAMP_ORACLE_DEGRADED_SERIALIZABLE_READS: ServerVar<bool> = ServerVar {
+    name: UncasedStr::new("timestamp_oracle_degraded_serializable_reads"),
+    value: false,
+    description: "Whether strict serializable reads are served with serializable semantics, instead of waiting, while the timestamp oracle is unavailable.",
+    internal: true,
+};
+
+/// Links between timelines whose timestamps are derived from the same clock.
+static LINKED_TIMELINES: Lazy<ServerVar<String>> = Lazy::new(|| ServerVar {
+    name: UncasedStr::new("linked_timelines"),
+    value: String::new(),
+    description:
+        "A comma-separated list of '<timeline>=<common timeline>' links between timelines whose \
+         timestamps are derived from the same clock, e.g. 'U.clock=M'. Collections in linked \
+         timelines can be queried together.",
+    internal: true,
+});
+
 /// The default for the `DISK` option when creating managed clusters and cluster replicas.
 const DISK_CLUSTER_REPLICAS_DEFAULT: ServerVar<bool> = ServerVar {
     name: UncasedStr::new("disk_cluster_replicas_default"),
@@ -1017,6 +1107,15 @@ const PG_SOURCE_SNAPSHOT_STATEMENT_TIMEOUT: ServerVar<Duration> = ServerVar {
     internal: true,
 };
 
+/// Sets the `lock_timeout` value to use during the snapshotting phase of PG
+/// sources.
+const PG_SOURCE_SNAPSHOT_LOCK_TIMEOUT: ServerVar<Duration> = ServerVar {
+    name: UncasedStr::new("pg_source_snapshot_lock_timeout"),
+    value: mz_postgres_util::DEFAULT_SNAPSHOT_LOCK_TIMEOUT,
+    description: "Sets the `lock_timeout` value to use during the snapshotting phase of PG sources (Materialize)",
+    internal: true,
+};
+
 /// Please see `PgSourceSnapshotConfig`.
 const PG_SOURCE_SNAPSHOT_COLLECT_STRICT_COUNT: ServerVar<bool> = ServerVar {
     name: UncasedStr::new("pg_source_snapshot_collect_strict_count"),
@@ -1044,6 +1143,51 @@ const PG_SOURCE_SNAPSHOT_WAIT_FOR_COUNT: ServerVar<bool> = ServerVar {
         /struct.PgSourceSnapshotConfig.html#structfield.wait_for_count>",
     internal: true,
 };
+/// Please see `PgSourceSnapshotConfig`.
+const PG_SOURCE_SNAPSHOT_MAX_IN_FLIGHT_BYTES: ServerVar<usize> = ServerVar {
+    name: UncasedStr::new("pg_source_snapshot_max_in_flight_bytes"),
+    value: mz_storage_types::parameters::PgSourceSnapshotConfig::new().max_in_flight_bytes,
+    description: "Please see <https://dev.materialize.com/api/rust-private\
+        /mz_storage_types/parameters\
+        /struct.PgSourceSnapshotConfig.html#structfield.max_in_flight_bytes>",
+    internal: true,
+};
+/// Please see `PgSourceSnapshotConfig`.
+const PG_SOURCE_SNAPSHOT_COPY_BUFFER_BYTES: ServerVar<usize> = ServerVar {
+    name: UncasedStr::new("pg_source_snapshot_copy_buffer_bytes"),
+    value: mz_storage_types::parameters::PgSourceSnapshotConfig::new().copy_buffer_bytes,
+    description: "Please see <https://dev.materialize.com/api/rust-private\
+        /mz_storage_types/parameters\
+        /struct.PgSourceSnapshotConfig.html#structfield.copy_buffer_bytes>",
+    internal: true,
+};
+/// Please see `PgSourceSnapshotConfig`.
+const PG_SOURCE_SNAPSHOT_PEEK_REWIND_CHANGES: ServerVar<bool> = ServerVar {
+    name: UncasedStr::new("pg_source_snapshot_peek_rewind_changes"),
+    value: mz_storage_types::parameters::PgSourceSnapshotConfig::new().peek_rewind_changes,
+    description: "Please see <https://dev.materialize.com/api/rust-private\
+        /mz_storage_types/parameters\
+        /struct.PgSourceSnapshotConfig.html#structfield.peek_rewind_changes>",
+    internal: true,
+};
+/// Please see `PgSourceSnapshotConfig`.
+const PG_SOURCE_SNAPSHOT_GROUP_WINDOW: ServerVar<Duration> = ServerVar {
+    name: UncasedStr::new("pg_source_snapshot_group_window"),
+    value: mz_storage_types::parameters::PgSourceSnapshotConfig::new().group_window,
+    description: "Please see <https://dev.materialize.com/api/rust-private\
+        /mz_storage_types/parameters\
+        /struct.PgSourceSnapshotConfig.html#structfield.group_window>",
+    internal: true,
+};
+/// Please see `PgSourceSnapshotConfig`.
+const PG_SOURCE_SNAPSHOT_GROUP_TIMEOUT: ServerVar<Duration> = ServerVar {
+    name: UncasedStr::new("pg_source_snapshot_group_timeout"),
+    value: mz_storage_types::parameters::PgSourceSnapshotConfig::new().group_timeout,
+    description: "Please see <https://dev.materialize.com/api/rust-private\
+        /mz_storage_types/parameters\
+        /struct.PgSourceSnapshotConfig.html#structfield.group_timeout>",
+    internal: true,
+};
 
 /// Controls the check interval for connections to SSH bastions via `mz_ssh_util`.
 const SSH_CHECK_INTERVAL: ServerVar<Duration> = ServerVar {
@@ -1245,6 +1389,44 @@ static REAL_TIME_RECENCY: ServerVar<bool> = ServerVar {
     internal: false,
 };
 
+static REAL_TIME_RECENCY_TIMEOUT: ServerVar<Option<Duration>> = ServerVar {
+    name: UncasedStr::new("real_time_recency_timeout"),
+    value: None,
+    description:
+        "Sets the maximum amount of time a query using real time recency may wait for its inputs \
+        to catch up with the upstream systems. If this value is specified without units, it is \
+        taken as milliseconds (Materialize).",
+    internal: false,
+};
+
+static INTROSPECTION_WAIT_TIMEOUT: ServerVar<Option<Duration>> = ServerVar {
+    name: UncasedStr::new("introspection_wait_timeout"),
+    value: None,
+    description:
+        "Sets the maximum amount of time a query over per-replica introspection sources may wait \
+        for the targeted replica to report its first introspection data. If this value is \
+        specified without units, it is taken as milliseconds (Materialize).",
+    internal: false,
+};
+
+static MAX_STALENESS: ServerVar<Option<Duration>> = ServerVar {
+    name: UncasedStr::new("max_staleness"),
+    value: None,
+    description:
+        "Sets the maximum amount by which the timestamp chosen for a query may lag behind \
+        the current time. If this value is specified without units, it is taken as milliseconds \
+        (Materialize).",
+    internal: false,
+};
+
+static READ_YOUR_WRITES: ServerVar<bool> = ServerVar {
+    name: UncasedStr::new("read_your_writes"),
+    value: false,
+    description: "Whether queries that are not strict serializable are guaranteed to observe \
+        the writes previously performed by the same session (Materialize).",
+    internal: false,
+};
+
 static EMIT_TIMESTAMP_NOTICE: ServerVar<bool> = ServerVar {
     name: UncasedStr::new("emit_timestamp_notice"),
     value: false,
@@ -1253,6 +1435,14 @@ static EMIT_TIMESTAMP_NOTICE: ServerVar<bool> = ServerVar {
     internal: false
 };
 
+static NOTICE_ON_DEFAULT_TIMELINE: ServerVar<bool> = ServerVar {
+    name: UncasedStr::new("notice_on_default_timeline"),
+    value: false,
+    description: "Whether to send a NOTICE when a query that reads no object in a timeline \
+        depends on its timestamp, and so defaults to the EpochMilliseconds timeline (Materialize).",
+    internal: false,
+};
+
 static EMIT_TRACE_ID_NOTICE: ServerVar<bool> = ServerVar {
     name: UncasedStr::new("emit_trace_id_notice"),
     value: false,
@@ -1421,6 +1611,48 @@ pub static STATEMENT_LOGGING_DEFAULT_SAMPLE_RATE: Lazy<ServerVar<Numeric>> =
         internal: false,
     });
 
+pub static STRICT_SERIALIZABLE_TIMESTAMP_DIFFERENCE_SAMPLE_RATE: Lazy<ServerVar<Numeric>> =
+    Lazy::new(|| ServerVar {
+        name: UncasedStr::new("strict_serializable_timestamp_difference_sample_rate"),
+        value: 1.0.into(),
+        description: "The fraction of strict serializable queries whose timestamp is compared \
+against the one a serializable query would have chosen, to report their difference (Materialize).",
+        internal: true,
+    });
+
+pub static TIMESTAMP_WAIT_SAMPLE_RATE: Lazy<ServerVar<Numeric>> = Lazy::new(|| ServerVar {
+    name: UncasedStr::new("timestamp_wait_sample_rate"),
+    value: 0.01.into(),
+    description: "The fraction of queries that wait for their timestamp to become readable whose \
+wait is timed, to report its duration (Materialize).",
+    internal: true,
+});
+
+pub static CLUSTER_REPLICA_DISK_HIGH_WATER_MARK: Lazy<ServerVar<Numeric>> =
+    Lazy::new(|| ServerVar {
+        name: UncasedStr::new("cluster_replica_disk_high_water_mark"),
+        value: 0.9.into(),
+        description: "The fraction of a replica process's scratch disk capacity at or above which \
+users are warned that the replica may run out of disk (Materialize).",
+        internal: true,
+    });
+
+pub const FRONTIER_DIVERGENCE_THRESHOLD: ServerVar<Duration> = ServerVar {
+    name: UncasedStr::new("frontier_divergence_threshold"),
+    value: Duration::from_secs(60),
+    description: "The difference between the compute and storage write frontiers of a collection \
+above which the two are considered to diverge (Materialize).",
+    internal: true,
+};
+
+pub const FRONTIER_DIVERGENCE_TICKS: ServerVar<usize> = ServerVar {
+    name: UncasedStr::new("frontier_divergence_ticks"),
+    value: 30,
+    description: "The number of consecutive frontier recordings, one per second, for which the \
+compute and storage write frontiers of a collection must diverge before it is reported (Materialize).",
+    internal: true,
+};
+
 pub const AUTO_ROUTE_INTROSPECTION_QUERIES: ServerVar<bool> = ServerVar {
     name: UncasedStr::new("auto_route_introspection_queries"),
     value: true,
@@ -1461,6 +1693,66 @@ const KEEP_N_PRIVATELINK_STATUS_HISTORY_ENTRIES: ServerVar<usize> = ServerVar {
     internal: true
 };
 
+/// Controls [`mz_storage_types::parameters::StorageParameters::frontiers_history_full_resolution_window`].
+const FRONTIERS_HISTORY_FULL_RESOLUTION_WINDOW: ServerVar<Duration> = ServerVar {
+    name: UncasedStr::new("frontiers_history_full_resolution_window"),
+    value: mz_storage_types::parameters::FRONTIERS_HISTORY_FULL_RESOLUTION_WINDOW_DEFAULT,
+    description: "How long write frontiers are retained at full resolution in \
+        `mz_internal.mz_object_frontiers_history` before being rolled up (Materialize).",
+    internal: true,
+};
+
+/// Controls [`mz_storage_types::parameters::StorageParameters::storage_command_max_bytes`].
+const STORAGE_COMMAND_MAX_BYTES: ServerVar<usize> = ServerVar {
+    name: UncasedStr::new("storage_command_max_bytes"),
+    value: mz_storage_types::parameters::STORAGE_COMMAND_MAX_BYTES_DEFAULT,
+    description: "The encoded size, in bytes, above which commands sent to storage replicas \
+        are split into several smaller commands (Materialize).",
+    internal: true,
+};
+
+/// Controls [`mz_storage_types::parameters::StorageParameters::storage_command_chunking`].
+const STORAGE_COMMAND_CHUNKING: ServerVar<bool> = ServerVar {
+    name: UncasedStr::new("storage_command_chunking"),
+    value: false,
+    description: "Whether commands to storage replicas that exceed storage_command_max_bytes \
+        even after splitting are sent in chunks, rather than whole (Materialize).",
+    internal: true,
+};
+
+/// Controls [`mz_storage_types::parameters::StorageParameters::dropped_ids_batch_size`].
+const STORAGE_DROPPED_IDS_BATCH_SIZE: ServerVar<usize> = ServerVar {
+    name: UncasedStr::new("storage_dropped_ids_batch_size"),
+    value: mz_storage_types::parameters::DROPPED_IDS_BATCH_SIZE_DEFAULT,
+    description: "The maximum number of dropped collections the storage controller cleans up \
+        after at a time (Materialize).",
+    internal: true,
+};
+
+const ALTER_CONNECTION_RESTART_CONCURRENCY: ServerVar<usize> = ServerVar {
+    name: UncasedStr::new("alter_connection_restart_concurrency"),
+    value: 4,
+    description: "The maximum number of sources restarted at a time to apply an \
+        `ALTER CONNECTION` (Materialize).",
+    internal: true,
+};
+
+const ALTER_CONNECTION_WAIT_FOR_RESTARTS: ServerVar<bool> = ServerVar {
+    name: UncasedStr::new("alter_connection_wait_for_restarts"),
+    value: false,
+    description: "Whether `ALTER CONNECTION` returns only once all sources using the connection \
+        have restarted (Materialize).",
+    internal: true,
+};
+
+const CATALOG_COMMIT_TIMEOUT: ServerVar<Duration> = ServerVar {
+    name: UncasedStr::new("catalog_commit_timeout"),
+    value: Duration::from_secs(60),
+    description: "The maximum duration of committing a change to the catalog, after which the \
+        change is reported as failed (Materialize).",
+    internal: true,
+};
+
 const ENABLE_STORAGE_SHARD_FINALIZATION: ServerVar<bool> = ServerVar {
     name: UncasedStr::new("enable_storage_shard_finalization"),
     value: true,
@@ -1628,6 +1920,46 @@ mod cluster_scheduling {
         description: "Always provisions a replica with disk, regardless of `DISK` DDL option.",
         internal: true,
     };
+
+    pub const CLUSTER_CRASH_LOOP_MAX_RESTARTS: ServerVar<usize> = ServerVar {
+        name: UncasedStr::new("cluster_crash_loop_max_restarts"),
+        value: DEFAULT_CRASH_LOOP_MAX_RESTARTS,
+        description: "The number of restarts within `cluster_crash_loop_window` above which a \
+            replica is considered crash-looping and held back from restarting (Materialize).",
+        internal: true,
+    };
+
+    pub const CLUSTER_CRASH_LOOP_WINDOW: ServerVar<Duration> = ServerVar {
+        name: UncasedStr::new("cluster_crash_loop_window"),
+        value: DEFAULT_CRASH_LOOP_WINDOW,
+        description: "The period over which replica restarts are counted to detect crash loops \
+            (Materialize).",
+        internal: true,
+    };
+
+    pub const CLUSTER_CRASH_LOOP_INITIAL_BACKOFF: ServerVar<Duration> = ServerVar {
+        name: UncasedStr::new("cluster_crash_loop_initial_backoff"),
+        value: DEFAULT_CRASH_LOOP_INITIAL_BACKOFF,
+        description: "How long a crash-looping replica is first held back from restarting \
+            (Materialize).",
+        internal: true,
+    };
+
+    pub const CLUSTER_CRASH_LOOP_MAX_BACKOFF: ServerVar<Duration> = ServerVar {
+        name: UncasedStr::new("cluster_crash_loop_max_backoff"),
+        value: DEFAULT_CRASH_LOOP_MAX_BACKOFF,
+        description: "The maximum time a crash-looping replica is held back from restarting \
+            (Materialize).",
+        internal: true,
+    };
+
+    pub const CLUSTER_CRASH_LOOP_HEALTHY_PERIOD: ServerVar<Duration> = ServerVar {
+        name: UncasedStr::new("cluster_crash_loop_healthy_period"),
+        value: DEFAULT_CRASH_LOOP_HEALTHY_PERIOD,
+        description: "How long a replica must stay ready for its crash-loop backoff to be reset \
+            (Materialize).",
+        internal: true,
+    };
 }
 
 /// Macro to simplify creating feature flags, i.e. boolean flags that we use to toggle the
@@ -2173,6 +2505,34 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: false,
     },
+    {
+        name: enable_source_parameter_overrides,
+        desc: "overriding parameters of individual sources",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
+    {
+        name: enable_soft_drop,
+        desc: "soft-dropping materialized views so they can be restored with UNDROP",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_append_only_read_at_upper,
+        desc: "reading append-only sources as of their upper under linearized isolation levels",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_pg_lsn_timeline,
+        desc: "CREATE SOURCE ... WITH (TIMELINE 'mz_pg_lsn')",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
 );
 
 /// Returns a new ConfigSet containing every `Config` in Materialize.
@@ -2259,7 +2619,11 @@ impl SessionVars {
             .with_value_constrained_var(&SERVER_VERSION_NUM, ValueConstraint::ReadOnly)
             .with_var(&SQL_SAFE_UPDATES)
             .with_feature_gated_var(&REAL_TIME_RECENCY, &ALLOW_REAL_TIME_RECENCY)
+            .with_var(&REAL_TIME_RECENCY_TIMEOUT)
+            .with_var(&MAX_STALENESS)
+            .with_var(&READ_YOUR_WRITES)
             .with_var(&EMIT_TIMESTAMP_NOTICE)
+            .with_var(&NOTICE_ON_DEFAULT_TIMELINE)
             .with_var(&EMIT_TRACE_ID_NOTICE)
             .with_var(&AUTO_ROUTE_INTROSPECTION_QUERIES)
             .with_var(&ENABLE_SESSION_RBAC_CHECKS)
@@ -2268,12 +2632,15 @@ impl SessionVars {
                 &ENABLE_CARDINALITY_ESTIMATES,
             )
             .with_var(&MAX_QUERY_RESULT_SIZE)
+            .with_var(&MAX_PORTAL_MEMORY)
+            .with_var(&MAX_SUBSCRIBE_BUFFER_SIZE)
             .with_var(&MAX_IDENTIFIER_LENGTH)
             .with_value_constrained_var(
                 &STATEMENT_LOGGING_SAMPLE_RATE,
                 ValueConstraint::Domain(&NumericInRange(0.0..=1.0)),
             )
             .with_var(&EMIT_INTROSPECTION_QUERY_NOTICE)
+            .with_var(&INTROSPECTION_WAIT_TIMEOUT)
             .with_var(&UNSAFE_NEW_TRANSACTION_WALL_TIME)
             .with_var(&WELCOME_MESSAGE)
     }
@@ -2651,16 +3018,45 @@ impl SessionVars {
         self.expect_value(&TRANSACTION_ISOLATION)
     }
 
+    /// Reports whether the `transaction_isolation` configuration parameter was set in this
+    /// session, rather than inherited from a system or role default.
+    pub fn transaction_isolation_is_set(&self) -> bool {
+        self.vars
+            .get(TRANSACTION_ISOLATION.name)
+            .expect("provided var should be in state")
+            .is_set()
+    }
+
     /// Returns the value of `real_time_recency` configuration parameter.
     pub fn real_time_recency(&self) -> bool {
         *self.expect_value(&REAL_TIME_RECENCY)
     }
 
+    /// Returns the value of `real_time_recency_timeout` configuration parameter.
+    pub fn real_time_recency_timeout(&self) -> Option<Duration> {
+        *self.expect_value(&REAL_TIME_RECENCY_TIMEOUT)
+    }
+
+    /// Returns the value of `max_staleness` configuration parameter.
+    pub fn max_staleness(&self) -> Option<Duration> {
+        *self.expect_value(&MAX_STALENESS)
+    }
+
+    /// Returns the value of `read_your_writes` configuration parameter.
+    pub fn read_your_writes(&self) -> bool {
+        *self.expect_value(&READ_YOUR_WRITES)
+    }
+
     /// Returns the value of `emit_timestamp_notice` configuration parameter.
     pub fn emit_timestamp_notice(&self) -> bool {
         *self.expect_value(&EMIT_TIMESTAMP_NOTICE)
     }
 
+    /// Returns the value of `notice_on_default_timeline` configuration parameter.
+    pub fn notice_on_default_timeline(&self) -> bool {
+        *self.expect_value(&NOTICE_ON_DEFAULT_TIMELINE)
+    }
+
     /// Returns the value of `emit_trace_id_notice` configuration parameter.
     pub fn emit_trace_id_notice(&self) -> bool {
         *self.expect_value(&EMIT_TRACE_ID_NOTICE)
@@ -2696,6 +3092,16 @@ impl SessionVars {
         self.expect_value(&MAX_QUERY_RESULT_SIZE).as_bytes()
     }
 
+    /// Returns the value of the `max_portal_memory` configuration parameter.
+    pub fn max_portal_memory(&self) -> u64 {
+        self.expect_value(&MAX_PORTAL_MEMORY).as_bytes()
+    }
+
+    /// Returns the value of the `max_subscribe_buffer_size` configuration parameter.
+    pub fn max_subscribe_buffer_size(&self) -> u64 {
+        self.expect_value(&MAX_SUBSCRIBE_BUFFER_SIZE).as_bytes()
+    }
+
     /// Sets the external metadata associated with the user.
     pub fn set_external_user_metadata(&mut self, metadata: ExternalUserMetadata) {
         self.user.external_metadata = Some(metadata);
@@ -2715,6 +3121,11 @@ impl SessionVars {
         *self.expect_value(&EMIT_INTROSPECTION_QUERY_NOTICE)
     }
 
+    /// Returns the value of the `introspection_wait_timeout` configuration parameter.
+    pub fn introspection_wait_timeout(&self) -> Option<Duration> {
+        *self.expect_value(&INTROSPECTION_WAIT_TIMEOUT)
+    }
+
     pub fn unsafe_new_transaction_wall_time(&self) -> Option<CheckedTimestamp<DateTime<Utc>>> {
         *self.expect_value(&UNSAFE_NEW_TRANSACTION_WALL_TIME)
     }
@@ -2916,6 +3327,7 @@ impl SystemVars {
             .with_var(&PERSIST_TXN_TABLES)
             .with_var(&CATALOG_KIND_IMPL)
             .with_var(&METRICS_RETENTION)
+            .with_var(&SOFT_DROP_RETENTION)
             .with_var(&UNSAFE_MOCK_AUDIT_EVENT_TIMESTAMP)
             .with_var(&ENABLE_RBAC_CHECKS)
             .with_var(&PG_SOURCE_CONNECT_TIMEOUT)
@@ -2924,9 +3336,15 @@ impl SystemVars {
             .with_var(&PG_SOURCE_KEEPALIVES_RETRIES)
             .with_var(&PG_SOURCE_TCP_USER_TIMEOUT)
             .with_var(&PG_SOURCE_SNAPSHOT_STATEMENT_TIMEOUT)
+            .with_var(&PG_SOURCE_SNAPSHOT_LOCK_TIMEOUT)
             .with_var(&PG_SOURCE_SNAPSHOT_COLLECT_STRICT_COUNT)
             .with_var(&PG_SOURCE_SNAPSHOT_FALLBACK_TO_STRICT_COUNT)
             .with_var(&PG_SOURCE_SNAPSHOT_WAIT_FOR_COUNT)
+            .with_var(&PG_SOURCE_SNAPSHOT_MAX_IN_FLIGHT_BYTES)
+            .with_var(&PG_SOURCE_SNAPSHOT_COPY_BUFFER_BYTES)
+            .with_var(&PG_SOURCE_SNAPSHOT_PEEK_REWIND_CHANGES)
+            .with_var(&PG_SOURCE_SNAPSHOT_GROUP_WINDOW)
+            .with_var(&PG_SOURCE_SNAPSHOT_GROUP_TIMEOUT)
             .with_var(&SSH_CHECK_INTERVAL)
             .with_var(&SSH_CONNECT_TIMEOUT)
             .with_var(&SSH_KEEPALIVES_IDLE)
@@ -2941,6 +3359,13 @@ impl SystemVars {
             .with_var(&KEEP_N_SOURCE_STATUS_HISTORY_ENTRIES)
             .with_var(&KEEP_N_SINK_STATUS_HISTORY_ENTRIES)
             .with_var(&KEEP_N_PRIVATELINK_STATUS_HISTORY_ENTRIES)
+            .with_var(&FRONTIERS_HISTORY_FULL_RESOLUTION_WINDOW)
+            .with_var(&STORAGE_COMMAND_MAX_BYTES)
+            .with_var(&STORAGE_COMMAND_CHUNKING)
+            .with_var(&STORAGE_DROPPED_IDS_BATCH_SIZE)
+            .with_var(&ALTER_CONNECTION_RESTART_CONCURRENCY)
+            .with_var(&ALTER_CONNECTION_WAIT_FOR_RESTARTS)
+            .with_var(&CATALOG_COMMIT_TIMEOUT)
             .with_var(&ENABLE_MZ_JOIN_CORE)
             .with_var(&LINEAR_JOIN_YIELDING)
             .with_var(&DEFAULT_IDLE_ARRANGEMENT_MERGE_EFFORT)
@@ -2971,6 +3396,11 @@ impl SystemVars {
             .with_var(&cluster_scheduling::CLUSTER_SOFTEN_AZ_AFFINITY)
             .with_var(&cluster_scheduling::CLUSTER_SOFTEN_AZ_AFFINITY_WEIGHT)
             .with_var(&cluster_scheduling::CLUSTER_ALWAYS_USE_DISK)
+            .with_var(&cluster_scheduling::CLUSTER_CRASH_LOOP_MAX_RESTARTS)
+            .with_var(&cluster_scheduling::CLUSTER_CRASH_LOOP_WINDOW)
+            .with_var(&cluster_scheduling::CLUSTER_CRASH_LOOP_INITIAL_BACKOFF)
+            .with_var(&cluster_scheduling::CLUSTER_CRASH_LOOP_MAX_BACKOFF)
+            .with_var(&cluster_scheduling::CLUSTER_CRASH_LOOP_HEALTHY_PERIOD)
             .with_var(&grpc_client::HTTP2_KEEP_ALIVE_TIMEOUT)
             .with_value_constrained_var(
                 &STATEMENT_LOGGING_MAX_SAMPLE_RATE,
@@ -2980,6 +3410,20 @@ impl SystemVars {
                 &STATEMENT_LOGGING_DEFAULT_SAMPLE_RATE,
                 ValueConstraint::Domain(&NumericInRange(0.0..=1.0)),
             )
+            .with_value_constrained_var(
+                &STRICT_SERIALIZABLE_TIMESTAMP_DIFFERENCE_SAMPLE_RATE,
+                ValueConstraint::Domain(&NumericInRange(0.0..=1.0)),
+            )
+            .with_value_constrained_var(
+                &TIMESTAMP_WAIT_SAMPLE_RATE,
+                ValueConstraint::Domain(&NumericInRange(0.0..=1.0)),
+            )
+            .with_value_constrained_var(
+                &CLUSTER_REPLICA_DISK_HIGH_WATER_MARK,
+                ValueConstraint::Domain(&NumericInRange(0.0..=1.0)),
+            )
+            .with_var(&FRONTIER_DIVERGENCE_THRESHOLD)
+            .with_var(&FRONTIER_DIVERGENCE_TICKS)
             .with_var(&STATEMENT_LOGGING_TARGET_DATA_RATE)
             .with_var(&STATEMENT_LOGGING_MAX_DATA_CREDIT)
             .with_var(&OPTIMIZER_STATS_TIMEOUT)
@@ -2993,7 +3437,14 @@ impl SystemVars {
             .with_var(&PG_TIMESTAMP_ORACLE_CONNECTION_POOL_MAX_SIZE)
             .with_var(&PG_TIMESTAMP_ORACLE_CONNECTION_POOL_MAX_WAIT)
             .with_var(&PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL)
-            .with_var(&PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL_STAGGER);
+            .with_var(&PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL_STAGGER)
+            .with_var(&PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_FAILURE_THRESHOLD)
+            .with_var(&PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_LATENCY_THRESHOLD)
+            .with_var(&PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_RECOVERY_THRESHOLD)
+            .with_var(&TIMESTAMP_ORACLE_DEGRADED_READ_QUEUE_SIZE)
+            .with_var(&TIMESTAMP_ORACLE_DEGRADED_READ_TIMEOUT)
+            .with_var(&TIMESTAMP_ORACLE_DEGRADED_SERIALIZABLE_READS)
+            .with_var(&LINKED_TIMELINES);
 
         for cfg in vars.persist_configs.entries() {
             let name = UncasedStr::new(cfg.name());
@@ -3506,6 +3957,11 @@ impl SystemVars {
         *self.expect_value(&PG_SOURCE_SNAPSHOT_STATEMENT_TIMEOUT)
     }
 
+    /// Returns the `pg_source_snapshot_lock_timeout` configuration parameter.
+    pub fn pg_source_snapshot_lock_timeout(&self) -> Duration {
+        *self.expect_value(&PG_SOURCE_SNAPSHOT_LOCK_TIMEOUT)
+    }
+
     /// Returns the `pg_source_snapshot_collect_strict_count` configuration parameter.
     pub fn pg_source_snapshot_collect_strict_count(&self) -> bool {
         *self.expect_value(&PG_SOURCE_SNAPSHOT_COLLECT_STRICT_COUNT)
@@ -3518,6 +3974,26 @@ impl SystemVars {
     pub fn pg_source_snapshot_wait_for_count(&self) -> bool {
         *self.expect_value(&PG_SOURCE_SNAPSHOT_WAIT_FOR_COUNT)
     }
+    /// Returns the `pg_source_snapshot_max_in_flight_bytes` configuration parameter.
+    pub fn pg_source_snapshot_max_in_flight_bytes(&self) -> usize {
+        *self.expect_value(&PG_SOURCE_SNAPSHOT_MAX_IN_FLIGHT_BYTES)
+    }
+    /// Returns the `pg_source_snapshot_copy_buffer_bytes` configuration parameter.
+    pub fn pg_source_snapshot_copy_buffer_bytes(&self) -> usize {
+        *self.expect_value(&PG_SOURCE_SNAPSHOT_COPY_BUFFER_BYTES)
+    }
+    /// Returns the `pg_source_snapshot_peek_rewind_changes` configuration parameter.
+    pub fn pg_source_snapshot_peek_rewind_changes(&self) -> bool {
+        *self.expect_value(&PG_SOURCE_SNAPSHOT_PEEK_REWIND_CHANGES)
+    }
+    /// Returns the `pg_source_snapshot_group_window` configuration parameter.
+    pub fn pg_source_snapshot_group_window(&self) -> Duration {
+        *self.expect_value(&PG_SOURCE_SNAPSHOT_GROUP_WINDOW)
+    }
+    /// Returns the `pg_source_snapshot_group_timeout` configuration parameter.
+    pub fn pg_source_snapshot_group_timeout(&self) -> Duration {
+        *self.expect_value(&PG_SOURCE_SNAPSHOT_GROUP_TIMEOUT)
+    }
 
     /// Returns the `ssh_check_interval` configuration parameter.
     pub fn ssh_check_interval(&self) -> Duration {
@@ -3661,6 +4137,11 @@ impl SystemVars {
         *self.expect_value(&METRICS_RETENTION)
     }
 
+    /// Returns the `soft_drop_retention` configuration parameter.
+    pub fn soft_drop_retention(&self) -> Duration {
+        *self.expect_value(&SOFT_DROP_RETENTION)
+    }
+
     /// Returns the `unsafe_mock_audit_event_timestamp` configuration parameter.
     pub fn unsafe_mock_audit_event_timestamp(&self) -> Option<mz_repr::Timestamp> {
         *self.expect_value(&UNSAFE_MOCK_AUDIT_EVENT_TIMESTAMP)
@@ -3688,6 +4169,41 @@ impl SystemVars {
         *self.expect_value(&KEEP_N_PRIVATELINK_STATUS_HISTORY_ENTRIES)
     }
 
+    /// Returns the `frontiers_history_full_resolution_window` configuration parameter.
+    pub fn frontiers_history_full_resolution_window(&self) -> Duration {
+        *self.expect_value(&FRONTIERS_HISTORY_FULL_RESOLUTION_WINDOW)
+    }
+
+    /// Returns the `storage_command_max_bytes` configuration parameter.
+    pub fn storage_command_max_bytes(&self) -> usize {
+        *self.expect_value(&STORAGE_COMMAND_MAX_BYTES)
+    }
+
+    /// Returns the `storage_command_chunking` configuration parameter.
+    pub fn storage_command_chunking(&self) -> bool {
+        *self.expect_value(&STORAGE_COMMAND_CHUNKING)
+    }
+
+    /// Returns the `storage_dropped_ids_batch_size` configuration parameter.
+    pub fn storage_dropped_ids_batch_size(&self) -> usize {
+        *self.expect_value(&STORAGE_DROPPED_IDS_BATCH_SIZE)
+    }
+
+    /// Returns the `alter_connection_restart_concurrency` configuration parameter.
+    pub fn alter_connection_restart_concurrency(&self) -> usize {
+        *self.expect_value(&ALTER_CONNECTION_RESTART_CONCURRENCY)
+    }
+
+    /// Returns the `alter_connection_wait_for_restarts` configuration parameter.
+    pub fn alter_connection_wait_for_restarts(&self) -> bool {
+        *self.expect_value(&ALTER_CONNECTION_WAIT_FOR_RESTARTS)
+    }
+
+    /// Returns the `catalog_commit_timeout` configuration parameter.
+    pub fn catalog_commit_timeout(&self) -> Duration {
+        *self.expect_value(&CATALOG_COMMIT_TIMEOUT)
+    }
+
     /// Returns the `enable_mz_join_core` configuration parameter.
     pub fn enable_mz_join_core(&self) -> bool {
         *self.expect_value(&ENABLE_MZ_JOIN_CORE)
@@ -3815,6 +4331,26 @@ impl SystemVars {
         *self.expect_value(&cluster_scheduling::CLUSTER_ALWAYS_USE_DISK)
     }
 
+    pub fn cluster_crash_loop_max_restarts(&self) -> usize {
+        *self.expect_value(&cluster_scheduling::CLUSTER_CRASH_LOOP_MAX_RESTARTS)
+    }
+
+    pub fn cluster_crash_loop_window(&self) -> Duration {
+        *self.expect_value(&cluster_scheduling::CLUSTER_CRASH_LOOP_WINDOW)
+    }
+
+    pub fn cluster_crash_loop_initial_backoff(&self) -> Duration {
+        *self.expect_value(&cluster_scheduling::CLUSTER_CRASH_LOOP_INITIAL_BACKOFF)
+    }
+
+    pub fn cluster_crash_loop_max_backoff(&self) -> Duration {
+        *self.expect_value(&cluster_scheduling::CLUSTER_CRASH_LOOP_MAX_BACKOFF)
+    }
+
+    pub fn cluster_crash_loop_healthy_period(&self) -> Duration {
+        *self.expect_value(&cluster_scheduling::CLUSTER_CRASH_LOOP_HEALTHY_PERIOD)
+    }
+
     /// Returns the `privatelink_status_update_quota_per_minute` configuration parameter.
     pub fn privatelink_status_update_quota_per_minute(&self) -> u32 {
         *self.expect_value(&PRIVATELINK_STATUS_UPDATE_QUOTA_PER_MINUTE)
@@ -3833,6 +4369,31 @@ impl SystemVars {
         *self.expect_value(&STATEMENT_LOGGING_MAX_SAMPLE_RATE)
     }
 
+    /// Returns the `strict_serializable_timestamp_difference_sample_rate` configuration parameter.
+    pub fn strict_serializable_timestamp_difference_sample_rate(&self) -> Numeric {
+        *self.expect_value(&STRICT_SERIALIZABLE_TIMESTAMP_DIFFERENCE_SAMPLE_RATE)
+    }
+
+    /// Returns the `timestamp_wait_sample_rate` configuration parameter.
+    pub fn timestamp_wait_sample_rate(&self) -> Numeric {
+        *self.expect_value(&TIMESTAMP_WAIT_SAMPLE_RATE)
+    }
+
+    /// Returns the `cluster_replica_disk_high_water_mark` configuration parameter.
+    pub fn cluster_replica_disk_high_water_mark(&self) -> Numeric {
+        *self.expect_value(&CLUSTER_REPLICA_DISK_HIGH_WATER_MARK)
+    }
+
+    /// Returns the `frontier_divergence_threshold` configuration parameter.
+    pub fn frontier_divergence_threshold(&self) -> Duration {
+        *self.expect_value(&FRONTIER_DIVERGENCE_THRESHOLD)
+    }
+
+    /// Returns the `frontier_divergence_ticks` configuration parameter.
+    pub fn frontier_divergence_ticks(&self) -> usize {
+        *self.expect_value(&FRONTIER_DIVERGENCE_TICKS)
+    }
+
     /// Returns the `statement_logging_default_sample_rate` configuration parameter.
     pub fn statement_logging_default_sample_rate(&self) -> Numeric {
         *self.expect_value(&STATEMENT_LOGGING_DEFAULT_SAMPLE_RATE)
@@ -3887,6 +4448,41 @@ impl SystemVars {
         *self.expect_value(&PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL_STAGGER)
     }
 
+    /// Returns the `pg_timestamp_oracle_circuit_breaker_failure_threshold` configuration parameter.
+    pub fn pg_timestamp_oracle_circuit_breaker_failure_threshold(&self) -> usize {
+        *self.expect_value(&PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_FAILURE_THRESHOLD)
+    }
+
+    /// Returns the `pg_timestamp_oracle_circuit_breaker_latency_threshold` configuration parameter.
+    pub fn pg_timestamp_oracle_circuit_breaker_latency_threshold(&self) -> Duration {
+        *self.expect_value(&PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_LATENCY_THRESHOLD)
+    }
+
+    /// Returns the `pg_timestamp_oracle_circuit_breaker_recovery_threshold` configuration parameter.
+    pub fn pg_timestamp_oracle_circuit_breaker_recovery_threshold(&self) -> usize {
+        *self.expect_value(&PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_RECOVERY_THRESHOLD)
+    }
+
+    /// Returns the `timestamp_oracle_degraded_read_queue_size` configuration parameter.
+    pub fn timestamp_oracle_degraded_read_queue_size(&self) -> usize {
+        *self.expect_value(&TIMESTAMP_ORACLE_DEGRADED_READ_QUEUE_SIZE)
+    }
+
+    /// Returns the `timestamp_oracle_degraded_read_timeout` configuration parameter.
+    pub fn timestamp_oracle_degraded_read_timeout(&self) -> Duration {
+        *self.expect_value(&TIMESTAMP_ORACLE_DEGRADED_READ_TIMEOUT)
+    }
+
+    /// Returns the `timestamp_oracle_degraded_serializable_reads` configuration parameter.
+    pub fn timestamp_oracle_degraded_serializable_reads(&self) -> bool {
+        *self.expect_value(&TIMESTAMP_ORACLE_DEGRADED_SERIALIZABLE_READS)
+    }
+
+    /// Returns the `linked_timelines` configuration parameter.
+    pub fn linked_timelines(&self) -> &String {
+        self.expect_value(&LINKED_TIMELINES)
+    }
+
     pub fn enable_dependency_read_hold_asserts(&self) -> bool {
         *self.expect_value(&ENABLE_DEPENDENCY_READ_HOLD_ASSERTS)
     }
@@ -4393,6 +4989,9 @@ pub trait SessionVarMut: Var + Send + Sync {
     /// Reset the stored value to the default.
     fn reset(&mut self, local: bool);
 
+    /// Reports whether a value was set in the session, as opposed to the default being used.
+    fn is_set(&self) -> bool;
+
     fn end_transaction(&mut self, action: EndTransactionAction);
 }
 
@@ -4450,6 +5049,10 @@ where
         }
     }
 
+    fn is_set(&self) -> bool {
+        self.local_value.is_some() || self.staged_value.is_some() || self.session_value.is_some()
+    }
+
     fn end_transaction(&mut self, action: EndTransactionAction) {
         self.local_value = None;
         match action {
@@ -5343,7 +5946,7 @@ impl Value for TimeZone {
 }
 
 /// List of valid isolation levels.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum IsolationLevel {
     ReadUncommitted,
     ReadCommitted,
@@ -5402,6 +6005,12 @@ impl IsolationLevel {
         }
     }
 
+    /// Parses an isolation level the way the `transaction_isolation` configuration parameter
+    /// does.
+    pub fn parse_str(s: &str) -> Result<Self, VarError> {
+        Self::parse(&TRANSACTION_ISOLATION, VarInput::Flat(s))
+    }
+
     fn valid_values() -> Vec<&'static str> {
         vec![
             Self::ReadUncommitted.as_str(),
@@ -5525,9 +6134,15 @@ impl SystemVars {
             || name == PG_SOURCE_KEEPALIVES_RETRIES.name()
             || name == PG_SOURCE_TCP_USER_TIMEOUT.name()
             || name == PG_SOURCE_SNAPSHOT_STATEMENT_TIMEOUT.name()
+            || name == PG_SOURCE_SNAPSHOT_LOCK_TIMEOUT.name()
             || name == PG_SOURCE_SNAPSHOT_COLLECT_STRICT_COUNT.name()
             || name == PG_SOURCE_SNAPSHOT_FALLBACK_TO_STRICT_COUNT.name()
             || name == PG_SOURCE_SNAPSHOT_WAIT_FOR_COUNT.name()
+            || name == PG_SOURCE_SNAPSHOT_MAX_IN_FLIGHT_BYTES.name()
+            || name == PG_SOURCE_SNAPSHOT_COPY_BUFFER_BYTES.name()
+            || name == PG_SOURCE_SNAPSHOT_PEEK_REWIND_CHANGES.name()
+            || name == PG_SOURCE_SNAPSHOT_GROUP_WINDOW.name()
+            || name == PG_SOURCE_SNAPSHOT_GROUP_TIMEOUT.name()
             || name == ENABLE_STORAGE_SHARD_FINALIZATION.name()
             || name == SSH_CHECK_INTERVAL.name()
             || name == SSH_CONNECT_TIMEOUT.name()
@@ -5544,6 +6159,10 @@ impl SystemVars {
             || name == STORAGE_DATAFLOW_DELAY_SOURCES_PAST_REHYDRATION.name()
             || name == STORAGE_SHRINK_UPSERT_UNUSED_BUFFERS_BY_RATIO.name()
             || name == STORAGE_RECORD_SOURCE_SINK_NAMESPACED_ERRORS.name()
+            || name == FRONTIERS_HISTORY_FULL_RESOLUTION_WINDOW.name()
+            || name == STORAGE_COMMAND_MAX_BYTES.name()
+            || name == STORAGE_COMMAND_CHUNKING.name()
+            || name == STORAGE_DROPPED_IDS_BATCH_SIZE.name()
             || is_upsert_rocksdb_config_var(name)
             || self.is_persist_config_var(name)
             || is_tracing_var(name)
@@ -5584,6 +6203,9 @@ pub fn is_pg_timestamp_oracle_config_var(name: &str) -> bool {
         || name == PG_TIMESTAMP_ORACLE_CONNECTION_POOL_MAX_WAIT.name()
         || name == PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL.name()
         || name == PG_TIMESTAMP_ORACLE_CONNECTION_POOL_TTL_STAGGER.name()
+        || name == PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_FAILURE_THRESHOLD.name()
+        || name == PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_LATENCY_THRESHOLD.name()
+        || name == PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_RECOVERY_THRESHOLD.name()
         || name == CRDB_CONNECT_TIMEOUT.name()
         || name == CRDB_TCP_USER_TIMEOUT.name()
 }
@@ -5600,6 +6222,11 @@ pub fn is_cluster_scheduling_var(name: &str) -> bool {
         || name == cluster_scheduling::CLUSTER_SOFTEN_AZ_AFFINITY.name()
         || name == cluster_scheduling::CLUSTER_SOFTEN_AZ_AFFINITY_WEIGHT.name()
         || name == cluster_scheduling::CLUSTER_ALWAYS_USE_DISK.name()
+        || name == cluster_scheduling::CLUSTER_CRASH_LOOP_MAX_RESTARTS.name()
+        || name == cluster_scheduling::CLUSTER_CRASH_LOOP_WINDOW.name()
+        || name == cluster_scheduling::CLUSTER_CRASH_LOOP_INITIAL_BACKOFF.name()
+        || name == cluster_scheduling::CLUSTER_CRASH_LOOP_MAX_BACKOFF.name()
+        || name == cluster_scheduling::CLUSTER_CRASH_LOOP_HEALTHY_PERIOD.name()
 }
 
 /// Returns whether the named variable is an HTTP server related config var.
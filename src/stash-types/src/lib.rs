@@ -45,6 +45,11 @@ impl StashError {
         }
     }
 
+    /// Reports whether the error is due to a transaction not completing before its deadline.
+    pub fn is_deadline_exceeded(&self) -> bool {
+        matches!(self.inner, InternalStashError::DeadlineExceeded)
+    }
+
     /// The underlying transaction failed in a way that must be resolved by retrying
     pub fn should_retry(&self) -> bool {
         match &self.inner {
@@ -71,6 +76,7 @@ pub enum InternalStashError {
     Decoding(prost::DecodeError),
     Uninitialized,
     StashNotWritable(String),
+    DeadlineExceeded,
     Other(String),
 }
 
@@ -92,6 +98,7 @@ impl fmt::Display for StashError {
             }
             InternalStashError::Uninitialized => write!(f, "uninitialized"),
             InternalStashError::StashNotWritable(e) => f.write_str(e),
+            InternalStashError::DeadlineExceeded => f.write_str("transaction deadline exceeded"),
             InternalStashError::Other(e) => f.write_str(e),
         }
     }
@@ -12,7 +12,7 @@ use std::collections::BTreeMap;
 use std::fmt::Write;
 use std::num::NonZeroI64;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use derivative::Derivative;
 use differential_dataflow::lattice::Lattice;
@@ -405,6 +405,8 @@ impl StashFactory {
             reconnect: tokio::time::interval(RECONNECT_INTERVAL),
             statements: None,
             epoch: None,
+            committed_version: None,
+            abandoned: false,
             // The call to rand::random here assumes that the seed source is from a secure
             // source that will differ per thread. The docs for ThreadRng say it "is
             // automatically seeded from OsRng", which meets this requirement.
@@ -412,6 +414,7 @@ impl StashFactory {
             sinces_tx,
             metrics: Arc::clone(&self.metrics),
             collections: BTreeMap::new(),
+            health: Arc::new(Mutex::new(ConnectionHealth::default())),
         };
 
         // Do the initial connection once here so we don't get stuck in transact's retry loop if the
@@ -461,10 +464,44 @@ pub struct Stash {
 
     statements: Option<PreparedStatements>,
     epoch: Option<NonZeroI64>,
+    /// The fence version written by the most recent transaction known to have committed.
+    committed_version: Option<i64>,
+    /// Whether a transaction was abandoned at its deadline without knowing if it committed.
+    abandoned: bool,
     nonce: [u8; 16],
     pub(crate) sinces_tx: mpsc::UnboundedSender<ConsolidateRequest>,
     pub(crate) collections: BTreeMap<String, Id>,
     pub metrics: Arc<Metrics>,
+    /// The health of the connection, which is shared with the task that drives it.
+    health: Arc<Mutex<ConnectionHealth>>,
+}
+
+/// The health of the connection of a [`Stash`] to its Postgres server.
+#[derive(Debug, Default)]
+pub(crate) struct ConnectionHealth {
+    /// Whether the connection failed since the most recent successful transaction.
+    failed: bool,
+    /// The error the connection most recently failed with, which is retained after it recovers.
+    last_error: Option<String>,
+}
+
+impl ConnectionHealth {
+    pub(crate) fn fail(&mut self, error: String) {
+        self.failed = true;
+        self.last_error = Some(error);
+    }
+
+    pub(crate) fn succeed(&mut self) {
+        self.failed = false;
+    }
+
+    pub(crate) fn is_connected(&self) -> bool {
+        !self.failed
+    }
+
+    pub(crate) fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
 }
 
 #[derive(Debug)]
@@ -570,6 +607,7 @@ impl Stash {
                     .connection_attempts
                     .with_label_values(&["success"])
                     .inc();
+                self.health.lock().unwrap().succeed();
                 (client, connection)
             }
             Err(e) => {
@@ -577,14 +615,17 @@ impl Stash {
                     .connection_attempts
                     .with_label_values(&["failure"])
                     .inc();
+                self.health.lock().unwrap().fail(e.to_string());
                 return Err(e.into());
             }
         };
 
         let metrics = Arc::clone(&self.metrics);
+        let health = Arc::clone(&self.health);
         mz_ore::task::spawn(|| "tokio-postgres stash connection", async move {
             if let Err(e) = connection.await {
                 metrics.connection_errors.inc();
+                health.lock().unwrap().fail(e.to_string());
                 tracing::warn!("postgres stash connection error: {}", e);
             }
         });
@@ -692,11 +733,15 @@ impl Stash {
     /// backoff-retry loop if the Postgres server is unavailable, so it should only
     /// call functions on its Transaction argument.
     ///
+    /// If `deadline` passes before the transaction completes, the transaction is abandoned and
+    /// [`InternalStashError::DeadlineExceeded`] is returned. An abandoned transaction may still
+    /// commit, which the next transaction determines before doing anything else.
+    ///
     /// # Examples
     ///
     /// ```text
     /// async fn x(&mut self) -> Result<(), StashError> {
-    ///     self.transact(move |stmts, tx| {
+    ///     self.transact(None, move |stmts, tx| {
     ///         Box::pin(async move {
     ///             // Use tx.
     ///         })
@@ -705,7 +750,11 @@ impl Stash {
     ///  }
     /// ```
     #[tracing::instrument(name = "stash::transact", level = "debug", skip_all)]
-    pub(crate) async fn transact<F, T>(&mut self, f: F) -> Result<T, StashError>
+    pub(crate) async fn transact<F, T>(
+        &mut self,
+        deadline: Option<Instant>,
+        f: F,
+    ) -> Result<T, StashError>
     where
         F: for<'a> Fn(
             &'a CountedStatements<'a>,
@@ -725,15 +774,31 @@ impl Stash {
             self.client = None;
         }
 
+        if self.abandoned {
+            with_deadline(deadline, self.resolve_abandoned()).await??;
+        }
+
         'transact_inner: loop {
             // Execute the operation in a transaction or savepoint.
-            match self.transact_inner(&f).await {
-                Ok(r) => return Ok(r),
+            let result = match with_deadline(deadline, self.transact_inner(&f)).await {
+                Ok(result) => result,
+                Err(err) => {
+                    self.abandon();
+                    return Err(err);
+                }
+            };
+            match result {
+                Ok((r, version)) => {
+                    self.committed_version = Some(version);
+                    self.health.lock().unwrap().succeed();
+                    return Ok(r);
+                }
                 Err(e) => {
                     // If this returns an error, close the connection to force a
                     // reconnect (and also not need to worry about any
                     // in-progress transaction state cleanup).
                     self.client = None;
+                    self.health.lock().unwrap().fail(e.to_string());
 
                     attempt += 1;
                     let cause = e.cause();
@@ -770,7 +835,7 @@ impl Stash {
                         // fatal crash in environmentd because a transaction
                         // could have committed without us receiving the commit
                         // confirmation
-                        retry.next().await;
+                        with_deadline(deadline, retry.next()).await?;
                     } else {
                         match e {
                             TransactionError::Commit {
@@ -780,9 +845,23 @@ impl Stash {
                                 // COMMIT is indeterminate. Check if it succeeded in a
                                 // new transaction.
                                 loop {
-                                    match self.determine_commit(committed_if_version).await {
+                                    let determined = match with_deadline(
+                                        deadline,
+                                        self.determine_commit(committed_if_version),
+                                    )
+                                    .await
+                                    {
+                                        Ok(determined) => determined,
+                                        Err(err) => {
+                                            self.abandon();
+                                            return Err(err);
+                                        }
+                                    };
+                                    match determined {
                                         Ok(succeeded) => {
                                             if succeeded {
+                                                self.committed_version = Some(committed_if_version);
+                                                self.health.lock().unwrap().succeed();
                                                 return Ok(result);
                                             } else {
                                                 // COMMIT failed, retry the transaction.
@@ -811,7 +890,7 @@ impl Stash {
     }
 
     #[tracing::instrument(name = "stash::transact_inner", level = "debug", skip_all)]
-    async fn transact_inner<F, T>(&mut self, f: &F) -> Result<T, TransactionError<T>>
+    async fn transact_inner<F, T>(&mut self, f: &F) -> Result<(T, i64), TransactionError<T>>
     where
         F: for<'a> Fn(
             &'a CountedStatements<'a>,
@@ -912,7 +991,56 @@ impl Stash {
             });
         }
 
-        Ok(res)
+        Ok((res, committed_if_version))
+    }
+
+    /// Abandons the transaction in flight, closing the connection so that it is rolled back
+    /// unless its COMMIT was already received.
+    fn abandon(&mut self) {
+        self.client = None;
+        if matches!(self.txn_mode, TransactionMode::Writeable) {
+            self.abandoned = true;
+        }
+    }
+
+    /// Determines whether a transaction abandoned at its deadline committed. Callers proceed as
+    /// if it did not, so this fails with an unrecoverable error if it did, or if that cannot be
+    /// determined.
+    #[tracing::instrument(name = "stash::resolve_abandoned", level = "debug", skip_all)]
+    async fn resolve_abandoned(&mut self) -> Result<(), StashError> {
+        self.connect(None).await?;
+
+        let client = self.client.as_mut().unwrap();
+        let row = client
+            .query_one("SELECT epoch, nonce, version FROM fence", &[])
+            .await?;
+        let epoch = NonZeroI64::new(row.get("epoch")).unwrap();
+        let nonce: Vec<u8> = row.get("nonce");
+        let version: i64 = row.get("version");
+        if Some(epoch) != self.epoch || nonce != self.nonce {
+            return Err(InternalStashError::Fence("unexpected epoch or nonce".into()).into());
+        }
+        if Some(version) != self.committed_version {
+            return Err(InternalStashError::Fence(format!(
+                "transaction abandoned at its deadline may have committed: \
+                found version {version}, expected {:?}",
+                self.committed_version
+            ))
+            .into());
+        }
+        self.abandoned = false;
+        Ok(())
+    }
+
+    /// Reads the epoch currently recorded in the fence, which is newer than [`Stash::epoch`] if
+    /// another stash has fenced this one out.
+    #[tracing::instrument(name = "stash::fence_epoch", level = "debug", skip_all)]
+    pub async fn fence_epoch(&mut self) -> Result<NonZeroI64, StashError> {
+        self.connect(None).await?;
+
+        let client = self.client.as_mut().unwrap();
+        let row = client.query_one("SELECT epoch FROM fence", &[]).await?;
+        Ok(NonZeroI64::new(row.get("epoch")).unwrap())
     }
 
     /// Reports whether a COMMIT that returned an error actually succeeded. An
@@ -939,6 +1067,19 @@ impl Stash {
     }
 }
 
+/// Awaits `fut`, failing with [`InternalStashError::DeadlineExceeded`] if `deadline` passes first.
+async fn with_deadline<T>(
+    deadline: Option<Instant>,
+    fut: impl Future<Output = T>,
+) -> Result<T, StashError> {
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline.into(), fut)
+            .await
+            .map_err(|_| InternalStashError::DeadlineExceeded.into()),
+        None => Ok(fut.await),
+    }
+}
+
 enum TransactionError<T> {
     /// A failure occurred pre-transaction.
     Connect(StashError),
@@ -1084,6 +1225,22 @@ impl Stash {
     pub fn epoch(&self) -> Option<NonZeroI64> {
         self.epoch
     }
+
+    /// Reports whether the stash is connected, i.e. whether its connection has not failed since
+    /// its most recent successful transaction.
+    pub fn is_connected(&self) -> bool {
+        self.health.lock().unwrap().is_connected()
+    }
+
+    /// Returns the error the connection most recently failed with, if any, even if the stash has
+    /// reconnected since.
+    pub fn last_connection_error(&self) -> Option<String> {
+        self.health
+            .lock()
+            .expect("lock poisoned")
+            .last_error()
+            .map(str::to_string)
+    }
 }
 
 /// The Consolidator receives since advancements on a channel and
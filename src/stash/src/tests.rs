@@ -500,7 +500,9 @@ where
 #[mz_ore::test(tokio::test)]
 #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
 async fn test_stash_fail_after_commit() {
-    Stash::with_debug_stash(|mut stash| async move {
+    let factory = DebugStashFactory::try_new().await.expect("must succeed");
+    {
+        let mut stash = factory.open().await;
         let col = collection::<i64, i64>(&mut stash, "c1").await.unwrap();
         let mut batch = make_batch(&col, &mut stash).await.unwrap();
         col.append_to_batch(&mut batch, &1, &2, 1);
@@ -509,6 +511,8 @@ async fn test_stash_fail_after_commit() {
             C1.peek_one(&mut stash).await.unwrap(),
             BTreeMap::from([(1, 2)])
         );
+        assert!(stash.is_connected());
+        assert_eq!(stash.last_connection_error(), None);
         let mut batch = make_batch(&col, &mut stash).await.unwrap();
         col.append_to_batch(&mut batch, &1, &2, -1);
 
@@ -533,11 +537,29 @@ async fn test_stash_fail_after_commit() {
         });
         append(&mut stash, vec![batch.clone()]).await.unwrap();
         assert_eq!(C1.peek_one(&mut stash).await.unwrap(), BTreeMap::new());
+        // The stash reconnected after the failed commits, but still reports their error.
+        assert!(stash.is_connected());
+        assert_contains!(
+            stash.last_connection_error().unwrap(),
+            "TransactionError::Commit"
+        );
         tx.send(()).unwrap();
         handle.await.unwrap();
-    })
-    .await
-    .expect("must succeed");
+
+        // Once another stash fences this one out, all later operations fail, and the stash
+        // reports the fence as the reason it is disconnected.
+        let _fencer = factory.open().await;
+        let err = C1.peek_one(&mut stash).await.unwrap_err();
+        assert!(err.is_unrecoverable(), "{err:?}");
+        let err = make_batch(&col, &mut stash).await.unwrap_err();
+        assert!(err.is_unrecoverable(), "{err:?}");
+        assert!(!stash.is_connected());
+        assert_contains!(
+            stash.last_connection_error().unwrap(),
+            "unexpected fence epoch"
+        );
+    }
+    factory.drop().await;
 }
 
 #[mz_ore::test(tokio::test)]
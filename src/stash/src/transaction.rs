@@ -11,6 +11,7 @@ use std::cmp;
 use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use futures::future::join_all;
 use futures::FutureExt;
@@ -48,11 +49,28 @@ pub(crate) const MAX_INSERT_ARGUMENTS: u16 = u16::MAX / 4;
 impl Stash {
     /// Transactionally executes closure `f`.
     pub async fn with_transaction<F, T>(&mut self, f: F) -> Result<T, StashError>
+    where
+        F: FnOnce(Transaction) -> BoxFuture<Result<T, StashError>> + Clone + Sync + Send + 'static,
+    {
+        self.with_transaction_deadline(None, f).await
+    }
+
+    /// Like [`Stash::with_transaction`], but abandons the transaction if it does not complete
+    /// before `deadline`, if any, returning an error for which
+    /// [`StashError::is_deadline_exceeded`] is true.
+    ///
+    /// An abandoned transaction may still commit. The next transaction determines whether it
+    /// did before doing anything else, and fails with an unrecoverable error if so.
+    pub async fn with_transaction_deadline<F, T>(
+        &mut self,
+        deadline: Option<Instant>,
+        f: F,
+    ) -> Result<T, StashError>
     where
         F: FnOnce(Transaction) -> BoxFuture<Result<T, StashError>> + Clone + Sync + Send + 'static,
     {
         let (res, mut cons_rx, txn_collections) = self
-            .transact(|stmts, client, collections| {
+            .transact(deadline, |stmts, client, collections| {
                 let f = f.clone();
                 let (cons_tx, cons_rx) = mpsc::unbounded_channel();
                 let txn_collections = Arc::new(Mutex::new(BTreeMap::new()));
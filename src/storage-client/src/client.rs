@@ -14,30 +14,38 @@
 
 //! The public API of the storage layer.
 
-use std::collections::{BTreeMap, BTreeSet};
-use std::fmt::Debug;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::{self, Debug};
 use std::iter;
 
 use async_trait::async_trait;
 use differential_dataflow::lattice::Lattice;
 use mz_cluster_client::client::{ClusterStartupEpoch, TimelyConfig, TryIntoTimelyConfig};
+use mz_cluster_client::ReplicaId;
+use mz_ore::soft_panic_or_log;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::{Diff, GlobalId, Row};
+use mz_service::chunk::{ChunkedCommand, ProtoCommandChunk, MAX_CHUNKED_COMMAND_BYTES};
 use mz_service::client::{GenericClient, Partitionable, PartitionedState};
 use mz_service::grpc::{GrpcClient, GrpcServer, ProtoServiceTypes, ResponseStream};
-use mz_storage_types::controller::CollectionMetadata;
+use mz_storage_types::controller::{CollectionMetadata, StorageError};
+use mz_storage_types::instances::StorageInstanceId;
 use mz_storage_types::parameters::StorageParameters;
 use mz_storage_types::sinks::{MetadataFilled, StorageSinkDesc};
+use mz_storage_types::sources::postgres::GroupSnapshot;
 use mz_storage_types::sources::IngestionDescription;
 use mz_timely_util::progress::any_antichain;
 use proptest::prelude::{any, Arbitrary};
 use proptest::strategy::{BoxedStrategy, Strategy, Union};
+use prost::Message;
 use serde::{Deserialize, Serialize};
 use timely::progress::frontier::{Antichain, MutableAntichain};
 use timely::PartialOrder;
 use tonic::{Request, Status as TonicStatus, Streaming};
 
 use crate::client::proto_storage_server::ProtoStorage;
+use crate::controller::CollectionNames;
 use crate::metrics::RehydratingStorageClientMetrics;
 use crate::statistics::{SinkStatisticsUpdate, SourceStatisticsUpdate};
 
@@ -72,6 +80,21 @@ impl ProtoServiceTypes for StorageProtoServiceTypes {
     const URL: &'static str = "/mz_storage_client.client.ProtoStorage/CommandResponseStream";
 }
 
+impl ChunkedCommand for ProtoStorageCommand {
+    fn from_chunk(chunk: ProtoCommandChunk) -> Option<Self> {
+        Some(ProtoStorageCommand {
+            kind: Some(proto_storage_command::Kind::Chunk(chunk)),
+        })
+    }
+
+    fn into_chunk(self) -> Result<ProtoCommandChunk, Self> {
+        match self.kind {
+            Some(proto_storage_command::Kind::Chunk(chunk)) => Ok(chunk),
+            kind => Err(ProtoStorageCommand { kind }),
+        }
+    }
+}
+
 pub type StorageGrpcClient = GrpcClient<StorageProtoServiceTypes>;
 
 #[async_trait]
@@ -112,6 +135,13 @@ pub enum StorageCommand<T = mz_repr::Timestamp> {
     /// accumulations must be correct.
     AllowCompaction(Vec<(GlobalId, Antichain<T>)>),
     RunSinks(Vec<RunSinkCommand<T>>),
+    /// Report the state of the identified collections, as read from persist and as last reported
+    /// by the workers, in a [`StorageResponse::CollectionStateReport`].
+    ///
+    /// This is a diagnostic command that lets the controller check its view of the collections
+    /// for consistency. It doesn't change the state of the replica and so is not replayed when
+    /// the replica reconnects.
+    ReportCollectionState(BTreeSet<GlobalId>),
 }
 
 /// A command that starts ingesting the given ingestion description
@@ -122,6 +152,44 @@ pub struct RunIngestionCommand {
     /// The description of what source type should be ingested and what post-processing steps must
     /// be applied to the data before writing them down into the storage collection
     pub description: IngestionDescription<CollectionMetadata>,
+    /// The generation of the storage parameters that the controller had applied when it built
+    /// this command. See [`StorageParameters::generation`].
+    pub parameter_generation: u64,
+    /// The snapshot that the controller exported for the snapshot group of the ingestion, which
+    /// the ingestion imports instead of taking a snapshot of its own.
+    pub group_snapshot: Option<GroupSnapshot>,
+}
+
+impl RunIngestionCommand {
+    /// Checks that the command that runs this ingestion can be sent to a replica under the
+    /// given storage parameters.
+    ///
+    /// Commands are split into commands of at most `storage_command_max_bytes`, but a single
+    /// ingestion can't be split further. Unless `storage_command_chunking` is enabled, an
+    /// ingestion whose encoding exceeds the limit on its own fails the check.
+    pub fn check_size(&self, params: &StorageParameters) -> Result<(), StorageError> {
+        let limit = if params.storage_command_chunking {
+            MAX_CHUNKED_COMMAND_BYTES
+        } else {
+            params.storage_command_max_bytes
+        };
+        let size = ProtoStorageCommand {
+            kind: Some(proto_storage_command::Kind::CreateSources(
+                ProtoCreateSources {
+                    sources: vec![self.into_proto()],
+                },
+            )),
+        }
+        .encoded_len();
+        if size > limit {
+            return Err(StorageError::CommandTooLarge {
+                id: self.id,
+                size,
+                limit,
+            });
+        }
+        Ok(())
+    }
 }
 
 impl Arbitrary for RunIngestionCommand {
@@ -132,8 +200,17 @@ impl Arbitrary for RunIngestionCommand {
         (
             any::<GlobalId>(),
             any::<IngestionDescription<CollectionMetadata>>(),
+            any::<u64>(),
+            proptest::option::of(any::<GroupSnapshot>()),
         )
-            .prop_map(|(id, description)| Self { id, description })
+            .prop_map(
+                |(id, description, parameter_generation, group_snapshot)| Self {
+                    id,
+                    description,
+                    parameter_generation,
+                    group_snapshot,
+                },
+            )
             .boxed()
     }
 }
@@ -143,6 +220,8 @@ impl RustType<ProtoRunIngestionCommand> for RunIngestionCommand {
         ProtoRunIngestionCommand {
             id: Some(self.id.into_proto()),
             description: Some(self.description.into_proto()),
+            parameter_generation: self.parameter_generation,
+            group_snapshot: self.group_snapshot.into_proto(),
         }
     }
 
@@ -152,6 +231,8 @@ impl RustType<ProtoRunIngestionCommand> for RunIngestionCommand {
             description: proto
                 .description
                 .into_rust_if_some("ProtoRunIngestionCommand::description")?,
+            parameter_generation: proto.parameter_generation,
+            group_snapshot: proto.group_snapshot.into_rust()?,
         })
     }
 }
@@ -161,6 +242,7 @@ impl RustType<ProtoRunSinkCommand> for RunSinkCommand<mz_repr::Timestamp> {
         ProtoRunSinkCommand {
             id: Some(self.id.into_proto()),
             description: Some(self.description.into_proto()),
+            reset_progress: self.reset_progress,
         }
     }
 
@@ -170,6 +252,7 @@ impl RustType<ProtoRunSinkCommand> for RunSinkCommand<mz_repr::Timestamp> {
             description: proto
                 .description
                 .into_rust_if_some("ProtoRunSinkCommand::description")?,
+            reset_progress: proto.reset_progress,
         })
     }
 }
@@ -179,6 +262,9 @@ impl RustType<ProtoRunSinkCommand> for RunSinkCommand<mz_repr::Timestamp> {
 pub struct RunSinkCommand<T> {
     pub id: GlobalId,
     pub description: StorageSinkDesc<MetadataFilled, T>,
+    /// Whether the sink should discard its recorded progress if it no longer matches the state
+    /// of its external system, and resume from the `as_of` of `description` instead.
+    pub reset_progress: bool,
 }
 
 impl Arbitrary for RunSinkCommand<mz_repr::Timestamp> {
@@ -189,8 +275,13 @@ impl Arbitrary for RunSinkCommand<mz_repr::Timestamp> {
         (
             any::<GlobalId>(),
             any::<StorageSinkDesc<MetadataFilled, mz_repr::Timestamp>>(),
+            any::<bool>(),
         )
-            .prop_map(|(id, description)| Self { id, description })
+            .prop_map(|(id, description, reset_progress)| Self {
+                id,
+                description,
+                reset_progress,
+            })
             .boxed()
     }
 }
@@ -220,6 +311,11 @@ impl RustType<ProtoStorageCommand> for StorageCommand<mz_repr::Timestamp> {
                 StorageCommand::RunSinks(sinks) => RunSinks(ProtoRunSinks {
                     sinks: sinks.into_proto(),
                 }),
+                StorageCommand::ReportCollectionState(ids) => {
+                    ReportCollectionState(ProtoReportCollectionState {
+                        ids: ids.into_proto(),
+                    })
+                }
             }),
         }
     }
@@ -247,6 +343,13 @@ impl RustType<ProtoStorageCommand> for StorageCommand<mz_repr::Timestamp> {
             Some(RunSinks(ProtoRunSinks { sinks })) => {
                 Ok(StorageCommand::RunSinks(sinks.into_rust()?))
             }
+            Some(ReportCollectionState(ProtoReportCollectionState { ids })) => {
+                Ok(StorageCommand::ReportCollectionState(ids.into_rust()?))
+            }
+            // Chunks are reassembled by the transport, and are never converted on their own.
+            Some(Chunk(_)) => Err(TryFromProtoError::UnknownEnumVariant(
+                "ProtoStorageCommand::kind::chunk".into(),
+            )),
             None => Err(TryFromProtoError::missing_field(
                 "ProtoStorageCommand::kind",
             )),
@@ -254,6 +357,69 @@ impl RustType<ProtoStorageCommand> for StorageCommand<mz_repr::Timestamp> {
     }
 }
 
+impl<T> StorageCommand<T>
+where
+    T: Clone,
+    StorageCommand<T>: RustType<ProtoStorageCommand>,
+{
+    /// Splits this command into a sequence of commands whose protobuf
+    /// encodings each fit within `max_bytes`.
+    ///
+    /// Only `RunIngestions`, `RunSinks`, and `AllowCompaction` are split, and
+    /// applying the resulting commands in order has the same effect as applying
+    /// the original command. An individual entry that exceeds `max_bytes` on
+    /// its own is sent in a command by itself.
+    pub fn split_by_size(self, max_bytes: usize) -> Vec<StorageCommand<T>> {
+        if self.into_proto().encoded_len() <= max_bytes {
+            return vec![self];
+        }
+
+        match self {
+            StorageCommand::RunIngestions(ingestions) => {
+                Self::chunk_by_size(ingestions, max_bytes, StorageCommand::RunIngestions)
+            }
+            StorageCommand::RunSinks(sinks) => {
+                Self::chunk_by_size(sinks, max_bytes, StorageCommand::RunSinks)
+            }
+            StorageCommand::AllowCompaction(frontiers) => {
+                Self::chunk_by_size(frontiers, max_bytes, StorageCommand::AllowCompaction)
+            }
+            command @ (StorageCommand::CreateTimely { .. }
+            | StorageCommand::InitializationComplete
+            | StorageCommand::UpdateConfiguration(_)
+            | StorageCommand::ReportCollectionState(_)) => vec![command],
+        }
+    }
+
+    /// Groups `items` into as few commands as possible, each constructed by
+    /// `wrap` and each no larger than `max_bytes` when encoded.
+    fn chunk_by_size<I: Clone>(
+        items: Vec<I>,
+        max_bytes: usize,
+        wrap: impl Fn(Vec<I>) -> StorageCommand<T>,
+    ) -> Vec<StorageCommand<T>> {
+        let mut commands = Vec::new();
+        let mut chunk = Vec::new();
+        let mut chunk_bytes = 0;
+        for item in items {
+            // Measuring each entry as a command of its own overestimates its
+            // contribution by the size of the command envelope, which keeps
+            // the estimate conservative.
+            let item_bytes = wrap(vec![item.clone()]).into_proto().encoded_len();
+            if !chunk.is_empty() && chunk_bytes + item_bytes > max_bytes {
+                commands.push(wrap(std::mem::take(&mut chunk)));
+                chunk_bytes = 0;
+            }
+            chunk_bytes += item_bytes;
+            chunk.push(item);
+        }
+        if !chunk.is_empty() || commands.is_empty() {
+            commands.push(wrap(chunk));
+        }
+        commands
+    }
+}
+
 impl Arbitrary for StorageCommand<mz_repr::Timestamp> {
     type Strategy = Union<BoxedStrategy<Self>>;
     type Parameters = ();
@@ -283,6 +449,9 @@ impl Arbitrary for StorageCommand<mz_repr::Timestamp> {
                 )
             })
             .boxed(),
+            proptest::collection::btree_set(any::<GlobalId>(), 1..4)
+                .prop_map(StorageCommand::ReportCollectionState)
+                .boxed(),
         ])
     }
 }
@@ -342,6 +511,22 @@ impl Status {
             _ => true,
         }
     }
+
+    /// Ranks how unhealthy an object in this status is, with higher values
+    /// being worse. Used to roll the statuses of related objects, e.g. a
+    /// source and its subsources, up into a single status.
+    ///
+    /// Keep in sync with `status_severity!` in `mz_catalog::builtin`.
+    pub fn severity(&self) -> u8 {
+        match self {
+            Status::Running => 0,
+            Status::Starting => 1,
+            Status::Paused => 2,
+            Status::Stalled => 3,
+            Status::Ceased => 4,
+            Status::Dropped => 5,
+        }
+    }
 }
 
 /// A source or sink status update.
@@ -374,35 +559,38 @@ impl StatusUpdate {
             namespaced_errors: Default::default(),
         }
     }
-}
 
-impl From<StatusUpdate> for Row {
-    fn from(update: StatusUpdate) -> Self {
+    /// Packs the update into a row of the object's status history relation, recording
+    /// `attribution` and `origin` along with it.
+    pub fn into_row(
+        self,
+        attribution: Option<&StatusAttribution>,
+        origin: Option<&StatusOrigin>,
+    ) -> Row {
         use mz_repr::Datum;
 
-        let timestamp = Datum::TimestampTz(update.timestamp.try_into().expect("must fit"));
-        let id = update.id.to_string();
+        let timestamp = Datum::TimestampTz(self.timestamp.try_into().expect("must fit"));
+        let id = self.id.to_string();
         let id = Datum::String(&id);
-        let status = Datum::String(update.status.to_str());
-        let error = update.error.as_deref().into();
+        let status = Datum::String(self.status.to_str());
+        let error = self.error.as_deref().into();
 
         let mut row = Row::default();
         let mut packer = row.packer();
         packer.extend([timestamp, id, status, error]);
 
-        if !update.hints.is_empty() || !update.namespaced_errors.is_empty() {
+        if !self.hints.is_empty() || !self.namespaced_errors.is_empty() {
             packer.push_dict_with(|dict_packer| {
                 // `hint` and `namespaced` are ordered,
                 // as well as the BTree's they each contain.
-                if !update.hints.is_empty() {
+                if !self.hints.is_empty() {
                     dict_packer.push(Datum::String("hints"));
-                    dict_packer.push_list(update.hints.iter().map(|s| Datum::String(s)));
+                    dict_packer.push_list(self.hints.iter().map(|s| Datum::String(s)));
                 }
-                if !update.namespaced_errors.is_empty() {
+                if !self.namespaced_errors.is_empty() {
                     dict_packer.push(Datum::String("namespaced"));
                     dict_packer.push_dict(
-                        update
-                            .namespaced_errors
+                        self.namespaced_errors
                             .iter()
                             .map(|(k, v)| (k.as_str(), Datum::String(v))),
                     );
@@ -412,10 +600,51 @@ impl From<StatusUpdate> for Row {
             packer.push(Datum::Null);
         }
 
+        let owner = attribution.and_then(|a| a.owner.as_deref());
+        let comment = attribution.and_then(|a| a.comment.as_deref());
+        packer.extend([Datum::from(owner), Datum::from(comment)]);
+
+        let cluster_id = origin.map(|o| o.cluster_id.to_string());
+        let replica_id = origin.and_then(|o| o.replica_id).map(|id| id.to_string());
+        packer.extend([
+            Datum::from(cluster_id.as_deref()),
+            Datum::from(replica_id.as_deref()),
+        ]);
+
         row
     }
 }
 
+/// The owner and comment of a source or sink, as of when one of its status updates is recorded.
+///
+/// The attribution is denormalized into the status history, so that rows remain attributable
+/// after the object they describe is dropped.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StatusAttribution {
+    /// The name of the role that owns the object.
+    pub owner: Option<String>,
+    /// The comment on the object, if any.
+    pub comment: Option<String>,
+}
+
+/// The storage cluster and replica that reported a status update.
+///
+/// Updates without an origin are recorded by the controller itself, and apply to all replicas of
+/// the object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StatusOrigin {
+    /// The storage instance whose client delivered the update.
+    pub cluster_id: StorageInstanceId,
+    /// The replica of the instance the update came from, if it is known.
+    pub replica_id: Option<ReplicaId>,
+}
+
+impl From<StatusUpdate> for Row {
+    fn from(update: StatusUpdate) -> Self {
+        update.into_row(None, None)
+    }
+}
+
 impl RustType<proto_storage_response::ProtoStatus> for Status {
     fn into_proto(&self) -> proto_storage_response::ProtoStatus {
         use proto_storage_response::proto_status::*;
@@ -484,6 +713,11 @@ impl RustType<proto_storage_response::ProtoStatusUpdate> for StatusUpdate {
 pub enum StorageResponse<T = mz_repr::Timestamp> {
     /// A list of identifiers of traces, with new upper frontiers.
     ///
+    /// For sources, the upper is the frontier up to which data has been written to the
+    /// collection's shard. For sinks, the upper is the frontier up to which the sink has emitted
+    /// its input to the external system, which is what the controller records as the sink's write
+    /// frontier.
+    ///
     /// TODO(teskje): Consider also reporting the previous upper frontier and using that
     /// information to assert the correct implementation of our protocols at various places.
     FrontierUppers(Vec<(GlobalId, Antichain<T>)>),
@@ -495,12 +729,129 @@ pub enum StorageResponse<T = mz_repr::Timestamp> {
     /// A list of status updates for sources and sinks. Periodically sent from
     /// storage workers to convey the latest status information about an object.
     StatusUpdates(Vec<StatusUpdate>),
+    /// The number of bytes the upstream system has produced for a source that the source has not
+    /// yet durably consumed, e.g. the backlog retained by a PostgreSQL replication slot.
+    /// Periodically sent from the storage worker reading from the upstream system.
+    UpstreamLag { id: GlobalId, bytes_behind: u64 },
+    /// The generation of the storage parameters an ingestion's dataflow was rendered with. Sent
+    /// by each storage worker whenever it (re-)renders the ingestion.
+    ParameterGeneration { id: GlobalId, generation: u64 },
+    /// The frontier up to which a sink has emitted its input to the external system. Unlike the
+    /// sink's entry in `FrontierUppers`, this is only reported by the worker that writes to the
+    /// external system, which lets the controller tell whether the sink has caught up or is still
+    /// backfilling its snapshot.
+    SinkProgress {
+        id: GlobalId,
+        emitted_upper: Antichain<T>,
+    },
+    /// The state of collections, in response to a [`StorageCommand::ReportCollectionState`].
+    CollectionStateReport(Vec<CollectionStateReport<T>>),
+}
+
+/// The state of a collection on a replica, as reported in a
+/// [`StorageResponse::CollectionStateReport`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CollectionStateReport<T = mz_repr::Timestamp> {
+    pub id: GlobalId,
+    /// The upper of the collection's shard, read from persist directly. `None` if the replica
+    /// doesn't know the shard of the collection.
+    pub persist_upper: Option<Antichain<T>>,
+    /// The since of the collection's shard, read from persist directly. `None` if the replica
+    /// doesn't know the shard of the collection.
+    pub persist_since: Option<Antichain<T>>,
+    /// The error that reading the collection's shard failed with, in which case the persist
+    /// frontiers are `None`.
+    pub persist_error: Option<String>,
+    /// The upper that the replica last reported for the collection in
+    /// [`StorageResponse::FrontierUppers`]. `None` if it hasn't reported an upper yet.
+    pub reported_upper: Option<Antichain<T>>,
+}
+
+impl<T: timely::progress::Timestamp + Lattice> CollectionStateReport<T> {
+    /// Merges the report of another partition of the replica into this one.
+    ///
+    /// Only one partition reads the shard, so the persist frontiers are taken from whichever
+    /// report has them. The collection has only been written up to a time once every partition
+    /// has written it, so the reported upper is the meet of the partitions' uppers.
+    fn merge(&mut self, other: CollectionStateReport<T>) {
+        fn join<T: Lattice + Clone>(a: &mut Option<Antichain<T>>, b: Option<Antichain<T>>) {
+            *a = match (a.take(), b) {
+                (Some(a), Some(b)) => Some(a.join(&b)),
+                (a, b) => a.or(b),
+            };
+        }
+        join(&mut self.persist_upper, other.persist_upper);
+        join(&mut self.persist_since, other.persist_since);
+        self.persist_error = self.persist_error.take().or(other.persist_error);
+        self.reported_upper = match (self.reported_upper.take(), other.reported_upper) {
+            (Some(a), Some(b)) => Some(a.meet(&b)),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+impl RustType<proto_storage_response::ProtoCollectionStateReport>
+    for CollectionStateReport<mz_repr::Timestamp>
+{
+    fn into_proto(&self) -> proto_storage_response::ProtoCollectionStateReport {
+        proto_storage_response::ProtoCollectionStateReport {
+            id: Some(self.id.into_proto()),
+            persist_upper: self.persist_upper.into_proto(),
+            persist_since: self.persist_since.into_proto(),
+            persist_error: self.persist_error.clone(),
+            reported_upper: self.reported_upper.into_proto(),
+        }
+    }
+
+    fn from_proto(
+        proto: proto_storage_response::ProtoCollectionStateReport,
+    ) -> Result<Self, TryFromProtoError> {
+        Ok(CollectionStateReport {
+            id: proto
+                .id
+                .into_rust_if_some("ProtoCollectionStateReport::id")?,
+            persist_upper: proto.persist_upper.into_rust()?,
+            persist_since: proto.persist_since.into_rust()?,
+            persist_error: proto.persist_error,
+            reported_upper: proto.reported_upper.into_rust()?,
+        })
+    }
+}
+
+impl Arbitrary for CollectionStateReport<mz_repr::Timestamp> {
+    type Strategy = BoxedStrategy<Self>;
+    type Parameters = ();
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<GlobalId>(),
+            proptest::option::of(any_antichain()),
+            proptest::option::of(any_antichain()),
+            any::<Option<String>>(),
+            proptest::option::of(any_antichain()),
+        )
+            .prop_map(
+                |(id, persist_upper, persist_since, persist_error, reported_upper)| {
+                    CollectionStateReport {
+                        id,
+                        persist_upper,
+                        persist_since,
+                        persist_error,
+                        reported_upper,
+                    }
+                },
+            )
+            .boxed()
+    }
 }
 
 impl RustType<ProtoStorageResponse> for StorageResponse<mz_repr::Timestamp> {
     fn into_proto(&self) -> ProtoStorageResponse {
         use proto_storage_response::Kind::*;
-        use proto_storage_response::{ProtoDroppedIds, ProtoStatisticsUpdates, ProtoStatusUpdates};
+        use proto_storage_response::{
+            ProtoCollectionStateReports, ProtoDroppedIds, ProtoParameterGeneration,
+            ProtoSinkProgress, ProtoStatisticsUpdates, ProtoStatusUpdates, ProtoUpstreamLag,
+        };
         ProtoStorageResponse {
             kind: Some(match self {
                 StorageResponse::FrontierUppers(traces) => FrontierUppers(traces.into_proto()),
@@ -522,13 +873,39 @@ impl RustType<ProtoStorageResponse> for StorageResponse<mz_repr::Timestamp> {
                 StorageResponse::StatusUpdates(updates) => StatusUpdates(ProtoStatusUpdates {
                     updates: updates.into_proto(),
                 }),
+                StorageResponse::UpstreamLag { id, bytes_behind } => {
+                    UpstreamLag(ProtoUpstreamLag {
+                        id: Some(id.into_proto()),
+                        bytes_behind: *bytes_behind,
+                    })
+                }
+                StorageResponse::ParameterGeneration { id, generation } => {
+                    ParameterGeneration(ProtoParameterGeneration {
+                        id: Some(id.into_proto()),
+                        generation: *generation,
+                    })
+                }
+                StorageResponse::SinkProgress { id, emitted_upper } => {
+                    SinkProgress(ProtoSinkProgress {
+                        id: Some(id.into_proto()),
+                        emitted_upper: Some(emitted_upper.into_proto()),
+                    })
+                }
+                StorageResponse::CollectionStateReport(reports) => {
+                    CollectionStateReport(ProtoCollectionStateReports {
+                        reports: reports.into_proto(),
+                    })
+                }
             }),
         }
     }
 
     fn from_proto(proto: ProtoStorageResponse) -> Result<Self, TryFromProtoError> {
         use proto_storage_response::Kind::*;
-        use proto_storage_response::{ProtoDroppedIds, ProtoStatusUpdates};
+        use proto_storage_response::{
+            ProtoCollectionStateReports, ProtoDroppedIds, ProtoParameterGeneration,
+            ProtoSinkProgress, ProtoStatusUpdates, ProtoUpstreamLag,
+        };
         match proto.kind {
             Some(DroppedIds(ProtoDroppedIds { ids })) => {
                 Ok(StorageResponse::DroppedIds(ids.into_rust()?))
@@ -551,6 +928,28 @@ impl RustType<ProtoStorageResponse> for StorageResponse<mz_repr::Timestamp> {
             Some(StatusUpdates(ProtoStatusUpdates { updates })) => {
                 Ok(StorageResponse::StatusUpdates(updates.into_rust()?))
             }
+            Some(UpstreamLag(ProtoUpstreamLag { id, bytes_behind })) => {
+                Ok(StorageResponse::UpstreamLag {
+                    id: id.into_rust_if_some("ProtoUpstreamLag::id")?,
+                    bytes_behind,
+                })
+            }
+            Some(ParameterGeneration(ProtoParameterGeneration { id, generation })) => {
+                Ok(StorageResponse::ParameterGeneration {
+                    id: id.into_rust_if_some("ProtoParameterGeneration::id")?,
+                    generation,
+                })
+            }
+            Some(SinkProgress(ProtoSinkProgress { id, emitted_upper })) => {
+                Ok(StorageResponse::SinkProgress {
+                    id: id.into_rust_if_some("ProtoSinkProgress::id")?,
+                    emitted_upper: emitted_upper
+                        .into_rust_if_some("ProtoSinkProgress::emitted_upper")?,
+                })
+            }
+            Some(CollectionStateReport(ProtoCollectionStateReports { reports })) => {
+                Ok(StorageResponse::CollectionStateReport(reports.into_rust()?))
+            }
             None => Err(TryFromProtoError::missing_field(
                 "ProtoStorageResponse::kind",
             )),
@@ -564,12 +963,26 @@ impl Arbitrary for StorageResponse<mz_repr::Timestamp> {
 
     fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
         // TODO(guswynn): test `SourceStatisticsUpdates`
-        Union::new(vec![proptest::collection::vec(
-            (any::<GlobalId>(), any_antichain()),
-            1..4,
-        )
-        .prop_map(StorageResponse::FrontierUppers)
-        .boxed()])
+        Union::new(vec![
+            proptest::collection::vec((any::<GlobalId>(), any_antichain()), 1..4)
+                .prop_map(StorageResponse::FrontierUppers)
+                .boxed(),
+            (any::<GlobalId>(), any::<u64>())
+                .prop_map(|(id, bytes_behind)| StorageResponse::UpstreamLag { id, bytes_behind })
+                .boxed(),
+            (any::<GlobalId>(), any::<u64>())
+                .prop_map(|(id, generation)| StorageResponse::ParameterGeneration {
+                    id,
+                    generation,
+                })
+                .boxed(),
+            (any::<GlobalId>(), any_antichain())
+                .prop_map(|(id, emitted_upper)| StorageResponse::SinkProgress { id, emitted_upper })
+                .boxed(),
+            proptest::collection::vec(any::<CollectionStateReport>(), 1..4)
+                .prop_map(StorageResponse::CollectionStateReport)
+                .boxed(),
+        ])
     }
 }
 
@@ -584,6 +997,23 @@ pub struct PartitionedStorageState<T> {
     /// Upper frontiers for sources and sinks, both unioned across all partitions and from each
     /// individual partition.
     uppers: BTreeMap<GlobalId, (MutableAntichain<T>, Vec<Option<Antichain<T>>>)>,
+    /// Upstream lag for sources, as last reported by each individual partition.
+    upstream_lag: BTreeMap<GlobalId, Vec<Option<u64>>>,
+    /// Parameter generations for ingestions, both as last forwarded and as last reported by each
+    /// individual partition.
+    parameter_generations: BTreeMap<GlobalId, (Option<u64>, Vec<Option<u64>>)>,
+    /// Emitted uppers for sinks, both as last forwarded and as last reported by each individual
+    /// partition.
+    sink_progress: BTreeMap<GlobalId, (Antichain<T>, Vec<Option<Antichain<T>>>)>,
+    /// The timestamp of the most recent status update forwarded for each object.
+    status_timestamps: BTreeMap<GlobalId, chrono::DateTime<chrono::Utc>>,
+    /// Collection state reports that each individual partition has sent but that have yet to be
+    /// merged with those of the other partitions, in the order they were sent.
+    collection_state_reports: Vec<VecDeque<Vec<CollectionStateReport<T>>>>,
+    /// Validates the order of the merged responses.
+    sequencer: ResponseSequencer<T>,
+    /// Names with which collections are identified in panics and errors.
+    names: CollectionNames,
 }
 
 impl<T> Partitionable<StorageCommand<T>, StorageResponse<T>>
@@ -597,6 +1027,13 @@ where
         PartitionedStorageState {
             parts,
             uppers: BTreeMap::new(),
+            upstream_lag: BTreeMap::new(),
+            parameter_generations: BTreeMap::new(),
+            sink_progress: BTreeMap::new(),
+            status_timestamps: BTreeMap::new(),
+            collection_state_reports: vec![VecDeque::new(); parts],
+            sequencer: ResponseSequencer::default(),
+            names: CollectionNames::default(),
         }
     }
 }
@@ -605,12 +1042,21 @@ impl<T> PartitionedStorageState<T>
 where
     T: timely::progress::Timestamp,
 {
+    /// Sets the names with which collections are identified in panics and errors.
+    pub fn set_collection_names(&mut self, names: CollectionNames) {
+        self.names = names;
+    }
+
     fn observe_command(&mut self, command: &StorageCommand<T>) {
         // Note that `observe_command` is quite different in `mz_compute_client`.
         // Compute (currently) only sends the command to 1 process,
         // but storage fan's out to all workers, allowing the storage processes
         // to self-coordinate how commands and internal commands are ordered.
         //
+        // Large commands may have been split by the controller (see
+        // `StorageCommand::split_by_size`), so observing a command must only
+        // ever add to the state and never assume a command is complete.
+        //
         // TODO(guswynn): cluster-unification: consolidate this with compute.
         let _ = match command {
             StorageCommand::CreateTimely { .. } => {
@@ -626,7 +1072,8 @@ where
             }
             StorageCommand::InitializationComplete
             | StorageCommand::UpdateConfiguration(_)
-            | StorageCommand::AllowCompaction(_) => {}
+            | StorageCommand::AllowCompaction(_)
+            | StorageCommand::ReportCollectionState(_) => {}
         };
     }
 
@@ -681,6 +1128,32 @@ where
         shard_id: usize,
         response: StorageResponse<T>,
     ) -> Option<Result<StorageResponse<T>, anyhow::Error>> {
+        let response = self.merge_response(shard_id, response)?;
+        match self.sequencer.sequence(&response) {
+            Ok(()) => Some(Ok(response)),
+            Err(violation) => {
+                // Forwarding the response would corrupt the controller's view
+                // of the collection. Report an error instead, which causes the
+                // connection to the replica to be reestablished.
+                let violation = violation.with_name(&self.names);
+                soft_panic_or_log!("{violation}");
+                Some(Err(violation.into()))
+            }
+        }
+    }
+}
+
+impl<T> PartitionedStorageState<T>
+where
+    T: timely::progress::Timestamp + Lattice,
+{
+    /// Merges a response from a single shard into a response that presents as
+    /// if it came from a single worker, if there is anything to report.
+    fn merge_response(
+        &mut self,
+        shard_id: usize,
+        response: StorageResponse<T>,
+    ) -> Option<StorageResponse<T>> {
         match response {
             // Avoid multiple retractions of minimum time, to present as updates from one worker.
             StorageResponse::FrontierUppers(list) => {
@@ -689,12 +1162,17 @@ where
                 for (id, new_shard_upper) in list {
                     let (frontier, shard_frontiers) = match self.uppers.get_mut(&id) {
                         Some(value) => value,
-                        None => panic!("Reference to absent collection: {id}"),
+                        None => {
+                            panic!("Reference to absent collection: {}", self.names.display(id))
+                        }
                     };
                     let old_upper = frontier.frontier().to_owned();
                     let shard_upper = match &mut shard_frontiers[shard_id] {
                         Some(shard_upper) => shard_upper,
-                        None => panic!("Reference to absent shard {shard_id} for collection {id}"),
+                        None => panic!(
+                            "Reference to absent shard {shard_id} for collection {}",
+                            self.names.display(id)
+                        ),
                     };
                     frontier.update_iter(shard_upper.iter().map(|t| (t.clone(), -1)));
                     frontier.update_iter(new_shard_upper.iter().map(|t| (t.clone(), 1)));
@@ -709,7 +1187,7 @@ where
                 if new_uppers.is_empty() {
                     None
                 } else {
-                    Some(Ok(StorageResponse::FrontierUppers(new_uppers)))
+                    Some(StorageResponse::FrontierUppers(new_uppers))
                 }
             }
             StorageResponse::DroppedIds(dropped_ids) => {
@@ -718,16 +1196,23 @@ where
                 for id in dropped_ids {
                     let (_, shard_frontiers) = match self.uppers.get_mut(&id) {
                         Some(value) => value,
-                        None => panic!("Reference to absent collection: {id}"),
+                        None => {
+                            panic!("Reference to absent collection: {}", self.names.display(id))
+                        }
                     };
                     let prev = shard_frontiers[shard_id].take();
                     assert!(
                         prev.is_some(),
-                        "got double drop for {id} from shard {shard_id}"
+                        "got double drop for {} from shard {shard_id}",
+                        self.names.display(id)
                     );
 
                     if shard_frontiers.iter().all(Option::is_none) {
                         self.uppers.remove(&id);
+                        self.upstream_lag.remove(&id);
+                        self.parameter_generations.remove(&id);
+                        self.sink_progress.remove(&id);
+                        self.status_timestamps.remove(&id);
                         new_drops.insert(id);
                     }
                 }
@@ -735,22 +1220,292 @@ where
                 if new_drops.is_empty() {
                     None
                 } else {
-                    Some(Ok(StorageResponse::DroppedIds(new_drops)))
+                    Some(StorageResponse::DroppedIds(new_drops))
                 }
             }
             StorageResponse::StatisticsUpdates(source_stats, sink_stats) => {
                 // Just forward it along; the `worker_id` should have been set in `storage_state`.
                 // We _could_ consolidate across worker_id's, here, but each worker only produces
                 // responses periodically, so we avoid that complexity.
-                Some(Ok(StorageResponse::StatisticsUpdates(
-                    source_stats,
-                    sink_stats,
-                )))
+                Some(StorageResponse::StatisticsUpdates(source_stats, sink_stats))
+            }
+            StorageResponse::StatusUpdates(mut updates) => {
+                // Each partition reports statuses independently, so after a restart a partition
+                // can report a status that is older than one already forwarded. Suppress those,
+                // so that the status history never moves backwards, unless they are terminal.
+                //
+                // A partition can also report a status after all partitions reported the object
+                // dropped, at which point the object is no longer tracked. Suppress those too.
+                updates.retain(|update| {
+                    if !self.uppers.contains_key(&update.id) {
+                        return false;
+                    }
+                    let last = self
+                        .status_timestamps
+                        .entry(update.id)
+                        .or_insert(update.timestamp);
+                    let terminal = matches!(update.status, Status::Dropped | Status::Ceased);
+                    if update.timestamp < *last && !terminal {
+                        return false;
+                    }
+                    *last = std::cmp::max(*last, update.timestamp);
+                    true
+                });
+
+                if updates.is_empty() {
+                    None
+                } else {
+                    Some(StorageResponse::StatusUpdates(updates))
+                }
+            }
+            StorageResponse::UpstreamLag { id, bytes_behind } => {
+                // Suppress lag reported late for a source that was dropped.
+                if !self.uppers.contains_key(&id) {
+                    return None;
+                }
+                // Present the largest lag reported by any partition, so that a partition that is
+                // far behind is not hidden by one that is caught up.
+                let shard_lags = self
+                    .upstream_lag
+                    .entry(id)
+                    .or_insert_with(|| vec![None; self.parts]);
+                shard_lags[shard_id] = Some(bytes_behind);
+                let bytes_behind = shard_lags
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .max()
+                    .expect("at least one lag reported");
+
+                Some(StorageResponse::UpstreamLag { id, bytes_behind })
+            }
+            StorageResponse::ParameterGeneration { id, generation } => {
+                // An ingestion has only been rendered with a generation once every partition has
+                // rendered it with at least that generation, so present the oldest generation,
+                // and only once all partitions have reported. Repeated reports of the same
+                // generation are suppressed.
+                let (forwarded, shard_generations) = self
+                    .parameter_generations
+                    .entry(id)
+                    .or_insert_with(|| (None, vec![None; self.parts]));
+                shard_generations[shard_id] = Some(generation);
+                let generation = shard_generations
+                    .iter()
+                    .copied()
+                    .collect::<Option<Vec<_>>>()?
+                    .into_iter()
+                    .min()
+                    .expect("at least one partition");
+                if *forwarded == Some(generation) {
+                    return None;
+                }
+                *forwarded = Some(generation);
+
+                Some(StorageResponse::ParameterGeneration { id, generation })
+            }
+            StorageResponse::SinkProgress { id, emitted_upper } => {
+                // A sink has only emitted its input up to a time once every partition has, so
+                // present the meet of the emitted uppers, and only once all partitions have
+                // reported. Partitions that don't write to the external system report the empty
+                // frontier and so don't hold back the meet. Only advancements are forwarded.
+                let (forwarded, shard_uppers) = self.sink_progress.entry(id).or_insert_with(|| {
+                    (Antichain::from_elem(T::minimum()), vec![None; self.parts])
+                });
+                shard_uppers[shard_id] = Some(emitted_upper);
+                let mut emitted_upper = Antichain::new();
+                for shard_upper in shard_uppers.iter() {
+                    emitted_upper.meet_assign(shard_upper.as_ref()?);
+                }
+                if !PartialOrder::less_than(forwarded, &emitted_upper) {
+                    return None;
+                }
+                forwarded.clone_from(&emitted_upper);
+
+                Some(StorageResponse::SinkProgress { id, emitted_upper })
+            }
+            StorageResponse::CollectionStateReport(reports) => {
+                // Every partition answers every request, in the order of the requests, so the
+                // n-th reports of all partitions answer the same request. Merge them once all
+                // partitions have answered.
+                self.collection_state_reports[shard_id].push_back(reports);
+                if self.collection_state_reports.iter().any(VecDeque::is_empty) {
+                    return None;
+                }
+                let mut merged: BTreeMap<GlobalId, CollectionStateReport<T>> = BTreeMap::new();
+                for shard_reports in &mut self.collection_state_reports {
+                    let reports = shard_reports.pop_front().expect("checked above");
+                    for report in reports {
+                        match merged.entry(report.id) {
+                            Entry::Vacant(entry) => {
+                                entry.insert(report);
+                            }
+                            Entry::Occupied(mut entry) => entry.get_mut().merge(report),
+                        }
+                    }
+                }
+
+                Some(StorageResponse::CollectionStateReport(
+                    merged.into_values().collect(),
+                ))
+            }
+        }
+    }
+}
+
+/// A merged [`StorageResponse`] that violates the ordering guarantees for one
+/// of the collections it reports on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseOrderViolation {
+    /// The collection whose responses are out of order.
+    pub id: GlobalId,
+    /// The sequence number assigned to the offending response.
+    pub sequence: u64,
+    /// A description of the violated invariant.
+    pub reason: String,
+    /// The name of the collection, if known.
+    pub name: Option<String>,
+}
+
+impl ResponseOrderViolation {
+    /// Attaches the name of the collection, as resolved by `names`.
+    pub fn with_name(mut self, names: &CollectionNames) -> Self {
+        self.name = names.resolve(self.id);
+        self
+    }
+}
+
+impl fmt::Display for ResponseOrderViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "storage response #{} for ", self.sequence)?;
+        match &self.name {
+            Some(name) => write!(f, "{name} ({})", self.id)?,
+            None => write!(f, "{}", self.id)?,
+        }
+        write!(f, " is out of order: {}", self.reason)
+    }
+}
+
+impl std::error::Error for ResponseOrderViolation {}
+
+/// Enforces the order in which [`StorageResponse`]s report on each collection.
+///
+/// Every response is stamped with the next sequence number of each collection
+/// it mentions, and is rejected if it breaks one of the following invariants:
+///
+///   * `FrontierUppers` never move the upper of a collection backwards.
+///   * No `StatusUpdates` follow a [`Status::Dropped`] status.
+///
+/// A collection is forgotten once it is reported in `DroppedIds`, so that the
+/// sequencer doesn't grow with every collection ever dropped. Responses that
+/// arrive late for a dropped collection are filtered out before they are
+/// sequenced, see [`PartitionedStorageState`].
+///
+/// `StatisticsUpdates` are reported independently by each worker and
+/// `CollectionStateReport`s are diagnostic answers to the controller, so
+/// neither is sequenced.
+#[derive(Debug)]
+pub struct ResponseSequencer<T> {
+    collections: BTreeMap<GlobalId, CollectionSequence<T>>,
+}
+
+/// The state [`ResponseSequencer`] tracks for a single collection.
+#[derive(Debug)]
+struct CollectionSequence<T> {
+    /// The sequence number of the most recent response.
+    sequence: u64,
+    /// The most recently reported upper.
+    upper: Antichain<T>,
+    /// Whether a [`Status::Dropped`] status has been reported.
+    status_dropped: bool,
+}
+
+impl<T> Default for ResponseSequencer<T> {
+    fn default() -> Self {
+        Self {
+            collections: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> ResponseSequencer<T>
+where
+    T: timely::progress::Timestamp,
+{
+    /// Returns the sequence number of the last response that reported on `id`.
+    pub fn last_sequence(&self, id: GlobalId) -> Option<u64> {
+        self.collections.get(&id).map(|c| c.sequence)
+    }
+
+    /// Stamps `response` with the next sequence number of every collection it
+    /// reports on, or returns the first violated invariant.
+    pub fn sequence(
+        &mut self,
+        response: &StorageResponse<T>,
+    ) -> Result<(), ResponseOrderViolation> {
+        match response {
+            StorageResponse::FrontierUppers(uppers) => {
+                for (id, upper) in uppers {
+                    let collection = self.stamp(*id)?;
+                    if !PartialOrder::less_equal(&collection.upper, upper) {
+                        return Err(ResponseOrderViolation {
+                            id: *id,
+                            sequence: collection.sequence,
+                            reason: format!(
+                                "upper regressed from {:?} to {:?}",
+                                collection.upper, upper
+                            ),
+                            name: None,
+                        });
+                    }
+                    collection.upper.clone_from(upper);
+                }
+            }
+            StorageResponse::DroppedIds(ids) => {
+                for id in ids {
+                    self.collections.remove(id);
+                }
             }
             StorageResponse::StatusUpdates(updates) => {
-                Some(Ok(StorageResponse::StatusUpdates(updates)))
+                for update in updates {
+                    let collection = self.stamp(update.id)?;
+                    if collection.status_dropped && update.status != Status::Dropped {
+                        return Err(ResponseOrderViolation {
+                            id: update.id,
+                            sequence: collection.sequence,
+                            reason: format!("status {:?} after dropped status", update.status),
+                            name: None,
+                        });
+                    }
+                    collection.status_dropped |= update.status == Status::Dropped;
+                }
+            }
+            StorageResponse::UpstreamLag { id, .. }
+            | StorageResponse::ParameterGeneration { id, .. }
+            | StorageResponse::SinkProgress { id, .. } => {
+                self.stamp(*id)?;
+            }
+            StorageResponse::StatisticsUpdates(..) | StorageResponse::CollectionStateReport(..) => {
             }
         }
+        Ok(())
+    }
+
+    /// Assigns the next sequence number of `id` to the response being
+    /// sequenced.
+    fn stamp(
+        &mut self,
+        id: GlobalId,
+    ) -> Result<&mut CollectionSequence<T>, ResponseOrderViolation> {
+        let collection = self
+            .collections
+            .entry(id)
+            .or_insert_with(|| CollectionSequence {
+                sequence: 0,
+                upper: Antichain::from_elem(T::minimum()),
+                status_dropped: false,
+            });
+        collection.sequence += 1;
+        Ok(collection)
     }
 }
 
@@ -827,9 +1582,16 @@ impl TryIntoTimelyConfig for StorageCommand {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use mz_ore::assert_contains;
     use mz_proto::protobuf_roundtrip;
+    use mz_service::chunk::{ChunkReassembler, ChunkSplitter};
+    use mz_storage_types::sources::SourceExport;
     use proptest::prelude::ProptestConfig;
     use proptest::proptest;
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
 
     use super::*;
 
@@ -851,5 +1613,556 @@ mod tests {
             assert!(actual.is_ok());
             assert_eq!(actual.unwrap(), expect);
         }
+
+        #[mz_ore::test]
+        #[cfg_attr(miri, ignore)] // too slow
+        fn split_run_ingestions_observed_cumulatively(
+            ingestions in proptest::collection::vec(any::<RunIngestionCommand>(), 2..8),
+        ) {
+            let expected: BTreeSet<_> = ingestions
+                .iter()
+                .flat_map(|i| i.description.subsource_ids())
+                .collect();
+            let command = StorageCommand::<mz_repr::Timestamp>::RunIngestions(ingestions.clone());
+
+            // A budget of one byte forces every ingestion into its own command.
+            let chunks = command.split_by_size(1);
+            assert_eq!(chunks.len(), ingestions.len());
+
+            let mut delivered = Vec::new();
+            let mut state = <(StorageCommand, StorageResponse) as Partitionable<_, _>>::new(2);
+            for chunk in chunks {
+                let StorageCommand::RunIngestions(chunk) = &chunk else {
+                    panic!("unexpected command: {chunk:?}");
+                };
+                delivered.extend(chunk.iter().cloned());
+                state.split_command(StorageCommand::RunIngestions(chunk.clone()));
+            }
+            assert_eq!(delivered, ingestions);
+            assert_eq!(state.uppers.keys().copied().collect::<BTreeSet<_>>(), expected);
+        }
+
+        #[mz_ore::test]
+        #[cfg_attr(miri, ignore)] // too slow
+        fn merged_responses_respect_ordering(
+            // For each shard, the increments by which it advances the upper of each collection.
+            shard_steps in proptest::collection::vec(
+                proptest::collection::vec(proptest::collection::vec(1..10u64, 0..5), 3),
+                1..4,
+            ),
+            choices in proptest::collection::vec(any::<usize>(), 64),
+        ) {
+            let ids: Vec<_> = (0..3).map(GlobalId::User).collect();
+            let running = |id| StatusUpdate::new(id, mz_ore::now::to_datetime(0), Status::Running);
+
+            // Each shard advances the uppers of its collections, reports their
+            // status, and finally drops them.
+            let mut scripts: Vec<VecDeque<StorageResponse>> = shard_steps
+                .iter()
+                .map(|steps| {
+                    let mut script = VecDeque::new();
+                    for (id, increments) in ids.iter().zip(steps) {
+                        let mut upper = 0;
+                        for increment in increments {
+                            upper += increment;
+                            script.push_back(StorageResponse::FrontierUppers(vec![(
+                                *id,
+                                Antichain::from_elem(mz_repr::Timestamp::from(upper)),
+                            )]));
+                        }
+                        script.push_back(StorageResponse::StatusUpdates(vec![running(*id)]));
+                    }
+                    script.push_back(StorageResponse::DroppedIds(ids.iter().copied().collect()));
+                    script
+                })
+                .collect();
+
+            let mut state =
+                <(StorageCommand, StorageResponse) as Partitionable<_, _>>::new(scripts.len());
+            state.insert_new_uppers(ids.iter().copied());
+
+            // Interleave the shards' responses arbitrarily, preserving the
+            // order within each shard, and validate the merged stream
+            // independently of the partitioned state.
+            let mut checker = ResponseSequencer::default();
+            let mut dropped = BTreeSet::new();
+            let mut choices = choices.into_iter().chain(iter::repeat(0));
+            loop {
+                let live: Vec<_> = (0..scripts.len()).filter(|i| !scripts[*i].is_empty()).collect();
+                if live.is_empty() {
+                    break;
+                }
+                let shard_id = live[choices.next().expect("infinite") % live.len()];
+                let response = scripts[shard_id].pop_front().expect("shard is live");
+                if let Some(merged) = state.absorb_response(shard_id, response) {
+                    let merged = merged.expect("merged responses are well ordered");
+                    checker.sequence(&merged).expect("merged responses are well ordered");
+                    if let StorageResponse::DroppedIds(ids) = merged {
+                        dropped.extend(ids);
+                    }
+                }
+            }
+            assert_eq!(dropped, ids.into_iter().collect());
+        }
+    }
+
+    #[mz_ore::test]
+    fn status_severity() {
+        let statuses = [
+            Status::Running,
+            Status::Starting,
+            Status::Paused,
+            Status::Stalled,
+            Status::Ceased,
+            Status::Dropped,
+        ];
+        for pair in statuses.windows(2) {
+            assert!(
+                pair[0].severity() < pair[1].severity(),
+                "{:?} should be less severe than {:?}",
+                pair[0],
+                pair[1],
+            );
+        }
+
+        // A family with a ceased member is ceased, no matter how many of its
+        // other members are paused or stalled.
+        let family = [
+            Status::Running,
+            Status::Paused,
+            Status::Ceased,
+            Status::Stalled,
+            Status::Paused,
+        ];
+        let worst = family.iter().max_by_key(|s| s.severity()).unwrap();
+        assert_eq!(*worst, Status::Ceased);
+
+        // Paused is worse than running, but not an error.
+        let family = [Status::Running, Status::Paused, Status::Starting];
+        let worst = family.iter().max_by_key(|s| s.severity()).unwrap();
+        assert_eq!(*worst, Status::Paused);
+    }
+
+    #[mz_ore::test]
+    fn response_sequencer_rejects_regressions() {
+        let id = GlobalId::User(1);
+        let upper = |t: u64| {
+            StorageResponse::FrontierUppers(vec![(
+                id,
+                Antichain::from_elem(mz_repr::Timestamp::from(t)),
+            )])
+        };
+        let status = |status| {
+            StorageResponse::StatusUpdates(vec![StatusUpdate::new(
+                id,
+                mz_ore::now::to_datetime(0),
+                status,
+            )])
+        };
+
+        // Frontiers must not move backwards.
+        let mut sequencer = ResponseSequencer::<mz_repr::Timestamp>::default();
+        sequencer.sequence(&upper(5)).expect("valid");
+        sequencer.sequence(&upper(5)).expect("valid");
+        let violation = sequencer.sequence(&upper(3)).expect_err("regression");
+        assert_eq!(violation.id, id);
+        assert_eq!(violation.sequence, 3);
+        assert_eq!(sequencer.last_sequence(id), Some(3));
+
+        // Nothing but another dropped status may follow a dropped status.
+        let mut sequencer = ResponseSequencer::<mz_repr::Timestamp>::default();
+        sequencer.sequence(&status(Status::Running)).expect("valid");
+        sequencer.sequence(&status(Status::Dropped)).expect("valid");
+        sequencer.sequence(&status(Status::Dropped)).expect("valid");
+        sequencer.sequence(&upper(1)).expect("valid");
+        sequencer
+            .sequence(&status(Status::Running))
+            .expect_err("status after dropped");
+
+        // Collections are forgotten once dropped.
+        let mut sequencer = ResponseSequencer::<mz_repr::Timestamp>::default();
+        sequencer.sequence(&upper(1)).expect("valid");
+        assert_eq!(sequencer.last_sequence(id), Some(1));
+        sequencer
+            .sequence(&StorageResponse::DroppedIds(BTreeSet::from([id])))
+            .expect("valid");
+        assert_eq!(sequencer.last_sequence(id), None);
+    }
+
+    #[mz_ore::test]
+    fn late_responses_after_drop_are_filtered() {
+        let id = GlobalId::User(1);
+        let mut state = <(StorageCommand, StorageResponse) as Partitionable<_, _>>::new(2);
+        state.insert_new_uppers([id]);
+        let running = || {
+            StorageResponse::StatusUpdates(vec![StatusUpdate::new(
+                id,
+                mz_ore::now::to_datetime(0),
+                Status::Running,
+            )])
+        };
+        let lag = || StorageResponse::UpstreamLag {
+            id,
+            bytes_behind: 10,
+        };
+        let absorb = |state: &mut PartitionedStorageState<_>, shard_id, response| {
+            state
+                .absorb_response(shard_id, response)
+                .map(|response| response.expect("response valid"))
+        };
+
+        assert_eq!(absorb(&mut state, 0, running()), Some(running()));
+        let dropped = || StorageResponse::DroppedIds(BTreeSet::from([id]));
+        assert_eq!(absorb(&mut state, 0, dropped()), None);
+        // Partition 1 still reports on the collection until it drops it too.
+        assert_eq!(absorb(&mut state, 1, lag()), Some(lag()));
+        assert_eq!(absorb(&mut state, 1, dropped()), Some(dropped()));
+        assert_eq!(state.sequencer.last_sequence(id), None);
+
+        // Responses arriving late for the dropped collection are suppressed, rather than
+        // breaking the order of responses.
+        assert_eq!(absorb(&mut state, 0, running()), None);
+        assert_eq!(absorb(&mut state, 1, lag()), None);
+        assert_eq!(state.sequencer.last_sequence(id), None);
+        assert!(state.status_timestamps.is_empty());
+        assert!(state.upstream_lag.is_empty());
+    }
+
+    #[mz_ore::test]
+    fn response_order_violation_names_collection() {
+        let id = GlobalId::User(1);
+        let upper = |t: u64| {
+            StorageResponse::FrontierUppers(vec![(
+                id,
+                Antichain::from_elem(mz_repr::Timestamp::from(t)),
+            )])
+        };
+        let mut sequencer = ResponseSequencer::<mz_repr::Timestamp>::default();
+        sequencer.sequence(&upper(5)).expect("valid");
+        let violation = sequencer.sequence(&upper(3)).expect_err("regression");
+
+        // Without a resolver, the collection is identified by its id only.
+        let names = CollectionNames::default();
+        let message = violation.clone().with_name(&names).to_string();
+        assert!(message.starts_with("storage response #2 for u1 is out of order"));
+
+        // A resolver registered through any handle is observed by all of them.
+        names.clone().register(Arc::new(|id| {
+            (id == GlobalId::User(1)).then(|| "materialize.public.t".into())
+        }));
+        let message = violation.with_name(&names).to_string();
+        assert_contains!(message, "materialize.public.t (u1)");
+        assert_eq!(
+            names.display(GlobalId::User(2)).to_string(),
+            "u2",
+            "unknown ids are formatted as-is"
+        );
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // too slow
+    fn split_allow_compaction_by_size() {
+        let frontiers: Vec<_> = (0..10_000)
+            .map(|id| {
+                (
+                    GlobalId::User(id),
+                    Antichain::from_elem(mz_repr::Timestamp::from(id)),
+                )
+            })
+            .collect();
+        let command = StorageCommand::AllowCompaction(frontiers.clone());
+        let max_bytes = 4096;
+        assert!(command.into_proto().encoded_len() > max_bytes);
+
+        let chunks = command.split_by_size(max_bytes);
+        assert!(chunks.len() > 1);
+
+        let mut delivered = Vec::new();
+        for chunk in chunks {
+            assert!(chunk.into_proto().encoded_len() <= max_bytes);
+            let StorageCommand::AllowCompaction(chunk) = chunk else {
+                panic!("unexpected command: {chunk:?}");
+            };
+            delivered.extend(chunk);
+        }
+        // Every id is delivered exactly once, in its original order.
+        assert_eq!(delivered, frontiers);
+
+        // Commands that already fit are passed through untouched.
+        let small = StorageCommand::AllowCompaction(frontiers[..1].to_vec());
+        assert_eq!(small.clone().split_by_size(max_bytes), vec![small]);
+    }
+
+    /// Returns a synthetic ingestion with `exports` source exports, like that of a source over
+    /// a publication with as many tables.
+    fn wide_ingestion(exports: u64) -> RunIngestionCommand {
+        let mut command = any::<RunIngestionCommand>()
+            .new_tree(&mut TestRunner::deterministic())
+            .expect("valid strategy")
+            .current();
+        let metadata = command.description.ingestion_metadata.clone();
+        command.description.source_exports = (0..exports)
+            .map(|id| {
+                let export = SourceExport {
+                    output_index: 0,
+                    storage_metadata: metadata.clone(),
+                };
+                (GlobalId::User(id), export)
+            })
+            .collect();
+        command
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // too slow
+    fn oversized_ingestion_fails_size_check() {
+        let command = wide_ingestion(1000);
+        let size = StorageCommand::<mz_repr::Timestamp>::RunIngestions(vec![command.clone()])
+            .into_proto()
+            .encoded_len();
+
+        // An ingestion right at the limit fits.
+        let mut params = StorageParameters::default();
+        params.storage_command_max_bytes = size;
+        command.check_size(&params).expect("fits");
+
+        // One past the limit is reported with its size, the limit, and how to resolve it.
+        params.storage_command_max_bytes = size - 1;
+        let error = command
+            .check_size(&params)
+            .expect_err("too large")
+            .to_string();
+        assert_contains!(
+            error,
+            format!(
+                "is {size} bytes, which exceeds the limit of {} bytes",
+                size - 1
+            )
+        );
+        assert_contains!(error, "splitting its publication across several sources");
+
+        // Chunked commands are only limited by what replicas reassemble.
+        params.storage_command_chunking = true;
+        command.check_size(&params).expect("sent in chunks");
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // too slow
+    fn oversized_ingestion_is_chunked() {
+        let command =
+            StorageCommand::<mz_repr::Timestamp>::RunIngestions(vec![wide_ingestion(1000)]);
+        let max_bytes = command.into_proto().encoded_len() / 10;
+
+        // A single ingestion can't be split by size.
+        assert_eq!(
+            command.clone().split_by_size(max_bytes),
+            vec![command.clone()]
+        );
+
+        let mut splitter = ChunkSplitter::default();
+        let mut reassembler = ChunkReassembler::new(MAX_CHUNKED_COMMAND_BYTES);
+        let chunks = splitter.split(command.into_proto(), max_bytes);
+        assert!(chunks.len() > 10, "{} chunks", chunks.len());
+
+        let mut reassembled = Vec::new();
+        for chunk in chunks {
+            // Each chunk exceeds its share of the command only by its envelope.
+            assert!(chunk.encoded_len() <= max_bytes + 64);
+            if let Some(command) = reassembler.absorb(chunk).expect("valid chunk") {
+                let command: StorageCommand<mz_repr::Timestamp> = command.into_rust().unwrap();
+                reassembled.push(command);
+            }
+        }
+        assert_eq!(reassembled, vec![command]);
+    }
+
+    #[mz_ore::test]
+    fn upstream_lag_is_max_across_partitions() {
+        let mut state = <(StorageCommand, StorageResponse) as Partitionable<_, _>>::new(2);
+        let id = GlobalId::User(1);
+
+        let lag = |bytes_behind| StorageResponse::UpstreamLag { id, bytes_behind };
+        let absorb = |state: &mut PartitionedStorageState<_>, shard_id, response| {
+            state
+                .absorb_response(shard_id, response)
+                .expect("response forwarded")
+                .expect("response valid")
+        };
+
+        assert_eq!(absorb(&mut state, 0, lag(100)), lag(100));
+        assert_eq!(absorb(&mut state, 1, lag(300)), lag(300));
+        // A caught-up partition must not mask the one that is still behind.
+        assert_eq!(absorb(&mut state, 0, lag(0)), lag(300));
+        assert_eq!(absorb(&mut state, 1, lag(50)), lag(50));
+    }
+
+    #[mz_ore::test]
+    fn parameter_generation_is_min_across_partitions() {
+        let mut state = <(StorageCommand, StorageResponse) as Partitionable<_, _>>::new(2);
+        let id = GlobalId::User(1);
+
+        let report = |generation| StorageResponse::ParameterGeneration { id, generation };
+        let absorb = |state: &mut PartitionedStorageState<_>, shard_id, response| {
+            state
+                .absorb_response(shard_id, response)
+                .map(|response| response.expect("response valid"))
+        };
+
+        // Nothing is reported until every partition has rendered the ingestion.
+        assert_eq!(absorb(&mut state, 0, report(2)), None);
+        assert_eq!(absorb(&mut state, 1, report(1)), Some(report(1)));
+        // An unchanged generation is not reported again.
+        assert_eq!(absorb(&mut state, 0, report(2)), None);
+        assert_eq!(absorb(&mut state, 1, report(2)), Some(report(2)));
+    }
+
+    #[mz_ore::test]
+    fn sink_progress_is_meet_across_partitions() {
+        let mut state = <(StorageCommand, StorageResponse) as Partitionable<_, _>>::new(2);
+        let id = GlobalId::User(1);
+
+        let progress = |emitted_upper: Option<u64>| StorageResponse::SinkProgress {
+            id,
+            emitted_upper: Antichain::from_iter(emitted_upper.map(mz_repr::Timestamp::from)),
+        };
+        let absorb = |state: &mut PartitionedStorageState<_>, shard_id, response| {
+            state
+                .absorb_response(shard_id, response)
+                .map(|response| response.expect("response valid"))
+        };
+
+        // Nothing is reported until every partition has reported.
+        assert_eq!(absorb(&mut state, 0, progress(Some(5))), None);
+        assert_eq!(
+            absorb(&mut state, 1, progress(Some(3))),
+            Some(progress(Some(3)))
+        );
+        // The partition that is behind holds back the emitted upper.
+        assert_eq!(absorb(&mut state, 0, progress(Some(10))), None);
+        assert_eq!(
+            absorb(&mut state, 1, progress(Some(7))),
+            Some(progress(Some(7)))
+        );
+        // A partition that doesn't write to the external system doesn't hold it back.
+        assert_eq!(
+            absorb(&mut state, 1, progress(None)),
+            Some(progress(Some(10)))
+        );
+        assert_eq!(absorb(&mut state, 0, progress(None)), Some(progress(None)));
+    }
+
+    #[mz_ore::test]
+    fn sink_progress_protobuf_roundtrip() {
+        for emitted_upper in [
+            Antichain::from_elem(mz_repr::Timestamp::from(5u64)),
+            Antichain::new(),
+        ] {
+            let expect = StorageResponse::SinkProgress {
+                id: GlobalId::User(1),
+                emitted_upper,
+            };
+            let actual = protobuf_roundtrip::<_, ProtoStorageResponse>(&expect);
+            assert_eq!(actual.expect("valid proto"), expect);
+        }
+    }
+
+    #[mz_ore::test]
+    fn collection_state_reports_merge_across_partitions() {
+        let mut state = <(StorageCommand, StorageResponse) as Partitionable<_, _>>::new(2);
+        let (source, remap) = (GlobalId::User(1), GlobalId::User(2));
+        let frontier = |t: u64| Some(Antichain::from_elem(mz_repr::Timestamp::from(t)));
+        let report =
+            |id, persist: Option<(u64, u64)>, reported_upper: Option<u64>| CollectionStateReport {
+                id,
+                persist_upper: persist.and_then(|(upper, _)| frontier(upper)),
+                persist_since: persist.and_then(|(_, since)| frontier(since)),
+                persist_error: None,
+                reported_upper: reported_upper.and_then(frontier),
+            };
+        let absorb = |state: &mut PartitionedStorageState<_>, shard_id, reports| {
+            state
+                .absorb_response(shard_id, StorageResponse::CollectionStateReport(reports))
+                .map(|response| response.expect("response valid"))
+        };
+
+        // Two requests are answered by both partitions, but only partition 0 reads persist.
+        // Nothing is forwarded until all partitions have answered a request.
+        let first_0 = vec![
+            report(source, Some((10, 5)), Some(8)),
+            report(remap, None, None),
+        ];
+        let second_0 = vec![report(source, Some((12, 5)), Some(12))];
+        assert_eq!(absorb(&mut state, 0, first_0), None);
+        assert_eq!(absorb(&mut state, 0, second_0), None);
+
+        // The reports of the same request are merged, the reported upper being the meet of the
+        // partitions' uppers.
+        let first_1 = vec![report(source, None, Some(9)), report(remap, None, Some(3))];
+        assert_eq!(
+            absorb(&mut state, 1, first_1),
+            Some(StorageResponse::CollectionStateReport(vec![
+                report(source, Some((10, 5)), Some(8)),
+                report(remap, None, Some(3)),
+            ]))
+        );
+        let second_1 = vec![report(source, None, Some(11))];
+        assert_eq!(
+            absorb(&mut state, 1, second_1),
+            Some(StorageResponse::CollectionStateReport(vec![report(
+                source,
+                Some((12, 5)),
+                Some(11)
+            )]))
+        );
+
+        // An error reading the shard is forwarded along with the other partitions' uppers.
+        let failed = CollectionStateReport {
+            persist_error: Some("shard unavailable".into()),
+            ..report(source, None, Some(12))
+        };
+        assert_eq!(absorb(&mut state, 0, vec![failed.clone()]), None);
+        assert_eq!(
+            absorb(&mut state, 1, vec![report(source, None, Some(13))]),
+            Some(StorageResponse::CollectionStateReport(vec![failed]))
+        );
+    }
+
+    #[mz_ore::test]
+    fn out_of_order_statuses_are_suppressed() {
+        let mut state = <(StorageCommand, StorageResponse) as Partitionable<_, _>>::new(2);
+        let id = GlobalId::User(1);
+
+        let status = |millis, status| {
+            StorageResponse::StatusUpdates(vec![StatusUpdate::new(
+                id,
+                mz_ore::now::to_datetime(millis),
+                status,
+            )])
+        };
+        let absorb = |state: &mut PartitionedStorageState<_>, shard_id, response| {
+            state
+                .absorb_response(shard_id, response)
+                .map(|response| response.expect("response valid"))
+        };
+
+        assert_eq!(
+            absorb(&mut state, 0, status(10, Status::Running)),
+            Some(status(10, Status::Running))
+        );
+        assert_eq!(
+            absorb(&mut state, 1, status(20, Status::Stalled)),
+            Some(status(20, Status::Stalled))
+        );
+        // A restarted partition reporting an older status must not roll the status back.
+        assert_eq!(absorb(&mut state, 0, status(15, Status::Starting)), None);
+        assert_eq!(
+            absorb(&mut state, 0, status(20, Status::Running)),
+            Some(status(20, Status::Running))
+        );
+        // Terminal statuses are always forwarded.
+        assert_eq!(
+            absorb(&mut state, 1, status(5, Status::Ceased)),
+            Some(status(5, Status::Ceased))
+        );
     }
 }
@@ -18,12 +18,14 @@
 //! Eventually, the source is dropped with either `drop_sources()` or by allowing compaction to the
 //! empty frontier.
 
-use std::collections::BTreeMap;
-use std::fmt::Debug;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Debug};
+use std::sync::{Arc, RwLock};
 
 use async_trait::async_trait;
 use differential_dataflow::lattice::Lattice;
 use mz_cluster_client::client::ClusterReplicaLocation;
+use mz_cluster_client::command_log::CommandRecorder;
 use mz_cluster_client::ReplicaId;
 use mz_persist_client::read::{Cursor, ReadHandle};
 use mz_persist_client::stats::SnapshotStats;
@@ -39,10 +41,13 @@ use mz_storage_types::sources::{IngestionDescription, SourceData};
 use serde::{Deserialize, Serialize};
 use timely::progress::frontier::MutableAntichain;
 use timely::progress::{Antichain, ChangeBatch, Timestamp};
+use timely::PartialOrder;
 use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::UnboundedReceiverStream;
 
-use crate::client::TimestamplessUpdate;
+use crate::client::{StatusAttribution, StatusUpdate, TimestamplessUpdate};
+use crate::statistics::{SinkStatisticsUpdate, SourceStatisticsUpdate};
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub enum IntrospectionType {
@@ -54,6 +59,18 @@ pub enum IntrospectionType {
 
     Frontiers,
     ReplicaFrontiers,
+    FrontiersHistory,
+    // Written by the controller from the write frontiers of collections.
+    WallclockLag,
+    FrontierAdvancementRates,
+    // Written by the Adapter from the frontier divergences reported by the controller.
+    FrontierDivergenceHistory,
+    // Written by the controller from the disk usage reported for replica processes.
+    ReplicaStatusHistory,
+    // Written by the storage controller from the upstream lag reported by ingestions.
+    SourceUpstreamLag,
+    // Written by the storage controller from the parameter generations reported by ingestions.
+    SourceParameterGenerations,
 
     // Note that this single-shard introspection source will be changed to per-replica,
     // once we allow multiplexing multiple sources/sinks on a single cluster.
@@ -76,6 +93,9 @@ pub enum IntrospectionType {
 
     // Written by the Adapter for tracking AWS PrivateLink Connection Status History
     PrivatelinkConnectionStatusHistory,
+
+    // Written by the Adapter from the state of the timestamp oracle's circuit breaker.
+    TimestampOracleStatus,
 }
 
 /// Describes how data is written to the collection.
@@ -187,9 +207,103 @@ impl<T: Codec64 + Timestamp + Lattice> SnapshotCursor<T> {
     }
 }
 
+/// A function that resolves a [`GlobalId`] to the name of the object it identifies, if known.
+pub type NameResolver = Arc<dyn Fn(GlobalId) -> Option<String> + Send + Sync>;
+
+/// Resolves [`GlobalId`]s to human-readable names when formatting logs and errors.
+///
+/// Clones share the registered [`NameResolver`], so a resolver registered after the handle was
+/// handed out, e.g. to a replica client, is observed by all of them. The resolver is only consulted
+/// when an id is actually formatted, never on hot paths, but it must still be cheap and must not
+/// block. Without a resolver, or for ids it does not know, ids are formatted as-is.
+#[derive(Clone, Default)]
+pub struct CollectionNames {
+    resolver: Arc<RwLock<Option<NameResolver>>>,
+}
+
+impl CollectionNames {
+    /// Registers the resolver to use from now on, replacing any previously registered one.
+    pub fn register(&self, resolver: NameResolver) {
+        *self.resolver.write().expect("lock poisoned") = Some(resolver);
+    }
+
+    /// Returns the name of the object identified by `id`, if it is known.
+    pub fn resolve(&self, id: GlobalId) -> Option<String> {
+        let resolver = self.resolver.read().expect("lock poisoned");
+        resolver.as_ref().and_then(|resolve| resolve(id))
+    }
+
+    /// Returns a displayable form of `id` that includes the name of the object, if it is known.
+    ///
+    /// The name is resolved lazily, when the returned value is formatted.
+    pub fn display(&self, id: GlobalId) -> DisplayCollection<'_> {
+        DisplayCollection { names: self, id }
+    }
+}
+
+impl Debug for CollectionNames {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let registered = self.resolver.read().expect("lock poisoned").is_some();
+        f.debug_struct("CollectionNames")
+            .field("registered", &registered)
+            .finish()
+    }
+}
+
+/// A [`GlobalId`] that is formatted along with the name of the object it identifies, if known.
+///
+/// See [`CollectionNames::display`].
+pub struct DisplayCollection<'a> {
+    names: &'a CollectionNames,
+    id: GlobalId,
+}
+
+impl fmt::Display for DisplayCollection<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.names.resolve(self.id) {
+            Some(name) => write!(f, "{name} ({})", self.id),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Response<T> {
     FrontierUpdates(Vec<(GlobalId, Antichain<T>)>),
+    /// Statistics of sources and sinks, as reported by the instances that run them.
+    StatisticsUpdates(Vec<SourceStatisticsUpdate>, Vec<SinkStatisticsUpdate>),
+}
+
+/// The status of the connection of a storage controller to its stash.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StashConnectionStatus {
+    /// Whether the stash is connected. A disconnected stash fails writes, while the controller
+    /// keeps serving its cached state.
+    pub connected: bool,
+    /// The error the connection most recently failed with, if any, which is retained after the
+    /// stash reconnects.
+    pub last_error: Option<String>,
+}
+
+/// An estimate of how much data compaction could reclaim in a collection if its since advanced
+/// to `proposed_since`.
+///
+/// The estimate is derived from the time ranges and sizes of the batches in the collection's
+/// shard, not from the updates in them, so it is coarse. Both figures are upper bounds: updates
+/// at the times that are advanced to the proposed since only take up less space to the extent
+/// that they consolidate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompactionEstimate<T> {
+    /// The current since of the collection.
+    pub since: Antichain<T>,
+    /// The since of the collection under the proposed read policy, taking into account the read
+    /// holds of others.
+    pub proposed_since: Antichain<T>,
+    /// The estimated number of bytes, in batches in persist, that compaction could reclaim.
+    pub estimated_reclaimable_bytes: u64,
+    /// The estimated number of distinct timestamps whose updates would be advanced to the
+    /// proposed since.
+    pub estimated_collapsed_timestamps: u64,
 }
 
 #[async_trait(?Send)]
@@ -209,6 +323,20 @@ pub trait StorageController: Debug {
     /// Get the current configuration, including parameters updated with `update_parameters`.
     fn config(&self) -> &StorageConfiguration;
 
+    /// Get the names with which collections are identified in logs and errors.
+    fn collection_names(&self) -> &CollectionNames;
+
+    /// Get the recorder of the commands sent to storage instances.
+    fn command_recorder(&self) -> &CommandRecorder;
+
+    /// Returns the number of commands sent to storage instances that have not yet been
+    /// delivered to their replicas, summed across all instances.
+    fn pending_command_count(&self) -> usize;
+
+    /// Returns the status of the connection to the stash that the controller persists its
+    /// metadata in.
+    fn stash_connection_status(&self) -> StashConnectionStatus;
+
     /// Acquire an immutable reference to the collection state, should it exist.
     fn collection(&self, id: GlobalId) -> Result<&CollectionState<Self::Timestamp>, StorageError>;
 
@@ -301,15 +429,45 @@ pub trait StorageController: Debug {
         collections: &BTreeMap<GlobalId, IngestionDescription>,
     ) -> Result<(), StorageError>;
 
+    /// Check that the command that runs the ingestion `id` with the given description can be
+    /// sent to a replica, see [`RunIngestionCommand::check_size`].
+    ///
+    /// Call this before creating the ingestion's collections, so that an oversized ingestion
+    /// fails the statement that creates it. Exports the controller doesn't know of yet are
+    /// described with placeholder metadata, so their size is slightly underestimated.
+    ///
+    /// [`RunIngestionCommand::check_size`]: crate::client::RunIngestionCommand::check_size
+    fn check_ingestion_size(
+        &self,
+        id: GlobalId,
+        ingestion: &IngestionDescription,
+    ) -> Result<(), StorageError>;
+
     /// Alter the identified collection to use the described ingestion.
     async fn alter_collection(
         &mut self,
         collections: BTreeMap<GlobalId, IngestionDescription>,
     ) -> Result<(), StorageError>;
 
+    /// Alter the identified collections to use the described ingestions after a change to a
+    /// connection they depend on.
+    ///
+    /// Unlike [`StorageController::alter_collection`], the ingestions are not all restarted at
+    /// once: at most `max_concurrency` restarts are in flight at a time, and each restart is
+    /// recorded in the source status history. The returned receiver resolves once all of the
+    /// ingestions have restarted.
+    async fn alter_collection_connections(
+        &mut self,
+        collections: BTreeMap<GlobalId, IngestionDescription>,
+        max_concurrency: usize,
+    ) -> Result<oneshot::Receiver<()>, StorageError>;
+
     /// Acquire an immutable reference to the export state, should it exist.
     fn export(&self, id: GlobalId) -> Result<&ExportState<Self::Timestamp>, StorageError>;
 
+    /// Acquire an iterator over all export states.
+    fn exports(&self) -> Box<dyn Iterator<Item = (&GlobalId, &ExportState<Self::Timestamp>)> + '_>;
+
     /// Acquire a mutable reference to the export state, should it exist.
     fn export_mut(
         &mut self,
@@ -328,6 +486,11 @@ pub trait StorageController: Debug {
         exports: BTreeMap<GlobalId, StorageSinkConnection>,
     ) -> Result<(), StorageError>;
 
+    /// Restarts the identified export, allowing it to discard its recorded progress if that no
+    /// longer matches the state of its external system. Such an export resumes from its current
+    /// read capability.
+    async fn reset_export_progress(&mut self, id: GlobalId) -> Result<(), StorageError>;
+
     /// Drops the read capability for the sources and allows their resources to be reclaimed.
     fn drop_sources(&mut self, identifiers: Vec<GlobalId>) -> Result<(), StorageError>;
 
@@ -399,6 +562,15 @@ pub trait StorageController: Debug {
         as_of: Antichain<Self::Timestamp>,
     ) -> Result<SnapshotStats, StorageError>;
 
+    /// Estimates how much data compaction could reclaim in each of the given collections if its
+    /// read policy was changed to the given one. Nothing about the collections is changed.
+    ///
+    /// See [`CompactionEstimate`] for how coarse the estimates are.
+    async fn estimate_compaction(
+        &self,
+        policies: Vec<(GlobalId, ReadPolicy<Self::Timestamp>)>,
+    ) -> Result<BTreeMap<GlobalId, CompactionEstimate<Self::Timestamp>>, StorageError>;
+
     /// Assigns a read policy to specific identifiers.
     ///
     /// The policies are assigned in the order presented, and repeated identifiers should
@@ -460,6 +632,20 @@ pub trait StorageController: Debug {
     /// be awaited to completion.
     async fn process(&mut self) -> Result<Option<Response<Self::Timestamp>>, anyhow::Error>;
 
+    /// Returns a stream of the status updates of sources and sinks that the
+    /// controller absorbs from now on, as they are recorded in the status
+    /// history collections.
+    ///
+    /// The stream is unregistered once it is dropped.
+    fn subscribe_status_updates(&mut self) -> UnboundedReceiverStream<StatusUpdate>;
+
+    /// Updates the owner and comment of sources and sinks, which are recorded along with their
+    /// status updates in the status history collections.
+    ///
+    /// Objects not mentioned in `attributions` keep their previous attribution. The attribution of
+    /// an object is forgotten once its `Dropped` status is recorded.
+    fn update_status_attributions(&mut self, attributions: BTreeMap<GlobalId, StatusAttribution>);
+
     /// Signal to the controller that the adapter has populated all of its
     /// initial state and the controller can reconcile (i.e. drop) any unclaimed
     /// resources.
@@ -479,6 +665,15 @@ pub trait StorageController: Debug {
     async fn inspect_persist_state(&self, id: GlobalId)
         -> Result<serde_json::Value, anyhow::Error>;
 
+    /// Asks the storage cluster that maintains collection `id` to report the state of the
+    /// collection as read from persist directly, for checking against the controller's view.
+    /// If `id` is an ingestion, the state of all its collections is requested.
+    ///
+    /// Mismatches are logged and counted in metrics once the report arrives. Returns the IDs of
+    /// the collections whose state was requested.
+    fn report_collection_state(&mut self, id: GlobalId)
+        -> Result<BTreeSet<GlobalId>, StorageError>;
+
     /// Records the current read and write frontiers of all known storage objects.
     ///
     /// The provided `external_frontiers` are merged with the frontiers known to
@@ -518,6 +713,10 @@ pub trait StorageController: Debug {
         updates: Vec<(Row, Diff)>,
     );
 
+    /// Returns the latest statistics of the sources and sinks that are not dropped, as reported
+    /// by the instances that run them.
+    fn statistics_snapshot(&self) -> (Vec<SourceStatisticsUpdate>, Vec<SinkStatisticsUpdate>);
+
     /// Resets the txns system to a set of invariants necessary for correctness.
     ///
     /// Must be called on boot before create_collections or the various appends.
@@ -555,6 +754,14 @@ pub struct CollectionState<T> {
     /// Reported write frontier.
     pub write_frontier: Antichain<T>,
 
+    /// The most recently reported number of bytes the upstream system is ahead of this
+    /// collection, for ingestions that report it.
+    pub upstream_lag: Option<u64>,
+
+    /// The generation of the storage parameters that the ingestion dataflow was most recently
+    /// rendered with, for ingestions that have been rendered.
+    pub parameter_generation: Option<u64>,
+
     pub collection_metadata: CollectionMetadata,
 }
 
@@ -578,6 +785,8 @@ impl<T: Timestamp> CollectionState<T> {
             },
             storage_dependencies,
             write_frontier,
+            upstream_lag: None,
+            parameter_generation: None,
             collection_metadata: metadata,
         }
     }
@@ -619,6 +828,10 @@ pub struct ExportState<T> {
 
     /// Reported write frontier.
     pub write_frontier: Antichain<T>,
+
+    /// The frontier up to which the sink has emitted its input to the external system, as
+    /// reported by the replica writing to it.
+    pub emitted_upper: Antichain<T>,
 }
 
 impl<T: Timestamp> ExportState<T> {
@@ -634,6 +847,7 @@ impl<T: Timestamp> ExportState<T> {
             read_policy,
             storage_dependencies,
             write_frontier: Antichain::from_elem(Timestamp::minimum()),
+            emitted_upper: Antichain::from_elem(Timestamp::minimum()),
         }
     }
 
@@ -646,6 +860,12 @@ impl<T: Timestamp> ExportState<T> {
     pub fn is_dropped(&self) -> bool {
         self.read_capability.is_empty()
     }
+
+    /// Returns whether the export is still backfilling, i.e. has not yet emitted the snapshot of
+    /// its input at its `as_of` to the external system.
+    pub fn is_backfilling(&self) -> bool {
+        !PartialOrder::less_than(&self.description.sink.as_of, &self.emitted_upper)
+    }
 }
 /// A channel that allows you to append a set of updates to a pre-defined [`GlobalId`].
 ///
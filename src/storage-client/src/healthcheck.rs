@@ -65,6 +65,10 @@ pub static MZ_STATEMENT_EXECUTION_HISTORY_DESC: Lazy<RelationDesc> = Lazy::new(|
         .with_column("execution_timestamp", ScalarType::UInt64.nullable(true))
         .with_column("transaction_id", ScalarType::UInt64.nullable(false))
         .with_column("transient_index_id", ScalarType::String.nullable(true))
+        .with_column(
+            "dependency_ids",
+            ScalarType::Array(Box::new(ScalarType::String)).nullable(true),
+        )
         .with_column(
             "params",
             ScalarType::Array(Box::new(ScalarType::String)).nullable(false),
@@ -93,6 +97,10 @@ pub static MZ_SOURCE_STATUS_HISTORY_DESC: Lazy<RelationDesc> = Lazy::new(|| {
         .with_column("status", ScalarType::String.nullable(false))
         .with_column("error", ScalarType::String.nullable(true))
         .with_column("details", ScalarType::Jsonb.nullable(true))
+        .with_column("owner", ScalarType::String.nullable(true))
+        .with_column("comment", ScalarType::String.nullable(true))
+        .with_column("cluster_id", ScalarType::String.nullable(true))
+        .with_column("replica_id", ScalarType::String.nullable(true))
 });
 
 pub static MZ_SINK_STATUS_HISTORY_DESC: Lazy<RelationDesc> = Lazy::new(|| {
@@ -105,6 +113,10 @@ pub static MZ_SINK_STATUS_HISTORY_DESC: Lazy<RelationDesc> = Lazy::new(|| {
         .with_column("status", ScalarType::String.nullable(false))
         .with_column("error", ScalarType::String.nullable(true))
         .with_column("details", ScalarType::Jsonb.nullable(true))
+        .with_column("owner", ScalarType::String.nullable(true))
+        .with_column("comment", ScalarType::String.nullable(true))
+        .with_column("cluster_id", ScalarType::String.nullable(true))
+        .with_column("replica_id", ScalarType::String.nullable(true))
 });
 
 pub static MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY_DESC: Lazy<RelationDesc> =
@@ -117,3 +129,15 @@ pub static MZ_AWS_PRIVATELINK_CONNECTION_STATUS_HISTORY_DESC: Lazy<RelationDesc>
             .with_column("connection_id", ScalarType::String.nullable(false))
             .with_column("status", ScalarType::String.nullable(false))
     });
+
+pub static MZ_FRONTIERS_HISTORY_DESC: Lazy<RelationDesc> = Lazy::new(|| {
+    RelationDesc::empty()
+        .with_column(
+            "occurred_at",
+            ScalarType::TimestampTz { precision: None }.nullable(false),
+        )
+        .with_column("object_id", ScalarType::String.nullable(false))
+        .with_column("resolution", ScalarType::String.nullable(false))
+        .with_column("min_write_frontier", ScalarType::MzTimestamp.nullable(true))
+        .with_column("max_write_frontier", ScalarType::MzTimestamp.nullable(true))
+});
@@ -13,10 +13,13 @@ use std::sync::Arc;
 
 use mz_ore::cast::{CastFrom, TryCastFrom};
 use mz_ore::metric;
-use mz_ore::metrics::{DeleteOnDropHistogram, HistogramVecExt, MetricsRegistry};
+use mz_ore::metrics::{
+    CounterVecExt, DeleteOnDropCounter, DeleteOnDropHistogram, HistogramVecExt, MetricsRegistry,
+};
 use mz_ore::stats::HISTOGRAM_BYTE_BUCKETS;
 use mz_service::codec::StatsCollector;
 use mz_storage_types::instances::StorageInstanceId;
+use prometheus::core::AtomicU64;
 
 use crate::client::{ProtoStorageCommand, ProtoStorageResponse};
 
@@ -25,6 +28,8 @@ use crate::client::{ProtoStorageCommand, ProtoStorageResponse};
 pub struct StorageControllerMetrics {
     messages_sent_bytes: prometheus::HistogramVec,
     messages_received_bytes: prometheus::HistogramVec,
+    response_order_violations: prometheus::IntCounterVec,
+    collection_state_mismatches: prometheus::IntCounterVec,
     startup_prepared_statements_kept: prometheus::IntGauge,
 }
 
@@ -45,6 +50,19 @@ impl StorageControllerMetrics {
                 buckets: HISTOGRAM_BYTE_BUCKETS.to_vec()
             )),
 
+            response_order_violations: metrics_registry.register(metric!(
+                name: "mz_storage_response_order_violations_total",
+                help: "number of storage responses rejected for violating per-collection ordering",
+                var_labels: ["instance"],
+            )),
+
+            collection_state_mismatches: metrics_registry.register(metric!(
+                name: "mz_storage_collection_state_mismatches_total",
+                help: "number of mismatches between the controller's view of a collection and \
+                       the state a replica reported for it",
+                var_labels: ["kind"],
+            )),
+
             startup_prepared_statements_kept: metrics_registry.register(metric!(
                 name: "mz_storage_startup_prepared_statements_kept",
                 help: "number of prepared statements kept on startup",
@@ -61,11 +79,22 @@ impl StorageControllerMetrics {
                     .get_delete_on_drop_histogram(labels.clone()),
                 messages_received_bytes: self
                     .messages_received_bytes
-                    .get_delete_on_drop_histogram(labels),
+                    .get_delete_on_drop_histogram(labels.clone()),
+                response_order_violations: self
+                    .response_order_violations
+                    .get_delete_on_drop_counter(labels),
             }),
         }
     }
 
+    /// Records a mismatch of the given kind between the controller's view of a collection and
+    /// the state a replica reported for it.
+    pub fn inc_collection_state_mismatches(&self, kind: &str) {
+        self.collection_state_mismatches
+            .with_label_values(&[kind])
+            .inc();
+    }
+
     pub fn set_startup_prepared_statements_kept(&self, n: u64) {
         let n: i64 = n.try_into().expect("realistic number");
         self.startup_prepared_statements_kept.set(n);
@@ -76,6 +105,7 @@ impl StorageControllerMetrics {
 struct RehydratingStorageClientMetricsInner {
     messages_sent_bytes: DeleteOnDropHistogram<'static, Vec<String>>,
     messages_received_bytes: DeleteOnDropHistogram<'static, Vec<String>>,
+    response_order_violations: DeleteOnDropCounter<'static, AtomicU64, Vec<String>>,
 }
 
 /// Per-instance metrics
@@ -84,6 +114,14 @@ pub struct RehydratingStorageClientMetrics {
     inner: Arc<RehydratingStorageClientMetricsInner>,
 }
 
+impl RehydratingStorageClientMetrics {
+    /// Records that a response was rejected for violating the ordering
+    /// guarantees enforced by [`ResponseSequencer`](crate::client::ResponseSequencer).
+    pub fn inc_response_order_violations(&self) {
+        self.inner.response_order_violations.inc();
+    }
+}
+
 /// Make ReplicaConnectionMetric pluggable into the gRPC connection.
 impl StatsCollector<ProtoStorageCommand, ProtoStorageResponse> for RehydratingStorageClientMetrics {
     fn send_event(&self, _item: &ProtoStorageCommand, size: usize) {
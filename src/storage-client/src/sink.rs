@@ -8,6 +8,7 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::BTreeMap;
+use std::fmt;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -26,7 +27,7 @@ use rdkafka::error::KafkaError;
 use rdkafka::message::ToBytes;
 use rdkafka::{ClientContext, Message, Offset, TopicPartitionList};
 use serde::{Deserialize, Deserializer, Serialize};
-use timely::progress::Antichain;
+use timely::progress::{Antichain, Timestamp as _};
 use timely::PartialOrder;
 use tracing::{info, warn};
 
@@ -347,19 +348,117 @@ where
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct ProgressRecord {
     pub frontier: Vec<Timestamp>,
+    /// The state of the data topic after the batch committed. Absent in records written by older
+    /// versions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_topic: Option<DataTopicProgress>,
 }
 
-fn parse_progress_record(payload: &[u8]) -> Result<Antichain<Timestamp>, anyhow::Error> {
+/// The state of a sink's data topic as recorded in its progress records, used to validate that
+/// the topic is still the one the sink wrote to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataTopicProgress {
+    /// The number of partitions of the data topic.
+    pub partition_count: usize,
+    /// The number of data messages the sink has committed to the data topic since it started
+    /// recording them.
+    pub messages_committed: u64,
+}
+
+impl DataTopicProgress {
+    /// Validates the recorded state against the `(low, high)` watermarks of each partition of the
+    /// data topic.
+    ///
+    /// Partitions can be added to a topic but never removed, and offsets are never reused, so
+    /// every committed message occupies an offset below the high watermark of its partition. A
+    /// topic with fewer partitions than recorded, or whose high watermarks add up to fewer offsets
+    /// than the sink has committed messages, must have been deleted and recreated.
+    pub fn validate(&self, watermarks: &[(i64, i64)]) -> Result<(), SinkProgressMismatch> {
+        if watermarks.len() < self.partition_count {
+            return Err(SinkProgressMismatch::PartitionCount {
+                recorded: self.partition_count,
+                actual: watermarks.len(),
+            });
+        }
+        let offsets: u64 = watermarks
+            .iter()
+            .map(|(_low, high)| u64::try_from(*high).unwrap_or(0))
+            .sum();
+        if offsets < self.messages_committed {
+            return Err(SinkProgressMismatch::MissingMessages {
+                committed: self.messages_committed,
+                offsets,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// The recorded progress of a sink does not match the state of its data topic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SinkProgressMismatch {
+    /// The data topic does not exist.
+    TopicMissing,
+    /// The data topic has fewer partitions than recorded.
+    PartitionCount { recorded: usize, actual: usize },
+    /// The data topic has fewer offsets than the sink committed messages.
+    MissingMessages { committed: u64, offsets: u64 },
+}
+
+impl fmt::Display for SinkProgressMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SinkProgressMismatch::TopicMissing => {
+                write!(
+                    f,
+                    "sink progress data exists, but sink data topic is missing"
+                )
+            }
+            SinkProgressMismatch::PartitionCount { recorded, actual } => write!(
+                f,
+                "sink progress records a data topic with {recorded} partitions, \
+                 but the data topic only has {actual} partitions"
+            ),
+            SinkProgressMismatch::MissingMessages { committed, offsets } => write!(
+                f,
+                "sink progress records {committed} committed messages, \
+                 but the data topic only has {offsets} offsets"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SinkProgressMismatch {}
+
+/// The latest progress a sink recorded in its progress topic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SinkProgress {
+    /// The frontier the sink needs to resume at.
+    pub upper: Antichain<Timestamp>,
+    /// The state of the data topic, if the progress record includes it.
+    pub data_topic: Option<DataTopicProgress>,
+}
+
+fn parse_progress_record(payload: &[u8]) -> Result<SinkProgress, anyhow::Error> {
     Ok(match serde_json::from_slice::<ProgressRecord>(payload) {
-        Ok(progress) => Antichain::from(progress.frontier),
+        Ok(progress) => SinkProgress {
+            upper: Antichain::from(progress.frontier),
+            data_topic: progress.data_topic,
+        },
         // If we fail to deserialize we might be reading a legacy progress record
         Err(_) => match serde_json::from_slice::<LegacyProgressRecord>(payload) {
             Ok(LegacyProgressRecord {
                 timestamp: Some(Some(time)),
-            }) => Antichain::from_elem(time.step_forward()),
+            }) => SinkProgress {
+                upper: Antichain::from_elem(time.step_forward()),
+                data_topic: None,
+            },
             Ok(LegacyProgressRecord {
                 timestamp: Some(None),
-            }) => Antichain::new(),
+            }) => SinkProgress {
+                upper: Antichain::new(),
+                data_topic: None,
+            },
             _ => match std::str::from_utf8(payload) {
                 Ok(payload) => bail!("invalid progress record: {payload}"),
                 Err(_) => bail!("invalid progress record bytes: {payload:?}"),
@@ -371,6 +470,9 @@ fn parse_progress_record(payload: &[u8]) -> Result<Antichain<Timestamp>, anyhow:
 /// Determines the latest progress record from the specified topic for the given
 /// progress key.
 ///
+/// A reset of the sink's progress is recorded as a progress record at the minimum timestamp,
+/// which is why the upper may regress.
+///
 /// IMPORTANT: to achieve exactly once guarantees, the producer that will resume
 /// production at the returned timestamp *must* have called `init_transactions`
 /// prior to calling this method.
@@ -378,7 +480,7 @@ pub async fn determine_sink_resume_upper(
     sink_id: mz_repr::GlobalId,
     connection: &KafkaSinkConnection,
     storage_configuration: &StorageConfiguration,
-) -> Result<Option<Antichain<Timestamp>>, ContextCreationError> {
+) -> Result<Option<SinkProgress>, ContextCreationError> {
     // ****************************** WARNING ******************************
     // Be VERY careful when editing the code in this function. It is very easy
     // to accidentally introduce a correctness or liveness bug when refactoring
@@ -589,7 +691,7 @@ pub async fn determine_sink_resume_upper(
         // returning an error) if we have positive proof of a position at or
         // beyond the high water mark. To make this invariant easy to check, do
         // not use `break` in the body of the loop.
-        let mut last_upper = None;
+        let mut last_progress = None;
         while get_position()? < hi {
             let message = match progress_client_read_committed.poll(progress_record_fetch_timeout) {
                 Some(Ok(message)) => message,
@@ -617,23 +719,29 @@ pub async fn determine_sink_resume_upper(
             let Some(payload) = message.payload() else {
                 continue
             };
-            let upper = parse_progress_record(payload)?;
-
-            match last_upper {
-                Some(last_upper) if !PartialOrder::less_equal(&last_upper, &upper) => {
+            let progress = parse_progress_record(payload)?;
+
+            match last_progress {
+                Some(SinkProgress {
+                    upper: last_upper,
+                    ..
+                }) if !PartialOrder::less_equal(&last_upper, &progress.upper)
+                    && *progress.upper != [Timestamp::minimum()] =>
+                {
                     bail!(
                         "upper regressed in topic {progress_topic}:{partition} \
-                        from {last_upper:?} to {upper:?}"
+                        from {last_upper:?} to {:?}",
+                        progress.upper
                     );
                 }
-                _ => last_upper = Some(upper),
+                _ => last_progress = Some(progress),
             }
         }
 
         // If we get here, we are assured that we've read all messages up to
-        // the high water mark, and therefore `last_timestamp` contains the
-        // most recent timestamp for the sink under consideration.
-        Ok(last_upper)
+        // the high water mark, and therefore `last_progress` contains the
+        // most recent progress for the sink under consideration.
+        Ok(last_progress)
     }).await.unwrap().check_ssh_status(&ctx)
 }
 
@@ -643,28 +751,82 @@ mod test {
 
     #[mz_ore::test]
     fn progress_record_migration() {
-        assert!(parse_progress_record(b"{}").is_err());
+        let upper = |payload: &[u8]| parse_progress_record(payload).map(|p| p.upper);
+
+        assert!(upper(b"{}").is_err());
 
         assert_eq!(
-            parse_progress_record(b"{\"timestamp\":1}").unwrap(),
+            upper(b"{\"timestamp\":1}").unwrap(),
             Antichain::from_elem(2.into()),
         );
 
+        assert_eq!(upper(b"{\"timestamp\":null}").unwrap(), Antichain::new());
+
         assert_eq!(
-            parse_progress_record(b"{\"timestamp\":null}").unwrap(),
-            Antichain::new(),
+            upper(b"{\"frontier\":[1]}").unwrap(),
+            Antichain::from_elem(1.into()),
         );
 
+        assert_eq!(upper(b"{\"frontier\":[]}").unwrap(), Antichain::new());
+
+        assert!(upper(b"{\"frontier\":null}").is_err());
+    }
+
+    #[mz_ore::test]
+    fn progress_record_data_topic() {
+        // Records written by older versions don't describe the data topic.
         assert_eq!(
-            parse_progress_record(b"{\"frontier\":[1]}").unwrap(),
-            Antichain::from_elem(1.into()),
+            parse_progress_record(b"{\"frontier\":[1]}")
+                .unwrap()
+                .data_topic,
+            None,
         );
 
+        let record = ProgressRecord {
+            frontier: vec![1.into()],
+            data_topic: Some(DataTopicProgress {
+                partition_count: 2,
+                messages_committed: 10,
+            }),
+        };
+        let payload = serde_json::to_vec(&record).unwrap();
         assert_eq!(
-            parse_progress_record(b"{\"frontier\":[]}").unwrap(),
-            Antichain::new(),
+            parse_progress_record(&payload).unwrap(),
+            SinkProgress {
+                upper: Antichain::from_elem(1.into()),
+                data_topic: record.data_topic,
+            },
         );
+    }
+
+    #[mz_ore::test]
+    fn data_topic_progress_validation() {
+        let progress = DataTopicProgress {
+            partition_count: 2,
+            messages_committed: 10,
+        };
 
-        assert!(parse_progress_record(b"{\"frontier\":null}").is_err());
+        // Transaction markers occupy offsets too, so there may be more offsets than messages.
+        assert_eq!(progress.validate(&[(0, 6), (0, 7)]), Ok(()));
+        // Retention advances the low watermarks, but not the high watermarks.
+        assert_eq!(progress.validate(&[(6, 6), (7, 7)]), Ok(()));
+        // Partitions may be added.
+        assert_eq!(progress.validate(&[(0, 6), (0, 7), (0, 0)]), Ok(()));
+
+        // A recreated topic starts over at offset zero.
+        assert_eq!(
+            progress.validate(&[(0, 0), (0, 3)]),
+            Err(SinkProgressMismatch::MissingMessages {
+                committed: 10,
+                offsets: 3,
+            }),
+        );
+        assert_eq!(
+            progress.validate(&[(0, 20)]),
+            Err(SinkProgressMismatch::PartitionCount {
+                recorded: 2,
+                actual: 1,
+            }),
+        );
     }
 }
@@ -25,7 +25,7 @@ use mz_repr::GlobalId;
 include!(concat!(env!("OUT_DIR"), "/mz_storage_client.statistics.rs"));
 
 /// A trait that abstracts over user-facing statistics objects, used
-/// by `spawn_statistics_scraper`.
+/// by the adapter to write the statistics collections.
 pub trait PackableStats {
     /// Pack `self` into the `Row`.
     fn pack(&self, packer: mz_repr::RowPacker<'_>);
@@ -43,6 +43,12 @@ pub struct SourceStatisticsUpdate {
     pub envelope_state_bytes: u64,
     pub envelope_state_records: u64,
     pub rehydration_latency_ms: Option<i64>,
+    /// The number of records the snapshot is expected to contain, if known. This may start as
+    /// an estimate and be revised as the snapshot progresses.
+    pub snapshot_records_known: Option<u64>,
+    /// The number of snapshot records staged so far. This is monotone within a single attempt
+    /// at taking the snapshot.
+    pub snapshot_records_staged: Option<u64>,
 }
 
 impl PackableStats for SourceStatisticsUpdate {
@@ -62,6 +68,8 @@ impl PackableStats for SourceStatisticsUpdate {
             self.rehydration_latency_ms
                 .map(chrono::Duration::milliseconds),
         ));
+        packer.push(Datum::from(self.snapshot_records_known));
+        packer.push(Datum::from(self.snapshot_records_staged));
     }
 }
 
@@ -81,6 +89,9 @@ impl RustType<ProtoSourceStatisticsUpdate> for SourceStatisticsUpdate {
             envelope_state_bytes: self.envelope_state_bytes,
             rehydration_latency_ms: self.rehydration_latency_ms,
 
+            snapshot_records_known: self.snapshot_records_known,
+            snapshot_records_staged: self.snapshot_records_staged,
+
             snapshot_committed: self.snapshot_committed,
         }
     }
@@ -102,6 +113,9 @@ impl RustType<ProtoSourceStatisticsUpdate> for SourceStatisticsUpdate {
             envelope_state_bytes: proto.envelope_state_bytes,
             rehydration_latency_ms: proto.rehydration_latency_ms,
 
+            snapshot_records_known: proto.snapshot_records_known,
+            snapshot_records_staged: proto.snapshot_records_staged,
+
             snapshot_committed: proto.snapshot_committed,
         })
     }
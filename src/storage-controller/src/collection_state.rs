@@ -0,0 +1,234 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Checks the controller's view of a collection against the state a replica reports for it.
+//!
+//! On request, a replica reports the upper and since of a collection's shard as read from
+//! persist directly, along with the upper its workers had last reported in `FrontierUppers`. A
+//! replica only reports an upper once the data up to it has been written, and it reports the
+//! state of a collection only after all uppers it reported before, so a consistent report
+//! satisfies:
+//!
+//! * The reported upper is not beyond the controller's write frontier. Otherwise the controller
+//!   missed a frontier update.
+//! * The reported upper is not beyond the persist upper. Otherwise the replica reported progress
+//!   that wasn't written.
+//! * The persist since is not beyond the controller's since. Otherwise the shard was compacted
+//!   past times that the controller still considers readable.
+
+use std::fmt;
+
+use mz_repr::GlobalId;
+use mz_storage_client::client::CollectionStateReport;
+use timely::progress::{Antichain, Timestamp};
+use timely::PartialOrder;
+
+/// A disagreement between a [`CollectionStateReport`] and the controller's view of the
+/// collection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum CollectionStateMismatch<T> {
+    /// The replica reported an upper that the controller's write frontier is not beyond.
+    MissedUpper {
+        id: GlobalId,
+        reported_upper: Antichain<T>,
+        write_frontier: Antichain<T>,
+    },
+    /// The replica reported an upper that the collection's shard is not beyond.
+    UnpersistedUpper {
+        id: GlobalId,
+        reported_upper: Antichain<T>,
+        persist_upper: Antichain<T>,
+    },
+    /// The collection's shard was compacted beyond the controller's since.
+    SinceAhead {
+        id: GlobalId,
+        persist_since: Antichain<T>,
+        since: Antichain<T>,
+    },
+}
+
+impl<T> CollectionStateMismatch<T> {
+    /// Returns the label with which the mismatch is recorded in metrics.
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            CollectionStateMismatch::MissedUpper { .. } => "missed_upper",
+            CollectionStateMismatch::UnpersistedUpper { .. } => "unpersisted_upper",
+            CollectionStateMismatch::SinceAhead { .. } => "since_ahead",
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Display for CollectionStateMismatch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CollectionStateMismatch::MissedUpper {
+                id,
+                reported_upper,
+                write_frontier,
+            } => write!(
+                f,
+                "replica reported upper {reported_upper:?} for {id}, \
+                 beyond the controller's write frontier {write_frontier:?}"
+            ),
+            CollectionStateMismatch::UnpersistedUpper {
+                id,
+                reported_upper,
+                persist_upper,
+            } => write!(
+                f,
+                "replica reported upper {reported_upper:?} for {id}, \
+                 beyond the upper {persist_upper:?} of its shard"
+            ),
+            CollectionStateMismatch::SinceAhead {
+                id,
+                persist_since,
+                since,
+            } => write!(
+                f,
+                "shard of {id} has since {persist_since:?}, \
+                 beyond the controller's since {since:?}"
+            ),
+        }
+    }
+}
+
+/// Checks `report` against the controller's `write_frontier` and `since` of the collection,
+/// returning all mismatches.
+pub(crate) fn check_collection_state<T: Timestamp>(
+    report: &CollectionStateReport<T>,
+    write_frontier: &Antichain<T>,
+    since: &Antichain<T>,
+) -> Vec<CollectionStateMismatch<T>> {
+    let id = report.id;
+    let mut mismatches = Vec::new();
+    if let Some(reported_upper) = &report.reported_upper {
+        if !PartialOrder::less_equal(reported_upper, write_frontier) {
+            mismatches.push(CollectionStateMismatch::MissedUpper {
+                id,
+                reported_upper: reported_upper.clone(),
+                write_frontier: write_frontier.clone(),
+            });
+        }
+        if let Some(persist_upper) = &report.persist_upper {
+            if !PartialOrder::less_equal(reported_upper, persist_upper) {
+                mismatches.push(CollectionStateMismatch::UnpersistedUpper {
+                    id,
+                    reported_upper: reported_upper.clone(),
+                    persist_upper: persist_upper.clone(),
+                });
+            }
+        }
+    }
+    if let Some(persist_since) = &report.persist_since {
+        if !PartialOrder::less_equal(persist_since, since) {
+            mismatches.push(CollectionStateMismatch::SinceAhead {
+                id,
+                persist_since: persist_since.clone(),
+                since: since.clone(),
+            });
+        }
+    }
+    mismatches
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::Timestamp;
+
+    use super::*;
+
+    fn frontier(t: u64) -> Antichain<Timestamp> {
+        Antichain::from_elem(Timestamp::from(t))
+    }
+
+    fn report(
+        persist: Option<(u64, u64)>,
+        reported_upper: Option<u64>,
+    ) -> CollectionStateReport<Timestamp> {
+        CollectionStateReport {
+            id: GlobalId::User(1),
+            persist_upper: persist.map(|(upper, _)| frontier(upper)),
+            persist_since: persist.map(|(_, since)| frontier(since)),
+            persist_error: None,
+            reported_upper: reported_upper.map(frontier),
+        }
+    }
+
+    fn labels(report: &CollectionStateReport<Timestamp>) -> Vec<&'static str> {
+        check_collection_state(report, &frontier(10), &frontier(5))
+            .iter()
+            .map(|mismatch| mismatch.label())
+            .collect()
+    }
+
+    #[mz_ore::test]
+    fn test_consistent_collection_state() {
+        // The replica may lag behind the controller, and persist ahead of both, as the report
+        // races with later progress.
+        assert!(labels(&report(Some((12, 5)), Some(10))).is_empty());
+        assert!(labels(&report(Some((10, 3)), Some(8))).is_empty());
+        // Replicas that don't read the shard, or haven't reported an upper, check nothing.
+        assert!(labels(&report(None, None)).is_empty());
+        assert!(labels(&report(None, Some(10))).is_empty());
+        // A sealed collection.
+        let sealed = CollectionStateReport {
+            id: GlobalId::User(1),
+            persist_upper: Some(Antichain::new()),
+            persist_since: Some(frontier(5)),
+            persist_error: None,
+            reported_upper: Some(Antichain::new()),
+        };
+        let mismatches = check_collection_state(&sealed, &Antichain::new(), &frontier(5));
+        assert!(mismatches.is_empty(), "{mismatches:?}");
+    }
+
+    #[mz_ore::test]
+    fn test_seeded_collection_state_mismatches() {
+        // The controller missed the replica's frontier update.
+        assert_eq!(labels(&report(Some((12, 5)), Some(11))), ["missed_upper"]);
+        // The replica reported progress that the shard hasn't made.
+        assert_eq!(
+            labels(&report(Some((9, 5)), Some(10))),
+            ["unpersisted_upper"]
+        );
+        // The shard was compacted beyond what the controller allowed.
+        assert_eq!(labels(&report(Some((12, 6)), Some(10))), ["since_ahead"]);
+        // All at once.
+        let mismatches = check_collection_state(
+            &report(Some((11, 7)), Some(12)),
+            &frontier(10),
+            &frontier(5),
+        );
+        assert_eq!(
+            mismatches,
+            vec![
+                CollectionStateMismatch::MissedUpper {
+                    id: GlobalId::User(1),
+                    reported_upper: frontier(12),
+                    write_frontier: frontier(10),
+                },
+                CollectionStateMismatch::UnpersistedUpper {
+                    id: GlobalId::User(1),
+                    reported_upper: frontier(12),
+                    persist_upper: frontier(11),
+                },
+                CollectionStateMismatch::SinceAhead {
+                    id: GlobalId::User(1),
+                    persist_since: frontier(7),
+                    since: frontier(5),
+                },
+            ]
+        );
+        assert_eq!(
+            mismatches[0].to_string(),
+            "replica reported upper Antichain { elements: [12] } for u1, \
+             beyond the controller's write frontier Antichain { elements: [10] }"
+        );
+    }
+}
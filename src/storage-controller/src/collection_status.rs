@@ -14,8 +14,10 @@ use differential_dataflow::lattice::Lattice;
 use mz_ore::now::EpochMillis;
 use mz_persist_types::Codec64;
 use mz_repr::{GlobalId, Row, TimestampManipulation};
-use mz_storage_client::client::{Status, StatusUpdate};
+use mz_storage_client::client::{Status, StatusAttribution, StatusOrigin, StatusUpdate};
 use timely::progress::Timestamp;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use crate::collection_mgmt::CollectionManager;
 use crate::IntrospectionType;
@@ -34,6 +36,10 @@ where
     /// A list of introspection IDs for managed collections
     introspection_ids: Arc<std::sync::Mutex<BTreeMap<IntrospectionType, GlobalId>>>,
     previous_statuses: BTreeMap<GlobalId, Status>,
+    /// The attribution to record along with the status updates of each object.
+    attributions: BTreeMap<GlobalId, StatusAttribution>,
+    /// Consumers of the status updates that are appended.
+    subscribers: StatusSubscribers,
 }
 
 impl<T> CollectionStatusManager<T>
@@ -48,9 +54,16 @@ where
             collection_manager,
             introspection_ids,
             previous_statuses: Default::default(),
+            attributions: Default::default(),
+            subscribers: Default::default(),
         }
     }
 
+    /// Returns a stream of all status updates that are appended from now on.
+    pub fn subscribe(&mut self) -> UnboundedReceiverStream<StatusUpdate> {
+        self.subscribers.subscribe()
+    }
+
     pub fn extend_previous_statuses<I>(&mut self, previous_statuses: I)
     where
         I: IntoIterator<Item = (GlobalId, Status)>,
@@ -58,10 +71,24 @@ where
         self.previous_statuses.extend(previous_statuses)
     }
 
+    /// Updates the attribution of the given objects.
+    ///
+    /// Attributions are retained until the object's `Dropped` status is appended, so that it
+    /// is attributed even if the object was removed from the catalog in the meantime.
+    pub fn update_attributions<I>(&mut self, attributions: I)
+    where
+        I: IntoIterator<Item = (GlobalId, StatusAttribution)>,
+    {
+        self.attributions.extend(attributions)
+    }
+
+    /// Appends `updates` to the status history of `type_`, recording that they were reported by
+    /// `origin`, or by the controller if it is `None`.
     pub(super) async fn append_updates(
         &mut self,
         updates: Vec<StatusUpdate>,
         type_: IntrospectionType,
+        origin: Option<StatusOrigin>,
     ) {
         let source_status_history_id = *self
             .introspection_ids
@@ -82,23 +109,60 @@ where
 
         self.previous_statuses
             .extend(new.iter().map(|r| (r.id, r.status)));
+        self.subscribers.forward(&new);
 
+        let rows = new
+            .into_iter()
+            .map(|update| {
+                let id = update.id;
+                let dropped = update.status == Status::Dropped;
+                let row = update.into_row(self.attributions.get(&id), origin.as_ref());
+                if dropped {
+                    self.attributions.remove(&id);
+                }
+                (row, 1)
+            })
+            .collect();
         self.collection_manager
-            .append_to_collection(
-                source_status_history_id,
-                new.into_iter()
-                    .map(|update| (Row::from(update), 1))
-                    .collect(),
-            )
+            .append_to_collection(source_status_history_id, rows)
             .await;
     }
 }
 
+/// The consumers of status updates, see [`CollectionStatusManager::subscribe`].
+///
+/// Subscriptions are unbounded, so that updates are never lost, and in
+/// particular terminal statuses are delivered before a subscription ends. A
+/// subscription is unregistered once its stream is dropped.
+#[derive(Debug, Clone, Default)]
+struct StatusSubscribers {
+    senders: Vec<mpsc::UnboundedSender<StatusUpdate>>,
+}
+
+impl StatusSubscribers {
+    fn subscribe(&mut self) -> UnboundedReceiverStream<StatusUpdate> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.senders.push(tx);
+        UnboundedReceiverStream::new(rx)
+    }
+
+    /// Sends `updates` to all subscribers, unregistering those whose stream
+    /// was dropped.
+    fn forward(&mut self, updates: &[StatusUpdate]) {
+        self.senders.retain(|tx| {
+            !tx.is_closed() && updates.iter().all(|update| tx.send(update.clone()).is_ok())
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeSet;
 
+    use futures::StreamExt;
+    use mz_cluster_client::ReplicaId;
     use mz_repr::Datum;
+    use mz_storage_types::instances::StorageInstanceId;
 
     use super::*;
 
@@ -315,4 +379,96 @@ mod tests {
             ("thing", Datum::String("error"))
         );
     }
+
+    #[mz_ore::test]
+    fn test_row_with_attribution() {
+        let id = GlobalId::User(1);
+        let status = Status::Dropped;
+        let update = StatusUpdate::new(id, chrono::offset::Utc::now(), status);
+
+        let unattributed = Row::from(update.clone());
+        assert_eq!(unattributed.iter().nth(5).unwrap(), Datum::Null);
+        assert_eq!(unattributed.iter().nth(6).unwrap(), Datum::Null);
+
+        let attribution = StatusAttribution {
+            owner: Some("owner".to_string()),
+            comment: Some("comment".to_string()),
+        };
+        let row = update.into_row(Some(&attribution), None);
+
+        for (datum, column_type) in row.iter().zip(MZ_SINK_STATUS_HISTORY_DESC.iter_types()) {
+            assert!(datum.is_instance_of(column_type));
+        }
+
+        for (datum, column_type) in row.iter().zip(MZ_SOURCE_STATUS_HISTORY_DESC.iter_types()) {
+            assert!(datum.is_instance_of(column_type));
+        }
+
+        assert_eq!(row.iter().count(), MZ_SOURCE_STATUS_HISTORY_DESC.arity());
+        assert_eq!(row.iter().nth(1).unwrap(), Datum::String(&id.to_string()));
+        assert_eq!(row.iter().nth(2).unwrap(), Datum::String(status.to_str()));
+        assert_eq!(row.iter().nth(5).unwrap(), Datum::String("owner"));
+        assert_eq!(row.iter().nth(6).unwrap(), Datum::String("comment"));
+    }
+
+    #[mz_ore::test]
+    fn test_row_with_origin() {
+        let id = GlobalId::User(1);
+        let update = StatusUpdate::new(id, chrono::offset::Utc::now(), Status::Stalled);
+
+        let unattributed = Row::from(update.clone());
+        assert_eq!(unattributed.iter().nth(7).unwrap(), Datum::Null);
+        assert_eq!(unattributed.iter().nth(8).unwrap(), Datum::Null);
+
+        let origin = StatusOrigin {
+            cluster_id: StorageInstanceId::User(1),
+            replica_id: Some(ReplicaId::User(2)),
+        };
+        let row = update.into_row(None, Some(&origin));
+
+        for (datum, column_type) in row.iter().zip(MZ_SINK_STATUS_HISTORY_DESC.iter_types()) {
+            assert!(datum.is_instance_of(column_type));
+        }
+
+        for (datum, column_type) in row.iter().zip(MZ_SOURCE_STATUS_HISTORY_DESC.iter_types()) {
+            assert!(datum.is_instance_of(column_type));
+        }
+
+        assert_eq!(row.iter().count(), MZ_SOURCE_STATUS_HISTORY_DESC.arity());
+        assert_eq!(row.iter().nth(7).unwrap(), Datum::String("u1"));
+        assert_eq!(row.iter().nth(8).unwrap(), Datum::String("u2"));
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `epoll_wait` on OS `linux`
+    async fn test_subscribe() {
+        let mut subscribers = StatusSubscribers::default();
+        let mut stream = subscribers.subscribe();
+        let dropped = subscribers.subscribe();
+        drop(dropped);
+
+        let update = StatusUpdate {
+            id: GlobalId::User(1),
+            timestamp: chrono::offset::Utc::now(),
+            status: Status::Running,
+            error: None,
+            hints: BTreeSet::new(),
+            namespaced_errors: Default::default(),
+        };
+        subscribers.forward(&[update.clone()]);
+        assert_eq!(stream.next().await, Some(update.clone()));
+
+        // The dropped subscription was unregistered.
+        assert_eq!(subscribers.senders.len(), 1);
+
+        // Terminal statuses are delivered even if the subscription ends.
+        let terminal = StatusUpdate {
+            status: Status::Dropped,
+            ..update
+        };
+        subscribers.forward(&[terminal.clone()]);
+        drop(subscribers);
+        assert_eq!(stream.next().await, Some(terminal));
+        assert_eq!(stream.next().await, None);
+    }
 }
@@ -0,0 +1,274 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Estimates of what compaction could reclaim under a proposed read policy.
+//!
+//! Advancing the since of a collection allows persist to advance the times of the updates that
+//! are not beyond the new since to it, and to consolidate them. Without reading the updates, the
+//! controller can't know how far they consolidate, so the estimates only rely on the time ranges
+//! and sizes of the batches in the collection's shard: the bytes of a batch are attributed
+//! evenly to the times it covers, and every time in the range between the current and the
+//! proposed since is assumed to hold updates, up to the number of updates in the batch.
+
+use std::cmp;
+
+use mz_persist_client::stats::BatchStats;
+use mz_storage_client::controller::{CollectionState, CompactionEstimate};
+use mz_storage_types::read_policy::ReadPolicy;
+use timely::progress::{Antichain, Timestamp};
+use timely::PartialOrder;
+
+/// Returns the since `collection` would have if its read policy was `policy`.
+///
+/// Like when the policy is assigned, the implied capability of the collection is not allowed to
+/// regress, and the read holds of others remain in place.
+pub(crate) fn proposed_since<T: Timestamp>(
+    collection: &CollectionState<T>,
+    policy: &ReadPolicy<T>,
+) -> Antichain<T> {
+    let implied_capability = policy.frontier(collection.write_frontier.borrow());
+    if !PartialOrder::less_equal(&collection.implied_capability, &implied_capability) {
+        return collection.read_capabilities.frontier().to_owned();
+    }
+    let mut read_capabilities = collection.read_capabilities.clone();
+    read_capabilities.update_iter(
+        collection
+            .implied_capability
+            .iter()
+            .map(|time| (time.clone(), -1))
+            .chain(implied_capability.iter().map(|time| (time.clone(), 1))),
+    );
+    read_capabilities.frontier().to_owned()
+}
+
+/// Estimates what compaction could reclaim in a shard with the given `batches` if its since
+/// advanced from `since` to `proposed_since`.
+///
+/// The timestamps must be totally ordered, so that frontiers hold at most one time.
+pub(crate) fn estimate_compaction<T>(
+    since: Antichain<T>,
+    proposed_since: Antichain<T>,
+    batches: &[BatchStats<T>],
+) -> CompactionEstimate<T>
+where
+    T: Timestamp + Into<mz_repr::Timestamp>,
+{
+    let to_u64 = |time: &T| u64::from(time.clone().into());
+
+    let mut estimated_reclaimable_bytes = 0;
+    let mut estimated_collapsed_timestamps = 0;
+    for batch in batches.iter().filter(|batch| batch.num_updates > 0) {
+        // The times in the batch that are still distinct start at the latest of its lower and
+        // its since, and compaction may already advance them to the current since.
+        let (Some(lower), Some(batch_since), Some(current_since)) = (
+            batch.lower.as_option(),
+            batch.since.as_option(),
+            since.as_option(),
+        ) else {
+            continue;
+        };
+        let distinct_start = cmp::max(lower, batch_since);
+        let start = cmp::max(distinct_start, current_since);
+        // An empty frontier bounds nothing, but a shard has no updates beyond its upper.
+        let end = match (batch.upper.as_option(), proposed_since.as_option()) {
+            (Some(upper), Some(proposed)) => cmp::min(upper, proposed),
+            (Some(upper), None) => upper,
+            (None, Some(proposed)) => proposed,
+            (None, None) => continue,
+        };
+        if end <= start {
+            continue;
+        }
+
+        let collapsed = to_u64(end) - to_u64(start);
+        let num_updates = u64::try_from(batch.num_updates).expect("must fit");
+        estimated_collapsed_timestamps += cmp::min(collapsed, num_updates);
+
+        let encoded_size_bytes = u64::try_from(batch.encoded_size_bytes).expect("must fit");
+        let batch_span = batch
+            .upper
+            .as_option()
+            .map(|upper| to_u64(upper) - to_u64(distinct_start));
+        estimated_reclaimable_bytes += match batch_span {
+            Some(span) if collapsed < span => {
+                let bytes =
+                    u128::from(encoded_size_bytes) * u128::from(collapsed) / u128::from(span);
+                u64::try_from(bytes).expect("smaller than encoded_size_bytes")
+            }
+            _ => encoded_size_bytes,
+        };
+    }
+
+    CompactionEstimate {
+        since,
+        proposed_since,
+        estimated_reclaimable_bytes,
+        estimated_collapsed_timestamps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use mz_build_info::DUMMY_BUILD_INFO;
+    use mz_ore::metrics::MetricsRegistry;
+    use mz_ore::now::SYSTEM_TIME;
+    use mz_persist_client::cache::PersistClientCache;
+    use mz_persist_client::cfg::PersistConfig;
+    use mz_persist_client::rpc::PubSubClientConnection;
+    use mz_persist_client::{Diagnostics, PersistLocation, ShardId};
+    use mz_persist_types::codec_impls::UnitSchema;
+    use mz_repr::{Datum, RelationDesc, Row, ScalarType, Timestamp};
+    use mz_storage_types::sources::SourceData;
+
+    use super::*;
+
+    fn batch(
+        lower: u64,
+        upper: u64,
+        since: u64,
+        bytes: usize,
+        updates: usize,
+    ) -> BatchStats<Timestamp> {
+        BatchStats {
+            lower: Antichain::from_elem(lower.into()),
+            upper: Antichain::from_elem(upper.into()),
+            since: Antichain::from_elem(since.into()),
+            encoded_size_bytes: bytes,
+            num_updates: updates,
+        }
+    }
+
+    fn estimate(since: u64, proposed_since: u64, batches: &[BatchStats<Timestamp>]) -> (u64, u64) {
+        let estimate = estimate_compaction(
+            Antichain::from_elem(since.into()),
+            Antichain::from_elem(proposed_since.into()),
+            batches,
+        );
+        (
+            estimate.estimated_reclaimable_bytes,
+            estimate.estimated_collapsed_timestamps,
+        )
+    }
+
+    #[mz_ore::test]
+    fn test_estimate_known_layout() {
+        let batches = [
+            // Already compacted up to 10.
+            batch(0, 10, 10, 100, 5),
+            batch(10, 20, 0, 1000, 100),
+            batch(20, 30, 0, 500, 3),
+            // Empty batches reclaim nothing.
+            batch(30, 40, 0, 0, 0),
+        ];
+
+        // Keeping the since reclaims nothing.
+        assert_eq!(estimate(10, 10, &batches), (0, 0));
+        // A since that would regress reclaims nothing either.
+        assert_eq!(estimate(10, 5, &batches), (0, 0));
+        // Half of the second batch.
+        assert_eq!(estimate(10, 15, &batches), (500, 5));
+        // All of the second batch and a fifth of the third, which holds fewer updates than times.
+        assert_eq!(estimate(10, 22, &batches), (1100, 12));
+        // Everything that hasn't been compacted yet.
+        assert_eq!(estimate(10, 40, &batches), (1500, 13));
+        // Times below the current since may be compacted already, and aren't attributed to the
+        // proposed since.
+        assert_eq!(estimate(15, 20, &batches), (500, 5));
+    }
+
+    #[mz_ore::test]
+    fn test_estimate_empty_frontiers() {
+        let batches = [batch(0, 10, 0, 100, 20)];
+        let empty = |since: Antichain<Timestamp>, proposed_since: Antichain<Timestamp>| {
+            let estimate = estimate_compaction(since, proposed_since, &batches);
+            (
+                estimate.estimated_reclaimable_bytes,
+                estimate.estimated_collapsed_timestamps,
+            )
+        };
+
+        // A collection that can be compacted completely reclaims what its shard holds.
+        assert_eq!(
+            empty(Antichain::from_elem(0.into()), Antichain::new()),
+            (100, 10)
+        );
+        // A collection that was compacted completely has nothing left to reclaim.
+        assert_eq!(empty(Antichain::new(), Antichain::new()), (0, 0));
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: integer-to-pointer casts and `ptr::from_exposed_addr`
+    async fn test_estimate_persist_shard() {
+        let client = PersistClientCache::new(
+            PersistConfig::new_default_configs(&DUMMY_BUILD_INFO, SYSTEM_TIME.clone()),
+            &MetricsRegistry::new(),
+            |_, _| PubSubClientConnection::noop(),
+        )
+        .open(PersistLocation {
+            blob_uri: "mem://".to_owned(),
+            consensus_uri: "mem://".to_owned(),
+        })
+        .await
+        .unwrap();
+        let shard_id = ShardId::new();
+        let mut write_handle = client
+            .open_writer::<SourceData, (), Timestamp, i64>(
+                shard_id,
+                Arc::new(
+                    RelationDesc::empty().with_column("time", ScalarType::UInt64.nullable(false)),
+                ),
+                Arc::new(UnitSchema),
+                Diagnostics::for_tests(),
+            )
+            .await
+            .unwrap();
+
+        // Three batches that each hold one update at each of ten times.
+        for lower in [0u64, 10, 20] {
+            let updates: Vec<_> = (lower..lower + 10)
+                .map(|time| {
+                    let row = Row::pack_slice(&[Datum::UInt64(time)]);
+                    ((SourceData(Ok(row)), ()), Timestamp::from(time), 1i64)
+                })
+                .collect();
+            let () = write_handle
+                .compare_and_append(
+                    &updates,
+                    Antichain::from_elem(lower.into()),
+                    Antichain::from_elem((lower + 10).into()),
+                )
+                .await
+                .unwrap()
+                .unwrap();
+        }
+
+        let batches = client.batch_stats::<Timestamp>(&shard_id).await.unwrap();
+        let total_bytes: usize = batches.iter().map(|b| b.encoded_size_bytes).sum();
+        let total_bytes = u64::try_from(total_bytes).unwrap();
+        assert!(total_bytes > 0);
+
+        // Persist may have merged some of the batches in the meantime, so the estimates are only
+        // known within bounds.
+        assert_eq!(estimate(0, 0, &batches), (0, 0));
+
+        let (bytes, timestamps) = estimate(0, 20, &batches);
+        assert!(bytes > 0 && bytes < total_bytes, "{bytes} of {total_bytes}");
+        assert_eq!(timestamps, 20);
+
+        let (bytes, timestamps) = estimate(0, 30, &batches);
+        assert_eq!(bytes, total_bytes);
+        assert_eq!(timestamps, 30);
+
+        let (bytes, timestamps) = estimate(20, 30, &batches);
+        assert!(bytes > 0 && bytes < total_bytes, "{bytes} of {total_bytes}");
+        assert_eq!(timestamps, 10);
+    }
+}
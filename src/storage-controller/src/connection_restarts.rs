@@ -0,0 +1,219 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Bookkeeping for restarting ingestions to apply a change to a connection they depend on.
+//!
+//! Restarting an ingestion can be expensive, e.g. when it has to re-snapshot its upstream, so
+//! rather than restarting all ingestions that depend on an altered connection at once, restarts
+//! are queued and only a limited number of them is in flight at any time. A restart is complete
+//! once the restarted dataflow has reported `starting` and then any other status.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use mz_repr::GlobalId;
+use mz_storage_client::client::Status;
+use tokio::sync::oneshot;
+
+#[derive(Debug)]
+pub(super) struct ConnectionRestarts {
+    /// The maximum number of restarts in flight at a time.
+    max_concurrency: usize,
+    /// Ingestions whose restart has not been issued yet, in the order they were requested.
+    queued: VecDeque<GlobalId>,
+    /// Ingestions whose restart has been issued, and whether the restarted dataflow has reported
+    /// `starting` yet.
+    in_flight: BTreeMap<GlobalId, bool>,
+    /// Senders to notify once the ingestions they wait on have restarted.
+    waiters: Vec<(BTreeSet<GlobalId>, oneshot::Sender<()>)>,
+}
+
+impl ConnectionRestarts {
+    pub(super) fn new() -> Self {
+        ConnectionRestarts {
+            max_concurrency: 1,
+            queued: VecDeque::new(),
+            in_flight: BTreeMap::new(),
+            waiters: Vec::new(),
+        }
+    }
+
+    /// Queues restarts of the given ingestions, allowing at most `max_concurrency` restarts to
+    /// be in flight from now on. The returned receiver resolves once all of them have restarted.
+    ///
+    /// Ingestions whose restart is already in flight are restarted again, as the restart in
+    /// flight may not observe the latest change.
+    pub(super) fn enqueue(
+        &mut self,
+        ids: BTreeSet<GlobalId>,
+        max_concurrency: usize,
+    ) -> oneshot::Receiver<()> {
+        self.max_concurrency = max_concurrency.max(1);
+        for id in &ids {
+            if !self.queued.contains(id) {
+                self.queued.push_back(*id);
+            }
+        }
+
+        let (tx, rx) = oneshot::channel();
+        if ids.is_empty() {
+            let _ = tx.send(());
+        } else {
+            self.waiters.push((ids, tx));
+        }
+        rx
+    }
+
+    /// Returns the ingestions whose restart should be issued now, marking them in flight.
+    pub(super) fn issue(&mut self) -> Vec<GlobalId> {
+        let mut issued = Vec::new();
+        while self.in_flight.len() < self.max_concurrency {
+            // A restart that is still in flight must complete before it can be re-issued.
+            let Some(pos) = self
+                .queued
+                .iter()
+                .position(|id| !self.in_flight.contains_key(id))
+            else {
+                break;
+            };
+            let id = self.queued.remove(pos).expect("known to exist");
+            self.in_flight.insert(id, false);
+            issued.push(id);
+        }
+        issued
+    }
+
+    /// Observes a status reported for `id`, completing its restart if the restarted dataflow
+    /// has come up.
+    pub(super) fn observe_status(&mut self, id: GlobalId, status: Status) {
+        let Some(&started) = self.in_flight.get(&id) else {
+            return;
+        };
+        match status {
+            Status::Starting => {
+                self.in_flight.insert(id, true);
+            }
+            // The ingestion won't run again, so neither will its restart.
+            Status::Ceased | Status::Dropped => self.remove(id),
+            _ if started => self.complete(id),
+            _ => {}
+        }
+    }
+
+    /// Returns the ingestions whose restart is in flight.
+    pub(super) fn in_flight(&self) -> impl Iterator<Item = GlobalId> + '_ {
+        self.in_flight.keys().copied()
+    }
+
+    /// Forgets about the restart of `id`, e.g. because the ingestion was dropped.
+    pub(super) fn remove(&mut self, id: GlobalId) {
+        self.queued.retain(|queued| *queued != id);
+        self.complete(id);
+    }
+
+    /// Completes the restart of `id`, notifying those that only waited on it.
+    pub(super) fn complete(&mut self, id: GlobalId) {
+        self.in_flight.remove(&id);
+        // The ingestion is restarted again, so those waiting on it keep waiting.
+        if self.queued.contains(&id) {
+            return;
+        }
+
+        let (done, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.waiters)
+            .into_iter()
+            .map(|(mut ids, tx)| {
+                ids.remove(&id);
+                (ids, tx)
+            })
+            .partition(|(ids, _)| ids.is_empty());
+        self.waiters = waiting;
+        for (_, tx) in done {
+            let _ = tx.send(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_connection_restarts_are_staggered() {
+        let mut restarts = ConnectionRestarts::new();
+        let (a, b, c) = (GlobalId::User(1), GlobalId::User(2), GlobalId::User(3));
+
+        let mut done = restarts.enqueue(BTreeSet::from([a, b, c]), 2);
+        assert_eq!(restarts.issue(), vec![a, b]);
+        assert_eq!(restarts.issue(), vec![]);
+
+        // Statuses of the dataflow that is being replaced don't complete the restart.
+        restarts.observe_status(a, Status::Running);
+        assert_eq!(restarts.issue(), vec![]);
+
+        restarts.observe_status(a, Status::Starting);
+        restarts.observe_status(a, Status::Running);
+        assert_eq!(restarts.issue(), vec![c]);
+        assert!(done.try_recv().is_err());
+
+        // Restarts complete even if the restarted dataflow stalls.
+        restarts.observe_status(b, Status::Starting);
+        restarts.observe_status(b, Status::Stalled);
+        restarts.remove(c);
+        assert_eq!(done.try_recv(), Ok(()));
+    }
+
+    #[mz_ore::test]
+    fn test_connection_restarts_reissued() {
+        let mut restarts = ConnectionRestarts::new();
+        let a = GlobalId::User(1);
+
+        let mut first = restarts.enqueue(BTreeSet::from([a]), 1);
+        assert_eq!(restarts.issue(), vec![a]);
+
+        // A second change while the first restart is in flight restarts the ingestion again.
+        let mut second = restarts.enqueue(BTreeSet::from([a]), 1);
+        assert_eq!(restarts.issue(), vec![]);
+        restarts.observe_status(a, Status::Starting);
+        restarts.observe_status(a, Status::Running);
+        assert!(first.try_recv().is_err());
+        assert_eq!(restarts.issue(), vec![a]);
+
+        restarts.observe_status(a, Status::Starting);
+        restarts.observe_status(a, Status::Running);
+        assert_eq!(first.try_recv(), Ok(()));
+        assert_eq!(second.try_recv(), Ok(()));
+    }
+
+    #[mz_ore::test]
+    fn test_connection_restarts_cleared_on_exit() {
+        let mut restarts = ConnectionRestarts::new();
+        let (a, b) = (GlobalId::User(1), GlobalId::User(2));
+
+        // An ingestion that ceases before its restarted dataflow comes up frees its slot.
+        let mut done = restarts.enqueue(BTreeSet::from([a, b]), 1);
+        assert_eq!(restarts.issue(), vec![a]);
+        restarts.observe_status(a, Status::Ceased);
+        assert_eq!(restarts.in_flight().collect::<Vec<_>>(), vec![]);
+        assert_eq!(restarts.issue(), vec![b]);
+
+        // So does an ingestion that is dropped.
+        restarts.remove(b);
+        assert_eq!(restarts.in_flight().collect::<Vec<_>>(), vec![]);
+        assert_eq!(done.try_recv(), Ok(()));
+
+        // And an ingestion whose replica goes away.
+        let mut done = restarts.enqueue(BTreeSet::from([a]), 1);
+        assert_eq!(restarts.issue(), vec![a]);
+        restarts.complete(a);
+        assert_eq!(done.try_recv(), Ok(()));
+
+        // Later restarts of all of them are issued again.
+        let _done = restarts.enqueue(BTreeSet::from([a, b]), 2);
+        assert_eq!(restarts.issue(), vec![a, b]);
+    }
+}
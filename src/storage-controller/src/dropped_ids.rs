@@ -0,0 +1,91 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Incremental cleanup of dropped collections.
+//!
+//! Dropping a source with many subsources makes its replica report all of them in a single
+//! `DroppedIds` response. Cleaning up after a collection involves the stash, so rather than
+//! cleaning up after all of them at once, the controller queues the IDs and cleans up after at
+//! most `storage_dropped_ids_batch_size` of them in each call to `StorageController::process`.
+//! This leaves room for other work in between. The cleanup of each batch is completed within the
+//! call that takes it from the queue.
+
+use std::collections::BTreeSet;
+
+use mz_repr::GlobalId;
+
+/// IDs of dropped collections that the controller has yet to clean up after.
+#[derive(Debug, Default)]
+pub(crate) struct PendingDrops {
+    ids: BTreeSet<GlobalId>,
+}
+
+impl PendingDrops {
+    /// Queues the cleanup after the collections `ids`.
+    pub(crate) fn extend(&mut self, ids: impl IntoIterator<Item = GlobalId>) {
+        self.ids.extend(ids);
+    }
+
+    /// Reports whether there are no collections left to clean up after.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Removes the next at most `batch_size` IDs from the queue. At least one ID is returned if
+    /// any are queued, even if `batch_size` is zero.
+    pub(crate) fn next_batch(&mut self, batch_size: usize) -> BTreeSet<GlobalId> {
+        let batch_size = std::cmp::max(batch_size, 1);
+        let mut batch = BTreeSet::new();
+        while batch.len() < batch_size {
+            match self.ids.pop_first() {
+                Some(id) => batch.insert(id),
+                None => break,
+            };
+        }
+        batch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_large_drop_is_batched() {
+        let mut pending = PendingDrops::default();
+        let dropped: BTreeSet<_> = (0..50_000).map(GlobalId::User).collect();
+        pending.extend(dropped.iter().copied());
+        // A later response may overlap with the queued IDs.
+        pending.extend([GlobalId::User(0), GlobalId::User(50_000)]);
+
+        let mut cleaned = BTreeSet::new();
+        let mut turns = 0;
+        while !pending.is_empty() {
+            let batch = pending.next_batch(1000);
+            assert!(!batch.is_empty());
+            assert!(batch.len() <= 1000, "batch of {} ids", batch.len());
+            assert!(cleaned.is_disjoint(&batch));
+            cleaned.extend(batch);
+            turns += 1;
+        }
+        assert_eq!(turns, 51);
+        assert_eq!(cleaned.len(), 50_001);
+        assert!(cleaned.is_superset(&dropped));
+    }
+
+    #[mz_ore::test]
+    fn test_zero_batch_size_makes_progress() {
+        let mut pending = PendingDrops::default();
+        pending.extend([GlobalId::User(1), GlobalId::User(2)]);
+        assert_eq!(pending.next_batch(0), BTreeSet::from([GlobalId::User(1)]));
+        assert_eq!(pending.next_batch(0), BTreeSet::from([GlobalId::User(2)]));
+        assert!(pending.is_empty());
+        assert!(pending.next_batch(10).is_empty());
+    }
+}
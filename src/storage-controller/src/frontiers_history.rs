@@ -0,0 +1,550 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Recording and downsampling of the write frontiers history.
+//!
+//! Write frontiers are recorded at full resolution whenever they change. Once a
+//! recording is older than the configured full-resolution window, it is rolled
+//! up with the other recordings of the same collection in the same minute into
+//! a single row holding the smallest and largest write frontier observed in
+//! that minute. Per-minute rollups are in turn rolled up per hour after
+//! [`MINUTE_RESOLUTION_WINDOW`], and dropped entirely after [`RETENTION`].
+//!
+//! Downsampling is computed from a snapshot of the collection alone, and the
+//! rollup of a bucket absorbs any rollup already present for that bucket. This
+//! makes it idempotent: re-running it, e.g. after a restart, never duplicates
+//! rows.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use differential_dataflow::consolidation::consolidate;
+use differential_dataflow::lattice::Lattice;
+use mz_ore::now::EpochMillis;
+use mz_persist_client::cache::PersistClientCache;
+use mz_persist_client::Diagnostics;
+use mz_persist_types::codec_impls::UnitSchema;
+use mz_persist_types::Codec64;
+use mz_repr::{Datum, Diff, GlobalId, Row, Timestamp, TimestampManipulation};
+use mz_storage_types::controller::CollectionMetadata;
+use mz_storage_types::sources::SourceData;
+use timely::progress::Antichain;
+use tokio::sync::mpsc;
+
+use crate::collection_mgmt::CollectionManager;
+
+/// How long per-minute rollups are retained before being rolled up per hour.
+pub(crate) const MINUTE_RESOLUTION_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How long per-hour rollups are retained.
+pub(crate) const RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// How often the history is downsampled.
+pub(crate) const DOWNSAMPLE_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// The resolution of a row in the frontiers history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Resolution {
+    /// A single recording of a write frontier.
+    Full,
+    /// The extremes of the recordings within a minute.
+    Minute,
+    /// The extremes of the recordings within an hour.
+    Hour,
+}
+
+impl Resolution {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Resolution::Full => "full",
+            Resolution::Minute => "minute",
+            Resolution::Hour => "hour",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "full" => Some(Resolution::Full),
+            "minute" => Some(Resolution::Minute),
+            "hour" => Some(Resolution::Hour),
+            _ => None,
+        }
+    }
+
+    /// The width of the buckets of this resolution, in milliseconds.
+    fn width(&self) -> EpochMillis {
+        match self {
+            Resolution::Full => 1,
+            Resolution::Minute => 60 * 1000,
+            Resolution::Hour => 60 * 60 * 1000,
+        }
+    }
+
+    /// The start of the bucket of this resolution that contains `t`.
+    fn bucket_start(&self, t: EpochMillis) -> EpochMillis {
+        t - t % self.width()
+    }
+
+    /// How long ago, as of `now`, the bucket of this resolution that contains
+    /// `t` ended.
+    fn bucket_age(&self, t: EpochMillis, now: EpochMillis) -> EpochMillis {
+        now.saturating_sub(self.bucket_start(t) + self.width())
+    }
+}
+
+/// A row of the frontiers history.
+///
+/// Write frontiers of `None` denote the empty frontier, which is beyond all
+/// timestamps.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Entry {
+    occurred_at: EpochMillis,
+    object_id: String,
+    resolution: Resolution,
+    min_write_frontier: Option<Timestamp>,
+    max_write_frontier: Option<Timestamp>,
+}
+
+impl Entry {
+    fn from_row(row: &Row) -> Self {
+        let mut datums = row.iter();
+        let mut next = || datums.next().expect("schema has not changed");
+        let occurred_at = next().unwrap_timestamptz().timestamp_millis();
+        let object_id = next().unwrap_str().to_string();
+        let resolution =
+            Resolution::parse(next().unwrap_str()).expect("resolutions must be uncorrupted");
+        let frontier = |datum: Datum| match datum {
+            Datum::Null => None,
+            datum => Some(datum.unwrap_mz_timestamp()),
+        };
+        Entry {
+            occurred_at: EpochMillis::try_from(occurred_at).unwrap_or(0),
+            object_id,
+            resolution,
+            min_write_frontier: frontier(next()),
+            max_write_frontier: frontier(next()),
+        }
+    }
+
+    fn to_row(&self) -> Row {
+        let frontier = |f: Option<Timestamp>| f.map_or(Datum::Null, Datum::MzTimestamp);
+        Row::pack_slice(&[
+            Datum::TimestampTz(
+                mz_ore::now::to_datetime(self.occurred_at)
+                    .try_into()
+                    .expect("must fit"),
+            ),
+            Datum::String(&self.object_id),
+            Datum::String(self.resolution.as_str()),
+            frontier(self.min_write_frontier),
+            frontier(self.max_write_frontier),
+        ])
+    }
+}
+
+/// Packs a full-resolution recording of the `write_frontier` of `object_id`
+/// at `occurred_at`.
+pub(crate) fn pack_recording(
+    occurred_at: EpochMillis,
+    object_id: &str,
+    write_frontier: Option<Timestamp>,
+) -> Row {
+    Entry {
+        occurred_at,
+        object_id: object_id.to_string(),
+        resolution: Resolution::Full,
+        min_write_frontier: write_frontier,
+        max_write_frontier: write_frontier,
+    }
+    .to_row()
+}
+
+/// The resolution a row recorded at `occurred_at` is due for as of `now`, or
+/// `None` if it should no longer be retained.
+///
+/// The decision is made per bucket of the target resolution, so that all rows
+/// of a bucket are rolled up together.
+fn due_resolution(
+    occurred_at: EpochMillis,
+    now: EpochMillis,
+    full_resolution_window: Duration,
+) -> Option<Resolution> {
+    let millis = |d: Duration| EpochMillis::try_from(d.as_millis()).unwrap_or(EpochMillis::MAX);
+    let hour_age = Resolution::Hour.bucket_age(occurred_at, now);
+    if hour_age >= millis(RETENTION) {
+        None
+    } else if hour_age >= millis(MINUTE_RESOLUTION_WINDOW) {
+        Some(Resolution::Hour)
+    } else if Resolution::Minute.bucket_age(occurred_at, now) >= millis(full_resolution_window) {
+        Some(Resolution::Minute)
+    } else {
+        Some(Resolution::Full)
+    }
+}
+
+/// Orders write frontiers, with the empty frontier beyond all others.
+fn frontier_key(f: &Option<Timestamp>) -> (bool, Option<Timestamp>) {
+    (f.is_none(), *f)
+}
+
+/// Returns the updates that downsample the frontiers history as of `now`.
+///
+/// `snapshot` must be consolidated.
+pub(crate) fn downsample(
+    snapshot: Vec<(Row, Diff)>,
+    now: EpochMillis,
+    full_resolution_window: Duration,
+) -> Vec<(Row, Diff)> {
+    let mut updates = Vec::new();
+    let mut buckets: BTreeMap<(String, Resolution, EpochMillis), Vec<(Entry, Diff)>> =
+        BTreeMap::new();
+    for (row, diff) in snapshot {
+        let entry = Entry::from_row(&row);
+        match due_resolution(entry.occurred_at, now, full_resolution_window) {
+            None => updates.push((row, -diff)),
+            // Rows that are already coarser than due, e.g. because the window
+            // was extended, are left alone. Rows at the due resolution join
+            // their bucket, so that rollups absorb the rows rolled up before.
+            Some(due) if due < entry.resolution || due == Resolution::Full => {}
+            Some(due) => {
+                let key = (
+                    entry.object_id.clone(),
+                    due,
+                    due.bucket_start(entry.occurred_at),
+                );
+                buckets.entry(key).or_default().push((entry, diff));
+            }
+        }
+    }
+
+    for ((object_id, resolution, occurred_at), entries) in buckets {
+        if let [(entry, 1)] = entries.as_slice() {
+            if entry.resolution == resolution {
+                // Already rolled up.
+                continue;
+            }
+        }
+        let min_write_frontier = entries
+            .iter()
+            .map(|(e, _)| e.min_write_frontier)
+            .min_by_key(frontier_key)
+            .expect("buckets are not empty");
+        let max_write_frontier = entries
+            .iter()
+            .map(|(e, _)| e.max_write_frontier)
+            .max_by_key(frontier_key)
+            .expect("buckets are not empty");
+        for (entry, diff) in &entries {
+            updates.push((entry.to_row(), -diff));
+        }
+        let rollup = Entry {
+            occurred_at,
+            object_id,
+            resolution,
+            min_write_frontier,
+            max_write_frontier,
+        };
+        updates.push((rollup.to_row(), 1));
+    }
+
+    updates
+}
+
+/// A request to downsample the frontiers history.
+#[derive(Debug)]
+pub(crate) struct DownsampleRequest<T> {
+    /// The time as of which to downsample.
+    pub now: EpochMillis,
+    /// The timestamp at which to snapshot the collection, which must be readable.
+    pub as_of: T,
+    /// How long recordings are kept at full resolution.
+    pub full_resolution_window: Duration,
+}
+
+/// Spawns a task that downsamples the frontiers history collection `id` whenever a
+/// [`DownsampleRequest`] is sent to the returned sender. The task shuts down once the sender is
+/// dropped.
+///
+/// The snapshot and its rollups are computed off the controller's thread, as the history can be
+/// large. The rollups are written through `collection_mgmt`, like other appends to managed
+/// collections.
+pub(crate) fn spawn_downsampler<T>(
+    id: GlobalId,
+    metadata: CollectionMetadata,
+    persist: Arc<PersistClientCache>,
+    collection_mgmt: CollectionManager<T>,
+) -> mpsc::UnboundedSender<DownsampleRequest<T>>
+where
+    T: timely::progress::Timestamp + Lattice + Codec64 + TimestampManipulation,
+{
+    let (tx, rx) = mpsc::unbounded_channel();
+    mz_ore::task::spawn(|| "frontiers_history_downsampler", async move {
+        let snapshot = |as_of| {
+            let metadata = metadata.clone();
+            let persist = Arc::clone(&persist);
+            async move { snapshot_collection(id, &metadata, &persist, as_of).await }
+        };
+        let append = |updates| {
+            let collection_mgmt = collection_mgmt.clone();
+            async move { collection_mgmt.append_to_collection(id, updates).await }
+        };
+        run_downsampler(rx, snapshot, append).await;
+
+        tracing::info!("shutting down frontiers history downsampler task");
+    });
+    tx
+}
+
+/// Serves the downsample requests received on `rx`, until the sender is dropped.
+///
+/// A request whose snapshot fails, e.g. because the collection was compacted beyond the
+/// requested timestamp in the meantime, is skipped. The next request downsamples the rows it
+/// would have.
+async fn run_downsampler<T, S, SFut, A, AFut>(
+    mut rx: mpsc::UnboundedReceiver<DownsampleRequest<T>>,
+    mut snapshot: S,
+    mut append: A,
+) where
+    S: FnMut(T) -> SFut,
+    SFut: Future<Output = Result<Vec<(Row, Diff)>, anyhow::Error>>,
+    A: FnMut(Vec<(Row, Diff)>) -> AFut,
+    AFut: Future<Output = ()>,
+{
+    while let Some(request) = rx.recv().await {
+        let mut rows = match snapshot(request.as_of).await {
+            Ok(rows) => rows,
+            Err(e) => {
+                tracing::warn!("skipping downsampling of the frontiers history: {e:#}");
+                continue;
+            }
+        };
+        consolidate(&mut rows);
+        let updates = downsample(rows, request.now, request.full_resolution_window);
+        append(updates).await;
+    }
+}
+
+/// Reads the contents of the collection `id`, stored in the shard of `metadata`, as of `as_of`.
+async fn snapshot_collection<T>(
+    id: GlobalId,
+    metadata: &CollectionMetadata,
+    persist: &PersistClientCache,
+    as_of: T,
+) -> Result<Vec<(Row, Diff)>, anyhow::Error>
+where
+    T: timely::progress::Timestamp + Lattice + Codec64,
+{
+    let client = persist.open(metadata.persist_location.clone()).await?;
+    let mut read_handle = client
+        .open_leased_reader::<SourceData, (), T, Diff>(
+            metadata.data_shard,
+            Arc::new(metadata.relation_desc.clone()),
+            Arc::new(UnitSchema),
+            Diagnostics {
+                shard_name: id.to_string(),
+                handle_purpose: format!("downsample frontiers history {id}"),
+            },
+        )
+        .await
+        .map_err(|e| anyhow!("opening reader: {e}"))?;
+    let contents = read_handle
+        .snapshot_and_fetch(Antichain::from_elem(as_of))
+        .await;
+    read_handle.expire().await;
+    let contents = contents.map_err(|since| anyhow!("snapshot before since {:?}", since.0))?;
+
+    let mut rows = Vec::with_capacity(contents.len());
+    for ((data, _), _, diff) in contents {
+        let row = data
+            .map_err(|e| anyhow!("decoding row: {e}"))?
+            .0
+            .map_err(|e| anyhow!("collection contains an error: {e}"))?;
+        rows.push((row, diff));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: EpochMillis = 60 * 1000;
+    const HOUR: EpochMillis = 60 * MINUTE;
+    const DAY: EpochMillis = 24 * HOUR;
+    const WINDOW: Duration = Duration::from_secs(60 * 60);
+
+    fn recording(occurred_at: EpochMillis, write_frontier: Option<u64>) -> (Row, Diff) {
+        (
+            pack_recording(occurred_at, "u1", write_frontier.map(Timestamp::from)),
+            1,
+        )
+    }
+
+    fn entries(rows: &[(Row, Diff)]) -> Vec<(EpochMillis, Resolution, Option<u64>, Option<u64>)> {
+        let mut entries: Vec<_> = rows
+            .iter()
+            .map(|(row, diff)| {
+                assert_eq!(*diff, 1);
+                let e = Entry::from_row(row);
+                (
+                    e.occurred_at,
+                    e.resolution,
+                    e.min_write_frontier.map(u64::from),
+                    e.max_write_frontier.map(u64::from),
+                )
+            })
+            .collect();
+        entries.sort();
+        entries
+    }
+
+    /// Downsamples `history` as of `now`, returning the consolidated result.
+    fn apply(history: &[(Row, Diff)], now: EpochMillis) -> Vec<(Row, Diff)> {
+        let mut result = history.to_vec();
+        result.extend(downsample(history.to_vec(), now, WINDOW));
+        consolidate(&mut result);
+        result
+    }
+
+    #[mz_ore::test]
+    fn test_minute_buckets() {
+        let now = 10 * DAY;
+        let old = now - 2 * HOUR;
+        let history = vec![
+            recording(old, Some(5)),
+            recording(old + MINUTE - 1, Some(9)),
+            recording(old + MINUTE, Some(10)),
+            recording(old + MINUTE + 1, None),
+        ];
+        let result = apply(&history, now);
+        assert_eq!(
+            entries(&result),
+            vec![
+                (old, Resolution::Minute, Some(5), Some(9)),
+                (old + MINUTE, Resolution::Minute, Some(10), None),
+            ]
+        );
+
+        // Re-running is a no-op.
+        assert_eq!(downsample(result, now, WINDOW), vec![]);
+    }
+
+    #[mz_ore::test]
+    fn test_recent_data_stays_full_resolution() {
+        let now = 10 * DAY;
+        // The minute this falls into ends less than `WINDOW` before `now`.
+        let recent = now - HOUR + 1;
+        let history = vec![recording(recent, Some(1)), recording(now, Some(2))];
+        assert_eq!(downsample(history, now, WINDOW), vec![]);
+    }
+
+    #[mz_ore::test]
+    fn test_rollups_absorb_earlier_rollups() {
+        let now = 10 * DAY;
+        let old = now - 2 * HOUR;
+        let first = apply(&[recording(old, Some(5))], now);
+        // A recording for the same minute bucket that was not part of the
+        // first run, e.g. because it was written concurrently with it.
+        let mut history = first;
+        history.push(recording(old + 1, Some(3)));
+        let result = apply(&history, now);
+        assert_eq!(
+            entries(&result),
+            vec![(old, Resolution::Minute, Some(3), Some(5))]
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_hour_buckets_and_retention() {
+        let now = 40 * DAY;
+        let hour = now - 2 * DAY;
+        let expired = now - 31 * DAY;
+        let history = vec![
+            recording(expired, Some(1)),
+            recording(hour, Some(7)),
+            recording(hour + 30 * MINUTE, Some(8)),
+            recording(hour + HOUR, Some(9)),
+        ];
+        let result = apply(&history, now);
+        assert_eq!(
+            entries(&result),
+            vec![
+                (hour, Resolution::Hour, Some(7), Some(8)),
+                (hour + HOUR, Resolution::Hour, Some(9), Some(9)),
+            ]
+        );
+
+        // Per-minute rollups graduate to per-hour rollups as they age.
+        let minute = apply(&[recording(hour, Some(7))], hour + 2 * HOUR);
+        assert_eq!(
+            entries(&minute),
+            vec![(hour, Resolution::Minute, Some(7), Some(7))]
+        );
+        let mut history = minute;
+        history.push(recording(hour + 30 * MINUTE, Some(8)));
+        assert_eq!(
+            entries(&apply(&history, now)),
+            vec![(hour, Resolution::Hour, Some(7), Some(8))]
+        );
+        assert_eq!(downsample(apply(&history, now), now, WINDOW), vec![]);
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `epoll_wait` on OS `linux`
+    async fn test_downsampler_skips_failed_snapshots() {
+        let now = 3 * DAY;
+        let history = vec![
+            recording(now - 2 * HOUR, Some(1)),
+            recording(now - 2 * HOUR + MINUTE, Some(2)),
+        ];
+        let (request_tx, request_rx) = mpsc::unbounded_channel();
+        let (appended_tx, mut appended_rx) = mpsc::unbounded_channel();
+        let snapshot = {
+            let history = history.clone();
+            move |as_of: Timestamp| {
+                let history = history.clone();
+                async move {
+                    if as_of == Timestamp::from(1u64) {
+                        Err(anyhow!("snapshot before since"))
+                    } else {
+                        Ok(history)
+                    }
+                }
+            }
+        };
+        let append = move |updates| {
+            let _ = appended_tx.send(updates);
+            async {}
+        };
+        let downsampler = mz_ore::task::spawn(
+            || "test_downsampler",
+            run_downsampler(request_rx, snapshot, append),
+        );
+
+        let request = |as_of: u64| DownsampleRequest {
+            now,
+            as_of: Timestamp::from(as_of),
+            full_resolution_window: WINDOW,
+        };
+        // A failed snapshot doesn't stop the downsampler, the next request does the work.
+        request_tx.send(request(1)).expect("downsampler running");
+        request_tx.send(request(2)).expect("downsampler running");
+        let updates = appended_rx.recv().await.expect("downsampler running");
+        assert_eq!(updates, downsample(history.clone(), now, WINDOW));
+        assert!(appended_rx.try_recv().is_err());
+
+        // Dropping the sender shuts the downsampler down.
+        drop(request_tx);
+        downsampler.await.expect("downsampler succeeds");
+        assert!(appended_rx.recv().await.is_none());
+    }
+}
@@ -9,7 +9,6 @@
 
 //! Implementation of the storage controller trait.
 
-use std::any::Any;
 use std::cmp::Reverse;
 use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
 use std::fmt::Debug;
@@ -27,6 +26,7 @@ use futures::FutureExt;
 use itertools::Itertools;
 use mz_build_info::BuildInfo;
 use mz_cluster_client::client::ClusterReplicaLocation;
+use mz_cluster_client::command_log::CommandRecorder;
 use mz_cluster_client::ReplicaId;
 
 use mz_ore::metrics::MetricsRegistry;
@@ -49,11 +49,13 @@ use mz_stash::{self, AppendBatch, StashFactory, TypedCollection};
 use mz_stash_types::metrics::Metrics as StashMetrics;
 use mz_storage_client::client::{
     ProtoStorageCommand, ProtoStorageResponse, RunIngestionCommand, RunSinkCommand, Status,
-    StatusUpdate, StorageCommand, StorageResponse, TimestamplessUpdate,
+    StatusAttribution, StatusOrigin, StatusUpdate, StorageCommand, StorageResponse,
+    TimestamplessUpdate,
 };
 use mz_storage_client::controller::{
-    CollectionDescription, CollectionState, DataSource, DataSourceOther, ExportDescription,
-    ExportState, IntrospectionType, MonotonicAppender, Response, SnapshotCursor, StorageController,
+    CollectionDescription, CollectionNames, CollectionState, CompactionEstimate, DataSource,
+    DataSourceOther, ExportDescription, ExportState, IntrospectionType, MonotonicAppender,
+    Response, SnapshotCursor, StashConnectionStatus, StorageController,
 };
 use mz_storage_client::metrics::StorageControllerMetrics;
 use mz_storage_client::statistics::{SinkStatisticsUpdate, SourceStatisticsUpdate};
@@ -67,11 +69,14 @@ use mz_storage_types::instances::StorageInstanceId;
 use mz_storage_types::parameters::StorageParameters;
 use mz_storage_types::read_policy::ReadPolicy;
 use mz_storage_types::sinks::{StorageSinkConnection, StorageSinkDesc};
-use mz_storage_types::sources::{IngestionDescription, SourceData, SourceExport};
+use mz_storage_types::sources::{
+    GenericSourceConnection, IngestionDescription, SourceData, SourceExport,
+};
 use mz_storage_types::AlterCompatible;
 use timely::order::{PartialOrder, TotalOrder};
 use timely::progress::{Antichain, ChangeBatch, Timestamp};
 use tokio::sync::oneshot;
+use tokio_stream::wrappers::UnboundedReceiverStream;
 use tokio_stream::StreamMap;
 use tracing::{debug, info, instrument, warn};
 
@@ -80,10 +85,16 @@ use crate::command_wals::ProtoShardId;
 use crate::persist_handles::SnapshotStatsAsOf;
 use crate::rehydration::RehydratingStorageClient;
 mod collection_mgmt;
+mod collection_state;
 mod collection_status;
 mod command_wals;
+mod compaction_estimate;
+mod connection_restarts;
+mod dropped_ids;
+mod frontiers_history;
 mod persist_handles;
 mod rehydration;
+mod snapshot_groups;
 mod statistics;
 
 pub static METADATA_COLLECTION: TypedCollection<proto::GlobalId, proto::DurableCollectionMetadata> =
@@ -92,9 +103,15 @@ pub static METADATA_COLLECTION: TypedCollection<proto::GlobalId, proto::DurableC
 pub static PERSIST_TXNS_SHARD: TypedCollection<(), String> =
     TypedCollection::new("persist-txns-shard");
 
+/// The [`PersistTxnTablesImpl`] that table shards were last operated in, as its
+/// `u64` representation.
+pub static PERSIST_TXN_TABLES: TypedCollection<(), u64> =
+    TypedCollection::new("persist-txn-tables");
+
 pub static ALL_COLLECTIONS: &[&str] = &[
     METADATA_COLLECTION.name(),
     PERSIST_TXNS_SHARD.name(),
+    PERSIST_TXN_TABLES.name(),
     command_wals::SHARD_FINALIZATION.name(),
 ];
 
@@ -173,9 +190,18 @@ pub struct Controller<T: Timestamp + Lattice + Codec64 + From<EpochMillis> + Tim
     txns_init_run: bool,
     txns_metrics: Arc<TxnMetrics>,
     stashed_response: Option<StorageResponse<T>>,
+    /// The storage instance whose client delivered `stashed_response`, or `None` if the
+    /// response was generated by the controller.
+    stashed_response_instance: Option<StorageInstanceId>,
+    /// An event of a snapshot group to process during the next call to
+    /// `StorageController::process`.
+    stashed_group_event: Option<snapshot_groups::GroupEvent>,
     /// Compaction commands to send during the next call to
     /// `StorageController::process`.
     pending_compaction_commands: Vec<PendingCompactionCommand<T>>,
+    /// Dropped collections to clean up after in batches during the next calls to
+    /// `StorageController::process`.
+    pending_drops: dropped_ids::PendingDrops,
 
     /// Interface for managed collections
     pub(crate) collection_manager: collection_mgmt::CollectionManager<T>,
@@ -184,18 +210,15 @@ pub struct Controller<T: Timestamp + Lattice + Codec64 + From<EpochMillis> + Tim
     pub(crate) collection_status_manager: collection_status::CollectionStatusManager<T>,
     /// Tracks which collection is responsible for which [`IntrospectionType`].
     pub(crate) introspection_ids: Arc<Mutex<BTreeMap<IntrospectionType, GlobalId>>>,
-    /// Tokens for tasks that drive updating introspection collections. Dropping
-    /// this will make sure that any tasks (or other resources) will stop when
-    /// needed.
-    // TODO(aljoscha): Should these live somewhere else?
-    introspection_tokens: BTreeMap<GlobalId, Box<dyn Any + Send + Sync>>,
-
-    /// Consolidated metrics updates to periodically write. We do not eagerly initialize this,
-    /// and its contents are entirely driven by `StorageResponse::StatisticsUpdates`'s.
+
+    /// Consolidated metrics updates, which the adapter periodically reconciles its statistics
+    /// collections with. We do not eagerly initialize this, and its contents are entirely driven
+    /// by `StorageResponse::StatisticsUpdates`'s.
     source_statistics:
         Arc<Mutex<BTreeMap<GlobalId, statistics::StatsInitState<SourceStatisticsUpdate>>>>,
-    /// Consolidated metrics updates to periodically write. We do not eagerly initialize this,
-    /// and its contents are entirely driven by `StorageResponse::StatisticsUpdates`'s.
+    /// Consolidated metrics updates, which the adapter periodically reconciles its statistics
+    /// collections with. We do not eagerly initialize this, and its contents are entirely driven
+    /// by `StorageResponse::StatisticsUpdates`'s.
     sink_statistics:
         Arc<Mutex<BTreeMap<GlobalId, statistics::StatsInitState<SinkStatisticsUpdate>>>>,
 
@@ -228,6 +251,25 @@ pub struct Controller<T: Timestamp + Lattice + Codec64 + From<EpochMillis> + Tim
     /// Write frontiers that have been recorded in the `ReplicaFrontiers` collection, kept to be
     /// able to retract old rows.
     recorded_replica_frontiers: BTreeMap<(GlobalId, ReplicaId), Antichain<T>>,
+    /// When the `FrontiersHistory` collection was last downsampled.
+    frontiers_history_downsampled_at: EpochMillis,
+    /// Sends requests to the task that downsamples the `FrontiersHistory` collection, once it
+    /// is registered.
+    frontiers_history_downsampler:
+        Option<tokio::sync::mpsc::UnboundedSender<frontiers_history::DownsampleRequest<T>>>,
+
+    /// The persist-txn tables mode recorded in [`PERSIST_TXN_TABLES`] before
+    /// this controller started, if any.
+    previous_persist_txn_tables: Option<PersistTxnTablesImpl>,
+
+    /// Restarts of ingestions that apply a change to a connection they depend on.
+    connection_restarts: connection_restarts::ConnectionRestarts,
+    /// Snapshot groups of PostgreSQL sources whose snapshots are pending.
+    snapshot_groups: snapshot_groups::SnapshotGroups,
+    /// Names with which collections are identified in logs and errors.
+    collection_names: CollectionNames,
+    /// The recorder of the commands sent to storage instances.
+    command_recorder: CommandRecorder,
 }
 
 #[async_trait(?Send)]
@@ -252,7 +294,10 @@ where
         }
     }
 
-    fn update_parameters(&mut self, config_params: StorageParameters) {
+    fn update_parameters(&mut self, mut config_params: StorageParameters) {
+        // Stamp the update with the next generation, so that replicas can report which parameters
+        // their ingestions were rendered with.
+        config_params.generation = self.config.parameters.generation + 1;
         config_params.persist.apply(self.persist.cfg());
 
         for client in self.clients.values_mut() {
@@ -266,6 +311,28 @@ where
         &self.config
     }
 
+    fn collection_names(&self) -> &CollectionNames {
+        &self.collection_names
+    }
+
+    fn command_recorder(&self) -> &CommandRecorder {
+        &self.command_recorder
+    }
+
+    fn pending_command_count(&self) -> usize {
+        self.clients
+            .values()
+            .map(|client| client.pending_commands())
+            .sum()
+    }
+
+    fn stash_connection_status(&self) -> StashConnectionStatus {
+        StashConnectionStatus {
+            connected: self.stash.is_connected(),
+            last_error: self.stash.last_connection_error(),
+        }
+    }
+
     fn collection(&self, id: GlobalId) -> Result<&CollectionState<Self::Timestamp>, StorageError> {
         self.collections
             .get(&id)
@@ -289,11 +356,14 @@ where
 
     fn create_instance(&mut self, id: StorageInstanceId) {
         let mut client = RehydratingStorageClient::new(
+            id,
             self.build_info,
             self.metrics.for_instance(id),
             self.envd_epoch,
             self.config.parameters.grpc_client.clone(),
             self.now.clone(),
+            self.collection_names.clone(),
+            self.command_recorder.clone(),
         );
         if self.initialized {
             client.send(StorageCommand::InitializationComplete);
@@ -333,6 +403,24 @@ where
         client.reset();
 
         self.replicas.remove(&instance_id);
+
+        // Without a replica, the restarted dataflows won't come up. The ingestions pick up the
+        // changes once they are scheduled on a new replica.
+        let restarted: Vec<_> = self
+            .connection_restarts
+            .in_flight()
+            .filter(|id| {
+                self.collections.get(id).map_or(false, |c| {
+                    matches!(
+                        &c.description.data_source,
+                        DataSource::Ingestion(ingestion) if ingestion.instance_id == instance_id
+                    )
+                })
+            })
+            .collect();
+        for id in restarted {
+            self.connection_restarts.complete(id);
+        }
     }
 
     // Add new migrations below and precede them with a short summary of the
@@ -688,12 +776,31 @@ where
         this.append_shard_mappings(to_create.iter().map(|(id, _)| *id), 1)
             .await;
 
+        // Hints for ingestions that can't use the snapshot of their snapshot group.
+        let mut group_hints = vec![];
+        let status_now = mz_ore::now::to_datetime((self.now)());
+
         // TODO(guswynn): perform the io in this final section concurrently.
         for (id, description) in to_create {
             match description.data_source {
                 DataSource::Ingestion(ingestion) => {
                     let description = self.enrich_ingestion(id, ingestion)?;
 
+                    // Members of snapshot groups that need to take their snapshots are run once
+                    // the snapshot of their group is exported.
+                    let group_snapshot = match self.join_snapshot_group(id, &description) {
+                        None => None,
+                        Some(snapshot_groups::Membership::Deferred) => continue,
+                        Some(snapshot_groups::Membership::Independent(reason)) => {
+                            let mut update = StatusUpdate::new(id, status_now, Status::Starting);
+                            update
+                                .hints
+                                .insert(format!("taking a snapshot of its own: {reason}"));
+                            group_hints.push(update);
+                            None
+                        }
+                    };
+
                     // Fetch the client for this ingestion's instance.
                     let client =
                         self.clients
@@ -702,7 +809,12 @@ where
                                 storage_instance_id: description.instance_id,
                                 ingestion_id: id,
                             })?;
-                    let augmented_ingestion = RunIngestionCommand { id, description };
+                    let augmented_ingestion = RunIngestionCommand {
+                        id,
+                        description,
+                        parameter_generation: self.config.parameters.generation,
+                        group_snapshot,
+                    };
 
                     client.send(StorageCommand::RunIngestions(vec![augmented_ingestion]));
                 }
@@ -723,41 +835,34 @@ where
                         IntrospectionType::ShardMapping => {
                             self.initialize_shard_mapping().await;
                         }
-                        IntrospectionType::Frontiers | IntrospectionType::ReplicaFrontiers => {
+                        IntrospectionType::Frontiers
+                        | IntrospectionType::ReplicaFrontiers
+                        | IntrospectionType::WallclockLag
+                        | IntrospectionType::FrontierAdvancementRates
+                        | IntrospectionType::FrontierDivergenceHistory
+                        | IntrospectionType::ReplicaStatusHistory
+                        | IntrospectionType::SourceUpstreamLag
+                        | IntrospectionType::SourceParameterGenerations
+                        | IntrospectionType::TimestampOracleStatus => {
                             // Set the collection to empty.
                             self.reconcile_managed_collection(id, vec![]).await;
                         }
-                        IntrospectionType::StorageSourceStatistics => {
-                            // Set the collection to empty.
-                            self.reconcile_managed_collection(id, vec![]).await;
-
-                            let scraper_token = statistics::spawn_statistics_scraper(
-                                id.clone(),
-                                // These do a shallow copy.
+                        IntrospectionType::FrontiersHistory => {
+                            let downsampler = frontiers_history::spawn_downsampler(
+                                id,
+                                self.collections[&id].collection_metadata.clone(),
+                                Arc::clone(&self.persist),
+                                // This does a shallow copy.
                                 self.collection_manager.clone(),
-                                Arc::clone(&self.source_statistics),
-                                self.config.parameters.statistics_interval,
                             );
-
-                            // Make sure this is dropped when the controller is
-                            // dropped, so that the internal task will stop.
-                            self.introspection_tokens.insert(id, scraper_token);
+                            self.frontiers_history_downsampler = Some(downsampler);
+                            self.downsample_frontiers_history();
                         }
-                        IntrospectionType::StorageSinkStatistics => {
-                            // Set the collection to empty.
+                        IntrospectionType::StorageSourceStatistics
+                        | IntrospectionType::StorageSinkStatistics => {
+                            // Set the collection to empty. The adapter writes the statistics
+                            // that are reported from now on, see `Response::StatisticsUpdates`.
                             self.reconcile_managed_collection(id, vec![]).await;
-
-                            let scraper_token = statistics::spawn_statistics_scraper(
-                                id.clone(),
-                                // These do a shallow copy.
-                                self.collection_manager.clone(),
-                                Arc::clone(&self.sink_statistics),
-                                self.config.parameters.statistics_interval,
-                            );
-
-                            // Make sure this is dropped when the controller is
-                            // dropped, so that the internal task will stop.
-                            self.introspection_tokens.insert(id, scraper_token);
                         }
                         IntrospectionType::SourceStatusHistory => {
                             let last_status_per_id = self
@@ -846,6 +951,8 @@ where
             }
         }
 
+        self.record_status_updates(group_hints, None).await;
+
         Ok(())
     }
 
@@ -859,6 +966,48 @@ where
         Ok(())
     }
 
+    fn check_ingestion_size(
+        &self,
+        id: GlobalId,
+        ingestion: &IngestionDescription,
+    ) -> Result<(), StorageError> {
+        let metadata = |id| match self.collection(id) {
+            Ok(collection) => collection.collection_metadata.clone(),
+            Err(_) => CollectionMetadata {
+                persist_location: self.persist_location.clone(),
+                remap_shard: None,
+                data_shard: ShardId::new(),
+                status_shard: None,
+                relation_desc: RelationDesc::empty(),
+                txns_shard: None,
+            },
+        };
+        let description = IngestionDescription {
+            desc: ingestion.desc.clone(),
+            ingestion_metadata: metadata(id),
+            source_exports: ingestion
+                .source_exports
+                .iter()
+                .map(|(id, export)| {
+                    let export = SourceExport {
+                        output_index: export.output_index,
+                        storage_metadata: metadata(*id),
+                    };
+                    (*id, export)
+                })
+                .collect(),
+            instance_id: ingestion.instance_id,
+            remap_collection_id: ingestion.remap_collection_id,
+        };
+        RunIngestionCommand {
+            id,
+            description,
+            parameter_generation: self.config.parameters.generation,
+            group_snapshot: None,
+        }
+        .check_size(&self.config.parameters)
+    }
+
     async fn alter_collection(
         &mut self,
         collections: BTreeMap<GlobalId, IngestionDescription>,
@@ -867,42 +1016,17 @@ where
             .expect("error avoided by calling check_alter_collection first");
 
         for (id, ingestion) in collections {
-            // Describe the ingestion in terms of collection metadata.
-            let description = self
-                .enrich_ingestion(id, ingestion.clone())
-                .expect("verified valid in check_alter_collection_inner");
-
-            let collection = self.collection_mut(id).expect("validated exists");
-            let new_source_exports = match &mut collection.description.data_source {
-                DataSource::Ingestion(active_ingestion) => {
-                    // Determine which IDs we're adding.
-                    let new_source_exports: Vec<_> = description
-                        .source_exports
-                        .keys()
-                        .filter(|id| !active_ingestion.source_exports.contains_key(id))
-                        .cloned()
-                        .collect();
-                    *active_ingestion = ingestion;
-
-                    new_source_exports
-                }
-                _ => unreachable!("verified collection refers to ingestion"),
-            };
-
-            // Assess dependency since, which we have to fast-forward this
-            // collection's since to.
-            let storage_dependencies = collection.description.get_storage_dependencies();
+            let description = self.apply_alter_collection(id, ingestion)?;
 
-            // Ensure this new collection's since is aligned with the dependencies.
-            // This will likely place its since beyond its upper which is OK because
-            // its snapshot will catch it up with the rest of the source, i.e. we
-            // will never see its upper at a state beyond 0 and less than its since.
-            self.install_dependency_read_holds(
-                new_source_exports.into_iter(),
-                &storage_dependencies,
-            )?;
+            // The ingestion is run with the altered description once the snapshot of its group is
+            // exported.
+            if self.snapshot_groups.is_deferred(id) {
+                continue;
+            }
 
             // Fetch the client for this ingestion's instance.
+            let parameter_generation = self.config.parameters.generation;
+            let group_snapshot = self.snapshot_groups.snapshot(id).cloned();
             let client = self
                 .clients
                 .get_mut(&description.instance_id)
@@ -911,18 +1035,43 @@ where
             client.send(StorageCommand::RunIngestions(vec![RunIngestionCommand {
                 id,
                 description,
+                parameter_generation,
+                group_snapshot,
             }]));
         }
 
         Ok(())
     }
 
+    async fn alter_collection_connections(
+        &mut self,
+        collections: BTreeMap<GlobalId, IngestionDescription>,
+        max_concurrency: usize,
+    ) -> Result<oneshot::Receiver<()>, StorageError> {
+        self.check_alter_collection(&collections)
+            .expect("error avoided by calling check_alter_collection first");
+
+        let mut ids = BTreeSet::new();
+        for (id, ingestion) in collections {
+            self.apply_alter_collection(id, ingestion)?;
+            ids.insert(id);
+        }
+
+        let done = self.connection_restarts.enqueue(ids, max_concurrency);
+        self.issue_connection_restarts().await;
+        Ok(done)
+    }
+
     fn export(&self, id: GlobalId) -> Result<&ExportState<Self::Timestamp>, StorageError> {
         self.exports
             .get(&id)
             .ok_or(StorageError::IdentifierMissing(id))
     }
 
+    fn exports(&self) -> Box<dyn Iterator<Item = (&GlobalId, &ExportState<Self::Timestamp>)> + '_> {
+        Box::new(self.exports.iter())
+    }
+
     fn export_mut(
         &mut self,
         id: GlobalId,
@@ -1007,6 +1156,7 @@ where
                     from_storage_metadata,
                     with_snapshot: description.sink.with_snapshot,
                 },
+                reset_progress: false,
             };
 
             // Fetch the client for this exports's cluster.
@@ -1079,6 +1229,7 @@ where
                     status_id,
                     from_storage_metadata,
                 },
+                reset_progress: false,
             };
 
             let update = updates_by_instance
@@ -1124,6 +1275,56 @@ where
         Ok(())
     }
 
+    async fn reset_export_progress(&mut self, id: GlobalId) -> Result<(), StorageError> {
+        let export = self.export(id)?;
+        let sink = &export.description.sink;
+
+        let from_storage_metadata = self.collection(sink.from)?.collection_metadata.clone();
+        let status_id = if let Some(status_collection_id) = sink.status_id {
+            Some(
+                self.collection(status_collection_id)?
+                    .collection_metadata
+                    .data_shard,
+            )
+        } else {
+            None
+        };
+
+        info!(
+            sink_id = id.to_string(),
+            as_of = ?export.read_capability,
+            "reset_export_progress: resetting sink progress"
+        );
+
+        let cmd = RunSinkCommand {
+            id,
+            description: StorageSinkDesc {
+                from: sink.from,
+                from_desc: sink.from_desc.clone(),
+                connection: sink.connection.clone(),
+                envelope: sink.envelope,
+                with_snapshot: sink.with_snapshot,
+                // The same caveat as in `update_export_connection` applies: the running sink might
+                // make additional progress before this command arrives.
+                as_of: export.read_capability.clone(),
+                status_id,
+                from_storage_metadata,
+            },
+            reset_progress: true,
+        };
+
+        let instance_id = export.description.instance_id;
+        let client = self.clients.get_mut(&instance_id).ok_or_else(|| {
+            StorageError::ExportInstanceMissing {
+                storage_instance_id: instance_id,
+                export_id: id,
+            }
+        })?;
+        client.send(StorageCommand::RunSinks(vec![cmd]));
+
+        Ok(())
+    }
+
     fn drop_sources(&mut self, identifiers: Vec<GlobalId>) -> Result<(), StorageError> {
         self.validate_collection_ids(identifiers.iter().cloned())?;
         self.drop_sources_unvalidated(identifiers);
@@ -1131,6 +1332,11 @@ where
     }
 
     fn drop_sources_unvalidated(&mut self, identifiers: Vec<GlobalId>) {
+        // Dropped sources no longer hold up other connection restarts.
+        for id in &identifiers {
+            self.connection_restarts.remove(*id);
+        }
+
         // We don't explicitly remove read capabilities! Downgrading the
         // frontier of the source to `[]` (the empty Antichain), will propagate
         // to the storage dependencies.
@@ -1316,6 +1522,32 @@ where
         self.persist_read_handles.snapshot_stats(id, as_of).await
     }
 
+    async fn estimate_compaction(
+        &self,
+        policies: Vec<(GlobalId, ReadPolicy<Self::Timestamp>)>,
+    ) -> Result<BTreeMap<GlobalId, CompactionEstimate<Self::Timestamp>>, StorageError> {
+        let mut estimates = BTreeMap::new();
+        for (id, policy) in policies {
+            let collection = self.collection(id)?;
+            let since = collection.read_capabilities.frontier().to_owned();
+            let proposed_since = compaction_estimate::proposed_since(collection, &policy);
+            let metadata = &collection.collection_metadata;
+            let client = self
+                .persist
+                .open(metadata.persist_location.clone())
+                .await
+                .map_err(|e| StorageError::Generic(e.into()))?;
+            let batches = client
+                .batch_stats::<Self::Timestamp>(&metadata.data_shard)
+                .await
+                .map_err(StorageError::Generic)?;
+            let estimate =
+                compaction_estimate::estimate_compaction(since, proposed_since, &batches);
+            estimates.insert(id, estimate);
+        }
+        Ok(estimates)
+    }
+
     #[instrument(level = "debug", skip(self))]
     fn set_read_policy(&mut self, policies: Vec<(GlobalId, ReadPolicy<Self::Timestamp>)>) {
         let mut read_capability_changes = BTreeMap::default();
@@ -1542,73 +1774,49 @@ where
     async fn ready(&mut self) {
         let mut clients = self
             .clients
-            .values_mut()
-            .map(|client| client.response_stream())
-            .enumerate()
+            .iter_mut()
+            .map(|(id, client)| (*id, client.response_stream()))
             .collect::<StreamMap<_, _>>();
 
         use tokio_stream::StreamExt;
-        let msg = tokio::select! {
+        tokio::select! {
             // Order matters here. We want to process internal commands
             // before processing external commands.
             biased;
 
-            Some(m) = self.internal_response_queue.recv() => m,
-            Some((_id, m)) = clients.next() => m,
-        };
-
-        self.stashed_response = Some(msg);
+            Some(m) = self.internal_response_queue.recv() => {
+                self.stashed_response = Some(m);
+                self.stashed_response_instance = None;
+            }
+            Some(event) = self.snapshot_groups.next_event() => {
+                self.stashed_group_event = Some(event);
+            }
+            Some((id, m)) = clients.next() => {
+                self.stashed_response = Some(m);
+                self.stashed_response_instance = Some(id);
+            }
+            // Dropped collections that remain to be cleaned up make us ready, but only after all
+            // other work. We yield first, so that a caller that selects over us and its own work
+            // gets to that work between two batches, rather than being held up until all of them
+            // are cleaned up.
+            _ = tokio::task::yield_now(), if !self.pending_drops.is_empty() => {}
+        }
     }
 
     #[instrument(level = "debug", skip(self))]
     async fn process(&mut self) -> Result<Option<Response<T>>, anyhow::Error> {
-        let mut updated_frontiers = None;
+        let mut response = None;
+        let instance_id = self.stashed_response_instance.take();
         match self.stashed_response.take() {
             None => (),
             Some(StorageResponse::FrontierUppers(updates)) => {
                 self.update_write_frontiers(&updates);
-                updated_frontiers = Some(Response::FrontierUpdates(updates));
+                self.release_snapshot_groups();
+                response = Some(Response::FrontierUpdates(updates));
             }
             Some(StorageResponse::DroppedIds(ids)) => {
-                let shards_to_finalize: Vec<_> = ids
-                    .iter()
-                    .filter_map(|id| {
-                        // Note: All handles to the id should be dropped by now and the since of
-                        // the collection should be downgraded to the empty antichain. If handles
-                        // to the shard still exist, then we will incorrectly report the shard as
-                        // alive, and if the since of the shard has not been downgraded, then we
-                        // will continuously fail to finalize it.
-                        //
-                        // TODO(parkmycar): Should we be asserting that .remove(...) is some? In
-                        // other words that we know about the collection we're receiving an event
-                        // for.
-                        self.collections
-                            .remove(id)
-                            .map(|state| state.collection_metadata.data_shard)
-                    })
-                    .collect();
-
-                // Ensure we don't leak any shards by tracking all of them we intend to
-                // finalize.
-                self.register_shards_for_finalization(shards_to_finalize)
-                    .await;
-
-                METADATA_COLLECTION
-                    .delete_keys(
-                        &mut self.stash,
-                        ids.into_iter()
-                            .map(|id| RustType::into_proto(&id))
-                            .collect(),
-                    )
-                    .await
-                    .expect("stash operation must succeed");
-
-                if self.config.parameters.finalize_shards {
-                    info!("triggering shard finalization due to dropped storage object");
-                    self.finalize_shards().await;
-                } else {
-                    info!("not triggering shard finalization due to dropped storage object because enable_storage_shard_finalization parameter is false")
-                }
+                // Cleaned up in batches below, so that large drops don't stall the caller.
+                self.pending_drops.extend(ids);
             }
             Some(StorageResponse::StatisticsUpdates(source_stats, sink_stats)) => {
                 // Note we only hold the locks while moving some plain-old-data around here.
@@ -1617,32 +1825,127 @@ where
                 // current values.
                 //
                 // We don't overwrite removed objects, as we may have received a late
-                // `StatisticsUpdates` while we were shutting down the storage object.
-                {
+                // `StatisticsUpdates` while we were shutting down the storage object. Neither
+                // do we forward their statistics.
+                let source_stats: Vec<_> = {
                     let mut shared_stats = self.source_statistics.lock().expect("poisoned");
-                    for stat in source_stats {
-                        statistics::StatsInitState::set_if_not_removed(
-                            shared_stats.get_mut(&stat.id),
-                            stat.worker_id,
-                            stat,
-                        )
-                    }
-                }
-
-                {
+                    source_stats
+                        .into_iter()
+                        .filter(|stat| {
+                            statistics::StatsInitState::set_if_not_removed(
+                                shared_stats.get_mut(&stat.id),
+                                stat.worker_id,
+                                stat.clone(),
+                            )
+                        })
+                        .collect()
+                };
+                let sink_stats: Vec<_> = {
                     let mut shared_stats = self.sink_statistics.lock().expect("poisoned");
-                    for stat in sink_stats {
-                        statistics::StatsInitState::set_if_not_removed(
-                            shared_stats.get_mut(&stat.id),
-                            stat.worker_id,
-                            stat,
-                        )
-                    }
+                    sink_stats
+                        .into_iter()
+                        .filter(|stat| {
+                            statistics::StatsInitState::set_if_not_removed(
+                                shared_stats.get_mut(&stat.id),
+                                stat.worker_id,
+                                stat.clone(),
+                            )
+                        })
+                        .collect()
+                };
+
+                if !source_stats.is_empty() || !sink_stats.is_empty() {
+                    response = Some(Response::StatisticsUpdates(source_stats, sink_stats));
                 }
             }
             Some(StorageResponse::StatusUpdates(updates)) => {
-                self.record_status_updates(updates).await;
+                for update in &updates {
+                    self.connection_restarts
+                        .observe_status(update.id, update.status);
+                }
+                // Attribute the updates to the replica of the instance that delivered them, so
+                // that the conflicting statuses of different replicas can be told apart.
+                let origin = instance_id.map(|cluster_id| StatusOrigin {
+                    cluster_id,
+                    replica_id: self.replicas.get(&cluster_id).copied(),
+                });
+                self.record_status_updates(updates, origin).await;
+                self.issue_connection_restarts().await;
+            }
+            Some(StorageResponse::UpstreamLag { id, bytes_behind }) => {
+                // We may receive a late report while the collection is being dropped.
+                if let Some(collection) = self.collections.get_mut(&id) {
+                    let previous = collection.upstream_lag.replace(bytes_behind);
+                    let updates = source_value_updates(id, previous, Some(bytes_behind));
+                    self.append_source_value_updates(IntrospectionType::SourceUpstreamLag, updates)
+                        .await;
+                }
+            }
+            Some(StorageResponse::ParameterGeneration { id, generation }) => {
+                // We may receive a late report while the collection is being dropped.
+                if let Some(collection) = self.collections.get_mut(&id) {
+                    let previous = collection.parameter_generation.replace(generation);
+                    let updates = source_value_updates(id, previous, Some(generation));
+                    self.append_source_value_updates(
+                        IntrospectionType::SourceParameterGenerations,
+                        updates,
+                    )
+                    .await;
+                }
+
+                // An ingestion that was rendered with an older generation than the current one
+                // raced with a configuration update and missed it. Running it again makes the
+                // replica re-render it with the current parameters.
+                if generation < self.config.parameters.generation {
+                    self.run_ingestion(id);
+                }
+            }
+            Some(StorageResponse::SinkProgress { id, emitted_upper }) => {
+                // We may receive a late report while the export is being dropped.
+                if let Some(export) = self.exports.get_mut(&id) {
+                    export.emitted_upper.join_assign(&emitted_upper);
+                }
             }
+            Some(StorageResponse::CollectionStateReport(reports)) => {
+                for report in reports {
+                    // We may receive a late report while the collection is being dropped.
+                    let Some(collection) = self.collections.get(&report.id) else {
+                        continue;
+                    };
+                    if let Some(error) = &report.persist_error {
+                        warn!(
+                            "replica failed to read the shard of collection {}: {error}",
+                            report.id
+                        );
+                    }
+                    let mismatches = collection_state::check_collection_state(
+                        &report,
+                        &collection.write_frontier,
+                        &collection.read_capabilities.frontier().to_owned(),
+                    );
+                    if mismatches.is_empty() {
+                        info!(
+                            "state of collection {} is consistent: {report:?}",
+                            report.id
+                        );
+                    }
+                    for mismatch in mismatches {
+                        warn!("inconsistent state of collection {}: {mismatch}", report.id);
+                        self.metrics
+                            .inc_collection_state_mismatches(mismatch.label());
+                    }
+                }
+            }
+        }
+
+        if !self.pending_drops.is_empty() {
+            let batch_size = self.config.parameters.dropped_ids_batch_size;
+            let ids = self.pending_drops.next_batch(batch_size);
+            self.process_dropped_ids(ids).await;
+        }
+
+        if let Some(event) = self.stashed_group_event.take() {
+            self.process_snapshot_group_event(event).await;
         }
 
         // IDs of sources that were dropped whose statuses should be updated.
@@ -1777,13 +2080,45 @@ where
         let status_now = mz_ore::now::to_datetime((self.now)());
 
         let mut dropped_sources = vec![];
+        let mut upstream_lag_retractions = vec![];
+        let mut parameter_generation_retractions = vec![];
         for id in pending_source_drops.drain(..) {
+            self.connection_restarts.remove(id);
+            self.snapshot_groups.remove_member(id);
+            if let Some(collection) = self.collections.get(&id) {
+                upstream_lag_retractions.extend(source_value_updates(
+                    id,
+                    collection.upstream_lag,
+                    None,
+                ));
+                parameter_generation_retractions.extend(source_value_updates(
+                    id,
+                    collection.parameter_generation,
+                    None,
+                ));
+            }
             dropped_sources.push(StatusUpdate::new(id, status_now, Status::Dropped));
         }
+        self.append_source_value_updates(
+            IntrospectionType::SourceUpstreamLag,
+            upstream_lag_retractions,
+        )
+        .await;
+        self.append_source_value_updates(
+            IntrospectionType::SourceParameterGenerations,
+            parameter_generation_retractions,
+        )
+        .await;
 
         self.collection_status_manager
-            .append_updates(dropped_sources, IntrospectionType::SourceStatusHistory)
+            .append_updates(
+                dropped_sources,
+                IntrospectionType::SourceStatusHistory,
+                None,
+            )
             .await;
+        // Dropped sources no longer hold up other connection restarts.
+        self.issue_connection_restarts().await;
 
         {
             let mut source_statistics = self.source_statistics.lock().expect("poisoned");
@@ -1802,10 +2137,19 @@ where
             }
         }
         self.collection_status_manager
-            .append_updates(dropped_sinks, IntrospectionType::SinkStatusHistory)
+            .append_updates(dropped_sinks, IntrospectionType::SinkStatusHistory, None)
             .await;
 
-        Ok(updated_frontiers)
+        Ok(response)
+    }
+
+    fn subscribe_status_updates(&mut self) -> UnboundedReceiverStream<StatusUpdate> {
+        self.collection_status_manager.subscribe()
+    }
+
+    fn update_status_attributions(&mut self, attributions: BTreeMap<GlobalId, StatusAttribution>) {
+        self.collection_status_manager
+            .update_attributions(attributions)
     }
 
     async fn reconcile_state(&mut self) {
@@ -1828,6 +2172,44 @@ where
         Ok(json_state)
     }
 
+    fn report_collection_state(
+        &mut self,
+        id: GlobalId,
+    ) -> Result<BTreeSet<GlobalId>, StorageError> {
+        self.collection(id)?;
+        // Only the collections of ingestions have their state reported, by the cluster that runs
+        // the ingestion.
+        let (ingestion_id, ingestion) = self
+            .collections
+            .iter()
+            .find_map(
+                |(ingestion_id, collection)| match &collection.description.data_source {
+                    DataSource::Ingestion(ingestion)
+                        if *ingestion_id == id || ingestion.subsource_ids().any(|s| s == id) =>
+                    {
+                        Some((*ingestion_id, ingestion))
+                    }
+                    _ => None,
+                },
+            )
+            .ok_or(StorageError::IdentifierInvalid(id))?;
+        let ids: BTreeSet<_> = if ingestion_id == id {
+            ingestion.subsource_ids().collect()
+        } else {
+            BTreeSet::from([id])
+        };
+        let instance_id = ingestion.instance_id;
+        let client =
+            self.clients
+                .get_mut(&instance_id)
+                .ok_or(StorageError::IngestionInstanceMissing {
+                    storage_instance_id: instance_id,
+                    ingestion_id,
+                })?;
+        client.send(StorageCommand::ReportCollectionState(ids.clone()));
+        Ok(ids)
+    }
+
     async fn record_frontiers(
         &mut self,
         external_frontiers: BTreeMap<
@@ -1869,15 +2251,31 @@ where
                 updates.push((row, diff));
             };
 
+        // Changed write frontiers are also recorded in the frontiers history.
+        let now = (self.now)();
+        let mut history_updates = Vec::new();
+        let mut push_history_update = |object_id: GlobalId, upper: &Antichain<Self::Timestamp>| {
+            let write_frontier = upper.as_option().map(|ts| ts.clone().into());
+            let row =
+                frontiers_history::pack_recording(now, &object_id.to_string(), write_frontier);
+            history_updates.push((row, 1));
+        };
+
         let mut old_frontiers = std::mem::replace(&mut self.recorded_frontiers, frontiers);
         for (&id, new) in &self.recorded_frontiers {
             match old_frontiers.remove(&id) {
                 Some(old) if &old != new => {
+                    if old.1 != new.1 {
+                        push_history_update(id, &new.1);
+                    }
                     push_update(id, new.clone(), 1);
                     push_update(id, old, -1);
                 }
                 Some(_) => (),
-                None => push_update(id, new.clone(), 1),
+                None => {
+                    push_history_update(id, &new.1);
+                    push_update(id, new.clone(), 1);
+                }
             }
         }
         for (id, old) in old_frontiers {
@@ -1886,6 +2284,17 @@ where
 
         let id = self.introspection_ids.lock().expect("poisoned")[&IntrospectionType::Frontiers];
         self.append_to_managed_collection(id, updates).await;
+
+        let history_id =
+            self.introspection_ids.lock().expect("poisoned")[&IntrospectionType::FrontiersHistory];
+        self.append_to_managed_collection(history_id, history_updates)
+            .await;
+
+        let interval = EpochMillis::try_from(frontiers_history::DOWNSAMPLE_INTERVAL.as_millis())
+            .expect("must fit");
+        if now.saturating_sub(self.frontiers_history_downsampled_at) >= interval {
+            self.downsample_frontiers_history();
+        }
     }
 
     async fn record_replica_frontiers(
@@ -1958,6 +2367,21 @@ where
         self.append_to_managed_collection(id, updates).await;
     }
 
+    fn statistics_snapshot(&self) -> (Vec<SourceStatisticsUpdate>, Vec<SinkStatisticsUpdate>) {
+        let source_stats = self.source_statistics.lock().expect("poisoned");
+        let sink_stats = self.sink_statistics.lock().expect("poisoned");
+        (
+            source_stats
+                .values()
+                .flat_map(|stats| stats.0.values().cloned())
+                .collect(),
+            sink_stats
+                .values()
+                .flat_map(|stats| stats.0.values().cloned())
+                .collect(),
+        )
+    }
+
     /// With the CRDB based timestamp oracle, there is no longer write timestamp
     /// fencing. As in, when a new Coordinator, `B`, starts up, there is nothing
     /// that prevents an old Coordinator, `A`, from getting a new write
@@ -2076,6 +2500,60 @@ where
 #[derive(PartialEq, Clone, Debug)]
 pub struct PersistEpoch(Option<NonZeroI64>);
 
+/// Returns an error if table shards last operated in `durable` mode cannot
+/// safely be operated in `configured` mode.
+///
+/// Switching from eager to lazy is safe, as is staying in the same mode.
+/// Switching back from lazy to eager is not: in lazy mode the physical uppers
+/// of the data shards may lag behind the txns shard, whereas eager mode reads
+/// the data shards directly and would observe them as missing committed
+/// writes. Operators that need to roll back must first clear the record, e.g.
+/// with `stash-debug`.
+fn check_persist_txn_tables_transition(
+    durable: Option<PersistTxnTablesImpl>,
+    configured: PersistTxnTablesImpl,
+) -> Result<(), StorageError> {
+    match (durable, configured) {
+        (Some(PersistTxnTablesImpl::Lazy), PersistTxnTablesImpl::Eager) => {
+            Err(StorageError::PersistTxnTablesRegression {
+                durable: PersistTxnTablesImpl::Lazy,
+                configured,
+            })
+        }
+        (None, _)
+        | (Some(PersistTxnTablesImpl::Eager), _)
+        | (Some(PersistTxnTablesImpl::Lazy), PersistTxnTablesImpl::Lazy) => Ok(()),
+    }
+}
+
+/// Durably records `configured` as the mode table shards are operated in,
+/// returning the previously recorded mode, if any.
+///
+/// The record is left untouched if the transition from the previous mode is
+/// unsafe, see [`check_persist_txn_tables_transition`].
+async fn record_persist_txn_tables(
+    stash: &mut mz_stash::Stash,
+    configured: PersistTxnTablesImpl,
+) -> Result<Option<PersistTxnTablesImpl>, StorageError> {
+    let res = PERSIST_TXN_TABLES
+        .upsert_key(stash, (), move |prev| {
+            let durable = prev.map(|prev| {
+                PersistTxnTablesImpl::try_from(*prev).map_err(|v| {
+                    StorageError::Generic(anyhow::anyhow!(
+                        "invalid persist-txn tables mode recorded: {v}"
+                    ))
+                })
+            });
+            let durable = durable.transpose()?;
+            check_persist_txn_tables_transition(durable, configured)?;
+            Ok::<_, StorageError>(u64::from(configured))
+        })
+        .await?;
+    let (prev, _) = res?;
+    let prev = prev.map(|prev| PersistTxnTablesImpl::try_from(prev).expect("validated above"));
+    Ok(prev)
+}
+
 impl Opaque for PersistEpoch {
     fn initial() -> Self {
         PersistEpoch(None)
@@ -2114,6 +2592,9 @@ where
     ///
     /// Note that when creating a new storage controller, you must also
     /// reconcile it with the previous state.
+    ///
+    /// Returns an error if `persist_txn_tables` cannot safely take over from
+    /// the mode the table shards were last operated in.
     pub async fn new(
         build_info: &'static BuildInfo,
         postgres_url: String,
@@ -2125,7 +2606,7 @@ where
         metrics_registry: MetricsRegistry,
         persist_txn_tables: PersistTxnTablesImpl,
         connection_context: ConnectionContext,
-    ) -> Self {
+    ) -> Result<Self, StorageError> {
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
 
         let tls = mz_tls_util::make_tls(
@@ -2176,15 +2657,18 @@ where
                     let (
                         metadata_collection,
                         persist_txns_shard,
+                        persist_txn_tables,
                         shard_finalization,
                     ) = futures::join!(
                         maybe_get_init_batch(&tx, &METADATA_COLLECTION),
                         maybe_get_init_batch(&tx, &PERSIST_TXNS_SHARD),
+                        maybe_get_init_batch(&tx, &PERSIST_TXN_TABLES),
                         maybe_get_init_batch(&tx, &command_wals::SHARD_FINALIZATION),
                     );
                     let batches: Vec<AppendBatch> = [
                         metadata_collection,
                         persist_txns_shard,
+                        persist_txn_tables,
                         shard_finalization,
                     ]
                     .into_iter()
@@ -2197,6 +2681,14 @@ where
             .await
             .expect("stash operation must succeed");
 
+        let previous_persist_txn_tables =
+            record_persist_txn_tables(&mut stash, persist_txn_tables).await?;
+        info!(
+            "persist-txn tables: operating in {} mode (previously {})",
+            persist_txn_tables,
+            previous_persist_txn_tables.map_or("unrecorded".into(), |mode| mode.to_string()),
+        );
+
         let txns_client = persist_clients
             .open(persist_location.clone())
             .await
@@ -2249,7 +2741,7 @@ where
             Arc::clone(&introspection_ids),
         );
 
-        Self {
+        Ok(Self {
             build_info,
             collections: BTreeMap::default(),
             exports: BTreeMap::default(),
@@ -2261,11 +2753,13 @@ where
             txns_init_run: false,
             txns_metrics,
             stashed_response: None,
+            stashed_response_instance: None,
+            stashed_group_event: None,
             pending_compaction_commands: vec![],
+            pending_drops: Default::default(),
             collection_manager,
             collection_status_manager,
             introspection_ids,
-            introspection_tokens: BTreeMap::new(),
             now,
             envd_epoch,
             source_statistics: Arc::new(Mutex::new(BTreeMap::new())),
@@ -2281,7 +2775,20 @@ where
             metrics: StorageControllerMetrics::new(metrics_registry),
             recorded_frontiers: BTreeMap::new(),
             recorded_replica_frontiers: BTreeMap::new(),
-        }
+            frontiers_history_downsampled_at: 0,
+            frontiers_history_downsampler: None,
+            previous_persist_txn_tables,
+            connection_restarts: connection_restarts::ConnectionRestarts::new(),
+            snapshot_groups: snapshot_groups::SnapshotGroups::new(),
+            collection_names: CollectionNames::default(),
+            command_recorder: CommandRecorder::default(),
+        })
+    }
+
+    /// Returns the persist-txn tables mode that was durably recorded before
+    /// this controller started, or `None` if no mode had been recorded.
+    pub fn previous_persist_txn_tables(&self) -> Option<PersistTxnTablesImpl> {
+        self.previous_persist_txn_tables
     }
 
     /// Validate that a collection exists for all identifiers, and error if any do not.
@@ -2536,6 +3043,37 @@ where
         self.reconcile_managed_collection(id, updates).await;
     }
 
+    /// Asks the downsampler task to roll up the rows of the `FrontiersHistory`
+    /// collection that are due for a coarser resolution, see [`frontiers_history`].
+    fn downsample_frontiers_history(&mut self) {
+        let now = (self.now)();
+        self.frontiers_history_downsampled_at = now;
+
+        let Some(downsampler) = &self.frontiers_history_downsampler else {
+            return;
+        };
+        let id =
+            self.introspection_ids.lock().expect("poisoned")[&IntrospectionType::FrontiersHistory];
+        let as_of = match self.collections[&id].write_frontier.as_option() {
+            Some(f) if f > &T::minimum() => f.step_back().unwrap(),
+            // If collection is closed or the frontier is the minimum, we cannot
+            // or don't need to downsample (respectively).
+            _ => return,
+        };
+
+        let request = frontiers_history::DownsampleRequest {
+            now,
+            as_of,
+            full_resolution_window: self
+                .config
+                .parameters
+                .frontiers_history_full_resolution_window,
+        };
+        // The task only goes away when we drop the sender, or when it panicked, in which case
+        // there is nothing left to do here.
+        let _ = downsampler.send(request);
+    }
+
     /// Effectively truncates the source status history shard except for the
     /// most recent updates from each ID.
     ///
@@ -2738,6 +3276,25 @@ where
         self.append_to_managed_collection(id, updates).await;
     }
 
+    /// Appends `updates` to the per-source introspection collection of type `type_`, like
+    /// `IntrospectionType::SourceUpstreamLag`, if it is registered.
+    async fn append_source_value_updates(
+        &self,
+        type_: IntrospectionType,
+        updates: Vec<(Row, Diff)>,
+    ) {
+        if updates.is_empty() {
+            return;
+        }
+
+        let id = match self.introspection_ids.lock().expect("poisoned").get(&type_) {
+            Some(id) => *id,
+            _ => return,
+        };
+
+        self.append_to_managed_collection(id, updates).await;
+    }
+
     /// Updates the on-disk and in-memory representation of `DurableCollectionMetadata` (i.e. KV
     /// pairs in `METADATA_COLLECTION` on-disk and `all_current_metadata` as its in-memory
     /// representation) to include that of `upsert_state`, i.e. upserting the KV pairs in
@@ -2872,6 +3429,50 @@ where
         }
     }
 
+    /// Cleans up after the dropped collections `ids`: forgets their state, registers their shards
+    /// for finalization and deletes their shard mappings.
+    async fn process_dropped_ids(&mut self, ids: BTreeSet<GlobalId>) {
+        let shards_to_finalize: Vec<_> = ids
+            .iter()
+            .filter_map(|id| {
+                // Note: All handles to the id should be dropped by now and the since of
+                // the collection should be downgraded to the empty antichain. If handles
+                // to the shard still exist, then we will incorrectly report the shard as
+                // alive, and if the since of the shard has not been downgraded, then we
+                // will continuously fail to finalize it.
+                //
+                // TODO(parkmycar): Should we be asserting that .remove(...) is some? In
+                // other words that we know about the collection we're receiving an event
+                // for.
+                self.collections
+                    .remove(id)
+                    .map(|state| state.collection_metadata.data_shard)
+            })
+            .collect();
+
+        // Ensure we don't leak any shards by tracking all of them we intend to
+        // finalize.
+        self.register_shards_for_finalization(shards_to_finalize)
+            .await;
+
+        METADATA_COLLECTION
+            .delete_keys(
+                &mut self.stash,
+                ids.into_iter()
+                    .map(|id| RustType::into_proto(&id))
+                    .collect(),
+            )
+            .await
+            .expect("stash operation must succeed");
+
+        if self.config.parameters.finalize_shards {
+            info!("triggering shard finalization due to dropped storage object");
+            self.finalize_shards().await;
+        } else {
+            info!("not triggering shard finalization due to dropped storage object because enable_storage_shard_finalization parameter is false")
+        }
+    }
+
     /// Attempts to close all shards marked for finalization.
     #[allow(dead_code)]
     #[instrument(level = "debug", skip(self))]
@@ -3000,6 +3601,9 @@ where
                 ingestion_id: id,
             })?;
 
+        // Altering an ingestion usually grows it.
+        self.check_ingestion_size(id, &ingestion)?;
+
         // Take a cloned copy of the description because we are going to treat it as a "scratch
         // space".
         let mut collection_description = self.collection(id)?.description.clone();
@@ -3168,6 +3772,248 @@ where
         Ok(())
     }
 
+    /// Updates the ingestion `id` to the given description, returning the enriched description
+    /// that the ingestion must be run with to apply the change.
+    fn apply_alter_collection(
+        &mut self,
+        id: GlobalId,
+        ingestion: IngestionDescription,
+    ) -> Result<IngestionDescription<CollectionMetadata>, StorageError> {
+        // Describe the ingestion in terms of collection metadata.
+        let description = self
+            .enrich_ingestion(id, ingestion.clone())
+            .expect("verified valid in check_alter_collection_inner");
+
+        let collection = self.collection_mut(id).expect("validated exists");
+        let new_source_exports = match &mut collection.description.data_source {
+            DataSource::Ingestion(active_ingestion) => {
+                // Determine which IDs we're adding.
+                let new_source_exports: Vec<_> = description
+                    .source_exports
+                    .keys()
+                    .filter(|id| !active_ingestion.source_exports.contains_key(id))
+                    .cloned()
+                    .collect();
+                *active_ingestion = ingestion;
+
+                new_source_exports
+            }
+            _ => unreachable!("verified collection refers to ingestion"),
+        };
+
+        // Assess dependency since, which we have to fast-forward this
+        // collection's since to.
+        let storage_dependencies = collection.description.get_storage_dependencies();
+
+        // Ensure this new collection's since is aligned with the dependencies.
+        // This will likely place its since beyond its upper which is OK because
+        // its snapshot will catch it up with the rest of the source, i.e. we
+        // will never see its upper at a state beyond 0 and less than its since.
+        self.install_dependency_read_holds(new_source_exports.into_iter(), &storage_dependencies)?;
+
+        Ok(description)
+    }
+
+    /// Issues as many of the queued connection restarts as the concurrency limit allows,
+    /// recording each of them in the source status history.
+    async fn issue_connection_restarts(&mut self) {
+        let mut updates = vec![];
+        loop {
+            let issued = self.connection_restarts.issue();
+            if issued.is_empty() {
+                break;
+            }
+            let status_now = mz_ore::now::to_datetime((self.now)());
+            for id in issued {
+                let instance_id =
+                    self.collections
+                        .get(&id)
+                        .and_then(|c| match &c.description.data_source {
+                            DataSource::Ingestion(ingestion) => Some(ingestion.instance_id),
+                            _ => None,
+                        });
+                let has_replica = instance_id.map_or(false, |i| self.replicas.contains_key(&i));
+
+                // Without a replica, the ingestion has no dataflow to restart. It picks up the
+                // change once it is scheduled on a replica.
+                if !self.run_ingestion(id) || !has_replica {
+                    self.connection_restarts.complete(id);
+                    continue;
+                }
+
+                let mut update = StatusUpdate::new(id, status_now, Status::Starting);
+                update
+                    .hints
+                    .insert("restarting to apply connection change".into());
+                updates.push(update);
+            }
+        }
+
+        if !updates.is_empty() {
+            self.collection_status_manager
+                .append_updates(updates, IntrospectionType::SourceStatusHistory, None)
+                .await;
+        }
+    }
+
+    /// Sends the command for the ingestion `id` to its instance, stamped with the current
+    /// parameter generation and the snapshot of its snapshot group, if any. Returns whether the
+    /// command was sent.
+    fn run_ingestion(&mut self, id: GlobalId) -> bool {
+        // The ingestion is run once the snapshot of its group is exported.
+        if self.snapshot_groups.is_deferred(id) {
+            return false;
+        }
+
+        let ingestion = match self
+            .collections
+            .get(&id)
+            .map(|c| &c.description.data_source)
+        {
+            Some(DataSource::Ingestion(ingestion)) => ingestion.clone(),
+            _ => return false,
+        };
+        let description = match self.enrich_ingestion(id, ingestion) {
+            Ok(description) => description,
+            // Some of the ingestion's collections are being dropped.
+            Err(e) => {
+                debug!("not re-running ingestion {id}: {e}");
+                return false;
+            }
+        };
+        let parameter_generation = self.config.parameters.generation;
+        let group_snapshot = self.snapshot_groups.snapshot(id).cloned();
+        let Some(client) = self.clients.get_mut(&description.instance_id) else {
+            return false;
+        };
+
+        info!("running ingestion {id} with parameter generation {parameter_generation}");
+        client.send(StorageCommand::RunIngestions(vec![RunIngestionCommand {
+            id,
+            description,
+            parameter_generation,
+            group_snapshot,
+        }]));
+        true
+    }
+
+    /// Adds the ingestion `id` to its snapshot group, if it is a PostgreSQL source that is a
+    /// member of one and has yet to take its snapshot.
+    fn join_snapshot_group(
+        &mut self,
+        id: GlobalId,
+        description: &IngestionDescription<CollectionMetadata>,
+    ) -> Option<snapshot_groups::Membership> {
+        let GenericSourceConnection::Postgres(connection) = &description.desc.connection else {
+            return None;
+        };
+        let group = connection.snapshot_group.as_ref()?;
+        if !self.needs_snapshot(id) {
+            return None;
+        }
+        // The snapshot of a group is exported from the primary, but these sources take their
+        // snapshots from the standby.
+        if connection.connection.snapshot_host.is_some() {
+            return Some(snapshot_groups::Membership::Independent(
+                "snapshot groups are not supported with a snapshot host",
+            ));
+        }
+
+        let key = (connection.connection_id, group.clone());
+        let window = self.config.parameters.pg_snapshot_config.group_window;
+        Some(self.snapshot_groups.join(key, id, window))
+    }
+
+    /// Reports whether any subsource of the ingestion `id` has yet to take its snapshot.
+    fn needs_snapshot(&self, id: GlobalId) -> bool {
+        let Some(DataSource::Ingestion(ingestion)) = self
+            .collections
+            .get(&id)
+            .map(|c| &c.description.data_source)
+        else {
+            return false;
+        };
+        ingestion
+            .source_exports
+            .keys()
+            .filter(|export_id| **export_id != id)
+            .any(|export_id| {
+                self.collections
+                    .get(export_id)
+                    .map_or(false, |c| c.write_frontier.elements() == &[T::minimum()])
+            })
+    }
+
+    /// Removes members of snapshot groups that have finished their snapshots from their groups.
+    fn release_snapshot_groups(&mut self) {
+        for id in self.snapshot_groups.exported_members() {
+            if !self.needs_snapshot(id) {
+                self.snapshot_groups.remove_member(id);
+            }
+        }
+    }
+
+    /// Advances the snapshot group of `event`, running its members once their snapshot is
+    /// exported and running them with snapshots of their own if it can't be used.
+    async fn process_snapshot_group_event(&mut self, event: snapshot_groups::GroupEvent) {
+        let (members, hint) = match event {
+            snapshot_groups::GroupEvent::Gathered(key) => {
+                let Some(members) = self.snapshot_groups.gathered(&key) else {
+                    return;
+                };
+                let mut connection = None;
+                let mut slots = vec![];
+                for id in members {
+                    let Some(DataSource::Ingestion(ingestion)) = self
+                        .collections
+                        .get(&id)
+                        .map(|c| &c.description.data_source)
+                    else {
+                        continue;
+                    };
+                    if let GenericSourceConnection::Postgres(pg) = &ingestion.desc.connection {
+                        slots.push(pg.publication_details.slot.clone());
+                        connection = Some(pg.connection.clone());
+                    }
+                }
+                let Some(connection) = connection else {
+                    self.snapshot_groups.remove(&key);
+                    return;
+                };
+                let timeout = self.config.parameters.pg_snapshot_config.group_timeout;
+                self.snapshot_groups
+                    .export(key, connection, slots, self.config.clone(), timeout);
+                return;
+            }
+            snapshot_groups::GroupEvent::Exported(key, Ok(snapshot)) => {
+                for id in self.snapshot_groups.exported(&key, snapshot) {
+                    self.run_ingestion(id);
+                }
+                return;
+            }
+            snapshot_groups::GroupEvent::Exported(key, Err(e)) => (
+                self.snapshot_groups.remove(&key),
+                format!("failed to export the snapshot of its snapshot group: {e}"),
+            ),
+            snapshot_groups::GroupEvent::Expired(key) => (
+                self.snapshot_groups.remove(&key),
+                "the snapshot of its snapshot group expired".to_string(),
+            ),
+        };
+        let hint = format!("taking a snapshot of its own: {hint}");
+
+        let status_now = mz_ore::now::to_datetime((self.now)());
+        let mut updates = vec![];
+        for id in members {
+            if self.run_ingestion(id) {
+                let mut update = StatusUpdate::new(id, status_now, Status::Starting);
+                update.hints.insert(hint.clone());
+                updates.push(update);
+            }
+        }
+        self.record_status_updates(updates, None).await;
+    }
+
     /// Converts an `IngestionDescription<()>` into `IngestionDescription<CollectionMetadata>`.
     fn enrich_ingestion(
         &self,
@@ -3283,8 +4129,12 @@ where
         }
     }
     /// Handles writing of status updates for sources/sinks to the appropriate
-    /// status relation
-    async fn record_status_updates(&mut self, updates: Vec<StatusUpdate>) {
+    /// status relation, recording that they were reported by `origin`
+    async fn record_status_updates(
+        &mut self,
+        updates: Vec<StatusUpdate>,
+        origin: Option<StatusOrigin>,
+    ) {
         let mut sink_status_updates = vec![];
         let mut source_status_updates = vec![];
 
@@ -3301,10 +4151,253 @@ where
             .append_updates(
                 source_status_updates,
                 IntrospectionType::SourceStatusHistory,
+                origin,
             )
             .await;
         self.collection_status_manager
-            .append_updates(sink_status_updates, IntrospectionType::SinkStatusHistory)
+            .append_updates(
+                sink_status_updates,
+                IntrospectionType::SinkStatusHistory,
+                origin,
+            )
             .await;
     }
 }
+
+/// Returns the updates that move the row of `id` in a per-source introspection collection, like
+/// `IntrospectionType::SourceUpstreamLag`, from `previous` to `current`. A `None` value has no
+/// row.
+fn source_value_updates(
+    id: GlobalId,
+    previous: Option<u64>,
+    current: Option<u64>,
+) -> Vec<(Row, Diff)> {
+    if previous == current {
+        return vec![];
+    }
+
+    let pack =
+        |value: u64| Row::pack_slice(&[Datum::String(&id.to_string()), Datum::UInt64(value)]);
+
+    let mut updates = vec![];
+    if let Some(value) = previous {
+        updates.push((pack(value), -1));
+    }
+    if let Some(value) = current {
+        updates.push((pack(value), 1));
+    }
+    updates
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_build_info::DUMMY_BUILD_INFO;
+    use mz_ore::cast::CastFrom;
+    use mz_ore::now::SYSTEM_TIME;
+    use mz_secrets::{InMemorySecretsController, SecretsController};
+    use mz_stash::{DebugStashFactory, Stash};
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_persist_txn_tables_transition() {
+        use PersistTxnTablesImpl::*;
+
+        for (durable, configured) in [
+            (None, Eager),
+            (None, Lazy),
+            (Some(Eager), Eager),
+            (Some(Eager), Lazy),
+            (Some(Lazy), Lazy),
+        ] {
+            assert!(
+                check_persist_txn_tables_transition(durable, configured).is_ok(),
+                "{durable:?} -> {configured:?}"
+            );
+        }
+
+        let err = check_persist_txn_tables_transition(Some(Lazy), Eager).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "storage error: persist-txn tables were last operated in lazy mode and cannot be \
+            switched to eager mode"
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_source_value_updates() {
+        let id = GlobalId::User(1);
+        let row = |value| Row::pack_slice(&[Datum::String("u1"), Datum::UInt64(value)]);
+
+        // The first report inserts a row.
+        assert_eq!(source_value_updates(id, None, Some(10)), vec![(row(10), 1)]);
+        // An unchanged report is not recorded again.
+        assert_eq!(source_value_updates(id, Some(10), Some(10)), vec![]);
+        // A changed report replaces the previous row.
+        assert_eq!(
+            source_value_updates(id, Some(10), Some(3)),
+            vec![(row(10), -1), (row(3), 1)]
+        );
+        // Dropping the source retracts its row.
+        assert_eq!(source_value_updates(id, Some(3), None), vec![(row(3), -1)]);
+        assert_eq!(source_value_updates(id, None, None), vec![]);
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+    async fn test_record_persist_txn_tables() {
+        Stash::with_debug_stash(|mut stash| async move {
+            // The upgrade path: a fresh environment starts in eager mode, and is
+            // then migrated to lazy mode.
+            assert_eq!(
+                record_persist_txn_tables(&mut stash, PersistTxnTablesImpl::Eager)
+                    .await
+                    .unwrap(),
+                None
+            );
+            assert_eq!(
+                record_persist_txn_tables(&mut stash, PersistTxnTablesImpl::Lazy)
+                    .await
+                    .unwrap(),
+                Some(PersistTxnTablesImpl::Eager)
+            );
+            assert_eq!(
+                record_persist_txn_tables(&mut stash, PersistTxnTablesImpl::Lazy)
+                    .await
+                    .unwrap(),
+                Some(PersistTxnTablesImpl::Lazy)
+            );
+
+            // Rolling back to eager mode is refused and leaves the record
+            // untouched.
+            let err = record_persist_txn_tables(&mut stash, PersistTxnTablesImpl::Eager)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    StorageError::PersistTxnTablesRegression {
+                        durable: PersistTxnTablesImpl::Lazy,
+                        configured: PersistTxnTablesImpl::Eager,
+                    }
+                ),
+                "{err:?}"
+            );
+            assert_eq!(
+                PERSIST_TXN_TABLES
+                    .peek_key_one(&mut stash, ())
+                    .await
+                    .unwrap(),
+                Some(u64::from(PersistTxnTablesImpl::Lazy))
+            );
+        })
+        .await
+        .unwrap();
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `TLS_client_method` on OS `linux`
+    async fn test_large_drop_is_processed_in_batches() {
+        const COLLECTIONS: u64 = 5_000;
+        const BATCH_SIZE: usize = 1_000;
+
+        let factory = DebugStashFactory::new().await;
+        let secrets_controller = InMemorySecretsController::new();
+        let metrics_registry = MetricsRegistry::new();
+        let mut controller = Controller::<mz_repr::Timestamp>::new(
+            &DUMMY_BUILD_INFO,
+            format!(
+                "{}?options=--search_path={}",
+                factory.url(),
+                factory.schema()
+            ),
+            PersistLocation::new_in_mem(),
+            Arc::new(PersistClientCache::new_no_metrics()),
+            SYSTEM_TIME.clone(),
+            Arc::new(StashMetrics::register_into(&metrics_registry)),
+            NonZeroI64::new(1).expect("nonzero"),
+            metrics_registry,
+            PersistTxnTablesImpl::Eager,
+            ConnectionContext::for_tests(secrets_controller.reader()),
+        )
+        .await
+        .expect("creating controller");
+        controller
+            .init_txns(mz_repr::Timestamp::minimum())
+            .await
+            .expect("initializing txns");
+        controller.update_parameters(StorageParameters {
+            dropped_ids_batch_size: BATCH_SIZE,
+            ..Default::default()
+        });
+
+        let ids: BTreeSet<_> = (1..=COLLECTIONS).map(GlobalId::User).collect();
+        let collections = ids
+            .iter()
+            .map(|id| {
+                let desc = CollectionDescription::from_desc(
+                    RelationDesc::empty(),
+                    DataSourceOther::Compute,
+                );
+                (*id, desc)
+            })
+            .collect();
+        controller
+            .create_collections(None, collections)
+            .await
+            .expect("creating collections");
+        controller.drop_sources_unvalidated(ids.iter().copied().collect());
+
+        // Stand in for a replica that reports all of the collections as dropped at once, like it
+        // does for the subsources of a dropped source.
+        controller
+            .internal_response_sender
+            .send(StorageResponse::DroppedIds(ids.clone()))
+            .expect("controller alive");
+
+        let mut batches = 0;
+        let mut interleaved = false;
+        while ids.iter().any(|id| controller.collections.contains_key(id)) {
+            let before = controller.collections.len();
+            controller.ready().await;
+            let response = controller.process().await.expect("processing");
+            let cleaned = before - controller.collections.len();
+            assert!(
+                cleaned <= BATCH_SIZE,
+                "cleaned up after {cleaned} collections"
+            );
+            if cleaned == 0 {
+                continue;
+            }
+            batches += 1;
+
+            match response {
+                Some(Response::FrontierUpdates(updates)) if updates.is_empty() => {
+                    // Other work that arrives in the meantime is handled before the remaining
+                    // drops.
+                    assert!(!controller.pending_drops.is_empty());
+                    interleaved = true;
+                }
+                _ if batches == 1 => controller
+                    .internal_response_sender
+                    .send(StorageResponse::FrontierUppers(vec![]))
+                    .expect("controller alive"),
+                _ => (),
+            }
+        }
+        assert!(interleaved);
+        assert_eq!(batches, COLLECTIONS / u64::cast_from(BATCH_SIZE));
+        assert!(controller.pending_drops.is_empty());
+
+        let durable_ids: BTreeSet<_> = METADATA_COLLECTION
+            .peek_one(&mut controller.stash)
+            .await
+            .expect("reading stash")
+            .into_keys()
+            .map(|id| GlobalId::from_proto(id).expect("valid id"))
+            .collect();
+        assert!(durable_ids.is_disjoint(&ids));
+
+        factory.drop().await;
+    }
+}
@@ -17,6 +17,8 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 use std::num::NonZeroI64;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::anyhow;
@@ -24,18 +26,22 @@ use differential_dataflow::lattice::Lattice;
 use futures::{Stream, StreamExt};
 use mz_build_info::BuildInfo;
 use mz_cluster_client::client::{ClusterReplicaLocation, ClusterStartupEpoch, TimelyConfig};
+use mz_cluster_client::command_log::{CommandProtocol, CommandRecorder};
 use mz_ore::now::NowFn;
 use mz_ore::retry::Retry;
 use mz_ore::task::AbortOnDropHandle;
 use mz_persist_types::Codec64;
+use mz_proto::RustType;
 use mz_repr::GlobalId;
 use mz_service::client::{GenericClient, Partitioned};
 use mz_service::params::GrpcClientParameters;
 use mz_storage_client::client::{
-    RunIngestionCommand, RunSinkCommand, Status, StorageClient, StorageCommand, StorageGrpcClient,
-    StorageResponse,
+    ProtoStorageCommand, ResponseOrderViolation, RunIngestionCommand, RunSinkCommand, Status,
+    StorageClient, StorageCommand, StorageGrpcClient, StorageResponse,
 };
+use mz_storage_client::controller::CollectionNames;
 use mz_storage_client::metrics::RehydratingStorageClientMetrics;
+use mz_storage_types::instances::StorageInstanceId;
 use mz_storage_types::parameters::StorageParameters;
 use timely::progress::{Antichain, Timestamp};
 use timely::PartialOrder;
@@ -52,6 +58,13 @@ use tracing::warn;
 pub struct RehydratingStorageClient<T> {
     command_tx: UnboundedSender<RehydrationCommand<T>>,
     response_rx: UnboundedReceiverStream<StorageResponse<T>>,
+    /// The number of commands sent through this client that have not yet been delivered to a
+    /// replica.
+    pending_commands: Arc<AtomicUsize>,
+    /// The ID of the instance the client connects to, for recording commands.
+    instance_id: StorageInstanceId,
+    /// The recorder of the commands sent through this client.
+    recorder: CommandRecorder,
     _task: AbortOnDropHandle<()>,
 }
 
@@ -61,18 +74,23 @@ impl<T> RehydratingStorageClient<T>
 where
     T: Timestamp + Lattice + Codec64,
     StorageGrpcClient: StorageClient<T>,
+    StorageCommand<T>: RustType<ProtoStorageCommand>,
 {
     /// Creates a `RehydratingStorageClient` that is not yet connected to
     /// a storage replica.
     pub fn new(
+        instance_id: StorageInstanceId,
         build_info: &'static BuildInfo,
         metrics: RehydratingStorageClientMetrics,
         envd_epoch: NonZeroI64,
         grpc_client_params: GrpcClientParameters,
         now: NowFn,
+        names: CollectionNames,
+        recorder: CommandRecorder,
     ) -> RehydratingStorageClient<T> {
         let (command_tx, command_rx) = unbounded_channel();
         let (response_tx, response_rx) = unbounded_channel();
+        let pending_commands = Arc::new(AtomicUsize::new(0));
         let mut task = RehydrationTask {
             build_info,
             command_rx,
@@ -87,11 +105,17 @@ where
             metrics,
             grpc_client_params,
             now,
+            names,
+            pending_commands: Arc::clone(&pending_commands),
+            undelivered_commands: 0,
         };
         let task = mz_ore::task::spawn(|| "rehydration", async move { task.run().await });
         RehydratingStorageClient {
             command_tx,
             response_rx: UnboundedReceiverStream::new(response_rx),
+            pending_commands,
+            instance_id,
+            recorder,
             _task: task.abort_on_drop(),
         }
     }
@@ -112,11 +136,20 @@ where
 
     /// Sends a command to the underlying client.
     pub fn send(&mut self, cmd: StorageCommand<T>) {
+        self.recorder
+            .record(CommandProtocol::Storage, self.instance_id, &cmd);
+        self.pending_commands.fetch_add(1, Ordering::Relaxed);
         self.command_tx
             .send(RehydrationCommand::Send(cmd))
             .expect("rehydration task should not drop first");
     }
 
+    /// Returns the number of commands sent through this client that have not yet been delivered
+    /// to a replica, e.g. because no replica is connected.
+    pub fn pending_commands(&self) -> usize {
+        self.pending_commands.load(Ordering::Relaxed)
+    }
+
     /// Returns a stream that produces responses from the underlying client.
     pub fn response_stream(&mut self) -> impl Stream<Item = StorageResponse<T>> + '_ {
         &mut self.response_rx
@@ -170,6 +203,14 @@ where
     grpc_client_params: GrpcClientParameters,
     /// A function that returns the current time.
     now: NowFn,
+    /// Names with which collections are identified in panics and errors.
+    names: CollectionNames,
+    /// The number of commands sent through the client that have not yet been delivered to a
+    /// replica, shared with the client.
+    pending_commands: Arc<AtomicUsize>,
+    /// The number of commands absorbed since the replica last received all of them, either
+    /// directly or as part of a rehydration.
+    undelivered_commands: usize,
 }
 
 enum RehydrationTaskState<T: Timestamp + Lattice> {
@@ -197,6 +238,7 @@ impl<T> RehydrationTask<T>
 where
     T: Timestamp + Lattice + Codec64,
     StorageGrpcClient: StorageClient<T>,
+    StorageCommand<T>: RustType<ProtoStorageCommand>,
 {
     async fn run(&mut self) {
         let mut state = RehydrationTaskState::AwaitAddress;
@@ -291,7 +333,7 @@ where
                 StorageGrpcClient::connect_partitioned(dests, version, &self.grpc_client_params)
                     .await;
 
-            let client = match client {
+            let mut client = match client {
                 Ok(client) => client,
                 Err(e) => {
                     if state.i >= mz_service::retry::INFO_MIN_RETRIES {
@@ -310,6 +352,7 @@ where
                     continue;
                 }
             };
+            client.state_mut().set_collection_names(self.names.clone());
 
             // The first epoch we actually send to the cluster will be `1`, just like compute.
             let new_epoch = ClusterStartupEpoch::new(
@@ -325,7 +368,12 @@ where
             break (client, timely_command);
         };
 
-        // Rehydrate all commands.
+        let commands = self.rehydration_commands(timely_command);
+        self.send_commands(location, client, commands).await
+    }
+
+    /// Returns the commands that bring a replica that starts with `timely_command` up to date.
+    fn rehydration_commands(&self, timely_command: StorageCommand<T>) -> Vec<StorageCommand<T>> {
         let mut commands = vec![
             timely_command,
             StorageCommand::UpdateConfiguration(self.config.clone()),
@@ -341,7 +389,7 @@ where
         if self.initialized {
             commands.push(StorageCommand::InitializationComplete)
         }
-        self.send_commands(location, client, commands).await
+        commands
     }
 
     async fn step_pump(
@@ -425,11 +473,25 @@ where
         mut client: PartitionedClient<T>,
         commands: impl IntoIterator<Item = StorageCommand<T>>,
     ) -> RehydrationTaskState<T> {
+        // Large commands are split so that no single message exceeds the
+        // gRPC message size limit. The chunks are absorbed cumulatively by
+        // the replica, so this is transparent to it.
+        let max_bytes = self.config.storage_command_max_bytes;
+        // Commands that can't be split, like a single very wide ingestion,
+        // are sent in chunks if enabled, which the replica reassembles.
+        client.set_command_chunk_bytes(self.config.storage_command_chunking.then_some(max_bytes));
+        let commands = commands
+            .into_iter()
+            .flat_map(|command| command.split_by_size(max_bytes));
         for command in commands {
             if let Err(e) = client.send(command).await {
                 return self.send_response(location.clone(), client, Err(e));
             }
         }
+        // The replica now reflects every command absorbed so far.
+        self.pending_commands
+            .fetch_sub(self.undelivered_commands, Ordering::Relaxed);
+        self.undelivered_commands = 0;
         RehydrationTaskState::Pump { location, client }
     }
 
@@ -452,6 +514,9 @@ where
                 }
             }
             Err(e) => {
+                if e.is::<ResponseOrderViolation>() {
+                    self.metrics.inc_response_order_violations();
+                }
                 warn!("storage cluster produced error, reconnecting: {e}");
                 RehydrationTaskState::Rehydrate { location }
             }
@@ -459,6 +524,7 @@ where
     }
 
     fn absorb_command(&mut self, command: &StorageCommand<T>) {
+        self.undelivered_commands += 1;
         match command {
             StorageCommand::CreateTimely { .. } => {
                 // We assume these are ordered correctly
@@ -480,7 +546,14 @@ where
             }
             StorageCommand::RunSinks(exports) => {
                 for export in exports {
-                    self.sinks.insert(export.id, export.clone());
+                    // Progress is reset by the command that requests it. A replica that is
+                    // rehydrated later resumes from the reset progress rather than resetting it
+                    // again.
+                    let export = RunSinkCommand {
+                        reset_progress: false,
+                        ..export.clone()
+                    };
+                    self.sinks.insert(export.id, export);
                     self.uppers
                         .entry(export.id)
                         .or_insert(Antichain::from_elem(T::minimum()));
@@ -500,6 +573,10 @@ where
                     }
                 }
             }
+            StorageCommand::ReportCollectionState(_) => {
+                // Diagnostic requests are not replayed. A request that a replica doesn't answer
+                // before it reconnects simply goes unanswered.
+            }
         }
     }
 
@@ -543,6 +620,131 @@ where
                 // Just forward it along.
                 Some(StorageResponse::StatusUpdates(updates))
             }
+            StorageResponse::UpstreamLag { id, bytes_behind } => {
+                // Just forward it along.
+                Some(StorageResponse::UpstreamLag { id, bytes_behind })
+            }
+            StorageResponse::ParameterGeneration { id, generation } => {
+                // Just forward it along.
+                Some(StorageResponse::ParameterGeneration { id, generation })
+            }
+            StorageResponse::SinkProgress { id, emitted_upper } => {
+                // Just forward it along.
+                Some(StorageResponse::SinkProgress { id, emitted_upper })
+            }
+            StorageResponse::CollectionStateReport(reports) => {
+                // Just forward it along.
+                Some(StorageResponse::CollectionStateReport(reports))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_build_info::DUMMY_BUILD_INFO;
+    use mz_ore::metrics::MetricsRegistry;
+    use mz_ore::now::SYSTEM_TIME;
+    use mz_storage_client::metrics::StorageControllerMetrics;
+    use mz_storage_types::instances::StorageInstanceId;
+    use proptest::prelude::*;
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+
+    use super::*;
+
+    /// Returns a task that has not absorbed any commands, which is not running.
+    fn idle_task() -> RehydrationTask<mz_repr::Timestamp> {
+        let (_command_tx, command_rx) = unbounded_channel();
+        let (response_tx, _response_rx) = unbounded_channel();
+        RehydrationTask {
+            build_info: &DUMMY_BUILD_INFO,
+            command_rx,
+            response_tx,
+            sources: BTreeMap::new(),
+            sinks: BTreeMap::new(),
+            uppers: BTreeMap::new(),
+            sinces: BTreeMap::new(),
+            initialized: false,
+            current_epoch: ClusterStartupEpoch::new(NonZeroI64::new(1).unwrap(), 0),
+            config: Default::default(),
+            metrics: StorageControllerMetrics::new(MetricsRegistry::new())
+                .for_instance(StorageInstanceId::User(1)),
+            grpc_client_params: GrpcClientParameters::default(),
+            now: SYSTEM_TIME.clone(),
+            names: CollectionNames::default(),
+            pending_commands: Arc::new(AtomicUsize::new(0)),
+            undelivered_commands: 0,
         }
     }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // too slow
+    fn test_rehydration_does_not_reset_progress_again() {
+        let mut runner = TestRunner::deterministic();
+        let mut export = any::<RunSinkCommand<mz_repr::Timestamp>>()
+            .new_tree(&mut runner)
+            .unwrap()
+            .current();
+        export.reset_progress = true;
+
+        let mut task = idle_task();
+        task.absorb_command(&StorageCommand::RunSinks(vec![export.clone()]));
+
+        // The replica that received the command reset the sink's progress, so a replica that is
+        // rehydrated after a reconnect resumes from it.
+        let timely_command = StorageCommand::CreateTimely {
+            config: Default::default(),
+            epoch: task.current_epoch,
+        };
+        let sinks: Vec<_> = task
+            .rehydration_commands(timely_command)
+            .into_iter()
+            .filter_map(|command| match command {
+                StorageCommand::RunSinks(sinks) => Some(sinks),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        assert_eq!(
+            sinks,
+            vec![RunSinkCommand {
+                reset_progress: false,
+                ..export
+            }]
+        );
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `socket` on OS `linux`
+    async fn test_pending_commands() {
+        let metrics = StorageControllerMetrics::new(MetricsRegistry::new())
+            .for_instance(StorageInstanceId::User(1));
+        let mut client = RehydratingStorageClient::<mz_repr::Timestamp>::new(
+            StorageInstanceId::User(1),
+            &DUMMY_BUILD_INFO,
+            metrics,
+            NonZeroI64::new(1).unwrap(),
+            GrpcClientParameters::default(),
+            SYSTEM_TIME.clone(),
+            CollectionNames::default(),
+            CommandRecorder::default(),
+        );
+
+        // Commands are pending while there is no replica to deliver them to.
+        client.send(StorageCommand::InitializationComplete);
+        client.send(StorageCommand::AllowCompaction(vec![]));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(client.pending_commands(), 2);
+
+        // They remain pending while the replica cannot be reached.
+        client.connect(ClusterReplicaLocation {
+            ctl_addrs: vec!["localhost:1".into()],
+            dataflow_addrs: vec!["localhost:2".into()],
+            workers: 1,
+        });
+        client.send(StorageCommand::InitializationComplete);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(client.pending_commands(), 3);
+    }
 }
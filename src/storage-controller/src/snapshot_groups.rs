@@ -0,0 +1,349 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Bookkeeping for snapshot groups, i.e. PostgreSQL sources that take their initial snapshots at
+//! the same LSN.
+//!
+//! Sources that name the same `SNAPSHOT GROUP` and use the same connection are members of the same
+//! group. Ingestions of members that still need to take their snapshot are not run right away.
+//! Instead, members are gathered for `pg_source_snapshot_group_window`, after which a single
+//! snapshot is exported for all of them and they are run with it. The session that exported the
+//! snapshot, and thereby keeps it importable, is held open until all members have finished their
+//! snapshots or until `pg_source_snapshot_group_timeout` has passed, whichever comes first.
+//!
+//! Members that join a group after its snapshot was exported, or whose group snapshot could not be
+//! exported, take snapshots of their own.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use mz_repr::GlobalId;
+use mz_storage_types::configuration::StorageConfiguration;
+use mz_storage_types::connections::PostgresConnection;
+use mz_storage_types::sources::postgres::GroupSnapshot;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{info, warn};
+
+/// The interval at which the session holding a group snapshot is checked for having been closed.
+const CLOSED_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Identifies a snapshot group by the ID of the connection its members use and its name.
+pub(super) type GroupKey = (GlobalId, String);
+
+/// Progress of a snapshot group that is reported by the tasks working on its behalf.
+#[derive(Debug)]
+pub(super) enum GroupEvent {
+    /// The window in which members join the group has closed.
+    Gathered(GroupKey),
+    /// The snapshot of the group was exported, or failed to.
+    Exported(GroupKey, Result<GroupSnapshot, String>),
+    /// The session holding the snapshot of the group timed out or was closed.
+    Expired(GroupKey),
+}
+
+/// How an ingestion that joined its snapshot group is to be run.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Membership {
+    /// The ingestion is run once the snapshot of its group is exported.
+    Deferred,
+    /// The ingestion is run right away and takes a snapshot of its own, for the given reason.
+    Independent(&'static str),
+}
+
+#[derive(Debug)]
+enum GroupState {
+    /// The group gathers members until its window closes.
+    Gathering,
+    /// The snapshot of the group is being exported.
+    Exporting {
+        /// Dropped to release the snapshot.
+        _release: oneshot::Sender<()>,
+    },
+    /// The snapshot of the group was exported and can be imported by its members.
+    Exported {
+        snapshot: GroupSnapshot,
+        /// Dropped to release the snapshot.
+        _release: oneshot::Sender<()>,
+    },
+}
+
+#[derive(Debug)]
+struct Group {
+    /// The members of the group that have yet to finish their snapshots.
+    members: BTreeSet<GlobalId>,
+    state: GroupState,
+}
+
+#[derive(Debug)]
+pub(super) struct SnapshotGroups {
+    groups: BTreeMap<GroupKey, Group>,
+    events_tx: mpsc::UnboundedSender<GroupEvent>,
+    events_rx: mpsc::UnboundedReceiver<GroupEvent>,
+}
+
+impl SnapshotGroups {
+    pub(super) fn new() -> Self {
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
+        SnapshotGroups {
+            groups: BTreeMap::new(),
+            events_tx,
+            events_rx,
+        }
+    }
+
+    /// Returns the next event of any snapshot group.
+    ///
+    /// This method is cancellation safe.
+    pub(super) async fn next_event(&mut self) -> Option<GroupEvent> {
+        self.events_rx.recv().await
+    }
+
+    /// Adds the ingestion `id`, which needs to take its snapshot, to the group `key`.
+    ///
+    /// The first member of a group opens its gathering window, which closes after `window`.
+    pub(super) fn join(&mut self, key: GroupKey, id: GlobalId, window: Duration) -> Membership {
+        if let Some(group) = self.groups.get_mut(&key) {
+            return match group.state {
+                GroupState::Gathering => {
+                    group.members.insert(id);
+                    Membership::Deferred
+                }
+                GroupState::Exporting { .. } | GroupState::Exported { .. } => {
+                    Membership::Independent(
+                        "joined the snapshot group after its snapshot was taken",
+                    )
+                }
+            };
+        }
+
+        let events_tx = self.events_tx.clone();
+        let gathered = key.clone();
+        mz_ore::task::spawn(
+            || format!("snapshot_group_window({}, {})", key.0, key.1),
+            async move {
+                tokio::time::sleep(window).await;
+                let _ = events_tx.send(GroupEvent::Gathered(gathered));
+            },
+        );
+        self.groups.insert(
+            key,
+            Group {
+                members: BTreeSet::from([id]),
+                state: GroupState::Gathering,
+            },
+        );
+        Membership::Deferred
+    }
+
+    /// Closes the gathering window of the group `key`, returning its members. Their snapshot is
+    /// to be exported with [`SnapshotGroups::export`].
+    ///
+    /// Returns nothing if all members of the group were removed while it was gathering.
+    pub(super) fn gathered(&mut self, key: &GroupKey) -> Option<BTreeSet<GlobalId>> {
+        let group = self.groups.get(key)?;
+        if group.members.is_empty() {
+            self.groups.remove(key);
+            return None;
+        }
+        Some(group.members.clone())
+    }
+
+    /// Spawns a task that exports the snapshot of the group `key` from the server of `connection`
+    /// for members that use the replication `slots`.
+    ///
+    /// The task holds the snapshot until it is released, `timeout` has passed, or the server
+    /// closes the session.
+    pub(super) fn export(
+        &mut self,
+        key: GroupKey,
+        connection: PostgresConnection,
+        slots: Vec<String>,
+        storage_configuration: StorageConfiguration,
+        timeout: Duration,
+    ) {
+        let Some(group) = self.groups.get_mut(&key) else {
+            return;
+        };
+        let (release_tx, mut release_rx) = oneshot::channel();
+        group.state = GroupState::Exporting {
+            _release: release_tx,
+        };
+
+        let events_tx = self.events_tx.clone();
+        mz_ore::task::spawn(
+            || format!("snapshot_group_export({}, {})", key.0, key.1),
+            async move {
+                let export = GroupSnapshot::export(&connection, &slots, &storage_configuration);
+                let client = match export.await {
+                    Ok((client, snapshot)) => {
+                        info!(
+                            "exported snapshot {} at {} for snapshot group {} of connection {}",
+                            snapshot.snapshot_id, snapshot.lsn, key.1, key.0
+                        );
+                        let _ = events_tx.send(GroupEvent::Exported(key.clone(), Ok(snapshot)));
+                        client
+                    }
+                    Err(e) => {
+                        warn!(
+                            "failed to export snapshot for snapshot group {} of connection {}: {e:#}",
+                            key.1, key.0
+                        );
+                        let _ = events_tx.send(GroupEvent::Exported(key, Err(format!("{e:#}"))));
+                        return;
+                    }
+                };
+
+                let closed = async {
+                    while !client.is_closed() {
+                        tokio::time::sleep(CLOSED_CHECK_INTERVAL).await;
+                    }
+                };
+                tokio::select! {
+                    // Released, either explicitly or because the group is gone.
+                    _ = &mut release_rx => return,
+                    () = tokio::time::sleep(timeout) => {}
+                    () = closed => {}
+                }
+                let _ = events_tx.send(GroupEvent::Expired(key));
+            },
+        );
+    }
+
+    /// Records the exported `snapshot` of the group `key`, returning its members, which are to be
+    /// run with it.
+    pub(super) fn exported(
+        &mut self,
+        key: &GroupKey,
+        snapshot: GroupSnapshot,
+    ) -> BTreeSet<GlobalId> {
+        let Some(group) = self.groups.get_mut(key) else {
+            return BTreeSet::new();
+        };
+        if group.members.is_empty() {
+            self.groups.remove(key);
+            return BTreeSet::new();
+        }
+        let state = std::mem::replace(&mut group.state, GroupState::Gathering);
+        group.state = match state {
+            GroupState::Exporting { _release } => GroupState::Exported { snapshot, _release },
+            state => state,
+        };
+        group.members.clone()
+    }
+
+    /// Removes the group `key`, releasing its snapshot, and returns the members that have yet to
+    /// finish their snapshots.
+    pub(super) fn remove(&mut self, key: &GroupKey) -> BTreeSet<GlobalId> {
+        self.groups
+            .remove(key)
+            .map(|group| group.members)
+            .unwrap_or_default()
+    }
+
+    /// Removes the ingestion `id` from its group, e.g. because it finished its snapshot or was
+    /// dropped. The snapshot of the group is released once no member needs it anymore.
+    pub(super) fn remove_member(&mut self, id: GlobalId) {
+        let Some((key, group)) = self
+            .groups
+            .iter_mut()
+            .find(|(_, group)| group.members.contains(&id))
+        else {
+            return;
+        };
+        group.members.remove(&id);
+        // Groups that are still gathering or exporting are cleaned up once they progress.
+        if group.members.is_empty() && matches!(group.state, GroupState::Exported { .. }) {
+            let key = key.clone();
+            self.groups.remove(&key);
+        }
+    }
+
+    /// Returns the members of groups whose snapshot was exported.
+    pub(super) fn exported_members(&self) -> Vec<GlobalId> {
+        self.groups
+            .values()
+            .filter(|group| matches!(group.state, GroupState::Exported { .. }))
+            .flat_map(|group| group.members.iter().copied())
+            .collect()
+    }
+
+    /// Reports whether the ingestion `id` waits for the snapshot of its group to be exported.
+    pub(super) fn is_deferred(&self, id: GlobalId) -> bool {
+        self.groups.values().any(|group| {
+            group.members.contains(&id) && !matches!(group.state, GroupState::Exported { .. })
+        })
+    }
+
+    /// Returns the snapshot the ingestion `id` is to import, if it is a member of a group whose
+    /// snapshot was exported.
+    pub(super) fn snapshot(&self, id: GlobalId) -> Option<&GroupSnapshot> {
+        self.groups
+            .values()
+            .find(|group| group.members.contains(&id))
+            .and_then(|group| match &group.state {
+                GroupState::Exported { snapshot, .. } => Some(snapshot),
+                GroupState::Gathering | GroupState::Exporting { .. } => None,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_storage_types::sources::MzOffset;
+
+    use super::*;
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `epoll_wait` on OS `linux`
+    async fn test_snapshot_groups() {
+        let mut groups = SnapshotGroups::new();
+        let key = (GlobalId::User(1), "g".to_string());
+        let (a, b, c) = (GlobalId::User(2), GlobalId::User(3), GlobalId::User(4));
+
+        let window = Duration::from_millis(10);
+        assert_eq!(groups.join(key.clone(), a, window), Membership::Deferred);
+        assert_eq!(groups.join(key.clone(), b, window), Membership::Deferred);
+        assert!(groups.is_deferred(a));
+
+        match groups.next_event().await {
+            Some(GroupEvent::Gathered(gathered)) => assert_eq!(gathered, key),
+            event => panic!("unexpected event: {event:?}"),
+        }
+        assert_eq!(groups.gathered(&key), Some(BTreeSet::from([a, b])));
+
+        // Pretend to export the snapshot, without connecting anywhere.
+        let (release_tx, _release_rx) = oneshot::channel();
+        groups.groups.get_mut(&key).unwrap().state = GroupState::Exporting {
+            _release: release_tx,
+        };
+        assert!(matches!(
+            groups.join(key.clone(), c, window),
+            Membership::Independent(_)
+        ));
+
+        let snapshot = GroupSnapshot {
+            snapshot_id: "00000003-00000002-1".into(),
+            lsn: MzOffset::from(42),
+        };
+        assert_eq!(
+            groups.exported(&key, snapshot.clone()),
+            BTreeSet::from([a, b])
+        );
+        assert!(!groups.is_deferred(a));
+        assert_eq!(groups.snapshot(b), Some(&snapshot));
+        assert_eq!(groups.exported_members(), vec![a, b]);
+
+        // The group is released once all members have finished their snapshots.
+        groups.remove_member(a);
+        assert_eq!(groups.snapshot(b), Some(&snapshot));
+        groups.remove_member(b);
+        assert!(groups.groups.is_empty());
+        assert_eq!(groups.snapshot(b), None);
+    }
+}
@@ -1032,6 +1032,9 @@ pub struct PostgresConnection<C: ConnectionAccess = InlinedConnection> {
     pub tls_root_cert: Option<StringOrSecret>,
     /// An optional TLS client certificate for authentication.
     pub tls_identity: Option<TlsIdentity>,
+    /// The hostname of a hot standby of the server to take the initial snapshot of tables from,
+    /// instead of the server itself.
+    pub snapshot_host: Option<String>,
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<PostgresConnection, R>
@@ -1048,6 +1051,7 @@ impl<R: ConnectionResolver> IntoInlineConnection<PostgresConnection, R>
             tls_mode,
             tls_root_cert,
             tls_identity,
+            snapshot_host,
         } = self;
 
         PostgresConnection {
@@ -1060,6 +1064,7 @@ impl<R: ConnectionResolver> IntoInlineConnection<PostgresConnection, R>
             tls_mode,
             tls_root_cert,
             tls_identity,
+            snapshot_host,
         }
     }
 }
@@ -1133,6 +1138,28 @@ impl PostgresConnection<InlinedConnection> {
         )?)
     }
 
+    /// Returns the configuration to connect to the server that the initial snapshot of tables is
+    /// taken from, i.e. the `snapshot_host` if there is one and the server itself otherwise.
+    pub async fn snapshot_config(
+        &self,
+        secrets_reader: &dyn mz_secrets::SecretsReader,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<mz_postgres_util::Config, anyhow::Error> {
+        match &self.snapshot_host {
+            Some(snapshot_host) => {
+                let connection = PostgresConnection {
+                    host: snapshot_host.clone(),
+                    snapshot_host: None,
+                    ..self.clone()
+                };
+                connection
+                    .config(secrets_reader, storage_configuration)
+                    .await
+            }
+            None => self.config(secrets_reader, storage_configuration).await,
+        }
+    }
+
     async fn validate(
         &self,
         _id: GlobalId,
@@ -1150,6 +1177,32 @@ impl PostgresConnection<InlinedConnection> {
                 &storage_configuration.connection_context.ssh_tunnel_manager,
             )
             .await?;
+
+        if let Some(snapshot_host) = &self.snapshot_host {
+            let config = self
+                .snapshot_config(
+                    &*storage_configuration.connection_context.secrets_reader,
+                    storage_configuration,
+                )
+                .await?;
+            let client = config
+                .connect(
+                    "connection validation",
+                    &storage_configuration.connection_context.ssh_tunnel_manager,
+                )
+                .await?;
+            // Snapshots are only consistent with the replication stream if they are taken at a
+            // known replay LSN, which only standbys have.
+            let in_recovery: bool = client
+                .query_one("SELECT pg_is_in_recovery()", &[])
+                .await?
+                .get(0);
+            if !in_recovery {
+                return Err(anyhow!(
+                    "snapshot host {snapshot_host} is not a standby server"
+                ));
+            }
+        }
         Ok(())
     }
 }
@@ -1166,6 +1219,7 @@ impl RustType<ProtoPostgresConnection> for PostgresConnection {
             tls_root_cert: self.tls_root_cert.into_proto(),
             tls_identity: self.tls_identity.into_proto(),
             tunnel: Some(self.tunnel.into_proto()),
+            snapshot_host: self.snapshot_host.into_proto(),
         }
     }
 
@@ -1186,6 +1240,7 @@ impl RustType<ProtoPostgresConnection> for PostgresConnection {
                 .into_rust_if_some("ProtoPostgresConnection::tls_mode")?,
             tls_root_cert: proto.tls_root_cert.into_rust()?,
             tls_identity: proto.tls_identity.into_rust()?,
+            snapshot_host: proto.snapshot_host,
         })
     }
 }
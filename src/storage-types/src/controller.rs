@@ -201,6 +201,19 @@ pub enum StorageError {
     ResourceExhausted(&'static str),
     /// The specified component is shutting down.
     ShuttingDown(&'static str),
+    /// The configured persist-txn tables mode cannot safely take over from the
+    /// mode in which the table shards were last operated.
+    PersistTxnTablesRegression {
+        durable: PersistTxnTablesImpl,
+        configured: PersistTxnTablesImpl,
+    },
+    /// The command that runs the ingestion `id` exceeds the size of the commands that can be
+    /// sent to replicas.
+    CommandTooLarge {
+        id: GlobalId,
+        size: usize,
+        limit: usize,
+    },
     /// A generic error that happens during operations of the storage controller.
     // TODO(aljoscha): Get rid of this!
     Generic(anyhow::Error),
@@ -224,6 +237,8 @@ impl Error for StorageError {
             Self::InvalidUsage(_) => None,
             Self::ResourceExhausted(_) => None,
             Self::ShuttingDown(_) => None,
+            Self::PersistTxnTablesRegression { .. } => None,
+            Self::CommandTooLarge { .. } => None,
             Self::Generic(err) => err.source(),
         }
     }
@@ -287,6 +302,21 @@ impl fmt::Display for StorageError {
             Self::InvalidUsage(err) => write!(f, "invalid usage: {}", err),
             Self::ResourceExhausted(rsc) => write!(f, "{rsc} is exhausted"),
             Self::ShuttingDown(cmp) => write!(f, "{cmp} is shutting down"),
+            Self::PersistTxnTablesRegression {
+                durable,
+                configured,
+            } => write!(
+                f,
+                "persist-txn tables were last operated in {durable} mode and cannot be \
+                switched to {configured} mode"
+            ),
+            Self::CommandTooLarge { id, size, limit } => write!(
+                f,
+                "the command to run ingestion {id} is {size} bytes, which exceeds the limit of \
+                {limit} bytes; reduce the number of tables the source ingests, e.g. by splitting \
+                its publication across several sources, or raise the limit with \
+                storage_command_max_bytes or storage_command_chunking"
+            ),
             Self::Generic(err) => std::fmt::Display::fmt(err, f),
         }
     }
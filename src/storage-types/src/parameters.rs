@@ -9,11 +9,13 @@
 
 //! Configuration parameter types.
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use mz_ore::cast::CastFrom;
 use mz_persist_client::cfg::PersistParameters;
-use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use mz_proto::{IntoRustIfSome, ProtoMapEntry, ProtoType, RustType, TryFromProtoError};
+use mz_repr::GlobalId;
 use mz_service::params::GrpcClientParameters;
 use mz_ssh_util::tunnel::SshTimeoutConfig;
 use mz_tracing::params::TracingParameters;
@@ -31,6 +33,7 @@ pub struct StorageParameters {
     pub persist: PersistParameters,
     pub pg_source_tcp_timeouts: mz_postgres_util::TcpTimeoutConfig,
     pub pg_source_snapshot_statement_timeout: Duration,
+    pub pg_source_snapshot_lock_timeout: Duration,
     pub keep_n_source_status_history_entries: usize,
     pub keep_n_sink_status_history_entries: usize,
     pub keep_n_privatelink_status_history_entries: usize,
@@ -73,10 +76,32 @@ pub struct StorageParameters {
     pub statistics_collection_interval: Duration,
     pub pg_snapshot_config: PgSourceSnapshotConfig,
     pub enable_dependency_read_hold_asserts: bool,
+    /// How long write frontiers are retained at full resolution in the
+    /// frontiers history before being rolled up.
+    pub frontiers_history_full_resolution_window: Duration,
+    /// The encoded size budget for a single command sent by the controller to
+    /// a storage replica. Larger commands are split into several smaller ones.
+    pub storage_command_max_bytes: usize,
+    /// Whether commands that exceed `storage_command_max_bytes` even after
+    /// splitting are sent in chunks of at most that size, rather than whole.
+    pub storage_command_chunking: bool,
+    /// The maximum number of dropped collections the storage controller cleans up after at a
+    /// time, so that dropping many collections at once doesn't stall it.
+    pub dropped_ids_batch_size: usize,
+    /// Per-source overrides of the parameters above, keyed by the id of the ingestion. Sources
+    /// without an entry use the global values.
+    pub source_overrides: BTreeMap<GlobalId, SourceParameterOverrides>,
+    /// The generation of these parameters. The storage controller bumps it with every
+    /// configuration update, so that replicas can tell which parameters an ingestion was
+    /// rendered with.
+    pub generation: u64,
 }
 
 pub const STATISTICS_INTERVAL_DEFAULT: Duration = Duration::from_secs(60);
 pub const STATISTICS_COLLECTION_INTERVAL_DEFAULT: Duration = Duration::from_secs(10);
+pub const FRONTIERS_HISTORY_FULL_RESOLUTION_WINDOW_DEFAULT: Duration = Duration::from_secs(60 * 60);
+pub const STORAGE_COMMAND_MAX_BYTES_DEFAULT: usize = 64 * 1024 * 1024;
+pub const DROPPED_IDS_BATCH_SIZE_DEFAULT: usize = 1000;
 
 // Implement `Default` manually, so that the default can match the
 // LD default. This is not strictly necessary, but improves clarity.
@@ -86,6 +111,7 @@ impl Default for StorageParameters {
             persist: Default::default(),
             pg_source_tcp_timeouts: Default::default(),
             pg_source_snapshot_statement_timeout: Default::default(),
+            pg_source_snapshot_lock_timeout: Default::default(),
             keep_n_source_status_history_entries: Default::default(),
             keep_n_sink_status_history_entries: Default::default(),
             keep_n_privatelink_status_history_entries: Default::default(),
@@ -104,6 +130,13 @@ impl Default for StorageParameters {
             statistics_collection_interval: STATISTICS_COLLECTION_INTERVAL_DEFAULT,
             pg_snapshot_config: Default::default(),
             enable_dependency_read_hold_asserts: true,
+            frontiers_history_full_resolution_window:
+                FRONTIERS_HISTORY_FULL_RESOLUTION_WINDOW_DEFAULT,
+            storage_command_max_bytes: STORAGE_COMMAND_MAX_BYTES_DEFAULT,
+            storage_command_chunking: Default::default(),
+            dropped_ids_batch_size: DROPPED_IDS_BATCH_SIZE_DEFAULT,
+            source_overrides: Default::default(),
+            generation: 0,
         }
     }
 }
@@ -183,6 +216,7 @@ impl StorageParameters {
             persist,
             pg_source_tcp_timeouts,
             pg_source_snapshot_statement_timeout,
+            pg_source_snapshot_lock_timeout,
             keep_n_source_status_history_entries,
             keep_n_sink_status_history_entries,
             keep_n_privatelink_status_history_entries,
@@ -201,11 +235,18 @@ impl StorageParameters {
             statistics_collection_interval,
             pg_snapshot_config,
             enable_dependency_read_hold_asserts,
+            frontiers_history_full_resolution_window,
+            storage_command_max_bytes,
+            storage_command_chunking,
+            dropped_ids_batch_size,
+            source_overrides,
+            generation,
         }: StorageParameters,
     ) {
         self.persist.update(persist);
         self.pg_source_tcp_timeouts = pg_source_tcp_timeouts;
         self.pg_source_snapshot_statement_timeout = pg_source_snapshot_statement_timeout;
+        self.pg_source_snapshot_lock_timeout = pg_source_snapshot_lock_timeout;
         self.keep_n_source_status_history_entries = keep_n_source_status_history_entries;
         self.keep_n_sink_status_history_entries = keep_n_sink_status_history_entries;
         self.keep_n_privatelink_status_history_entries = keep_n_privatelink_status_history_entries;
@@ -227,6 +268,79 @@ impl StorageParameters {
         self.statistics_collection_interval = statistics_collection_interval;
         self.pg_snapshot_config = pg_snapshot_config;
         self.enable_dependency_read_hold_asserts = enable_dependency_read_hold_asserts;
+        self.frontiers_history_full_resolution_window = frontiers_history_full_resolution_window;
+        self.storage_command_max_bytes = storage_command_max_bytes;
+        self.storage_command_chunking = storage_command_chunking;
+        self.dropped_ids_batch_size = dropped_ids_batch_size;
+        self.source_overrides = source_overrides;
+        // Generations only move forward, even if updates are applied out of order.
+        self.generation = std::cmp::max(self.generation, generation);
+    }
+
+    /// Returns the backpressure configuration for the dataflow of the source `id`, taking its
+    /// overrides into account.
+    pub fn max_inflight_bytes_config(&self, id: GlobalId) -> StorageMaxInflightBytesConfig {
+        let config = self.storage_dataflow_max_inflight_bytes_config.clone();
+        match self
+            .source_overrides
+            .get(&id)
+            .and_then(|o| o.max_inflight_bytes)
+        {
+            // An explicit budget takes precedence over one derived from the cluster size.
+            Some(max_inflight_bytes) => StorageMaxInflightBytesConfig {
+                max_inflight_bytes_default: Some(max_inflight_bytes),
+                max_inflight_bytes_cluster_size_fraction: None,
+                ..config
+            },
+            None => config,
+        }
+    }
+
+    /// Returns the PostgreSQL snapshot configuration for the source `id`, taking its overrides
+    /// into account.
+    pub fn pg_snapshot_config(&self, id: GlobalId) -> PgSourceSnapshotConfig {
+        let mut config = self.pg_snapshot_config;
+        let Some(overrides) = self.source_overrides.get(&id) else {
+            return config;
+        };
+        if let Some(bytes) = overrides.snapshot_max_inflight_bytes {
+            config.max_in_flight_bytes = bytes;
+        }
+        if let Some(read_committed) = overrides.snapshot_read_committed {
+            config.read_committed = read_committed;
+        }
+        config
+    }
+
+    /// Returns the maximum number of messages the dataflow of the source `id` timestamps at a
+    /// time, if it is limited.
+    pub fn max_batch_size(&self, id: GlobalId) -> Option<usize> {
+        self.source_overrides
+            .get(&id)
+            .and_then(|o| o.max_batch_size)
+    }
+
+    /// Returns the number of messages the dataflow of the source `id` emits before yielding, if
+    /// it is limited.
+    pub fn yield_budget(&self, id: GlobalId) -> Option<usize> {
+        self.source_overrides.get(&id).and_then(|o| o.yield_budget)
+    }
+
+    /// Returns the interval at which the statistics of the source `id` are reported, taking its
+    /// overrides into account.
+    pub fn statistics_collection_interval(&self, id: GlobalId) -> Duration {
+        self.source_overrides
+            .get(&id)
+            .and_then(|o| o.statistics_interval)
+            .unwrap_or(self.statistics_collection_interval)
+    }
+
+    /// Returns the shortest interval at which the statistics of any source are reported.
+    pub fn min_statistics_collection_interval(&self) -> Duration {
+        self.source_overrides
+            .values()
+            .filter_map(|o| o.statistics_interval)
+            .fold(self.statistics_collection_interval, std::cmp::min)
     }
 }
 
@@ -238,6 +352,9 @@ impl RustType<ProtoStorageParameters> for StorageParameters {
             pg_source_snapshot_statement_timeout: Some(
                 self.pg_source_snapshot_statement_timeout.into_proto(),
             ),
+            pg_source_snapshot_lock_timeout: Some(
+                self.pg_source_snapshot_lock_timeout.into_proto(),
+            ),
             keep_n_source_status_history_entries: u64::cast_from(
                 self.keep_n_source_status_history_entries,
             ),
@@ -266,6 +383,14 @@ impl RustType<ProtoStorageParameters> for StorageParameters {
             statistics_collection_interval: Some(self.statistics_collection_interval.into_proto()),
             pg_snapshot_config: Some(self.pg_snapshot_config.into_proto()),
             enable_dependency_read_hold_asserts: self.enable_dependency_read_hold_asserts,
+            frontiers_history_full_resolution_window: Some(
+                self.frontiers_history_full_resolution_window.into_proto(),
+            ),
+            storage_command_max_bytes: u64::cast_from(self.storage_command_max_bytes),
+            storage_command_chunking: self.storage_command_chunking,
+            dropped_ids_batch_size: u64::cast_from(self.dropped_ids_batch_size),
+            source_overrides: self.source_overrides.into_proto(),
+            generation: self.generation,
         }
     }
 
@@ -282,6 +407,9 @@ impl RustType<ProtoStorageParameters> for StorageParameters {
                 .into_rust_if_some(
                     "ProtoStorageParameters::pg_source_snapshot_statement_timeout",
                 )?,
+            pg_source_snapshot_lock_timeout: proto
+                .pg_source_snapshot_lock_timeout
+                .into_rust_if_some("ProtoStorageParameters::pg_source_snapshot_lock_timeout")?,
             keep_n_source_status_history_entries: usize::cast_from(
                 proto.keep_n_source_status_history_entries,
             ),
@@ -330,6 +458,16 @@ impl RustType<ProtoStorageParameters> for StorageParameters {
                 .pg_snapshot_config
                 .into_rust_if_some("ProtoStorageParameters::pg_snapshot_config")?,
             enable_dependency_read_hold_asserts: proto.enable_dependency_read_hold_asserts,
+            frontiers_history_full_resolution_window: proto
+                .frontiers_history_full_resolution_window
+                .into_rust_if_some(
+                    "ProtoStorageParameters::frontiers_history_full_resolution_window",
+                )?,
+            storage_command_max_bytes: usize::cast_from(proto.storage_command_max_bytes),
+            storage_command_chunking: proto.storage_command_chunking,
+            dropped_ids_batch_size: usize::cast_from(proto.dropped_ids_batch_size),
+            source_overrides: proto.source_overrides.into_rust()?,
+            generation: proto.generation,
         })
     }
 }
@@ -370,6 +508,30 @@ pub struct PgSourceSnapshotConfig {
     /// Whether or not we wait for `count(*)` to finish before beginning to read the snapshot for
     /// the given table.
     pub wait_for_count: bool,
+    /// The maximum number of raw `COPY` bytes a table reader lets be in flight to the decoding
+    /// workers. A reader pauses consuming its `COPY` stream while over this budget.
+    pub max_in_flight_bytes: usize,
+    /// The number of raw `COPY` bytes a table reader coalesces before giving them to the decoding
+    /// workers as a single message. Small `COPY` chunks are buffered up to this size to reduce the
+    /// per-message overhead of distributing them.
+    pub copy_buffer_bytes: usize,
+    /// Whether the snapshot leader peeks at the changes in the replication slot between its
+    /// consistent point and the snapshot point, and logs their number and LSN range. This is a
+    /// diagnostic for investigating rewind issues and does not consume the slot.
+    pub peek_rewind_changes: bool,
+    /// How long the storage controller waits for further members of a snapshot group to start
+    /// before it exports the snapshot that the members share.
+    pub group_window: Duration,
+    /// How long the storage controller holds the snapshot of a snapshot group open for its members
+    /// to finish their snapshots. Members that have not finished by then fall back to independent
+    /// snapshots.
+    pub group_timeout: Duration,
+    /// Whether snapshots are taken in `READ COMMITTED` transactions at the current WAL LSN of the
+    /// server, rather than at the consistent point of a temporary replication slot. This starts
+    /// up faster, but rows that are inserted while the snapshot is taken can be ingested twice,
+    /// and rows that are updated or deleted meanwhile can be ingested inconsistently. It is thus
+    /// only suitable for tables that are append-only, and whose duplicates are tolerable.
+    pub read_committed: bool,
 }
 
 impl PgSourceSnapshotConfig {
@@ -380,6 +542,12 @@ impl PgSourceSnapshotConfig {
             fallback_to_strict_count: true,
             // For now, wait to start snapshotting until after we have the count.
             wait_for_count: true,
+            max_in_flight_bytes: 256 * 1024 * 1024,
+            copy_buffer_bytes: 64 * 1024,
+            peek_rewind_changes: false,
+            group_window: Duration::from_secs(10),
+            group_timeout: Duration::from_secs(60 * 60),
+            read_committed: false,
         }
     }
 }
@@ -396,6 +564,12 @@ impl RustType<ProtoPgSourceSnapshotConfig> for PgSourceSnapshotConfig {
             collect_strict_count: self.collect_strict_count,
             fallback_to_strict_count: self.fallback_to_strict_count,
             wait_for_count: self.wait_for_count,
+            max_in_flight_bytes: u64::cast_from(self.max_in_flight_bytes),
+            copy_buffer_bytes: u64::cast_from(self.copy_buffer_bytes),
+            peek_rewind_changes: self.peek_rewind_changes,
+            group_window: Some(self.group_window.into_proto()),
+            group_timeout: Some(self.group_timeout.into_proto()),
+            read_committed: self.read_committed,
         }
     }
 
@@ -404,10 +578,80 @@ impl RustType<ProtoPgSourceSnapshotConfig> for PgSourceSnapshotConfig {
             collect_strict_count: proto.collect_strict_count,
             fallback_to_strict_count: proto.fallback_to_strict_count,
             wait_for_count: proto.wait_for_count,
+            max_in_flight_bytes: usize::cast_from(proto.max_in_flight_bytes),
+            copy_buffer_bytes: usize::cast_from(proto.copy_buffer_bytes),
+            peek_rewind_changes: proto.peek_rewind_changes,
+            group_window: proto
+                .group_window
+                .into_rust_if_some("ProtoPgSourceSnapshotConfig::group_window")?,
+            group_timeout: proto
+                .group_timeout
+                .into_rust_if_some("ProtoPgSourceSnapshotConfig::group_timeout")?,
+            read_committed: proto.read_committed,
         })
     }
 }
 
+/// Tuning parameters of a single source that take precedence over the global
+/// [`StorageParameters`]. Unset fields fall back to the global values.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SourceParameterOverrides {
+    /// The backpressure budget of the source's dataflow. Overrides
+    /// `StorageParameters::storage_dataflow_max_inflight_bytes_config`.
+    pub max_inflight_bytes: Option<usize>,
+    /// The number of raw `COPY` bytes a PostgreSQL table reader lets be in flight. Overrides
+    /// `PgSourceSnapshotConfig::max_in_flight_bytes`.
+    pub snapshot_max_inflight_bytes: Option<usize>,
+    /// Whether PostgreSQL snapshots are taken in fast, `READ COMMITTED` transactions. Overrides
+    /// `PgSourceSnapshotConfig::read_committed`.
+    pub snapshot_read_committed: Option<bool>,
+    /// The maximum number of messages the source's dataflow timestamps at a time, before it
+    /// checks for new remap bindings and source progress again. There is no global limit.
+    pub max_batch_size: Option<usize>,
+    /// The number of messages the source's dataflow emits before it yields the worker to other
+    /// dataflows. There is no global budget.
+    pub yield_budget: Option<usize>,
+    /// The interval at which the statistics of the source and its subsources are reported.
+    /// Overrides `StorageParameters::statistics_collection_interval`.
+    pub statistics_interval: Option<Duration>,
+}
+
+impl SourceParameterOverrides {
+    /// Whether no parameter is overridden.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+impl ProtoMapEntry<GlobalId, SourceParameterOverrides> for ProtoSourceParameterOverrides {
+    fn from_rust<'a>(entry: (&'a GlobalId, &'a SourceParameterOverrides)) -> Self {
+        ProtoSourceParameterOverrides {
+            id: Some(entry.0.into_proto()),
+            max_inflight_bytes: entry.1.max_inflight_bytes.map(u64::cast_from),
+            snapshot_max_inflight_bytes: entry.1.snapshot_max_inflight_bytes.map(u64::cast_from),
+            snapshot_read_committed: entry.1.snapshot_read_committed,
+            max_batch_size: entry.1.max_batch_size.map(u64::cast_from),
+            yield_budget: entry.1.yield_budget.map(u64::cast_from),
+            statistics_interval: entry.1.statistics_interval.into_proto(),
+        }
+    }
+
+    fn into_rust(self) -> Result<(GlobalId, SourceParameterOverrides), TryFromProtoError> {
+        Ok((
+            self.id
+                .into_rust_if_some("ProtoSourceParameterOverrides::id")?,
+            SourceParameterOverrides {
+                max_inflight_bytes: self.max_inflight_bytes.map(usize::cast_from),
+                snapshot_max_inflight_bytes: self.snapshot_max_inflight_bytes.map(usize::cast_from),
+                snapshot_read_committed: self.snapshot_read_committed,
+                max_batch_size: self.max_batch_size.map(usize::cast_from),
+                yield_budget: self.yield_budget.map(usize::cast_from),
+                statistics_interval: self.statistics_interval.into_rust()?,
+            },
+        ))
+    }
+}
+
 impl RustType<ProtoKafkaTimeouts> for mz_kafka_util::client::TimeoutConfig {
     fn into_proto(&self) -> ProtoKafkaTimeouts {
         ProtoKafkaTimeouts {
@@ -469,3 +713,72 @@ impl RustType<ProtoSshTimeoutConfig> for SshTimeoutConfig {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_proto::protobuf_roundtrip;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_source_overrides() {
+        let overridden = GlobalId::User(1);
+        let other = GlobalId::User(2);
+
+        let mut params = StorageParameters::default();
+        params.storage_dataflow_max_inflight_bytes_config = StorageMaxInflightBytesConfig {
+            max_inflight_bytes_default: Some(1000),
+            max_inflight_bytes_cluster_size_fraction: Some(0.5),
+            disk_only: true,
+        };
+        params.source_overrides.insert(
+            overridden,
+            SourceParameterOverrides {
+                max_inflight_bytes: Some(42),
+                snapshot_max_inflight_bytes: Some(7),
+                snapshot_read_committed: Some(true),
+                max_batch_size: Some(100),
+                yield_budget: Some(10),
+                statistics_interval: Some(Duration::from_secs(1)),
+            },
+        );
+
+        // The overrides survive the trip to the storage workers.
+        let params: StorageParameters =
+            protobuf_roundtrip::<_, ProtoStorageParameters>(&params).unwrap();
+
+        assert_eq!(
+            params.max_inflight_bytes_config(overridden),
+            StorageMaxInflightBytesConfig {
+                max_inflight_bytes_default: Some(42),
+                max_inflight_bytes_cluster_size_fraction: None,
+                disk_only: true,
+            }
+        );
+        assert_eq!(params.pg_snapshot_config(overridden).max_in_flight_bytes, 7);
+        assert!(params.pg_snapshot_config(overridden).read_committed);
+        assert_eq!(params.max_batch_size(overridden), Some(100));
+        assert_eq!(params.yield_budget(overridden), Some(10));
+        assert_eq!(
+            params.statistics_collection_interval(overridden),
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            params.min_statistics_collection_interval(),
+            Duration::from_secs(1)
+        );
+
+        // Sources without overrides keep the global values.
+        assert_eq!(
+            params.max_inflight_bytes_config(other),
+            params.storage_dataflow_max_inflight_bytes_config
+        );
+        assert_eq!(params.pg_snapshot_config(other), params.pg_snapshot_config);
+        assert_eq!(params.max_batch_size(other), None);
+        assert_eq!(params.yield_budget(other), None);
+        assert_eq!(
+            params.statistics_collection_interval(other),
+            STATISTICS_COLLECTION_INTERVAL_DEFAULT
+        );
+    }
+}
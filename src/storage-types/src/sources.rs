@@ -287,6 +287,12 @@ impl ProtoMapEntry<GlobalId, SourceExport<CollectionMetadata>> for ProtoSourceEx
 pub trait SourceTimestamp: timely::progress::Timestamp + Refines<()> + std::fmt::Display {
     fn encode_row(&self) -> Row;
     fn decode_row(row: &Row) -> Self;
+
+    /// Returns this timestamp as a timestamp of the source's own timeline, for sources whose
+    /// data is not reclocked into wall clock time. See [`SourceConnection::native_timestamps`].
+    fn native_timestamp(&self) -> Option<mz_repr::Timestamp> {
+        None
+    }
 }
 
 impl SourceTimestamp for MzOffset {
@@ -301,6 +307,10 @@ impl SourceTimestamp for MzOffset {
             _ => panic!("invalid row {row:?}"),
         }
     }
+
+    fn native_timestamp(&self) -> Option<mz_repr::Timestamp> {
+        Some(self.offset.into())
+    }
 }
 
 /// Universal language for describing message positions in Materialize, in a source independent
@@ -484,18 +494,25 @@ pub enum Timeline {
     /// String is specified by the user, allowing them to decide sources that are
     /// joinable.
     User(String),
+    /// PostgresLsn means the timestamp is a log sequence number of the upstream
+    /// PostgreSQL server of a source, i.e. its native timestamp. The attached
+    /// String is the ID of the source's connection, as the LSNs of sources
+    /// with different connections are not necessarily from the same server.
+    PostgresLsn(String),
 }
 
 impl Timeline {
     const EPOCH_MILLISECOND_ID_CHAR: char = 'M';
     const EXTERNAL_ID_CHAR: char = 'E';
     const USER_ID_CHAR: char = 'U';
+    const POSTGRES_LSN_ID_CHAR: char = 'L';
 
     fn id_char(&self) -> char {
         match self {
             Self::EpochMilliseconds => Self::EPOCH_MILLISECOND_ID_CHAR,
             Self::External(_) => Self::EXTERNAL_ID_CHAR,
             Self::User(_) => Self::USER_ID_CHAR,
+            Self::PostgresLsn(_) => Self::POSTGRES_LSN_ID_CHAR,
         }
     }
 }
@@ -508,6 +525,7 @@ impl RustType<ProtoTimeline> for Timeline {
                 Timeline::EpochMilliseconds => Kind::EpochMilliseconds(()),
                 Timeline::External(s) => Kind::External(s.clone()),
                 Timeline::User(s) => Kind::User(s.clone()),
+                Timeline::PostgresLsn(s) => Kind::PostgresLsn(s.clone()),
             }),
         }
     }
@@ -521,6 +539,7 @@ impl RustType<ProtoTimeline> for Timeline {
             Kind::EpochMilliseconds(()) => Timeline::EpochMilliseconds,
             Kind::External(s) => Timeline::External(s),
             Kind::User(s) => Timeline::User(s),
+            Kind::PostgresLsn(s) => Timeline::PostgresLsn(s),
         })
     }
 }
@@ -531,6 +550,7 @@ impl ToString for Timeline {
             Self::EpochMilliseconds => format!("{}", self.id_char()),
             Self::External(id) => format!("{}.{id}", self.id_char()),
             Self::User(id) => format!("{}.{id}", self.id_char()),
+            Self::PostgresLsn(id) => format!("{}.{id}", self.id_char()),
         }
     }
 }
@@ -556,6 +576,10 @@ impl FromStr for Timeline {
                 Some('.') => Ok(Self::User(chars.as_str().to_string())),
                 _ => Err(format!("unknown timeline: {s}")),
             },
+            Self::POSTGRES_LSN_ID_CHAR => match chars.next() {
+                Some('.') => Ok(Self::PostgresLsn(chars.as_str().to_string())),
+                _ => Err(format!("unknown timeline: {s}")),
+            },
             _ => Err(format!("unknown timeline: {s}")),
         }
     }
@@ -592,6 +616,13 @@ pub trait SourceConnection: Debug + Clone + PartialEq + crate::AlterCompatible {
     /// Returns metadata columns that this connection *instance* will produce once rendered. The
     /// columns are returned in the order specified by the user.
     fn metadata_columns(&self) -> Vec<(&str, ColumnType)>;
+
+    /// Whether the data of this connection *instance* is timestamped with its own timestamps,
+    /// rather than reclocked into wall clock time. The timestamps must then support
+    /// [`SourceTimestamp::native_timestamp`].
+    fn native_timestamps(&self) -> bool {
+        false
+    }
 }
 
 #[derive(Arbitrary, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -883,6 +914,15 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::LoadGenerator(conn) => conn.metadata_columns(),
         }
     }
+
+    fn native_timestamps(&self) -> bool {
+        match self {
+            Self::Kafka(conn) => conn.native_timestamps(),
+            Self::Postgres(conn) => conn.native_timestamps(),
+            Self::MySql(conn) => conn.native_timestamps(),
+            Self::LoadGenerator(conn) => conn.native_timestamps(),
+        }
+    }
 }
 
 impl<C: ConnectionAccess> crate::AlterCompatible for GenericSourceConnection<C> {
@@ -1162,10 +1202,12 @@ mod tests {
         assert_eq!(Ok(Timeline::EpochMilliseconds), "M".parse());
         assert_eq!(Ok(Timeline::External("JOE".to_string())), "E.JOE".parse());
         assert_eq!(Ok(Timeline::User("MIKE".to_string())), "U.MIKE".parse());
+        assert_eq!(Ok(Timeline::PostgresLsn("PG".to_string())), "L.PG".parse());
 
         assert!("Materialize".parse::<Timeline>().is_err());
         assert!("Ejoe".parse::<Timeline>().is_err());
         assert!("Umike".parse::<Timeline>().is_err());
+        assert!("Lpg".parse::<Timeline>().is_err());
         assert!("Dance".parse::<Timeline>().is_err());
         assert!("".parse::<Timeline>().is_err());
     }
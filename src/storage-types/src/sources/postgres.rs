@@ -21,13 +21,17 @@ use once_cell::sync::Lazy;
 use proptest::prelude::any;
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
+use tokio_postgres::Client;
+use uuid::Uuid;
 
+use crate::configuration::StorageConfiguration;
 use crate::connections::inline::{
     ConnectionAccess, ConnectionResolver, InlinedConnection, IntoInlineConnection,
     ReferencedConnection,
 };
+use crate::connections::PostgresConnection;
 use crate::controller::StorageError;
-use crate::sources::SourceConnection;
+use crate::sources::{MzOffset, SourceConnection};
 
 include!(concat!(
     env!("OUT_DIR"),
@@ -46,6 +50,13 @@ pub struct PostgresSourceConnection<C: ConnectionAccess = InlinedConnection> {
     pub table_casts: BTreeMap<usize, Vec<MirScalarExpr>>,
     pub publication: String,
     pub publication_details: PostgresSourcePublicationDetails,
+    /// The snapshot group of the source, if any. See [`GroupSnapshot`].
+    pub snapshot_group: Option<String>,
+    /// Whether the data of the source is timestamped with the LSNs it was committed at, rather
+    /// than reclocked into wall clock time. Set for sources in a [`Timeline::PostgresLsn`].
+    ///
+    /// [`Timeline::PostgresLsn`]: crate::sources::Timeline::PostgresLsn
+    pub lsn_timestamps: bool,
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<PostgresSourceConnection, R>
@@ -58,6 +69,8 @@ impl<R: ConnectionResolver> IntoInlineConnection<PostgresSourceConnection, R>
             table_casts,
             publication,
             publication_details,
+            snapshot_group,
+            lsn_timestamps,
         } = self;
 
         PostgresSourceConnection {
@@ -66,6 +79,8 @@ impl<R: ConnectionResolver> IntoInlineConnection<PostgresSourceConnection, R>
             table_casts,
             publication,
             publication_details,
+            snapshot_group,
+            lsn_timestamps,
         }
     }
 }
@@ -103,6 +118,10 @@ impl<C: ConnectionAccess> SourceConnection for PostgresSourceConnection<C> {
     fn metadata_columns(&self) -> Vec<(&str, ColumnType)> {
         vec![]
     }
+
+    fn native_timestamps(&self) -> bool {
+        self.lsn_timestamps
+    }
 }
 
 impl<C: ConnectionAccess> crate::AlterCompatible for PostgresSourceConnection<C> {
@@ -118,6 +137,9 @@ impl<C: ConnectionAccess> crate::AlterCompatible for PostgresSourceConnection<C>
             table_casts,
             publication,
             publication_details,
+            // The snapshot group only affects the initial snapshot
+            snapshot_group: _,
+            lsn_timestamps,
         } = self;
 
         let compatibility_checks = [
@@ -139,6 +161,7 @@ impl<C: ConnectionAccess> crate::AlterCompatible for PostgresSourceConnection<C>
                 publication_details == &other.publication_details,
                 "publication_details",
             ),
+            (lsn_timestamps == &other.lsn_timestamps, "lsn_timestamps"),
         ];
 
         for (compatible, field) in compatibility_checks {
@@ -180,6 +203,8 @@ impl RustType<ProtoPostgresSourceConnection> for PostgresSourceConnection {
             details: Some(self.publication_details.into_proto()),
             table_casts,
             table_cast_pos,
+            snapshot_group: self.snapshot_group.clone(),
+            lsn_timestamps: self.lsn_timestamps,
         }
     }
 
@@ -221,6 +246,8 @@ impl RustType<ProtoPostgresSourceConnection> for PostgresSourceConnection {
                 .details
                 .into_rust_if_some("ProtoPostgresSourceConnection::details")?,
             table_casts,
+            snapshot_group: proto.snapshot_group,
+            lsn_timestamps: proto.lsn_timestamps,
         })
     }
 }
@@ -257,3 +284,111 @@ impl RustType<ProtoPostgresSourcePublicationDetails> for PostgresSourcePublicati
         })
     }
 }
+
+/// A snapshot of the upstream database that is shared by the members of a snapshot group, i.e. by
+/// the sources that name the same `SNAPSHOT GROUP` and use the same connection.
+///
+/// The storage controller exports the snapshot before it runs the members, which then import it
+/// instead of exporting snapshots of their own, so that all of them snapshot their tables at the
+/// same LSN.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct GroupSnapshot {
+    /// The identifier with which SQL sessions import the snapshot.
+    pub snapshot_id: String,
+    /// The LSN the snapshot is at.
+    pub lsn: MzOffset,
+}
+
+impl GroupSnapshot {
+    /// Exports a snapshot of the server of `connection` for a snapshot group whose members use the
+    /// replication `slots`.
+    ///
+    /// The slots are created first, if they don't exist yet, so that the snapshot is not before
+    /// the consistent point of any of them and each member can rewind the snapshot using its own
+    /// slot. The snapshot can be imported for as long as the returned client is not dropped.
+    pub async fn export(
+        connection: &PostgresConnection,
+        slots: &[String],
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<(Client, GroupSnapshot), anyhow::Error> {
+        let config = connection
+            .config(
+                &*storage_configuration.connection_context.secrets_reader,
+                storage_configuration,
+            )
+            .await?;
+        let client = config
+            .connect_replication(&storage_configuration.connection_context.ssh_tunnel_manager)
+            .await?;
+        for slot in slots {
+            mz_postgres_util::ensure_replication_slot(&client, slot).await?;
+        }
+        let temporary_slot = format!("mzsnapshot_{}", Uuid::new_v4()).replace('-', "");
+        let (snapshot_id, lsn) =
+            mz_postgres_util::export_snapshot(&client, &temporary_slot).await?;
+        let snapshot = GroupSnapshot {
+            snapshot_id,
+            lsn: MzOffset::from(lsn),
+        };
+        Ok((client, snapshot))
+    }
+}
+
+impl RustType<ProtoGroupSnapshot> for GroupSnapshot {
+    fn into_proto(&self) -> ProtoGroupSnapshot {
+        ProtoGroupSnapshot {
+            snapshot_id: self.snapshot_id.clone(),
+            lsn: self.lsn.offset,
+        }
+    }
+
+    fn from_proto(proto: ProtoGroupSnapshot) -> Result<Self, TryFromProtoError> {
+        Ok(GroupSnapshot {
+            snapshot_id: proto.snapshot_id,
+            lsn: MzOffset::from(proto.lsn),
+        })
+    }
+}
+
+/// Formats `lsn` in the textual representation of PostgreSQL's `pg_lsn` type, e.g. `16/B374D848`.
+pub fn format_lsn(lsn: u64) -> String {
+    format!("{:X}/{:X}", lsn >> 32, lsn & 0xFFFF_FFFF)
+}
+
+/// Parses the textual representation of PostgreSQL's `pg_lsn` type.
+pub fn parse_lsn(s: &str) -> Option<u64> {
+    let parse_half = |half: &str| {
+        if half.is_empty() || half.len() > 8 || !half.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        u32::from_str_radix(half, 16).ok()
+    };
+    let (hi, lo) = s.split_once('/')?;
+    Some(u64::from(parse_half(hi)?) << 32 | u64::from(parse_half(lo)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_lsn_format() {
+        for lsn in [0, 1, 0xB374D848, 0x16_B374D848, u64::MAX] {
+            assert_eq!(parse_lsn(&format_lsn(lsn)), Some(lsn));
+        }
+        assert_eq!(format_lsn(0x16_B374D848), "16/B374D848");
+        assert_eq!(parse_lsn("16/b374d848"), Some(0x16_B374D848));
+
+        for invalid in [
+            "",
+            "16",
+            "/B374D848",
+            "16/",
+            "16/B374D848/0",
+            "+16/0",
+            "123456789/0",
+        ] {
+            assert_eq!(parse_lsn(invalid), None, "{invalid}");
+        }
+    }
+}
@@ -47,6 +47,9 @@ const SUSPEND_AND_RESTART_DELAY: Duration = Duration::from_secs(30);
 /// Ensure you update `is_sidechannel` when adding variants.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum StatusNamespace {
+    /// The counts of the tables being snapshotted. Counting doesn't affect the data of the
+    /// source, so its errors are displayed under those of any other namespace.
+    SnapshotCount,
     /// A normal status namespaces. Any `Running` status from any worker will mark the object
     /// `Running`.
     Generator,
@@ -61,7 +64,7 @@ pub enum StatusNamespace {
 
 impl StatusNamespace {
     fn is_sidechannel(&self) -> bool {
-        matches!(self, StatusNamespace::Ssh)
+        matches!(self, StatusNamespace::Ssh | StatusNamespace::SnapshotCount)
     }
 }
 
@@ -69,6 +72,7 @@ impl fmt::Display for StatusNamespace {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use StatusNamespace::*;
         match self {
+            SnapshotCount => write!(f, "snapshot_count"),
             Generator => write!(f, "generator"),
             Kafka => write!(f, "kafka"),
             Postgres => write!(f, "postgres"),
@@ -108,16 +112,20 @@ impl PerWorkerHealthStatus {
     }
 
     fn decide_status(&self) -> OverallStatus {
-        let mut output_status = OverallStatus::Starting;
+        let mut output_status = OverallStatus::Starting {
+            hints: BTreeSet::new(),
+        };
+        let mut starting = false;
         let mut namespaced_errors: BTreeMap<StatusNamespace, String> = BTreeMap::new();
         let mut hints: BTreeSet<String> = BTreeSet::new();
 
         for status in self.errors_by_worker.iter() {
             for (ns, ns_status) in status.iter() {
                 match ns_status {
-                    HealthStatusUpdate::Ceased { error } => {
+                    HealthStatusUpdate::Ceased { error, hint } => {
                         return OverallStatus::Ceased {
                             error: error.clone(),
+                            hints: hint.iter().cloned().collect(),
                         };
                     }
                     HealthStatusUpdate::Stalled { error, hint, .. } => {
@@ -129,9 +137,22 @@ impl PerWorkerHealthStatus {
                             hints.insert(hint.to_string());
                         }
                     }
-                    HealthStatusUpdate::Running => {
+                    HealthStatusUpdate::Starting { hint } => {
+                        starting = true;
+
+                        if let Some(hint) = hint {
+                            hints.insert(hint.to_string());
+                        }
+                    }
+                    HealthStatusUpdate::Running { hint } => {
                         if !ns.is_sidechannel() {
-                            output_status = OverallStatus::Running;
+                            output_status = OverallStatus::Running {
+                                hints: BTreeSet::new(),
+                            };
+                        }
+
+                        if let Some(hint) = hint {
+                            hints.insert(hint.to_string());
                         }
                     }
                 }
@@ -146,6 +167,15 @@ impl PerWorkerHealthStatus {
                 hints,
                 namespaced_errors,
             }
+        } else if starting {
+            // An object that is still starting up in any namespace, e.g. because it is taking its
+            // initial snapshot, is starting even if it already runs in the others.
+            output_status = OverallStatus::Starting { hints };
+        } else if let OverallStatus::Running {
+            hints: running_hints,
+        } = &mut output_status
+        {
+            *running_hints = hints;
         }
 
         output_status
@@ -154,8 +184,12 @@ impl PerWorkerHealthStatus {
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum OverallStatus {
-    Starting,
-    Running,
+    Starting {
+        hints: BTreeSet<String>,
+    },
+    Running {
+        hints: BTreeSet<String>,
+    },
     Stalled {
         error: String,
         hints: BTreeSet<String>,
@@ -163,6 +197,7 @@ pub enum OverallStatus {
     },
     Ceased {
         error: String,
+        hints: BTreeSet<String>,
     },
 }
 
@@ -170,7 +205,7 @@ impl OverallStatus {
     /// The user-readable error string, if there is one.
     pub(crate) fn error(&self) -> Option<&str> {
         match self {
-            OverallStatus::Starting | OverallStatus::Running => None,
+            OverallStatus::Starting { .. } | OverallStatus::Running { .. } => None,
             OverallStatus::Stalled { error, .. } | OverallStatus::Ceased { error, .. } => {
                 Some(error)
             }
@@ -180,7 +215,9 @@ impl OverallStatus {
     /// A set of namespaced errors, if there are any.
     pub(crate) fn errors(&self) -> Option<&BTreeMap<StatusNamespace, String>> {
         match self {
-            OverallStatus::Starting | OverallStatus::Running | OverallStatus::Ceased { .. } => None,
+            OverallStatus::Starting { .. }
+            | OverallStatus::Running { .. }
+            | OverallStatus::Ceased { .. } => None,
             OverallStatus::Stalled {
                 namespaced_errors, ..
             } => Some(namespaced_errors),
@@ -190,10 +227,10 @@ impl OverallStatus {
     /// A set of hints, if there are any.
     pub(crate) fn hints(&self) -> BTreeSet<String> {
         match self {
-            OverallStatus::Starting | OverallStatus::Running | OverallStatus::Ceased { .. } => {
-                BTreeSet::new()
-            }
-            OverallStatus::Stalled { hints, .. } => hints.clone(),
+            OverallStatus::Starting { hints }
+            | OverallStatus::Running { hints }
+            | OverallStatus::Stalled { hints, .. }
+            | OverallStatus::Ceased { hints, .. } => hints.clone(),
         }
     }
 }
@@ -201,8 +238,8 @@ impl OverallStatus {
 impl<'a> From<&'a OverallStatus> for Status {
     fn from(val: &'a OverallStatus) -> Self {
         match val {
-            OverallStatus::Starting => Status::Starting,
-            OverallStatus::Running => Status::Running,
+            OverallStatus::Starting { .. } => Status::Starting,
+            OverallStatus::Running { .. } => Status::Running,
             OverallStatus::Stalled { .. } => Status::Stalled,
             OverallStatus::Ceased { .. } => Status::Ceased,
         }
@@ -386,7 +423,9 @@ where
         if is_active_worker {
             for state in health_states.values_mut() {
                 if mark_starting.contains(&state.id) {
-                    let status = OverallStatus::Starting;
+                    let status = OverallStatus::Starting {
+                        hints: BTreeSet::new(),
+                    };
                     let timestamp = mz_ore::now::to_datetime(now());
                     health_operator_impl
                         .record_new_status(
@@ -527,7 +566,13 @@ use serde::{Deserialize, Serialize};
 /// source to be stalled.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum HealthStatusUpdate {
-    Running,
+    /// The object is still starting up, e.g. taking its initial snapshot.
+    Starting {
+        hint: Option<String>,
+    },
+    Running {
+        hint: Option<String>,
+    },
     Stalled {
         error: String,
         hint: Option<String>,
@@ -535,13 +580,26 @@ pub enum HealthStatusUpdate {
     },
     Ceased {
         error: String,
+        hint: Option<String>,
     },
 }
 
 impl HealthStatusUpdate {
+    /// Generates a starting [`HealthStatusUpdate`] with a `hint`, e.g. about the progress of the
+    /// startup.
+    pub(crate) fn starting_with_hint(hint: String) -> Self {
+        HealthStatusUpdate::Starting { hint: Some(hint) }
+    }
+
     /// Generates a running [`HealthStatusUpdate`].
     pub(crate) fn running() -> Self {
-        HealthStatusUpdate::Running
+        HealthStatusUpdate::Running { hint: None }
+    }
+
+    /// Generates a running [`HealthStatusUpdate`] with a `hint`, e.g. about a weaker guarantee
+    /// that the object runs with.
+    pub(crate) fn running_with_hint(hint: String) -> Self {
+        HealthStatusUpdate::Running { hint: Some(hint) }
     }
 
     /// Generates a non-halting [`HealthStatusUpdate`] with `update`.
@@ -563,14 +621,15 @@ impl HealthStatusUpdate {
     }
 
     /// Generates a ceasing [`HealthStatusUpdate`] with `update`.
-    pub(crate) fn ceasing(error: String) -> Self {
-        HealthStatusUpdate::Ceased { error }
+    pub(crate) fn ceasing(error: String, hint: Option<String>) -> Self {
+        HealthStatusUpdate::Ceased { error, hint }
     }
 
     /// Whether or not we should halt the dataflow instances and restart it.
     pub(crate) fn should_halt(&self) -> bool {
         match self {
-            HealthStatusUpdate::Running |
+            HealthStatusUpdate::Starting { .. } |
+            HealthStatusUpdate::Running { .. } |
             // HealthStatusUpdate::Ceased should never halt because it can occur
             // at the subsource level and should not cause the entire dataflow
             // to halt. Instead, the dataflow itself should handle shutting
@@ -959,6 +1018,145 @@ mod tests {
                     status: Status::Running,
                     ..Default::default()
                 }]),
+                // Running statuses can carry hints too.
+                Update(TestUpdate {
+                    worker_id: 0,
+                    namespace: StatusNamespace::Generator,
+                    input_index: 0,
+                    update: HealthStatusUpdate::running_with_hint("hint4".to_string()),
+                }),
+                AssertStatus(vec![StatusToAssert {
+                    collection_index: 0,
+                    status: Status::Running,
+                    hint: Some("hint4".to_string()),
+                    ..Default::default()
+                }]),
+                // As can ceased ones, e.g. to name the remediation of a misconfiguration.
+                Update(TestUpdate {
+                    worker_id: 1,
+                    namespace: StatusNamespace::Generator,
+                    input_index: 0,
+                    update: HealthStatusUpdate::ceasing(
+                        "gone".to_string(),
+                        Some("hint5".to_string()),
+                    ),
+                }),
+                AssertStatus(vec![StatusToAssert {
+                    collection_index: 0,
+                    status: Status::Ceased,
+                    error: Some("gone".to_string()),
+                    hint: Some("hint5".to_string()),
+                    ..Default::default()
+                }]),
+            ],
+        );
+    }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: returning ready events from epoll_wait is not yet implemented
+    fn test_health_operator_snapshot_count() {
+        use Step::*;
+
+        health_operator_runner(
+            2,
+            1,
+            true,
+            vec![
+                AssertStatus(vec![StatusToAssert {
+                    collection_index: 0,
+                    status: Status::Starting,
+                    ..Default::default()
+                }]),
+                Update(TestUpdate {
+                    worker_id: 0,
+                    namespace: StatusNamespace::Postgres,
+                    input_index: 0,
+                    update: HealthStatusUpdate::running(),
+                }),
+                AssertStatus(vec![StatusToAssert {
+                    collection_index: 0,
+                    status: Status::Running,
+                    ..Default::default()
+                }]),
+                // The source is starting while it takes its snapshot, whose progress is reported
+                // as a hint that is updated as the snapshot proceeds.
+                Update(TestUpdate {
+                    worker_id: 0,
+                    namespace: StatusNamespace::SnapshotCount,
+                    input_index: 0,
+                    update: HealthStatusUpdate::starting_with_hint("progress 1".to_string()),
+                }),
+                AssertStatus(vec![StatusToAssert {
+                    collection_index: 0,
+                    status: Status::Starting,
+                    hint: Some("progress 1".to_string()),
+                    ..Default::default()
+                }]),
+                Update(TestUpdate {
+                    worker_id: 0,
+                    namespace: StatusNamespace::SnapshotCount,
+                    input_index: 0,
+                    update: HealthStatusUpdate::starting_with_hint("progress 2".to_string()),
+                }),
+                AssertStatus(vec![StatusToAssert {
+                    collection_index: 0,
+                    status: Status::Starting,
+                    hint: Some("progress 2".to_string()),
+                    ..Default::default()
+                }]),
+                // A failure to count surfaces as a stall.
+                Update(TestUpdate {
+                    worker_id: 0,
+                    namespace: StatusNamespace::SnapshotCount,
+                    input_index: 0,
+                    update: HealthStatusUpdate::stalled("timeout".to_string(), None),
+                }),
+                AssertStatus(vec![StatusToAssert {
+                    collection_index: 0,
+                    status: Status::Stalled,
+                    error: Some("snapshot_count: timeout".to_string()),
+                    errors: Some("snapshot_count: timeout".to_string()),
+                    ..Default::default()
+                }]),
+                // Errors of the source itself are displayed over it.
+                Update(TestUpdate {
+                    worker_id: 0,
+                    namespace: StatusNamespace::Postgres,
+                    input_index: 0,
+                    update: HealthStatusUpdate::stalled("uhoh".to_string(), None),
+                }),
+                AssertStatus(vec![StatusToAssert {
+                    collection_index: 0,
+                    status: Status::Stalled,
+                    error: Some("postgres: uhoh".to_string()),
+                    errors: Some("snapshot_count: timeout, postgres: uhoh".to_string()),
+                    ..Default::default()
+                }]),
+                Update(TestUpdate {
+                    worker_id: 0,
+                    namespace: StatusNamespace::Postgres,
+                    input_index: 0,
+                    update: HealthStatusUpdate::running(),
+                }),
+                AssertStatus(vec![StatusToAssert {
+                    collection_index: 0,
+                    status: Status::Stalled,
+                    error: Some("snapshot_count: timeout".to_string()),
+                    errors: Some("snapshot_count: timeout".to_string()),
+                    ..Default::default()
+                }]),
+                // The stall ends once the tables have been read.
+                Update(TestUpdate {
+                    worker_id: 0,
+                    namespace: StatusNamespace::SnapshotCount,
+                    input_index: 0,
+                    update: HealthStatusUpdate::running(),
+                }),
+                AssertStatus(vec![StatusToAssert {
+                    collection_index: 0,
+                    status: Status::Running,
+                    ..Default::default()
+                }]),
             ],
         );
     }
@@ -11,13 +11,15 @@
 
 use mz_ore::metric;
 use mz_ore::metrics::{
-    CounterVecExt, DeleteOnDropCounter, DeleteOnDropGauge, GaugeVec, GaugeVecExt, IntCounterVec,
-    IntGaugeVec, MetricsRegistry, UIntGaugeVec,
+    CounterVec, CounterVecExt, DeleteOnDropCounter, DeleteOnDropGauge, GaugeVec, GaugeVecExt,
+    IntCounterVec, IntGaugeVec, MetricsRegistry, UIntGaugeVec,
 };
 use mz_repr::GlobalId;
 use prometheus::core::{AtomicF64, AtomicI64, AtomicU64};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
 
 /// Definitions for Postgres source metrics.
 #[derive(Clone, Debug)]
@@ -34,6 +36,7 @@ pub(crate) struct PgSourceMetricDefs {
     pub(crate) table_count_latency: GaugeVec,
     pub(crate) table_estimate: IntGaugeVec,
     pub(crate) table_estimate_latency: GaugeVec,
+    pub(crate) table_backpressure_seconds: CounterVec,
 }
 
 impl PgSourceMetricDefs {
@@ -99,6 +102,12 @@ impl PgSourceMetricDefs {
                 help: "The wall time used to obtain `mz_postgres_snapshot_estimate`.",
                 var_labels: ["source_id", "table_name"],
             )),
+            table_backpressure_seconds: registry.register(metric!(
+                name: "mz_postgres_snapshot_backpressure_seconds",
+                help: "The wall time a table's snapshot reader spent paused because too many \
+                    COPY bytes were in flight to the decoders.",
+                var_labels: ["source_id", "table_name"],
+            )),
         }
     }
 }
@@ -117,6 +126,9 @@ pub(crate) struct PgSnapshotMetrics {
             )>,
         >,
     >,
+    // Shared for the same reason as `gauges`, keyed by table name.
+    backpressure:
+        Arc<Mutex<BTreeMap<String, DeleteOnDropCounter<'static, AtomicF64, Vec<String>>>>>,
     defs: PgSourceMetricDefs,
 }
 
@@ -153,6 +165,20 @@ impl PgSnapshotMetrics {
             .expect("poisoned")
             .push((gauge, latency_gauge))
     }
+    pub(crate) fn record_table_backpressure(&self, table_name: &str, elapsed: Duration) {
+        let mut backpressure = self.backpressure.lock().expect("poisoned");
+        let counter = backpressure
+            .entry(table_name.to_string())
+            .or_insert_with(|| {
+                self.defs
+                    .table_backpressure_seconds
+                    .get_delete_on_drop_counter(vec![
+                        self.source_id.to_string(),
+                        table_name.to_string(),
+                    ])
+            });
+        counter.inc_by(elapsed.as_secs_f64());
+    }
 }
 
 /// Metrics for Postgres sources.
@@ -199,6 +225,7 @@ impl PgSourceMetrics {
             snapshot_metrics: PgSnapshotMetrics {
                 source_id,
                 gauges: Default::default(),
+                backpressure: Default::default(),
                 defs: defs.clone(),
             },
         }
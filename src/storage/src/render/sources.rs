@@ -114,9 +114,11 @@ where
         shared_remap_upper: Rc::clone(
             &storage_state.source_uppers[&description.remap_collection_id],
         ),
+        shared_upstream_lag: Rc::clone(&storage_state.upstream_lag[&id]),
         // This might quite a large clone, but its just during rendering
         config: storage_state.storage_configuration.clone(),
         remap_collection_id: description.remap_collection_id.clone(),
+        group_snapshot: storage_state.ingestion_group_snapshots.get(&id).cloned(),
     };
 
     // A set of channels (1 per worker) used to signal rehydration being finished
@@ -232,6 +234,10 @@ where
                 .as_option()
                 .expect("resuming an already finished ingestion")
                 .clone();
+            let max_inflight_bytes_config = storage_state
+                .storage_configuration
+                .parameters
+                .max_inflight_bytes_config(base_source_config.id);
             let (upsert, health_update) = scope.scoped(
                 &format!("upsert_rehydration_backpressure({})", id),
                 |scope| {
@@ -241,10 +247,7 @@ where
 
                             let backpressure_max_inflight_bytes =
                                 get_backpressure_max_inflight_bytes(
-                                    &storage_state
-                                        .storage_configuration
-                                        .parameters
-                                        .storage_dataflow_max_inflight_bytes_config,
+                                    &max_inflight_bytes_config,
                                     &storage_state.instance_context.cluster_memory_limit,
                                 );
 
@@ -258,11 +261,7 @@ where
                                         base_source_config.worker_id,
                                         id
                                     );
-                                    if !storage_state
-                                        .storage_configuration
-                                        .parameters
-                                        .storage_dataflow_max_inflight_bytes_config
-                                        .disk_only
+                                    if !max_inflight_bytes_config.disk_only
                                         || storage_state
                                             .instance_context
                                             .scratch_directory
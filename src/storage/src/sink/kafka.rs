@@ -68,13 +68,22 @@
 //! Both the encoding operator and the sinking operator can produce a transient error that is wired
 //! up with our health monitoring and will trigger a restart of the sink dataflow.
 //!
+//! ## Progress validation
+//!
+//! Alongside each upper, the progress topic records how many messages the sink has committed to
+//! the data topic. When the sink resumes, and periodically while it runs, it checks that the data
+//! topic still holds at least that many offsets. A data topic that was deleted and recreated fails
+//! this check, in which case the sink halts rather than silently skip the data that was lost with
+//! the old topic. Running `ALTER SINK ... RESET PROGRESS` allows the sink to discard its recorded
+//! progress in that case and resume emission from its `as_of`.
+//!
 //! [1]: https://github.com/confluentinc/librdkafka/blob/master/INTRODUCTION.md#message-reliability
 
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::rc::Rc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Context};
 use differential_dataflow::{AsCollection, Collection, Hashable};
@@ -88,7 +97,9 @@ use mz_ore::task;
 use mz_ore::vec::VecExt;
 use mz_repr::{Diff, GlobalId, Row, Timestamp};
 use mz_storage_client::sink::progress_key::ProgressKey;
-use mz_storage_client::sink::{ProgressRecord, TopicCleanupPolicy, TopicConfig};
+use mz_storage_client::sink::{
+    DataTopicProgress, ProgressRecord, SinkProgressMismatch, TopicCleanupPolicy, TopicConfig,
+};
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::errors::{ContextCreationError, ContextCreationErrorExt, DataflowError};
 use mz_storage_types::sinks::{
@@ -100,7 +111,6 @@ use mz_timely_util::builder_async::{
 };
 use rdkafka::error::KafkaError;
 use rdkafka::message::{Header, OwnedHeaders};
-use rdkafka::metadata::Metadata;
 use rdkafka::producer::{BaseProducer, BaseRecord, Producer};
 use rdkafka::types::RDKafkaErrorCode;
 use timely::dataflow::channels::pact::{Exchange, Pipeline};
@@ -115,6 +125,9 @@ use crate::render::sinks::SinkRender;
 use crate::statistics::SinkStatistics;
 use crate::storage_state::StorageState;
 
+/// The interval at which a running sink validates its recorded progress against the data topic.
+const PROGRESS_VALIDATION_INTERVAL: Duration = Duration::from_secs(60);
+
 impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for KafkaSinkConnection {
     fn uses_keys(&self) -> bool {
         true
@@ -162,6 +175,7 @@ impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for KafkaSinkConnection {
             self.clone(),
             storage_state.storage_configuration.clone(),
             sink.as_of.clone(),
+            storage_state.sink_progress_resets.contains(&sink_id),
             storage_state
                 .aggregated_statistics
                 .get_sink(&sink_id)
@@ -172,7 +186,7 @@ impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for KafkaSinkConnection {
 
         let running_status = Some(HealthStatusMessage {
             index: 0,
-            update: HealthStatusUpdate::Running,
+            update: HealthStatusUpdate::running(),
             namespace: StatusNamespace::Kafka,
         })
         .to_stream(&mut scope);
@@ -206,6 +220,9 @@ struct TransactionalProducer {
     socket_timeout: Duration,
     /// The maximum duration of a transaction.
     transaction_timeout: Duration,
+    /// The state of the data topic as of the last committed transaction, which is recorded
+    /// alongside each upper.
+    data_topic_progress: DataTopicProgress,
 }
 
 impl TransactionalProducer {
@@ -277,6 +294,10 @@ impl TransactionalProducer {
             staged_bytes: 0,
             socket_timeout: timeout_config.socket_timeout,
             transaction_timeout: timeout_config.transaction_timeout,
+            data_topic_progress: DataTopicProgress {
+                partition_count: 0,
+                messages_committed: 0,
+            },
         };
 
         let timeout = timeout_config.socket_timeout;
@@ -303,8 +324,47 @@ impl TransactionalProducer {
             .check_ssh_status(self.producer.context())
     }
 
-    async fn fetch_metadata(&self) -> Result<Metadata, ContextCreationError> {
-        self.spawn_blocking(|p| p.client().fetch_metadata(None, Duration::from_secs(10)))
+    /// Fetches the `(low, high)` watermarks of each partition of the data topic, or `None` if the
+    /// data topic does not exist.
+    async fn fetch_data_topic_watermarks(
+        &self,
+    ) -> Result<Option<Vec<(i64, i64)>>, ContextCreationError> {
+        let topic = self.data_topic.clone();
+        let timeout = self.socket_timeout;
+        self.spawn_blocking(move |p| {
+            let meta = p.client().fetch_metadata(None, timeout)?;
+            let Some(topic_meta) = meta.topics().iter().find(|t| t.name() == topic) else {
+                return Ok(None);
+            };
+            topic_meta
+                .partitions()
+                .iter()
+                .map(|partition| p.client().fetch_watermarks(&topic, partition.id(), timeout))
+                .collect::<Result<_, _>>()
+                .map(Some)
+        })
+        .await
+    }
+
+    /// Validates the `recorded` progress against the current state of the data topic, and adopts
+    /// the current partition count of the data topic for future progress records.
+    async fn validate_data_topic(
+        &mut self,
+        recorded: Option<&DataTopicProgress>,
+    ) -> Result<Result<(), SinkProgressMismatch>, ContextCreationError> {
+        let Some(watermarks) = self.fetch_data_topic_watermarks().await? else {
+            return Ok(Err(SinkProgressMismatch::TopicMissing));
+        };
+        self.data_topic_progress.partition_count = watermarks.len();
+        Ok(recorded.map_or(Ok(()), |recorded| recorded.validate(&watermarks)))
+    }
+
+    /// Commits an empty transaction with a progress record at the minimum timestamp, which makes
+    /// the sink resume from its `as_of` and restarts the count of committed messages.
+    async fn reset_progress(&mut self) -> Result<(), ContextCreationError> {
+        self.data_topic_progress.messages_committed = 0;
+        self.begin_transaction().await?;
+        self.commit_transaction(Antichain::from_elem(Timestamp::minimum()))
             .await
     }
 
@@ -368,8 +428,13 @@ impl TransactionalProducer {
         &mut self,
         upper: Antichain<Timestamp>,
     ) -> Result<(), ContextCreationError> {
+        let data_topic_progress = DataTopicProgress {
+            partition_count: self.data_topic_progress.partition_count,
+            messages_committed: self.data_topic_progress.messages_committed + self.staged_messages,
+        };
         let progress = ProgressRecord {
             frontier: upper.into(),
+            data_topic: Some(data_topic_progress.clone()),
         };
         let payload = serde_json::to_vec(&progress).expect("infallible");
         let record = BaseRecord::to(&self.progress_topic)
@@ -401,6 +466,7 @@ impl TransactionalProducer {
                 self.statistics.inc_bytes_committed_by(self.staged_bytes);
                 self.staged_messages = 0;
                 self.staged_bytes = 0;
+                self.data_topic_progress = data_topic_progress;
                 Ok(())
             }
             Err(ContextCreationError::KafkaError(KafkaError::Transaction(err))) => {
@@ -435,6 +501,7 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
     connection: KafkaSinkConnection,
     storage_configuration: StorageConfiguration,
     as_of: Antichain<Timestamp>,
+    reset_progress: bool,
     statistics: SinkStatistics,
     write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
 ) -> (Stream<G, HealthStatusMessage>, PressOnDropButton) {
@@ -467,46 +534,69 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
             .await?;
             // Instantiating the transactional producer fences out all previous ones, making it
             // safe to determine the resume upper.
-            let resume_upper = mz_storage_client::sink::determine_sink_resume_upper(
+            let progress = mz_storage_client::sink::determine_sink_resume_upper(
                 sink_id,
                 &connection,
                 &storage_configuration,
             )
             .await?;
 
-            let resume_upper = match resume_upper {
-                Some(upper) => {
-                    // If there are committed progress messages then we only check if the data
-                    // topic exists, because if it does not then it must have been deleted after
-                    // the fact, which is a bit of a problem.
-                    let meta = producer.fetch_metadata().await?;
-                    if !meta.topics().iter().any(|t| t.name() == &connection.topic) {
-                        return Err(anyhow!(
-                            "sink progress data exists, but sink data topic is missing"
-                        )
-                        .into());
+            let ensure_data_topic = || {
+                mz_storage_client::sink::ensure_kafka_topic(
+                    &connection,
+                    &storage_configuration,
+                    &connection.topic,
+                    // TODO: allow users to configure these parameters.
+                    TopicConfig {
+                        partition_count: -1,
+                        replication_factor: -1,
+                        cleanup_policy: TopicCleanupPolicy::Retention {
+                            ms: Some(-1),
+                            bytes: Some(-1),
+                        },
+                    },
+                )
+            };
+
+            let resume_upper = match progress {
+                Some(progress) => {
+                    // If there are committed progress messages then the data topic must still
+                    // hold everything they account for. If it does not then it must have been
+                    // deleted after the fact, which is a bit of a problem.
+                    let validation = producer
+                        .validate_data_topic(progress.data_topic.as_ref())
+                        .await?;
+                    if let Some(data_topic) = &progress.data_topic {
+                        producer.data_topic_progress.messages_committed =
+                            data_topic.messages_committed;
+                    }
+                    match validation {
+                        Ok(()) => progress.upper,
+                        Err(mismatch) if reset_progress => {
+                            info!("{name}: resetting sink progress: {mismatch}");
+                            if mismatch == SinkProgressMismatch::TopicMissing {
+                                ensure_data_topic().await?;
+                                producer
+                                    .validate_data_topic(None)
+                                    .await?
+                                    .map_err(anyhow::Error::new)?;
+                            }
+                            producer.reset_progress().await?;
+                            Antichain::from_elem(Timestamp::minimum())
+                        }
+                        Err(mismatch) => return Err(anyhow::Error::new(mismatch).into()),
                     }
-                    upper
                 }
                 None => {
-                    mz_storage_client::sink::ensure_kafka_topic(
-                        &connection,
-                        &storage_configuration,
-                        &connection.topic,
-                        // TODO: allow users to configure these parameters.
-                        TopicConfig {
-                            partition_count: -1,
-                            replication_factor: -1,
-                            cleanup_policy: TopicCleanupPolicy::Retention {
-                                ms: Some(-1),
-                                bytes: Some(-1),
-                            },
-                        },
-                    )
-                    .await?;
+                    ensure_data_topic().await?;
+                    producer
+                        .validate_data_topic(None)
+                        .await?
+                        .map_err(anyhow::Error::new)?;
                     Antichain::from_elem(Timestamp::minimum())
                 }
             };
+            let mut last_validation = Instant::now();
 
             // The input has overcompacted if
             let overcompacted =
@@ -618,6 +708,14 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                         info!("{name}: committing transaction for {}", progress.pretty());
                         producer.commit_transaction(progress.clone()).await?;
                         transaction_begun = false;
+                        if last_validation.elapsed() >= PROGRESS_VALIDATION_INTERVAL {
+                            let committed = producer.data_topic_progress.clone();
+                            producer
+                                .validate_data_topic(Some(&committed))
+                                .await?
+                                .map_err(anyhow::Error::new)?;
+                            last_validation = Instant::now();
+                        }
                         *write_frontier.borrow_mut() = progress.clone();
                         match progress.into_option() {
                             Some(new_upper) => upper = new_upper,
@@ -642,6 +740,11 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                     None
                 }
             }
+            ContextCreationError::Other(ref e) if e.is::<SinkProgressMismatch>() => Some(
+                "If the data topic was deleted or recreated, run ALTER SINK ... RESET PROGRESS \
+                to resume emission from the sink's as_of."
+                    .to_owned(),
+            ),
             _ => None,
         };
 
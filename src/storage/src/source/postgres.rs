@@ -90,7 +90,6 @@ use mz_ore::error::ErrorExt;
 use mz_postgres_util::desc::PostgresTableDesc;
 use mz_postgres_util::{simple_query_opt, PostgresError};
 use mz_repr::{Datum, Diff, Row};
-use mz_sql_parser::ast::{display::AstDisplay, Ident};
 use mz_storage_types::errors::SourceErrorDetails;
 use mz_storage_types::sources::{MzOffset, PostgresSourceConnection, SourceTimestamp};
 use mz_timely_util::builder_async::PressOnDropButton;
@@ -98,7 +97,6 @@ use serde::{Deserialize, Serialize};
 use timely::dataflow::operators::{Concat, Map, ToStream};
 use timely::dataflow::{Scope, Stream};
 use timely::progress::Antichain;
-use tokio_postgres::error::SqlState;
 use tokio_postgres::types::PgLsn;
 use tokio_postgres::Client;
 
@@ -106,6 +104,7 @@ use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespac
 use crate::source::types::{ProgressStatisticsUpdate, SourceRender};
 use crate::source::{RawSourceCreationConfig, SourceMessage, SourceReaderError};
 
+mod preflight;
 mod replication;
 mod snapshot;
 
@@ -161,15 +160,18 @@ impl SourceRender for PostgresSourceConnection {
         }
 
         let metrics = config.metrics.get_postgres_source_metrics(config.id);
-
-        let (snapshot_updates, rewinds, snapshot_err, snapshot_token) = snapshot::render(
-            scope.clone(),
-            config.clone(),
-            self.clone(),
-            subsource_resume_uppers.clone(),
-            table_info.clone(),
-            metrics.snapshot_metrics.clone(),
-        );
+        let read_committed_hint =
+            snapshot::read_committed_hint(&config, &self, &subsource_resume_uppers);
+
+        let (snapshot_updates, rewinds, snapshot_err, snapshot_health, snapshot_token) =
+            snapshot::render(
+                scope.clone(),
+                config.clone(),
+                self.clone(),
+                subsource_resume_uppers.clone(),
+                table_info.clone(),
+                metrics.snapshot_metrics.clone(),
+            );
 
         let (repl_updates, uppers, stats_stream, repl_err, repl_token) = replication::render(
             scope.clone(),
@@ -194,7 +196,10 @@ impl SourceRender for PostgresSourceConnection {
         let init = std::iter::once(HealthStatusMessage {
             index: 0,
             namespace: Self::STATUS_NAMESPACE,
-            update: HealthStatusUpdate::Running,
+            update: match read_committed_hint {
+                Some(hint) => HealthStatusUpdate::running_with_hint(hint),
+                None => HealthStatusUpdate::running(),
+            },
         })
         .to_stream(scope);
 
@@ -202,9 +207,18 @@ impl SourceRender for PostgresSourceConnection {
         // restart we are going to set the status to an ssh error correctly, so we don't do this
         // extra work.
         let errs = snapshot_err.concat(&repl_err).map(move |err| {
-            // This update will cause the dataflow to restart
             let err_string = err.display_with_causes().to_string();
-            let update = HealthStatusUpdate::halting(err_string.clone(), None);
+            let remediation = match &err {
+                ReplicationError::Definite(err) => err.remediation(),
+                ReplicationError::Transient(_) => None,
+            };
+            let update = match remediation {
+                // A restart won't help a server that cannot support the source, so the source
+                // ceases, naming what needs to change upstream.
+                Some(hint) => HealthStatusUpdate::ceasing(err_string.clone(), Some(hint)),
+                // This update will cause the dataflow to restart
+                None => HealthStatusUpdate::halting(err_string.clone(), None),
+            };
 
             let namespace = match err {
                 ReplicationError::Transient(err)
@@ -226,7 +240,7 @@ impl SourceRender for PostgresSourceConnection {
             }
         });
 
-        let health = init.concat(&errs);
+        let health = init.concat(&errs).concat(&snapshot_health);
 
         (
             updates,
@@ -270,6 +284,10 @@ pub enum TransientError {
     NestedTransaction,
     #[error("recoverable errors should crash the process during snapshots")]
     SyntheticError,
+    #[error("snapshot host is not a standby server")]
+    SnapshotHostNotStandby,
+    #[error("timed out waiting for a lock on {0} during the snapshot")]
+    SnapshotLockTimeout(String),
     #[error("sql client error")]
     SQLClient(#[from] tokio_postgres::Error),
     #[error(transparent)]
@@ -308,29 +326,69 @@ pub enum DefiniteError {
     InvalidUTF8(Vec<u8>),
     #[error("failed to cast raw column: {0}")]
     CastError(#[source] EvalError),
+    #[error("wal_level is {0}, but logical replication requires it to be logical")]
+    WalLevelNotLogical(String),
+    #[error(
+        "too few free replication slots: {needed} needed, but {available} of \
+        max_replication_slots = {max} are free"
+    )]
+    ReplicationSlotsExhausted {
+        needed: i64,
+        available: i64,
+        max: i64,
+    },
+    #[error(
+        "too few free WAL senders: {needed} needed, but {available} of max_wal_senders = {max} \
+        are free"
+    )]
+    WalSendersExhausted {
+        needed: i64,
+        available: i64,
+        max: i64,
+    },
 }
 
-impl From<DefiniteError> for SourceReaderError {
-    fn from(err: DefiniteError) -> Self {
-        SourceReaderError {
-            inner: SourceErrorDetails::Other(err.to_string()),
+impl DefiniteError {
+    /// The remediation of errors that are caused by a configuration of the upstream server that
+    /// cannot support the source, if this is one.
+    ///
+    /// These errors are definite, so the source does not recover once the configuration is
+    /// fixed, and the remediation includes recreating it.
+    fn remediation(&self) -> Option<String> {
+        match self {
+            DefiniteError::WalLevelNotLogical(_) => Some(
+                "Set wal_level = logical in the PostgreSQL configuration and restart the server, \
+                 then drop and recreate the source."
+                    .to_string(),
+            ),
+            DefiniteError::ReplicationSlotsExhausted {
+                needed,
+                available,
+                max,
+            } => Some(format!(
+                "Increase max_replication_slots to at least {} and restart the server, or drop \
+                 unused replication slots, then drop and recreate the source.",
+                max + needed - available
+            )),
+            DefiniteError::WalSendersExhausted {
+                needed,
+                available,
+                max,
+            } => Some(format!(
+                "Increase max_wal_senders to at least {} and restart the server, or close unused \
+                 replication connections, then drop and recreate the source.",
+                max + needed - available
+            )),
+            _ => None,
         }
     }
 }
 
-/// Ensures the replication slot of this connection is created.
-async fn ensure_replication_slot(client: &Client, slot: &str) -> Result<(), TransientError> {
-    // Note: Using unchecked here is okay because we're using it in a SQL query.
-    let slot = Ident::new_unchecked(slot).to_ast_string();
-    let query = format!("CREATE_REPLICATION_SLOT {slot} LOGICAL \"pgoutput\" NOEXPORT_SNAPSHOT");
-    match simple_query_opt(client, &query).await {
-        Ok(_) => Ok(()),
-        // If the slot already exists that's still ok
-        Err(PostgresError::Postgres(err)) if err.code() == Some(&SqlState::DUPLICATE_OBJECT) => {
-            tracing::trace!("replication slot {slot} already existed");
-            Ok(())
+impl From<DefiniteError> for SourceReaderError {
+    fn from(err: DefiniteError) -> Self {
+        SourceReaderError {
+            inner: SourceErrorDetails::Other(err.to_string()),
         }
-        Err(err) => Err(TransientError::PostgresError(err)),
     }
 }
 
@@ -375,6 +433,34 @@ async fn fetch_max_lsn(client: &Client) -> Result<MzOffset, TransientError> {
     }
 }
 
+/// Fetch the number of bytes of WAL the replication slot `slot` retains beyond what it has
+/// confirmed, i.e. how far the slot's `confirmed_flush_lsn` is behind `pg_current_wal_lsn`.
+///
+/// Returns `None` if the slot has not confirmed any LSN yet.
+async fn fetch_slot_lag(client: &Client, slot: &str) -> Result<Option<u64>, TransientError> {
+    let query = format!(
+        "SELECT (pg_current_wal_lsn() - confirmed_flush_lsn)::text AS bytes_behind \
+            FROM pg_replication_slots WHERE slot_name = '{slot}'"
+    );
+    let Some(row) = simple_query_opt(client, &query).await? else {
+        return Err(TransientError::MissingReplicationSlot);
+    };
+
+    match row.get("bytes_behind") {
+        // `pg_lsn` subtraction yields a `numeric`, which can be negative if a concurrent flush
+        // confirmation raced with reading the current WAL position.
+        Some(bytes_behind) => {
+            let bytes_behind: i128 = bytes_behind.parse().map_err(|e| {
+                TransientError::Generic(anyhow::anyhow!(
+                    "invalid replication slot lag {bytes_behind}: {e}"
+                ))
+            })?;
+            Ok(Some(u64::try_from(bytes_behind.max(0)).unwrap_or(u64::MAX)))
+        }
+        None => Ok(None),
+    }
+}
+
 // Ensures that the table with oid `oid` and expected schema `expected_schema` is still compatible
 // with the current upstream schema `upstream_info`.
 fn verify_schema(
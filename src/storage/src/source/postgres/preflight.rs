@@ -0,0 +1,292 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Checks that the upstream server can support a source before its snapshot is taken.
+//!
+//! Replication only starts once the snapshot is taken, so a server that cannot support it, e.g.
+//! because its `wal_level` isn't `logical` or because all of its replication slots are taken,
+//! would otherwise only be noticed after a snapshot that may take hours. The snapshot leader
+//! instead inspects the configuration of the server before it creates any slot and fails the
+//! source with a [`DefiniteError`] that names the remediation if the configuration cannot support
+//! it. The error is definite, so the remediation includes recreating the source.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use mz_postgres_util::replication::WalLevel;
+use mz_postgres_util::simple_query_opt;
+use tokio_postgres::Client;
+
+use crate::source::postgres::{DefiniteError, TransientError};
+
+/// The number of WAL senders a source needs at once, i.e. those of the replication connection of
+/// the snapshot leader and of the replication stream.
+const WAL_SENDERS_NEEDED: i64 = 2;
+
+/// The configuration and usage of the upstream server that a source depends on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ReplicationPreflight {
+    pub(super) wal_level: WalLevel,
+    pub(super) max_replication_slots: i64,
+    /// The number of replication slots that exist, including the main slot of the source.
+    pub(super) used_replication_slots: i64,
+    /// Whether the main slot of the source exists already.
+    pub(super) slot_exists: bool,
+    pub(super) max_wal_senders: i64,
+    /// The number of WAL senders that stream changes to other clients than the source.
+    ///
+    /// Only roles with the privileges of `pg_read_all_stats` see the state of the WAL senders of
+    /// other roles, so this is a lower bound for roles without them.
+    pub(super) active_wal_senders: i64,
+    pub(super) publication_exists: bool,
+}
+
+impl ReplicationPreflight {
+    /// Inspects the server `client` is connected to for the source with the main slot `slot`
+    /// that replicates `publication`.
+    pub(super) async fn fetch(
+        client: &Client,
+        slot: &str,
+        publication: &str,
+    ) -> Result<Self, TransientError> {
+        let query = format!(
+            "SELECT
+                current_setting('wal_level') AS wal_level,
+                CAST(current_setting('max_replication_slots') AS int8) AS max_replication_slots,
+                (SELECT count(*) FROM pg_catalog.pg_replication_slots) AS used_replication_slots,
+                EXISTS (
+                    SELECT 1 FROM pg_catalog.pg_replication_slots WHERE slot_name = {slot}
+                ) AS slot_exists,
+                CAST(current_setting('max_wal_senders') AS int8) AS max_wal_senders,
+                (
+                    SELECT count(*) FROM pg_catalog.pg_stat_replication
+                    WHERE pid <> pg_backend_pid()
+                        AND state IN ('catchup', 'streaming')
+                        AND pid IS DISTINCT FROM (
+                            SELECT active_pid FROM pg_catalog.pg_replication_slots
+                            WHERE slot_name = {slot}
+                        )
+                ) AS active_wal_senders,
+                EXISTS (
+                    SELECT 1 FROM pg_catalog.pg_publication WHERE pubname = {publication}
+                ) AS publication_exists;",
+            slot = quote_literal(slot),
+            publication = quote_literal(publication),
+        );
+        let row = simple_query_opt(client, &query)
+            .await?
+            .ok_or_else(|| anyhow!("preflight query returned no rows"))?;
+        Ok(Self::parse(|column| row.get(column))?)
+    }
+
+    /// Parses the result of the preflight query, whose columns `column` returns by name.
+    fn parse<'a>(column: impl Fn(&str) -> Option<&'a str>) -> Result<Self, anyhow::Error> {
+        let get = |name: &str| column(name).with_context(|| format!("{name} is NULL"));
+        let int = |name: &str| -> Result<i64, anyhow::Error> {
+            get(name)?
+                .parse()
+                .with_context(|| format!("invalid {name}"))
+        };
+        let flag = |name: &str| -> Result<bool, anyhow::Error> {
+            match get(name)? {
+                "t" => Ok(true),
+                "f" => Ok(false),
+                value => Err(anyhow!("invalid {name}: {value}")),
+            }
+        };
+        Ok(ReplicationPreflight {
+            wal_level: WalLevel::from_str(get("wal_level")?)?,
+            max_replication_slots: int("max_replication_slots")?,
+            used_replication_slots: int("used_replication_slots")?,
+            slot_exists: flag("slot_exists")?,
+            max_wal_senders: int("max_wal_senders")?,
+            active_wal_senders: int("active_wal_senders")?,
+            publication_exists: flag("publication_exists")?,
+        })
+    }
+
+    /// Checks that the server can support the source, whose snapshot creates a temporary
+    /// replication slot if `temporary_slot` is set.
+    pub(super) fn check(
+        &self,
+        publication: &str,
+        temporary_slot: bool,
+    ) -> Result<(), DefiniteError> {
+        if self.wal_level < WalLevel::Logical {
+            return Err(DefiniteError::WalLevelNotLogical(
+                self.wal_level.to_string(),
+            ));
+        }
+        if !self.publication_exists {
+            return Err(DefiniteError::PublicationDropped(publication.to_string()));
+        }
+        let slots_needed = i64::from(!self.slot_exists) + i64::from(temporary_slot);
+        let slots_available = self.max_replication_slots - self.used_replication_slots;
+        if slots_available < slots_needed {
+            return Err(DefiniteError::ReplicationSlotsExhausted {
+                needed: slots_needed,
+                available: slots_available.max(0),
+                max: self.max_replication_slots,
+            });
+        }
+        let wal_senders_available = self.max_wal_senders - self.active_wal_senders;
+        if wal_senders_available < WAL_SENDERS_NEEDED {
+            return Err(DefiniteError::WalSendersExhausted {
+                needed: WAL_SENDERS_NEEDED,
+                available: wal_senders_available.max(0),
+                max: self.max_wal_senders,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Quotes `s` as a string literal.
+fn quote_literal(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn parse(columns: &[(&str, &str)]) -> Result<ReplicationPreflight, anyhow::Error> {
+        let columns: BTreeMap<_, _> = columns.iter().copied().collect();
+        ReplicationPreflight::parse(|name| columns.get(name).copied())
+    }
+
+    fn preflight() -> ReplicationPreflight {
+        parse(&[
+            ("wal_level", "logical"),
+            ("max_replication_slots", "10"),
+            ("used_replication_slots", "8"),
+            ("slot_exists", "f"),
+            ("max_wal_senders", "10"),
+            ("active_wal_senders", "8"),
+            ("publication_exists", "t"),
+        ])
+        .unwrap()
+    }
+
+    #[mz_ore::test]
+    fn test_parse_preflight() {
+        assert_eq!(
+            preflight(),
+            ReplicationPreflight {
+                wal_level: WalLevel::Logical,
+                max_replication_slots: 10,
+                used_replication_slots: 8,
+                slot_exists: false,
+                max_wal_senders: 10,
+                active_wal_senders: 8,
+                publication_exists: true,
+            }
+        );
+
+        assert!(parse(&[("wal_level", "logical")]).is_err());
+        let mut columns = vec![
+            ("wal_level", "archive"),
+            ("max_replication_slots", "10"),
+            ("used_replication_slots", "8"),
+            ("slot_exists", "f"),
+            ("max_wal_senders", "10"),
+            ("active_wal_senders", "8"),
+            ("publication_exists", "t"),
+        ];
+        assert!(parse(&columns).is_err());
+        columns[0] = ("wal_level", "replica");
+        columns[3] = ("slot_exists", "maybe");
+        assert!(parse(&columns).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_check_wal_level() {
+        let mut preflight = preflight();
+        preflight.wal_level = WalLevel::Replica;
+        let err = preflight.check("mz_source", true).unwrap_err();
+        assert!(matches!(&err, DefiniteError::WalLevelNotLogical(level) if level == "replica"));
+        assert_eq!(
+            err.remediation().unwrap(),
+            "Set wal_level = logical in the PostgreSQL configuration and restart the server, \
+             then drop and recreate the source."
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_check_publication() {
+        let mut preflight = preflight();
+        preflight.publication_exists = false;
+        let err = preflight.check("mz_source", true).unwrap_err();
+        assert!(
+            matches!(err, DefiniteError::PublicationDropped(publication) if publication == "mz_source")
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_check_replication_slots() {
+        // The main slot and the temporary slot of the snapshot fit.
+        let mut preflight = preflight();
+        assert!(preflight.check("mz_source", true).is_ok());
+
+        // Only the main slot does.
+        preflight.used_replication_slots = 9;
+        let err = preflight.check("mz_source", true).unwrap_err();
+        assert!(matches!(
+            &err,
+            DefiniteError::ReplicationSlotsExhausted {
+                needed: 2,
+                available: 1,
+                max: 10,
+            }
+        ));
+        assert_eq!(
+            err.remediation().unwrap(),
+            "Increase max_replication_slots to at least 11 and restart the server, or drop \
+             unused replication slots, then drop and recreate the source."
+        );
+        // Unless the snapshot doesn't create a temporary slot, or the main slot exists already.
+        assert!(preflight.check("mz_source", false).is_ok());
+        preflight.slot_exists = true;
+        assert!(preflight.check("mz_source", true).is_ok());
+
+        // Other slots can exceed the maximum, e.g. after it was lowered.
+        preflight.used_replication_slots = 12;
+        let err = preflight.check("mz_source", true).unwrap_err();
+        assert!(matches!(
+            &err,
+            DefiniteError::ReplicationSlotsExhausted {
+                needed: 1,
+                available: 0,
+                max: 10,
+            }
+        ));
+    }
+
+    #[mz_ore::test]
+    fn test_check_wal_senders() {
+        let mut preflight = preflight();
+        preflight.active_wal_senders = 9;
+        let err = preflight.check("mz_source", true).unwrap_err();
+        assert!(matches!(
+            &err,
+            DefiniteError::WalSendersExhausted {
+                needed: 2,
+                available: 1,
+                max: 10,
+            }
+        ));
+        assert_eq!(
+            err.remediation().unwrap(),
+            "Increase max_wal_senders to at least 11 and restart the server, or close unused \
+             replication connections, then drop and recreate the source."
+        );
+    }
+}
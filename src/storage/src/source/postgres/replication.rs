@@ -129,17 +129,38 @@ static PG_EPOCH: Lazy<SystemTime> = Lazy::new(|| UNIX_EPOCH + Duration::from_sec
 /// See: <https://www.postgresql.org/message-id/CAMsr+YE2dSfHVr7iEv1GSPZihitWX-PMkD9QALEGcTYa+sdsgg@mail.gmail.com>
 const FEEDBACK_INTERVAL: Duration = Duration::from_secs(30);
 
-// A request to rewind a snapshot taken at `snapshot_lsn` to the initial LSN of the replication
-// slot. This is accomplished by emitting `(data, 0, -diff)` for all updates `(data, lsn, diff)`
-// whose `lsn <= snapshot_lsn`. By convention the snapshot is always emitted at LSN 0.
+/// The maximum number of tables that a single [`RewindRequest`] covers.
+const REWIND_REQUEST_BATCH_SIZE: usize = 1024;
+
+// A request to rewind the snapshots of a batch of tables taken at `snapshot_lsn` to the initial
+// LSN of the replication slot. This is accomplished by emitting `(data, 0, -diff)` for all updates
+// `(data, lsn, diff)` of each table whose `lsn <= snapshot_lsn`. By convention the snapshot is
+// always emitted at LSN 0.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct RewindRequest {
-    /// The table OID that should be rewound.
-    pub(crate) oid: u32,
-    /// The LSN that the snapshot was taken at.
+    /// The table OIDs that should be rewound.
+    pub(crate) oids: Vec<u32>,
+    /// The LSN that the snapshots were taken at.
     pub(crate) snapshot_lsn: MzOffset,
 }
 
+impl RewindRequest {
+    /// Returns the requests that rewind the snapshots of `oids` taken at `snapshot_lsn`, each
+    /// covering at most [`REWIND_REQUEST_BATCH_SIZE`] tables.
+    pub(crate) fn batched(
+        oids: impl IntoIterator<Item = u32>,
+        snapshot_lsn: MzOffset,
+    ) -> Vec<RewindRequest> {
+        let oids: Vec<_> = oids.into_iter().collect();
+        oids.chunks(REWIND_REQUEST_BATCH_SIZE)
+            .map(|oids| RewindRequest {
+                oids: oids.to_vec(),
+                snapshot_lsn,
+            })
+            .collect()
+    }
+}
+
 /// Renders the replication dataflow. See the module documentation for more information.
 pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
     scope: G,
@@ -202,7 +223,7 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                 .await?;
 
             tracing::info!(%id, "ensuring replication slot {slot} exists");
-            super::ensure_replication_slot(&replication_client, slot).await?;
+            mz_postgres_util::ensure_replication_slot(&replication_client, slot).await?;
             let slot_lsn = super::fetch_slot_resume_lsn(&replication_client, slot).await?;
 
             let resume_upper = Antichain::from_iter(
@@ -246,7 +267,7 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                                 .await;
                             return Ok(());
                         }
-                        rewinds.insert(req.oid, (caps.clone(), req));
+                        insert_rewinds(&mut rewinds, &caps, &req);
                     }
                 }
             }
@@ -338,13 +359,14 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
 
                                 let event_is_ok = event.is_ok();
                                 let data = (oid, event);
-                                if let Some((rewind_caps, req)) = rewinds.get(&oid) {
-                                    let [data_cap, _upper_cap] = rewind_caps;
-                                    // Do not "rewind" definite errors because
-                                    // we cannot guarantee that the snapshot
-                                    // dataflow produced a definite error.
-                                    if commit_lsn <= req.snapshot_lsn && event_is_ok {
-                                        let update = (data.clone(), MzOffset::from(0), -diff);
+                                // Do not "rewind" definite errors because
+                                // we cannot guarantee that the snapshot
+                                // dataflow produced a definite error.
+                                if event_is_ok {
+                                    let rewind =
+                                        rewind_update(&rewinds, oid, &data, commit_lsn, diff);
+                                    if let Some((rewind_caps, update)) = rewind {
+                                        let [data_cap, _upper_cap] = rewind_caps;
                                         data_output.give(data_cap, update).await;
                                     }
                                 }
@@ -387,7 +409,7 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                         .await;
                     upper_cap_set.downgrade([&new_upper]);
                     data_cap_set.downgrade([&new_upper]);
-                    rewinds.retain(|_, (_, req)| data_cap_set[0].time() <= &req.snapshot_lsn);
+                    rewinds.retain(|_, (_, snapshot_lsn)| data_cap_set[0].time() <= snapshot_lsn);
                 }
             }
             // We never expect the replication stream to gracefully end
@@ -436,6 +458,30 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
     )
 }
 
+/// Records the rewind that `req` requests for each of its tables, to be emitted with `caps`.
+fn insert_rewinds<C: Clone>(
+    rewinds: &mut BTreeMap<u32, (C, MzOffset)>,
+    caps: &C,
+    req: &RewindRequest,
+) {
+    for &oid in &req.oids {
+        rewinds.insert(oid, (caps.clone(), req.snapshot_lsn));
+    }
+}
+
+/// Returns the update that rewinds the update `(data, commit_lsn, diff)` of table `oid`, along
+/// with the capabilities to emit it with, if the table's snapshot includes the update.
+fn rewind_update<'a, C, D: Clone>(
+    rewinds: &'a BTreeMap<u32, (C, MzOffset)>,
+    oid: u32,
+    data: &D,
+    commit_lsn: MzOffset,
+    diff: Diff,
+) -> Option<(&'a C, (D, MzOffset, Diff))> {
+    let (caps, snapshot_lsn) = rewinds.get(&oid)?;
+    (commit_lsn <= *snapshot_lsn).then(|| (caps, (data.clone(), MzOffset::from(0), -diff)))
+}
+
 /// Produces the logical replication stream while taking care of regularly sending standby
 /// keepalive messages with the provided `uppers` stream.
 ///
@@ -506,6 +552,9 @@ async fn raw_stream<'a>(
 
     let progress_stat_shared_value = Arc::new(Mutex::new(None));
     let progress_stat_task_value = Arc::clone(&progress_stat_shared_value);
+    let slot_lag_shared_value = Arc::new(Mutex::new(None));
+    let slot_lag_task_value = Arc::clone(&slot_lag_shared_value);
+    let task_slot = slot.to_owned();
     let max_lsn_task_handle =
         mz_ore::task::spawn(|| format!("pg_current_wal_lsn:{}", config.id), async move {
             let mut interval = tokio::time::interval(std::time::Duration::from_secs(10));
@@ -514,6 +563,8 @@ async fn raw_stream<'a>(
                 interval.tick().await;
                 let lsn_or_err = super::fetch_max_lsn(&metadata_client).await;
                 *progress_stat_task_value.lock().expect("poisoned") = Some(lsn_or_err);
+                let lag_or_err = super::fetch_slot_lag(&metadata_client, &task_slot).await;
+                *slot_lag_task_value.lock().expect("poisoned") = Some(lag_or_err);
             }
         })
         .abort_on_drop();
@@ -586,6 +637,11 @@ async fn raw_stream<'a>(
                         )
                         .await;
                 }
+
+                let slot_lag = { slot_lag_shared_value.lock().expect("poisoned").take() };
+                if let Some(bytes_behind) = slot_lag.transpose()?.flatten() {
+                    *config.shared_upstream_lag.borrow_mut() = Some(bytes_behind);
+                }
             }
         }
     });
@@ -672,7 +728,7 @@ fn extract_transaction<'a>(
                             ssh_tunnel_manager,
                             connection_config,
                             publication,
-                            Some(rel_id),
+                            Some(&[rel_id]),
                         )
                         .await?;
                         let upstream_info = upstream_info.into_iter().map(|t| (t.oid, t)).collect();
@@ -767,3 +823,36 @@ async fn ensure_replication_timeline_id(
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_batched_rewinds() {
+        let snapshot_lsn = MzOffset::from(100);
+        let oids = 1..=5000;
+        let reqs = RewindRequest::batched(oids.clone(), snapshot_lsn);
+        assert_eq!(reqs.len(), 5);
+        assert!(reqs
+            .iter()
+            .all(|req| req.oids.len() <= REWIND_REQUEST_BATCH_SIZE));
+
+        let mut rewinds = BTreeMap::new();
+        for req in &reqs {
+            insert_rewinds(&mut rewinds, &(), req);
+        }
+
+        // Every table gets its own rewind of the updates up to the snapshot LSN.
+        for oid in oids {
+            for lsn in [0, 99, 100] {
+                let rewind = rewind_update(&rewinds, oid, &oid, MzOffset::from(lsn), 2);
+                assert_eq!(rewind, Some((&(), (oid, MzOffset::from(0), -2))), "{oid}");
+            }
+            let rewind = rewind_update(&rewinds, oid, &oid, MzOffset::from(101), 2);
+            assert_eq!(rewind, None, "{oid}");
+        }
+        // Tables that weren't snapshotted are not rewound.
+        assert_eq!(rewind_update(&rewinds, 5001, &5001, snapshot_lsn, 1), None);
+    }
+}
@@ -59,6 +59,41 @@
 //! The leader and follower steps described above are accomplished by the [`export_snapshot`] and
 //! [`use_snapshot`] functions respectively.
 //!
+//! ## Snapshotting from a standby
+//!
+//! The connection can name a hot standby of the server as its `SNAPSHOT HOST`, in which case the
+//! `COPY` queries run against the standby while replication still happens from the server. A
+//! standby cannot create the temporary replication slot, so the leader instead waits until the
+//! standby has replayed past the current WAL LSN of the server, which is not before the consistent
+//! point of the main slot, and then takes the snapshot at a replay LSN that it observes not to
+//! change while the snapshot is taken. That replay LSN then serves as the consistent point. The
+//! snapshot is exported and shared among workers on the standby as described above. See
+//! [`export_standby_snapshot`] for the details.
+//!
+//! ## Snapshot groups
+//!
+//! Sources that name the same `SNAPSHOT GROUP` on the same connection take their snapshots at the
+//! same LSN, so that their snapshots are consistent with each other. The storage controller
+//! creates the main slots of all members of a group and then exports a single snapshot, which it
+//! passes to the members when it runs them. The leader of each member imports that snapshot
+//! instead of exporting its own, and shares it among workers as described above. Because the
+//! snapshot was exported after the main slot was created, it can be rewound like any other.
+//!
+//! ## Read committed snapshots
+//!
+//! Sources can opt into fast snapshots with `ALTER SOURCE ... SET (SNAPSHOT READ COMMITTED =
+//! true)`, which skip the temporary replication slot. The leader instead takes the current WAL
+//! LSN of the server as the snapshot LSN, and every worker runs its `COPY` queries in its own
+//! `READ COMMITTED` transaction. Nothing is exported, so the leader broadcasts the
+//! [`READ_COMMITTED_SNAPSHOT`] marker instead of a snapshot identifier.
+//!
+//! Each `COPY` sees the rows committed before it starts, which includes everything up to the
+//! snapshot LSN, so rewinding works as described below. However, rows committed between the
+//! snapshot LSN and the start of a `COPY` are ingested both by the snapshot and by replication.
+//! The mode is thus only safe for append-only tables whose duplicates are tolerable, and the
+//! source reports a hint about the reduced guarantee with its status. Snapshots from a standby
+//! and of snapshot groups are taken as described above regardless.
+//!
 //! ## Coordinated transaction COMMIT
 //!
 //! When follower workers are done with snapshotting they commit their transaction, close their
@@ -131,19 +166,50 @@
 //!      │ updates  │requests
 //!      v          v
 //! ```
+//!
+//! Decoding can be slower than reading, e.g. when the casts are expensive and the upstream is
+//! fast, in which case the raw COPY data would pile up in the exchange between the reader and the
+//! decoders. To bound the memory used during snapshots, the decoders acknowledge the raw bytes
+//! they receive to the worker that read them via a feedback edge. A reader stops consuming its
+//! COPY stream while more than `max_in_flight_bytes` of it are unacknowledged.
+//!
+//! # Snapshot statistics
+//!
+//! Each worker reports the progress of the tables it snapshots in the source statistics of the
+//! ingestion, so that summing them across workers yields the progress of the whole snapshot:
+//!
+//! * `snapshot_records_known` is the number of rows in the worker's tables, i.e. the strict count
+//!   if one is collected and the estimate from `pg_class` otherwise. It is unknown until all of
+//!   the worker's tables have been counted and, because estimates may be off, it is revised to
+//!   the number of rows actually read once the worker has finished reading its tables.
+//! * `snapshot_records_staged` is the number of rows the worker has read so far. It only grows
+//!   within an attempt at taking the snapshot.
+//!
+//! Both are reset when the snapshot is restarted.
+//!
+//! Once a worker has counted its tables, it also reports the source as starting, with the progress
+//! of its snapshot as a hint that is refreshed every [`SNAPSHOT_PROGRESS_INTERVAL`], in the
+//! `snapshot_count` namespace of the source status, or a stall in that namespace if the tables
+//! could not be counted. Both are cleared once the worker has read its tables. If
+//! `wait_for_count` is set, the tables are counted before they are read and a failure to count
+//! them fails the snapshot instead.
 
 use std::collections::{BTreeMap, BTreeSet};
+use std::future::Future;
 use std::pin::pin;
 use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use anyhow::{bail, Context};
+use anyhow::{anyhow, bail, Context};
+use bytes::{Bytes, BytesMut};
 use differential_dataflow::{AsCollection, Collection};
 use futures::TryStreamExt;
 use mz_expr::MirScalarExpr;
+use mz_ore::cast::CastFrom;
 use mz_ore::result::ResultExt;
+use mz_ore::retry::{Retry, RetryResult};
 use mz_ore::task::{AbortOnDropHandle, JoinHandleExt};
 use mz_postgres_util::desc::PostgresTableDesc;
 use mz_postgres_util::tunnel::Config;
@@ -156,15 +222,19 @@ use mz_timely_util::builder_async::{
     Event as AsyncEvent, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
 };
 use mz_timely_util::operator::StreamExt as TimelyStreamExt;
-use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::channels::pact::{Exchange, Pipeline};
 use timely::dataflow::operators::{Broadcast, CapabilitySet, Concat, ConnectLoop, Feedback, Map};
 use timely::dataflow::{Scope, Stream};
 use timely::progress::{Antichain, Timestamp};
+use tokio::sync::oneshot;
+use tokio_postgres::error::SqlState;
 use tokio_postgres::types::{Oid, PgLsn};
-use tokio_postgres::Client;
-use tracing::{trace, warn};
+use tokio_postgres::{Client, SimpleQueryRow};
+use tracing::{debug, trace, warn};
 
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
 use crate::metrics::source::postgres::PgSnapshotMetrics;
+use crate::source::postgres::preflight::ReplicationPreflight;
 use crate::source::postgres::replication::RewindRequest;
 use crate::source::postgres::{verify_schema, DefiniteError, ReplicationError, TransientError};
 use crate::source::types::SourceReaderError;
@@ -182,17 +252,20 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
     Collection<G, (usize, Result<Row, SourceReaderError>), Diff>,
     Stream<G, RewindRequest>,
     Stream<G, ReplicationError>,
+    Stream<G, HealthStatusMessage>,
     PressOnDropButton,
 ) {
     let op_name = format!("TableReader({})", config.id);
     let mut builder = AsyncOperatorBuilder::new(op_name, scope.clone());
 
     let (feedback_handle, feedback_data) = scope.feedback(Default::default());
+    let (ack_handle, ack_data) = scope.feedback(Default::default());
 
     let (mut raw_handle, raw_data) = builder.new_output();
     let (mut rewinds_handle, rewinds) = builder.new_output();
     let (mut snapshot_handle, snapshot) = builder.new_output();
     let (mut definite_error_handle, definite_errors) = builder.new_output();
+    let (mut health_handle, health) = builder.new_output();
 
     // This operator needs to broadcast data to itself in order to synchronize the transaction
     // snapshot. However, none of the feedback capabilities result in output messages and for the
@@ -202,6 +275,13 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
     // The export id must be sent to all workes, so we broadcast the feedback connection
     snapshot.broadcast().connect_loop(feedback_handle);
 
+    // Acknowledgements of the raw COPY bytes received by the decoders, routed back to the worker
+    // that read them. Like the snapshot feedback, this input must not be connected to any output.
+    let mut ack_input = builder.new_disconnected_input(
+        &ack_data,
+        Exchange::new(|(reader, _): &(usize, usize)| u64::cast_from(*reader)),
+    );
+
     let is_snapshot_leader = config.responsible_for("snapshot_leader");
 
     // A global view of all exports that need to be snapshot by all workers. Note that this affects
@@ -233,17 +313,30 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
         .map(|(k, v)| (*k, v.clone()))
         .collect();
 
+    // Whether any worker has a table to snapshot. Like `exports_to_snapshot`, this is the same for
+    // all workers.
+    let any_table_to_snapshot = any_table_to_snapshot(
+        &exports_to_snapshot,
+        table_info
+            .values()
+            .map(|(output_index, _, _)| *output_index),
+    );
+
     let (button, transient_errors) = builder.build_fallible(move |caps| {
         Box::pin(async move {
             let id = config.id;
             let worker_id = config.worker_id;
+            let snapshot_config = config.config.parameters.pg_snapshot_config(id);
+            let max_in_flight_bytes = snapshot_config.max_in_flight_bytes;
+            let copy_buffer_bytes = snapshot_config.copy_buffer_bytes;
 
             let [
                 data_cap_set,
                 rewind_cap_set,
                 snapshot_cap_set,
-                definite_error_cap_set
-            ]: &mut [_; 4] = caps.try_into().unwrap();
+                definite_error_cap_set,
+                health_cap_set
+            ]: &mut [_; 5] = caps.try_into().unwrap();
 
             trace!(
                 %id,
@@ -258,6 +351,19 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                 return Ok(());
             }
 
+            let statistics = &config.source_statistics;
+            statistics.initialize_snapshot_records_staged();
+            if reader_snapshot_table_info.is_empty() {
+                statistics.set_snapshot_records_known(0);
+            }
+
+            // No worker would import the snapshot, so the leader doesn't export one. All workers
+            // know this, so the followers don't wait for it either.
+            if !any_table_to_snapshot {
+                trace!(%id, "no tables to snapshot");
+                return Ok(());
+            }
+
             let connection_config = connection
                 .connection
                 .config(
@@ -265,36 +371,125 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                     &config.config,
                 )
                 .await?;
+            // The snapshot is taken from the standby, if one is configured.
+            let snapshot_connection_config = connection
+                .connection
+                .snapshot_config(
+                    &*config.config.connection_context.secrets_reader,
+                    &config.config,
+                )
+                .await?;
+            let from_standby = connection.connection.snapshot_host.is_some();
+            // Snapshots exported by the server cannot be imported on a standby, so the snapshot of
+            // the snapshot group is only used when snapshotting from the server.
+            let group_snapshot = config.group_snapshot.clone().filter(|_| !from_standby);
             let task_name = format!("timely-{worker_id} PG snapshotter");
 
+            let snapshot_method =
+                SnapshotMethod::new(&snapshot_config, group_snapshot.is_some(), from_standby);
+
             let client = if is_snapshot_leader {
                 let client = connection_config
                     .connect_replication(&config.config.connection_context.ssh_tunnel_manager)
                     .await?;
-                // The main slot must be created *before* we start snapshotting so that we can be
-                // certain that the temporarly slot created for the snapshot start at an LSN that
-                // is greater than or equal to that of the main slot.
-                super::ensure_replication_slot(&client, &connection.publication_details.slot)
-                    .await?;
-
-                let snapshot_info = export_snapshot(&client).await?;
-                trace!(
-                    %id,
-                    "timely-{worker_id} exporting snapshot info {snapshot_info:?}");
-                snapshot_handle
-                    .give(&snapshot_cap_set[0], snapshot_info)
-                    .await;
+                // Fail before taking a snapshot that replication could not follow up on.
+                let preflight = ReplicationPreflight::fetch(
+                    &client,
+                    &connection.publication_details.slot,
+                    &connection.publication,
+                )
+                .await?;
+                let temporary_slot = snapshot_method == SnapshotMethod::TemporarySlot;
+                match preflight.check(&connection.publication, temporary_slot) {
+                    Ok(()) => {
+                        // The main slot must be created *before* we start snapshotting so that we
+                        // can be certain that the temporarly slot created for the snapshot start at
+                        // an LSN that is greater than or equal to that of the main slot.
+                        mz_postgres_util::ensure_replication_slot(
+                            &client,
+                            &connection.publication_details.slot,
+                        )
+                        .await?;
 
-                client
+                        let (client, snapshot_info) = match snapshot_method {
+                            SnapshotMethod::Group => {
+                                let group_snapshot =
+                                    group_snapshot.expect("snapshotting as a group");
+                                // The storage controller created the main slot before it exported
+                                // the group snapshot, so the group snapshot can be rewound using
+                                // the main slot.
+                                use_snapshot(&client, &group_snapshot.snapshot_id).await?;
+                                let snapshot_info =
+                                    (group_snapshot.snapshot_id, group_snapshot.lsn);
+                                (client, snapshot_info)
+                            }
+                            SnapshotMethod::Standby => {
+                                // The main slot's consistent point is not beyond the current WAL
+                                // LSN, so a snapshot at or beyond it can be rewound using the main
+                                // slot.
+                                let min_lsn = current_wal_lsn(&client).await?;
+                                drop(client);
+                                let client = snapshot_connection_config
+                                    .connect(
+                                        &task_name,
+                                        &config.config.connection_context.ssh_tunnel_manager,
+                                    )
+                                    .await?;
+                                let snapshot_info =
+                                    export_standby_snapshot(&client, min_lsn).await?;
+                                (client, snapshot_info)
+                            }
+                            SnapshotMethod::ReadCommitted => {
+                                let snapshot_info = begin_read_committed_snapshot(&client).await?;
+                                (client, snapshot_info)
+                            }
+                            SnapshotMethod::TemporarySlot => {
+                                let snapshot_info = export_snapshot(&client).await?;
+                                (client, snapshot_info)
+                            }
+                        };
+                        // Cross-check the snapshot against the main slot, which lives on the
+                        // server, so that a snapshot that can't be rewound into replication doesn't
+                        // go unnoticed.
+                        if !from_standby {
+                            let slot = &connection.publication_details.slot;
+                            if let Some(position) = fetch_slot_position(&client, slot).await? {
+                                if let Err(e) = check_snapshot_lsn(snapshot_info.1, &position) {
+                                    mz_ore::soft_panic_or_log!(
+                                        "snapshot of source {id} at {} does not match slot \
+                                            {slot}: {e}",
+                                        snapshot_info.1
+                                    );
+                                }
+                            }
+                        }
+                        trace!(
+                            %id,
+                            "timely-{worker_id} exporting snapshot info {snapshot_info:?}");
+                        snapshot_handle
+                            .give(&snapshot_cap_set[0], Ok(snapshot_info))
+                            .await;
+                        client
+                    }
+                    // All workers report the error for their tables once they receive it.
+                    Err(err) => {
+                        warn!(%id, "timely-{worker_id} preflight check failed: {err}");
+                        snapshot_handle.give(&snapshot_cap_set[0], Err(err)).await;
+                        client
+                    }
+                }
             } else {
                 // Only the snapshot leader needs a replication connection.
-                connection_config
+                snapshot_connection_config
                     .connect(
                         &task_name,
                         &config.config.connection_context.ssh_tunnel_manager,
                     )
                     .await?
             };
+            // Label the session so that the upstream's `pg_stat_activity` shows which source
+            // and worker runs each `COPY`.
+            set_application_name(&client, &snapshot_application_name(id, worker_id)).await?;
 
             // Configure statement_timeout based on param. We want to be able to
             // override the server value here in case it's set too low,
@@ -324,7 +519,28 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                     == config.config.parameters.pg_source_snapshot_statement_timeout
             }, "SET statement_timeout in PG snapshot did not take effect"};
 
-            let (snapshot, snapshot_lsn) = loop {
+            // Configure lock_timeout based on param, so that a `COPY` that is blocked by a
+            // conflicting lock upstream fails fast instead of waiting out the statement_timeout.
+            set_lock_timeout(
+                &client,
+                config.config.parameters.pg_source_snapshot_lock_timeout,
+            )
+            .await?;
+
+            mz_ore::soft_assert_no_log! {{
+                let row = simple_query_opt(&client, "SHOW lock_timeout;")
+                    .await?
+                    .unwrap();
+                let timeout = row.get("lock_timeout").unwrap().to_owned();
+
+                mz_repr::adt::interval::Interval::from_str(&timeout)
+                    .map(|i| i.duration())
+                    .unwrap()
+                    .unwrap()
+                    == config.config.parameters.pg_source_snapshot_lock_timeout
+            }, "SET lock_timeout in PG snapshot did not take effect"};
+
+            let snapshot_info = loop {
                 match snapshot_input.next().await {
                     Some(AsyncEvent::Data(_, mut data)) => {
                         break data.pop().expect("snapshot sent above")
@@ -336,6 +552,28 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                     ),
                 }
             };
+            let (snapshot, snapshot_lsn) = match snapshot_info {
+                Ok(snapshot_info) => snapshot_info,
+                // The upstream server cannot support the source, so there is nothing else to do.
+                // Like a missing publication below, this is not retractable.
+                Err(err) => {
+                    for oid in reader_snapshot_table_info.keys() {
+                        let update = (
+                            (*oid, worker_id, Err(err.clone())),
+                            MzOffset::from(u64::MAX),
+                            1,
+                        );
+                        raw_handle.give(&data_cap_set[0], update).await;
+                    }
+                    definite_error_handle
+                        .give(
+                            &definite_error_cap_set[0],
+                            ReplicationError::Definite(Rc::new(err)),
+                        )
+                        .await;
+                    return Ok(());
+                }
+            };
             // Snapshot leader is already in identified transaction but all other workers need to enter it.
             if !is_snapshot_leader {
                 trace!(%id, "timely-{worker_id} using snapshot id {snapshot:?}");
@@ -343,18 +581,58 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
             }
 
             // We have established a snapshot LSN so we can broadcast the rewind requests
-            for &oid in reader_snapshot_table_info.keys() {
-                trace!(%id, "timely-{worker_id} producing rewind request for {oid}");
-                let req = RewindRequest { oid, snapshot_lsn };
+            let oids = reader_snapshot_table_info.keys().copied();
+            for req in RewindRequest::batched(oids, snapshot_lsn) {
+                trace!(%id, "timely-{worker_id} producing rewind request for {:?}", req.oids);
                 rewinds_handle.give(&rewind_cap_set[0], req).await;
             }
             *rewind_cap_set = CapabilitySet::new();
 
+            if is_snapshot_leader {
+                let query = rewind_peek_query(
+                    &config.config.parameters.pg_snapshot_config(id),
+                    &connection.publication_details.slot,
+                    &connection.publication,
+                    snapshot_lsn,
+                );
+                if let Some(query) = query {
+                    // The snapshot transaction must not be disturbed, so peek from a separate
+                    // session. This is purely diagnostic so failures, including failures to
+                    // connect, are only logged.
+                    let peek = async {
+                        let peek_client = connection_config
+                            .connect(
+                                &format!("{task_name} rewind peek"),
+                                &config.config.connection_context.ssh_tunnel_manager,
+                            )
+                            .await?;
+                        simple_query_opt(&peek_client, &query).await
+                    };
+                    match peek.await {
+                        Ok(Some(row)) => debug!(
+                            %id,
+                            "timely-{worker_id} rewind window up to {snapshot_lsn} contains {} \
+                                changes, LSN range [{}, {}]",
+                            row.get("count").unwrap_or("0"),
+                            row.get("min_lsn").unwrap_or("none"),
+                            row.get("max_lsn").unwrap_or("none"),
+                        ),
+                        Ok(None) => {}
+                        Err(err) => {
+                            debug!(%id, "timely-{worker_id} failed to peek rewind window: {err}")
+                        }
+                    }
+                }
+            }
+
+            // Only fetch the metadata of the tables this worker snapshots; publications can cover
+            // many more tables than the source ingests.
+            let snapshot_oids: Vec<_> = reader_snapshot_table_info.keys().copied().collect();
             let upstream_info = match mz_postgres_util::publication_info(
                 &config.config.connection_context.ssh_tunnel_manager,
                 &connection_config,
                 &connection.publication,
-                None,
+                Some(&snapshot_oids),
             )
             .await
             {
@@ -370,7 +648,11 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                         // We pick `u64::MAX` as the LSN which will (in
                         // practice) never conflict any previously revealed
                         // portions of the TVC.
-                        let update = ((*oid, Err(err.clone())), MzOffset::from(u64::MAX), 1);
+                        let update = (
+                            (*oid, worker_id, Err(err.clone())),
+                            MzOffset::from(u64::MAX),
+                            1,
+                        );
                         raw_handle.give(&data_cap_set[0], update).await;
                     }
 
@@ -403,22 +685,35 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
                 .collect();
 
             let client = Arc::new(client);
+            let (records_known_tx, mut records_known_rx) = oneshot::channel();
             let _count_join_handle = record_table_sizes(
                 &config,
-                &connection_config,
+                &snapshot_connection_config,
                 &snapshot,
-                metrics,
+                metrics.clone(),
                 worker_tables,
                 Arc::clone(&client),
+                records_known_tx,
             )
             .await?;
 
+            // The number of raw bytes given to the decoders but not yet acknowledged by them. This
+            // carries over between tables, since they share the distribution.
+            let mut in_flight_bytes = 0;
+            let mut records_staged = 0;
+            // The number of rows the tables were counted to have, and when the progress of reading
+            // them was last reported, once they have been counted.
+            let mut records_known = None;
+            let mut progress_reported_at = Instant::now();
             for (&oid, (_, expected_desc, _)) in reader_snapshot_table_info.iter() {
                 let desc = match verify_schema(oid, expected_desc, &upstream_info) {
                     Ok(()) => expected_desc,
                     Err(err) => {
                         raw_handle
-                            .give(&data_cap_set[0], ((oid, Err(err)), MzOffset::minimum(), 1))
+                            .give(
+                                &data_cap_set[0],
+                                ((oid, worker_id, Err(err)), MzOffset::minimum(), 1),
+                            )
                             .await;
                         continue;
                     }
@@ -432,19 +727,94 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
 
                 // To handle quoted/keyword names, we can use `Ident`'s AST printing, which
                 // emulate's PG's rules for name formatting.
-                let query = format!(
-                    "COPY {}.{} TO STDOUT (FORMAT TEXT, DELIMITER '\t')",
+                let table_name = format!(
+                    "{}.{}",
                     Ident::new_unchecked(desc.namespace.clone()).to_ast_string(),
                     Ident::new_unchecked(desc.name.clone()).to_ast_string(),
                 );
-                let mut stream = pin!(client.copy_out_simple(&query).await?);
+                let query = format!("COPY {table_name} TO STDOUT (FORMAT TEXT, DELIMITER '\t')");
+                let mut stream = pin!(client
+                    .copy_out_simple(&query)
+                    .await
+                    .map_err(|err| lock_timeout_error(&table_name, err))?);
+                let mut buffer = CopyBuffer::new(copy_buffer_bytes);
+
+                while let Some(bytes) = stream
+                    .try_next()
+                    .await
+                    .map_err(|err| lock_timeout_error(&table_name, err))?
+                {
+                    if let Ok(count) = records_known_rx.try_recv() {
+                        if let Ok(count) = &count {
+                            statistics.set_snapshot_records_known(*count);
+                        }
+                        let update = snapshot_count_status(&count, records_staged);
+                        health_handle.give(&health_cap_set[0], update).await;
+                        records_known = count.ok();
+                        progress_reported_at = Instant::now();
+                    } else if let Some(count) = records_known {
+                        if progress_reported_at.elapsed() >= SNAPSHOT_PROGRESS_INTERVAL {
+                            let update = snapshot_count_status(&Ok(count), records_staged);
+                            health_handle.give(&health_cap_set[0], update).await;
+                            progress_reported_at = Instant::now();
+                        }
+                    }
 
-                while let Some(bytes) = stream.try_next().await? {
+                    // Each `CopyData` message carries a single row.
+                    records_staged += 1;
+                    statistics.inc_snapshot_records_staged_by(1);
+                    let Some(bytes) = buffer.push(bytes) else {
+                        continue;
+                    };
+
+                    in_flight_bytes += bytes.len();
                     raw_handle
-                        .give(&data_cap_set[0], ((oid, Ok(bytes)), MzOffset::minimum(), 1))
+                        .give(
+                            &data_cap_set[0],
+                            ((oid, worker_id, Ok(bytes)), MzOffset::minimum(), 1),
+                        )
+                        .await;
+
+                    if in_flight_bytes > max_in_flight_bytes {
+                        // Waiting on the acknowledgements yields to timely, which flushes the raw
+                        // output and lets the decoders drain it.
+                        let paused_at = Instant::now();
+                        while in_flight_bytes > max_in_flight_bytes {
+                            match ack_input.next().await {
+                                Some(AsyncEvent::Data(_, data)) => {
+                                    for (_, acked) in data {
+                                        in_flight_bytes -= acked;
+                                    }
+                                }
+                                Some(AsyncEvent::Progress(_)) => {}
+                                None => break,
+                            }
+                        }
+                        metrics.record_table_backpressure(&table_name, paused_at.elapsed());
+                    }
+                }
+                // Give the rows that are still buffered. Backpressure is applied once the next
+                // table is read, if any.
+                if let Some(bytes) = buffer.flush() {
+                    in_flight_bytes += bytes.len();
+                    raw_handle
+                        .give(
+                            &data_cap_set[0],
+                            ((oid, worker_id, Ok(bytes)), MzOffset::minimum(), 1),
+                        )
                         .await;
                 }
             }
+            // All rows have been read, so the number of rows is no longer an estimate, and neither
+            // its progress nor a failure to count it are worth reporting anymore.
+            drop(records_known_rx);
+            statistics.set_snapshot_records_known(records_staged);
+            let update = HealthStatusMessage {
+                index: 0,
+                namespace: StatusNamespace::SnapshotCount,
+                update: HealthStatusUpdate::running(),
+            };
+            health_handle.give(&health_cap_set[0], update).await;
             // Failure scenario after we have produced the snapshot, but before a successful COMMIT
             fail::fail_point!("pg_snapshot_failure", |_| Err(
                 TransientError::SyntheticError
@@ -469,63 +839,337 @@ pub(crate) fn render<G: Scope<Timestamp = MzOffset>>(
     });
 
     // Distribute the raw COPY data to all workers and turn it into a collection
-    let raw_collection = raw_data.distribute().as_collection();
+    let raw_data = raw_data.distribute();
+
+    // Acknowledge the raw COPY bytes to the workers that read them as soon as they have been
+    // received, which bounds the amount of data buffered in the distribution.
+    raw_data
+        .map(|((_, reader, event), _, _)| {
+            let bytes = event.as_ref().map_or(0, |bytes| bytes.len());
+            (reader, bytes)
+        })
+        .connect_loop(ack_handle);
+
+    let raw_collection = raw_data
+        .map(|((oid, _, event), time, diff)| ((oid, event), time, diff))
+        .as_collection();
 
-    // We now decode the COPY protocol and apply the cast expressions
+    // We now decode the COPY protocol and apply the cast expressions. Each message carries one or
+    // more whole rows, see `CopyBuffer`.
     let mut text_row = Row::default();
     let mut final_row = Row::default();
     let mut datum_vec = DatumVec::new();
-    let snapshot_updates = raw_collection.map(move |(oid, event)| {
+    let snapshot_updates = raw_collection.flat_map(move |(oid, event)| {
         let (output_index, _, casts) = &table_info[&oid];
 
-        let event = event.and_then(|bytes| {
-            decode_copy_row(&bytes, casts.len(), &mut text_row)?;
-            let datums = datum_vec.borrow_with(&text_row);
-            super::cast_row(casts, &datums, &mut final_row)?;
-            Ok(final_row.clone())
-        });
+        // Used by tests to simulate a slow decoder.
+        fail::fail_point!("pg_snapshot_decode");
 
-        (*output_index, event.err_into())
+        let bytes = match event {
+            Ok(bytes) => bytes,
+            Err(err) => return vec![(*output_index, Err(err.into()))],
+        };
+        copy_rows(&bytes)
+            .map(|data| {
+                let event = decode_copy_row(data, casts.len(), &mut text_row).and_then(|()| {
+                    let datums = datum_vec.borrow_with(&text_row);
+                    super::cast_row(casts, &datums, &mut final_row)?;
+                    Ok(final_row.clone())
+                });
+                (*output_index, event.err_into())
+            })
+            .collect::<Vec<_>>()
     });
 
     let errors = definite_errors.concat(&transient_errors.map(ReplicationError::from));
 
-    (snapshot_updates, rewinds, errors, button.press_on_drop())
+    (
+        snapshot_updates,
+        rewinds,
+        errors,
+        health,
+        button.press_on_drop(),
+    )
+}
+
+/// How often a worker refreshes the progress of its snapshot in the source status.
+const SNAPSHOT_PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Returns the health status that reports the outcome of counting a worker's tables, once it has
+/// read `records_staged` rows of them.
+///
+/// The source is starting until the worker has read its tables. Counting doesn't affect the data
+/// of the source, so a failure to count is reported as a stall in its own namespace, which ends
+/// once the worker has read its tables.
+fn snapshot_count_status(
+    records_known: &Result<u64, String>,
+    records_staged: u64,
+) -> HealthStatusMessage {
+    let update = match records_known {
+        Ok(records_known) => HealthStatusUpdate::starting_with_hint(format!(
+            "snapshot progress: {records_staged} of an estimated {records_known} rows read"
+        )),
+        Err(err) => HealthStatusUpdate::stalled(
+            format!("failed to count the rows to snapshot: {err}"),
+            None,
+        ),
+    };
+    HealthStatusMessage {
+        index: 0,
+        namespace: StatusNamespace::SnapshotCount,
+        update,
+    }
+}
+
+/// Reports whether any of the `exports_to_snapshot` is the output of a table, given the output
+/// indexes of all tables of the publication.
+fn any_table_to_snapshot(
+    exports_to_snapshot: &BTreeSet<usize>,
+    mut output_indexes: impl Iterator<Item = usize>,
+) -> bool {
+    output_indexes.any(|output_index| exports_to_snapshot.contains(&output_index))
+}
+
+/// How the snapshot leader establishes the snapshot that all workers read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SnapshotMethod {
+    /// Import the snapshot that the storage controller exported for the snapshot group.
+    Group,
+    /// Export a snapshot on the standby, at a replay LSN.
+    Standby,
+    /// Read in `READ COMMITTED` transactions, at the current WAL LSN of the server.
+    ReadCommitted,
+    /// Export a snapshot at the consistent point of a temporary replication slot.
+    TemporarySlot,
+}
+
+impl SnapshotMethod {
+    /// Returns the method of a source with the snapshot `config` that is a member of a snapshot
+    /// group with an exported snapshot, or snapshots from a standby.
+    fn new(config: &PgSourceSnapshotConfig, group_snapshot: bool, from_standby: bool) -> Self {
+        if group_snapshot {
+            SnapshotMethod::Group
+        } else if from_standby {
+            SnapshotMethod::Standby
+        } else if config.read_committed {
+            SnapshotMethod::ReadCommitted
+        } else {
+            SnapshotMethod::TemporarySlot
+        }
+    }
+}
+
+/// The snapshot identifier that the snapshot leader broadcasts for a `READ COMMITTED` snapshot,
+/// which cannot be exported.
+const READ_COMMITTED_SNAPSHOT: &str = "";
+
+/// The hint that a source reports with its status while it snapshots in `READ COMMITTED` mode.
+const READ_COMMITTED_HINT: &str = "The snapshot is taken in READ COMMITTED mode, so rows that \
+    are inserted while it is taken can be ingested twice, and rows that are updated or deleted \
+    meanwhile can be ingested inconsistently. Set SNAPSHOT READ COMMITTED to false to take \
+    consistent snapshots.";
+
+/// Returns the hint about the reduced consistency guarantee of `READ COMMITTED` snapshots to
+/// report for the source `config.id`, if it snapshots any of its exports in that mode.
+pub(crate) fn read_committed_hint(
+    config: &RawSourceCreationConfig,
+    connection: &PostgresSourceConnection,
+    subsource_resume_uppers: &BTreeMap<GlobalId, Antichain<MzOffset>>,
+) -> Option<String> {
+    let from_standby = connection.connection.snapshot_host.is_some();
+    let group_snapshot = config.group_snapshot.is_some() && !from_standby;
+    let snapshot_config = config.config.parameters.pg_snapshot_config(config.id);
+    let method = SnapshotMethod::new(&snapshot_config, group_snapshot, from_standby);
+    let any_export_to_snapshot = subsource_resume_uppers
+        .iter()
+        .any(|(id, upper)| *id != config.id && *upper == [MzOffset::minimum()]);
+    (method == SnapshotMethod::ReadCommitted && any_export_to_snapshot)
+        .then(|| READ_COMMITTED_HINT.to_string())
+}
+
+/// Starts a read-only `READ COMMITTED` transaction on the SQL session of `client`, which is
+/// connected to the server. Returns the [`READ_COMMITTED_SNAPSHOT`] marker and the current WAL
+/// LSN of the server, which is taken before the transaction and so before any `COPY` of any
+/// worker.
+async fn begin_read_committed_snapshot(
+    client: &Client,
+) -> Result<(String, MzOffset), TransientError> {
+    let snapshot_lsn = current_wal_lsn(client).await?;
+    use_snapshot(client, READ_COMMITTED_SNAPSHOT).await?;
+    Ok((
+        READ_COMMITTED_SNAPSHOT.to_string(),
+        MzOffset::from(snapshot_lsn),
+    ))
 }
 
 /// Starts a read-only transaction on the SQL session of `client` at a consistent LSN point by
 /// creating a temporary replication slot. Returns a snapshot identifier that can be imported in
 /// other SQL session and the LSN of the consistent point.
 async fn export_snapshot(client: &Client) -> Result<(String, MzOffset), TransientError> {
-    client
-        .simple_query("BEGIN READ ONLY ISOLATION LEVEL REPEATABLE READ;")
-        .await?;
-    // A temporary replication slot is the only way to get the tx in a consistent LSN point
     let slot = format!("mzsnapshot_{}", uuid::Uuid::new_v4()).replace('-', "");
-    let query =
-        format!("CREATE_REPLICATION_SLOT {slot:?} TEMPORARY LOGICAL \"pgoutput\" USE_SNAPSHOT");
-    let row = simple_query_opt(client, &query).await?.unwrap();
-    let consistent_point: PgLsn = row.get("consistent_point").unwrap().parse().unwrap();
+    let (snapshot, snapshot_lsn) = mz_postgres_util::export_snapshot(client, &slot).await?;
+    Ok((snapshot, MzOffset::from(snapshot_lsn)))
+}
+
+/// Returns the current WAL LSN of the server `client` is connected to.
+async fn current_wal_lsn(client: &Client) -> Result<PgLsn, TransientError> {
+    let row = simple_query_opt(client, "SELECT pg_current_wal_lsn() AS lsn;")
+        .await?
+        .unwrap();
+    Ok(row.get("lsn").unwrap().parse().unwrap())
+}
+
+/// Starts a read-only transaction on the SQL session of `client`, which is connected to a hot
+/// standby, at a consistent LSN point that is not before `min_lsn`. Returns a snapshot identifier
+/// that can be imported in other SQL sessions on the standby and the LSN of the consistent point.
+///
+/// Standbys cannot create replication slots, so the consistent point is instead found by reading
+/// the replay LSN of the standby right before and right after taking the snapshot. If it did not
+/// change in between, the snapshot contains exactly the transactions that were replayed up to it,
+/// which makes the replay LSN the equivalent of the consistent point of a replication slot.
+async fn export_standby_snapshot(
+    client: &Client,
+    min_lsn: PgLsn,
+) -> Result<(String, MzOffset), TransientError> {
+    loop {
+        let row = simple_query_opt(
+            client,
+            "SELECT pg_is_in_recovery() AS in_recovery, pg_last_wal_replay_lsn() AS replay_lsn;",
+        )
+        .await?
+        .unwrap();
+        if row.get("in_recovery") != Some("t") {
+            return Err(TransientError::SnapshotHostNotStandby);
+        }
+        let before: Option<PgLsn> = row.get("replay_lsn").map(|lsn| lsn.parse().unwrap());
+        // The standby must have caught up with the server before the snapshot is taken, or the
+        // snapshot would be at an LSN that the main slot cannot rewind from.
+        let before = match before {
+            Some(before) if before >= min_lsn => before,
+            _ => {
+                trace!("standby replayed up to {before:?}, waiting for {min_lsn}");
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                continue;
+            }
+        };
 
-    let row = simple_query_opt(client, "SELECT pg_export_snapshot();")
+        client
+            .simple_query("BEGIN READ ONLY ISOLATION LEVEL REPEATABLE READ;")
+            .await?;
+        // The snapshot is taken at the start of the first statement in the transaction.
+        let row = simple_query_opt(
+            client,
+            "SELECT pg_export_snapshot() AS snapshot, pg_last_wal_replay_lsn() AS replay_lsn;",
+        )
         .await?
         .unwrap();
-    let snapshot = row.get("pg_export_snapshot").unwrap().to_owned();
-
-    // When creating a replication slot postgres returns the LSN of its consistent point, which is
-    // the LSN that must be passed to `START_REPLICATION` to cleanly transition from the snapshot
-    // phase to the replication phase. `START_REPLICATION` includes all transactions that commit at
-    // LSNs *greater than or equal* to the passed LSN. Therefore the snapshot phase must happen at
-    // the greatest LSN that is not beyond the consistent point. That LSN is `consistent_point - 1`
-    let consistent_point = u64::from(consistent_point)
-        .checked_sub(1)
-        .expect("consistent point is always non-zero");
-    Ok((snapshot, MzOffset::from(consistent_point)))
+        let after: PgLsn = row.get("replay_lsn").unwrap().parse().unwrap();
+        if before != after {
+            // The standby replayed more changes while the snapshot was taken, so it is unknown
+            // which of them the snapshot contains.
+            client.simple_query("ROLLBACK;").await?;
+            continue;
+        }
+        let snapshot = row.get("snapshot").unwrap().to_owned();
+
+        // Like the consistent point of a replication slot, the replay LSN is the LSN that the
+        // first transaction not contained in the snapshot commits at or after.
+        let consistent_point = u64::from(before)
+            .checked_sub(1)
+            .expect("replay LSN is always non-zero");
+        return Ok((snapshot, MzOffset::from(consistent_point)));
+    }
+}
+
+/// Returns the query that peeks at the changes of the main replication `slot` that a snapshot taken
+/// at `snapshot_lsn` is rewound by, or `None` if this diagnostic is disabled in `config`.
+///
+/// `pgoutput` produces binary output, so the binary variant of `pg_logical_slot_peek_changes` is
+/// used. Peeking does not consume the changes nor advance the slot.
+fn rewind_peek_query(
+    config: &PgSourceSnapshotConfig,
+    slot: &str,
+    publication: &str,
+    snapshot_lsn: MzOffset,
+) -> Option<String> {
+    if !config.peek_rewind_changes {
+        return None;
+    }
+    // The rewind includes all transactions committed at LSNs up to and including `snapshot_lsn`.
+    let upto_lsn = PgLsn::from(snapshot_lsn.offset + 1);
+    Some(format!(
+        "SELECT count(*) AS count, min(lsn) AS min_lsn, max(lsn) AS max_lsn \
+            FROM pg_logical_slot_peek_binary_changes(\
+                '{}', '{upto_lsn}', NULL, 'proto_version', '1', 'publication_names', '{}')",
+        slot.replace('\'', "''"),
+        publication.replace('\'', "''"),
+    ))
+}
+
+/// The position of a replication slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SlotPosition {
+    /// The oldest LSN the slot retains WAL for.
+    restart_lsn: MzOffset,
+    /// Replication from the slot resumes with the transactions that commit at or after this LSN.
+    confirmed_flush_lsn: MzOffset,
+}
+
+/// Returns the position of the replication `slot`, or `None` if the slot is still initializing.
+async fn fetch_slot_position(
+    client: &Client,
+    slot: &str,
+) -> Result<Option<SlotPosition>, TransientError> {
+    let query = format!(
+        "SELECT restart_lsn, confirmed_flush_lsn FROM pg_replication_slots WHERE slot_name = '{}'",
+        slot.replace('\'', "''"),
+    );
+    let Some(row) = simple_query_opt(client, &query).await? else {
+        return Err(TransientError::MissingReplicationSlot);
+    };
+    let lsn = |column: &str| {
+        row.get(column)
+            .map(|lsn: &str| MzOffset::from(lsn.parse::<PgLsn>().unwrap()))
+    };
+    let position = match (lsn("restart_lsn"), lsn("confirmed_flush_lsn")) {
+        (Some(restart_lsn), Some(confirmed_flush_lsn)) => Some(SlotPosition {
+            restart_lsn,
+            confirmed_flush_lsn,
+        }),
+        _ => None,
+    };
+    Ok(position)
+}
+
+/// Checks that a snapshot at `snapshot_lsn` can be rewound and continued using the main slot at
+/// `position`.
+fn check_snapshot_lsn(snapshot_lsn: MzOffset, position: &SlotPosition) -> Result<(), String> {
+    if position.restart_lsn > position.confirmed_flush_lsn {
+        return Err(format!(
+            "restart_lsn {} is beyond confirmed_flush_lsn {}",
+            position.restart_lsn, position.confirmed_flush_lsn
+        ));
+    }
+    // The snapshot contains the transactions committed up to and including `snapshot_lsn`, so
+    // replication must resume no later than the LSN after it, or transactions in between are lost.
+    if position.confirmed_flush_lsn > snapshot_lsn + 1 {
+        return Err(format!(
+            "confirmed_flush_lsn {} is beyond the LSN after the snapshot",
+            position.confirmed_flush_lsn
+        ));
+    }
+    Ok(())
 }
 
 /// Starts a read-only transaction on the SQL session of `client` at a the consistent LSN point of
 /// `snapshot`.
 async fn use_snapshot(client: &Client, snapshot: &str) -> Result<(), TransientError> {
+    if snapshot == READ_COMMITTED_SNAPSHOT {
+        client
+            .simple_query("BEGIN READ ONLY ISOLATION LEVEL READ COMMITTED;")
+            .await?;
+        return Ok(());
+    }
     client
         .simple_query("BEGIN READ ONLY ISOLATION LEVEL REPEATABLE READ;")
         .await?;
@@ -534,6 +1178,24 @@ async fn use_snapshot(client: &Client, snapshot: &str) -> Result<(), TransientEr
     Ok(())
 }
 
+/// Returns the `application_name` of the session that worker `worker_id` uses to snapshot the
+/// source `id`.
+///
+/// PostgreSQL truncates names to 63 bytes, which this stays within for all ids.
+fn snapshot_application_name(id: GlobalId, worker_id: usize) -> String {
+    format!("mz-snapshot-{id}-worker-{worker_id}")
+}
+
+async fn set_application_name(client: &Client, name: &str) -> Result<(), TransientError> {
+    client
+        .simple_query(&format!(
+            "SET application_name = '{}'",
+            name.replace('\'', "''")
+        ))
+        .await?;
+    Ok(())
+}
+
 async fn set_statement_timeout(client: &Client, timeout: Duration) -> Result<(), TransientError> {
     // Value is known to accept milliseconds w/o units.
     // https://www.postgresql.org/docs/current/runtime-config-client.html
@@ -543,6 +1205,64 @@ async fn set_statement_timeout(client: &Client, timeout: Duration) -> Result<(),
     Ok(())
 }
 
+async fn set_lock_timeout(client: &Client, timeout: Duration) -> Result<(), TransientError> {
+    // Like statement_timeout, accepts milliseconds w/o units, and 0 disables the timeout.
+    client
+        .simple_query(&format!("SET lock_timeout = {}", timeout.as_millis()))
+        .await?;
+    Ok(())
+}
+
+/// Converts an error of a query against `table` into a [`TransientError`], telling apart the
+/// failures to acquire a lock within `lock_timeout`.
+fn lock_timeout_error(table: &str, err: tokio_postgres::Error) -> TransientError {
+    if err.code() == Some(&SqlState::LOCK_NOT_AVAILABLE) {
+        TransientError::SnapshotLockTimeout(table.to_owned())
+    } else {
+        TransientError::SQLClient(err)
+    }
+}
+
+/// Coalesces the `CopyData` messages of a text encoded COPY stream into larger chunks, to reduce
+/// the number of messages the raw COPY data is distributed in.
+///
+/// Each `CopyData` message carries a single, whole row terminated by a newline, and newlines
+/// within values are escaped, so the chunks are always made up of whole rows that can be split
+/// again with [`copy_rows`].
+struct CopyBuffer {
+    /// The size at or above which a chunk is complete.
+    size: usize,
+    buffer: BytesMut,
+}
+
+impl CopyBuffer {
+    fn new(size: usize) -> Self {
+        CopyBuffer {
+            size,
+            buffer: BytesMut::new(),
+        }
+    }
+
+    /// Adds the row in `bytes` to the current chunk, returning the chunk if it is complete.
+    fn push(&mut self, bytes: Bytes) -> Option<Bytes> {
+        if self.buffer.is_empty() && bytes.len() >= self.size {
+            return Some(bytes);
+        }
+        self.buffer.extend_from_slice(&bytes);
+        (self.buffer.len() >= self.size).then(|| self.buffer.split().freeze())
+    }
+
+    /// Returns the current chunk, if it is not empty.
+    fn flush(&mut self) -> Option<Bytes> {
+        (!self.buffer.is_empty()).then(|| self.buffer.split().freeze())
+    }
+}
+
+/// Splits a chunk of text encoded COPY data into its rows, including their newline terminators.
+fn copy_rows(data: &[u8]) -> impl Iterator<Item = &[u8]> {
+    data.split_inclusive(|b| *b == b'\n')
+}
+
 /// Decodes a row of `col_len` columns obtained from a text encoded COPY query into `row`.
 fn decode_copy_row(data: &[u8], col_len: usize, row: &mut Row) -> Result<(), DefiniteError> {
     let mut packer = row.packer();
@@ -560,7 +1280,12 @@ fn decode_copy_row(data: &[u8], col_len: usize, row: &mut Row) -> Result<(), Def
     Ok(())
 }
 
-/// Record the sizes of the tables being snapshotted in `PgSnapshotMetrics`.
+/// Record the sizes of the tables being snapshotted in `PgSnapshotMetrics`, and send their total
+/// on `records_known_tx` once all tables have been counted, or the error if any of them could not
+/// be counted.
+///
+/// If `wait_for_count` is set, the tables are counted before this returns and errors are returned
+/// instead, which fails the snapshot.
 async fn record_table_sizes(
     config: &RawSourceCreationConfig,
     connection_config: &Config,
@@ -571,12 +1296,14 @@ async fn record_table_sizes(
     // An optimization: when `wait_for_count` is true, we can use the client
     // used for replication.
     replication_client: Arc<Client>,
+    records_known_tx: oneshot::Sender<Result<u64, String>>,
 ) -> Result<Option<AbortOnDropHandle<Result<(), anyhow::Error>>>, anyhow::Error> {
-    let snapshot_config = config.config.parameters.pg_snapshot_config;
+    let snapshot_config = config.config.parameters.pg_snapshot_config(config.id);
     let statement_timeout = config
         .config
         .parameters
         .pg_source_snapshot_statement_timeout;
+    let lock_timeout = config.config.parameters.pg_source_snapshot_lock_timeout;
     let connection_context = &config.config.connection_context;
 
     let source_id = config.id;
@@ -588,6 +1315,12 @@ async fn record_table_sizes(
 
     let task_name = format!("timely-{worker_id} PG snapshot counter");
 
+    // Whether the statistics are collected within a transaction, which is the case whenever they are
+    // collected from the snapshot.
+    let in_transaction = snapshot_config.wait_for_count
+        || snapshot_config.collect_strict_count
+        || snapshot_config.fallback_to_strict_count;
+
     // We create a new connection here so that postgres can actually process this in parallel
     // with the main snapshotting, unless we are waiting for the count.
     let client = if snapshot_config.wait_for_count {
@@ -598,6 +1331,7 @@ async fn record_table_sizes(
             .await?;
 
         set_statement_timeout(&new_client, statement_timeout).await?;
+        set_lock_timeout(&new_client, lock_timeout).await?;
 
         // If we want a strict count, we want to count the rows in the snapshot
         // determined in the operator.
@@ -612,8 +1346,11 @@ async fn record_table_sizes(
         let client = &client;
 
         let mut result = Ok(());
+        let mut records_known = 0;
         for (table, oid) in tables {
-            match collect_table_statistics(client, snapshot_config, &table, oid).await {
+            match collect_table_statistics(client, snapshot_config, in_transaction, &table, oid)
+                .await
+            {
                 Ok(stats) => {
                     if let Some(count) = stats.estimate_count {
                         metrics.record_table_estimate(table.clone(), count, stats.estimate_latency);
@@ -621,6 +1358,9 @@ async fn record_table_sizes(
                     if let Some(count) = stats.count {
                         metrics.record_table_count(table.clone(), count, stats.count_latency);
                     }
+                    // Prefer the strict count over the estimate.
+                    let count = stats.count.or(stats.estimate_count).unwrap_or(0);
+                    records_known += u64::try_from(count).unwrap_or(0);
                 }
                 Err(err) => {
                     if !snapshot_config.wait_for_count {
@@ -630,6 +1370,11 @@ async fn record_table_sizes(
                 }
             }
         }
+        // The operator may already have finished reading the tables.
+        let _ = records_known_tx.send(match &result {
+            Ok(()) => Ok(records_known),
+            Err(err) => Err(format!("{err:#}")),
+        });
         result.context(format!("{source_id}: "))?;
 
         // If we want a strict count, we want to count the rows in the snapshot
@@ -645,7 +1390,6 @@ async fn record_table_sizes(
     if snapshot_config.wait_for_count {
         jh.wait_and_assert_finished().await.map(|_| None)
     } else {
-        // TODO(guswynn): should errors be communicated back to the health stream?
         Ok(Some(jh.abort_on_drop()))
     }
 }
@@ -661,39 +1405,357 @@ struct TableStatistics {
 async fn collect_table_statistics(
     client: &Client,
     config: PgSourceSnapshotConfig,
+    in_transaction: bool,
     table: &str,
     oid: u32,
 ) -> Result<TableStatistics, anyhow::Error> {
+    collect_table_statistics_with(config, table, oid, |query| async move {
+        let row = query_table_statistic(client, &query, in_transaction).await?;
+        Ok(row.map(|row| row.get(0).unwrap().parse().unwrap()))
+    })
+    .await
+}
+
+/// Collects the statistics of `table`, running the statistic queries with `query`, which returns
+/// the single value of the row of a query, if it returned one.
+///
+/// The estimate from `pg_class` is skipped when the tables are strictly counted before they are
+/// read, as it would only hold the replication client for longer. Otherwise it is collected for
+/// its diagnostic value, even alongside a strict count.
+async fn collect_table_statistics_with<F, Fut>(
+    config: PgSourceSnapshotConfig,
+    table: &str,
+    oid: u32,
+    mut query: F,
+) -> Result<TableStatistics, anyhow::Error>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<Option<i64>, PostgresError>>,
+{
     use mz_ore::metrics::MetricsFutureExt;
     let mut stats = TableStatistics::default();
 
-    let estimate_row = simple_query_opt(
-        client,
-        &format!("SELECT reltuples::bigint AS estimate_count FROM pg_class WHERE oid = '{oid}'"),
-    )
-    .wall_time()
-    .set_at(&mut stats.estimate_latency)
-    .await?;
-
-    match estimate_row {
-        Some(row) => match row.get("estimate_count").unwrap().parse().unwrap() {
-            -1 => stats.estimate_count = None,
-            n => stats.estimate_count = Some(n),
-        },
-        None => bail!("failed to get estimate count for {table}"),
+    let skip_estimate = config.wait_for_count && config.collect_strict_count;
+    if !skip_estimate {
+        let estimate = query(format!(
+            "SELECT reltuples::bigint AS estimate_count FROM pg_class WHERE oid = '{oid}'"
+        ))
+        .wall_time()
+        .set_at(&mut stats.estimate_latency)
+        .await?;
+
+        match estimate {
+            Some(-1) => stats.estimate_count = None,
+            Some(n) => stats.estimate_count = Some(n),
+            None => bail!("failed to get estimate count for {table}"),
+        }
     }
 
     // Postgres returns an estimate of -1 if the table doesn't have sufficient writes/analysis/vacuuming happening.
     let should_fallback = config.fallback_to_strict_count && stats.estimate_count.is_none();
     if config.collect_strict_count || should_fallback {
-        let count_row = simple_query_opt(client, &format!("SELECT count(*) as count from {table}"))
+        let count = query(format!("SELECT count(*) as count from {table}"))
             .wall_time()
             .set_at(&mut stats.count_latency)
             .await?;
-        match count_row {
-            Some(row) => stats.count = Some(row.get("count").unwrap().parse().unwrap()),
+        match count {
+            Some(count) => stats.count = Some(count),
             None => bail!("failed to get count for {table}"),
         }
     }
     Ok(stats)
 }
+
+/// The number of times a table statistics query is attempted before giving up.
+const TABLE_STATISTICS_MAX_TRIES: usize = 3;
+
+/// Runs a query for a table statistic, retrying errors that are likely transient, e.g. a lock or
+/// statement timeout caused by contention on `pg_class`.
+///
+/// If `in_transaction` is set, each attempt runs in a savepoint so that a failed attempt does not
+/// abort the transaction the statistics are collected in.
+async fn query_table_statistic(
+    client: &Client,
+    query: &str,
+    in_transaction: bool,
+) -> Result<Option<SimpleQueryRow>, PostgresError> {
+    Retry::default()
+        .max_tries(TABLE_STATISTICS_MAX_TRIES)
+        .retry_async(|_| async {
+            // Used by tests to simulate a transient failure.
+            fail::fail_point!("pg_snapshot_statistic_failure", |_| {
+                RetryResult::RetryableErr(PostgresError::Generic(anyhow!(
+                    "synthetic table statistic failure"
+                )))
+            });
+
+            if in_transaction {
+                if let Err(err) = client.simple_query("SAVEPOINT mz_table_statistic").await {
+                    return RetryResult::FatalErr(err.into());
+                }
+            }
+
+            let result = simple_query_opt(client, query).await;
+
+            if in_transaction {
+                let end_savepoint = match &result {
+                    Ok(_) => "RELEASE SAVEPOINT mz_table_statistic",
+                    Err(_) => "ROLLBACK TO SAVEPOINT mz_table_statistic",
+                };
+                if let Err(err) = client.simple_query(end_savepoint).await {
+                    return RetryResult::FatalErr(err.into());
+                }
+            }
+
+            match result {
+                Ok(row) => RetryResult::Ok(row),
+                Err(err) if is_transient_statistic_error(&err) => {
+                    warn!(
+                        ?err,
+                        "transient error when querying table statistics, retrying"
+                    );
+                    RetryResult::RetryableErr(err)
+                }
+                Err(err) => RetryResult::FatalErr(err),
+            }
+        })
+        .await
+}
+
+/// Whether a table statistics query that failed with `err` is worth retrying.
+fn is_transient_statistic_error(err: &PostgresError) -> bool {
+    let PostgresError::Postgres(err) = err else {
+        return false;
+    };
+    let transient_codes = [
+        SqlState::LOCK_NOT_AVAILABLE,
+        SqlState::QUERY_CANCELED,
+        SqlState::T_R_DEADLOCK_DETECTED,
+        SqlState::T_R_SERIALIZATION_FAILURE,
+    ];
+    err.code()
+        .map_or(false, |code| transient_codes.contains(code))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_rewind_peek_query() {
+        let mut config = PgSourceSnapshotConfig::new();
+        let snapshot_lsn = MzOffset::from(0x1_0000_0010);
+
+        // Disabled by default, in which case no peek is issued.
+        assert!(!config.peek_rewind_changes);
+        assert_eq!(
+            rewind_peek_query(&config, "materialize_slot", "mz_pub", snapshot_lsn),
+            None
+        );
+
+        config.peek_rewind_changes = true;
+        let query = rewind_peek_query(&config, "materialize_slot", "it's", snapshot_lsn).unwrap();
+        assert!(query.contains("pg_logical_slot_peek_binary_changes('materialize_slot', '1/11'"));
+        assert!(query.contains("'publication_names', 'it''s'"));
+    }
+
+    #[mz_ore::test]
+    fn test_check_snapshot_lsn() {
+        let position = |restart_lsn: u64, confirmed_flush_lsn: u64| SlotPosition {
+            restart_lsn: MzOffset::from(restart_lsn),
+            confirmed_flush_lsn: MzOffset::from(confirmed_flush_lsn),
+        };
+
+        // A snapshot exported right after the slot was created is at its consistent point minus
+        // one, and later snapshots are rewound past the slot's position.
+        assert_eq!(
+            check_snapshot_lsn(MzOffset::from(99), &position(90, 100)),
+            Ok(())
+        );
+        assert_eq!(
+            check_snapshot_lsn(MzOffset::from(150), &position(90, 100)),
+            Ok(())
+        );
+
+        // An off-by-one in the snapshot LSN would skip the transaction at 100.
+        assert!(check_snapshot_lsn(MzOffset::from(98), &position(90, 100)).is_err());
+
+        // A slot can't resume before the WAL it retains.
+        assert!(check_snapshot_lsn(MzOffset::from(150), &position(110, 100)).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_any_table_to_snapshot() {
+        let exports_to_snapshot = BTreeSet::from([1, 2]);
+
+        // An export to snapshot whose table is not part of the publication anymore leaves no
+        // worker with a table to snapshot.
+        assert!(!any_table_to_snapshot(
+            &exports_to_snapshot,
+            [3, 4].into_iter()
+        ));
+        assert!(!any_table_to_snapshot(
+            &exports_to_snapshot,
+            std::iter::empty()
+        ));
+        assert!(!any_table_to_snapshot(&BTreeSet::new(), [1, 2].into_iter()));
+
+        assert!(any_table_to_snapshot(
+            &exports_to_snapshot,
+            [3, 2].into_iter()
+        ));
+    }
+
+    #[mz_ore::test]
+    fn test_snapshot_method() {
+        let mut config = PgSourceSnapshotConfig::new();
+        assert_eq!(
+            SnapshotMethod::new(&config, false, false),
+            SnapshotMethod::TemporarySlot
+        );
+
+        // Read committed snapshots don't create a temporary slot, and don't apply to snapshots
+        // of groups or from a standby.
+        config.read_committed = true;
+        assert_eq!(
+            SnapshotMethod::new(&config, false, false),
+            SnapshotMethod::ReadCommitted
+        );
+        assert_eq!(
+            SnapshotMethod::new(&config, true, false),
+            SnapshotMethod::Group
+        );
+        assert_eq!(
+            SnapshotMethod::new(&config, false, true),
+            SnapshotMethod::Standby
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_snapshot_application_name() {
+        let name = snapshot_application_name(GlobalId::User(42), 3);
+        assert_eq!(name, "mz-snapshot-u42-worker-3");
+
+        // The name must survive PostgreSQL's truncation to `NAMEDATALEN - 1` bytes.
+        let name = snapshot_application_name(GlobalId::User(u64::MAX), usize::MAX);
+        assert!(name.len() <= 63, "{name} would be truncated");
+    }
+
+    #[mz_ore::test]
+    fn test_snapshot_count_status() {
+        let status = snapshot_count_status(&Ok(1000), 10);
+        assert_eq!(status.namespace, StatusNamespace::SnapshotCount);
+        assert_eq!(
+            status.update,
+            HealthStatusUpdate::starting_with_hint(
+                "snapshot progress: 10 of an estimated 1000 rows read".into()
+            )
+        );
+
+        let status = snapshot_count_status(&Err("statement timeout".into()), 10);
+        assert_eq!(status.namespace, StatusNamespace::SnapshotCount);
+        assert_eq!(
+            status.update,
+            HealthStatusUpdate::stalled(
+                "failed to count the rows to snapshot: statement timeout".into(),
+                None
+            )
+        );
+        assert!(!status.update.should_halt());
+    }
+
+    #[mz_ore::test]
+    fn test_copy_buffer() {
+        // The `CopyData` messages of a COPY stream, each carrying a row with an escaped newline.
+        let messages: Vec<Bytes> = (0..1000)
+            .map(|i| Bytes::from(format!("{i}\ta\\nb\n")))
+            .collect();
+        let chunks = |size: usize| {
+            let mut buffer = CopyBuffer::new(size);
+            let mut chunks: Vec<Bytes> = messages
+                .iter()
+                .filter_map(|message| buffer.push(message.clone()))
+                .collect();
+            chunks.extend(buffer.flush());
+            chunks
+        };
+
+        // Without coalescing, every row is distributed in a message of its own.
+        let unbuffered = chunks(0);
+        assert_eq!(unbuffered.len(), messages.len());
+
+        // With it, far fewer messages are needed.
+        let buffered = chunks(4096);
+        assert!(
+            buffered.len() * 100 < unbuffered.len(),
+            "{} messages",
+            buffered.len()
+        );
+        let (_last, complete) = buffered.split_last().unwrap();
+        assert!(complete.iter().all(|chunk| chunk.len() >= 4096));
+
+        // Either way, the decoders see the same rows.
+        let rows: Vec<&[u8]> = buffered.iter().flat_map(|chunk| copy_rows(chunk)).collect();
+        let expected: Vec<&[u8]> = messages.iter().map(|message| &message[..]).collect();
+        assert_eq!(rows, expected);
+
+        let mut row = Row::default();
+        decode_copy_row(rows[1], 2, &mut row).unwrap();
+        assert_eq!(
+            row.unpack(),
+            vec![Datum::String("1"), Datum::String("a\nb")]
+        );
+    }
+
+    /// Collects the statistics of a table whose estimate is `estimate`, and returns them along
+    /// with the queries that were run.
+    async fn statistic_queries(
+        config: PgSourceSnapshotConfig,
+        estimate: i64,
+    ) -> (TableStatistics, Vec<String>) {
+        let queries = RefCell::new(Vec::new());
+        let stats = collect_table_statistics_with(config, "\"public\".\"t\"", 42, |query| {
+            let value = if query.contains("reltuples") {
+                estimate
+            } else {
+                100
+            };
+            queries.borrow_mut().push(query);
+            async move { Ok(Some(value)) }
+        })
+        .await
+        .unwrap();
+        (stats, queries.into_inner())
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `epoll_wait` on OS `linux`
+    async fn test_collect_table_statistics() {
+        let estimate_query =
+            "SELECT reltuples::bigint AS estimate_count FROM pg_class WHERE oid = '42'";
+        let count_query = "SELECT count(*) as count from \"public\".\"t\"";
+        let mut config = PgSourceSnapshotConfig::new();
+        config.collect_strict_count = true;
+
+        // Without waiting for the count, the estimate is collected alongside it.
+        config.wait_for_count = false;
+        let (stats, queries) = statistic_queries(config, 90).await;
+        assert_eq!(queries, [estimate_query, count_query]);
+        assert_eq!((stats.estimate_count, stats.count), (Some(90), Some(100)));
+
+        // When waiting for the count, only the count query runs.
+        config.wait_for_count = true;
+        let (stats, queries) = statistic_queries(config, 90).await;
+        assert_eq!(queries, [count_query]);
+        assert_eq!((stats.estimate_count, stats.count), (None, Some(100)));
+
+        // Falling back to the strict count still relies on the estimate.
+        config.collect_strict_count = false;
+        config.fallback_to_strict_count = true;
+        let (stats, queries) = statistic_queries(config, -1).await;
+        assert_eq!(queries, [estimate_query, count_query]);
+        assert_eq!((stats.estimate_count, stats.count), (None, Some(100)));
+    }
+}
@@ -38,7 +38,7 @@ use std::time::Duration;
 use differential_dataflow::difference::Semigroup;
 use differential_dataflow::lattice::Lattice;
 use differential_dataflow::{AsCollection, Collection, Hashable};
-use futures::stream::StreamExt;
+use futures::stream::{LocalBoxStream, StreamExt};
 use itertools::Itertools;
 use mz_ore::cast::CastFrom;
 use mz_ore::channel::{InstrumentedChannelMetric, InstrumentedUnboundedReceiver};
@@ -51,6 +51,7 @@ use mz_repr::{Diff, GlobalId, RelationDesc, Row};
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::controller::CollectionMetadata;
 use mz_storage_types::errors::SourceError;
+use mz_storage_types::sources::postgres::GroupSnapshot;
 use mz_storage_types::sources::{SourceConnection, SourceExport, SourceTimestamp};
 use mz_timely_util::antichain::AntichainExt;
 use mz_timely_util::builder_async::{
@@ -116,10 +117,16 @@ pub struct RawSourceCreationConfig {
     pub source_statistics: SourceStatistics,
     /// Enables reporting the remap operator's write frontier.
     pub shared_remap_upper: Rc<RefCell<Antichain<mz_repr::Timestamp>>>,
+    /// Place to report the number of bytes the upstream system is ahead of this source, for
+    /// source implementations that can measure it.
+    pub shared_upstream_lag: Rc<RefCell<Option<u64>>>,
     /// Configuration parameters, possibly from LaunchDarkly
     pub config: StorageConfiguration,
     /// The ID of this source remap/progress collection.
     pub remap_collection_id: GlobalId,
+    /// The snapshot of the snapshot group of this source that the storage controller exported, if
+    /// any. Only Postgres sources have snapshot groups.
+    pub group_snapshot: Option<GroupSnapshot>,
 }
 
 impl RawSourceCreationConfig {
@@ -202,6 +209,7 @@ where
         reclock_resume_upper(resume_rx, reclock_follower.share(), worker_id, id);
 
     let timestamp_desc = source_connection.timestamp_desc();
+    let native_timestamps = source_connection.native_timestamps();
 
     let (health, source_tokens) = {
         let config = config.clone();
@@ -224,8 +232,13 @@ where
     };
     tokens.extend(source_tokens);
 
-    let (remap_stream, remap_token) =
-        remap_operator(scope, config.clone(), source_upper_rx, timestamp_desc);
+    let (remap_stream, remap_token) = remap_operator(
+        scope,
+        config.clone(),
+        source_upper_rx,
+        timestamp_desc,
+        native_timestamps,
+    );
     tokens.push(remap_token);
 
     let streams = reclock_operator(scope, config, reclock_follower, source_rx, remap_stream);
@@ -307,7 +320,7 @@ where
                             *diff > 0,
                             "unexpected retraction of definite error"
                         );
-                        HealthStatusUpdate::ceasing(error.inner.to_string())
+                        HealthStatusUpdate::ceasing(error.inner.to_string(), None)
                     }
                 };
 
@@ -402,16 +415,48 @@ impl futures::Stream for RemapClock {
     }
 }
 
+/// A clock for sources whose data is timestamped with their own timestamps, which mints the
+/// timestamp that the source upper corresponds to whenever the source upper advances.
+///
+/// The clock is only ready once the source upper has advanced beyond the last timestamp it
+/// minted. The reclock operator only awaits the clock when minting bindings for an advanced source
+/// upper, so the clock doesn't need to wake it.
+struct NativeClock {
+    /// The source upper, as a timestamp of the source's own timeline.
+    source_upper: Rc<RefCell<Antichain<mz_repr::Timestamp>>>,
+    upper: Antichain<mz_repr::Timestamp>,
+}
+
+impl futures::Stream for NativeClock {
+    type Item = (mz_repr::Timestamp, Antichain<mz_repr::Timestamp>);
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Some(new_ts) = self.source_upper.borrow().as_option().copied() else {
+            return Poll::Pending;
+        };
+        if self.upper.less_equal(&new_ts) {
+            self.upper = Antichain::from_elem(new_ts.step_forward());
+            Poll::Ready(Some((new_ts, self.upper.clone())))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
 /// Mints new contents for the remap shard based on summaries about the source
 /// upper it receives from the raw reader operators.
 ///
 /// Only one worker will be active and write to the remap shard. All source
 /// upper summaries will be exchanged to it.
+///
+/// If `native_timestamps` is set, the source upper is bound to the timestamp it corresponds to in
+/// the source's own timeline, rather than to the wall clock.
 fn remap_operator<G, FromTime, M>(
     scope: &G,
     config: RawSourceCreationConfig,
     mut source_upper_rx: InstrumentedUnboundedReceiver<Event<FromTime, Infallible>, M>,
     remap_relation_desc: RelationDesc,
+    native_timestamps: bool,
 ) -> (Collection<G, FromTime, Diff>, PressOnDropButton)
 where
     G: Scope<Timestamp = mz_repr::Timestamp>,
@@ -434,8 +479,10 @@ where
         persist_clients,
         source_statistics: _,
         shared_remap_upper,
+        shared_upstream_lag: _,
         config: _,
         remap_collection_id,
+        group_snapshot: _,
     } = config;
 
     let chosen_worker = usize::cast_from(id.hashed() % u64::cast_from(worker_count));
@@ -469,7 +516,15 @@ where
         )
         .await
         .unwrap_or_else(|e| panic!("Failed to create remap handle for source {}: {}", name, e.display_with_causes()));
-        let clock = RemapClock::new(now.clone(), timestamp_interval);
+        let native_upper = Rc::new(RefCell::new(Antichain::from_elem(Timestamp::minimum())));
+        let clock: LocalBoxStream<'static, _> = if native_timestamps {
+            Box::pin(NativeClock {
+                source_upper: Rc::clone(&native_upper),
+                upper: Antichain::from_elem(Timestamp::minimum()),
+            })
+        } else {
+            Box::pin(RemapClock::new(now.clone(), timestamp_interval))
+        };
         let (mut timestamper, mut initial_batch) = ReclockOperator::new(remap_handle, clock).await;
 
         let mut source_upper = MutableAntichain::new_bottom(FromTime::minimum());
@@ -538,6 +593,13 @@ where
                         Event::Messages(_, _) => unreachable!(),
                     });
                     source_upper.update_iter(progress);
+                    if native_timestamps {
+                        *native_upper.borrow_mut() = source_upper
+                            .frontier()
+                            .iter()
+                            .map(|ts| ts.native_timestamp().expect("source has native timestamps"))
+                            .collect();
+                    }
                     trace!("timely-{worker_id} remap({id}) received source upper: {}", source_upper.pretty());
                 }
             }
@@ -576,6 +638,8 @@ where
     D: Semigroup + Into<Diff>,
     M: InstrumentedChannelMetric + 'static,
 {
+    let max_batch_size = config.config.parameters.max_batch_size(config.id);
+    let yield_budget = config.config.parameters.yield_budget(config.id);
     let RawSourceCreationConfig {
         name,
         id,
@@ -592,8 +656,10 @@ where
         persist_clients: _,
         source_statistics: _,
         shared_remap_upper: _,
+        shared_upstream_lag: _,
         config: _,
         remap_collection_id: _,
+        group_snapshot: _,
     } = config;
 
     // TODO(guswynn): expose function
@@ -698,6 +764,14 @@ where
                         .flat_map(|(_, batch)| batch)
                         .take_while(|(_, ts, _)| !reclock_source_upper.less_equal(ts))
                         .count();
+                    // Leave the messages beyond the batch size of the source for another pass, so
+                    // that new remap bindings and source progress are handled in between.
+                    if let Some(max_batch_size) = max_batch_size {
+                        if reclockable_count > max_batch_size {
+                            reclockable_count = max_batch_size;
+                            work_to_do.notify_one();
+                        }
+                    }
 
                     let msgs = untimestamped_batches
                         .iter_mut()
@@ -737,6 +811,11 @@ where
                         let ts_cap = cap_set.delayed(&into_ts);
                         reclocked_output.give(&ts_cap, (output, into_ts, diff)).await;
                         total_processed += 1;
+                        // Let the worker make progress on other dataflows once the source has
+                        // used up its yield budget.
+                        if yield_budget.is_some_and(|budget| total_processed % budget == 0) {
+                            tokio::task::yield_now().await;
+                        }
                     }
                     // The loop above might have completely emptied batches. We can now remove them
                     untimestamped_batches.retain(|(_, batch)| !batch.is_empty());
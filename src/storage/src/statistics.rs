@@ -42,6 +42,8 @@ pub(crate) struct SourceStatisticsMetricDefs {
     pub(crate) envelope_state_bytes: UIntGaugeVec,
     pub(crate) envelope_state_records: UIntGaugeVec,
     pub(crate) rehydration_latency_ms: IntGaugeVec,
+    pub(crate) snapshot_records_known: UIntGaugeVec,
+    pub(crate) snapshot_records_staged: UIntGaugeVec,
 
     // statistics that are not yet exposed to users.
     pub(crate) upstream_values: UIntGaugeVec,
@@ -91,6 +93,16 @@ impl SourceStatisticsMetricDefs {
                 help: "The amount of time in milliseconds it took for the worker to rehydrate the source envelope state. This will be specific to the envelope in use.",
                 var_labels: ["source_id", "worker_id", "parent_source_id", "shard_id", "envelope"],
             )),
+            snapshot_records_known: registry.register(metric!(
+                name: "mz_source_snapshot_records_known",
+                help: "The number of records in the source's snapshot that the worker is expected to stage. This may be an estimate.",
+                var_labels: ["source_id", "worker_id", "parent_source_id", "shard_id"],
+            )),
+            snapshot_records_staged: registry.register(metric!(
+                name: "mz_source_snapshot_records_staged",
+                help: "The number of records in the source's snapshot that the worker has staged.",
+                var_labels: ["source_id", "worker_id", "parent_source_id", "shard_id"],
+            )),
             upstream_values: registry.register(metric!(
                 name: "mz_source_upstream_values",
                 help: "The total number of _values_ (source-defined unit) present in upstream.",
@@ -119,6 +131,8 @@ pub struct SourceStatisticsMetrics {
     pub(crate) envelope_state_bytes: DeleteOnDropGauge<'static, AtomicU64, Vec<String>>,
     pub(crate) envelope_state_records: DeleteOnDropGauge<'static, AtomicU64, Vec<String>>,
     pub(crate) rehydration_latency_ms: DeleteOnDropGauge<'static, AtomicI64, Vec<String>>,
+    pub(crate) snapshot_records_known: DeleteOnDropGauge<'static, AtomicU64, Vec<String>>,
+    pub(crate) snapshot_records_staged: DeleteOnDropGauge<'static, AtomicU64, Vec<String>>,
 
     // statistics that are not yet exposed to users.
     pub(crate) upstream_values: DeleteOnDropGauge<'static, AtomicU64, Vec<String>>,
@@ -189,6 +203,18 @@ impl SourceStatisticsMetrics {
                 shard.clone(),
                 envelope.to_string(),
             ]),
+            snapshot_records_known: defs.snapshot_records_known.get_delete_on_drop_gauge(vec![
+                id.to_string(),
+                worker_id.to_string(),
+                parent_source_id.to_string(),
+                shard.clone(),
+            ]),
+            snapshot_records_staged: defs.snapshot_records_staged.get_delete_on_drop_gauge(vec![
+                id.to_string(),
+                worker_id.to_string(),
+                parent_source_id.to_string(),
+                shard.clone(),
+            ]),
             upstream_values: defs.upstream_values.get_delete_on_drop_gauge(vec![
                 id.to_string(),
                 worker_id.to_string(),
@@ -342,6 +368,9 @@ pub struct SourceStatisticsRecord {
     envelope_state_bytes: Option<u64>,
     envelope_state_records: Option<u64>,
     rehydration_latency_ms: Option<Option<i64>>,
+    // Snapshot progress, `None` if the source is not snapshotting.
+    snapshot_records_known: Option<u64>,
+    snapshot_records_staged: Option<u64>,
 }
 
 impl SourceStatisticsRecord {
@@ -356,6 +385,8 @@ impl SourceStatisticsRecord {
         self.envelope_state_bytes = Some(0);
         self.envelope_state_records = Some(0);
         self.rehydration_latency_ms = None;
+        self.snapshot_records_known = None;
+        self.snapshot_records_staged = None;
     }
 }
 
@@ -412,6 +443,8 @@ impl SourceStatistics {
                     envelope_state_bytes: Some(0),
                     envelope_state_records: Some(0),
                     rehydration_latency_ms: None,
+                    snapshot_records_known: None,
+                    snapshot_records_staged: None,
                 },
                 prom: SourceStatisticsMetrics::new(
                     metrics,
@@ -448,6 +481,8 @@ impl SourceStatistics {
             envelope_state_bytes: cur.stats.envelope_state_bytes?,
             envelope_state_records: cur.stats.envelope_state_records?,
             rehydration_latency_ms: cur.stats.rehydration_latency_ms?,
+            snapshot_records_known: cur.stats.snapshot_records_known,
+            snapshot_records_staged: cur.stats.snapshot_records_staged,
         })
     }
 
@@ -603,6 +638,31 @@ impl SourceStatistics {
         cur.prom.rehydration_latency_ms.set(value);
     }
 
+    /// Set the `snapshot_records_known` stat to the given value.
+    ///
+    /// The value may be an estimate, and can be revised by calling this again.
+    pub fn set_snapshot_records_known(&self, value: u64) {
+        let mut cur = self.stats.borrow_mut();
+        cur.stats.snapshot_records_known = Some(value);
+        cur.prom.snapshot_records_known.set(value);
+    }
+
+    /// Initialize the `snapshot_records_staged` stat to 0, marking the start of an attempt to
+    /// take the snapshot.
+    pub fn initialize_snapshot_records_staged(&self) {
+        let mut cur = self.stats.borrow_mut();
+        cur.stats.snapshot_records_staged = Some(0);
+        cur.prom.snapshot_records_staged.set(0);
+    }
+
+    /// Increment the `snapshot_records_staged` stat.
+    pub fn inc_snapshot_records_staged_by(&self, value: u64) {
+        let mut cur = self.stats.borrow_mut();
+        let updated = cur.stats.snapshot_records_staged.unwrap_or(0) + value;
+        cur.stats.snapshot_records_staged = Some(updated);
+        cur.prom.snapshot_records_staged.set(updated);
+    }
+
     /// Set the `upstream_values` stat to the given value.
     pub fn set_upstream_values(&self, value: u64) {
         let cur = self.stats.borrow_mut();
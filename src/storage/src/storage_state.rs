@@ -74,7 +74,7 @@
 //! clients on the way toward being run.
 
 use std::cell::RefCell;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -93,13 +93,15 @@ use mz_persist_types::codec_impls::UnitSchema;
 use mz_repr::{Diff, GlobalId, Timestamp};
 use mz_rocksdb::config::SharedWriteBufferManager;
 use mz_storage_client::client::{
-    RunIngestionCommand, StatusUpdate, StorageCommand, StorageResponse,
+    CollectionStateReport, RunIngestionCommand, StatusUpdate, StorageCommand, StorageResponse,
 };
+use mz_storage_client::statistics::SourceStatisticsUpdate;
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::connections::ConnectionContext;
 use mz_storage_types::controller::CollectionMetadata;
 use mz_storage_types::sinks::{MetadataFilled, StorageSinkDesc};
-use mz_storage_types::sources::{IngestionDescription, SourceData};
+use mz_storage_types::sources::postgres::GroupSnapshot;
+use mz_storage_types::sources::{IngestionDescription, SourceConnection, SourceData};
 use mz_storage_types::AlterCompatible;
 use mz_timely_util::builder_async::PressOnDropButton;
 use timely::communication::Allocate;
@@ -116,7 +118,9 @@ use crate::internal_control::{
 };
 use crate::metrics::StorageMetrics;
 use crate::statistics::{AggregatedStatistics, SinkStatistics, SourceStatistics};
-use crate::storage_state::async_storage_worker::{AsyncStorageWorker, AsyncStorageWorkerResponse};
+use crate::storage_state::async_storage_worker::{
+    AsyncStorageWorker, AsyncStorageWorkerResponse, CollectionShard,
+};
 
 pub mod async_storage_worker;
 
@@ -216,12 +220,20 @@ impl<'w, A: Allocate> Worker<'w, A> {
             sink_tokens: BTreeMap::new(),
             sink_write_frontiers: BTreeMap::new(),
             sink_handles: BTreeMap::new(),
+            sink_progress_resets: BTreeSet::new(),
             dropped_ids: BTreeSet::new(),
             aggregated_statistics: AggregatedStatistics::new(
                 timely_worker.index(),
                 timely_worker.peers(),
             ),
             object_status_updates: Default::default(),
+            upstream_lag: BTreeMap::new(),
+            statistics_reported_at: BTreeMap::new(),
+            ingestion_parameter_generations: BTreeMap::new(),
+            rendered_parameter_generations: BTreeMap::new(),
+            pending_parameter_generations: BTreeSet::new(),
+            ingestion_group_snapshots: BTreeMap::new(),
+            collection_state_requests: VecDeque::new(),
             internal_cmd_tx: command_sequencer,
             async_worker,
             storage_configuration: StorageConfiguration::new(connection_context),
@@ -288,6 +300,9 @@ pub struct StorageState {
     pub sink_write_frontiers: BTreeMap<GlobalId, Rc<RefCell<Antichain<Timestamp>>>>,
     /// See: [SinkHandle]
     pub sink_handles: BTreeMap<GlobalId, SinkHandle>,
+    /// Sinks that may discard their recorded progress if it no longer matches the state of their
+    /// external system. See [`mz_storage_client::client::RunSinkCommand::reset_progress`].
+    pub sink_progress_resets: BTreeSet<GlobalId>,
     /// Collection ids that have been dropped but not yet reported as dropped
     pub dropped_ids: BTreeSet<GlobalId>,
 
@@ -300,6 +315,27 @@ pub struct StorageState {
     /// status updates if the status of the ingestion/export in question has _changed_.
     pub object_status_updates: Rc<RefCell<Vec<StatusUpdate>>>,
 
+    /// Upstream lag, in bytes, reported by the source implementation of each ingestion.
+    ///
+    /// Source implementations put the latest observed lag in here and the worker forwards it to
+    /// the controller, leaving `None` behind.
+    pub upstream_lag: BTreeMap<GlobalId, Rc<RefCell<Option<u64>>>>,
+    /// When the statistics of each source collection were last reported to the controller.
+    pub statistics_reported_at: BTreeMap<GlobalId, Instant>,
+
+    /// The parameter generation that the most recent command for each ingestion was stamped with.
+    pub ingestion_parameter_generations: BTreeMap<GlobalId, u64>,
+    /// The parameter generation that each ingestion was last rendered with.
+    pub rendered_parameter_generations: BTreeMap<GlobalId, u64>,
+    /// Ingestions whose rendered parameter generation has not yet been reported to the controller.
+    pub pending_parameter_generations: BTreeSet<GlobalId>,
+    /// The snapshots of their snapshot groups that ingestions import. See
+    /// [`mz_storage_client::client::RunIngestionCommand::group_snapshot`].
+    pub ingestion_group_snapshots: BTreeMap<GlobalId, GroupSnapshot>,
+    /// Requests for the state of collections that have yet to be answered, in the order they
+    /// were received.
+    pub collection_state_requests: VecDeque<CollectionStateRequest>,
+
     /// Sender for cluster-internal storage commands. These can be sent from
     /// within workers/operators and will be distributed to all workers. For
     /// example, for shutting down an entire dataflow from within a
@@ -359,6 +395,21 @@ impl StorageInstanceContext {
     }
 }
 
+/// A [`StorageCommand::ReportCollectionState`] that has yet to be answered.
+#[derive(Debug)]
+pub struct CollectionStateRequest {
+    /// The reports of the requested collections, with the uppers that the worker had reported
+    /// when the request was received. Reporting an upper happens only after the data up to it
+    /// has been written, so the reported uppers are not beyond the persist uppers read after.
+    pub reports: Vec<CollectionStateReport>,
+    /// Whether the persist frontiers of the collections have yet to be read. Only worker 0 reads
+    /// them, so the other workers answer right away, without any.
+    pub awaiting_persist: bool,
+    /// Whether the request was received on an earlier connection to the controller, which the
+    /// answer cannot be sent to anymore.
+    pub stale: bool,
+}
+
 /// This maintains an additional read hold on the source data for a sink, alongside
 /// the controller's hold and the handle used to read the shard internally.
 /// This is useful because environmentd's hold might expire, and the handle we use
@@ -519,13 +570,10 @@ impl<'w, A: Allocate> Worker<'w, A> {
                 self.timely_worker.step();
             }
 
-            // Rerport any dropped ids
-            if !self.storage_state.dropped_ids.is_empty() {
-                let ids = std::mem::take(&mut self.storage_state.dropped_ids);
-                self.send_storage_response(&response_tx, StorageResponse::DroppedIds(ids));
-            }
-
             self.report_frontier_progress(&response_tx);
+            self.report_collection_state(&response_tx);
+            self.report_upstream_lag(&response_tx);
+            self.report_parameter_generations(&response_tx);
 
             // Report status updates if any are present
             if self.storage_state.object_status_updates.borrow().len() > 0 {
@@ -535,13 +583,23 @@ impl<'w, A: Allocate> Worker<'w, A> {
                 );
             }
 
+            // Report any dropped ids. This happens after all other responses,
+            // as no response may follow the `DroppedIds` for a collection.
+            if !self.storage_state.dropped_ids.is_empty() {
+                let ids = std::mem::take(&mut self.storage_state.dropped_ids);
+                self.send_storage_response(&response_tx, StorageResponse::DroppedIds(ids));
+            }
+
+            // Sources can override the interval at which their statistics are reported, so
+            // collect them at the shortest interval. `report_storage_statistics` only reports the
+            // statistics of sources whose interval has elapsed.
             if last_stats_time.is_none()
                 || last_stats_time.as_ref().unwrap().elapsed()
                     >= self
                         .storage_state
                         .storage_configuration
                         .parameters
-                        .statistics_collection_interval
+                        .min_statistics_collection_interval()
             {
                 let mut internal_cmd_tx = command_sequencer.borrow_mut();
                 self.report_storage_statistics(&response_tx, &mut *internal_cmd_tx);
@@ -632,6 +690,31 @@ impl<'w, A: Allocate> Worker<'w, A> {
                     source_resume_uppers,
                 });
             }
+            AsyncStorageWorkerResponse::CollectionStateRead(frontiers) => {
+                assert_eq!(
+                    self.timely_worker.index(),
+                    0,
+                    "only worker #0 is doing async processing"
+                );
+                // The async worker answers in the order of the requests.
+                let request = self
+                    .storage_state
+                    .collection_state_requests
+                    .iter_mut()
+                    .find(|request| request.awaiting_persist)
+                    .expect("collection state read without a request");
+                for report in &mut request.reports {
+                    match frontiers.get(&report.id) {
+                        Some(Ok((upper, since))) => {
+                            report.persist_upper = Some(upper.clone());
+                            report.persist_since = Some(since.clone());
+                        }
+                        Some(Err(e)) => report.persist_error = Some(e.clone()),
+                        None => (),
+                    }
+                }
+                request.awaiting_persist = false;
+            }
         }
     }
 
@@ -782,6 +865,11 @@ impl<'w, A: Allocate> Worker<'w, A> {
                     }
                 }
 
+                self.storage_state
+                    .upstream_lag
+                    .entry(ingestion_id)
+                    .or_insert_with(|| Rc::new(RefCell::new(None)));
+
                 // If all subsources of the source are finished, we can skip rendering entirely.
                 // Also, if `as_of` is empty, the dataflow has been finalized, so we can skip it as
                 // well.
@@ -810,6 +898,27 @@ impl<'w, A: Allocate> Worker<'w, A> {
                     resume_uppers,
                     source_resume_uppers,
                 );
+
+                // The controller stamps commands with the generation it had applied when building
+                // them, and it sends configuration updates ahead of the commands that depend on
+                // them, so the dataflow has been rendered with whichever of the two is newer.
+                let generation = std::cmp::max(
+                    self.storage_state
+                        .ingestion_parameter_generations
+                        .get(&ingestion_id)
+                        .copied()
+                        .unwrap_or_default(),
+                    self.storage_state
+                        .storage_configuration
+                        .parameters
+                        .generation,
+                );
+                self.storage_state
+                    .rendered_parameter_generations
+                    .insert(ingestion_id, generation);
+                self.storage_state
+                    .pending_parameter_generations
+                    .insert(ingestion_id);
             }
             InternalStorageCommand::RunSinkDataflow(sink_id, sink_description) => {
                 info!(
@@ -854,6 +963,13 @@ impl<'w, A: Allocate> Worker<'w, A> {
                     // Clean up per-source / per-sink state.
                     self.storage_state.source_uppers.remove(id);
                     self.storage_state.source_tokens.remove(id);
+                    self.storage_state.upstream_lag.remove(id);
+                    self.storage_state
+                        .ingestion_parameter_generations
+                        .remove(id);
+                    self.storage_state.rendered_parameter_generations.remove(id);
+                    self.storage_state.pending_parameter_generations.remove(id);
+                    self.storage_state.ingestion_group_snapshots.remove(id);
 
                     self.storage_state.sink_tokens.remove(id);
 
@@ -912,9 +1028,74 @@ impl<'w, A: Allocate> Worker<'w, A> {
             }
         }
 
+        // Sinks additionally report how far they have emitted their input, so that the
+        // controller can tell whether they are still backfilling.
+        let sink_progress: Vec<_> = new_uppers
+            .iter()
+            .filter(|(id, _)| self.storage_state.sink_write_frontiers.contains_key(id))
+            .map(|(id, emitted_upper)| StorageResponse::SinkProgress {
+                id: *id,
+                emitted_upper: emitted_upper.clone(),
+            })
+            .collect();
+
         if !new_uppers.is_empty() {
             self.send_storage_response(response_tx, StorageResponse::FrontierUppers(new_uppers));
         }
+        for response in sink_progress {
+            self.send_storage_response(response_tx, response);
+        }
+    }
+
+    /// Answer the requests for the state of collections whose persist frontiers have been read,
+    /// in the order they were received.
+    pub fn report_collection_state(&mut self, response_tx: &ResponseSender) {
+        let requests = &mut self.storage_state.collection_state_requests;
+        while requests
+            .front()
+            .is_some_and(|request| !request.awaiting_persist)
+        {
+            let request = requests.pop_front().expect("checked above");
+            if !request.stale {
+                // Ignore send errors because the coordinator is free to ignore our
+                // responses. This happens during shutdown.
+                let _ = response_tx.send(StorageResponse::CollectionStateReport(request.reports));
+            }
+        }
+    }
+
+    /// Report any upstream lag observed by source implementations back to the controller.
+    pub fn report_upstream_lag(&mut self, response_tx: &ResponseSender) {
+        for (id, lag) in self.storage_state.upstream_lag.iter() {
+            let Some(bytes_behind) = lag.borrow_mut().take() else {
+                continue;
+            };
+            self.send_storage_response(
+                response_tx,
+                StorageResponse::UpstreamLag {
+                    id: *id,
+                    bytes_behind,
+                },
+            );
+        }
+    }
+
+    /// Report the parameter generations that ingestions were rendered with back to the controller.
+    pub fn report_parameter_generations(&mut self, response_tx: &ResponseSender) {
+        let pending = std::mem::take(&mut self.storage_state.pending_parameter_generations);
+        for id in pending {
+            let Some(generation) = self.storage_state.rendered_parameter_generations.get(&id)
+            else {
+                continue;
+            };
+            self.send_storage_response(
+                response_tx,
+                StorageResponse::ParameterGeneration {
+                    id,
+                    generation: *generation,
+                },
+            );
+        }
     }
 
     /// Report source statistics back to the controller.
@@ -929,6 +1110,7 @@ impl<'w, A: Allocate> Worker<'w, A> {
         }
 
         let (sources, sinks) = self.storage_state.aggregated_statistics.snapshot();
+        let sources = self.sources_due_for_statistics(sources);
         if !sources.is_empty() || !sinks.is_empty() {
             self.send_storage_response(
                 response_tx,
@@ -937,6 +1119,52 @@ impl<'w, A: Allocate> Worker<'w, A> {
         }
     }
 
+    /// Retains the statistics of the source collections whose statistics interval has elapsed
+    /// since they were last reported. The statistics of subsources are reported at the interval
+    /// of their ingestion.
+    fn sources_due_for_statistics(
+        &mut self,
+        sources: Vec<SourceStatisticsUpdate>,
+    ) -> Vec<SourceStatisticsUpdate> {
+        let parameters = &self.storage_state.storage_configuration.parameters;
+        let intervals: BTreeMap<_, _> = self
+            .storage_state
+            .ingestions
+            .iter()
+            .flat_map(|(ingestion_id, ingestion)| {
+                let interval = parameters.statistics_collection_interval(*ingestion_id);
+                ingestion
+                    .source_exports
+                    .keys()
+                    .map(move |id| (*id, interval))
+            })
+            .collect();
+
+        let now = Instant::now();
+        let reported_at = &mut self.storage_state.statistics_reported_at;
+        // Whether each collection is due, decided once for the statistics of all its workers.
+        let mut due = BTreeMap::new();
+        let sources = sources
+            .into_iter()
+            .filter(|stat| {
+                let Some(interval) = intervals.get(&stat.id) else {
+                    return true;
+                };
+                *due.entry(stat.id).or_insert_with(|| {
+                    let due = reported_at
+                        .get(&stat.id)
+                        .map_or(true, |at| now.duration_since(*at) >= *interval);
+                    if due {
+                        reported_at.insert(stat.id, now);
+                    }
+                    due
+                })
+            })
+            .collect();
+        reported_at.retain(|id, _| intervals.contains_key(id));
+        sources
+    }
+
     /// Send a response to the coordinator.
     fn send_storage_response(&self, response_tx: &ResponseSender, response: StorageResponse) {
         // Ignore send errors because the coordinator is free to ignore our
@@ -1039,9 +1267,9 @@ impl<'w, A: Allocate> Worker<'w, A> {
                         }
                     }
                 }
-                StorageCommand::InitializationComplete | StorageCommand::UpdateConfiguration(_) => {
-                    ()
-                }
+                StorageCommand::InitializationComplete
+                | StorageCommand::UpdateConfiguration(_)
+                | StorageCommand::ReportCollectionState(_) => (),
             }
         }
 
@@ -1069,14 +1297,32 @@ impl<'w, A: Allocate> Worker<'w, A> {
 
                             let running_ingestion =
                                 self.storage_state.ingestions.get(&ingestion.id);
+                            let rendered_generation = self
+                                .storage_state
+                                .rendered_parameter_generations
+                                .get(&ingestion.id);
 
                             // We keep only:
                             // - The most recent version of the ingestion, which
                             //   is why these commands are run in reverse.
                             // - Ingestions whose descriptions are not exactly
-                            //   those that are currently running.
+                            //   those that are currently running, or that were
+                            //   rendered with older parameters than the
+                            //   command was built against.
+                            // - Ingestions that are asked to import a
+                            //   different group snapshot than the one they
+                            //   were rendered with.
+                            let group_snapshot_changed = ingestion.group_snapshot.is_some()
+                                && self
+                                    .storage_state
+                                    .ingestion_group_snapshots
+                                    .get(&ingestion.id)
+                                    != ingestion.group_snapshot.as_ref();
                             seen_most_recent_definition.insert(ingestion.id)
-                                && running_ingestion != Some(&ingestion.description)
+                                && (running_ingestion != Some(&ingestion.description)
+                                    || rendered_generation
+                                        .is_some_and(|g| *g < ingestion.parameter_generation)
+                                    || group_snapshot_changed)
                         }
                     })
                 }
@@ -1103,14 +1349,18 @@ impl<'w, A: Allocate> Worker<'w, A> {
                             //   is why these commands are run in reverse.
                             // - Sinks whose descriptions are not exactly
                             //   those that are currently running.
+                            // - Sinks that are asked to reset their
+                            //   progress.
                             seen_most_recent_definition.insert(export.id)
-                                && running_sink != Some(&export.description)
+                                && (running_sink != Some(&export.description)
+                                    || export.reset_progress)
                         }
                     })
                 }
                 StorageCommand::InitializationComplete
                 | StorageCommand::UpdateConfiguration(_)
-                | StorageCommand::AllowCompaction(_) => (),
+                | StorageCommand::AllowCompaction(_)
+                | StorageCommand::ReportCollectionState(_) => (),
             }
         }
 
@@ -1157,6 +1407,23 @@ impl<'w, A: Allocate> Worker<'w, A> {
             .reported_frontiers
             .retain(|id, _| expected_objects.contains(id));
 
+        // The new controller has not yet heard which parameters the running
+        // ingestions were rendered with.
+        self.storage_state.pending_parameter_generations = self
+            .storage_state
+            .rendered_parameter_generations
+            .keys()
+            .filter(|id| expected_objects.contains(id))
+            .copied()
+            .collect();
+
+        // The new controller has not requested the state of any collections yet. The requests of
+        // the previous one are kept until the async worker has answered them, so that its
+        // answers are matched to the right requests.
+        for request in &mut self.storage_state.collection_state_requests {
+            request.stale = true;
+        }
+
         // Reset the reported frontiers for the remaining objects.
         for (_, frontier) in &mut self.storage_state.reported_frontiers {
             *frontier = Antichain::from_elem(<_>::minimum());
@@ -1216,10 +1483,26 @@ impl StorageState {
                 }
             }
             StorageCommand::RunIngestions(ingestions) => {
-                for RunIngestionCommand { id, description } in ingestions {
+                for RunIngestionCommand {
+                    id,
+                    description,
+                    parameter_generation,
+                    group_snapshot,
+                } in ingestions
+                {
                     // Remember the ingestion description to facilitate possible
                     // reconciliation later.
                     self.ingestions.insert(id, description.clone());
+                    self.ingestion_parameter_generations
+                        .insert(id, parameter_generation);
+                    match group_snapshot {
+                        Some(group_snapshot) => {
+                            self.ingestion_group_snapshots.insert(id, group_snapshot);
+                        }
+                        None => {
+                            self.ingestion_group_snapshots.remove(&id);
+                        }
+                    }
 
                     // Initialize shared frontier reporting.
                     for id in description.subsource_ids() {
@@ -1248,6 +1531,11 @@ impl StorageState {
                     // Remember the sink description to facilitate possible
                     // reconciliation later.
                     let prev = self.exports.insert(export.id, export.description.clone());
+                    if export.reset_progress {
+                        self.sink_progress_resets.insert(export.id);
+                    } else {
+                        self.sink_progress_resets.remove(&export.id);
+                    }
 
                     // New sink, add state.
                     if prev.is_none() {
@@ -1314,6 +1602,7 @@ impl StorageState {
                         self.ingestions.remove(&id);
                         self.exports.remove(&id);
                         self.sink_handles.remove(&id);
+                        self.sink_progress_resets.remove(&id);
                         drop_ids.push(id);
 
                         // This will stop reporting of frontiers.
@@ -1350,6 +1639,60 @@ impl StorageState {
                     internal_cmd_tx.broadcast(InternalStorageCommand::DropDataflow(drop_ids));
                 }
             }
+            StorageCommand::ReportCollectionState(ids) => {
+                // Every worker answers, with the uppers it has reported. The persist frontiers
+                // are read by one worker, which answers once the async worker has read them.
+                let awaiting_persist = worker_index == 0;
+                if awaiting_persist {
+                    let shards = ids
+                        .iter()
+                        .filter_map(|id| self.collection_shard(*id))
+                        .collect();
+                    async_worker.read_collection_state(shards);
+                }
+                let reports = ids
+                    .into_iter()
+                    .map(|id| CollectionStateReport {
+                        id,
+                        persist_upper: None,
+                        persist_since: None,
+                        persist_error: None,
+                        reported_upper: self.reported_frontiers.get(&id).cloned(),
+                    })
+                    .collect();
+                self.collection_state_requests
+                    .push_back(CollectionStateRequest {
+                        reports,
+                        awaiting_persist,
+                        stale: false,
+                    });
+            }
         }
     }
+
+    /// Returns the shard of the collection `id` if it is exported by an ingestion, or is the
+    /// remap collection of one.
+    fn collection_shard(&self, id: GlobalId) -> Option<CollectionShard> {
+        self.ingestions.values().find_map(|ingestion| {
+            if let Some(export) = ingestion.source_exports.get(&id) {
+                let metadata = &export.storage_metadata;
+                Some(CollectionShard {
+                    id,
+                    persist_location: metadata.persist_location.clone(),
+                    shard: metadata.data_shard,
+                    relation_desc: metadata.relation_desc.clone(),
+                })
+            } else if ingestion.remap_collection_id == id {
+                let metadata = &ingestion.ingestion_metadata;
+                Some(CollectionShard {
+                    id,
+                    persist_location: metadata.persist_location.clone(),
+                    shard: metadata.remap_shard?,
+                    relation_desc: ingestion.desc.connection.timestamp_desc(),
+                })
+            } else {
+                None
+            }
+        })
+    }
 }
@@ -16,14 +16,15 @@
 use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::sync::Arc;
+use std::time::Duration;
 
 use differential_dataflow::lattice::Lattice;
 use mz_persist_client::cache::PersistClientCache;
 use mz_persist_client::read::ListenEvent;
-use mz_persist_client::Diagnostics;
+use mz_persist_client::{Diagnostics, PersistLocation, ShardId};
 use mz_persist_types::codec_impls::UnitSchema;
 use mz_persist_types::Codec64;
-use mz_repr::{Diff, GlobalId, Row};
+use mz_repr::{Diff, GlobalId, RelationDesc, Row};
 use mz_service::local::Activatable;
 use mz_storage_types::controller::CollectionMetadata;
 use mz_storage_types::sources::{
@@ -52,6 +53,22 @@ pub struct AsyncStorageWorker<T: Timestamp + Lattice + Codec64> {
 pub enum AsyncStorageWorkerCommand {
     /// Calculate a recent resumption frontier for the ingestion.
     UpdateFrontiers(GlobalId, IngestionDescription<CollectionMetadata>),
+    /// Read the upper and since of the shards of the given collections.
+    ReadCollectionState(Vec<CollectionShard>),
+}
+
+/// The shard of a collection whose state is read by
+/// [`AsyncStorageWorkerCommand::ReadCollectionState`].
+#[derive(Debug)]
+pub struct CollectionShard {
+    /// ID of the collection.
+    pub id: GlobalId,
+    /// The persist location of the shard.
+    pub persist_location: PersistLocation,
+    /// The shard of the collection.
+    pub shard: ShardId,
+    /// The `RelationDesc` that describes the contents of the shard.
+    pub relation_desc: RelationDesc,
 }
 
 /// Responses from [AsyncStorageWorker].
@@ -73,6 +90,9 @@ pub enum AsyncStorageWorkerResponse<T: Timestamp + Lattice + Codec64> {
         /// have already been durably ingested.
         source_resume_uppers: BTreeMap<GlobalId, Vec<Row>>,
     },
+    /// The upper and since of the shards of collections, or the error reading them failed with,
+    /// in response to [`AsyncStorageWorkerCommand::ReadCollectionState`].
+    CollectionStateRead(BTreeMap<GlobalId, Result<(Antichain<T>, Antichain<T>), String>>),
 }
 
 async fn reclock_resume_uppers<C, IntoTime>(
@@ -184,6 +204,58 @@ where
     source_resume_uppers
 }
 
+/// How long reading the state of a collection may take before the read is given up on. Reads
+/// share this worker with the rendering of ingestions, which an unresponsive persist location
+/// must not hold up indefinitely.
+const COLLECTION_STATE_READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Reads the current upper and since of the shard of a collection from persist, giving up after
+/// `timeout`.
+///
+/// This only reads the shard, so that reporting on a collection never interferes with its
+/// writers. Errors are returned as strings, to be reported to the controller.
+async fn read_collection_state<T>(
+    persist_clients: &PersistClientCache,
+    shard: &CollectionShard,
+    timeout: Duration,
+) -> Result<(Antichain<T>, Antichain<T>), String>
+where
+    T: Timestamp + Lattice + Codec64,
+{
+    let read = async {
+        let client = persist_clients
+            .open(shard.persist_location.clone())
+            .await
+            .map_err(|e| format!("error creating persist client: {e}"))?;
+
+        let mut read_handle = client
+            .open_leased_reader::<SourceData, (), T, Diff>(
+                shard.shard,
+                Arc::new(shard.relation_desc.clone()),
+                Arc::new(UnitSchema),
+                Diagnostics {
+                    shard_name: shard.id.to_string(),
+                    handle_purpose: format!("collection state report for {}", shard.id),
+                },
+            )
+            .await
+            .map_err(|e| format!("error opening shard {}: {e}", shard.shard))?;
+        let since = read_handle.since().clone();
+        let upper = read_handle.fetch_recent_upper().await;
+        read_handle.expire().await;
+
+        Ok((upper, since))
+    };
+
+    match tokio::time::timeout(timeout, read).await {
+        Ok(result) => result,
+        Err(_) => Err(format!(
+            "timed out reading shard {} after {timeout:?}",
+            shard.shard
+        )),
+    }
+}
+
 impl<T: Timestamp + Lattice + Codec64 + Display> AsyncStorageWorker<T> {
     /// Creates a new [`AsyncStorageWorker`].
     ///
@@ -392,6 +464,26 @@ impl<T: Timestamp + Lattice + Codec64 + Display> AsyncStorageWorker<T> {
                             source_resume_uppers,
                         });
 
+                        if let Err(_err) = res {
+                            // Receiver must have hung up.
+                            break;
+                        }
+                    }
+                    AsyncStorageWorkerCommand::ReadCollectionState(shards) => {
+                        let mut frontiers = BTreeMap::new();
+                        for shard in shards {
+                            let frontier = read_collection_state::<T>(
+                                &persist_clients,
+                                &shard,
+                                COLLECTION_STATE_READ_TIMEOUT,
+                            )
+                            .await;
+                            frontiers.insert(shard.id, frontier);
+                        }
+
+                        let res = response_tx
+                            .send(AsyncStorageWorkerResponse::CollectionStateRead(frontiers));
+
                         if let Err(_err) = res {
                             // Receiver must have hung up.
                             break;
@@ -419,6 +511,14 @@ impl<T: Timestamp + Lattice + Codec64 + Display> AsyncStorageWorker<T> {
         self.send(AsyncStorageWorkerCommand::UpdateFrontiers(id, ingestion))
     }
 
+    /// Reads the current upper and since of the shards of the given collections.
+    ///
+    /// Responses are sent in the order of the commands, so the responses to consecutive reads
+    /// can be told apart by their order.
+    pub fn read_collection_state(&self, shards: Vec<CollectionShard>) {
+        self.send(AsyncStorageWorkerCommand::ReadCollectionState(shards))
+    }
+
     fn send(&self, cmd: AsyncStorageWorkerCommand) {
         self.tx
             .send(cmd)
@@ -458,3 +558,44 @@ impl<T, A: Activatable> ActivatingSender<T, A> {
         res
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_ore::assert_contains;
+
+    use super::*;
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `socket` on OS `linux`
+    async fn test_read_collection_state_times_out() {
+        // A consensus server that accepts connections but never responds.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("binding listener");
+        let addr = listener.local_addr().expect("listener address");
+        mz_ore::task::spawn(|| "unresponsive-consensus", async move {
+            let mut connections = vec![];
+            while let Ok((connection, _)) = listener.accept().await {
+                connections.push(connection);
+            }
+        });
+
+        let shard = CollectionShard {
+            id: GlobalId::User(1),
+            persist_location: PersistLocation {
+                blob_uri: "mem://".into(),
+                consensus_uri: format!("postgres://root@{addr}/consensus"),
+            },
+            shard: ShardId::new(),
+            relation_desc: RelationDesc::empty(),
+        };
+        let persist_clients = PersistClientCache::new_no_metrics();
+        let result = read_collection_state::<mz_repr::Timestamp>(
+            &persist_clients,
+            &shard,
+            Duration::from_millis(500),
+        )
+        .await;
+        assert_contains!(result.unwrap_err(), "timed out reading shard");
+    }
+}
@@ -0,0 +1,303 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A circuit breaker that tracks the health of the backend of a timestamp
+//! oracle.
+//!
+//! Oracle operations retry until they succeed, so an unavailable backend only
+//! shows up as operations that don't return. The circuit breaker observes every
+//! attempt against the backend and opens once
+//! `circuit_breaker_failure_threshold` consecutive attempts have failed or
+//! taken longer than `circuit_breaker_latency_threshold`. It closes again once
+//! `circuit_breaker_recovery_threshold` consecutive attempts have succeeded in
+//! time. While it is open, callers can degrade gracefully instead of waiting on
+//! the oracle.
+
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::metrics::{CircuitBreakerMetrics, Metrics};
+use crate::postgres_oracle::DynamicConfig;
+
+/// The state of a [`CircuitBreaker`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitBreakerState {
+    /// The backend is healthy.
+    Closed,
+    /// The backend is unavailable or too slow.
+    Open,
+}
+
+impl CircuitBreakerState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitBreakerState::Closed => "closed",
+            CircuitBreakerState::Open => "open",
+        }
+    }
+}
+
+impl fmt::Display for CircuitBreakerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Tracks the health of the backend of a timestamp oracle, see the [module
+/// documentation](self).
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: Arc<DynamicConfig>,
+    metrics: CircuitBreakerMetrics,
+    /// The number of consecutive attempts that disagree with the current state,
+    /// i.e. unhealthy attempts while closed and healthy attempts while open.
+    streak: Mutex<usize>,
+    state: watch::Sender<CircuitBreakerState>,
+    /// The number of reads waiting on the backend while the breaker is open.
+    queued_reads: AtomicUsize,
+}
+
+impl CircuitBreaker {
+    /// Returns a new, closed [`CircuitBreaker`] whose thresholds are read from
+    /// `config`.
+    pub fn new(config: Arc<DynamicConfig>, metrics: &Metrics) -> Self {
+        let metrics = metrics.circuit_breaker.clone();
+        metrics.open.set(0);
+        let (state, _) = watch::channel(CircuitBreakerState::Closed);
+        CircuitBreaker {
+            config,
+            metrics,
+            streak: Mutex::new(0),
+            state,
+            queued_reads: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the current state of the circuit breaker.
+    pub fn state(&self) -> CircuitBreakerState {
+        *self.state.borrow()
+    }
+
+    /// Reports whether the circuit breaker is open.
+    pub fn is_open(&self) -> bool {
+        self.state() == CircuitBreakerState::Open
+    }
+
+    /// Returns a receiver that observes all changes to the state of the
+    /// circuit breaker.
+    pub fn subscribe(&self) -> watch::Receiver<CircuitBreakerState> {
+        self.state.subscribe()
+    }
+
+    /// Resolves once the circuit breaker is open.
+    pub async fn opened(&self) {
+        let mut rx = self.subscribe();
+        while *rx.borrow_and_update() != CircuitBreakerState::Open {
+            // The sender lives as long as `self`, so this can't fail.
+            let _ = rx.changed().await;
+        }
+    }
+
+    /// Runs `attempt` against the backend and records its outcome.
+    pub async fn observe<R, F>(&self, attempt: F) -> Result<R, anyhow::Error>
+    where
+        F: Future<Output = Result<R, anyhow::Error>>,
+    {
+        let start = Instant::now();
+        let res = attempt.await;
+        self.record_attempt(start.elapsed(), res.is_ok());
+        res
+    }
+
+    /// Records an attempt against the backend that took `elapsed` and either
+    /// succeeded or failed.
+    pub fn record_attempt(&self, elapsed: Duration, succeeded: bool) {
+        let healthy = succeeded && elapsed <= self.config.circuit_breaker_latency_threshold();
+
+        let mut streak = self.streak.lock().expect("lock poisoned");
+        match (self.state(), healthy) {
+            (CircuitBreakerState::Closed, true) | (CircuitBreakerState::Open, false) => {
+                *streak = 0;
+            }
+            (CircuitBreakerState::Closed, false) => {
+                *streak += 1;
+                let threshold = self.config.circuit_breaker_failure_threshold();
+                if *streak >= threshold {
+                    warn!(
+                        "opening timestamp oracle circuit breaker after {} failed or slow attempts",
+                        *streak
+                    );
+                    *streak = 0;
+                    self.metrics.trips.inc();
+                    self.transition(CircuitBreakerState::Open);
+                }
+            }
+            (CircuitBreakerState::Open, true) => {
+                *streak += 1;
+                let threshold = self.config.circuit_breaker_recovery_threshold();
+                if *streak >= threshold {
+                    info!(
+                        "closing timestamp oracle circuit breaker after {} successful attempts",
+                        *streak
+                    );
+                    *streak = 0;
+                    self.transition(CircuitBreakerState::Closed);
+                }
+            }
+        }
+    }
+
+    fn transition(&self, state: CircuitBreakerState) {
+        self.metrics
+            .open
+            .set(u64::from(state == CircuitBreakerState::Open));
+        self.state.send_replace(state);
+    }
+
+    /// Enqueues a read that waits on the backend while the circuit breaker is
+    /// open, unless `max_queued_reads` reads are waiting already.
+    pub fn try_queue_read(self: &Arc<Self>, max_queued_reads: usize) -> Option<QueuedRead> {
+        self.queued_reads
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |queued| {
+                (queued < max_queued_reads).then_some(queued + 1)
+            })
+            .ok()?;
+        self.metrics.queued_reads.inc();
+        Some(QueuedRead {
+            breaker: Arc::clone(self),
+        })
+    }
+}
+
+/// A read that waits on the backend while the circuit breaker is open. The
+/// read leaves the queue when this is dropped.
+#[derive(Debug)]
+pub struct QueuedRead {
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl Drop for QueuedRead {
+    fn drop(&mut self) {
+        self.breaker.queued_reads.fetch_sub(1, Ordering::SeqCst);
+        self.breaker.metrics.queued_reads.dec();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+
+    use mz_ore::metrics::MetricsRegistry;
+
+    use crate::postgres_oracle::{
+        retry_fallible, PostgresTimestampOracleConfig, PostgresTimestampOracleParameters,
+    };
+
+    use super::*;
+
+    fn test_config() -> PostgresTimestampOracleConfig {
+        let config =
+            PostgresTimestampOracleConfig::new("postgres://unused", &MetricsRegistry::new());
+        PostgresTimestampOracleParameters {
+            circuit_breaker_failure_threshold: Some(3),
+            circuit_breaker_latency_threshold: Some(Duration::from_secs(1)),
+            circuit_breaker_recovery_threshold: Some(2),
+            ..Default::default()
+        }
+        .apply(&config);
+        config
+    }
+
+    #[mz_ore::test(tokio::test)]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `epoll_wait` on OS `linux`
+    async fn test_circuit_breaker_opens_and_recovers() {
+        let config = test_config();
+        let breaker = Arc::clone(&config.circuit_breaker);
+        let metrics = Arc::clone(&config.metrics);
+
+        // An operation against an unavailable backend keeps retrying, and
+        // opens the breaker while it does.
+        let available = Arc::new(AtomicBool::new(false));
+        let op = mz_ore::task::spawn(|| "unavailable_oracle_op", {
+            let breaker = Arc::clone(&breaker);
+            let available = Arc::clone(&available);
+            async move {
+                retry_fallible(&metrics.retries.read_ts, || {
+                    breaker.observe(async {
+                        if available.load(Ordering::SeqCst) {
+                            Ok(())
+                        } else {
+                            Err(anyhow::anyhow!("unavailable"))
+                        }
+                    })
+                })
+                .await
+            }
+        });
+        tokio::time::timeout(Duration::from_secs(10), breaker.opened())
+            .await
+            .expect("breaker opens");
+        assert!(breaker.is_open());
+        assert_eq!(config.metrics.circuit_breaker.open.get(), 1);
+        assert_eq!(config.metrics.circuit_breaker.trips.get(), 1);
+
+        // A single success doesn't close the breaker.
+        available.store(true, Ordering::SeqCst);
+        op.await.expect("op succeeds");
+        assert!(breaker.is_open());
+
+        // Neither does a success that is too slow, which restarts the recovery.
+        breaker.record_attempt(Duration::from_secs(2), true);
+        breaker.record_attempt(Duration::ZERO, true);
+        assert!(breaker.is_open());
+
+        // Sustained success does.
+        breaker.record_attempt(Duration::ZERO, true);
+        assert_eq!(breaker.state(), CircuitBreakerState::Closed);
+        assert_eq!(config.metrics.circuit_breaker.open.get(), 0);
+    }
+
+    #[mz_ore::test]
+    fn test_circuit_breaker_latency() {
+        let config = test_config();
+        let breaker = &config.circuit_breaker;
+
+        // Slow attempts count as failures, but only consecutive ones.
+        breaker.record_attempt(Duration::from_secs(2), true);
+        breaker.record_attempt(Duration::from_secs(2), true);
+        breaker.record_attempt(Duration::ZERO, true);
+        breaker.record_attempt(Duration::from_secs(2), true);
+        assert!(!breaker.is_open());
+        breaker.record_attempt(Duration::ZERO, false);
+        breaker.record_attempt(Duration::from_secs(2), true);
+        assert!(breaker.is_open());
+    }
+
+    #[mz_ore::test]
+    fn test_circuit_breaker_queued_reads() {
+        let config = test_config();
+        let breaker = &config.circuit_breaker;
+
+        let first = breaker.try_queue_read(2).expect("queue has room");
+        let second = breaker.try_queue_read(2).expect("queue has room");
+        assert!(breaker.try_queue_read(2).is_none());
+        assert_eq!(config.metrics.circuit_breaker.queued_reads.get(), 2);
+
+        drop(first);
+        let _third = breaker.try_queue_read(2).expect("queue has room");
+        drop(second);
+        assert_eq!(config.metrics.circuit_breaker.queued_reads.get(), 1);
+    }
+}
@@ -20,6 +20,7 @@ use async_trait::async_trait;
 use mz_ore::now::{EpochMillis, NowFn};
 
 pub mod batching_oracle;
+pub mod circuit_breaker;
 pub mod metrics;
 pub mod postgres_oracle;
 pub mod retry;
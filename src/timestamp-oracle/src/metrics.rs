@@ -13,7 +13,7 @@ use std::time::{Duration, Instant};
 
 use mz_ore::metric;
 use mz_ore::metrics::raw::{CounterVec, IntCounterVec};
-use mz_ore::metrics::{Counter, IntCounter, MetricsRegistry};
+use mz_ore::metrics::{Counter, IntCounter, MetricsRegistry, UIntGauge};
 use mz_postgres_client::metrics::PostgresClientMetrics;
 
 use crate::retry::RetryStream;
@@ -37,6 +37,9 @@ pub struct Metrics {
     /// Metrics for each retry loop.
     pub retries: RetriesMetrics,
 
+    /// Metrics for the [`CircuitBreaker`](crate::circuit_breaker::CircuitBreaker).
+    pub circuit_breaker: CircuitBreakerMetrics,
+
     /// Metrics for [`PostgresClient`](mz_postgres_client::PostgresClient).
     pub postgres_client: PostgresClientMetrics,
 }
@@ -56,6 +59,7 @@ impl Metrics {
             oracle: vecs.oracle_metrics(),
             batching: vecs.batching_metrics(),
             retries: vecs.retries_metrics(),
+            circuit_breaker: vecs.circuit_breaker_metrics(),
             postgres_client: PostgresClientMetrics::new(registry, "mz_ts_oracle"),
             _vecs: vecs,
         }
@@ -76,6 +80,10 @@ struct MetricsVecs {
 
     batched_op_count: IntCounterVec,
     batches_count: IntCounterVec,
+
+    circuit_breaker_open: UIntGauge,
+    circuit_breaker_trips: IntCounter,
+    circuit_breaker_queued_reads: UIntGauge,
 }
 
 impl MetricsVecs {
@@ -134,6 +142,19 @@ impl MetricsVecs {
                 help: "count of batches of operations",
                 var_labels: ["op"],
             )),
+
+            circuit_breaker_open: registry.register(metric!(
+                name: "mz_ts_oracle_circuit_breaker_open",
+                help: "whether the oracle circuit breaker is open",
+            )),
+            circuit_breaker_trips: registry.register(metric!(
+                name: "mz_ts_oracle_circuit_breaker_trips_count",
+                help: "count of times the oracle circuit breaker opened",
+            )),
+            circuit_breaker_queued_reads: registry.register(metric!(
+                name: "mz_ts_oracle_circuit_breaker_queued_reads",
+                help: "number of reads waiting on the oracle while the circuit breaker is open",
+            )),
         }
     }
 
@@ -179,6 +200,14 @@ impl MetricsVecs {
         }
     }
 
+    fn circuit_breaker_metrics(&self) -> CircuitBreakerMetrics {
+        CircuitBreakerMetrics {
+            open: self.circuit_breaker_open.clone(),
+            trips: self.circuit_breaker_trips.clone(),
+            queued_reads: self.circuit_breaker_queued_reads.clone(),
+        }
+    }
+
     fn retry_metrics(&self, name: &str) -> RetryMetrics {
         RetryMetrics {
             name: name.to_owned(),
@@ -263,6 +292,13 @@ pub struct RetriesMetrics {
     pub(crate) apply_write: RetryMetrics,
 }
 
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerMetrics {
+    pub(crate) open: UIntGauge,
+    pub(crate) trips: IntCounter,
+    pub(crate) queued_reads: UIntGauge,
+}
+
 struct IncOnDrop(IntCounter);
 
 impl Drop for IncOnDrop {
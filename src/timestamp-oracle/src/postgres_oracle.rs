@@ -19,6 +19,9 @@ use async_trait::async_trait;
 use deadpool_postgres::{Object, PoolError};
 use dec::Decimal;
 use mz_adapter_types::timestamp_oracle::{
+    DEFAULT_PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_FAILURE_THRESHOLD,
+    DEFAULT_PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_LATENCY_THRESHOLD,
+    DEFAULT_PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_RECOVERY_THRESHOLD,
     DEFAULT_PG_TIMESTAMP_ORACLE_CONNECT_TIMEOUT, DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_MAX_SIZE,
     DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_MAX_WAIT, DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_TTL,
     DEFAULT_PG_TIMESTAMP_ORACLE_CONNPOOL_TTL_STAGGER, DEFAULT_PG_TIMESTAMP_ORACLE_TCP_USER_TIMEOUT,
@@ -31,6 +34,7 @@ use mz_repr::Timestamp;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
+use crate::circuit_breaker::CircuitBreaker;
 use crate::metrics::{Metrics, RetryMetrics};
 use crate::retry::Retry;
 use crate::WriteTimestamp;
@@ -65,6 +69,7 @@ where
     next: N,
     postgres_client: Arc<PostgresClient>,
     metrics: Arc<Metrics>,
+    circuit_breaker: Arc<CircuitBreaker>,
 }
 
 /// Configuration to connect to a Postgres-backed implementation of
@@ -76,6 +81,10 @@ pub struct PostgresTimestampOracleConfig {
 
     /// Configurations that can be dynamically updated.
     pub dynamic: Arc<DynamicConfig>,
+
+    /// Tracks the health of the Postgres/CRDB backend, shared by all oracles
+    /// opened with this config.
+    pub circuit_breaker: Arc<CircuitBreaker>,
 }
 
 impl From<PostgresTimestampOracleConfig> for PostgresClientConfig {
@@ -92,12 +101,14 @@ impl PostgresTimestampOracleConfig {
     pub fn new(url: &str, metrics_registry: &MetricsRegistry) -> Self {
         let metrics = Arc::new(Metrics::new(metrics_registry));
 
-        let dynamic = DynamicConfig::default();
+        let dynamic = Arc::new(DynamicConfig::default());
+        let circuit_breaker = Arc::new(CircuitBreaker::new(Arc::clone(&dynamic), &metrics));
 
         PostgresTimestampOracleConfig {
             url: url.to_string(),
             metrics,
-            dynamic: Arc::new(dynamic),
+            dynamic,
+            circuit_breaker,
         }
     }
 
@@ -120,12 +131,15 @@ impl PostgresTimestampOracleConfig {
             }
         };
 
-        let dynamic = DynamicConfig::default();
+        let metrics = Arc::new(Metrics::new(&MetricsRegistry::new()));
+        let dynamic = Arc::new(DynamicConfig::default());
+        let circuit_breaker = Arc::new(CircuitBreaker::new(Arc::clone(&dynamic), &metrics));
 
         let config = PostgresTimestampOracleConfig {
             url: url.to_string(),
-            metrics: Arc::new(Metrics::new(&MetricsRegistry::new())),
-            dynamic: Arc::new(dynamic),
+            metrics,
+            dynamic,
+            circuit_breaker,
         };
 
         Some(config)
@@ -173,6 +187,18 @@ pub struct DynamicConfig {
     /// amount of time that transmitted data may remain unacknowledged before
     /// the TCP connection is forcibly closed.
     pg_connection_pool_tcp_user_timeout: RwLock<Duration>,
+
+    /// The number of consecutive attempts against Postgres/CRDB that must fail,
+    /// or succeed too slowly, for the circuit breaker to open.
+    circuit_breaker_failure_threshold: AtomicUsize,
+
+    /// The latency above which a successful attempt against Postgres/CRDB
+    /// counts as a failure for the circuit breaker.
+    circuit_breaker_latency_threshold: RwLock<Duration>,
+
+    /// The number of consecutive attempts against Postgres/CRDB that must
+    /// succeed in time for an open circuit breaker to close again.
+    circuit_breaker_recovery_threshold: AtomicUsize,
 }
 
 impl Default for DynamicConfig {
@@ -197,6 +223,15 @@ impl Default for DynamicConfig {
             pg_connection_pool_tcp_user_timeout: RwLock::new(
                 DEFAULT_PG_TIMESTAMP_ORACLE_TCP_USER_TIMEOUT,
             ),
+            circuit_breaker_failure_threshold: AtomicUsize::new(
+                DEFAULT_PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_FAILURE_THRESHOLD,
+            ),
+            circuit_breaker_latency_threshold: RwLock::new(
+                DEFAULT_PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_LATENCY_THRESHOLD,
+            ),
+            circuit_breaker_recovery_threshold: AtomicUsize::new(
+                DEFAULT_PG_TIMESTAMP_ORACLE_CIRCUIT_BREAKER_RECOVERY_THRESHOLD,
+            ),
         }
     }
 }
@@ -241,6 +276,23 @@ impl DynamicConfig {
             .read()
             .expect("lock poisoned")
     }
+
+    pub(crate) fn circuit_breaker_failure_threshold(&self) -> usize {
+        self.circuit_breaker_failure_threshold
+            .load(Self::LOAD_ORDERING)
+    }
+
+    pub(crate) fn circuit_breaker_latency_threshold(&self) -> Duration {
+        *self
+            .circuit_breaker_latency_threshold
+            .read()
+            .expect("lock poisoned")
+    }
+
+    pub(crate) fn circuit_breaker_recovery_threshold(&self) -> usize {
+        self.circuit_breaker_recovery_threshold
+            .load(Self::LOAD_ORDERING)
+    }
 }
 
 impl PostgresClientKnobs for PostgresTimestampOracleConfig {
@@ -296,6 +348,12 @@ pub struct PostgresTimestampOracleParameters {
     pub pg_connection_pool_connect_timeout: Option<Duration>,
     /// Configures `DynamicConfig::pg_connection_pool_tcp_user_timeout`.
     pub pg_connection_pool_tcp_user_timeout: Option<Duration>,
+    /// Configures `DynamicConfig::circuit_breaker_failure_threshold`.
+    pub circuit_breaker_failure_threshold: Option<usize>,
+    /// Configures `DynamicConfig::circuit_breaker_latency_threshold`.
+    pub circuit_breaker_latency_threshold: Option<Duration>,
+    /// Configures `DynamicConfig::circuit_breaker_recovery_threshold`.
+    pub circuit_breaker_recovery_threshold: Option<usize>,
 }
 
 impl PostgresTimestampOracleParameters {
@@ -310,6 +368,9 @@ impl PostgresTimestampOracleParameters {
             pg_connection_pool_ttl_stagger: self_pg_connection_pool_ttl_stagger,
             pg_connection_pool_connect_timeout: self_pg_connection_pool_connect_timeout,
             pg_connection_pool_tcp_user_timeout: self_pg_connection_pool_tcp_user_timeout,
+            circuit_breaker_failure_threshold: self_circuit_breaker_failure_threshold,
+            circuit_breaker_latency_threshold: self_circuit_breaker_latency_threshold,
+            circuit_breaker_recovery_threshold: self_circuit_breaker_recovery_threshold,
         } = self;
         let Self {
             pg_connection_pool_max_size: other_pg_connection_pool_max_size,
@@ -318,6 +379,9 @@ impl PostgresTimestampOracleParameters {
             pg_connection_pool_ttl_stagger: other_pg_connection_pool_ttl_stagger,
             pg_connection_pool_connect_timeout: other_pg_connection_pool_connect_timeout,
             pg_connection_pool_tcp_user_timeout: other_pg_connection_pool_tcp_user_timeout,
+            circuit_breaker_failure_threshold: other_circuit_breaker_failure_threshold,
+            circuit_breaker_latency_threshold: other_circuit_breaker_latency_threshold,
+            circuit_breaker_recovery_threshold: other_circuit_breaker_recovery_threshold,
         } = other;
         if let Some(v) = other_pg_connection_pool_max_size {
             *self_pg_connection_pool_max_size = Some(v);
@@ -337,6 +401,15 @@ impl PostgresTimestampOracleParameters {
         if let Some(v) = other_pg_connection_pool_tcp_user_timeout {
             *self_pg_connection_pool_tcp_user_timeout = Some(v);
         }
+        if let Some(v) = other_circuit_breaker_failure_threshold {
+            *self_circuit_breaker_failure_threshold = Some(v);
+        }
+        if let Some(v) = other_circuit_breaker_latency_threshold {
+            *self_circuit_breaker_latency_threshold = Some(v);
+        }
+        if let Some(v) = other_circuit_breaker_recovery_threshold {
+            *self_circuit_breaker_recovery_threshold = Some(v);
+        }
     }
 
     /// Applies the parameter values to the given in-memory config object.
@@ -355,6 +428,9 @@ impl PostgresTimestampOracleParameters {
             pg_connection_pool_ttl_stagger,
             pg_connection_pool_connect_timeout,
             pg_connection_pool_tcp_user_timeout,
+            circuit_breaker_failure_threshold,
+            circuit_breaker_latency_threshold,
+            circuit_breaker_recovery_threshold,
         } = self;
         if let Some(pg_connection_pool_max_size) = pg_connection_pool_max_size {
             cfg.dynamic
@@ -401,6 +477,26 @@ impl PostgresTimestampOracleParameters {
                 .expect("lock poisoned");
             *timeout = *pg_connection_pool_tcp_user_timeout;
         }
+        if let Some(circuit_breaker_failure_threshold) = circuit_breaker_failure_threshold {
+            cfg.dynamic.circuit_breaker_failure_threshold.store(
+                *circuit_breaker_failure_threshold,
+                DynamicConfig::STORE_ORDERING,
+            );
+        }
+        if let Some(circuit_breaker_latency_threshold) = circuit_breaker_latency_threshold {
+            let mut threshold = cfg
+                .dynamic
+                .circuit_breaker_latency_threshold
+                .write()
+                .expect("lock poisoned");
+            *threshold = *circuit_breaker_latency_threshold;
+        }
+        if let Some(circuit_breaker_recovery_threshold) = circuit_breaker_recovery_threshold {
+            cfg.dynamic.circuit_breaker_recovery_threshold.store(
+                *circuit_breaker_recovery_threshold,
+                DynamicConfig::STORE_ORDERING,
+            );
+        }
     }
 }
 
@@ -447,6 +543,7 @@ where
                 next: next.clone(),
                 postgres_client: Arc::new(postgres_client),
                 metrics,
+                circuit_breaker: Arc::clone(&config.circuit_breaker),
             };
 
             // Create a row for our timeline, if it doesn't exist. The
@@ -680,10 +777,12 @@ where
         let metrics = &self.metrics.retries.write_ts;
 
         let res = retry_fallible(metrics, || {
-            self.metrics
-                .oracle
-                .write_ts
-                .run_op(|| self.fallible_write_ts())
+            self.circuit_breaker.observe(
+                self.metrics
+                    .oracle
+                    .write_ts
+                    .run_op(|| self.fallible_write_ts()),
+            )
         })
         .await;
 
@@ -694,10 +793,12 @@ where
         let metrics = &self.metrics.retries.peek_write_ts;
 
         let res = retry_fallible(metrics, || {
-            self.metrics
-                .oracle
-                .peek_write_ts
-                .run_op(|| self.fallible_peek_write_ts())
+            self.circuit_breaker.observe(
+                self.metrics
+                    .oracle
+                    .peek_write_ts
+                    .run_op(|| self.fallible_peek_write_ts()),
+            )
         })
         .await;
 
@@ -708,10 +809,12 @@ where
         let metrics = &self.metrics.retries.read_ts;
 
         let res = retry_fallible(metrics, || {
-            self.metrics
-                .oracle
-                .read_ts
-                .run_op(|| self.fallible_read_ts())
+            self.circuit_breaker.observe(
+                self.metrics
+                    .oracle
+                    .read_ts
+                    .run_op(|| self.fallible_read_ts()),
+            )
         })
         .await;
 
@@ -722,10 +825,12 @@ where
         let metrics = &self.metrics.retries.apply_write;
 
         let res = retry_fallible(metrics, || {
-            self.metrics
-                .oracle
-                .apply_write
-                .run_op(|| self.fallible_apply_write(write_ts.clone()))
+            self.circuit_breaker.observe(
+                self.metrics
+                    .oracle
+                    .apply_write
+                    .run_op(|| self.fallible_apply_write(write_ts.clone())),
+            )
         })
         .await;
 
@@ -824,6 +929,7 @@ where
             next: self.next.clone(),
             postgres_client: Arc::clone(&self.postgres_client),
             metrics: Arc::clone(&self.metrics),
+            circuit_breaker: Arc::clone(&self.circuit_breaker),
         };
 
         Some(Arc::new(shallow_clone))
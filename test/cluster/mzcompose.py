@@ -1638,6 +1638,103 @@ def workflow_test_replica_targeted_select_abort(c: Composition) -> None:
     killer.join()
 
 
+def workflow_test_introspection_data_not_reported(c: Composition) -> None:
+    """
+    Test that queries over the introspection sources of a replica that has
+    yet to report introspection data emit a notice, or wait for the data if
+    `introspection_wait_timeout` is set.
+    """
+
+    c.down(destroy_volumes=True)
+    c.up("materialized")
+
+    c.sql(
+        "ALTER SYSTEM SET enable_unmanaged_cluster_replicas = true;",
+        port=6877,
+        user="mz_system",
+    )
+
+    # The clusterds are only started later, to mock replicas that are slow to
+    # start.
+    c.sql(
+        """
+        DROP CLUSTER IF EXISTS cluster1 CASCADE;
+        CREATE CLUSTER cluster1 REPLICAS (
+            replica1 (
+                STORAGECTL ADDRESSES ['clusterd1:2100'],
+                STORAGE ADDRESSES ['clusterd1:2103'],
+                COMPUTECTL ADDRESSES ['clusterd1:2101'],
+                COMPUTE ADDRESSES ['clusterd1:2102'],
+                WORKERS 1
+            ),
+            replica2 (
+                STORAGECTL ADDRESSES ['clusterd2:2100'],
+                STORAGE ADDRESSES ['clusterd2:2103'],
+                COMPUTECTL ADDRESSES ['clusterd2:2101'],
+                COMPUTE ADDRESSES ['clusterd2:2102'],
+                WORKERS 1
+            )
+        );
+        CREATE TABLE t (a int);
+        CREATE INDEX t_idx IN CLUSTER cluster1 ON t (a);
+        """
+    )
+
+    query = "SELECT count(*) FROM mz_internal.mz_dataflow_operators_per_worker"
+
+    def start_clusterd_with_delay(name: str) -> None:
+        time.sleep(2)
+        c.up(name)
+
+    def not_reported_notices(cursor: Cursor) -> list[str]:
+        return [
+            notice["M"]
+            for notice in cursor.connection.notices
+            if "has not yet reported introspection data" in notice["M"]
+        ]
+
+    starter = Thread(target=start_clusterd_with_delay, args=("clusterd1",))
+    starter.start()
+
+    cursor = c.sql_cursor()
+    cursor.execute("SET cluster = cluster1")
+    cursor.execute("SET cluster_replica = replica1")
+    cursor.execute(query)
+    cursor.fetchall()
+    assert not_reported_notices(cursor) == [
+        "replica replica1 has not yet reported introspection data; it may still be starting"
+    ], cursor.connection.notices
+
+    starter.join()
+
+    # Once the replica has reported introspection data, it is returned without
+    # a notice.
+    cursor.connection.notices.clear()
+    for _ in range(60):
+        cursor.execute(query)
+        (count,) = cursor.fetchone()
+        if count > 0:
+            break
+        time.sleep(1)
+    else:
+        assert False, "replica1 didn't report introspection data"
+    assert not_reported_notices(cursor) == [], cursor.connection.notices
+
+    # With `introspection_wait_timeout`, queries wait for the replica to
+    # report introspection data instead.
+    starter = Thread(target=start_clusterd_with_delay, args=("clusterd2",))
+    starter.start()
+
+    cursor.connection.notices.clear()
+    cursor.execute("SET cluster_replica = replica2")
+    cursor.execute("SET introspection_wait_timeout = '60s'")
+    cursor.execute(query)
+    cursor.fetchall()
+    assert not_reported_notices(cursor) == [], cursor.connection.notices
+
+    starter.join()
+
+
 def workflow_pg_snapshot_partial_failure(c: Composition) -> None:
     """Test PostgreSQL snapshot partial failure"""
 
@@ -1671,6 +1768,58 @@ def workflow_pg_snapshot_partial_failure(c: Composition) -> None:
             c.run_testdrive_files("pg-snapshot-partial-failure/05-verify-data.td")
 
 
+def workflow_pg_snapshot_statistics_retry(c: Composition) -> None:
+    """Test that a transient failure of a PostgreSQL table statistics query is retried"""
+
+    c.down(destroy_volumes=True)
+
+    with c.override(
+        # Start postgres for the pg source
+        Postgres(),
+        Testdrive(no_reset=True),
+        # Fail the first statistics query, which is the estimate of the only table.
+        Clusterd(
+            name="storage",
+            environment_extra=["FAILPOINTS=pg_snapshot_statistic_failure=1*return"],
+        ),
+    ):
+        c.up("materialized", "postgres", "storage")
+
+        c.run_testdrive_files("pg-snapshot-statistics-retry/01-create-source.td")
+        c.run_testdrive_files("pg-snapshot-statistics-retry/02-verify-data.td")
+
+
+def workflow_pg_snapshot_backpressure(c: Composition) -> None:
+    """Test that a slow PostgreSQL snapshot decoder pauses the table readers"""
+
+    c.down(destroy_volumes=True)
+
+    with c.override(
+        # Start postgres for the pg source
+        Postgres(),
+        Testdrive(no_reset=True),
+        # Delay decoding each row by a millisecond.
+        Clusterd(
+            name="storage", environment_extra=["FAILPOINTS=pg_snapshot_decode=sleep(1)"]
+        ),
+    ):
+        c.up("materialized", "postgres", "storage")
+
+        c.run_testdrive_files("pg-snapshot-backpressure/01-create-source.td")
+        c.run_testdrive_files("pg-snapshot-backpressure/02-verify-data.td")
+
+        # The reader must have waited for the decoders to catch up.
+        metrics = c.exec(
+            "storage", "curl", "localhost:6878/metrics", capture=True
+        ).stdout
+        backpressure = sum(
+            float(metric.split()[1])
+            for metric in metrics.splitlines()
+            if metric.startswith("mz_postgres_snapshot_backpressure_seconds")
+        )
+        assert backpressure > 0, "snapshot reader was never backpressured"
+
+
 def workflow_test_compute_reconciliation_reuse(c: Composition) -> None:
     """
     Test that compute reconciliation reuses existing dataflows.
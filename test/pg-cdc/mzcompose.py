@@ -7,7 +7,11 @@
 # the Business Source License, use of this software will be governed
 # by the Apache License, Version 2.0.
 
-from materialize.mzcompose.composition import Composition, WorkflowArgumentParser
+from materialize.mzcompose.composition import (
+    Composition,
+    Service,
+    WorkflowArgumentParser,
+)
 from materialize.mzcompose.services.materialized import Materialized
 from materialize.mzcompose.services.postgres import Postgres
 from materialize.mzcompose.services.test_certs import TestCerts
@@ -19,6 +23,29 @@ SERVICES = [
     TestCerts(),
     # Set the max slot WAL keep size to 10MB
     Postgres(extra_command=["-c", "max_slot_wal_keep_size=10"]),
+    # A hot standby of `postgres`, cloned from it on startup.
+    Service(
+        "postgres-standby",
+        {
+            "mzbuild": "postgres",
+            "entrypoint": [
+                "bash",
+                "-c",
+                "until gosu postgres pg_basebackup --host=postgres --username=postgres "
+                "--pgdata=$$PGDATA --write-recovery-conf --wal-method=stream; "
+                "do rm -rf $$PGDATA/*; sleep 1; done; "
+                # A standby must allow at least as many connections as its primary.
+                "exec docker-entrypoint.sh postgres -c max_connections=5000 "
+                "-c max_wal_senders=100 -c max_replication_slots=100",
+            ],
+            "environment": ["PGPASSWORD=postgres"],
+            "healthcheck": {
+                "test": ["CMD", "pg_isready"],
+                "interval": "1s",
+                "start_period": "30s",
+            },
+        },
+    ),
 ]
 
 
@@ -41,6 +68,23 @@ def workflow_replication_slots(c: Composition, parser: WorkflowArgumentParser) -
         c.run_testdrive_files("override/replication-slots.td")
 
 
+def workflow_preflight(c: Composition, parser: WorkflowArgumentParser) -> None:
+    with c.override(Postgres(extra_command=["-c", "max_replication_slots=2"])):
+        c.up("materialized", "postgres")
+        c.run_testdrive_files(
+            f"--var=default-storage-size={Materialized.Size.DEFAULT_SIZE}-1",
+            "override/preflight-replication-slots.td",
+        )
+
+
+def workflow_snapshot_read_committed(
+    c: Composition, parser: WorkflowArgumentParser
+) -> None:
+    with c.override(Postgres(extra_command=["-c", "max_replication_slots=1"])):
+        c.up("materialized", "postgres")
+        c.run_testdrive_files("override/snapshot-read-committed.td")
+
+
 def workflow_wal_level(c: Composition, parser: WorkflowArgumentParser) -> None:
     for wal_level in ["replica", "minimal"]:
         with c.override(
@@ -65,6 +109,14 @@ def workflow_replication_disabled(
         c.run_testdrive_files("override/replication-disabled.td")
 
 
+def workflow_snapshot_standby(c: Composition, parser: WorkflowArgumentParser) -> None:
+    c.up("materialized", "postgres")
+    c.up("postgres-standby")
+    c.run_testdrive_files("override/snapshot-standby.td")
+    c.kill("postgres-standby")
+    c.rm("postgres-standby")
+
+
 def workflow_cdc(c: Composition, parser: WorkflowArgumentParser) -> None:
     parser.add_argument(
         "filter",
@@ -163,7 +163,7 @@ def run_test_with_mv_on_table(c: Composition) -> None:
               LEFT OUTER JOIN retain_history_mv1 mv
               ON t.key = mv.key
               AS OF '{mz_time2}'::TIMESTAMP;
-            contains: is not valid for all inputs
+            contains: is before the earliest available timestamp
 
             > UPDATE retain_history_table SET key = 9 WHERE key = 1;
             """,
@@ -203,7 +203,7 @@ def run_test_with_mv_on_table(c: Composition) -> None:
             f"""
             # retain period exceeded
             ! SELECT * FROM retain_history_mv_on_mv1 AS OF '{mz_time1}'::TIMESTAMP;
-            contains: is not valid for all inputs
+            contains: is before the earliest available timestamp
 
             # retain period on wrapping mv still valid
             > SELECT * FROM retain_history_mv_on_mv_on_mv1 AS OF '{mz_time1}'::TIMESTAMP;
@@ -452,10 +452,17 @@ disruptions: list[Disruption] = [
         name="delete-sink-topic-recreate-topic-fix",
         breakage=lambda c, seed: delete_sink_topic(c, seed),
         expected_error="sink progress data exists, but sink data topic is missing",
-        # If we recreate the sink topic, the sink will work but will likely be inconsistent.
-        fixage=lambda c, seed: c.exec(
-            "redpanda", "rpk", "topic", "create", f"testdrive-sink-topic-{seed}"
-        ),
+        # If we recreate the sink topic, the sink notices that the topic lost the messages it
+        # committed, and only resumes once its progress is reset.
+        fixage=lambda c, seed: recreate_sink_topic(c, seed),
+    ),
+    KafkaSinkDisruption(
+        name="delete-sink-topic-reset-progress-fix",
+        breakage=lambda c, seed: delete_sink_topic(c, seed),
+        expected_error="sink progress data exists, but sink data topic is missing",
+        # If we reset the progress of the sink, it recreates the sink topic and emits a new
+        # snapshot.
+        fixage=lambda c, seed: reset_sink_progress(c),
     ),
     KafkaDisruption(
         name="delete-source-topic",
@@ -560,6 +567,21 @@ def delete_sink_topic(c: Composition, seed: int) -> None:
     )
 
 
+def recreate_sink_topic(c: Composition, seed: int) -> None:
+    c.exec("redpanda", "rpk", "topic", "create", f"testdrive-sink-topic-{seed}")
+    reset_sink_progress(c)
+
+
+def reset_sink_progress(c: Composition) -> None:
+    c.testdrive(
+        dedent(
+            """
+            > ALTER SINK sink1 RESET PROGRESS
+            """
+        )
+    )
+
+
 def alter_pg_table(c: Composition) -> None:
     c.testdrive(
         dedent(